@@ -1,20 +1,44 @@
 use scrypto::prelude::*;
 
+/// The most XRD the faucet gives away within an epoch.
+const MAX_PAYOUT_PER_EPOCH: u32 = 1_000_000;
+
 // Faucet - TestNet only
 blueprint! {
     struct SysFaucet {
         vault: Vault,
         transactions: KeyValueStore<Hash, u64>,
+        epoch_payouts: KeyValueStore<u64, Decimal>,
     }
 
     impl SysFaucet {
         /// Gives away XRD tokens.
         pub fn free_xrd(&mut self) -> Bucket {
+            self.free_xrd_amount(1000.into())
+        }
+
+        /// Gives away the given amount of XRD tokens, once per transaction and up to the
+        /// limit of the current epoch.
+        pub fn free_xrd_amount(&mut self, amount: Decimal) -> Bucket {
+            assert!(!amount.is_negative(), "Negative amount");
             let transaction_hash = Runtime::transaction_hash();
             let epoch = Runtime::current_epoch();
             assert!(self.transactions.get(&transaction_hash).is_none());
             self.transactions.insert(transaction_hash, epoch);
-            self.vault.take(1000)
+
+            let paid_out = self
+                .epoch_payouts
+                .get(&epoch)
+                .map(|paid_out| *paid_out)
+                .unwrap_or_default()
+                + amount;
+            assert!(
+                paid_out <= MAX_PAYOUT_PER_EPOCH.into(),
+                "The faucet gives away at most {} XRD per epoch",
+                MAX_PAYOUT_PER_EPOCH
+            );
+            self.epoch_payouts.insert(epoch, paid_out);
+            self.vault.take(amount)
         }
 
         /// Locks fees.
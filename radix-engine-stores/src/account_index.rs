@@ -0,0 +1,229 @@
+use std::collections::VecDeque;
+
+use radix_engine::model::ComponentState;
+use radix_engine::types::*;
+
+use crate::indexed_store::{CommittableSubstateStore, ReadableMetadataStore, StagedState};
+
+/// The prefix of the metadata keys of the vaults owned by each component, which are followed
+/// by the encoded `ComponentAddress`
+const COMPONENT_VAULTS_PREFIX: &[u8] = b"component_vaults/";
+/// The prefix of the metadata keys of the key value stores owned by each component
+const COMPONENT_KV_STORES_PREFIX: &[u8] = b"component_kv_stores/";
+/// The prefix of the metadata keys of the component owning each key value store, directly or
+/// through other key value stores, which are followed by the encoded `KeyValueStoreId`
+const KV_STORE_OWNER_PREFIX: &[u8] = b"kv_store_owner/";
+
+/// An index of the vaults owned by each component.
+///
+/// The components affected by a state diff are re-indexed as it's committed. Balances are then
+/// read from the indexed vaults, without traversing the component state.
+pub struct AccountIndex;
+
+impl AccountIndex {
+    /// Re-indexes the components whose state, or the key value stores they own, the staged
+    /// state diff writes.
+    pub fn update<S: CommittableSubstateStore>(&self, staged: &mut StagedState<S>) {
+        let mut components = BTreeSet::new();
+        for substate_id in staged.state_diff().up_substates.keys() {
+            match substate_id {
+                SubstateId::ComponentState(component_address) => {
+                    components.insert(*component_address);
+                }
+                // A new key value store is referenced from a changed component state or entry,
+                // so only the owners of the existing ones need to be looked up
+                SubstateId::KeyValueStoreEntry(kv_store_id, _) => {
+                    if let Some(owner) =
+                        staged.get_metadata(&metadata_key(KV_STORE_OWNER_PREFIX, kv_store_id))
+                    {
+                        components.insert(scrypto_decode(&owner).unwrap());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for component_address in components {
+            index_component(staged, component_address);
+        }
+    }
+
+    /// Returns the vaults owned by the given component.
+    pub fn get_component_vaults<S: ReadableMetadataStore>(
+        &self,
+        store: &S,
+        component_address: &ComponentAddress,
+    ) -> HashSet<VaultId> {
+        store
+            .get_metadata(&metadata_key(COMPONENT_VAULTS_PREFIX, component_address))
+            .map(|bytes| scrypto_decode::<Vec<VaultId>>(&bytes).unwrap())
+            .unwrap_or_default()
+            .into_iter()
+            .collect()
+    }
+}
+
+fn index_component<S: CommittableSubstateStore>(
+    staged: &mut StagedState<S>,
+    component_address: ComponentAddress,
+) {
+    let kv_stores_key = metadata_key(COMPONENT_KV_STORES_PREFIX, &component_address);
+    if let Some(bytes) = staged.get_metadata(&kv_stores_key) {
+        for kv_store_id in scrypto_decode::<Vec<KeyValueStoreId>>(&bytes).unwrap() {
+            staged.delete_metadata(metadata_key(KV_STORE_OWNER_PREFIX, &kv_store_id));
+        }
+    }
+
+    let state: ComponentState =
+        match staged.get_substate(&SubstateId::ComponentState(component_address)) {
+            Some(output_value) => output_value.substate.into(),
+            None => return,
+        };
+    let state = ScryptoValue::from_slice(state.state()).unwrap();
+
+    // Find all vaults owned by the component, assuming a tree structure.
+    let mut vaults: BTreeSet<VaultId> = state.vault_ids.into_iter().collect();
+    let mut kv_stores = Vec::new();
+    let mut queue: VecDeque<KeyValueStoreId> = state.kv_store_ids.into_iter().collect();
+    while let Some(kv_store_id) = queue.pop_front() {
+        kv_stores.push(kv_store_id);
+        for (_, substate) in staged.get_kv_store_entries(&kv_store_id) {
+            if let Some(value) = substate.kv_entry() {
+                queue.extend(value.kv_store_ids.iter().cloned());
+                vaults.extend(value.vault_ids.iter().cloned());
+            }
+        }
+    }
+
+    for kv_store_id in &kv_stores {
+        staged.put_metadata(
+            metadata_key(KV_STORE_OWNER_PREFIX, kv_store_id),
+            scrypto_encode(&component_address),
+        );
+    }
+    staged.put_metadata(kv_stores_key, scrypto_encode(&kv_stores));
+    staged.put_metadata(
+        metadata_key(COMPONENT_VAULTS_PREFIX, &component_address),
+        scrypto_encode(&vaults.into_iter().collect::<Vec<VaultId>>()),
+    );
+}
+
+fn metadata_key<T: Encode + ?Sized>(prefix: &[u8], id: &T) -> Vec<u8> {
+    let mut key = prefix.to_vec();
+    key.extend(scrypto_encode(id));
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexed_store::IndexedSubstateStore;
+    use crate::memory_db::SerializedInMemorySubstateStore;
+    use radix_engine::engine::Substate;
+    use radix_engine::ledger::{AccountQueryStore, OutputValue};
+    use radix_engine::model::{ResourceContainer, Vault};
+    use radix_engine::state_manager::StateDiff;
+    use scrypto::component::KeyValueStore;
+    use std::marker::PhantomData;
+
+    const COMPONENT: ComponentAddress = ComponentAddress::Normal([1u8; 26]);
+    const KV_STORE: KeyValueStoreId = (Hash([1u8; 32]), 0);
+
+    fn vault_id(n: u32) -> VaultId {
+        (Hash([2u8; 32]), n)
+    }
+
+    fn resource_address(n: u8) -> ResourceAddress {
+        ResourceAddress::Normal([n; 26])
+    }
+
+    fn vault(resource: u8, amount: u32) -> OutputValue {
+        OutputValue {
+            substate: Substate::Vault(Vault::new(ResourceContainer::new_fungible(
+                resource_address(resource),
+                18,
+                amount.into(),
+            ))),
+            version: 0,
+        }
+    }
+
+    /// Creates a state diff with a component holding vault 0 in its state, and vault 1 in a
+    /// key value store.
+    fn component_diff() -> StateDiff {
+        let kv_store = KeyValueStore::<u32, scrypto::resource::Vault> {
+            id: KV_STORE,
+            key: PhantomData,
+            value: PhantomData,
+        };
+        let state = scrypto_encode(&(scrypto::resource::Vault(vault_id(0)), kv_store));
+
+        let mut state_diff = StateDiff::new();
+        state_diff.up_substates.insert(
+            SubstateId::ComponentState(COMPONENT),
+            OutputValue {
+                substate: Substate::ComponentState(ComponentState::new(state)),
+                version: 0,
+            },
+        );
+        state_diff.up_substates.insert(
+            SubstateId::KeyValueStoreEntry(KV_STORE, scrypto_encode(&0u32)),
+            OutputValue {
+                substate: Substate::KeyValueStoreEntry(Some(ScryptoValue::from_typed(
+                    &scrypto::resource::Vault(vault_id(1)),
+                ))),
+                version: 0,
+            },
+        );
+        state_diff
+            .up_substates
+            .insert(SubstateId::Vault(vault_id(0)), vault(1, 5));
+        state_diff
+            .up_substates
+            .insert(SubstateId::Vault(vault_id(1)), vault(1, 7));
+        state_diff
+    }
+
+    #[test]
+    fn test_component_balances() {
+        let mut store =
+            IndexedSubstateStore::new(SerializedInMemorySubstateStore::new()).with_account_index();
+
+        store.commit_state_diff(&component_diff());
+
+        let mut expected = HashSet::new();
+        expected.insert(vault_id(0));
+        expected.insert(vault_id(1));
+        assert_eq!(expected, store.get_component_vaults(&COMPONENT));
+        let mut expected = HashMap::new();
+        expected.insert(resource_address(1), Decimal::from(12));
+        assert_eq!(expected, store.get_component_balances(&COMPONENT));
+    }
+
+    #[test]
+    fn test_kv_store_update_reindexes_owner() {
+        let mut store =
+            IndexedSubstateStore::new(SerializedInMemorySubstateStore::new()).with_account_index();
+        store.commit_state_diff(&component_diff());
+
+        let mut state_diff = StateDiff::new();
+        state_diff.up_substates.insert(
+            SubstateId::KeyValueStoreEntry(KV_STORE, scrypto_encode(&1u32)),
+            OutputValue {
+                substate: Substate::KeyValueStoreEntry(Some(ScryptoValue::from_typed(
+                    &scrypto::resource::Vault(vault_id(2)),
+                ))),
+                version: 0,
+            },
+        );
+        state_diff
+            .up_substates
+            .insert(SubstateId::Vault(vault_id(2)), vault(2, 3));
+        store.commit_state_diff(&state_diff);
+
+        let mut expected = HashMap::new();
+        expected.insert(resource_address(1), Decimal::from(12));
+        expected.insert(resource_address(2), Decimal::from(3));
+        assert_eq!(expected, store.get_component_balances(&COMPONENT));
+    }
+}
@@ -0,0 +1,204 @@
+//! Tests shared by all substate store implementations.
+
+use radix_engine::engine::Substate;
+use radix_engine::ledger::{
+    bootstrap, OutputValue, QueryableSubstateStore, ReadableSubstateStore, WriteableSubstateStore,
+};
+use radix_engine::model::{NonFungible, ResourceContainer, Vault};
+use radix_engine::state_manager::StateDiff;
+use radix_engine::types::*;
+
+use crate::indexed_store::{CommittableSubstateStore, MetadataWrites};
+
+/// Generates the conformance tests for a substate store, given an expression which creates an
+/// empty store.
+macro_rules! substate_store_conformance_tests {
+    ($new_store:expr) => {
+        #[test]
+        fn test_get_missing_substate() {
+            crate::conformance::test_get_missing_substate($new_store);
+        }
+
+        #[test]
+        fn test_put_and_get_substate() {
+            crate::conformance::test_put_and_get_substate($new_store);
+        }
+
+        #[test]
+        fn test_set_root() {
+            crate::conformance::test_set_root($new_store);
+        }
+
+        #[test]
+        fn test_get_kv_store_entries() {
+            crate::conformance::test_get_kv_store_entries($new_store);
+        }
+
+        #[test]
+        fn test_get_non_fungibles() {
+            crate::conformance::test_get_non_fungibles($new_store);
+        }
+
+        #[test]
+        fn test_get_vaults_holding() {
+            crate::conformance::test_get_vaults_holding($new_store);
+        }
+
+        #[test]
+        fn test_bootstrap() {
+            crate::conformance::test_bootstrap($new_store);
+        }
+
+        #[test]
+        fn test_commit_with_metadata() {
+            crate::conformance::test_commit_with_metadata($new_store);
+        }
+    };
+}
+
+fn kv_store_entry_id(kv_store: u32, key: u8) -> SubstateId {
+    SubstateId::KeyValueStoreEntry((Hash([0u8; 32]), kv_store), scrypto_encode(&key))
+}
+
+fn output_value(n: u8) -> OutputValue {
+    OutputValue {
+        substate: Substate::KeyValueStoreEntry(Some(ScryptoValue::from_typed(&n))),
+        version: n as u32,
+    }
+}
+
+pub fn test_get_missing_substate<S: ReadableSubstateStore>(store: S) {
+    assert_eq!(None, store.get_substate(&kv_store_entry_id(0, 0)));
+    assert!(!store.is_root(&kv_store_entry_id(0, 0)));
+}
+
+pub fn test_put_and_get_substate<S: ReadableSubstateStore + WriteableSubstateStore>(mut store: S) {
+    store.put_substate(kv_store_entry_id(0, 0), output_value(0));
+    store.put_substate(kv_store_entry_id(0, 1), output_value(1));
+    store.put_substate(kv_store_entry_id(0, 0), output_value(2));
+
+    assert_eq!(
+        Some(output_value(2)),
+        store.get_substate(&kv_store_entry_id(0, 0))
+    );
+    assert_eq!(
+        Some(output_value(1)),
+        store.get_substate(&kv_store_entry_id(0, 1))
+    );
+}
+
+pub fn test_set_root<S: ReadableSubstateStore + WriteableSubstateStore>(mut store: S) {
+    store.set_root(kv_store_entry_id(0, 0));
+
+    assert!(store.is_root(&kv_store_entry_id(0, 0)));
+    assert!(!store.is_root(&kv_store_entry_id(0, 1)));
+}
+
+pub fn test_get_kv_store_entries<S: WriteableSubstateStore + QueryableSubstateStore>(mut store: S) {
+    store.put_substate(kv_store_entry_id(0, 0), output_value(0));
+    store.put_substate(kv_store_entry_id(1, 0), output_value(1));
+    store.put_substate(kv_store_entry_id(1, 1), output_value(2));
+    store.put_substate(kv_store_entry_id(2, 0), output_value(3));
+
+    let entries = store.get_kv_store_entries(&(Hash([0u8; 32]), 1));
+
+    let mut expected = HashMap::new();
+    expected.insert(scrypto_encode(&0u8), output_value(1).substate);
+    expected.insert(scrypto_encode(&1u8), output_value(2).substate);
+    assert_eq!(expected, entries);
+}
+
+pub fn test_get_non_fungibles<S: WriteableSubstateStore + QueryableSubstateStore>(mut store: S) {
+    let non_fungible = |n: u8| OutputValue {
+        substate: Substate::NonFungible(Some(NonFungible::new(vec![n], vec![]))),
+        version: 0,
+    };
+    for (resource, id) in [(0u8, 0u32), (1, 0), (1, 1), (1, 256), (2, 0)] {
+        store.put_substate(
+            SubstateId::NonFungible(
+                ResourceAddress::Normal([resource; 26]),
+                NonFungibleId::from_u32(id),
+            ),
+            non_fungible(resource),
+        );
+    }
+    store.put_substate(kv_store_entry_id(0, 0), output_value(0));
+
+    let non_fungibles = store.get_non_fungibles(&ResourceAddress::Normal([1u8; 26]));
+
+    let mut expected = HashMap::new();
+    for id in [0u32, 1, 256] {
+        expected.insert(NonFungibleId::from_u32(id), non_fungible(1).substate);
+    }
+    assert_eq!(expected, non_fungibles);
+}
+
+pub fn test_get_vaults_holding<S: WriteableSubstateStore + QueryableSubstateStore>(mut store: S) {
+    let vault = |resource: u8, amount: u32| OutputValue {
+        substate: Substate::Vault(Vault::new(ResourceContainer::new_fungible(
+            ResourceAddress::Normal([resource; 26]),
+            18,
+            amount.into(),
+        ))),
+        version: 0,
+    };
+    store.put_substate(SubstateId::Vault((Hash([0u8; 32]), 0)), vault(0, 1));
+    store.put_substate(SubstateId::Vault((Hash([0u8; 32]), 1)), vault(1, 1));
+    store.put_substate(SubstateId::Vault((Hash([1u8; 32]), 0)), vault(1, 2));
+    store.put_substate(SubstateId::Vault((Hash([1u8; 32]), 0)), vault(1, 3));
+
+    let vaults = store.get_vaults_holding(&ResourceAddress::Normal([1u8; 26]));
+
+    let mut expected = HashMap::new();
+    expected.insert((Hash([0u8; 32]), 1), vault(1, 1).substate);
+    expected.insert((Hash([1u8; 32]), 0), vault(1, 3).substate);
+    assert_eq!(expected, vaults);
+}
+
+pub fn test_bootstrap<S: ReadableSubstateStore + WriteableSubstateStore>(store: S) {
+    let store = bootstrap(store);
+
+    assert!(store
+        .get_substate(&SubstateId::Package(SYS_FAUCET_PACKAGE))
+        .is_some());
+    assert!(store
+        .get_substate(&SubstateId::ResourceManager(RADIX_TOKEN))
+        .is_some());
+}
+
+pub fn test_commit_with_metadata<S: CommittableSubstateStore>(mut store: S) {
+    let mut state_diff = StateDiff::new();
+    state_diff
+        .up_substates
+        .insert(kv_store_entry_id(0, 1), output_value(1));
+    let mut metadata = MetadataWrites::new();
+    for key in [b"b/2", b"a/1", b"b/1", b"c/1"] {
+        metadata.insert(key.to_vec(), Some(key.to_vec()));
+    }
+    store.commit_with_metadata(&state_diff, &metadata);
+
+    let mut metadata = MetadataWrites::new();
+    metadata.insert(b"b/2".to_vec(), None);
+    metadata.insert(b"b/3".to_vec(), Some(b"3".to_vec()));
+    store.commit_with_metadata(&StateDiff::new(), &metadata);
+
+    assert_eq!(
+        Some(output_value(1)),
+        store.get_substate(&kv_store_entry_id(0, 1))
+    );
+    assert_eq!(Some(b"a/1".to_vec()), store.get_metadata(b"a/1"));
+    assert_eq!(None, store.get_metadata(b"b/2"));
+    let mut scanned = Vec::new();
+    store.scan_metadata(b"b/", &mut |key, value| {
+        scanned.push((key.to_vec(), value.to_vec()));
+        key.starts_with(b"b/")
+    });
+    assert_eq!(
+        vec![
+            (b"b/1".to_vec(), b"b/1".to_vec()),
+            (b"b/3".to_vec(), b"3".to_vec()),
+            (b"c/1".to_vec(), b"c/1".to_vec()),
+        ],
+        scanned
+    );
+}
@@ -0,0 +1,402 @@
+use radix_engine::engine::{Substate, TrackReceipt};
+use radix_engine::ledger::{
+    genesis_state_diff, AccountQueryStore, OutputValue, QueryableSubstateStore,
+    ReadableSubstateStore, WriteableSubstateStore,
+};
+use radix_engine::state_manager::{CommitReceipt, StateDiff, StateProof};
+use radix_engine::transaction::TransactionResult;
+use radix_engine::types::*;
+
+use crate::account_index::AccountIndex;
+use crate::state_tree::StateTree;
+use crate::substate_history::{RetentionPolicy, SubstateHistory};
+
+/// The key of the number of state versions committed through an `IndexedSubstateStore`
+const STATE_VERSION_KEY: &[u8] = b"state_version";
+
+/// The metadata writes of a commit, by key, where `None` deletes the key.
+pub type MetadataWrites = BTreeMap<Vec<u8>, Option<Vec<u8>>>;
+
+/// A store which can keep arbitrary metadata, such as indexes, alongside the substates.
+pub trait ReadableMetadataStore {
+    fn get_metadata(&self, key: &[u8]) -> Option<Vec<u8>>;
+
+    /// Calls `f` with each metadata key and value, in order of key, starting from the given key,
+    /// until it returns `false` or there is no more metadata.
+    fn scan_metadata(&self, from: &[u8], f: &mut dyn FnMut(&[u8], &[u8]) -> bool);
+}
+
+/// A store which can commit state changes along with metadata derived from them.
+pub trait CommittableSubstateStore:
+    ReadableSubstateStore + QueryableSubstateStore + ReadableMetadataStore
+{
+    /// Writes the state changes and the metadata, such that either all or none of them are
+    /// persisted should the process crash mid-commit.
+    fn commit_with_metadata(
+        &mut self,
+        state_diff: &StateDiff,
+        metadata: &MetadataWrites,
+    ) -> CommitReceipt;
+}
+
+/// The state of a store with a state diff applied but not yet committed, which the indexes
+/// read while deciding their metadata writes.
+pub struct StagedState<'a, S> {
+    store: &'a S,
+    state_diff: &'a StateDiff,
+    metadata: MetadataWrites,
+}
+
+impl<'a, S: CommittableSubstateStore> StagedState<'a, S> {
+    fn new(store: &'a S, state_diff: &'a StateDiff) -> Self {
+        Self {
+            store,
+            state_diff,
+            metadata: MetadataWrites::new(),
+        }
+    }
+
+    pub fn state_diff(&self) -> &'a StateDiff {
+        self.state_diff
+    }
+
+    /// Returns the store the state diff is applied to.
+    pub fn committed(&self) -> &'a S {
+        self.store
+    }
+
+    pub fn get_substate(&self, substate_id: &SubstateId) -> Option<OutputValue> {
+        match self.state_diff.up_substates.get(substate_id) {
+            Some(output_value) => Some(output_value.clone()),
+            None => self.store.get_substate(substate_id),
+        }
+    }
+
+    pub fn get_kv_store_entries(
+        &self,
+        kv_store_id: &KeyValueStoreId,
+    ) -> HashMap<Vec<u8>, Substate> {
+        let mut entries = self.store.get_kv_store_entries(kv_store_id);
+        for (substate_id, output_value) in &self.state_diff.up_substates {
+            if let SubstateId::KeyValueStoreEntry(id, key) = substate_id {
+                if id == kv_store_id {
+                    entries.insert(key.clone(), output_value.substate.clone());
+                }
+            }
+        }
+        entries
+    }
+
+    pub fn get_metadata(&self, key: &[u8]) -> Option<Vec<u8>> {
+        match self.metadata.get(key) {
+            Some(value) => value.clone(),
+            None => self.store.get_metadata(key),
+        }
+    }
+
+    pub fn put_metadata(&mut self, key: Vec<u8>, value: Vec<u8>) {
+        self.metadata.insert(key, Some(value));
+    }
+
+    pub fn delete_metadata(&mut self, key: Vec<u8>) {
+        self.metadata.insert(key, None);
+    }
+}
+
+/// A substate store which maintains indexes over the substates committed to it.
+///
+/// The indexes are kept as metadata of the underlying store, and written in the same commit as
+/// the substates they derive from. Only the state changes committed through this store are
+/// indexed, so it should wrap the underlying store from genesis, e.g. with `bootstrap`.
+///
+/// Each commit closes a state version, starting from 0.
+pub struct IndexedSubstateStore<S> {
+    store: S,
+    history: Option<SubstateHistory>,
+    account_index: Option<AccountIndex>,
+    state_tree: Option<StateTree>,
+}
+
+impl<S> IndexedSubstateStore<S> {
+    pub fn new(store: S) -> Self {
+        Self {
+            store,
+            history: None,
+            account_index: None,
+            state_tree: None,
+        }
+    }
+
+    /// Retains the prior values of the substates, as given by the retention policy.
+    pub fn with_history(mut self, retention_policy: RetentionPolicy) -> Self {
+        self.history = Some(SubstateHistory::new(retention_policy));
+        self
+    }
+
+    /// Indexes the vaults owned by each component.
+    pub fn with_account_index(mut self) -> Self {
+        self.account_index = Some(AccountIndex);
+        self
+    }
+
+    /// Maintains a sparse Merkle tree over the substates.
+    pub fn with_state_tree(mut self) -> Self {
+        self.state_tree = Some(StateTree);
+        self
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.store
+    }
+
+    pub fn into_inner(self) -> S {
+        self.store
+    }
+}
+
+impl<S: CommittableSubstateStore> IndexedSubstateStore<S> {
+    /// Commits the genesis state, unless the store has already been bootstrapped.
+    pub fn bootstrap(mut self) -> Self {
+        if let Some(state_diff) = genesis_state_diff(&self.store) {
+            self.commit_state_diff(&state_diff);
+        }
+        self
+    }
+
+    /// Returns the number of state versions committed, which is the version the next commit
+    /// will close.
+    pub fn state_version(&self) -> u64 {
+        self.store
+            .get_metadata(STATE_VERSION_KEY)
+            .map_or(0, |bytes| u64::from_be_bytes(bytes.try_into().unwrap()))
+    }
+
+    /// Commits the state changes of a transaction, if any.
+    pub fn commit(&mut self, receipt: &TrackReceipt) -> Option<CommitReceipt> {
+        match &receipt.result {
+            TransactionResult::Commit(commit) => {
+                Some(self.commit_state_diff(&commit.state_updates))
+            }
+            TransactionResult::Reject(..) => None,
+        }
+    }
+
+    /// Commits the state changes along with the index updates they cause, closing a state
+    /// version.
+    pub fn commit_state_diff(&mut self, state_diff: &StateDiff) -> CommitReceipt {
+        let state_version = self.state_version();
+        let mut staged = StagedState::new(&self.store, state_diff);
+        if let Some(history) = &self.history {
+            history.update(&mut staged, state_version);
+        }
+        if let Some(account_index) = &self.account_index {
+            account_index.update(&mut staged);
+        }
+        if let Some(state_tree) = &self.state_tree {
+            state_tree.update(&mut staged);
+        }
+        staged.put_metadata(
+            STATE_VERSION_KEY.to_vec(),
+            (state_version + 1).to_be_bytes().to_vec(),
+        );
+
+        let metadata = staged.metadata;
+        self.store.commit_with_metadata(state_diff, &metadata)
+    }
+
+    /// Returns the value of the given substate as of the given committed state version, if it
+    /// existed and the version is retained.
+    ///
+    /// # Panics
+    /// If the store keeps no history.
+    pub fn get_substate_at(
+        &self,
+        substate_id: &SubstateId,
+        state_version: u64,
+    ) -> Option<OutputValue> {
+        self.history
+            .as_ref()
+            .expect("The store keeps no history")
+            .get_substate_at(
+                &self.store,
+                substate_id,
+                state_version,
+                self.state_version(),
+            )
+    }
+
+    /// Returns a view of the substates as of the given committed state version.
+    ///
+    /// # Panics
+    /// If the store keeps no history.
+    pub fn at_version(&self, state_version: u64) -> SubstatesAtVersion<'_, S> {
+        assert!(self.history.is_some(), "The store keeps no history");
+        SubstatesAtVersion {
+            store: self,
+            state_version,
+        }
+    }
+
+    /// Returns the root hash of the state tree.
+    ///
+    /// # Panics
+    /// If the store keeps no state tree.
+    pub fn state_root(&self) -> Hash {
+        self.state_tree
+            .as_ref()
+            .expect("The store keeps no state tree")
+            .root(&self.store)
+    }
+
+    /// Returns a proof of the current value of the given substate, if it's part of the state tree.
+    ///
+    /// # Panics
+    /// If the store keeps no state tree.
+    pub fn inclusion_proof(&self, substate_id: &SubstateId) -> Option<StateProof> {
+        self.state_tree
+            .as_ref()
+            .expect("The store keeps no state tree")
+            .inclusion_proof(&self.store, substate_id)
+    }
+
+    /// Returns the given substate along with a proof of it against the current state root.
+    pub fn get_substate_with_proof(
+        &self,
+        substate_id: &SubstateId,
+    ) -> Option<(OutputValue, StateProof)> {
+        let output_value = self.store.get_substate(substate_id)?;
+        let proof = self.inclusion_proof(substate_id)?;
+        Some((output_value, proof))
+    }
+}
+
+impl<S: CommittableSubstateStore> AccountQueryStore for IndexedSubstateStore<S> {
+    fn get_component_vaults(&self, component_address: &ComponentAddress) -> HashSet<VaultId> {
+        self.account_index
+            .as_ref()
+            .expect("The store keeps no account index")
+            .get_component_vaults(&self.store, component_address)
+    }
+
+    fn get_component_balances(
+        &self,
+        component_address: &ComponentAddress,
+    ) -> HashMap<ResourceAddress, Decimal> {
+        let mut balances = HashMap::new();
+        for vault_id in self.get_component_vaults(component_address) {
+            if let Some(output_value) = self.store.get_substate(&SubstateId::Vault(vault_id)) {
+                let vault = output_value.substate.vault();
+                *balances
+                    .entry(vault.resource_address())
+                    .or_insert_with(Decimal::zero) += vault.total_amount();
+            }
+        }
+        balances
+    }
+}
+
+impl<S: ReadableSubstateStore> ReadableSubstateStore for IndexedSubstateStore<S> {
+    fn get_substate(&self, substate_id: &SubstateId) -> Option<OutputValue> {
+        self.store.get_substate(substate_id)
+    }
+
+    fn is_root(&self, substate_id: &SubstateId) -> bool {
+        self.store.is_root(substate_id)
+    }
+}
+
+/// Writes are committed, and indexed, one at a time, so `commit_state_diff` should be preferred.
+impl<S: CommittableSubstateStore> WriteableSubstateStore for IndexedSubstateStore<S> {
+    fn put_substate(&mut self, substate_id: SubstateId, substate: OutputValue) {
+        let mut state_diff = StateDiff::new();
+        state_diff.up_substates.insert(substate_id, substate);
+        self.commit_state_diff(&state_diff);
+    }
+
+    fn set_root(&mut self, substate_id: SubstateId) {
+        let mut state_diff = StateDiff::new();
+        state_diff.new_roots.push(substate_id);
+        self.commit_state_diff(&state_diff);
+    }
+}
+
+impl<S: QueryableSubstateStore> QueryableSubstateStore for IndexedSubstateStore<S> {
+    fn get_kv_store_entries(&self, kv_store_id: &KeyValueStoreId) -> HashMap<Vec<u8>, Substate> {
+        self.store.get_kv_store_entries(kv_store_id)
+    }
+
+    fn get_non_fungibles(
+        &self,
+        resource_address: &ResourceAddress,
+    ) -> HashMap<NonFungibleId, Substate> {
+        self.store.get_non_fungibles(resource_address)
+    }
+
+    fn get_vaults_holding(&self, resource_address: &ResourceAddress) -> HashMap<VaultId, Substate> {
+        self.store.get_vaults_holding(resource_address)
+    }
+}
+
+/// A read-only view of the substates of an `IndexedSubstateStore` as of a committed state
+/// version.
+pub struct SubstatesAtVersion<'a, S> {
+    store: &'a IndexedSubstateStore<S>,
+    state_version: u64,
+}
+
+impl<'a, S: CommittableSubstateStore> SubstatesAtVersion<'a, S> {
+    /// Replaces the current values of the given substates with those as of the state version,
+    /// dropping the substates which didn't exist then.
+    fn rewind<K>(
+        &self,
+        substates: HashMap<K, Substate>,
+        mut substate_id: impl FnMut(&K) -> SubstateId,
+    ) -> HashMap<K, Substate>
+    where
+        K: Eq + std::hash::Hash,
+    {
+        substates
+            .into_keys()
+            .filter_map(|key| {
+                self.get_substate(&substate_id(&key))
+                    .map(|output_value| (key, output_value.substate))
+            })
+            .collect()
+    }
+}
+
+impl<'a, S: CommittableSubstateStore> ReadableSubstateStore for SubstatesAtVersion<'a, S> {
+    fn get_substate(&self, substate_id: &SubstateId) -> Option<OutputValue> {
+        self.store.get_substate_at(substate_id, self.state_version)
+    }
+
+    fn is_root(&self, substate_id: &SubstateId) -> bool {
+        self.store.is_root(substate_id)
+    }
+}
+
+/// Substates are never deleted from an archival store, so the substates as of a state version
+/// are those of the current state which existed at that version.
+impl<'a, S: CommittableSubstateStore> QueryableSubstateStore for SubstatesAtVersion<'a, S> {
+    fn get_kv_store_entries(&self, kv_store_id: &KeyValueStoreId) -> HashMap<Vec<u8>, Substate> {
+        self.rewind(self.store.get_kv_store_entries(kv_store_id), |key| {
+            SubstateId::KeyValueStoreEntry(*kv_store_id, key.clone())
+        })
+    }
+
+    fn get_non_fungibles(
+        &self,
+        resource_address: &ResourceAddress,
+    ) -> HashMap<NonFungibleId, Substate> {
+        self.rewind(self.store.get_non_fungibles(resource_address), |id| {
+            SubstateId::NonFungible(*resource_address, id.clone())
+        })
+    }
+
+    fn get_vaults_holding(&self, resource_address: &ResourceAddress) -> HashMap<VaultId, Substate> {
+        self.rewind(
+            self.store.get_vaults_holding(resource_address),
+            |vault_id| SubstateId::Vault(*vault_id),
+        )
+    }
+}
@@ -1,2 +1,15 @@
+#[cfg(test)]
+#[macro_use]
+mod conformance;
+
+pub mod account_index;
+pub mod indexed_store;
 pub mod memory_db;
+pub mod ordered_store;
 pub mod rocks_db;
+#[cfg(feature = "sled")]
+pub mod sled_db;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_db;
+pub mod state_tree;
+pub mod substate_history;
@@ -2,13 +2,17 @@ use radix_engine::engine::Substate;
 use radix_engine::ledger::{
     bootstrap, OutputValue, QueryableSubstateStore, ReadableSubstateStore, WriteableSubstateStore,
 };
+use radix_engine::state_manager::{CommitReceipt, StateDiff};
 use radix_engine::types::*;
 
+use crate::indexed_store::{CommittableSubstateStore, MetadataWrites, ReadableMetadataStore};
+
 /// A substate store that stores all typed substates in host memory.
 #[derive(Debug, PartialEq, Eq)]
 pub struct SerializedInMemorySubstateStore {
     substates: HashMap<Vec<u8>, Vec<u8>>,
     roots: HashSet<Vec<u8>>,
+    metadata: BTreeMap<Vec<u8>, Vec<u8>>,
 }
 
 impl SerializedInMemorySubstateStore {
@@ -16,6 +20,7 @@ impl SerializedInMemorySubstateStore {
         Self {
             substates: HashMap::new(),
             roots: HashSet::new(),
+            metadata: BTreeMap::new(),
         }
     }
 
@@ -73,4 +78,82 @@ impl QueryableSubstateStore for SerializedInMemorySubstateStore {
             })
             .collect()
     }
+
+    fn get_non_fungibles(
+        &self,
+        resource_address: &ResourceAddress,
+    ) -> HashMap<NonFungibleId, Substate> {
+        self.substates
+            .iter()
+            .filter_map(|(key, value)| {
+                let substate_id: SubstateId = scrypto_decode(key).unwrap();
+                if let SubstateId::NonFungible(address, id) = substate_id {
+                    if address == *resource_address {
+                        let output_value: OutputValue = scrypto_decode(value).unwrap();
+                        Some((id, output_value.substate))
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn get_vaults_holding(&self, resource_address: &ResourceAddress) -> HashMap<VaultId, Substate> {
+        self.substates
+            .iter()
+            .filter_map(|(key, value)| {
+                let substate_id: SubstateId = scrypto_decode(key).unwrap();
+                if let SubstateId::Vault(id) = substate_id {
+                    let output_value: OutputValue = scrypto_decode(value).unwrap();
+                    if output_value.substate.vault().resource_address() == *resource_address {
+                        Some((id, output_value.substate))
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+impl ReadableMetadataStore for SerializedInMemorySubstateStore {
+    fn get_metadata(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.metadata.get(key).cloned()
+    }
+
+    fn scan_metadata(&self, from: &[u8], f: &mut dyn FnMut(&[u8], &[u8]) -> bool) {
+        for (key, value) in self.metadata.range(from.to_vec()..) {
+            if !f(key, value) {
+                break;
+            }
+        }
+    }
+}
+
+impl CommittableSubstateStore for SerializedInMemorySubstateStore {
+    fn commit_with_metadata(
+        &mut self,
+        state_diff: &StateDiff,
+        metadata: &MetadataWrites,
+    ) -> CommitReceipt {
+        for (key, value) in metadata {
+            match value {
+                Some(value) => self.metadata.insert(key.clone(), value.clone()),
+                None => self.metadata.remove(key),
+            };
+        }
+        state_diff.commit(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    substate_store_conformance_tests!(SerializedInMemorySubstateStore::new());
 }
@@ -0,0 +1,54 @@
+use radix_engine::engine::Substate;
+use radix_engine::ledger::OutputValue;
+use radix_engine::types::*;
+
+/// A store which keeps its substates ordered by encoded `SubstateId`, so that the substates of a
+/// key value store or resource can be found by scanning a contiguous range rather than all of them.
+pub trait OrderedSubstateStore {
+    /// Calls `f` with the encoded id and value of each substate, in order, starting from the
+    /// given id, until it returns `false` or there are no more substates.
+    fn scan_substates(&self, from: &SubstateId, f: &mut dyn FnMut(&[u8], &[u8]) -> bool);
+
+    fn get_kv_store_entries(&self, kv_store_id: &KeyValueStoreId) -> HashMap<Vec<u8>, Substate> {
+        // Entries are keyed by key value store first, so those of a store are contiguous and an
+        // empty key sorts before all the others
+        let from = SubstateId::KeyValueStoreEntry(*kv_store_id, Vec::new());
+
+        let mut items = HashMap::new();
+        self.scan_substates(
+            &from,
+            &mut |key, value| match scrypto_decode(key).unwrap() {
+                SubstateId::KeyValueStoreEntry(id, key) if id == *kv_store_id => {
+                    let output_value: OutputValue = scrypto_decode(value).unwrap();
+                    items.insert(key, output_value.substate);
+                    true
+                }
+                _ => false,
+            },
+        );
+        items
+    }
+
+    fn get_non_fungibles(
+        &self,
+        resource_address: &ResourceAddress,
+    ) -> HashMap<NonFungibleId, Substate> {
+        // Non-fungibles are keyed by resource first, so those of a resource are contiguous and
+        // an empty id sorts before all the others
+        let from = SubstateId::NonFungible(*resource_address, NonFungibleId(Vec::new()));
+
+        let mut items = HashMap::new();
+        self.scan_substates(
+            &from,
+            &mut |key, value| match scrypto_decode(key).unwrap() {
+                SubstateId::NonFungible(address, id) if address == *resource_address => {
+                    let output_value: OutputValue = scrypto_decode(value).unwrap();
+                    items.insert(id, output_value.substate);
+                    true
+                }
+                _ => false,
+            },
+        );
+        items
+    }
+}
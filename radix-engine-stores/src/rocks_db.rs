@@ -1,19 +1,168 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::ffi::CStr;
+use std::path::{Path, PathBuf};
 
-use radix_engine::engine::Substate;
+use radix_engine::engine::{Substate, TrackReceipt};
 use radix_engine::ledger::*;
+use radix_engine::state_manager::{CommitReceipt, StateDiff};
+use radix_engine::transaction::TransactionResult;
 use radix_engine::types::*;
-use rocksdb::{DBWithThreadMode, Direction, IteratorMode, SingleThreaded, DB};
+use rocksdb::properties::{ESTIMATE_NUM_KEYS, TOTAL_SST_FILES_SIZE};
+use rocksdb::{
+    ColumnFamily, ColumnFamilyDescriptor, DBCompactionStyle, DBWithThreadMode, Direction,
+    IteratorMode, Options, SingleThreaded, WriteBatch, DB, DEFAULT_COLUMN_FAMILY_NAME,
+};
+
+use crate::indexed_store::{CommittableSubstateStore, MetadataWrites, ReadableMetadataStore};
+use crate::ordered_store::OrderedSubstateStore;
+use crate::substate_history::RetentionPolicy;
+
+/// The version of the layout of the store, recorded under `FORMAT_VERSION_KEY` of the default
+/// column family when the store is created.
+///
+/// Stores of a different version, or created before the version was recorded, are refused.
+pub const FORMAT_VERSION: u32 = 2;
+const FORMAT_VERSION_KEY: &[u8] = b"format_version";
+/// The key of the `SUBSTATE_FORMAT_VERSION` the substates are encoded with, in the default
+/// column family
+const SUBSTATE_FORMAT_VERSION_KEY: &[u8] = b"substate_format_version";
+
+const PACKAGES_CF: &str = "packages";
+const COMPONENTS_CF: &str = "components";
+const ACCESS_CONTROLLERS_CF: &str = "access_controllers";
+const RESOURCE_MANAGERS_CF: &str = "resource_managers";
+const KEY_VALUE_STORE_ENTRIES_CF: &str = "key_value_store_entries";
+const NON_FUNGIBLES_CF: &str = "non_fungibles";
+const VAULTS_CF: &str = "vaults";
+const SYSTEM_CF: &str = "system";
+const ROOTS_CF: &str = "roots";
+/// An index of vaults by resource, keyed by encoded `ResourceAddress` followed by encoded `VaultId`
+const VAULTS_BY_RESOURCE_CF: &str = "vaults_by_resource";
+const COMMIT_LOG_CF: &str = "commit_log";
+/// The values superseded by each commit, keyed by a root flag byte, the big-endian commit
+/// sequence and the encoded `SubstateId`
+const HISTORY_CF: &str = "history";
+/// The key value store entries deleted by each commit, keyed by the big-endian commit sequence
+/// followed by the encoded `SubstateId`
+const TOMBSTONES_CF: &str = "tombstones";
+/// The metadata written along with the substates, such as indexes, by key
+const METADATA_CF: &str = "metadata";
+
+/// The column families holding substates, keyed by encoded `SubstateId`
+const SUBSTATE_CFS: [&str; 8] = [
+    PACKAGES_CF,
+    COMPONENTS_CF,
+    ACCESS_CONTROLLERS_CF,
+    RESOURCE_MANAGERS_CF,
+    KEY_VALUE_STORE_ENTRIES_CF,
+    NON_FUNGIBLES_CF,
+    VAULTS_CF,
+    SYSTEM_CF,
+];
+
+/// The memory budget of the memtables of each substate column family: 64 MiB
+const MEMTABLE_MEMORY_BUDGET: usize = 64 * 1024 * 1024;
+
+/// Statistics of a `RadixEngineDB`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoreInfo {
+    /// The total size of the SST files of all column families, in bytes
+    pub size_on_disk: u64,
+    /// The estimated number of substates
+    pub substate_count: u64,
+}
 
 pub struct RadixEngineDB {
     db: DBWithThreadMode<SingleThreaded>,
+    retention_policy: RetentionPolicy,
+    /// Whether this is a read-only instance following a primary instance
+    secondary: bool,
 }
 
 impl RadixEngineDB {
+    /// Opens an archival store, which retains every superseded value and deleted entry.
     pub fn new(root: PathBuf) -> Self {
-        let db = DB::open_default(root.as_path()).unwrap();
-        Self { db }
+        Self::with_retention_policy(root, RetentionPolicy::KeepAll)
+    }
+
+    /// Opens a pruning store, which only retains the given number of most recent state versions.
+    pub fn pruning(root: PathBuf, keep_last: u64) -> Self {
+        Self::with_retention_policy(root, RetentionPolicy::KeepLast(keep_last))
+    }
+
+    /// Opens a store which retains the values superseded within the state versions given by
+    /// the retention policy, and drops the key value store entries deleted before them.
+    ///
+    /// The superseded values of root substates are always retained.
+    ///
+    /// # Panics
+    /// If the store at `root` has a different `FORMAT_VERSION` or `SUBSTATE_FORMAT_VERSION`.
+    pub fn with_retention_policy(root: PathBuf, retention_policy: RetentionPolicy) -> Self {
+        let created = !Self::exists(&root);
+        let mut db_options = Options::default();
+        db_options.create_if_missing(true);
+        db_options.create_missing_column_families(true);
+        db_options.increase_parallelism(
+            std::thread::available_parallelism().map_or(1, |n| n.get() as i32),
+        );
+
+        let db = DB::open_cf_descriptors(&db_options, root.as_path(), column_families()).unwrap();
+        if created {
+            db.put(FORMAT_VERSION_KEY, FORMAT_VERSION.to_be_bytes())
+                .unwrap();
+            db.put(
+                SUBSTATE_FORMAT_VERSION_KEY,
+                SUBSTATE_FORMAT_VERSION.to_be_bytes(),
+            )
+            .unwrap();
+        }
+        check_format_version(&db, &root);
+        Self {
+            db,
+            retention_policy,
+            secondary: false,
+        }
+    }
+
+    /// Opens a secondary instance of the store at `root`, which can be read while another
+    /// process has the store open for writing.
+    ///
+    /// The secondary instance keeps its logs under `secondary_root`, and only sees the changes
+    /// committed by the primary instance up to the last `catch_up_with_primary`.
+    pub fn open_as_secondary(root: PathBuf, secondary_root: PathBuf) -> Self {
+        let mut db_options = Options::default();
+        // The files compacted away by the primary instance must stay readable
+        db_options.set_max_open_files(-1);
+
+        let db = DB::open_cf_descriptors_as_secondary(
+            &db_options,
+            root.as_path(),
+            secondary_root.as_path(),
+            column_families(),
+        )
+        .unwrap();
+        check_format_version(&db, &root);
+        Self {
+            db,
+            retention_policy: RetentionPolicy::KeepAll,
+            secondary: true,
+        }
+    }
+
+    /// Returns whether a store has been created at `root`.
+    pub fn exists(root: &Path) -> bool {
+        root.join("CURRENT").exists()
+    }
+
+    pub fn is_secondary(&self) -> bool {
+        self.secondary
+    }
+
+    /// Applies the changes written by the primary instance since this secondary instance was
+    /// opened or last caught up, including those only in its write-ahead log.
+    pub fn catch_up_with_primary(&self) {
+        assert!(self.secondary, "Not a secondary instance");
+        self.db.try_catch_up_with_primary().unwrap();
     }
 
     pub fn with_bootstrap(root: PathBuf) -> Self {
@@ -21,11 +170,199 @@ impl RadixEngineDB {
         bootstrap(substate_store)
     }
 
+    /// Commits the state changes of a transaction, if any, in a single atomic write.
+    pub fn commit(&mut self, receipt: &TrackReceipt) -> Option<CommitReceipt> {
+        match &receipt.result {
+            TransactionResult::Commit(commit) => {
+                Some(self.commit_state_diff(&commit.state_updates))
+            }
+            TransactionResult::Reject(..) => None,
+        }
+    }
+
+    /// Writes the state changes along with a record of the commit in a single batch, so that
+    /// either all or none of them are persisted should the process crash mid-commit.
+    pub fn commit_state_diff(&mut self, state_diff: &StateDiff) -> CommitReceipt {
+        self.write_commit(state_diff, &MetadataWrites::new())
+    }
+
+    fn write_commit(&mut self, state_diff: &StateDiff, metadata: &MetadataWrites) -> CommitReceipt {
+        let receipt = state_diff.to_commit_receipt();
+        let sequence = self.last_commit_sequence().map_or(0, |s| s + 1);
+
+        let mut batch = WriteBatch::default();
+        for (substate_id, output_value) in &state_diff.up_substates {
+            if self.retention_policy != RetentionPolicy::KeepLast(0) {
+                if let Some(superseded) = self.read(substate_id) {
+                    batch.put_cf(
+                        self.cf(HISTORY_CF),
+                        history_key(self.is_root(substate_id), sequence, substate_id),
+                        superseded,
+                    );
+                }
+            }
+            if let (RetentionPolicy::KeepLast(_), Substate::KeyValueStoreEntry(entry)) =
+                (self.retention_policy, &output_value.substate)
+            {
+                if entry.is_none() {
+                    batch.put_cf(
+                        self.cf(TOMBSTONES_CF),
+                        sequence_key(sequence, substate_id),
+                        vec![],
+                    );
+                }
+            }
+            self.put_substate_in_batch(&mut batch, substate_id, output_value);
+        }
+        for substate_id in &state_diff.new_roots {
+            batch.put_cf(self.cf(ROOTS_CF), scrypto_encode(substate_id), vec![]);
+        }
+        for (key, value) in metadata {
+            match value {
+                Some(value) => batch.put_cf(self.cf(METADATA_CF), key, value),
+                None => batch.delete_cf(self.cf(METADATA_CF), key),
+            }
+        }
+        batch.put_cf(
+            self.cf(COMMIT_LOG_CF),
+            sequence.to_be_bytes(),
+            scrypto_encode(&receipt),
+        );
+        self.write(batch);
+
+        // Pruning is idempotent, so it doesn't need to be part of the commit batch
+        self.prune(sequence);
+
+        receipt
+    }
+
+    /// Drops the data no longer retained once the commit with the given sequence is written.
+    fn prune(&self, sequence: u64) {
+        let cutoff = match self.retention_policy {
+            RetentionPolicy::KeepAll => return,
+            RetentionPolicy::KeepLast(n) => match (sequence + 1).checked_sub(n) {
+                Some(cutoff) => cutoff,
+                None => return,
+            },
+        };
+
+        let mut batch = WriteBatch::default();
+        batch.delete_range_cf(
+            self.cf(HISTORY_CF),
+            history_key_prefix(false, 0),
+            history_key_prefix(false, cutoff),
+        );
+        for kv in self
+            .db
+            .iterator_cf(self.cf(TOMBSTONES_CF), IteratorMode::Start)
+        {
+            let (key, _value) = kv.unwrap();
+            let (tombstone_sequence, substate_id) = key.split_at(8);
+            if u64::from_be_bytes(tombstone_sequence.try_into().unwrap()) >= cutoff {
+                break;
+            }
+            // The entry may have been set again since it was deleted
+            let substate_id: SubstateId = scrypto_decode(substate_id).unwrap();
+            if let Some(Substate::KeyValueStoreEntry(None)) = self
+                .get_substate(&substate_id)
+                .map(|output_value| output_value.substate)
+            {
+                batch.delete_cf(self.substate_cf(&substate_id), scrypto_encode(&substate_id));
+            }
+            batch.delete_cf(self.cf(TOMBSTONES_CF), key);
+        }
+        self.write(batch);
+    }
+
+    /// Returns the statistics of this store.
+    pub fn store_info(&self) -> StoreInfo {
+        let mut cfs: Vec<&str> = SUBSTATE_CFS.to_vec();
+        cfs.extend([
+            ROOTS_CF,
+            VAULTS_BY_RESOURCE_CF,
+            COMMIT_LOG_CF,
+            HISTORY_CF,
+            TOMBSTONES_CF,
+            METADATA_CF,
+        ]);
+        let size_on_disk = cfs
+            .iter()
+            .map(|cf| self.int_property(cf, TOTAL_SST_FILES_SIZE))
+            .sum();
+        let substate_count = SUBSTATE_CFS
+            .iter()
+            .map(|cf| self.int_property(cf, ESTIMATE_NUM_KEYS))
+            .sum();
+
+        StoreInfo {
+            size_on_disk,
+            substate_count,
+        }
+    }
+
+    fn int_property(&self, cf: &str, name: &CStr) -> u64 {
+        self.db
+            .property_int_value_cf(self.cf(cf), name)
+            .unwrap()
+            .unwrap_or(0)
+    }
+
+    /// Returns the sequence number of the last commit, if any.
+    pub fn last_commit_sequence(&self) -> Option<u64> {
+        // Sequence numbers are big-endian encoded, so the last key is the last commit
+        self.db
+            .iterator_cf(self.cf(COMMIT_LOG_CF), IteratorMode::End)
+            .next()
+            .map(|kv| {
+                let (key, _value) = kv.unwrap();
+                u64::from_be_bytes(key.as_ref().try_into().unwrap())
+            })
+    }
+
+    /// Returns the record of the commit with the given sequence number.
+    pub fn get_commit_record(&self, sequence: u64) -> Option<CommitReceipt> {
+        self.db
+            .get_cf(self.cf(COMMIT_LOG_CF), sequence.to_be_bytes())
+            .unwrap()
+            .map(|b| scrypto_decode(&b).unwrap())
+    }
+
+    /// Writes all substates and roots, along with the given nonce, to the given file.
+    pub fn export_state(&self, path: &Path, nonce: u64) -> Result<(), SnapshotError> {
+        let substates = SUBSTATE_CFS.iter().flat_map(|cf| {
+            self.db
+                .iterator_cf(self.cf(cf), IteratorMode::Start)
+                .map(|kv| {
+                    let (key, value) = kv.unwrap();
+                    (
+                        scrypto_decode(key.as_ref()).unwrap(),
+                        scrypto_decode(value.as_ref()).unwrap(),
+                    )
+                })
+                .collect::<Vec<(SubstateId, OutputValue)>>()
+        });
+        StateSnapshot::new(substates, self.list_substate_ids(ROOTS_CF), nonce).write_to(path)
+    }
+
+    /// Loads the substates and roots of the given file, on top of the existing ones, in a
+    /// single batch, and returns the nonce.
+    pub fn import_state(&mut self, path: &Path) -> Result<u64, SnapshotError> {
+        let snapshot = StateSnapshot::read_from(path)?;
+
+        let mut batch = WriteBatch::default();
+        for (substate_id, output_value) in &snapshot.substates {
+            self.put_substate_in_batch(&mut batch, substate_id, output_value);
+        }
+        for substate_id in &snapshot.roots {
+            batch.put_cf(self.cf(ROOTS_CF), scrypto_encode(substate_id), vec![]);
+        }
+        self.write(batch);
+
+        Ok(snapshot.nonce)
+    }
+
     pub fn list_packages(&self) -> Vec<PackageAddress> {
-        let start = &scrypto_encode(&SubstateId::Package(PackageAddress::Normal([0; 26])));
-        let end = &scrypto_encode(&SubstateId::Package(PackageAddress::Normal([255; 26])));
-        let substate_ids: Vec<SubstateId> = self.list_items(start, end);
-        substate_ids
+        self.list_substate_ids(PACKAGES_CF)
             .into_iter()
             .map(|id| {
                 if let SubstateId::Package(package_address) = id {
@@ -37,52 +374,21 @@ impl RadixEngineDB {
             .collect()
     }
 
-    fn list_components_helper(
-        &self,
-        start: ComponentAddress,
-        end: ComponentAddress,
-    ) -> Vec<ComponentAddress> {
-        let start = &scrypto_encode(&SubstateId::ComponentState(start));
-        let end = &scrypto_encode(&SubstateId::ComponentState(end));
-        let substate_ids: Vec<SubstateId> = self.list_items(start, end);
-        substate_ids
+    pub fn list_components(&self) -> Vec<ComponentAddress> {
+        self.list_substate_ids(COMPONENTS_CF)
             .into_iter()
-            .map(|id| {
+            .filter_map(|id| {
                 if let SubstateId::ComponentState(component_address) = id {
-                    component_address
+                    Some(component_address)
                 } else {
-                    panic!("Expected a component substate id.")
+                    None
                 }
             })
             .collect()
     }
 
-    pub fn list_components(&self) -> Vec<ComponentAddress> {
-        let mut addresses = Vec::new();
-        addresses.extend(self.list_components_helper(
-            ComponentAddress::System([0u8; 26]),
-            ComponentAddress::System([255u8; 26]),
-        ));
-        addresses.extend(self.list_components_helper(
-            ComponentAddress::Account([0u8; 26]),
-            ComponentAddress::Account([255u8; 26]),
-        ));
-        addresses.extend(self.list_components_helper(
-            ComponentAddress::Normal([0u8; 26]),
-            ComponentAddress::Normal([255u8; 26]),
-        ));
-        addresses
-    }
-
     pub fn list_resource_managers(&self) -> Vec<ResourceAddress> {
-        let start = &scrypto_encode(&SubstateId::ResourceManager(ResourceAddress::Normal(
-            [0; 26],
-        )));
-        let end = &scrypto_encode(&SubstateId::ResourceManager(ResourceAddress::Normal(
-            [255; 26],
-        )));
-        let substate_ids: Vec<SubstateId> = self.list_items(start, end);
-        substate_ids
+        self.list_substate_ids(RESOURCE_MANAGERS_CF)
             .into_iter()
             .map(|id| {
                 if let SubstateId::ResourceManager(resource_address) = id {
@@ -94,70 +400,130 @@ impl RadixEngineDB {
             .collect()
     }
 
-    fn list_items<T: Decode>(&self, start: &[u8], inclusive_end: &[u8]) -> Vec<T> {
-        let mut iter = self
-            .db
-            .iterator(IteratorMode::From(start, Direction::Forward));
-        let mut items = Vec::new();
-        while let Some(kv) = iter.next() {
-            let (key, _value) = kv.unwrap();
-            if key.as_ref() > inclusive_end {
-                break;
-            }
-            if key.len() == start.len() {
-                items.push(scrypto_decode(key.as_ref()).unwrap());
-            }
-        }
-        items
+    fn list_substate_ids(&self, cf: &str) -> Vec<SubstateId> {
+        self.db
+            .iterator_cf(self.cf(cf), IteratorMode::Start)
+            .map(|kv| {
+                let (key, _value) = kv.unwrap();
+                scrypto_decode(key.as_ref()).unwrap()
+            })
+            .collect()
+    }
+
+    fn write(&self, batch: WriteBatch) {
+        assert!(!self.secondary, "A secondary instance is read-only");
+        self.db.write(batch).unwrap();
+    }
+
+    fn cf(&self, name: &str) -> &ColumnFamily {
+        self.db
+            .cf_handle(name)
+            .expect("Column family should have been created")
+    }
+
+    fn substate_cf(&self, substate_id: &SubstateId) -> &ColumnFamily {
+        self.cf(match substate_id {
+            SubstateId::Package(..) => PACKAGES_CF,
+            SubstateId::ComponentInfo(..)
+            | SubstateId::ComponentState(..)
+            | SubstateId::ComponentAccessRules(..) => COMPONENTS_CF,
+            SubstateId::AccessController(..) => ACCESS_CONTROLLERS_CF,
+            SubstateId::ResourceManager(..) => RESOURCE_MANAGERS_CF,
+            SubstateId::KeyValueStoreEntry(..) => KEY_VALUE_STORE_ENTRIES_CF,
+            SubstateId::NonFungible(..) => NON_FUNGIBLES_CF,
+            SubstateId::Vault(..) => VAULTS_CF,
+            SubstateId::System => SYSTEM_CF,
+            // Spaces only exist through their entries, and the rest only live within a transaction
+            SubstateId::NonFungibleSpace(..)
+            | SubstateId::KeyValueStoreSpace(..)
+            | SubstateId::Bucket(..)
+            | SubstateId::Proof(..)
+            | SubstateId::Worktop => panic!("Substate {:?} is never persisted", substate_id),
+        })
     }
 
     fn read(&self, substate_id: &SubstateId) -> Option<Vec<u8>> {
         // TODO: Use get_pinned
-        self.db.get(scrypto_encode(substate_id)).unwrap()
+        self.db
+            .get_cf(self.substate_cf(substate_id), scrypto_encode(substate_id))
+            .unwrap()
+    }
+
+    /// Adds the write of a substate to the given batch, along with its index entries.
+    fn put_substate_in_batch(
+        &self,
+        batch: &mut WriteBatch,
+        substate_id: &SubstateId,
+        output_value: &OutputValue,
+    ) {
+        batch.put_cf(
+            self.substate_cf(substate_id),
+            scrypto_encode(substate_id),
+            scrypto_encode(output_value),
+        );
+        // The resource of a vault never changes, so its index entry is never stale
+        if let (SubstateId::Vault(vault_id), Substate::Vault(vault)) =
+            (substate_id, &output_value.substate)
+        {
+            batch.put_cf(
+                self.cf(VAULTS_BY_RESOURCE_CF),
+                vault_index_key(&vault.resource_address(), vault_id),
+                vec![],
+            );
+        }
     }
+}
 
-    fn write(&self, substate_id: SubstateId, value: Vec<u8>) {
-        self.db.put(scrypto_encode(&substate_id), value).unwrap();
+impl OrderedSubstateStore for RadixEngineDB {
+    fn scan_substates(&self, from: &SubstateId, f: &mut dyn FnMut(&[u8], &[u8]) -> bool) {
+        // Substates of a kind share a column family, so the scan ends with them
+        let cf = self.substate_cf(from);
+        let from = scrypto_encode(from);
+        for kv in self
+            .db
+            .iterator_cf(cf, IteratorMode::From(&from, Direction::Forward))
+        {
+            let (key, value) = kv.unwrap();
+            if !f(&key, &value) {
+                break;
+            }
+        }
     }
 }
 
 impl QueryableSubstateStore for RadixEngineDB {
     fn get_kv_store_entries(&self, kv_store_id: &KeyValueStoreId) -> HashMap<Vec<u8>, Substate> {
-        let unit = scrypto_encode(&());
-        let id = scrypto_encode(&SubstateId::KeyValueStoreEntry(
-            kv_store_id.clone(),
-            scrypto_encode(&unit),
-        ));
+        OrderedSubstateStore::get_kv_store_entries(self, kv_store_id)
+    }
+
+    fn get_non_fungibles(
+        &self,
+        resource_address: &ResourceAddress,
+    ) -> HashMap<NonFungibleId, Substate> {
+        OrderedSubstateStore::get_non_fungibles(self, resource_address)
+    }
+
+    fn get_vaults_holding(&self, resource_address: &ResourceAddress) -> HashMap<VaultId, Substate> {
+        let prefix = scrypto_encode(resource_address);
 
-        let mut iter = self
-            .db
-            .iterator(IteratorMode::From(&id, Direction::Forward));
         let mut items = HashMap::new();
-        while let Some(kv) = iter.next() {
-            let (key, value) = kv.unwrap();
-            let substate: OutputValue = scrypto_decode(&value.to_vec()).unwrap();
-            let substate_id: SubstateId = scrypto_decode(&key).unwrap();
-            if let SubstateId::KeyValueStoreEntry(id, key) = substate_id {
-                if id == *kv_store_id {
-                    items.insert(key, substate.substate)
-                } else {
-                    break;
-                }
-            } else {
+        for kv in self.db.iterator_cf(
+            self.cf(VAULTS_BY_RESOURCE_CF),
+            IteratorMode::From(&prefix, Direction::Forward),
+        ) {
+            let (key, _value) = kv.unwrap();
+            if !key.starts_with(&prefix) {
                 break;
-            };
+            }
+            let vault_id: VaultId = scrypto_decode(&key[prefix.len()..]).unwrap();
+            if let Some(output_value) = self.get_substate(&SubstateId::Vault(vault_id)) {
+                items.insert(vault_id, output_value.substate);
+            }
         }
         items
     }
 }
 
-// Implement this as an enum for now to prevent clashes with Substates
-// TODO: Have a better key prefixing strategy
-#[derive(Debug, Clone, TypeId, Encode, Decode)]
-pub enum Root {
-    Root(SubstateId),
-}
-
 impl ReadableSubstateStore for RadixEngineDB {
     fn get_substate(&self, substate_id: &SubstateId) -> Option<OutputValue> {
         self.read(substate_id).map(|b| scrypto_decode(&b).unwrap())
@@ -165,7 +531,7 @@ impl ReadableSubstateStore for RadixEngineDB {
 
     fn is_root(&self, substate_id: &SubstateId) -> bool {
         self.db
-            .get(scrypto_encode(&Root::Root(substate_id.clone())))
+            .get_cf(self.cf(ROOTS_CF), scrypto_encode(substate_id))
             .unwrap()
             .is_some()
     }
@@ -173,12 +539,252 @@ impl ReadableSubstateStore for RadixEngineDB {
 
 impl WriteableSubstateStore for RadixEngineDB {
     fn put_substate(&mut self, substate_id: SubstateId, substate: OutputValue) {
-        self.write(substate_id, scrypto_encode(&substate));
+        let mut batch = WriteBatch::default();
+        self.put_substate_in_batch(&mut batch, &substate_id, &substate);
+        self.write(batch);
     }
 
     fn set_root(&mut self, substate_id: SubstateId) {
-        self.db
-            .put(scrypto_encode(&Root::Root(substate_id)), vec![])
+        let mut batch = WriteBatch::default();
+        batch.put_cf(self.cf(ROOTS_CF), scrypto_encode(&substate_id), vec![]);
+        self.write(batch);
+    }
+}
+
+impl ReadableMetadataStore for RadixEngineDB {
+    fn get_metadata(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.db.get_cf(self.cf(METADATA_CF), key).unwrap()
+    }
+
+    fn scan_metadata(&self, from: &[u8], f: &mut dyn FnMut(&[u8], &[u8]) -> bool) {
+        for kv in self.db.iterator_cf(
+            self.cf(METADATA_CF),
+            IteratorMode::From(from, Direction::Forward),
+        ) {
+            let (key, value) = kv.unwrap();
+            if !f(&key, &value) {
+                break;
+            }
+        }
+    }
+}
+
+impl CommittableSubstateStore for RadixEngineDB {
+    /// Writes the metadata in the same batch as the state changes and the record of the commit.
+    fn commit_with_metadata(
+        &mut self,
+        state_diff: &StateDiff,
+        metadata: &MetadataWrites,
+    ) -> CommitReceipt {
+        self.write_commit(state_diff, metadata)
+    }
+}
+
+/// Panics if the store was not created with the current `FORMAT_VERSION` and
+/// `SUBSTATE_FORMAT_VERSION`.
+fn check_format_version(db: &DB, root: &Path) {
+    for (key, required) in [
+        (FORMAT_VERSION_KEY, FORMAT_VERSION),
+        (SUBSTATE_FORMAT_VERSION_KEY, SUBSTATE_FORMAT_VERSION),
+    ] {
+        let version = db
+            .get(key)
+            .unwrap()
+            .map(|bytes| u32::from_be_bytes(bytes.as_slice().try_into().unwrap()));
+        if version != Some(required) {
+            panic!(
+                "The store at {} has {} {:?}, but {} is required, so it must be recreated",
+                root.display(),
+                String::from_utf8_lossy(key),
+                version,
+                required
+            );
+        }
+    }
+}
+
+fn column_families() -> Vec<ColumnFamilyDescriptor> {
+    let mut column_families: Vec<ColumnFamilyDescriptor> = SUBSTATE_CFS
+        .iter()
+        .map(|name| {
+            let mut options = Options::default();
+            options.optimize_level_style_compaction(MEMTABLE_MEMORY_BUDGET);
+            options.set_level_compaction_dynamic_level_bytes(true);
+            ColumnFamilyDescriptor::new(*name, options)
+        })
+        .collect();
+    // Holds only the format version
+    column_families.push(ColumnFamilyDescriptor::new(
+        DEFAULT_COLUMN_FAMILY_NAME,
+        Options::default(),
+    ));
+    column_families.push(ColumnFamilyDescriptor::new(ROOTS_CF, Options::default()));
+    column_families.push(ColumnFamilyDescriptor::new(
+        VAULTS_BY_RESOURCE_CF,
+        Options::default(),
+    ));
+    // The commit log is append-only and never updated in place
+    let mut commit_log_options = Options::default();
+    commit_log_options.set_compaction_style(DBCompactionStyle::Universal);
+    column_families.push(ColumnFamilyDescriptor::new(
+        COMMIT_LOG_CF,
+        commit_log_options,
+    ));
+    column_families.push(ColumnFamilyDescriptor::new(HISTORY_CF, Options::default()));
+    column_families.push(ColumnFamilyDescriptor::new(
+        TOMBSTONES_CF,
+        Options::default(),
+    ));
+    column_families.push(ColumnFamilyDescriptor::new(METADATA_CF, Options::default()));
+
+    column_families
+}
+
+fn sequence_key(sequence: u64, substate_id: &SubstateId) -> Vec<u8> {
+    let mut key = sequence.to_be_bytes().to_vec();
+    key.extend(scrypto_encode(substate_id));
+    key
+}
+
+fn history_key_prefix(is_root: bool, sequence: u64) -> Vec<u8> {
+    let mut key = vec![is_root as u8];
+    key.extend(sequence.to_be_bytes());
+    key
+}
+
+fn history_key(is_root: bool, sequence: u64, substate_id: &SubstateId) -> Vec<u8> {
+    let mut key = history_key_prefix(is_root, sequence);
+    key.extend(scrypto_encode(substate_id));
+    key
+}
+
+fn vault_index_key(resource_address: &ResourceAddress, vault_id: &VaultId) -> Vec<u8> {
+    let mut key = scrypto_encode(resource_address);
+    key.extend(scrypto_encode(vault_id));
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn temporary_dir() -> PathBuf {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let root = std::env::temp_dir().join(format!(
+            "radix-engine-db-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        let _ = std::fs::remove_dir_all(&root);
+        root
+    }
+
+    fn temporary_db(retention_policy: RetentionPolicy) -> RadixEngineDB {
+        RadixEngineDB::with_retention_policy(temporary_dir(), retention_policy)
+    }
+
+    substate_store_conformance_tests!(temporary_db(RetentionPolicy::KeepLast(0)));
+
+    fn kv_store_entry_diff(value: Option<u8>) -> StateDiff {
+        let mut state_diff = StateDiff::new();
+        state_diff.up_substates.insert(
+            SubstateId::KeyValueStoreEntry((Hash([0u8; 32]), 0), vec![0]),
+            OutputValue {
+                substate: Substate::KeyValueStoreEntry(value.map(|v| ScryptoValue::from_typed(&v))),
+                version: 0,
+            },
+        );
+        state_diff
+    }
+
+    #[test]
+    fn test_deleted_entries_are_pruned_beyond_retained_versions() {
+        let mut db = RadixEngineDB::pruning(temporary_dir(), 2);
+        let substate_id = SubstateId::KeyValueStoreEntry((Hash([0u8; 32]), 0), vec![0]);
+
+        db.commit_state_diff(&kv_store_entry_diff(Some(1)));
+        db.commit_state_diff(&kv_store_entry_diff(None));
+        assert!(db.get_substate(&substate_id).is_some());
+        db.commit_state_diff(&StateDiff::new());
+        assert!(db.get_substate(&substate_id).is_some());
+        db.commit_state_diff(&StateDiff::new());
+
+        assert!(db.get_substate(&substate_id).is_none());
+    }
+
+    #[test]
+    fn test_archival_db_keeps_deleted_entries() {
+        // Stores are archival unless pruning is asked for
+        let mut db = RadixEngineDB::new(temporary_dir());
+        let substate_id = SubstateId::KeyValueStoreEntry((Hash([0u8; 32]), 0), vec![0]);
+
+        db.commit_state_diff(&kv_store_entry_diff(Some(1)));
+        db.commit_state_diff(&kv_store_entry_diff(None));
+        for _ in 0..3 {
+            db.commit_state_diff(&StateDiff::new());
+        }
+
+        assert!(db.get_substate(&substate_id).is_some());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_store_without_format_version_is_refused() {
+        let root = temporary_dir();
+        let mut db_options = Options::default();
+        db_options.create_if_missing(true);
+        db_options.create_missing_column_families(true);
+        DB::open_cf_descriptors(&db_options, root.as_path(), column_families()).unwrap();
+
+        RadixEngineDB::new(root);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_store_with_other_substate_format_is_refused() {
+        let root = temporary_dir();
+        let db = RadixEngineDB::new(root.clone());
+        db.db
+            .put(
+                SUBSTATE_FORMAT_VERSION_KEY,
+                (SUBSTATE_FORMAT_VERSION + 1).to_be_bytes(),
+            )
             .unwrap();
+        drop(db);
+
+        RadixEngineDB::new(root);
+    }
+
+    #[test]
+    fn test_store_can_be_reopened() {
+        let root = temporary_dir();
+        let mut db = RadixEngineDB::new(root.clone());
+        db.commit_state_diff(&kv_store_entry_diff(Some(1)));
+        drop(db);
+
+        let db = RadixEngineDB::new(root);
+        assert_eq!(db.last_commit_sequence(), Some(0));
+    }
+
+    #[test]
+    fn test_secondary_catches_up_with_primary() {
+        let root = temporary_dir();
+        let mut primary = RadixEngineDB::new(root.clone());
+        let substate_id = SubstateId::KeyValueStoreEntry((Hash([0u8; 32]), 0), vec![0]);
+        primary.commit_state_diff(&kv_store_entry_diff(Some(1)));
+
+        let secondary = RadixEngineDB::open_as_secondary(root, temporary_dir());
+        primary.commit_state_diff(&kv_store_entry_diff(Some(2)));
+        assert_eq!(
+            Substate::KeyValueStoreEntry(Some(ScryptoValue::from_typed(&1u8))),
+            secondary.get_substate(&substate_id).unwrap().substate
+        );
+        secondary.catch_up_with_primary();
+
+        assert_eq!(
+            Substate::KeyValueStoreEntry(Some(ScryptoValue::from_typed(&2u8))),
+            secondary.get_substate(&substate_id).unwrap().substate
+        );
     }
 }
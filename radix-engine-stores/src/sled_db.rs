@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use radix_engine::engine::Substate;
+use radix_engine::ledger::*;
+use radix_engine::state_manager::{CommitReceipt, StateDiff};
+use radix_engine::types::*;
+use sled::transaction::{ConflictableTransactionResult, TransactionalTree};
+use sled::{Db, Transactional, Tree};
+
+use crate::indexed_store::{CommittableSubstateStore, MetadataWrites, ReadableMetadataStore};
+use crate::ordered_store::OrderedSubstateStore;
+
+const SUBSTATES_TREE: &str = "substates";
+const ROOTS_TREE: &str = "roots";
+/// An index of vaults by resource, keyed by encoded `ResourceAddress` followed by encoded `VaultId`
+const VAULTS_BY_RESOURCE_TREE: &str = "vaults_by_resource";
+/// The metadata written along with the substates, such as indexes, by key
+const METADATA_TREE: &str = "metadata";
+/// The key of the `SUBSTATE_FORMAT_VERSION` the substates are encoded with, in the default tree
+const SUBSTATE_FORMAT_VERSION_KEY: &[u8] = b"substate_format_version";
+
+/// A substate store backed by sled, for embedders which can't build RocksDB.
+pub struct SledSubstateStore {
+    db: Db,
+    substates: Tree,
+    roots: Tree,
+    vaults_by_resource: Tree,
+    metadata: Tree,
+}
+
+impl SledSubstateStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self::open(sled::open(root).unwrap())
+    }
+
+    /// Creates a store which is deleted once dropped.
+    pub fn temporary() -> Self {
+        Self::open(sled::Config::new().temporary(true).open().unwrap())
+    }
+
+    pub fn with_bootstrap(root: PathBuf) -> Self {
+        let substate_store = Self::new(root);
+        bootstrap(substate_store)
+    }
+
+    /// # Panics
+    /// If the store was created with a different `SUBSTATE_FORMAT_VERSION`.
+    fn open(db: Db) -> Self {
+        if !db.was_recovered() {
+            db.insert(
+                SUBSTATE_FORMAT_VERSION_KEY,
+                &SUBSTATE_FORMAT_VERSION.to_be_bytes(),
+            )
+            .unwrap();
+        }
+        let substate_format_version = db
+            .get(SUBSTATE_FORMAT_VERSION_KEY)
+            .unwrap()
+            .map(|bytes| u32::from_be_bytes(bytes.as_ref().try_into().unwrap()));
+        if substate_format_version != Some(SUBSTATE_FORMAT_VERSION) {
+            panic!(
+                "The store has substate format version {:?}, but {} is required, so it must be recreated",
+                substate_format_version, SUBSTATE_FORMAT_VERSION
+            );
+        }
+
+        let substates = db.open_tree(SUBSTATES_TREE).unwrap();
+        let roots = db.open_tree(ROOTS_TREE).unwrap();
+        let vaults_by_resource = db.open_tree(VAULTS_BY_RESOURCE_TREE).unwrap();
+        let metadata = db.open_tree(METADATA_TREE).unwrap();
+        Self {
+            db,
+            substates,
+            roots,
+            vaults_by_resource,
+            metadata,
+        }
+    }
+
+    /// Blocks until all previous writes are persisted to disk.
+    pub fn flush(&self) {
+        self.db.flush().unwrap();
+    }
+}
+
+impl OrderedSubstateStore for SledSubstateStore {
+    fn scan_substates(&self, from: &SubstateId, f: &mut dyn FnMut(&[u8], &[u8]) -> bool) {
+        for kv in self.substates.range(scrypto_encode(from)..) {
+            let (key, value) = kv.unwrap();
+            if !f(&key, &value) {
+                break;
+            }
+        }
+    }
+}
+
+impl QueryableSubstateStore for SledSubstateStore {
+    fn get_kv_store_entries(&self, kv_store_id: &KeyValueStoreId) -> HashMap<Vec<u8>, Substate> {
+        OrderedSubstateStore::get_kv_store_entries(self, kv_store_id)
+    }
+
+    fn get_non_fungibles(
+        &self,
+        resource_address: &ResourceAddress,
+    ) -> HashMap<NonFungibleId, Substate> {
+        OrderedSubstateStore::get_non_fungibles(self, resource_address)
+    }
+
+    fn get_vaults_holding(&self, resource_address: &ResourceAddress) -> HashMap<VaultId, Substate> {
+        let prefix = scrypto_encode(resource_address);
+
+        let mut items = HashMap::new();
+        for kv in self.vaults_by_resource.scan_prefix(&prefix) {
+            let (key, _value) = kv.unwrap();
+            let vault_id: VaultId = scrypto_decode(&key[prefix.len()..]).unwrap();
+            if let Some(output_value) = self.get_substate(&SubstateId::Vault(vault_id)) {
+                items.insert(vault_id, output_value.substate);
+            }
+        }
+        items
+    }
+}
+
+impl ReadableSubstateStore for SledSubstateStore {
+    fn get_substate(&self, substate_id: &SubstateId) -> Option<OutputValue> {
+        self.substates
+            .get(scrypto_encode(substate_id))
+            .unwrap()
+            .map(|b| scrypto_decode(&b).unwrap())
+    }
+
+    fn is_root(&self, substate_id: &SubstateId) -> bool {
+        self.roots
+            .contains_key(scrypto_encode(substate_id))
+            .unwrap()
+    }
+}
+
+impl WriteableSubstateStore for SledSubstateStore {
+    fn put_substate(&mut self, substate_id: SubstateId, substate: OutputValue) {
+        // The resource of a vault never changes, so its index entry is never stale
+        if let Some(key) = vault_index_key(&substate_id, &substate) {
+            self.vaults_by_resource.insert(key, vec![]).unwrap();
+        }
+        self.substates
+            .insert(scrypto_encode(&substate_id), scrypto_encode(&substate))
+            .unwrap();
+    }
+
+    fn set_root(&mut self, substate_id: SubstateId) {
+        self.roots
+            .insert(scrypto_encode(&substate_id), vec![])
+            .unwrap();
+    }
+}
+
+impl ReadableMetadataStore for SledSubstateStore {
+    fn get_metadata(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.metadata.get(key).unwrap().map(|value| value.to_vec())
+    }
+
+    fn scan_metadata(&self, from: &[u8], f: &mut dyn FnMut(&[u8], &[u8]) -> bool) {
+        for kv in self.metadata.range(from..) {
+            let (key, value) = kv.unwrap();
+            if !f(&key, &value) {
+                break;
+            }
+        }
+    }
+}
+
+impl CommittableSubstateStore for SledSubstateStore {
+    /// Writes the state changes and the metadata in a single transaction across the trees.
+    fn commit_with_metadata(
+        &mut self,
+        state_diff: &StateDiff,
+        metadata: &MetadataWrites,
+    ) -> CommitReceipt {
+        (
+            &self.substates,
+            &self.roots,
+            &self.vaults_by_resource,
+            &self.metadata,
+        )
+            .transaction(|(substates, roots, vaults_by_resource, metadata_tree)| {
+                write_commit(
+                    substates,
+                    roots,
+                    vaults_by_resource,
+                    metadata_tree,
+                    state_diff,
+                    metadata,
+                )
+            })
+            .unwrap();
+        state_diff.to_commit_receipt()
+    }
+}
+
+fn write_commit(
+    substates: &TransactionalTree,
+    roots: &TransactionalTree,
+    vaults_by_resource: &TransactionalTree,
+    metadata_tree: &TransactionalTree,
+    state_diff: &StateDiff,
+    metadata: &MetadataWrites,
+) -> ConflictableTransactionResult<()> {
+    for (substate_id, output_value) in &state_diff.up_substates {
+        if let Some(key) = vault_index_key(substate_id, output_value) {
+            vaults_by_resource.insert(key, vec![])?;
+        }
+        substates.insert(scrypto_encode(substate_id), scrypto_encode(output_value))?;
+    }
+    for substate_id in &state_diff.new_roots {
+        roots.insert(scrypto_encode(substate_id), vec![])?;
+    }
+    for (key, value) in metadata {
+        match value {
+            Some(value) => metadata_tree.insert(key.as_slice(), value.as_slice())?,
+            None => metadata_tree.remove(key.as_slice())?,
+        };
+    }
+    Ok(())
+}
+
+/// Returns the key of the index entry of the given substate if it's a vault, which is the
+/// encoded `ResourceAddress` of the vault followed by the encoded `VaultId`.
+fn vault_index_key(substate_id: &SubstateId, output_value: &OutputValue) -> Option<Vec<u8>> {
+    match (substate_id, &output_value.substate) {
+        (SubstateId::Vault(vault_id), Substate::Vault(vault)) => {
+            let mut key = scrypto_encode(&vault.resource_address());
+            key.extend(scrypto_encode(vault_id));
+            Some(key)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    substate_store_conformance_tests!(SledSubstateStore::temporary());
+}
@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use radix_engine::engine::{Substate, TrackReceipt};
+use radix_engine::ledger::*;
+use radix_engine::state_manager::{CommitReceipt, StateDiff};
+use radix_engine::transaction::TransactionResult;
+use radix_engine::types::*;
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::indexed_store::{CommittableSubstateStore, MetadataWrites, ReadableMetadataStore};
+use crate::ordered_store::OrderedSubstateStore;
+
+/// A substate store backed by SQLite, for embedders which can't build RocksDB.
+pub struct SqliteSubstateStore {
+    connection: Connection,
+}
+
+impl SqliteSubstateStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self::open(Connection::open(root).unwrap())
+    }
+
+    /// Creates a store which only lives in memory.
+    pub fn in_memory() -> Self {
+        Self::open(Connection::open_in_memory().unwrap())
+    }
+
+    pub fn with_bootstrap(root: PathBuf) -> Self {
+        let substate_store = Self::new(root);
+        bootstrap(substate_store)
+    }
+
+    /// Commits the state changes of a transaction, if any, in a single SQL transaction.
+    pub fn commit(&mut self, receipt: &TrackReceipt) -> Option<CommitReceipt> {
+        match &receipt.result {
+            TransactionResult::Commit(commit) => {
+                Some(self.commit_state_diff(&commit.state_updates))
+            }
+            TransactionResult::Reject(..) => None,
+        }
+    }
+
+    /// Writes the state changes in a single SQL transaction, so that either all or none of them
+    /// are persisted should the process crash mid-commit.
+    pub fn commit_state_diff(&mut self, state_diff: &StateDiff) -> CommitReceipt {
+        self.commit_with_metadata(state_diff, &MetadataWrites::new())
+    }
+
+    /// # Panics
+    /// If the store was created with a different `SUBSTATE_FORMAT_VERSION`.
+    fn open(connection: Connection) -> Self {
+        // The substate format version is kept as the user version, which is 0 until set
+        let created: bool = connection
+            .query_row(
+                "SELECT NOT EXISTS (SELECT 1 FROM sqlite_master WHERE name = 'substates')",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        if created {
+            connection
+                .pragma_update(None, "user_version", SUBSTATE_FORMAT_VERSION)
+                .unwrap();
+        }
+        let substate_format_version: u32 = connection
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .unwrap();
+        if substate_format_version != SUBSTATE_FORMAT_VERSION {
+            panic!(
+                "The store has substate format version {}, but {} is required, so it must be recreated",
+                substate_format_version, SUBSTATE_FORMAT_VERSION
+            );
+        }
+
+        connection
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS substates (id BLOB PRIMARY KEY, value BLOB NOT NULL);
+                CREATE TABLE IF NOT EXISTS roots (id BLOB PRIMARY KEY);
+                CREATE TABLE IF NOT EXISTS vaults_by_resource (
+                    resource_address BLOB NOT NULL,
+                    vault_id BLOB NOT NULL,
+                    PRIMARY KEY (resource_address, vault_id)
+                );
+                CREATE TABLE IF NOT EXISTS metadata (key BLOB PRIMARY KEY, value BLOB NOT NULL);",
+            )
+            .unwrap();
+        Self { connection }
+    }
+}
+
+impl OrderedSubstateStore for SqliteSubstateStore {
+    fn scan_substates(&self, from: &SubstateId, f: &mut dyn FnMut(&[u8], &[u8]) -> bool) {
+        // Blobs are compared with memcmp, so substates are ordered the same way as in RocksDB
+        let mut statement = self
+            .connection
+            .prepare("SELECT id, value FROM substates WHERE id >= ?1 ORDER BY id")
+            .unwrap();
+        let mut rows = statement.query(params![scrypto_encode(from)]).unwrap();
+        while let Some(row) = rows.next().unwrap() {
+            let key: Vec<u8> = row.get(0).unwrap();
+            let value: Vec<u8> = row.get(1).unwrap();
+            if !f(&key, &value) {
+                break;
+            }
+        }
+    }
+}
+
+impl QueryableSubstateStore for SqliteSubstateStore {
+    fn get_kv_store_entries(&self, kv_store_id: &KeyValueStoreId) -> HashMap<Vec<u8>, Substate> {
+        OrderedSubstateStore::get_kv_store_entries(self, kv_store_id)
+    }
+
+    fn get_non_fungibles(
+        &self,
+        resource_address: &ResourceAddress,
+    ) -> HashMap<NonFungibleId, Substate> {
+        OrderedSubstateStore::get_non_fungibles(self, resource_address)
+    }
+
+    fn get_vaults_holding(&self, resource_address: &ResourceAddress) -> HashMap<VaultId, Substate> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT vault_id FROM vaults_by_resource WHERE resource_address = ?1")
+            .unwrap();
+        let mut rows = statement
+            .query(params![scrypto_encode(resource_address)])
+            .unwrap();
+
+        let mut items = HashMap::new();
+        while let Some(row) = rows.next().unwrap() {
+            let vault_id: Vec<u8> = row.get(0).unwrap();
+            let vault_id: VaultId = scrypto_decode(&vault_id).unwrap();
+            if let Some(output_value) = self.get_substate(&SubstateId::Vault(vault_id)) {
+                items.insert(vault_id, output_value.substate);
+            }
+        }
+        items
+    }
+}
+
+impl ReadableSubstateStore for SqliteSubstateStore {
+    fn get_substate(&self, substate_id: &SubstateId) -> Option<OutputValue> {
+        self.connection
+            .query_row(
+                "SELECT value FROM substates WHERE id = ?1",
+                params![scrypto_encode(substate_id)],
+                |row| row.get::<_, Vec<u8>>(0),
+            )
+            .optional()
+            .unwrap()
+            .map(|b| scrypto_decode(&b).unwrap())
+    }
+
+    fn is_root(&self, substate_id: &SubstateId) -> bool {
+        self.connection
+            .query_row(
+                "SELECT 1 FROM roots WHERE id = ?1",
+                params![scrypto_encode(substate_id)],
+                |_| Ok(()),
+            )
+            .optional()
+            .unwrap()
+            .is_some()
+    }
+}
+
+impl WriteableSubstateStore for SqliteSubstateStore {
+    fn put_substate(&mut self, substate_id: SubstateId, substate: OutputValue) {
+        let transaction = self.connection.transaction().unwrap();
+        put_substate(&transaction, &substate_id, &substate);
+        transaction.commit().unwrap();
+    }
+
+    fn set_root(&mut self, substate_id: SubstateId) {
+        set_root(&self.connection, &substate_id);
+    }
+}
+
+impl ReadableMetadataStore for SqliteSubstateStore {
+    fn get_metadata(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.connection
+            .query_row(
+                "SELECT value FROM metadata WHERE key = ?1",
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()
+            .unwrap()
+    }
+
+    fn scan_metadata(&self, from: &[u8], f: &mut dyn FnMut(&[u8], &[u8]) -> bool) {
+        let mut statement = self
+            .connection
+            .prepare("SELECT key, value FROM metadata WHERE key >= ?1 ORDER BY key")
+            .unwrap();
+        let mut rows = statement.query(params![from]).unwrap();
+        while let Some(row) = rows.next().unwrap() {
+            let key: Vec<u8> = row.get(0).unwrap();
+            let value: Vec<u8> = row.get(1).unwrap();
+            if !f(&key, &value) {
+                break;
+            }
+        }
+    }
+}
+
+impl CommittableSubstateStore for SqliteSubstateStore {
+    /// Writes the state changes and the metadata in a single SQL transaction.
+    fn commit_with_metadata(
+        &mut self,
+        state_diff: &StateDiff,
+        metadata: &MetadataWrites,
+    ) -> CommitReceipt {
+        let transaction = self.connection.transaction().unwrap();
+        for (substate_id, output_value) in &state_diff.up_substates {
+            put_substate(&transaction, substate_id, output_value);
+        }
+        for substate_id in &state_diff.new_roots {
+            set_root(&transaction, substate_id);
+        }
+        for (key, value) in metadata {
+            match value {
+                Some(value) => transaction.execute(
+                    "INSERT OR REPLACE INTO metadata (key, value) VALUES (?1, ?2)",
+                    params![key, value],
+                ),
+                None => transaction.execute("DELETE FROM metadata WHERE key = ?1", params![key]),
+            }
+            .unwrap();
+        }
+        transaction.commit().unwrap();
+
+        state_diff.to_commit_receipt()
+    }
+}
+
+fn put_substate(connection: &Connection, substate_id: &SubstateId, substate: &OutputValue) {
+    // The resource of a vault never changes, so its index entry is never stale
+    if let (SubstateId::Vault(vault_id), Substate::Vault(vault)) = (substate_id, &substate.substate)
+    {
+        connection
+            .execute(
+                "INSERT OR IGNORE INTO vaults_by_resource (resource_address, vault_id) VALUES (?1, ?2)",
+                params![
+                    scrypto_encode(&vault.resource_address()),
+                    scrypto_encode(vault_id)
+                ],
+            )
+            .unwrap();
+    }
+    connection
+        .execute(
+            "INSERT OR REPLACE INTO substates (id, value) VALUES (?1, ?2)",
+            params![scrypto_encode(substate_id), scrypto_encode(substate)],
+        )
+        .unwrap();
+}
+
+fn set_root(connection: &Connection, substate_id: &SubstateId) {
+    connection
+        .execute(
+            "INSERT OR IGNORE INTO roots (id) VALUES (?1)",
+            params![scrypto_encode(substate_id)],
+        )
+        .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    substate_store_conformance_tests!(SqliteSubstateStore::in_memory());
+
+    #[test]
+    fn test_commit_state_diff() {
+        let mut store = SqliteSubstateStore::in_memory();
+        let substate_id = SubstateId::KeyValueStoreEntry((Hash([0u8; 32]), 0), vec![0]);
+        let output_value = OutputValue {
+            substate: Substate::KeyValueStoreEntry(Some(ScryptoValue::from_typed(&1u8))),
+            version: 0,
+        };
+        let mut state_diff = StateDiff::new();
+        state_diff
+            .up_substates
+            .insert(substate_id.clone(), output_value.clone());
+        state_diff.new_roots.push(substate_id.clone());
+
+        store.commit_state_diff(&state_diff);
+
+        assert_eq!(store.get_substate(&substate_id), Some(output_value));
+        assert!(store.is_root(&substate_id));
+    }
+}
@@ -0,0 +1,310 @@
+use radix_engine::state_manager::{
+    key_bit, leaf_hash, leaf_key, node_hash, value_hash, StateProof, EMPTY_SUBTREE_HASH,
+};
+use radix_engine::types::*;
+
+use crate::indexed_store::{CommittableSubstateStore, ReadableMetadataStore, StagedState};
+
+/// The prefix of the metadata keys of the tree nodes, which are followed by the big-endian depth
+/// of the node and the path to it, as a key with the bits below that depth cleared
+const NODE_PREFIX: &[u8] = b"state_tree/";
+
+/// A node of the state tree.
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode)]
+enum TreeNode {
+    Leaf { key: Hash, value_hash: Hash },
+    Internal { left: Hash, right: Hash },
+}
+
+impl TreeNode {
+    fn hash(&self) -> Hash {
+        match self {
+            TreeNode::Leaf { key, value_hash } => leaf_hash(key, value_hash),
+            TreeNode::Internal { left, right } => node_hash(left, right),
+        }
+    }
+}
+
+/// A sparse Merkle tree over the substates of a store.
+///
+/// Each substate is a leaf keyed by the hash of its `SubstateId` and holding the hash of its
+/// value. A subtree containing a single leaf is represented by the leaf itself, so the depth
+/// of the tree grows with the number of substates rather than the key size.
+///
+/// The nodes are kept as metadata of the store, by path, so that writing a substate, reading the
+/// root and proving a substate each only touch the nodes along the path to its leaf.
+pub struct StateTree;
+
+impl StateTree {
+    /// Inserts the leaves of the substates written by the staged state diff.
+    pub fn update<S: CommittableSubstateStore>(&self, staged: &mut StagedState<S>) {
+        for (substate_id, output_value) in &staged.state_diff().up_substates {
+            insert(
+                staged,
+                leaf_key(substate_id),
+                value_hash(&output_value.substate),
+            );
+        }
+    }
+
+    /// Returns the root hash of the state tree.
+    pub fn root<S: ReadableMetadataStore>(&self, store: &S) -> Hash {
+        get_node(|key| store.get_metadata(key), &EMPTY_SUBTREE_HASH, 0)
+            .map_or(EMPTY_SUBTREE_HASH, |node| node.hash())
+    }
+
+    /// Returns a proof of the current value of the given substate, if it's part of the tree.
+    pub fn inclusion_proof<S: ReadableMetadataStore>(
+        &self,
+        store: &S,
+        substate_id: &SubstateId,
+    ) -> Option<StateProof> {
+        let key = leaf_key(substate_id);
+        let mut siblings = Vec::new();
+        let mut depth = 0;
+        loop {
+            match get_node(|k| store.get_metadata(k), &key, depth)? {
+                TreeNode::Internal { left, right } => {
+                    siblings.push(if key_bit(&key, depth) { left } else { right });
+                    depth += 1;
+                }
+                TreeNode::Leaf {
+                    key: leaf_key,
+                    value_hash,
+                } => {
+                    return (leaf_key == key).then_some(StateProof {
+                        key,
+                        value_hash,
+                        siblings,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Sets the leaf of the given key, then rehashes the nodes along the path to it.
+fn insert<S: CommittableSubstateStore>(staged: &mut StagedState<S>, key: Hash, value_hash: Hash) {
+    let mut depth = 0;
+    let leaf_depth = loop {
+        match get_node(|k| staged.get_metadata(k), &key, depth) {
+            Some(TreeNode::Internal { .. }) => depth += 1,
+            Some(TreeNode::Leaf {
+                key: other_key,
+                value_hash: other_value_hash,
+            }) if other_key != key => {
+                // Both leaves move below the depth where their keys diverge
+                let mut split_depth = depth;
+                while key_bit(&key, split_depth) == key_bit(&other_key, split_depth) {
+                    split_depth += 1;
+                }
+                put_node(
+                    staged,
+                    &other_key,
+                    split_depth + 1,
+                    &TreeNode::Leaf {
+                        key: other_key,
+                        value_hash: other_value_hash,
+                    },
+                );
+                break split_depth + 1;
+            }
+            Some(TreeNode::Leaf { .. }) | None => break depth,
+        }
+    };
+    put_node(
+        staged,
+        &key,
+        leaf_depth,
+        &TreeNode::Leaf { key, value_hash },
+    );
+
+    for depth in (0..leaf_depth).rev() {
+        let child_hash = |bit: bool| {
+            get_node(
+                |k| staged.get_metadata(k),
+                &with_bit(&key, depth, bit),
+                depth + 1,
+            )
+            .map_or(EMPTY_SUBTREE_HASH, |node| node.hash())
+        };
+        let node = TreeNode::Internal {
+            left: child_hash(false),
+            right: child_hash(true),
+        };
+        put_node(staged, &key, depth, &node);
+    }
+}
+
+/// Returns the metadata key of the node at the given depth on the path to the given key.
+fn node_key(key: &Hash, depth: usize) -> Vec<u8> {
+    let mut path = key.0;
+    for (i, byte) in path.iter_mut().enumerate() {
+        let kept_bits = depth.saturating_sub(i * 8).min(8);
+        *byte &= !(0xffu16 >> kept_bits) as u8;
+    }
+
+    let mut node_key = NODE_PREFIX.to_vec();
+    node_key.extend((depth as u16).to_be_bytes());
+    node_key.extend(path);
+    node_key
+}
+
+/// Returns the given key with the bit at the given depth set to the given value.
+fn with_bit(key: &Hash, depth: usize, bit: bool) -> Hash {
+    let mut key = *key;
+    let mask = 0x80u8 >> (depth % 8);
+    if bit {
+        key.0[depth / 8] |= mask;
+    } else {
+        key.0[depth / 8] &= !mask;
+    }
+    key
+}
+
+fn get_node(
+    get_metadata: impl FnOnce(&[u8]) -> Option<Vec<u8>>,
+    key: &Hash,
+    depth: usize,
+) -> Option<TreeNode> {
+    get_metadata(&node_key(key, depth)).map(|bytes| scrypto_decode(&bytes).unwrap())
+}
+
+fn put_node<S: CommittableSubstateStore>(
+    staged: &mut StagedState<S>,
+    key: &Hash,
+    depth: usize,
+    node: &TreeNode,
+) {
+    staged.put_metadata(node_key(key, depth), scrypto_encode(node));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexed_store::IndexedSubstateStore;
+    use crate::memory_db::SerializedInMemorySubstateStore;
+    use radix_engine::engine::Substate;
+    use radix_engine::ledger::{OutputValue, WriteableSubstateStore};
+    use radix_engine::state_manager::StateDiff;
+    use radix_engine::state_manager::{verify_substate_proof, MAX_TREE_DEPTH};
+
+    fn substate_id(n: u8) -> SubstateId {
+        SubstateId::KeyValueStoreEntry((Hash([0u8; 32]), 0), vec![n])
+    }
+
+    fn output_value(n: u8) -> OutputValue {
+        OutputValue {
+            substate: Substate::KeyValueStoreEntry(Some(ScryptoValue::from_typed(&n))),
+            version: 0,
+        }
+    }
+
+    fn new_store() -> IndexedSubstateStore<SerializedInMemorySubstateStore> {
+        IndexedSubstateStore::new(SerializedInMemorySubstateStore::new()).with_state_tree()
+    }
+
+    fn store_with(n: u8) -> IndexedSubstateStore<SerializedInMemorySubstateStore> {
+        let mut store = new_store();
+        for i in 0..n {
+            store.put_substate(substate_id(i), output_value(i));
+        }
+        store
+    }
+
+    #[test]
+    fn test_state_root_is_independent_of_write_order() {
+        let store = store_with(10);
+        let mut reversed = new_store();
+        for i in (0..10).rev() {
+            reversed.put_substate(substate_id(i), output_value(i));
+        }
+
+        assert_ne!(EMPTY_SUBTREE_HASH, store.state_root());
+        assert_eq!(store.state_root(), reversed.state_root());
+    }
+
+    #[test]
+    fn test_state_root_is_independent_of_commits() {
+        let store = store_with(10);
+        let mut batched = new_store();
+        let mut state_diff = StateDiff::new();
+        for i in 0..10 {
+            state_diff
+                .up_substates
+                .insert(substate_id(i), output_value(i));
+        }
+        batched.commit_state_diff(&state_diff);
+
+        assert_eq!(store.state_root(), batched.state_root());
+    }
+
+    #[test]
+    fn test_state_root_changes_with_value() {
+        let mut store = store_with(10);
+        let root = store.state_root();
+
+        store.put_substate(substate_id(3), output_value(100));
+
+        assert_ne!(root, store.state_root());
+    }
+
+    #[test]
+    fn test_inclusion_proof() {
+        let store = store_with(10);
+        let root = store.state_root();
+
+        for i in 0..10 {
+            let proof = store.inclusion_proof(&substate_id(i)).unwrap();
+            assert!(proof.verify(root));
+        }
+        assert!(store.inclusion_proof(&substate_id(10)).is_none());
+    }
+
+    #[test]
+    fn test_inclusion_proof_of_stale_value_is_rejected() {
+        let mut store = store_with(10);
+        let proof = store.inclusion_proof(&substate_id(3)).unwrap();
+
+        store.put_substate(substate_id(3), output_value(100));
+
+        assert!(!proof.verify(store.state_root()));
+    }
+
+    #[test]
+    fn test_inclusion_proof_with_too_many_siblings_is_rejected() {
+        let store = store_with(10);
+        let mut proof = store.inclusion_proof(&substate_id(3)).unwrap();
+
+        proof.siblings = vec![EMPTY_SUBTREE_HASH; MAX_TREE_DEPTH + 1];
+
+        assert!(!proof.verify(store.state_root()));
+    }
+
+    #[test]
+    fn test_get_substate_with_proof() {
+        let store = store_with(10);
+        let root = store.state_root();
+
+        let (value, proof) = store.get_substate_with_proof(&substate_id(3)).unwrap();
+
+        assert!(verify_substate_proof(
+            root,
+            &substate_id(3),
+            &value.substate,
+            &proof
+        ));
+        assert!(!verify_substate_proof(
+            root,
+            &substate_id(4),
+            &value.substate,
+            &proof
+        ));
+        assert!(!verify_substate_proof(
+            root,
+            &substate_id(3),
+            &output_value(4).substate,
+            &proof
+        ));
+    }
+}
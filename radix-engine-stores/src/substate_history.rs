@@ -0,0 +1,244 @@
+use radix_engine::ledger::OutputValue;
+use radix_engine::types::*;
+
+use crate::indexed_store::{CommittableSubstateStore, ReadableMetadataStore, StagedState};
+
+/// The prefix of the metadata keys of the history, which are followed by the encoded
+/// `SubstateId` and the big-endian state version
+const HISTORY_PREFIX: &[u8] = b"history/";
+
+/// Decides which historical substate versions a store retains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetentionPolicy {
+    /// Retain every state version
+    KeepAll,
+    /// Retain the given number of most recent state versions
+    KeepLast(u64),
+}
+
+/// An index of the values each substate had at the state versions it was written in.
+///
+/// Any retained state version can then be queried with `get_substate_at`.
+pub struct SubstateHistory {
+    retention_policy: RetentionPolicy,
+}
+
+impl SubstateHistory {
+    pub fn new(retention_policy: RetentionPolicy) -> Self {
+        Self { retention_policy }
+    }
+
+    /// Records the values written by the staged state diff, which closes the given state version.
+    pub fn update<S: CommittableSubstateStore>(
+        &self,
+        staged: &mut StagedState<S>,
+        state_version: u64,
+    ) {
+        let oldest = self.oldest_retained_version(state_version + 1);
+        let state_diff = staged.state_diff();
+        for (substate_id, output_value) in &state_diff.up_substates {
+            staged.put_metadata(
+                history_key(substate_id, state_version),
+                scrypto_encode(output_value),
+            );
+
+            if self.retention_policy == RetentionPolicy::KeepAll {
+                continue;
+            }
+            // Only the substates written are pruned, which keeps the cost of a commit bounded.
+            // The values of the others are either still in effect or never read, being older
+            // than the oldest retained version.
+            let mut versions = Vec::new();
+            scan_history(staged.committed(), substate_id, &mut |version, _| {
+                if version <= oldest {
+                    versions.push(version);
+                }
+                version <= oldest
+            });
+            if state_version <= oldest {
+                versions.push(state_version);
+            }
+            // The last version up to the oldest retained one is in effect at it, so it's kept
+            if let Some((_, superseded)) = versions.split_last() {
+                for version in superseded {
+                    staged.delete_metadata(history_key(substate_id, *version));
+                }
+            }
+        }
+    }
+
+    /// Returns the value of the given substate as of the given state version, if it existed
+    /// and the version is retained, given the number of state versions committed.
+    pub fn get_substate_at<S: ReadableMetadataStore>(
+        &self,
+        store: &S,
+        substate_id: &SubstateId,
+        state_version: u64,
+        committed_versions: u64,
+    ) -> Option<OutputValue> {
+        if state_version >= committed_versions
+            || state_version < self.oldest_retained_version(committed_versions)
+        {
+            return None;
+        }
+
+        let mut in_effect = None;
+        scan_history(store, substate_id, &mut |version, value| {
+            if version <= state_version {
+                in_effect = Some(value.to_vec());
+            }
+            version <= state_version
+        });
+        in_effect.map(|value| scrypto_decode(&value).unwrap())
+    }
+
+    fn oldest_retained_version(&self, committed_versions: u64) -> u64 {
+        match self.retention_policy {
+            RetentionPolicy::KeepAll => 0,
+            RetentionPolicy::KeepLast(n) => committed_versions.saturating_sub(n),
+        }
+    }
+}
+
+fn history_key_prefix(substate_id: &SubstateId) -> Vec<u8> {
+    let mut key = HISTORY_PREFIX.to_vec();
+    key.extend(scrypto_encode(substate_id));
+    key
+}
+
+fn history_key(substate_id: &SubstateId, state_version: u64) -> Vec<u8> {
+    let mut key = history_key_prefix(substate_id);
+    key.extend(state_version.to_be_bytes());
+    key
+}
+
+/// Calls `f` with the recorded versions and values of the given substate, in order, until it
+/// returns `false`.
+fn scan_history<S: ReadableMetadataStore>(
+    store: &S,
+    substate_id: &SubstateId,
+    f: &mut dyn FnMut(u64, &[u8]) -> bool,
+) {
+    // Encoded substate ids are never a prefix of one another
+    let prefix = history_key_prefix(substate_id);
+    store.scan_metadata(&prefix, &mut |key, value| {
+        if !key.starts_with(&prefix) {
+            return false;
+        }
+        let version = u64::from_be_bytes(key[prefix.len()..].try_into().unwrap());
+        f(version, value)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexed_store::IndexedSubstateStore;
+    use crate::memory_db::SerializedInMemorySubstateStore;
+    use radix_engine::engine::Substate;
+    use radix_engine::ledger::{QueryableSubstateStore, ReadableSubstateStore};
+    use radix_engine::state_manager::StateDiff;
+
+    const KV_STORE: KeyValueStoreId = (Hash([0u8; 32]), 0);
+
+    fn substate_id() -> SubstateId {
+        SubstateId::KeyValueStoreEntry(KV_STORE, vec![0])
+    }
+
+    fn output_value(n: u8) -> OutputValue {
+        OutputValue {
+            substate: Substate::KeyValueStoreEntry(Some(ScryptoValue::from_typed(&n))),
+            version: n as u32,
+        }
+    }
+
+    fn state_diff(substate_id: SubstateId, output_value: OutputValue) -> StateDiff {
+        let mut state_diff = StateDiff::new();
+        state_diff.up_substates.insert(substate_id, output_value);
+        state_diff
+    }
+
+    /// Writes the substate once per state version, from version 0 to `n - 1`.
+    fn store_with(
+        n: u8,
+        retention_policy: RetentionPolicy,
+    ) -> IndexedSubstateStore<SerializedInMemorySubstateStore> {
+        let mut store = IndexedSubstateStore::new(SerializedInMemorySubstateStore::new())
+            .with_history(retention_policy);
+        for i in 0..n {
+            store.commit_state_diff(&state_diff(substate_id(), output_value(i)));
+        }
+        store
+    }
+
+    #[test]
+    fn test_get_substate_at() {
+        let mut store = store_with(3, RetentionPolicy::KeepAll);
+        store.commit_state_diff(&StateDiff::new());
+
+        assert_eq!(4, store.state_version());
+        assert_eq!(
+            Some(output_value(0)),
+            store.get_substate_at(&substate_id(), 0)
+        );
+        assert_eq!(
+            Some(output_value(2)),
+            store.get_substate_at(&substate_id(), 2)
+        );
+        assert_eq!(
+            Some(output_value(2)),
+            store.get_substate_at(&substate_id(), 3)
+        );
+        assert_eq!(Some(output_value(2)), store.get_substate(&substate_id()));
+    }
+
+    #[test]
+    fn test_get_substate_at_uncommitted_version() {
+        let store = store_with(3, RetentionPolicy::KeepAll);
+
+        assert_eq!(None, store.get_substate_at(&substate_id(), 3));
+        assert_eq!(None, store.get_substate_at(&substate_id(), 100));
+    }
+
+    #[test]
+    fn test_prune_keeps_last_versions() {
+        let mut store = store_with(5, RetentionPolicy::KeepLast(2));
+        store.commit_state_diff(&StateDiff::new());
+        store.commit_state_diff(&StateDiff::new());
+
+        assert_eq!(None, store.get_substate_at(&substate_id(), 4));
+        assert_eq!(
+            Some(output_value(4)),
+            store.get_substate_at(&substate_id(), 5)
+        );
+        assert_eq!(
+            Some(output_value(4)),
+            store.get_substate_at(&substate_id(), 6)
+        );
+        // Version 3 was in effect at the oldest retained version when version 4 was written
+        let mut versions = Vec::new();
+        scan_history(store.inner(), &substate_id(), &mut |version, _| {
+            versions.push(version);
+            true
+        });
+        assert_eq!(vec![3, 4], versions);
+    }
+
+    #[test]
+    fn test_at_version() {
+        let mut store = store_with(2, RetentionPolicy::KeepAll);
+        let other_id = SubstateId::KeyValueStoreEntry(KV_STORE, vec![1]);
+        store.commit_state_diff(&state_diff(other_id.clone(), output_value(5)));
+
+        let substates = store.at_version(1);
+
+        assert_eq!(
+            Some(output_value(1)),
+            substates.get_substate(&substate_id())
+        );
+        assert_eq!(None, substates.get_substate(&other_id));
+        let mut expected = HashMap::new();
+        expected.insert(vec![0], output_value(1).substate);
+        assert_eq!(expected, substates.get_kv_store_entries(&KV_STORE));
+    }
+}
@@ -64,6 +64,8 @@ fn bench_transaction_validation(c: &mut Criterion) {
             notary_as_signatory: true,
             cost_unit_limit: 1_000_000,
             tip_percentage: 5,
+            declared_substates: None,
+            fee_payer: None,
         })
         .manifest(
             ManifestBuilder::new(&NetworkDefinition::simulator())
@@ -1,3 +1,5 @@
+use crate::types::{DecodeLimits, Hash, VaultId};
+
 /// The default cost units loaned from the system to bootstrap execution (lock fee).
 /// TODO: reduce this number by more optimization, currently limited by `Account` wasm instantiation
 pub const DEFAULT_SYSTEM_LOAN: u32 = 1_500_000;
@@ -14,6 +16,25 @@ pub const DEFAULT_COST_UNIT_PRICE: &'static str = "0.0000001";
 /// The default max call depth.
 pub const DEFAULT_MAX_CALL_DEPTH: usize = 16;
 
+/// The default max size, in bytes, of the nodes owned by all call frames.
+pub const DEFAULT_MAX_HEAP_SIZE: usize = 32 * 1024 * 1024;
+
+/// The max number of random bytes a single system call may generate.
+pub const MAX_RANDOM_BYTES_LENGTH: u32 = 64 * 1024;
+
+/// The max number of allow-list lookups made by a single authorization check.
+pub const MAX_MEMBERSHIP_LOOKUPS: usize = 64;
+
+/// The limits enforced when decoding SBOR values from untrusted input, such as call
+/// arguments and substate writes from blueprints.
+pub const DEFAULT_DECODE_LIMITS: DecodeLimits = DecodeLimits {
+    max_depth: 64,
+    max_payload_length: 4 * 1024 * 1024,
+};
+
+/// The vault of the network treasury, which receives the network share of transaction fees.
+pub const SYS_TREASURY_VAULT: VaultId = (Hash([0u8; 32]), 2);
+
 pub const EXTRACT_ABI_CREDIT: u32 = 100_000_000;
 pub const PREVIEW_CREDIT: u32 = 100_000_000;
 pub const GENESIS_CREATION_CREDIT: u32 = 100_000_000;
@@ -7,6 +7,28 @@ pub struct REActor {
 }
 
 impl REActor {
+    /// Returns the package and component, if any, on whose behalf this actor makes calls.
+    pub fn origins(&self) -> Vec<CallerOrigin> {
+        match (&self.fn_identifier, &self.receiver) {
+            (
+                FnIdentifier::Scrypto {
+                    package_address, ..
+                },
+                Some(Receiver::Ref(RENodeId::Component(component_address))),
+            ) => vec![
+                CallerOrigin::Package(*package_address),
+                CallerOrigin::Component(*component_address),
+            ],
+            (
+                FnIdentifier::Scrypto {
+                    package_address, ..
+                },
+                _,
+            ) => vec![CallerOrigin::Package(*package_address)],
+            (FnIdentifier::Native(..), _) => Vec::new(),
+        }
+    }
+
     pub fn is_substate_readable(&self, substate_id: &SubstateId) -> bool {
         match &self.fn_identifier {
             FnIdentifier::Native(..) => true,
@@ -23,6 +23,9 @@ pub struct CallFrame {
     pub owned_heap_nodes: HashMap<RENodeId, HeapRootRENode>,
 
     pub auth_zone: AuthZone,
+
+    /// Execution checkpoints created by this frame, in creation order
+    pub checkpoints: Vec<CheckpointId>,
 }
 
 impl CallFrame {
@@ -39,6 +42,7 @@ impl CallFrame {
             node_refs: HashMap::new(),
             owned_heap_nodes: HashMap::new(),
             auth_zone: AuthZone::new(),
+            checkpoints: Vec::new(),
         }
     }
 
@@ -47,6 +51,7 @@ impl CallFrame {
         actor: REActor,
         owned_heap_nodes: HashMap<RENodeId, HeapRootRENode>,
         node_refs: HashMap<RENodeId, RENodePointer>,
+        is_barrier: bool,
         _system_api: &mut Y,
     ) -> Self
     where
@@ -55,7 +60,7 @@ impl CallFrame {
         I: WasmInstance,
         R: FeeReserve,
     {
-        let auth_zone = AuthZone::new();
+        let auth_zone = AuthZone::new_for_frame(actor.origins(), is_barrier);
 
         Self {
             depth,
@@ -63,17 +68,21 @@ impl CallFrame {
             node_refs,
             owned_heap_nodes,
             auth_zone,
+            checkpoints: Vec::new(),
         }
     }
 
-    pub fn drop_owned_values(&mut self) -> Result<(), RuntimeError> {
-        let values = self
+    /// Drops the nodes owned by this frame, returning their size.
+    pub fn drop_owned_values(&mut self) -> Result<usize, RuntimeError> {
+        let values: Vec<HeapRootRENode> = self
             .owned_heap_nodes
             .drain()
             .map(|(_id, value)| value)
             .collect();
+        let size = values.iter().map(|value| value.size()).sum();
         HeapRENode::drop_nodes(values)
-            .map_err(|e| RuntimeError::KernelError(KernelError::DropFailure(e)))
+            .map_err(|e| RuntimeError::KernelError(KernelError::DropFailure(e)))?;
+        Ok(size)
     }
 
     pub fn take_available_values(
@@ -41,9 +41,11 @@ pub enum KernelError {
     InvokeMethodInvalidReferencePass(RENodeId),
     InvokeMethodInvalidReferenceReturn(RENodeId),
     MaxCallDepthLimitReached,
+    MaxHeapSizeExceeded { size: usize, limit: usize },
     MethodNotFound(FnIdentifier),
     InvalidFnInput { fn_identifier: FnIdentifier },
     InvalidFnOutput { fn_identifier: FnIdentifier },
+    RandomBytesLengthExceeded { len: u32, limit: u32 },
 
     // ID allocation
     IdAllocationError(IdAllocationError),
@@ -65,12 +67,18 @@ pub enum KernelError {
     RENodeCreateNodeNotFound(RENodeId),
     RENodeAlreadyTouched,
     RENodeNotInTrack,
+    RENodeTypeMismatch(RENodeId),
 
     // Substate
     Reentrancy(SubstateId),
     SubstateReadNotReadable(REActor, SubstateId),
     SubstateWriteNotWriteable(REActor, SubstateId),
     SubstateReadSubstateNotFound(SubstateId),
+    SubstateNotDeclared(SubstateId),
+    SubstateTypeMismatch(SubstateId),
+    SubstateReadNotSupported(SubstateId),
+    SubstateWriteNotSupported(SubstateId),
+    NonFungibleRemoveNotSupported(NonFungibleId),
 
     // constraints
     ValueNotAllowed,
@@ -82,9 +90,14 @@ pub enum KernelError {
     CantMoveRestrictedProof,
     CantMoveWorktop,
     CantMoveAuthZone,
+    AuthZoneNotStashed,
     DropFailure(DropFailure),
 
     BlobNotFound(Hash),
+
+    // Checkpoint
+    CheckpointNotFound(CheckpointId),
+    CheckpointNotAllowed,
 }
 
 #[derive(Debug, Encode, Decode, TypeId)]
@@ -135,6 +148,8 @@ pub enum ApplicationError {
     WorktopError(WorktopError),
 
     AuthZoneError(AuthZoneError),
+
+    AccessControllerError(AccessControllerError),
 }
 
 #[derive(Debug, PartialEq, Encode, Decode, TypeId)]
@@ -147,6 +162,124 @@ pub enum DropFailure {
     Vault,
     Package,
     KeyValueStore,
+    AccessController,
+}
+
+/// A stable numeric identifier of an error, which wallets and SDKs can map to user-facing
+/// messages.
+///
+/// Codes are grouped by category (`1xxx` kernel, `2xxx` module, `3xxx` application and
+/// `4xxx` rejection); existing codes must never be renumbered.
+pub type ErrorCode = u32;
+
+impl RejectionError {
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            RejectionError::SuccessButFeeLoanNotRepaid => 4001,
+            RejectionError::ErrorBeforeFeeLoanRepaid(..) => 4002,
+        }
+    }
+
+    /// Returns the code of the runtime error which caused the rejection, if any.
+    pub fn cause_error_code(&self) -> Option<ErrorCode> {
+        match self {
+            RejectionError::SuccessButFeeLoanNotRepaid => None,
+            RejectionError::ErrorBeforeFeeLoanRepaid(e) => Some(e.error_code()),
+        }
+    }
+}
+
+impl RuntimeError {
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            RuntimeError::KernelError(e) => e.error_code(),
+            RuntimeError::ModuleError(e) => e.error_code(),
+            RuntimeError::ApplicationError(e) => e.error_code(),
+        }
+    }
+}
+
+impl KernelError {
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            KernelError::WasmError(..) => 1001,
+            KernelError::InvokeMethodInvalidReceiver(..) => 1002,
+            KernelError::InvokeMethodInvalidReferencePass(..) => 1003,
+            KernelError::InvokeMethodInvalidReferenceReturn(..) => 1004,
+            KernelError::MaxCallDepthLimitReached => 1005,
+            KernelError::MaxHeapSizeExceeded { .. } => 1006,
+            KernelError::MethodNotFound(..) => 1007,
+            KernelError::InvalidFnInput { .. } => 1008,
+            KernelError::InvalidFnOutput { .. } => 1009,
+            KernelError::RandomBytesLengthExceeded { .. } => 1010,
+            KernelError::IdAllocationError(..) => 1101,
+            KernelError::DecodeError(..) => 1201,
+            KernelError::BucketNotFound(..) => 1301,
+            KernelError::ProofNotFound(..) => 1302,
+            KernelError::PackageNotFound(..) => 1303,
+            KernelError::BlueprintNotFound(..) => 1304,
+            KernelError::ResourceManagerNotFound(..) => 1305,
+            KernelError::WorktopNotFound => 1306,
+            KernelError::RENodeNotFound(..) => 1307,
+            KernelError::StoredNodeRemoved(..) => 1308,
+            KernelError::RENodeGlobalizeTypeNotAllowed(..) => 1309,
+            KernelError::RENodeCreateInvalidPermission => 1310,
+            KernelError::RENodeCreateNodeNotFound(..) => 1311,
+            KernelError::RENodeAlreadyTouched => 1312,
+            KernelError::RENodeNotInTrack => 1313,
+            KernelError::RENodeTypeMismatch(..) => 1314,
+            KernelError::Reentrancy(..) => 1401,
+            KernelError::SubstateReadNotReadable(..) => 1402,
+            KernelError::SubstateWriteNotWriteable(..) => 1403,
+            KernelError::SubstateReadSubstateNotFound(..) => 1404,
+            KernelError::SubstateNotDeclared(..) => 1405,
+            KernelError::SubstateTypeMismatch(..) => 1406,
+            KernelError::SubstateReadNotSupported(..) => 1407,
+            KernelError::SubstateWriteNotSupported(..) => 1408,
+            KernelError::NonFungibleRemoveNotSupported(..) => 1409,
+            KernelError::ValueNotAllowed => 1501,
+            KernelError::BucketNotAllowed => 1502,
+            KernelError::ProofNotAllowed => 1503,
+            KernelError::VaultNotAllowed => 1504,
+            KernelError::KeyValueStoreNotAllowed => 1505,
+            KernelError::CantMoveLockedBucket => 1506,
+            KernelError::CantMoveRestrictedProof => 1507,
+            KernelError::CantMoveWorktop => 1508,
+            KernelError::CantMoveAuthZone => 1509,
+            KernelError::DropFailure(..) => 1510,
+            KernelError::AuthZoneNotStashed => 1511,
+            KernelError::BlobNotFound(..) => 1601,
+            KernelError::CheckpointNotFound(..) => 1701,
+            KernelError::CheckpointNotAllowed => 1702,
+        }
+    }
+}
+
+impl ModuleError {
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            ModuleError::AuthorizationError { .. } => 2001,
+            ModuleError::CostingError(..) => 2002,
+        }
+    }
+}
+
+impl ApplicationError {
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            ApplicationError::TransactionProcessorError(..) => 3001,
+            ApplicationError::PackageError(..) => 3002,
+            ApplicationError::SystemError(..) => 3003,
+            ApplicationError::ResourceManagerError(..) => 3004,
+            ApplicationError::ComponentError(..) => 3005,
+            ApplicationError::BucketError(..) => 3006,
+            ApplicationError::ProofError(..) => 3007,
+            ApplicationError::VaultError(..) => 3008,
+            ApplicationError::WorktopError(..) => 3009,
+            ApplicationError::AuthZoneError(..) => 3010,
+            ApplicationError::AccessControllerError(..) => 3011,
+        }
+    }
 }
 
 impl fmt::Display for RuntimeError {
@@ -154,3 +287,58 @@ impl fmt::Display for RuntimeError {
         write!(f, "{:?}", self)
     }
 }
+
+impl fmt::Display for KernelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl fmt::Display for ModuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl fmt::Display for ApplicationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for RejectionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RejectionError::SuccessButFeeLoanNotRepaid => None,
+            RejectionError::ErrorBeforeFeeLoanRepaid(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for RuntimeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RuntimeError::KernelError(e) => Some(e),
+            RuntimeError::ModuleError(e) => Some(e),
+            RuntimeError::ApplicationError(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for KernelError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            KernelError::WasmError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for ModuleError {}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for ApplicationError {}
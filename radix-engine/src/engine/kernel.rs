@@ -1,7 +1,9 @@
+use sbor::rust::mem;
 use transaction::errors::IdAllocationError;
 use transaction::model::Instruction;
 use transaction::validation::*;
 
+use crate::constants::MAX_RANDOM_BYTES_LENGTH;
 use crate::engine::*;
 use crate::fee::FeeReserve;
 use crate::model::*;
@@ -18,6 +20,9 @@ macro_rules! trace {
     };
 }
 
+/// The proofs and virtual proof resources of an auth zone set aside while a subintent runs
+type StashedAuthZone = (Vec<Proof>, BTreeSet<ResourceAddress>);
+
 pub struct Kernel<
     'g, // Lifetime of values outliving all frames
     's, // Substate store lifetime
@@ -31,10 +36,16 @@ pub struct Kernel<
 {
     /// The transaction hash
     transaction_hash: Hash,
+    /// The transaction signers, as placed in the initial auth zone
+    transaction_signers: Vec<NonFungibleAddress>,
     /// Blobs attached to the transaction
     blobs: &'g HashMap<Hash, Vec<u8>>,
     /// The max call depth
     max_depth: usize,
+    /// The max size of the nodes owned by all call frames
+    max_heap_size: usize,
+    /// The size of the nodes owned by all call frames, as last measured
+    heap_size: usize,
 
     /// State track
     track: &'g mut Track<'s, R>,
@@ -42,22 +53,34 @@ pub struct Kernel<
     wasm_engine: &'g mut W,
     /// WASM Instrumenter
     wasm_instrumenter: &'g mut WasmInstrumenter,
+    /// The modules instantiated by packages
+    package_module_cache: &'g mut PackageModuleCache,
     /// WASM metering params
     wasm_metering_params: WasmMeteringParams,
 
     /// ID allocator
     id_allocator: IdAllocator,
+    /// The index of the manifest instruction the transaction processor is running
+    instruction_index: u32,
 
     /// Execution trace
     execution_trace: &'g mut ExecutionTrace,
 
     /// Call frames
     call_frames: Vec<CallFrame>,
+    /// The proofs of the fee payer's signature, which are only usable to lock fees
+    fee_payer_auth_zone: AuthZone,
+    /// The auth zone contents set aside while a subintent runs, with the depth of the frame
+    /// running it
+    stashed_auth_zones: Vec<(usize, Vec<StashedAuthZone>)>,
 
     /// Kernel modules
     /// TODO: move execution trace and  authorization to modules
     modules: Vec<Box<dyn Module<R>>>,
 
+    /// Native function dispatch table
+    native_fns: NativeFnRegistry<Kernel<'g, 's, W, I, R>>,
+
     phantom: PhantomData<I>,
 }
 
@@ -72,63 +95,97 @@ where
         initial_proofs: Vec<NonFungibleAddress>,
         blobs: &'g HashMap<Hash, Vec<u8>>,
         max_depth: usize,
+        max_heap_size: usize,
         track: &'g mut Track<'s, R>,
         wasm_engine: &'g mut W,
         wasm_instrumenter: &'g mut WasmInstrumenter,
+        package_module_cache: &'g mut PackageModuleCache,
         wasm_metering_params: WasmMeteringParams,
         execution_trace: &'g mut ExecutionTrace,
         modules: Vec<Box<dyn Module<R>>>,
+        native_fns: NativeFnRegistry<Self>,
     ) -> Self {
         let frame = CallFrame::new_root();
         let mut kernel = Self {
             transaction_hash,
+            transaction_signers: initial_proofs.clone(),
             blobs,
             max_depth,
+            max_heap_size,
+            heap_size: 0,
             track,
             wasm_engine,
             wasm_instrumenter,
+            package_module_cache,
             wasm_metering_params,
             id_allocator: IdAllocator::new(IdSpace::Application),
+            instruction_index: 0,
             execution_trace,
             call_frames: vec![frame],
+            fee_payer_auth_zone: AuthZone::new(),
+            stashed_auth_zones: Vec::new(),
             modules,
+            native_fns,
             phantom: PhantomData,
         };
 
         // Initial authzone
         // TODO: Move into module initialization
-        let mut proofs_to_create = BTreeMap::<ResourceAddress, BTreeSet<NonFungibleId>>::new();
-        for non_fungible in initial_proofs {
-            proofs_to_create
-                .entry(non_fungible.resource_address())
-                .or_insert(BTreeSet::new())
-                .insert(non_fungible.non_fungible_id());
-        }
-        for (resource_address, non_fungible_ids) in proofs_to_create {
-            let bucket_id = match kernel
-                .node_create(HeapRENode::Bucket(Bucket::new(
-                    ResourceContainer::new_non_fungible(resource_address, non_fungible_ids),
-                )))
+        let proofs = kernel.create_signature_proofs(initial_proofs);
+        Self::current_frame_mut(&mut kernel.call_frames)
+            .auth_zone
+            .proofs
+            .extend(proofs);
+
+        kernel
+    }
+
+    /// Sets the fee payer, whose signature badge is only usable to lock fees from an account
+    /// with the instructions of the transaction.
+    pub fn set_fee_payer(&mut self, fee_payer: NonFungibleAddress) {
+        let proofs = self.create_signature_proofs(vec![fee_payer]);
+        self.fee_payer_auth_zone.proofs.extend(proofs);
+    }
+
+    /// Assumes any non-fungible of the resources to be proven in the root auth zone.
+    pub fn add_virtual_proof_resources(&mut self, resource_addresses: Vec<ResourceAddress>) {
+        self.call_frames[0]
+            .auth_zone
+            .virtual_proof_resources
+            .extend(resource_addresses);
+    }
+
+    /// Returns the requirements which were met only by the virtual proof resources.
+    pub fn virtually_proven(&self) -> Vec<HardResourceOrNonFungible> {
+        self.call_frames[0]
+            .auth_zone
+            .virtually_proven
+            .borrow()
+            .clone()
+    }
+
+    fn create_signature_proofs(&mut self, non_fungibles: Vec<NonFungibleAddress>) -> Vec<Proof> {
+        let badges = VirtualProofs::signer_badges(non_fungibles);
+        let mut proofs = Vec::new();
+        for container in VirtualProofs::create_containers(badges) {
+            let bucket_id = match self
+                .node_create(HeapRENode::Bucket(Bucket::new(container)))
                 .expect("Failed to create bucket")
             {
                 RENodeId::Bucket(bucket_id) => bucket_id,
                 _ => panic!("Expected Bucket RENodeId but received something else"),
             };
             let substate_id = SubstateId::Bucket(bucket_id);
-            let mut node_ref = kernel
+            let mut node_ref = self
                 .substate_borrow_mut(&substate_id)
                 .expect("Failed to borrow bucket substate");
-            let bucket = node_ref.bucket();
+            let bucket = node_ref.bucket().expect("Expected bucket substate");
             let proof = bucket
                 .create_proof(bucket_id)
                 .expect("Failed to create proof");
-            Self::current_frame_mut(&mut kernel.call_frames)
-                .auth_zone
-                .proofs
-                .push(proof);
+            proofs.push(proof);
         }
-
-        kernel
+        proofs
     }
 
     fn process_call_data(validated: &ScryptoValue) -> Result<(), RuntimeError> {
@@ -243,6 +300,11 @@ where
                 )?;
                 Ok(RENodeId::Component(component_address))
             }
+            HeapRENode::AccessController(..) => {
+                let component_address =
+                    id_allocator.new_access_controller_address(transaction_hash)?;
+                Ok(RENodeId::AccessController(component_address))
+            }
             HeapRENode::System(..) => {
                 panic!("Attempted to create System RENodeId");
             }
@@ -259,7 +321,15 @@ where
                 REActor {
                     receiver,
                     fn_identifier: FnIdentifier::Native(native_fn),
-                } => NativeInterpreter::run(receiver, auth_zone_frame_id, native_fn, input, self),
+                } => {
+                    let handler =
+                        self.native_fns
+                            .get(&native_fn)
+                            .ok_or(RuntimeError::KernelError(KernelError::MethodNotFound(
+                                FnIdentifier::Native(native_fn),
+                            )))?;
+                    handler(receiver, auth_zone_frame_id, native_fn, input, self)
+                }
                 REActor {
                     receiver,
                     fn_identifier:
@@ -283,10 +353,7 @@ where
                             )
                             .map_err(RuntimeError::ModuleError)?;
                         }
-                        let instrumented_code = self
-                            .wasm_instrumenter
-                            .instrument(package.code(), &self.wasm_metering_params);
-                        let mut instance = self.wasm_engine.instantiate(instrumented_code);
+                        let mut instance = self.instantiate_package(package_address, &package);
                         let blueprint_abi = package
                             .blueprint_abi(&blueprint_name)
                             .expect("Blueprint not found"); // TODO: assumption will break if auth module is optional
@@ -318,14 +385,21 @@ where
                         };
                         let mut runtime: Box<dyn WasmRuntime> =
                             Box::new(RadixEngineWasmRuntime::new(scrypto_actor, self));
-                        instance
-                            .invoke_export(&export_name, &input, &mut runtime)
-                            .map_err(|e| match e {
-                                InvokeError::Error(e) => {
-                                    RuntimeError::KernelError(KernelError::WasmError(e))
-                                }
-                                InvokeError::Downstream(runtime_error) => runtime_error,
-                            })?
+                        let result = instance.invoke_export(&export_name, &input, &mut runtime);
+                        drop(runtime);
+
+                        let current_frame = Self::current_frame(&self.call_frames);
+                        self.execution_trace.trace_wasm_memory_usage(
+                            current_frame.depth,
+                            current_frame.actor.fn_identifier.clone(),
+                            instance.peak_memory_pages(),
+                        );
+                        result.map_err(|e| match e {
+                            InvokeError::Error(e) => {
+                                RuntimeError::KernelError(KernelError::WasmError(e))
+                            }
+                            InvokeError::Downstream(runtime_error) => runtime_error,
+                        })?
                     };
 
                     let package = self
@@ -388,11 +462,133 @@ where
         Self::current_frame_mut(&mut self.call_frames)
             .auth_zone
             .clear();
-        Self::current_frame_mut(&mut self.call_frames).drop_owned_values()?;
+        let dropped_size = Self::current_frame_mut(&mut self.call_frames).drop_owned_values()?;
+        self.update_heap_size(0, dropped_size)?;
+
+        // Keep state changes made after the checkpoints of this frame
+        if let Some(checkpoint) = Self::current_frame(&self.call_frames).checkpoints.first() {
+            self.track.release_checkpoint(*checkpoint);
+        }
 
         Ok((output, received_values))
     }
 
+    fn write_value_internal(
+        &mut self,
+        substate_id: SubstateId,
+        value: ScryptoValue,
+    ) -> Result<(), RuntimeError> {
+        let (taken_nodes, missing_nodes) = self.take_value_nodes(&substate_id, &value)?;
+        let (pointer, current_value) =
+            Self::read_value_internal(&mut self.call_frames, self.track, &substate_id)?;
+        self.write_value_at(
+            pointer,
+            &current_value,
+            substate_id,
+            value,
+            taken_nodes,
+            missing_nodes,
+        )
+    }
+
+    /// Takes the nodes of a value about to be written from the current frame, returning them
+    /// along with the ids of those the frame doesn't own.
+    fn take_value_nodes(
+        &mut self,
+        substate_id: &SubstateId,
+        value: &ScryptoValue,
+    ) -> Result<(HashMap<RENodeId, HeapRootRENode>, HashSet<RENodeId>), RuntimeError> {
+        // TODO: Do this in a better way once references cleaned up
+        for component_address in &value.refed_component_addresses {
+            if !self
+                .track
+                .is_root(&SubstateId::ComponentInfo(*component_address))
+            {
+                return Err(RuntimeError::KernelError(KernelError::ValueNotAllowed));
+            }
+        }
+
+        // Take values from current frame
+        let node_ids = value.node_ids();
+        if node_ids.is_empty() {
+            return Ok((HashMap::new(), HashSet::new()));
+        }
+        if !SubstateProperties::can_own_nodes(substate_id) {
+            return Err(RuntimeError::KernelError(KernelError::ValueNotAllowed));
+        }
+        Self::current_frame_mut(&mut self.call_frames).take_available_values(node_ids, true)
+    }
+
+    /// Writes a value over the current one, which was read through the given pointer.
+    fn write_value_at(
+        &mut self,
+        pointer: RENodePointer,
+        current_value: &ScryptoValue,
+        substate_id: SubstateId,
+        value: ScryptoValue,
+        taken_nodes: HashMap<RENodeId, HeapRootRENode>,
+        missing_nodes: HashSet<RENodeId>,
+    ) -> Result<(), RuntimeError> {
+        let cur_children = current_value.node_ids();
+
+        // Fulfill method
+        verify_stored_value_update(&cur_children, &missing_nodes)?;
+
+        // TODO: verify against some schema
+
+        // Write values
+        let taken_size = taken_nodes.values().map(|node| node.size()).sum();
+        let mut node_ref = pointer.to_ref_mut(&mut self.call_frames, &mut self.track);
+        node_ref.write_value(substate_id, value, taken_nodes)?;
+
+        // The taken nodes are now part of the written node, which is measured again if on heap
+        self.update_heap_size(0, taken_size)?;
+        if let RENodePointer::Heap { frame_id, root, .. } = pointer {
+            self.resize_heap_node(frame_id, root)?;
+        }
+        Ok(())
+    }
+
+    fn verify_checkpoint_allowed(frame: &CallFrame) -> Result<(), RuntimeError> {
+        // Rolling back must not make the track diverge from the resources held on heap
+        if !frame.owned_heap_nodes.is_empty() || !frame.auth_zone.proofs.is_empty() {
+            return Err(RuntimeError::KernelError(KernelError::CheckpointNotAllowed));
+        }
+        Ok(())
+    }
+
+    /// Adjusts the heap size by the size of the nodes added to and removed from the heap, failing
+    /// if it then exceeds the max heap size.
+    fn update_heap_size(&mut self, added: usize, removed: usize) -> Result<(), RuntimeError> {
+        self.heap_size = (self.heap_size + added).saturating_sub(removed);
+        if self.heap_size > self.max_heap_size {
+            return Err(RuntimeError::KernelError(
+                KernelError::MaxHeapSizeExceeded {
+                    size: self.heap_size,
+                    limit: self.max_heap_size,
+                },
+            ));
+        }
+        Ok(())
+    }
+
+    /// Measures a node on heap again after it may have been updated, e.g. by a write or by a
+    /// method of a bucket or vault within it.
+    fn resize_heap_node(&mut self, frame_id: usize, root: RENodeId) -> Result<(), RuntimeError> {
+        let node = match self
+            .call_frames
+            .get_mut(frame_id)
+            .and_then(|frame| frame.owned_heap_nodes.get_mut(&root))
+        {
+            Some(node) => node,
+            // The node has since been consumed
+            None => return Ok(()),
+        };
+        let previous_size = node.resize();
+        let size = node.size();
+        self.update_heap_size(size, previous_size)
+    }
+
     fn current_frame_mut(call_frames: &mut Vec<CallFrame>) -> &mut CallFrame {
         call_frames.last_mut().expect("Current frame always exists")
     }
@@ -400,6 +596,29 @@ where
     fn current_frame(call_frames: &Vec<CallFrame>) -> &CallFrame {
         call_frames.last().expect("Current frame always exists")
     }
+
+    /// Instantiates the code of a package, reusing the module it instantiated last if it's still
+    /// compiled.
+    fn instantiate_package(&mut self, package_address: PackageAddress, package: &Package) -> I {
+        let metering_params_id = self.wasm_metering_params.identifier();
+        if let Some(instance) = self
+            .package_module_cache
+            .get(package_address, metering_params_id)
+            .and_then(|code_hash| self.wasm_engine.instantiate_cached(&code_hash))
+        {
+            return instance;
+        }
+
+        let instrumented_code = self
+            .wasm_instrumenter
+            .instrument(package.code(), &self.wasm_metering_params);
+        self.package_module_cache.insert(
+            package_address,
+            metering_params_id,
+            hash(instrumented_code),
+        );
+        self.wasm_engine.instantiate(instrumented_code)
+    }
 }
 
 impl<'g, 's, W, I, R> SystemApi<'s, W, I, R> for Kernel<'g, 's, W, I, R>
@@ -505,6 +724,9 @@ where
                         TrackError::Reentrancy => {
                             panic!("Package reentrancy error should never occur.")
                         }
+                        TrackError::NotDeclared => RuntimeError::KernelError(
+                            KernelError::SubstateNotDeclared(SubstateId::Package(*package_address)),
+                        ),
                         TrackError::StateTrackError(..) => panic!("Unexpected"),
                     })?;
                 locked_values.insert(SubstateId::Package(package_address.clone()));
@@ -542,7 +764,11 @@ where
             }
             let input: TransactionProcessorRunInput =
                 scrypto_decode(&input.raw).expect("Transaction processor received invalid input");
-            for instruction in &input.instructions {
+            let subintent_instructions = input
+                .subintents
+                .iter()
+                .flat_map(|subintent| subintent.instructions.iter());
+            for instruction in input.instructions.iter().chain(subintent_instructions) {
                 match instruction {
                     Instruction::CallFunction { args, .. }
                     | Instruction::CallMethod { args, .. } => {
@@ -601,6 +827,7 @@ where
                 },
                 next_owned_values,
                 next_frame_node_refs,
+                false,
                 self,
             );
             self.call_frames.push(frame);
@@ -697,6 +924,11 @@ where
 
         let mut locked_pointers = Vec::new();
         let mut next_frame_node_refs = HashMap::new();
+        // Calls into global components hide the auth zones of the caller's callers
+        let mut is_barrier = false;
+
+        // The receiver, if it's on heap, to be measured again once the method returns
+        let mut heap_receiver = None;
 
         // Authorization and state load
         let auth_zone_frame_id = match &receiver {
@@ -715,9 +947,10 @@ where
                     match node_id {
                         // Let these be globally accessible for now
                         // TODO: Remove when references cleaned up
-                        RENodeId::ResourceManager(..) | RENodeId::System => {
-                            RENodePointer::Store(*node_id)
-                        }
+                        RENodeId::ResourceManager(..)
+                        | RENodeId::AccessController(..)
+                        | RENodeId::Package(..)
+                        | RENodeId::System => RENodePointer::Store(*node_id),
                         _ => {
                             return Err(RuntimeError::KernelError(
                                 KernelError::InvokeMethodInvalidReceiver(*node_id),
@@ -726,6 +959,9 @@ where
                     }
                 };
 
+                is_barrier = matches!(node_id, RENodeId::Component(..))
+                    && matches!(node_pointer, RENodePointer::Store(..));
+
                 // Lock Primary Substate
                 let substate_id =
                     RENodeProperties::to_primary_substate_id(&fn_identifier, *node_id)?;
@@ -766,8 +1002,21 @@ where
                                 .map_err(RuntimeError::KernelError)?;
                             temporary_locks.push((node_pointer, temporary_substate_id, false));
 
+                            // Access rules are only needed for the authorization check below
+                            let temporary_substate_id =
+                                SubstateId::ComponentAccessRules(*component_address);
+                            node_pointer
+                                .acquire_lock(
+                                    temporary_substate_id.clone(),
+                                    false,
+                                    false,
+                                    &mut self.track,
+                                )
+                                .map_err(RuntimeError::KernelError)?;
+                            temporary_locks.push((node_pointer, temporary_substate_id, false));
+
                             let node_ref = node_pointer.to_ref(&self.call_frames, &mut self.track);
-                            let component = node_ref.component_info();
+                            let component = node_ref.component_info()?;
 
                             // Don't support traits yet
                             if !package_address.eq(&component.package_address()) {
@@ -783,7 +1032,24 @@ where
                         }
                         _ => panic!("Should not get here."),
                     },
-                    _ => {}
+                    FnIdentifier::Native(..) => match node_id {
+                        RENodeId::Component(component_address) => {
+                            // Native component methods operate on the access rules, but still
+                            // need the component info, e.g. to check the blueprint ABI
+                            let component_info_substate_id =
+                                SubstateId::ComponentInfo(*component_address);
+                            node_pointer
+                                .acquire_lock(
+                                    component_info_substate_id.clone(),
+                                    false,
+                                    false,
+                                    &mut self.track,
+                                )
+                                .map_err(RuntimeError::KernelError)?;
+                            locked_pointers.push((node_pointer, component_info_substate_id, false));
+                        }
+                        _ => {}
+                    },
                 };
 
                 // Lock Parent Substates
@@ -792,7 +1058,7 @@ where
                     RENodeId::Component(..) => {
                         let package_address = {
                             let node_ref = node_pointer.to_ref(&self.call_frames, &self.track);
-                            node_ref.component_info().package_address()
+                            node_ref.component_info()?.package_address()
                         };
                         let package_substate_id = SubstateId::Package(package_address);
                         let package_node_id = RENodeId::Package(package_address);
@@ -815,7 +1081,7 @@ where
                     RENodeId::Bucket(..) => {
                         let resource_address = {
                             let node_ref = node_pointer.to_ref(&self.call_frames, &self.track);
-                            node_ref.bucket().resource_address()
+                            node_ref.bucket()?.resource_address()
                         };
                         let resource_substate_id = SubstateId::ResourceManager(resource_address);
                         let resource_node_id = RENodeId::ResourceManager(resource_address);
@@ -834,7 +1100,7 @@ where
                     RENodeId::Vault(..) => {
                         let resource_address = {
                             let node_ref = node_pointer.to_ref(&self.call_frames, &self.track);
-                            node_ref.vault().resource_address()
+                            node_ref.vault()?.resource_address()
                         };
                         let resource_substate_id = SubstateId::ResourceManager(resource_address);
                         let resource_node_id = RENodeId::ResourceManager(resource_address);
@@ -850,6 +1116,16 @@ where
                         locked_pointers.push((resource_node_pointer, resource_substate_id, false));
                         next_frame_node_refs.insert(resource_node_id, resource_node_pointer);
                     }
+                    RENodeId::AccessController(..) => {
+                        // The controlled badge is only reachable through the access controller
+                        let vault_id = {
+                            let node_ref = node_pointer.to_ref(&self.call_frames, &self.track);
+                            node_ref.access_controller()?.vault_id()
+                        };
+                        let vault_node_id = RENodeId::Vault(vault_id);
+                        next_frame_node_refs
+                            .insert(vault_node_id, node_pointer.child(vault_node_id));
+                    }
                     _ => {}
                 }
 
@@ -906,7 +1182,9 @@ where
                     receiver.clone(),
                     &input,
                     node_pointer.clone(),
+                    is_barrier,
                     &mut self.call_frames,
+                    &self.fee_payer_auth_zone,
                     &mut self.track,
                 )?;
 
@@ -927,6 +1205,9 @@ where
                     node_pointer.release_lock(substate_id, write_through, &mut self.track);
                 }
 
+                if let RENodePointer::Heap { frame_id, root, .. } = node_pointer {
+                    heap_receiver = Some((frame_id, root));
+                }
                 next_frame_node_refs.insert(node_id.clone(), node_pointer.clone());
                 None
             }
@@ -973,6 +1254,7 @@ where
                 },
                 next_owned_values,
                 next_frame_node_refs,
+                is_barrier,
                 self,
             );
             self.call_frames.push(frame);
@@ -982,6 +1264,11 @@ where
         // Remove the last after clean-up
         self.call_frames.pop();
 
+        // Buckets and vaults grow and shrink through their methods
+        if let Some((frame_id, root)) = heap_receiver {
+            self.resize_heap_node(frame_id, root)?;
+        }
+
         // Release locked addresses
         for (node_pointer, substate_id, write_through) in locked_pointers {
             // TODO: refactor after introducing `Lock` representation.
@@ -1159,6 +1446,7 @@ where
                 "Attempt to drop node {:?}, which is not owned by current frame",
                 node_id
             )); // TODO: Assumption will break if auth is optional
+        self.update_heap_size(0, node.size())?;
 
         for m in &mut self.modules {
             m.post_sys_call(
@@ -1194,6 +1482,7 @@ where
                 KernelError::RENodeCreateNodeNotFound(missing_node),
             ));
         }
+        let taken_size = taken_root_nodes.values().map(|node| node.size()).sum();
         let mut child_nodes = HashMap::new();
         for (id, taken_root_node) in taken_root_nodes {
             child_nodes.extend(taken_root_node.to_nodes(id));
@@ -1202,13 +1491,12 @@ where
         // Insert node into heap
         let node_id = Self::new_node_id(&mut self.id_allocator, self.transaction_hash, &re_node)
             .map_err(|e| RuntimeError::KernelError(KernelError::IdAllocationError(e)))?;
-        let heap_root_node = HeapRootRENode {
-            root: re_node,
-            child_nodes,
-        };
+        let heap_root_node = HeapRootRENode::new(re_node, child_nodes);
+        let size = heap_root_node.size();
         Self::current_frame_mut(&mut self.call_frames)
             .owned_heap_nodes
             .insert(node_id, heap_root_node);
+        self.update_heap_size(size, taken_size)?;
 
         // TODO: Clean the following up
         match node_id {
@@ -1283,9 +1571,10 @@ where
         assert!(missing_nodes.is_empty());
         assert!(taken_nodes.len() == 1);
         let root_node = taken_nodes.into_values().nth(0).unwrap();
+        self.update_heap_size(0, root_node.size())?;
 
         let (substates, maybe_non_fungibles) = match root_node.root {
-            HeapRENode::Component(component, component_state) => {
+            HeapRENode::Component(component, component_state, access_rules) => {
                 let mut substates = HashMap::new();
                 let component_address = node_id.into();
                 substates.insert(
@@ -1296,6 +1585,10 @@ where
                     SubstateId::ComponentState(component_address),
                     Substate::ComponentState(component_state),
                 );
+                substates.insert(
+                    SubstateId::ComponentAccessRules(component_address),
+                    Substate::ComponentAccessRules(access_rules),
+                );
                 let mut visible_substates = HashSet::new();
                 visible_substates.insert(SubstateId::ComponentInfo(component_address));
                 (substates, None)
@@ -1318,6 +1611,15 @@ where
                 );
                 (substates, non_fungibles)
             }
+            HeapRENode::AccessController(access_controller) => {
+                let mut substates = HashMap::new();
+                let component_address: ComponentAddress = node_id.into();
+                substates.insert(
+                    SubstateId::AccessController(component_address),
+                    Substate::AccessController(access_controller),
+                );
+                (substates, None)
+            }
             _ => panic!("Not expected"),
         };
 
@@ -1330,17 +1632,23 @@ where
         for (id, value) in root_node.child_nodes.into_iter() {
             to_store_values.insert(id, value);
         }
-        insert_non_root_nodes(self.track, to_store_values);
+        insert_non_root_nodes(self.track, to_store_values)?;
 
         if let Some(non_fungibles) = maybe_non_fungibles {
             let resource_address: ResourceAddress = node_id.into();
             let parent_address = SubstateId::NonFungibleSpace(resource_address.clone());
             for (id, non_fungible) in non_fungibles {
-                self.track.set_key_value(
-                    parent_address.clone(),
-                    id.to_vec(),
-                    Substate::NonFungible(NonFungibleWrapper(Some(non_fungible))),
-                );
+                self.track
+                    .set_key_value(
+                        parent_address.clone(),
+                        id.to_vec(),
+                        Substate::NonFungible(Some(non_fungible)),
+                    )
+                    .map_err(|_| {
+                        RuntimeError::KernelError(KernelError::SubstateNotDeclared(
+                            key_value_substate_id(&parent_address, id.to_vec()),
+                        ))
+                    })?;
             }
         }
 
@@ -1452,7 +1760,7 @@ where
 
         // Write values
         let mut node_ref = pointer.to_ref_mut(&mut self.call_frames, &mut self.track);
-        node_ref.replace_value_with_default(&substate_id);
+        node_ref.replace_value_with_default(&substate_id)?;
 
         for m in &mut self.modules {
             m.post_sys_call(
@@ -1498,43 +1806,7 @@ where
             ));
         }
 
-        // TODO: Do this in a better way once references cleaned up
-        for component_address in &value.refed_component_addresses {
-            if !self
-                .track
-                .is_root(&SubstateId::ComponentInfo(*component_address))
-            {
-                return Err(RuntimeError::KernelError(KernelError::ValueNotAllowed));
-            }
-        }
-
-        // Take values from current frame
-        let (taken_nodes, missing_nodes) = {
-            let node_ids = value.node_ids();
-            if !node_ids.is_empty() {
-                if !SubstateProperties::can_own_nodes(&substate_id) {
-                    return Err(RuntimeError::KernelError(KernelError::ValueNotAllowed));
-                }
-
-                Self::current_frame_mut(&mut self.call_frames)
-                    .take_available_values(node_ids, true)?
-            } else {
-                (HashMap::new(), HashSet::new())
-            }
-        };
-
-        let (pointer, current_value) =
-            Self::read_value_internal(&mut self.call_frames, self.track, &substate_id)?;
-        let cur_children = current_value.node_ids();
-
-        // Fulfill method
-        verify_stored_value_update(&cur_children, &missing_nodes)?;
-
-        // TODO: verify against some schema
-
-        // Write values
-        let mut node_ref = pointer.to_ref_mut(&mut self.call_frames, &mut self.track);
-        node_ref.write_value(substate_id, value, taken_nodes);
+        self.write_value_internal(substate_id, value)?;
 
         for m in &mut self.modules {
             m.post_sys_call(
@@ -1600,6 +1872,30 @@ where
         Ok(self.transaction_hash)
     }
 
+    fn transaction_signers(&mut self) -> Result<Vec<NonFungibleAddress>, RuntimeError> {
+        for m in &mut self.modules {
+            m.pre_sys_call(
+                &mut self.track,
+                &mut self.call_frames,
+                SysCallInput::ReadTransactionSigners,
+            )
+            .map_err(RuntimeError::ModuleError)?;
+        }
+
+        for m in &mut self.modules {
+            m.post_sys_call(
+                &mut self.track,
+                &mut self.call_frames,
+                SysCallOutput::ReadTransactionSigners {
+                    signers: &self.transaction_signers,
+                },
+            )
+            .map_err(RuntimeError::ModuleError)?;
+        }
+
+        Ok(self.transaction_signers.clone())
+    }
+
     fn generate_uuid(&mut self) -> Result<u128, RuntimeError> {
         for m in &mut self.modules {
             m.pre_sys_call(
@@ -1625,6 +1921,143 @@ where
         Ok(uuid)
     }
 
+    fn set_instruction_index(&mut self, index: u32) -> Result<(), RuntimeError> {
+        self.instruction_index = index;
+        Ok(())
+    }
+
+    fn generate_random_bytes(&mut self, len: u32) -> Result<Vec<u8>, RuntimeError> {
+        if len > MAX_RANDOM_BYTES_LENGTH {
+            return Err(RuntimeError::KernelError(
+                KernelError::RandomBytesLengthExceeded {
+                    len,
+                    limit: MAX_RANDOM_BYTES_LENGTH,
+                },
+            ));
+        }
+
+        for m in &mut self.modules {
+            m.pre_sys_call(
+                &mut self.track,
+                &mut self.call_frames,
+                SysCallInput::GenerateRandomBytes { len },
+            )
+            .map_err(RuntimeError::ModuleError)?;
+        }
+
+        let seed = self
+            .id_allocator
+            .new_random_seed(self.transaction_hash, self.instruction_index)
+            .map_err(|e| RuntimeError::KernelError(KernelError::IdAllocationError(e)))?;
+        let mut bytes = Vec::with_capacity(len as usize);
+        let mut block = 0u32;
+        while bytes.len() < len as usize {
+            let mut data = seed.to_vec();
+            data.extend(block.to_le_bytes());
+            bytes.extend(hash(data).to_vec());
+            block += 1;
+        }
+        bytes.truncate(len as usize);
+
+        for m in &mut self.modules {
+            m.post_sys_call(
+                &mut self.track,
+                &mut self.call_frames,
+                SysCallOutput::GenerateRandomBytes { bytes: &bytes },
+            )
+            .map_err(RuntimeError::ModuleError)?;
+        }
+
+        Ok(bytes)
+    }
+
+    fn remaining_cost_units(&mut self) -> Result<u32, RuntimeError> {
+        for m in &mut self.modules {
+            m.pre_sys_call(
+                &mut self.track,
+                &mut self.call_frames,
+                SysCallInput::GetRemainingCostUnits,
+            )
+            .map_err(RuntimeError::ModuleError)?;
+        }
+
+        let remaining = self.track.fee_reserve.remaining();
+
+        for m in &mut self.modules {
+            m.post_sys_call(
+                &mut self.track,
+                &mut self.call_frames,
+                SysCallOutput::GetRemainingCostUnits { remaining },
+            )
+            .map_err(RuntimeError::ModuleError)?;
+        }
+
+        Ok(remaining)
+    }
+
+    fn compute_hash(
+        &mut self,
+        algorithm: HashAlgorithm,
+        data: &[u8],
+    ) -> Result<Hash, RuntimeError> {
+        for m in &mut self.modules {
+            m.pre_sys_call(
+                &mut self.track,
+                &mut self.call_frames,
+                SysCallInput::ComputeHash {
+                    algorithm: &algorithm,
+                    data,
+                },
+            )
+            .map_err(RuntimeError::ModuleError)?;
+        }
+
+        let hash = algorithm.hash(data);
+
+        for m in &mut self.modules {
+            m.post_sys_call(
+                &mut self.track,
+                &mut self.call_frames,
+                SysCallOutput::ComputeHash { hash: &hash },
+            )
+            .map_err(RuntimeError::ModuleError)?;
+        }
+
+        Ok(hash)
+    }
+
+    fn verify_signature(
+        &mut self,
+        message: &[u8],
+        public_key: PublicKey,
+        signature: Signature,
+    ) -> Result<bool, RuntimeError> {
+        for m in &mut self.modules {
+            m.pre_sys_call(
+                &mut self.track,
+                &mut self.call_frames,
+                SysCallInput::VerifySignature {
+                    message,
+                    public_key: &public_key,
+                },
+            )
+            .map_err(RuntimeError::ModuleError)?;
+        }
+
+        let is_valid = verify(message, &public_key, &signature);
+
+        for m in &mut self.modules {
+            m.post_sys_call(
+                &mut self.track,
+                &mut self.call_frames,
+                SysCallOutput::VerifySignature { is_valid },
+            )
+            .map_err(RuntimeError::ModuleError)?;
+        }
+
+        Ok(is_valid)
+    }
+
     fn emit_log(&mut self, level: Level, message: String) -> Result<(), RuntimeError> {
         for m in &mut self.modules {
             m.pre_sys_call(
@@ -1652,6 +2085,38 @@ where
         Ok(())
     }
 
+    fn emit_event(&mut self, name: String, data: Vec<u8>) -> Result<(), RuntimeError> {
+        for m in &mut self.modules {
+            m.pre_sys_call(
+                &mut self.track,
+                &mut self.call_frames,
+                SysCallInput::EmitEvent {
+                    name: &name,
+                    data: &data,
+                },
+            )
+            .map_err(RuntimeError::ModuleError)?;
+        }
+
+        let emitter = Self::current_frame(&self.call_frames).actor.origins().pop();
+        self.track.add_event(ApplicationEvent {
+            emitter,
+            name,
+            data,
+        });
+
+        for m in &mut self.modules {
+            m.post_sys_call(
+                &mut self.track,
+                &mut self.call_frames,
+                SysCallOutput::EmitEvent,
+            )
+            .map_err(RuntimeError::ModuleError)?;
+        }
+
+        Ok(())
+    }
+
     fn check_access_rule(
         &mut self,
         access_rule: scrypto::resource::AccessRule,
@@ -1687,7 +2152,16 @@ where
         let mut simulated_auth_zone = AuthZone::new_with_proofs(proofs);
 
         let method_authorization = convert(&Type::Unit, &ScryptoValue::unit(), &access_rule);
-        let is_authorized = method_authorization.check(&[&simulated_auth_zone]).is_ok();
+        let track = &mut self.track;
+        let is_authorized = method_authorization
+            .resolve_membership(
+                &[&simulated_auth_zone],
+                |kv_store_id, non_fungible_address| {
+                    AuthModule::is_member(kv_store_id, non_fungible_address, track)
+                },
+            )?
+            .check(&[&simulated_auth_zone])
+            .is_ok();
         simulated_auth_zone.clear();
 
         for m in &mut self.modules {
@@ -1704,6 +2178,190 @@ where
         Ok(is_authorized)
     }
 
+    fn kv_store_put_if_absent(
+        &mut self,
+        kv_store_id: KeyValueStoreId,
+        key: Vec<u8>,
+        value: ScryptoValue,
+    ) -> Result<bool, RuntimeError> {
+        let substate_id = SubstateId::KeyValueStoreEntry(kv_store_id, key);
+        for m in &mut self.modules {
+            m.pre_sys_call(
+                &mut self.track,
+                &mut self.call_frames,
+                SysCallInput::KeyValueStorePutIfAbsent {
+                    substate_id: &substate_id,
+                    value: &value,
+                },
+            )
+            .map_err(RuntimeError::ModuleError)?;
+        }
+
+        // Authorization
+        if !Self::current_frame(&self.call_frames)
+            .actor
+            .is_substate_writeable(&substate_id)
+        {
+            return Err(RuntimeError::KernelError(
+                KernelError::SubstateWriteNotWriteable(
+                    Self::current_frame(&self.call_frames).actor.clone(),
+                    substate_id,
+                ),
+            ));
+        }
+
+        let (pointer, current_value) =
+            Self::read_value_internal(&mut self.call_frames, self.track, &substate_id)?;
+        let inserted = match &current_value.dom {
+            Value::Option { value } => value.is_none(),
+            _ => {
+                return Err(RuntimeError::KernelError(
+                    KernelError::SubstateTypeMismatch(substate_id),
+                ))
+            }
+        };
+        if inserted {
+            let (taken_nodes, missing_nodes) = self.take_value_nodes(&substate_id, &value)?;
+            // The store itself can't be moved into one of its entries
+            let node_id = SubstateProperties::get_node_id(&substate_id);
+            if !Self::current_frame(&self.call_frames)
+                .node_refs
+                .contains_key(&node_id)
+            {
+                return Err(RuntimeError::KernelError(
+                    KernelError::SubstateReadSubstateNotFound(substate_id),
+                ));
+            }
+            self.write_value_at(
+                pointer,
+                &current_value,
+                substate_id,
+                value,
+                taken_nodes,
+                missing_nodes,
+            )?;
+        }
+
+        for m in &mut self.modules {
+            m.post_sys_call(
+                &mut self.track,
+                &mut self.call_frames,
+                SysCallOutput::KeyValueStorePutIfAbsent { inserted },
+            )
+            .map_err(RuntimeError::ModuleError)?;
+        }
+
+        Ok(inserted)
+    }
+
+    fn execution_checkpoint(&mut self) -> Result<CheckpointId, RuntimeError> {
+        for m in &mut self.modules {
+            m.pre_sys_call(
+                &mut self.track,
+                &mut self.call_frames,
+                SysCallInput::ExecutionCheckpoint,
+            )
+            .map_err(RuntimeError::ModuleError)?;
+        }
+
+        Self::verify_checkpoint_allowed(Self::current_frame(&self.call_frames))?;
+        let checkpoint = self
+            .track
+            .checkpoint()
+            .map_err(|_| RuntimeError::KernelError(KernelError::CheckpointNotAllowed))?;
+        Self::current_frame_mut(&mut self.call_frames)
+            .checkpoints
+            .push(checkpoint);
+
+        for m in &mut self.modules {
+            m.post_sys_call(
+                &mut self.track,
+                &mut self.call_frames,
+                SysCallOutput::ExecutionCheckpoint { checkpoint },
+            )
+            .map_err(RuntimeError::ModuleError)?;
+        }
+
+        Ok(checkpoint)
+    }
+
+    fn rollback_to(&mut self, checkpoint: CheckpointId) -> Result<(), RuntimeError> {
+        for m in &mut self.modules {
+            m.pre_sys_call(
+                &mut self.track,
+                &mut self.call_frames,
+                SysCallInput::RollbackTo {
+                    checkpoint: &checkpoint,
+                },
+            )
+            .map_err(RuntimeError::ModuleError)?;
+        }
+
+        let frame = Self::current_frame_mut(&mut self.call_frames);
+        if !frame.checkpoints.contains(&checkpoint) {
+            return Err(RuntimeError::KernelError(KernelError::CheckpointNotFound(
+                checkpoint,
+            )));
+        }
+        Self::verify_checkpoint_allowed(frame)?;
+        self.track.rollback_to(checkpoint).map_err(|e| match e {
+            CheckpointError::NotFound => {
+                RuntimeError::KernelError(KernelError::CheckpointNotFound(checkpoint))
+            }
+            CheckpointError::NotAllowed => {
+                RuntimeError::KernelError(KernelError::CheckpointNotAllowed)
+            }
+        })?;
+        Self::current_frame_mut(&mut self.call_frames)
+            .checkpoints
+            .retain(|c| *c < checkpoint);
+
+        for m in &mut self.modules {
+            m.post_sys_call(
+                &mut self.track,
+                &mut self.call_frames,
+                SysCallOutput::RollbackTo,
+            )
+            .map_err(RuntimeError::ModuleError)?;
+        }
+
+        Ok(())
+    }
+
+    fn auth_zone_stash(&mut self) -> Result<(), RuntimeError> {
+        let depth = self.call_frames.len() - 1;
+        let stashed = self
+            .call_frames
+            .iter_mut()
+            .map(|frame| {
+                (
+                    frame.auth_zone.drain(),
+                    mem::take(&mut frame.auth_zone.virtual_proof_resources),
+                )
+            })
+            .collect();
+        self.stashed_auth_zones.push((depth, stashed));
+        Ok(())
+    }
+
+    fn auth_zone_unstash(&mut self) -> Result<(), RuntimeError> {
+        let depth = self.call_frames.len() - 1;
+        match self.stashed_auth_zones.last() {
+            Some((stash_depth, _)) if *stash_depth == depth => {}
+            _ => return Err(RuntimeError::KernelError(KernelError::AuthZoneNotStashed)),
+        }
+        let (_, stashed) = self.stashed_auth_zones.pop().unwrap();
+
+        Self::current_frame_mut(&mut self.call_frames)
+            .auth_zone
+            .clear();
+        for (frame, (proofs, virtual_proof_resources)) in self.call_frames.iter_mut().zip(stashed) {
+            frame.auth_zone.proofs.extend(proofs);
+            frame.auth_zone.virtual_proof_resources = virtual_proof_resources;
+        }
+        Ok(())
+    }
+
     fn auth_zone(&mut self, frame_id: usize) -> &mut AuthZone {
         &mut self
             .call_frames
@@ -1711,4 +2369,14 @@ where
             .expect(&format!("CallFrame #{} not found", frame_id))
             .auth_zone
     }
+
+    fn worktop(&mut self) -> Option<&Worktop> {
+        Self::current_frame(&self.call_frames)
+            .owned_heap_nodes
+            .get(&RENodeId::Worktop)
+            .map(|node| match node.root() {
+                HeapRENode::Worktop(worktop) => worktop,
+                _ => panic!("Expected a worktop"),
+            })
+    }
 }
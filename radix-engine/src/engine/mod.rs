@@ -17,7 +17,7 @@ pub use call_frame::CallFrame;
 pub use errors::*;
 pub use kernel::*;
 pub use modules::*;
-pub use native_interpreter::NativeInterpreter;
+pub use native_interpreter::{NativeFn, NativeFnProvider, NativeFnRegistry};
 pub use node::*;
 pub use node_properties::*;
 pub use node_ref::*;
@@ -1,38 +1,89 @@
 use crate::engine::*;
-use crate::fee::FeeReserve;
+use crate::fee::{FeeReserve, SystemApiCostingEntry};
 use crate::model::*;
 use crate::types::*;
 
 pub struct AuthModule;
 
 impl AuthModule {
-    fn auth(
+    /// Collects the auth zones visible to a call, starting from the caller's zone.
+    ///
+    /// The zones of the call frames form a stack, in which each call into a global component
+    /// pushes a barrier. A call into a global component may only see the zones up to the first
+    /// barrier, while any other call may cross a single barrier and see exactly one zone beyond
+    /// it, e.g. so that a component can withdraw from its vaults using the proofs of its caller.
+    fn visible_auth_zones(call_frames: &[CallFrame], is_barrier: bool) -> Vec<&AuthZone> {
+        let mut crossed_barrier = false;
+        let mut auth_zones = Vec::new();
+        for frame in call_frames.iter().rev() {
+            auth_zones.push(&frame.auth_zone);
+            if crossed_barrier {
+                break;
+            }
+            if frame.auth_zone.barrier {
+                if is_barrier {
+                    break;
+                }
+                crossed_barrier = true;
+            }
+        }
+        auth_zones
+    }
+
+    /// Checks whether a non-fungible is listed in an on-ledger allow-list, charging for the
+    /// read of the entry like for any other substate read.
+    pub fn is_member<'s, R: FeeReserve>(
+        kv_store_id: KeyValueStoreId,
+        non_fungible_address: &NonFungibleAddress,
+        track: &mut Track<'s, R>,
+    ) -> Result<bool, RuntimeError> {
+        let cost = track
+            .fee_table
+            .system_api_cost(SystemApiCostingEntry::ReadSubstate { size: 0 });
+        track
+            .fee_reserve
+            .consume(cost, "read_allow_list", false)
+            .map_err(|e| RuntimeError::ModuleError(ModuleError::CostingError(e)))?;
+
+        let key = scrypto_encode(non_fungible_address);
+        let entry = RENodeRefMut::Track(track, RENodeId::KeyValueStore(kv_store_id))
+            .read_scrypto_value(&SubstateId::KeyValueStoreEntry(kv_store_id, key))?;
+        let entry: Option<ScryptoValue> = scrypto_decode(&entry.raw)
+            .map_err(|e| RuntimeError::KernelError(KernelError::DecodeError(e)))?;
+        Ok(match entry {
+            Some(value) => matches!(scrypto_decode::<bool>(&value.raw), Ok(true)),
+            None => false,
+        })
+    }
+
+    fn auth<'s, R: FeeReserve>(
         function: &FnIdentifier,
         method_auths: Vec<MethodAuthorization>,
+        is_barrier: bool,
         call_frames: &mut Vec<CallFrame>, // TODO remove this once heap is implemented
+        fee_payer_auth_zone: Option<&AuthZone>,
+        track: &mut Track<'s, R>,
     ) -> Result<(), RuntimeError> {
-        let mut auth_zones = vec![
-            &call_frames
-                .last()
-                .expect("Current call frame does not exist")
-                .auth_zone,
-        ];
-        // FIXME: This is wrong as it allows extern component calls to use caller's auth zone
-        // Also, need to add a test for this
-        if let Some(frame) = call_frames.iter().rev().nth(1) {
-            auth_zones.push(&frame.auth_zone);
+        let mut auth_zones = Self::visible_auth_zones(call_frames, is_barrier);
+        if let Some(fee_payer_auth_zone) = fee_payer_auth_zone {
+            auth_zones.push(fee_payer_auth_zone);
         }
 
         // Authorization check
         if !method_auths.is_empty() {
             for method_auth in method_auths {
-                method_auth.check(&auth_zones).map_err(|error| {
-                    RuntimeError::ModuleError(ModuleError::AuthorizationError {
-                        function: function.clone(),
-                        authorization: method_auth,
-                        error,
-                    })
-                })?;
+                method_auth
+                    .resolve_membership(&auth_zones, |kv_store_id, non_fungible_address| {
+                        Self::is_member(kv_store_id, non_fungible_address, track)
+                    })?
+                    .check(&auth_zones)
+                    .map_err(|error| {
+                        RuntimeError::ModuleError(ModuleError::AuthorizationError {
+                            function: function.clone(),
+                            authorization: method_auth,
+                            error,
+                        })
+                    })?;
             }
         }
 
@@ -44,7 +95,9 @@ impl AuthModule {
         receiver: Receiver,
         input: &ScryptoValue,
         node_pointer: RENodePointer,
+        is_barrier: bool,
         call_frames: &mut Vec<CallFrame>,
+        fee_payer_auth_zone: &AuthZone,
         track: &mut Track<'s, R>,
     ) -> Result<(), RuntimeError> {
         let auth = match (receiver, function) {
@@ -54,7 +107,7 @@ impl AuthModule {
             ) => {
                 let resource_address = {
                     let node_ref = node_pointer.to_ref(call_frames, track);
-                    node_ref.bucket().resource_address()
+                    node_ref.bucket()?.resource_address()
                 };
                 let resource_manager = track
                     .read_substate(SubstateId::ResourceManager(resource_address))
@@ -73,12 +126,54 @@ impl AuthModule {
             }
             (
                 Receiver::Ref(RENodeId::System),
-                FnIdentifier::Native(NativeFnIdentifier::System(SystemFnIdentifier::SetEpoch)),
+                FnIdentifier::Native(NativeFnIdentifier::System(
+                    SystemFnIdentifier::SetEpoch
+                    | SystemFnIdentifier::SetFeeTable
+                    | SystemFnIdentifier::SetCostUnitPrice,
+                )),
             ) => {
                 vec![MethodAuthorization::Protected(HardAuthRule::ProofRule(
                     HardProofRule::Require(HardResourceOrNonFungible::Resource(SYSTEM_TOKEN)),
                 ))]
             }
+            (
+                Receiver::Ref(RENodeId::Component(..)),
+                FnIdentifier::Native(NativeFnIdentifier::Component(
+                    ComponentFnIdentifier::SetAccessRule,
+                )),
+            ) => match node_pointer {
+                RENodePointer::Store(..) => {
+                    match scrypto_decode::<ComponentSetAccessRuleInput>(&input.raw) {
+                        Ok(input) => {
+                            let node_ref = node_pointer.to_ref(call_frames, track);
+                            vec![node_ref
+                                .component_access_rules()?
+                                .set_access_rule_authorization(input.index, &input.method)]
+                        }
+                        Err(_) => vec![MethodAuthorization::Unsupported],
+                    }
+                }
+                RENodePointer::Heap { .. } => vec![],
+            },
+            (
+                Receiver::Ref(RENodeId::Component(..)),
+                FnIdentifier::Native(NativeFnIdentifier::Component(
+                    ComponentFnIdentifier::SetRoleRule,
+                )),
+            ) => match node_pointer {
+                RENodePointer::Store(..) => {
+                    match scrypto_decode::<ComponentSetRoleRuleInput>(&input.raw) {
+                        Ok(input) => {
+                            let node_ref = node_pointer.to_ref(call_frames, track);
+                            vec![node_ref
+                                .component_access_rules()?
+                                .set_role_rule_authorization(input.index, &input.role)]
+                        }
+                        Err(_) => vec![MethodAuthorization::Unsupported],
+                    }
+                }
+                RENodePointer::Heap { .. } => vec![],
+            },
             (Receiver::Ref(RENodeId::Component(..)), FnIdentifier::Native(..)) => {
                 match node_pointer {
                     RENodePointer::Store(..) => vec![MethodAuthorization::DenyAll],
@@ -112,18 +207,39 @@ impl AuthModule {
 
                 {
                     let value_ref = node_pointer.to_ref(call_frames, track);
-                    let component = value_ref.component_info();
-                    let component_state = value_ref.component_state();
-                    component.method_authorization(component_state, &abi.structure, ident)
+                    let access_rules = value_ref.component_access_rules()?;
+                    let component_state = value_ref.component_state()?;
+                    access_rules.method_authorization(
+                        component_state,
+                        &abi.structure,
+                        ident,
+                        &fn_abi.roles,
+                    )
                 }
             }
+            (
+                Receiver::Ref(RENodeId::AccessController(..)),
+                FnIdentifier::Native(NativeFnIdentifier::AccessController(access_controller_fn)),
+            ) => {
+                let node_ref = node_pointer.to_ref(call_frames, track);
+                vec![node_ref
+                    .access_controller()?
+                    .get_auth(*access_controller_fn, &input)]
+            }
+            (
+                Receiver::Ref(RENodeId::Package(..)),
+                FnIdentifier::Native(NativeFnIdentifier::Package(package_fn)),
+            ) => {
+                let node_ref = node_pointer.to_ref(call_frames, track);
+                vec![node_ref.package()?.get_auth(*package_fn)]
+            }
             (
                 Receiver::Ref(RENodeId::Vault(..)),
                 FnIdentifier::Native(NativeFnIdentifier::Vault(vault_fn)),
             ) => {
                 let resource_address = {
                     let node_ref = node_pointer.to_ref(call_frames, track);
-                    node_ref.vault().resource_address()
+                    node_ref.vault()?.resource_address()
                 };
                 let resource_manager = track
                     .read_substate(SubstateId::ResourceManager(resource_address))
@@ -133,6 +249,35 @@ impl AuthModule {
             _ => vec![],
         };
 
-        Self::auth(function, auth, call_frames)
+        // The fee payer's badge is only usable by the transaction processor to lock fees from an account
+        let is_fee_lock = match function {
+            FnIdentifier::Scrypto {
+                package_address,
+                ident,
+                ..
+            } => {
+                *package_address == ACCOUNT_PACKAGE
+                    && (ident == "lock_fee" || ident == "lock_contingent_fee")
+                    && call_frames
+                        .last()
+                        .map(|frame| {
+                            frame.actor.fn_identifier
+                                == FnIdentifier::Native(NativeFnIdentifier::TransactionProcessor(
+                                    TransactionProcessorFnIdentifier::Run,
+                                ))
+                        })
+                        .unwrap_or(false)
+            }
+            _ => false,
+        };
+
+        Self::auth(
+            function,
+            auth,
+            is_barrier,
+            call_frames,
+            is_fee_lock.then(|| fee_payer_auth_zone),
+            track,
+        )
     }
 }
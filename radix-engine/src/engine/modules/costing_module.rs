@@ -6,11 +6,28 @@ use crate::types::*;
 #[derive(Default)]
 pub struct CostingModule;
 
-impl<R: FeeReserve> Module<R> for CostingModule {
-    fn pre_sys_call(
+fn consumed<R: FeeReserve>(track: &Track<R>) -> u32 {
+    track.fee_reserve.consumed_instant() + track.fee_reserve.consumed_deferred()
+}
+
+/// Attributes the cost units consumed since `consumed_before` to the current call frame.
+fn attribute_to_call_frame<R: FeeReserve>(
+    track: &mut Track<R>,
+    call_frames: &[CallFrame],
+    consumed_before: u32,
+) {
+    let n = consumed(track) - consumed_before;
+    if let (Some(frame), true) = (call_frames.last(), n > 0) {
+        track
+            .fee_reserve
+            .attribute_to_call_frame(n, frame.depth, &frame.actor.fn_identifier);
+    }
+}
+
+impl CostingModule {
+    fn charge_sys_call<R: FeeReserve>(
         &mut self,
         track: &mut Track<R>,
-        _heap: &mut Vec<CallFrame>,
         input: SysCallInput,
     ) -> Result<(), ModuleError> {
         match input {
@@ -118,6 +135,13 @@ impl<R: FeeReserve> Module<R> for CostingModule {
                                     loaded: false,
                                     size: 0,
                                 },
+                                RENodeId::AccessController(_) => {
+                                    SystemApiCostingEntry::BorrowNode {
+                                        // TODO: figure out loaded state and size
+                                        loaded: false,
+                                        size: 0,
+                                    }
+                                }
                                 RENodeId::System => SystemApiCostingEntry::BorrowNode {
                                     // TODO: figure out loaded state and size
                                     loaded: false,
@@ -142,7 +166,7 @@ impl<R: FeeReserve> Module<R> for CostingModule {
                     )
                     .map_err(ModuleError::CostingError)?;
             }
-            SysCallInput::CreateNode { .. } => {
+            SysCallInput::CreateNode { node } => {
                 // Costing
                 track
                     .fee_reserve
@@ -150,7 +174,7 @@ impl<R: FeeReserve> Module<R> for CostingModule {
                         track
                             .fee_table
                             .system_api_cost(SystemApiCostingEntry::CreateNode {
-                                size: 0, // TODO: get size of the value
+                                size: node.size() as u32,
                             }),
                         "create_node",
                         false,
@@ -213,6 +237,13 @@ impl<R: FeeReserve> Module<R> for CostingModule {
                                         size: 0,
                                     }
                                 }
+                                SubstateId::ComponentAccessRules(..) => {
+                                    SystemApiCostingEntry::BorrowSubstate {
+                                        // TODO: figure out loaded state and size
+                                        loaded: false,
+                                        size: 0,
+                                    }
+                                }
                                 SubstateId::KeyValueStoreSpace(_) => {
                                     SystemApiCostingEntry::BorrowSubstate {
                                         // TODO: figure out loaded state and size
@@ -253,6 +284,13 @@ impl<R: FeeReserve> Module<R> for CostingModule {
                                     loaded: false,
                                     size: 0,
                                 },
+                                SubstateId::AccessController(..) => {
+                                    SystemApiCostingEntry::BorrowSubstate {
+                                        // TODO: figure out loaded state and size
+                                        loaded: false,
+                                        size: 0,
+                                    }
+                                }
                                 SubstateId::System => SystemApiCostingEntry::BorrowSubstate {
                                     // TODO: figure out loaded state and size
                                     loaded: false,
@@ -302,6 +340,12 @@ impl<R: FeeReserve> Module<R> for CostingModule {
                                     SubstateId::ComponentState(_) => {
                                         SystemApiCostingEntry::ReturnSubstate { size: 0 }
                                     }
+                                    SubstateId::ComponentAccessRules(_) => {
+                                        SystemApiCostingEntry::ReturnSubstate { size: 0 }
+                                    }
+                                    SubstateId::AccessController(_) => {
+                                        SystemApiCostingEntry::ReturnSubstate { size: 0 }
+                                    }
                                     SubstateId::System => {
                                         SystemApiCostingEntry::ReturnSubstate { size: 0 }
                                     }
@@ -367,6 +411,21 @@ impl<R: FeeReserve> Module<R> for CostingModule {
                     )
                     .map_err(ModuleError::CostingError)?;
             }
+            SysCallInput::KeyValueStorePutIfAbsent { .. } => {
+                // Costing
+                track
+                    .fee_reserve
+                    .consume(
+                        track.fee_table.system_api_cost(
+                            SystemApiCostingEntry::KeyValueStorePutIfAbsent {
+                                size: 0, // TODO: get size of the value
+                            },
+                        ),
+                        "kv_store_put_if_absent",
+                        false,
+                    )
+                    .map_err(ModuleError::CostingError)?;
+            }
             SysCallInput::ReadTransactionHash => {
                 track
                     .fee_reserve
@@ -379,6 +438,18 @@ impl<R: FeeReserve> Module<R> for CostingModule {
                     )
                     .map_err(ModuleError::CostingError)?;
             }
+            SysCallInput::ReadTransactionSigners => {
+                track
+                    .fee_reserve
+                    .consume(
+                        track
+                            .fee_table
+                            .system_api_cost(SystemApiCostingEntry::ReadTransactionSigners),
+                        "read_transaction_signers",
+                        false,
+                    )
+                    .map_err(ModuleError::CostingError)?;
+            }
             SysCallInput::ReadBlob { .. } => {
                 track
                     .fee_reserve
@@ -403,6 +474,58 @@ impl<R: FeeReserve> Module<R> for CostingModule {
                     )
                     .map_err(ModuleError::CostingError)?;
             }
+            SysCallInput::GenerateRandomBytes { len } => {
+                track
+                    .fee_reserve
+                    .consume(
+                        track.fee_table.system_api_cost(
+                            SystemApiCostingEntry::GenerateRandomBytes { size: len },
+                        ),
+                        "generate_random_bytes",
+                        false,
+                    )
+                    .map_err(ModuleError::CostingError)?;
+            }
+            SysCallInput::GetRemainingCostUnits => {
+                track
+                    .fee_reserve
+                    .consume(
+                        track
+                            .fee_table
+                            .system_api_cost(SystemApiCostingEntry::ReadRemainingCostUnits),
+                        "read_remaining_cost_units",
+                        false,
+                    )
+                    .map_err(ModuleError::CostingError)?;
+            }
+            SysCallInput::ComputeHash { data, .. } => {
+                track
+                    .fee_reserve
+                    .consume(
+                        track
+                            .fee_table
+                            .system_api_cost(SystemApiCostingEntry::ComputeHash {
+                                size: data.len() as u32,
+                            }),
+                        "compute_hash",
+                        false,
+                    )
+                    .map_err(ModuleError::CostingError)?;
+            }
+            SysCallInput::VerifySignature { message, .. } => {
+                track
+                    .fee_reserve
+                    .consume(
+                        track
+                            .fee_table
+                            .system_api_cost(SystemApiCostingEntry::VerifySignature {
+                                size: message.len() as u32,
+                            }),
+                        "verify_signature",
+                        false,
+                    )
+                    .map_err(ModuleError::CostingError)?;
+            }
             SysCallInput::EmitLog { message, .. } => {
                 track
                     .fee_reserve
@@ -417,6 +540,20 @@ impl<R: FeeReserve> Module<R> for CostingModule {
                     )
                     .map_err(ModuleError::CostingError)?;
             }
+            SysCallInput::EmitEvent { name, data } => {
+                track
+                    .fee_reserve
+                    .consume(
+                        track
+                            .fee_table
+                            .system_api_cost(SystemApiCostingEntry::EmitEvent {
+                                size: (name.len() + data.len()) as u32,
+                            }),
+                        "emit_event",
+                        false,
+                    )
+                    .map_err(ModuleError::CostingError)?;
+            }
             SysCallInput::CheckAccessRule { proof_ids, .. } => {
                 // Costing
                 track
@@ -432,10 +569,48 @@ impl<R: FeeReserve> Module<R> for CostingModule {
                     )
                     .map_err(ModuleError::CostingError)?;
             }
+            SysCallInput::ExecutionCheckpoint => {
+                track
+                    .fee_reserve
+                    .consume(
+                        track
+                            .fee_table
+                            .system_api_cost(SystemApiCostingEntry::ExecutionCheckpoint),
+                        "execution_checkpoint",
+                        false,
+                    )
+                    .map_err(ModuleError::CostingError)?;
+            }
+            SysCallInput::RollbackTo { .. } => {
+                track
+                    .fee_reserve
+                    .consume(
+                        track
+                            .fee_table
+                            .system_api_cost(SystemApiCostingEntry::RollbackTo),
+                        "rollback_to",
+                        false,
+                    )
+                    .map_err(ModuleError::CostingError)?;
+            }
         }
 
         Ok(())
     }
+}
+
+impl<R: FeeReserve> Module<R> for CostingModule {
+    fn pre_sys_call(
+        &mut self,
+        track: &mut Track<R>,
+        heap: &mut Vec<CallFrame>,
+        input: SysCallInput,
+    ) -> Result<(), ModuleError> {
+        let consumed_before = consumed(track);
+        let result = self.charge_sys_call(track, input);
+        attribute_to_call_frame(track, heap, consumed_before);
+        result
+    }
 
     fn post_sys_call(
         &mut self,
@@ -449,29 +624,46 @@ impl<R: FeeReserve> Module<R> for CostingModule {
     fn on_wasm_instantiation(
         &mut self,
         track: &mut Track<R>,
-        _heap: &mut Vec<CallFrame>,
+        heap: &mut Vec<CallFrame>,
         code: &[u8],
     ) -> Result<(), ModuleError> {
-        track
+        let consumed_before = consumed(track);
+        let result = track
             .fee_reserve
             .consume(
                 track.fee_table.wasm_instantiation_per_byte() * code.len() as u32,
                 "instantiate_wasm",
                 false,
             )
-            .map_err(ModuleError::CostingError)
+            .map_err(ModuleError::CostingError);
+        attribute_to_call_frame(track, heap, consumed_before);
+        result
     }
 
     fn on_wasm_costing(
         &mut self,
         track: &mut Track<R>,
-        _heap: &mut Vec<CallFrame>,
+        heap: &mut Vec<CallFrame>,
         units: u32,
     ) -> Result<(), ModuleError> {
+        let consumed_before = consumed(track);
         track
             .fee_reserve
             .consume(units, "run_wasm", false)
-            .map_err(ModuleError::CostingError)
+            .map_err(ModuleError::CostingError)?;
+        attribute_to_call_frame(track, heap, consumed_before);
+
+        if let Some(REActor {
+            fn_identifier: FnIdentifier::Scrypto { .. },
+            receiver: Some(Receiver::Ref(RENodeId::Component(component_address))),
+        }) = heap.last().map(|frame| &frame.actor)
+        {
+            track
+                .fee_reserve
+                .attribute_to_component(units, *component_address);
+        }
+
+        Ok(())
     }
 
     fn on_lock_fee(
@@ -11,23 +11,47 @@ pub struct ResourceChange {
     pub amount: Decimal,
 }
 
+/// The peak memory used by a WASM invocation at a given call depth.
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode)]
+pub struct WasmMemoryUsage {
+    pub depth: usize,
+    pub fn_identifier: FnIdentifier,
+    pub peak_memory_pages: u32,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ExecutionTraceReceipt {
     pub resource_changes: Vec<ResourceChange>,
+    pub wasm_memory_usage: Vec<WasmMemoryUsage>,
 }
 
 #[derive(Debug)]
 pub struct ExecutionTrace {
     pub resource_changes: HashMap<ComponentAddress, HashMap<VaultId, (ResourceAddress, Decimal)>>,
+    pub wasm_memory_usage: Vec<WasmMemoryUsage>,
 }
 
 impl ExecutionTrace {
     pub fn new() -> ExecutionTrace {
         Self {
             resource_changes: HashMap::new(),
+            wasm_memory_usage: Vec::new(),
         }
     }
 
+    pub fn trace_wasm_memory_usage(
+        &mut self,
+        depth: usize,
+        fn_identifier: FnIdentifier,
+        peak_memory_pages: u32,
+    ) {
+        self.wasm_memory_usage.push(WasmMemoryUsage {
+            depth,
+            fn_identifier,
+            peak_memory_pages,
+        });
+    }
+
     pub fn trace_invoke_method<'s, R: FeeReserve>(
         &mut self,
         call_frames: &Vec<CallFrame>,
@@ -74,7 +98,7 @@ impl ExecutionTrace {
 
                         let vault_node_ref = node_pointer.to_ref(call_frames, track);
 
-                        let resource_address = vault_node_ref.vault().resource_address();
+                        let resource_address = vault_node_ref.vault()?.resource_address();
 
                         self.handle_vault_take(
                             &resource_address,
@@ -174,6 +198,9 @@ impl ExecutionTrace {
             })
             .filter(|el| !el.amount.is_zero())
             .collect();
-        ExecutionTraceReceipt { resource_changes }
+        ExecutionTraceReceipt {
+            resource_changes,
+            wasm_memory_usage: self.wasm_memory_usage,
+        }
     }
 }
@@ -95,21 +95,58 @@ impl<R: FeeReserve> Module<R> for LoggerModule {
             SysCallInput::TakeSubstate { substate_id } => {
                 log!(self, "Taking substate: substate_id = {:?}", substate_id);
             }
+            SysCallInput::KeyValueStorePutIfAbsent { substate_id, value } => {
+                log!(
+                    self,
+                    "Putting substate if absent: substate_id = {:?}, value = {:?}",
+                    substate_id,
+                    value
+                );
+            }
             SysCallInput::ReadTransactionHash => {
                 log!(self, "Reading transaction hash");
             }
+            SysCallInput::ReadTransactionSigners => {
+                log!(self, "Reading transaction signers");
+            }
             SysCallInput::ReadBlob { blob_hash } => {
                 log!(self, "Reading blob: {}", blob_hash);
             }
             SysCallInput::GenerateUuid => {
                 log!(self, "Generating UUID");
             }
+            SysCallInput::GenerateRandomBytes { len } => {
+                log!(self, "Generating random bytes: len = {}", len);
+            }
+            SysCallInput::GetRemainingCostUnits => {
+                log!(self, "Reading remaining cost units");
+            }
+            SysCallInput::ComputeHash { algorithm, data } => {
+                log!(
+                    self,
+                    "Computing hash: algorithm = {:?}, size = {}",
+                    algorithm,
+                    data.len()
+                );
+            }
+            SysCallInput::VerifySignature { public_key, .. } => {
+                log!(self, "Verifying signature: public_key = {:?}", public_key);
+            }
             SysCallInput::EmitLog { .. } => {
                 log!(self, "Emitting application log");
             }
+            SysCallInput::EmitEvent { name, .. } => {
+                log!(self, "Emitting event: {}", name);
+            }
             SysCallInput::CheckAccessRule { .. } => {
                 log!(self, "Checking access rule");
             }
+            SysCallInput::ExecutionCheckpoint => {
+                log!(self, "Creating execution checkpoint");
+            }
+            SysCallInput::RollbackTo { checkpoint } => {
+                log!(self, "Rolling back to checkpoint: {}", checkpoint);
+            }
         }
 
         Ok(())
@@ -139,11 +176,20 @@ impl<R: FeeReserve> Module<R> for LoggerModule {
             SysCallOutput::ReadSubstate { .. } => {}
             SysCallOutput::WriteSubstate { .. } => {}
             SysCallOutput::TakeSubstate { .. } => {}
+            SysCallOutput::KeyValueStorePutIfAbsent { .. } => {}
             SysCallOutput::ReadTransactionHash { .. } => {}
+            SysCallOutput::ReadTransactionSigners { .. } => {}
             SysCallOutput::ReadBlob { .. } => {}
             SysCallOutput::GenerateUuid { .. } => {}
+            SysCallOutput::GenerateRandomBytes { .. } => {}
+            SysCallOutput::GetRemainingCostUnits { .. } => {}
+            SysCallOutput::ComputeHash { .. } => {}
+            SysCallOutput::VerifySignature { .. } => {}
             SysCallOutput::EmitLog { .. } => {}
+            SysCallOutput::EmitEvent { .. } => {}
             SysCallOutput::CheckAccessRule { .. } => {}
+            SysCallOutput::ExecutionCheckpoint { .. } => {}
+            SysCallOutput::RollbackTo { .. } => {}
         }
 
         Ok(())
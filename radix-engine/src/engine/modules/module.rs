@@ -41,19 +41,44 @@ pub enum SysCallInput<'a> {
     TakeSubstate {
         substate_id: &'a SubstateId,
     },
+    KeyValueStorePutIfAbsent {
+        substate_id: &'a SubstateId,
+        value: &'a ScryptoValue,
+    },
     ReadTransactionHash,
+    ReadTransactionSigners,
     ReadBlob {
         blob_hash: &'a Hash,
     },
     GenerateUuid,
+    GenerateRandomBytes {
+        len: u32,
+    },
+    GetRemainingCostUnits,
+    ComputeHash {
+        algorithm: &'a HashAlgorithm,
+        data: &'a [u8],
+    },
+    VerifySignature {
+        message: &'a [u8],
+        public_key: &'a PublicKey,
+    },
     EmitLog {
         level: &'a Level,
         message: &'a String,
     },
+    EmitEvent {
+        name: &'a String,
+        data: &'a [u8],
+    },
     CheckAccessRule {
         access_rule: &'a AccessRule,
         proof_ids: &'a Vec<ProofId>,
     },
+    ExecutionCheckpoint,
+    RollbackTo {
+        checkpoint: &'a CheckpointId,
+    },
 }
 
 pub enum SysCallOutput<'a> {
@@ -68,11 +93,20 @@ pub enum SysCallOutput<'a> {
     ReadSubstate { value: &'a ScryptoValue },
     WriteSubstate,
     TakeSubstate { value: &'a ScryptoValue },
+    KeyValueStorePutIfAbsent { inserted: bool },
     ReadTransactionHash { hash: &'a Hash },
+    ReadTransactionSigners { signers: &'a [NonFungibleAddress] },
     ReadBlob { blob: &'a [u8] },
     GenerateUuid { uuid: u128 },
+    GenerateRandomBytes { bytes: &'a [u8] },
+    GetRemainingCostUnits { remaining: u32 },
+    ComputeHash { hash: &'a Hash },
+    VerifySignature { is_valid: bool },
     EmitLog,
+    EmitEvent,
     CheckAccessRule { result: bool },
+    ExecutionCheckpoint { checkpoint: CheckpointId },
+    RollbackTo,
 }
 
 pub trait Module<R: FeeReserve> {
@@ -4,8 +4,6 @@ use crate::model::*;
 use crate::types::*;
 use crate::wasm::*;
 
-pub struct NativeInterpreter;
-
 impl<E: Into<ApplicationError>> Into<RuntimeError> for InvokeError<E> {
     fn into(self) -> RuntimeError {
         match self {
@@ -75,80 +73,345 @@ impl Into<ApplicationError> for SystemError {
     }
 }
 
-impl NativeInterpreter {
-    pub fn run<'s, Y, W, I, R>(
-        receiver: Option<Receiver>,
-        auth_zone_frame_id: Option<usize>,
+impl Into<ApplicationError> for AccessControllerError {
+    fn into(self) -> ApplicationError {
+        ApplicationError::AccessControllerError(self)
+    }
+}
+
+/// A native function handler, invoked with the receiver and input of the call.
+pub type NativeFn<Y> = fn(
+    receiver: Option<Receiver>,
+    auth_zone_frame_id: Option<usize>,
+    fn_identifier: NativeFnIdentifier,
+    input: ScryptoValue,
+    system_api: &mut Y,
+) -> Result<ScryptoValue, RuntimeError>;
+
+/// Dispatch table of the native functions known to the engine.
+pub struct NativeFnRegistry<Y> {
+    handlers: HashMap<NativeFnIdentifier, NativeFn<Y>>,
+}
+
+impl<Y> NativeFnRegistry<Y> {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self {
+            handlers: HashMap::new(),
+        }
+    }
+
+    /// Registers the handler of a native function, returning the handler it replaces, if any.
+    pub fn register(
+        &mut self,
         fn_identifier: NativeFnIdentifier,
-        input: ScryptoValue,
-        system_api: &mut Y,
-    ) -> Result<ScryptoValue, RuntimeError>
+        handler: NativeFn<Y>,
+    ) -> Option<NativeFn<Y>> {
+        self.handlers.insert(fn_identifier, handler)
+    }
+
+    /// Returns the handler of a native function.
+    pub fn get(&self, fn_identifier: &NativeFnIdentifier) -> Option<NativeFn<Y>> {
+        self.handlers.get(fn_identifier).cloned()
+    }
+
+    /// Creates a registry with all the native blueprints of the engine.
+    pub fn standard<'s, W, I, R>() -> Self
     where
         Y: SystemApi<'s, W, I, R>,
         W: WasmEngine<I>,
         I: WasmInstance,
         R: FeeReserve,
     {
-        match (receiver, fn_identifier) {
-            (None, NativeFnIdentifier::TransactionProcessor(transaction_processor_fn)) => {
-                TransactionProcessor::static_main(transaction_processor_fn, input, system_api)
-                    .map_err(|e| e.into())
-            }
-            (None, NativeFnIdentifier::Package(package_fn)) => {
-                Package::static_main(package_fn, input, system_api).map_err(|e| e.into())
-            }
-            (None, NativeFnIdentifier::ResourceManager(resource_manager_fn)) => {
-                ResourceManager::static_main(resource_manager_fn, input, system_api)
+        let mut registry = Self::new();
+
+        let transaction_processor: NativeFn<Y> =
+            |receiver, _, fn_identifier, input, system_api| match (receiver, fn_identifier) {
+                (None, NativeFnIdentifier::TransactionProcessor(transaction_processor_fn)) => {
+                    TransactionProcessor::static_main(transaction_processor_fn, input, system_api)
+                        .map_err(|e| e.into())
+                }
+                _ => Err(method_not_found(fn_identifier)),
+            };
+        registry.register(
+            NativeFnIdentifier::TransactionProcessor(TransactionProcessorFnIdentifier::Run),
+            transaction_processor,
+        );
+
+        let package: NativeFn<Y> =
+            |receiver, _, fn_identifier, input, system_api| match (receiver, fn_identifier) {
+                (None, NativeFnIdentifier::Package(package_fn)) => {
+                    Package::static_main(package_fn, input, system_api).map_err(|e| e.into())
+                }
+                (
+                    Some(Receiver::Ref(RENodeId::Package(package_address))),
+                    NativeFnIdentifier::Package(package_fn),
+                ) => Package::main(package_address, package_fn, input, system_api)
+                    .map_err(|e| e.into()),
+                _ => Err(method_not_found(fn_identifier)),
+            };
+        for package_fn in [
+            PackageFnIdentifier::Publish,
+            PackageFnIdentifier::PublishWithOwner,
+            PackageFnIdentifier::UpdateMetadata,
+        ] {
+            registry.register(NativeFnIdentifier::Package(package_fn), package);
+        }
+
+        let resource_manager: NativeFn<Y> =
+            |receiver, _, fn_identifier, input, system_api| match (receiver, fn_identifier) {
+                (None, NativeFnIdentifier::ResourceManager(resource_manager_fn)) => {
+                    ResourceManager::static_main(resource_manager_fn, input, system_api)
+                        .map_err(|e| e.into())
+                }
+                (
+                    Some(Receiver::Ref(RENodeId::ResourceManager(resource_address))),
+                    NativeFnIdentifier::ResourceManager(resource_manager_fn),
+                ) => {
+                    ResourceManager::main(resource_address, resource_manager_fn, input, system_api)
+                        .map_err(|e| e.into())
+                }
+                _ => Err(method_not_found(fn_identifier)),
+            };
+        for resource_manager_fn in [
+            ResourceManagerFnIdentifier::Create,
+            ResourceManagerFnIdentifier::UpdateAuth,
+            ResourceManagerFnIdentifier::LockAuth,
+            ResourceManagerFnIdentifier::Mint,
+            ResourceManagerFnIdentifier::UpdateNonFungibleData,
+            ResourceManagerFnIdentifier::GetNonFungible,
+            ResourceManagerFnIdentifier::GetMetadata,
+            ResourceManagerFnIdentifier::GetResourceType,
+            ResourceManagerFnIdentifier::GetTotalSupply,
+            ResourceManagerFnIdentifier::UpdateMetadata,
+            ResourceManagerFnIdentifier::NonFungibleExists,
+            ResourceManagerFnIdentifier::CreateBucket,
+            ResourceManagerFnIdentifier::CreateVault,
+        ] {
+            registry.register(
+                NativeFnIdentifier::ResourceManager(resource_manager_fn),
+                resource_manager,
+            );
+        }
+
+        let bucket: NativeFn<Y> =
+            |receiver, _, fn_identifier, input, system_api| match (receiver, fn_identifier) {
+                (Some(Receiver::Consumed(node_id)), NativeFnIdentifier::Bucket(bucket_fn)) => {
+                    Bucket::consuming_main(node_id, bucket_fn, input, system_api)
+                        .map_err(|e| e.into())
+                }
+                (
+                    Some(Receiver::Ref(RENodeId::Bucket(bucket_id))),
+                    NativeFnIdentifier::Bucket(bucket_fn),
+                ) => Bucket::main(bucket_id, bucket_fn, input, system_api).map_err(|e| e.into()),
+                _ => Err(method_not_found(fn_identifier)),
+            };
+        for bucket_fn in [
+            BucketFnIdentifier::Burn,
+            BucketFnIdentifier::Take,
+            BucketFnIdentifier::TakeNonFungibles,
+            BucketFnIdentifier::Put,
+            BucketFnIdentifier::GetNonFungibleIds,
+            BucketFnIdentifier::GetAmount,
+            BucketFnIdentifier::GetResourceAddress,
+            BucketFnIdentifier::CreateProof,
+        ] {
+            registry.register(NativeFnIdentifier::Bucket(bucket_fn), bucket);
+        }
+
+        let proof: NativeFn<Y> =
+            |receiver, _, fn_identifier, input, system_api| match (receiver, fn_identifier) {
+                (Some(Receiver::Consumed(node_id)), NativeFnIdentifier::Proof(proof_fn)) => {
+                    Proof::main_consume(node_id, proof_fn, input, system_api).map_err(|e| e.into())
+                }
+                (
+                    Some(Receiver::Ref(RENodeId::Proof(proof_id))),
+                    NativeFnIdentifier::Proof(proof_fn),
+                ) => Proof::main(proof_id, proof_fn, input, system_api).map_err(|e| e.into()),
+                _ => Err(method_not_found(fn_identifier)),
+            };
+        for proof_fn in [
+            ProofFnIdentifier::Clone,
+            ProofFnIdentifier::GetAmount,
+            ProofFnIdentifier::GetNonFungibleIds,
+            ProofFnIdentifier::GetResourceAddress,
+            ProofFnIdentifier::Drop,
+        ] {
+            registry.register(NativeFnIdentifier::Proof(proof_fn), proof);
+        }
+
+        let auth_zone: NativeFn<Y> =
+            |receiver, auth_zone_frame_id, fn_identifier, input, system_api| match (
+                receiver,
+                fn_identifier,
+            ) {
+                (Some(Receiver::CurrentAuthZone), NativeFnIdentifier::AuthZone(auth_zone_fn)) => {
+                    AuthZone::main(
+                        auth_zone_frame_id.expect("AuthZone receiver frame id not specified"),
+                        auth_zone_fn,
+                        input,
+                        system_api,
+                    )
                     .map_err(|e| e.into())
-            }
-            (Some(Receiver::Consumed(node_id)), NativeFnIdentifier::Bucket(bucket_fn)) => {
-                Bucket::consuming_main(node_id, bucket_fn, input, system_api).map_err(|e| e.into())
-            }
-            (Some(Receiver::Consumed(node_id)), NativeFnIdentifier::Proof(proof_fn)) => {
-                Proof::main_consume(node_id, proof_fn, input, system_api).map_err(|e| e.into())
-            }
-            (Some(Receiver::CurrentAuthZone), NativeFnIdentifier::AuthZone(auth_zone_fn)) => {
-                AuthZone::main(
-                    auth_zone_frame_id.expect("AuthZone receiver frame id not specified"),
-                    auth_zone_fn,
+                }
+                _ => Err(method_not_found(fn_identifier)),
+            };
+        for auth_zone_fn in [
+            AuthZoneFnIdentifier::Pop,
+            AuthZoneFnIdentifier::Push,
+            AuthZoneFnIdentifier::CreateProof,
+            AuthZoneFnIdentifier::CreateProofByAmount,
+            AuthZoneFnIdentifier::CreateProofByIds,
+            AuthZoneFnIdentifier::Clear,
+        ] {
+            registry.register(NativeFnIdentifier::AuthZone(auth_zone_fn), auth_zone);
+        }
+
+        let worktop: NativeFn<Y> =
+            |receiver, _, fn_identifier, input, system_api| match (receiver, fn_identifier) {
+                (
+                    Some(Receiver::Ref(RENodeId::Worktop)),
+                    NativeFnIdentifier::Worktop(worktop_fn),
+                ) => Worktop::main(worktop_fn, input, system_api).map_err(|e| e.into()),
+                _ => Err(method_not_found(fn_identifier)),
+            };
+        for worktop_fn in [
+            WorktopFnIdentifier::TakeAll,
+            WorktopFnIdentifier::TakeAmount,
+            WorktopFnIdentifier::TakeNonFungibles,
+            WorktopFnIdentifier::Put,
+            WorktopFnIdentifier::AssertContains,
+            WorktopFnIdentifier::AssertContainsAmount,
+            WorktopFnIdentifier::AssertContainsNonFungibles,
+            WorktopFnIdentifier::Drain,
+            WorktopFnIdentifier::Contents,
+        ] {
+            registry.register(NativeFnIdentifier::Worktop(worktop_fn), worktop);
+        }
+
+        let vault: NativeFn<Y> =
+            |receiver, _, fn_identifier, input, system_api| match (receiver, fn_identifier) {
+                (
+                    Some(Receiver::Ref(RENodeId::Vault(vault_id))),
+                    NativeFnIdentifier::Vault(vault_fn),
+                ) => Vault::main(vault_id, vault_fn, input, system_api).map_err(|e| e.into()),
+                _ => Err(method_not_found(fn_identifier)),
+            };
+        for vault_fn in [
+            VaultFnIdentifier::Take,
+            VaultFnIdentifier::LockFee,
+            VaultFnIdentifier::LockContingentFee,
+            VaultFnIdentifier::Put,
+            VaultFnIdentifier::TakeNonFungibles,
+            VaultFnIdentifier::GetAmount,
+            VaultFnIdentifier::GetResourceAddress,
+            VaultFnIdentifier::GetNonFungibleIds,
+            VaultFnIdentifier::CreateProof,
+            VaultFnIdentifier::CreateProofByAmount,
+            VaultFnIdentifier::CreateProofByIds,
+        ] {
+            registry.register(NativeFnIdentifier::Vault(vault_fn), vault);
+        }
+
+        let component: NativeFn<Y> =
+            |receiver, _, fn_identifier, input, system_api| match (receiver, fn_identifier) {
+                (
+                    Some(Receiver::Ref(RENodeId::Component(component_address))),
+                    NativeFnIdentifier::Component(component_fn),
+                ) => ComponentInfo::main(component_address, component_fn, input, system_api)
+                    .map_err(|e| e.into()),
+                _ => Err(method_not_found(fn_identifier)),
+            };
+        for component_fn in [
+            ComponentFnIdentifier::AddAccessCheck,
+            ComponentFnIdentifier::SetAccessRule,
+            ComponentFnIdentifier::SetRoleRule,
+        ] {
+            registry.register(NativeFnIdentifier::Component(component_fn), component);
+        }
+
+        let system: NativeFn<Y> =
+            |receiver, _, fn_identifier, input, system_api| match (receiver, fn_identifier) {
+                (Some(Receiver::Ref(RENodeId::System)), NativeFnIdentifier::System(system_fn)) => {
+                    System::main(system_fn, input, system_api).map_err(|e| e.into())
+                }
+                _ => Err(method_not_found(fn_identifier)),
+            };
+        for system_fn in [
+            SystemFnIdentifier::GetTransactionHash,
+            SystemFnIdentifier::GetCurrentEpoch,
+            SystemFnIdentifier::SetEpoch,
+            SystemFnIdentifier::GetTransactionSigners,
+            SystemFnIdentifier::SetFeeTable,
+            SystemFnIdentifier::GetCostUnitPrice,
+            SystemFnIdentifier::SetCostUnitPrice,
+        ] {
+            registry.register(NativeFnIdentifier::System(system_fn), system);
+        }
+
+        let access_controller: NativeFn<Y> =
+            |receiver, _, fn_identifier, input, system_api| match (receiver, fn_identifier) {
+                (None, NativeFnIdentifier::AccessController(access_controller_fn)) => {
+                    AccessController::static_main(access_controller_fn, input, system_api)
+                        .map_err(|e| e.into())
+                }
+                (
+                    Some(Receiver::Ref(RENodeId::AccessController(component_address))),
+                    NativeFnIdentifier::AccessController(access_controller_fn),
+                ) => AccessController::main(
+                    component_address,
+                    access_controller_fn,
                     input,
                     system_api,
                 )
-                .map_err(|e| e.into())
-            }
-            (
-                Some(Receiver::Ref(RENodeId::Bucket(bucket_id))),
-                NativeFnIdentifier::Bucket(bucket_fn),
-            ) => Bucket::main(bucket_id, bucket_fn, input, system_api).map_err(|e| e.into()),
-            (
-                Some(Receiver::Ref(RENodeId::Proof(proof_id))),
-                NativeFnIdentifier::Proof(proof_fn),
-            ) => Proof::main(proof_id, proof_fn, input, system_api).map_err(|e| e.into()),
-            (Some(Receiver::Ref(RENodeId::Worktop)), NativeFnIdentifier::Worktop(worktop_fn)) => {
-                Worktop::main(worktop_fn, input, system_api).map_err(|e| e.into())
-            }
-            (
-                Some(Receiver::Ref(RENodeId::Vault(vault_id))),
-                NativeFnIdentifier::Vault(vault_fn),
-            ) => Vault::main(vault_id, vault_fn, input, system_api).map_err(|e| e.into()),
-            (
-                Some(Receiver::Ref(RENodeId::Component(component_address))),
-                NativeFnIdentifier::Component(component_fn),
-            ) => ComponentInfo::main(component_address, component_fn, input, system_api)
-                .map_err(|e| e.into()),
-            (
-                Some(Receiver::Ref(RENodeId::ResourceManager(resource_address))),
-                NativeFnIdentifier::ResourceManager(resource_manager_fn),
-            ) => ResourceManager::main(resource_address, resource_manager_fn, input, system_api)
                 .map_err(|e| e.into()),
-            (Some(Receiver::Ref(RENodeId::System)), NativeFnIdentifier::System(system_fn)) => {
-                System::main(system_fn, input, system_api).map_err(|e| e.into())
-            }
-            _ => {
-                return Err(RuntimeError::KernelError(KernelError::MethodNotFound(
-                    FnIdentifier::Native(fn_identifier.clone()),
-                )))
-            }
+                _ => Err(method_not_found(fn_identifier)),
+            };
+        for access_controller_fn in [
+            AccessControllerFnIdentifier::Create,
+            AccessControllerFnIdentifier::CreateProof,
+            AccessControllerFnIdentifier::InitiateRecovery,
+            AccessControllerFnIdentifier::QuickConfirmRecovery,
+            AccessControllerFnIdentifier::TimedConfirmRecovery,
+            AccessControllerFnIdentifier::CancelRecovery,
+            AccessControllerFnIdentifier::LockPrimaryRole,
+            AccessControllerFnIdentifier::UnlockPrimaryRole,
+        ] {
+            registry.register(
+                NativeFnIdentifier::AccessController(access_controller_fn),
+                access_controller,
+            );
         }
+
+        registry
+    }
+}
+
+/// A source of native functions which an executor registers on top of the standard ones,
+/// either adding handlers or replacing those of the engine.
+pub trait NativeFnProvider {
+    fn register<'s, Y, W, I, R>(&self, registry: &mut NativeFnRegistry<Y>)
+    where
+        Y: SystemApi<'s, W, I, R>,
+        W: WasmEngine<I>,
+        I: WasmInstance,
+        R: FeeReserve;
+}
+
+/// Provides no native functions beyond the standard ones.
+impl NativeFnProvider for () {
+    fn register<'s, Y, W, I, R>(&self, _registry: &mut NativeFnRegistry<Y>)
+    where
+        Y: SystemApi<'s, W, I, R>,
+        W: WasmEngine<I>,
+        I: WasmInstance,
+        R: FeeReserve,
+    {
     }
 }
+
+fn method_not_found(fn_identifier: NativeFnIdentifier) -> RuntimeError {
+    RuntimeError::KernelError(KernelError::MethodNotFound(FnIdentifier::Native(
+        fn_identifier,
+    )))
+}
@@ -10,10 +10,14 @@ pub enum Substate {
     Resource(ResourceManager),
     ComponentInfo(ComponentInfo),
     ComponentState(ComponentState),
+    ComponentAccessRules(ComponentAccessRules),
     Package(Package),
     Vault(Vault),
-    NonFungible(NonFungibleWrapper),
-    KeyValueStoreEntry(KeyValueStoreEntryWrapper),
+    AccessController(AccessController),
+    /// `None` if the non-fungible has been deleted.
+    NonFungible(Option<NonFungible>),
+    /// `None` if the entry has been deleted.
+    KeyValueStoreEntry(Option<ScryptoValue>),
 }
 
 impl Substate {
@@ -81,6 +85,14 @@ impl Substate {
         }
     }
 
+    pub fn component_access_rules(&self) -> &ComponentAccessRules {
+        if let Substate::ComponentAccessRules(access_rules) = self {
+            access_rules
+        } else {
+            panic!("Not component access rules");
+        }
+    }
+
     pub fn component_mut(&mut self) -> &mut ComponentInfo {
         if let Substate::ComponentInfo(component) = self {
             component
@@ -97,7 +109,15 @@ impl Substate {
         }
     }
 
-    pub fn non_fungible(&self) -> &NonFungibleWrapper {
+    pub fn access_controller(&self) -> &AccessController {
+        if let Substate::AccessController(access_controller) = self {
+            access_controller
+        } else {
+            panic!("Not an access controller");
+        }
+    }
+
+    pub fn non_fungible(&self) -> &Option<NonFungible> {
         if let Substate::NonFungible(non_fungible) = self {
             non_fungible
         } else {
@@ -105,7 +125,7 @@ impl Substate {
         }
     }
 
-    pub fn kv_entry(&self) -> &KeyValueStoreEntryWrapper {
+    pub fn kv_entry(&self) -> &Option<ScryptoValue> {
         if let Substate::KeyValueStoreEntry(kv_entry) = self {
             kv_entry
         } else {
@@ -138,27 +158,27 @@ impl Into<Substate> for ComponentState {
     }
 }
 
-impl Into<Substate> for ResourceManager {
+impl Into<Substate> for ComponentAccessRules {
     fn into(self) -> Substate {
-        Substate::Resource(self)
+        Substate::ComponentAccessRules(self)
     }
 }
 
-impl Into<Substate> for Vault {
+impl Into<Substate> for ResourceManager {
     fn into(self) -> Substate {
-        Substate::Vault(self)
+        Substate::Resource(self)
     }
 }
 
-impl Into<Substate> for NonFungibleWrapper {
+impl Into<Substate> for Vault {
     fn into(self) -> Substate {
-        Substate::NonFungible(self)
+        Substate::Vault(self)
     }
 }
 
-impl Into<Substate> for KeyValueStoreEntryWrapper {
+impl Into<Substate> for AccessController {
     fn into(self) -> Substate {
-        Substate::KeyValueStoreEntry(self)
+        Substate::AccessController(self)
     }
 }
 
@@ -182,6 +202,16 @@ impl Into<ComponentState> for Substate {
     }
 }
 
+impl Into<ComponentAccessRules> for Substate {
+    fn into(self) -> ComponentAccessRules {
+        if let Substate::ComponentAccessRules(access_rules) = self {
+            access_rules
+        } else {
+            panic!("Not component access rules");
+        }
+    }
+}
+
 impl Into<ResourceManager> for Substate {
     fn into(self) -> ResourceManager {
         if let Substate::Resource(resource_manager) = self {
@@ -202,22 +232,32 @@ impl Into<Package> for Substate {
     }
 }
 
-impl Into<NonFungibleWrapper> for Substate {
-    fn into(self) -> NonFungibleWrapper {
+impl Into<Option<NonFungible>> for Substate {
+    fn into(self) -> Option<NonFungible> {
         if let Substate::NonFungible(non_fungible) = self {
             non_fungible
         } else {
-            panic!("Not a non-fungible wrapper");
+            panic!("Not a non-fungible");
         }
     }
 }
 
-impl Into<KeyValueStoreEntryWrapper> for Substate {
-    fn into(self) -> KeyValueStoreEntryWrapper {
+impl Into<Option<ScryptoValue>> for Substate {
+    fn into(self) -> Option<ScryptoValue> {
         if let Substate::KeyValueStoreEntry(kv_entry) = self {
             kv_entry
         } else {
-            panic!("Not a key value store entry wrapper");
+            panic!("Not a key value store entry");
+        }
+    }
+}
+
+impl Into<AccessController> for Substate {
+    fn into(self) -> AccessController {
+        if let Substate::AccessController(access_controller) = self {
+            access_controller
+        } else {
+            panic!("Not an access controller");
         }
     }
 }
@@ -238,17 +278,18 @@ pub enum HeapRENode {
     Proof(Proof),
     Vault(Vault),
     KeyValueStore(HeapKeyValueStore),
-    Component(ComponentInfo, ComponentState),
+    Component(ComponentInfo, ComponentState, ComponentAccessRules),
     Worktop(Worktop),
     Package(Package),
     Resource(ResourceManager, Option<HashMap<NonFungibleId, NonFungible>>),
+    AccessController(AccessController),
     System(System),
 }
 
 impl HeapRENode {
     pub fn get_child_nodes(&self) -> Result<HashSet<RENodeId>, RuntimeError> {
         match self {
-            HeapRENode::Component(_, component_state) => {
+            HeapRENode::Component(_, component_state, _) => {
                 let value = ScryptoValue::from_slice(component_state.state())
                     .map_err(|e| RuntimeError::KernelError(KernelError::DecodeError(e)))?;
                 Ok(value.node_ids())
@@ -266,10 +307,50 @@ impl HeapRENode {
             }
             HeapRENode::Vault(..) => Ok(HashSet::new()),
             HeapRENode::Worktop(..) => Ok(HashSet::new()),
+            HeapRENode::AccessController(access_controller) => {
+                let mut child_nodes = HashSet::new();
+                child_nodes.insert(RENodeId::Vault(access_controller.vault_id()));
+                Ok(child_nodes)
+            }
             HeapRENode::System(..) => Ok(HashSet::new()),
         }
     }
 
+    /// Returns the approximate serialized size of this node, which is retained on heap for as
+    /// long as the node is owned by a call frame.
+    pub fn size(&self) -> usize {
+        match self {
+            HeapRENode::Bucket(bucket) => bucket.size(),
+            HeapRENode::Proof(proof) => proof.size(),
+            HeapRENode::Vault(vault) => scrypto_encode(vault).len(),
+            HeapRENode::KeyValueStore(kv_store) => kv_store
+                .store
+                .iter()
+                .map(|(key, value)| key.len() + value.raw.len())
+                .sum(),
+            HeapRENode::Component(component_info, component_state, access_rules) => {
+                scrypto_encode(component_info).len()
+                    + component_state.state().len()
+                    + scrypto_encode(access_rules).len()
+            }
+            HeapRENode::Worktop(worktop) => worktop.size(),
+            HeapRENode::Package(package) => package.size(),
+            HeapRENode::Resource(resource_manager, non_fungibles) => {
+                let mut size = scrypto_encode(resource_manager).len();
+                if let Some(non_fungibles) = non_fungibles {
+                    for (id, non_fungible) in non_fungibles {
+                        size += id.to_vec().len() + scrypto_encode(non_fungible).len();
+                    }
+                }
+                size
+            }
+            HeapRENode::AccessController(access_controller) => {
+                scrypto_encode(access_controller).len()
+            }
+            HeapRENode::System(system) => scrypto_encode(system).len(),
+        }
+    }
+
     pub fn system(&self) -> &System {
         match self {
             HeapRENode::System(system) => system,
@@ -335,18 +416,39 @@ impl HeapRENode {
 
     pub fn component_state(&self) -> &ComponentState {
         match self {
-            HeapRENode::Component(_, component_state) => component_state,
+            HeapRENode::Component(_, component_state, _) => component_state,
+            _ => panic!("Expected to be a store"),
+        }
+    }
+
+    pub fn component_access_rules(&self) -> &ComponentAccessRules {
+        match self {
+            HeapRENode::Component(.., access_rules) => access_rules,
             _ => panic!("Expected to be a store"),
         }
     }
 
     pub fn component_state_mut(&mut self) -> &mut ComponentState {
         match self {
-            HeapRENode::Component(_, component_state) => component_state,
+            HeapRENode::Component(_, component_state, _) => component_state,
             _ => panic!("Expected to be a store"),
         }
     }
 
+    pub fn access_controller(&self) -> &AccessController {
+        match self {
+            HeapRENode::AccessController(access_controller) => access_controller,
+            _ => panic!("Expected to be an access controller"),
+        }
+    }
+
+    pub fn access_controller_mut(&mut self) -> &mut AccessController {
+        match self {
+            HeapRENode::AccessController(access_controller) => access_controller,
+            _ => panic!("Expected to be an access controller"),
+        }
+    }
+
     pub fn kv_store(&self) -> &HeapKeyValueStore {
         match self {
             HeapRENode::KeyValueStore(store) => store,
@@ -399,6 +501,7 @@ impl HeapRENode {
             HeapRENode::Resource(..) => Ok(()),
             HeapRENode::Package(..) => Ok(()),
             HeapRENode::Worktop(..) => Err(RuntimeError::KernelError(KernelError::CantMoveWorktop)),
+            HeapRENode::AccessController(..) => Ok(()),
             HeapRENode::System(..) => Ok(()),
         }
     }
@@ -415,6 +518,9 @@ impl HeapRENode {
             HeapRENode::Bucket(..) => Err(RuntimeError::KernelError(KernelError::ValueNotAllowed)),
             HeapRENode::Proof(..) => Err(RuntimeError::KernelError(KernelError::ValueNotAllowed)),
             HeapRENode::Worktop(..) => Err(RuntimeError::KernelError(KernelError::ValueNotAllowed)),
+            HeapRENode::AccessController(..) => {
+                Err(RuntimeError::KernelError(KernelError::ValueNotAllowed))
+            }
             HeapRENode::System(..) => Err(RuntimeError::KernelError(KernelError::ValueNotAllowed)),
         }
     }
@@ -427,6 +533,7 @@ impl HeapRENode {
             HeapRENode::Component(..) => Err(DropFailure::Component),
             HeapRENode::Bucket(..) => Err(DropFailure::Bucket),
             HeapRENode::Resource(..) => Err(DropFailure::Resource),
+            HeapRENode::AccessController(..) => Err(DropFailure::AccessController),
             HeapRENode::System(..) => Err(DropFailure::System),
             HeapRENode::Proof(proof) => {
                 proof.drop();
@@ -457,9 +564,21 @@ impl HeapRENode {
 pub struct HeapRootRENode {
     pub root: HeapRENode,
     pub child_nodes: HashMap<RENodeId, HeapRENode>,
+    /// The size of the root and all child nodes, as of the last `resize`
+    size: usize,
 }
 
 impl HeapRootRENode {
+    pub fn new(root: HeapRENode, child_nodes: HashMap<RENodeId, HeapRENode>) -> Self {
+        let mut node = Self {
+            root,
+            child_nodes,
+            size: 0,
+        };
+        node.resize();
+        node
+    }
+
     pub fn root(&self) -> &HeapRENode {
         &self.root
     }
@@ -492,6 +611,24 @@ impl HeapRootRENode {
         }
     }
 
+    /// Returns the approximate serialized size of the root and all child nodes, as of the last
+    /// `resize`.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Measures the root and all child nodes again, after they may have been updated, returning
+    /// the size they had before.
+    pub fn resize(&mut self) -> usize {
+        let size = self.root.size()
+            + self
+                .child_nodes
+                .values()
+                .map(|node| node.size())
+                .sum::<usize>();
+        std::mem::replace(&mut self.size, size)
+    }
+
     pub fn insert_non_root_nodes(&mut self, nodes: HashMap<RENodeId, HeapRENode>) {
         for (id, node) in nodes {
             self.child_nodes.insert(id, node);
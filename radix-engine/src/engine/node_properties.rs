@@ -15,6 +15,7 @@ impl RENodeProperties {
             RENodeId::Vault(..) => false,
             RENodeId::ResourceManager(..) => true,
             RENodeId::Package(..) => true,
+            RENodeId::AccessController(..) => true,
             RENodeId::System => true,
         }
     }
@@ -33,9 +34,13 @@ impl RENodeProperties {
                 RENodeId::System => SubstateId::System,
                 RENodeId::Worktop => SubstateId::Worktop,
                 RENodeId::Component(component_address) => {
-                    SubstateId::ComponentInfo(component_address)
+                    SubstateId::ComponentAccessRules(component_address)
                 }
                 RENodeId::Vault(vault_id) => SubstateId::Vault(vault_id),
+                RENodeId::AccessController(component_address) => {
+                    SubstateId::AccessController(component_address)
+                }
+                RENodeId::Package(package_address) => SubstateId::Package(package_address),
                 _ => {
                     return Err(RuntimeError::KernelError(KernelError::MethodNotFound(
                         function.clone(),
@@ -69,6 +74,9 @@ impl SubstateProperties {
             SubstateId::ComponentState(component_address) => {
                 RENodeId::Component(*component_address)
             }
+            SubstateId::ComponentAccessRules(component_address) => {
+                RENodeId::Component(*component_address)
+            }
             SubstateId::NonFungibleSpace(resource_address) => {
                 RENodeId::ResourceManager(*resource_address)
             }
@@ -84,6 +92,9 @@ impl SubstateProperties {
             SubstateId::ResourceManager(resource_address) => {
                 RENodeId::ResourceManager(*resource_address)
             }
+            SubstateId::AccessController(component_address) => {
+                RENodeId::AccessController(*component_address)
+            }
             SubstateId::System => RENodeId::System,
             SubstateId::Bucket(bucket_id) => RENodeId::Bucket(*bucket_id),
             SubstateId::Proof(proof_id) => RENodeId::Proof(*proof_id),
@@ -96,12 +107,14 @@ impl SubstateProperties {
             SubstateId::KeyValueStoreEntry(..) => true,
             SubstateId::ComponentState(..) => true,
             SubstateId::ComponentInfo(..) => false,
+            SubstateId::ComponentAccessRules(..) => false,
             SubstateId::NonFungible(..) => false,
             SubstateId::NonFungibleSpace(..) => false,
             SubstateId::KeyValueStoreSpace(..) => false,
             SubstateId::Vault(..) => false,
             SubstateId::Package(..) => false,
             SubstateId::ResourceManager(..) => false,
+            SubstateId::AccessController(..) => false,
             SubstateId::System => false,
             SubstateId::Bucket(..) => false,
             SubstateId::Proof(..) => false,
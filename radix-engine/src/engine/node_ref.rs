@@ -41,6 +41,9 @@ impl RENodePointer {
                         // TODO: Remove when references cleaned up
                         TrackError::NotFound => KernelError::RENodeNotFound(self.node_id()),
                         TrackError::Reentrancy => KernelError::Reentrancy(substate_id.clone()),
+                        TrackError::NotDeclared => {
+                            KernelError::SubstateNotDeclared(substate_id.clone())
+                        }
                     })
             }
             RENodePointer::Heap { .. } => Ok(()),
@@ -97,7 +100,11 @@ impl RENodePointer {
         match self {
             RENodePointer::Heap { frame_id, root, id } => {
                 let frame = call_frames.get(*frame_id).unwrap();
-                RENodeRef::Stack(frame.owned_heap_nodes.get(root).unwrap(), id.clone())
+                RENodeRef::Stack(
+                    frame.owned_heap_nodes.get(root).unwrap(),
+                    root.clone(),
+                    id.clone(),
+                )
             }
             RENodePointer::Store(node_id) => RENodeRef::Track(track, node_id.clone()),
         }
@@ -125,80 +132,130 @@ pub enum NativeSubstateRef {
 }
 
 impl NativeSubstateRef {
-    pub fn bucket(&mut self) -> &mut Bucket {
+    pub fn bucket(&mut self) -> Result<&mut Bucket, RuntimeError> {
         match self {
-            NativeSubstateRef::Stack(root, _frame_id, _root_id, maybe_child) => {
+            NativeSubstateRef::Stack(root, _frame_id, root_id, maybe_child) => {
+                let node_id = maybe_child.unwrap_or(*root_id);
                 match root.get_node_mut(maybe_child.as_ref()) {
-                    HeapRENode::Bucket(bucket) => bucket,
-                    _ => panic!("Expecting to be a bucket"),
+                    HeapRENode::Bucket(bucket) => Ok(bucket),
+                    _ => Err(node_type_mismatch(node_id)),
                 }
             }
-            _ => panic!("Expecting to be a bucket"),
+            NativeSubstateRef::Track(substate_id, _) => Err(substate_type_mismatch(substate_id)),
         }
     }
 
-    pub fn proof(&mut self) -> &mut Proof {
+    pub fn proof(&mut self) -> Result<&mut Proof, RuntimeError> {
         match self {
-            NativeSubstateRef::Stack(ref mut root, _frame_id, _root_id, maybe_child) => {
+            NativeSubstateRef::Stack(root, _frame_id, root_id, maybe_child) => {
+                let node_id = maybe_child.unwrap_or(*root_id);
                 match root.get_node_mut(maybe_child.as_ref()) {
-                    HeapRENode::Proof(proof) => proof,
-                    _ => panic!("Expecting to be a proof"),
+                    HeapRENode::Proof(proof) => Ok(proof),
+                    _ => Err(node_type_mismatch(node_id)),
                 }
             }
-            _ => panic!("Expecting to be a proof"),
+            NativeSubstateRef::Track(substate_id, _) => Err(substate_type_mismatch(substate_id)),
         }
     }
 
-    pub fn worktop(&mut self) -> &mut Worktop {
+    pub fn worktop(&mut self) -> Result<&mut Worktop, RuntimeError> {
         match self {
-            NativeSubstateRef::Stack(ref mut root, _frame_id, _root_id, maybe_child) => {
+            NativeSubstateRef::Stack(root, _frame_id, root_id, maybe_child) => {
+                let node_id = maybe_child.unwrap_or(*root_id);
                 match root.get_node_mut(maybe_child.as_ref()) {
-                    HeapRENode::Worktop(worktop) => worktop,
-                    _ => panic!("Expecting to be a worktop"),
+                    HeapRENode::Worktop(worktop) => Ok(worktop),
+                    _ => Err(node_type_mismatch(node_id)),
                 }
             }
-            _ => panic!("Expecting to be a worktop"),
+            NativeSubstateRef::Track(substate_id, _) => Err(substate_type_mismatch(substate_id)),
         }
     }
 
-    pub fn vault(&mut self) -> &mut Vault {
+    pub fn vault(&mut self) -> Result<&mut Vault, RuntimeError> {
         match self {
-            NativeSubstateRef::Stack(root, _frame_id, _root_id, maybe_child) => {
-                root.get_node_mut(maybe_child.as_ref()).vault_mut()
+            NativeSubstateRef::Stack(root, _frame_id, root_id, maybe_child) => {
+                let node_id = maybe_child.unwrap_or(*root_id);
+                match root.get_node_mut(maybe_child.as_ref()) {
+                    HeapRENode::Vault(vault) => Ok(vault),
+                    _ => Err(node_type_mismatch(node_id)),
+                }
             }
-            NativeSubstateRef::Track(_address, value) => value.vault_mut(),
+            NativeSubstateRef::Track(substate_id, value) => match value {
+                Substate::Vault(vault) => Ok(vault),
+                _ => Err(substate_type_mismatch(substate_id)),
+            },
         }
     }
 
-    pub fn system(&mut self) -> &mut System {
+    pub fn system(&mut self) -> Result<&mut System, RuntimeError> {
         match self {
-            NativeSubstateRef::Track(_address, value) => value.system_mut(),
-            _ => panic!("Expecting to be system"),
+            NativeSubstateRef::Stack(_root, _frame_id, root_id, maybe_child) => {
+                Err(node_type_mismatch(maybe_child.unwrap_or(*root_id)))
+            }
+            NativeSubstateRef::Track(substate_id, value) => match value {
+                Substate::System(system) => Ok(system),
+                _ => Err(substate_type_mismatch(substate_id)),
+            },
         }
     }
 
-    pub fn component_info(&mut self) -> &mut ComponentInfo {
+    pub fn component_access_rules(&mut self) -> Result<&mut ComponentAccessRules, RuntimeError> {
         match self {
-            NativeSubstateRef::Stack(root, _frame_id, _root_id, maybe_child) => {
-                root.get_node_mut(maybe_child.as_ref()).component_info_mut()
+            NativeSubstateRef::Stack(root, _frame_id, root_id, maybe_child) => {
+                let node_id = maybe_child.unwrap_or(*root_id);
+                match root.get_node_mut(maybe_child.as_ref()) {
+                    HeapRENode::Component(.., access_rules) => Ok(access_rules),
+                    _ => Err(node_type_mismatch(node_id)),
+                }
             }
-            _ => panic!("Expecting to be a component"),
+            NativeSubstateRef::Track(substate_id, value) => match value {
+                Substate::ComponentAccessRules(access_rules) => Ok(access_rules),
+                _ => Err(substate_type_mismatch(substate_id)),
+            },
         }
     }
 
-    pub fn package(&mut self) -> &Package {
+    pub fn access_controller(&mut self) -> Result<&mut AccessController, RuntimeError> {
         match self {
-            NativeSubstateRef::Track(_address, value) => value.package(),
-            _ => panic!("Expecting to be tracked"),
+            NativeSubstateRef::Stack(root, _frame_id, root_id, maybe_child) => {
+                let node_id = maybe_child.unwrap_or(*root_id);
+                match root.get_node_mut(maybe_child.as_ref()) {
+                    HeapRENode::AccessController(access_controller) => Ok(access_controller),
+                    _ => Err(node_type_mismatch(node_id)),
+                }
+            }
+            NativeSubstateRef::Track(substate_id, value) => match value {
+                Substate::AccessController(access_controller) => Ok(access_controller),
+                _ => Err(substate_type_mismatch(substate_id)),
+            },
         }
     }
 
-    pub fn resource_manager(&mut self) -> &mut ResourceManager {
+    pub fn package(&mut self) -> Result<&mut Package, RuntimeError> {
         match self {
-            NativeSubstateRef::Stack(value, _frame_id, _root_id, maybe_child) => value
-                .get_node_mut(maybe_child.as_ref())
-                .resource_manager_mut(),
-            NativeSubstateRef::Track(_address, value) => value.resource_manager_mut(),
+            NativeSubstateRef::Stack(_root, _frame_id, root_id, maybe_child) => {
+                Err(node_type_mismatch(maybe_child.unwrap_or(*root_id)))
+            }
+            NativeSubstateRef::Track(substate_id, value) => match value {
+                Substate::Package(package) => Ok(package),
+                _ => Err(substate_type_mismatch(substate_id)),
+            },
+        }
+    }
+
+    pub fn resource_manager(&mut self) -> Result<&mut ResourceManager, RuntimeError> {
+        match self {
+            NativeSubstateRef::Stack(root, _frame_id, root_id, maybe_child) => {
+                let node_id = maybe_child.unwrap_or(*root_id);
+                match root.get_node_mut(maybe_child.as_ref()) {
+                    HeapRENode::Resource(resource_manager, ..) => Ok(resource_manager),
+                    _ => Err(node_type_mismatch(node_id)),
+                }
+            }
+            NativeSubstateRef::Track(substate_id, value) => match value {
+                Substate::Resource(resource_manager) => Ok(resource_manager),
+                _ => Err(substate_type_mismatch(substate_id)),
+            },
         }
     }
 
@@ -220,121 +277,179 @@ impl NativeSubstateRef {
 }
 
 pub enum RENodeRef<'f, 's, R: FeeReserve> {
-    Stack(&'f HeapRootRENode, Option<RENodeId>),
+    Stack(&'f HeapRootRENode, RENodeId, Option<RENodeId>),
     Track(&'f Track<'s, R>, RENodeId),
 }
 
 impl<'f, 's, R: FeeReserve> RENodeRef<'f, 's, R> {
-    pub fn bucket(&self) -> &Bucket {
+    pub fn bucket(&self) -> Result<&Bucket, RuntimeError> {
         match self {
-            RENodeRef::Stack(value, id) => id
-                .as_ref()
-                .map_or(value.root(), |v| value.non_root(v))
-                .bucket(),
-            RENodeRef::Track(..) => {
-                panic!("Unexpected")
-            }
+            RENodeRef::Stack(value, root_id, id) => match value.get_node(id.as_ref()) {
+                HeapRENode::Bucket(bucket) => Ok(bucket),
+                _ => Err(node_type_mismatch(id.unwrap_or(*root_id))),
+            },
+            RENodeRef::Track(_, node_id) => Err(node_type_mismatch(*node_id)),
         }
     }
 
-    pub fn vault(&self) -> &Vault {
+    pub fn vault(&self) -> Result<&Vault, RuntimeError> {
         match self {
-            RENodeRef::Stack(value, id) => id
-                .as_ref()
-                .map_or(value.root(), |v| value.non_root(v))
-                .vault(),
+            RENodeRef::Stack(value, root_id, id) => match value.get_node(id.as_ref()) {
+                HeapRENode::Vault(vault) => Ok(vault),
+                _ => Err(node_type_mismatch(id.unwrap_or(*root_id))),
+            },
             RENodeRef::Track(track, node_id) => {
                 let substate_id = match node_id {
                     RENodeId::Vault(vault_id) => SubstateId::Vault(*vault_id),
-                    _ => panic!("Unexpected"),
+                    _ => return Err(node_type_mismatch(*node_id)),
                 };
-                track.read_substate(substate_id).vault()
+                match track.read_substate(substate_id.clone()) {
+                    Substate::Vault(vault) => Ok(vault),
+                    _ => Err(substate_type_mismatch(&substate_id)),
+                }
             }
         }
     }
 
-    pub fn system(&self) -> &System {
+    pub fn system(&self) -> Result<&System, RuntimeError> {
         match self {
-            RENodeRef::Stack(value, id) => id
-                .as_ref()
-                .map_or(value.root(), |v| value.non_root(v))
-                .system(),
+            RENodeRef::Stack(value, root_id, id) => match value.get_node(id.as_ref()) {
+                HeapRENode::System(system) => Ok(system),
+                _ => Err(node_type_mismatch(id.unwrap_or(*root_id))),
+            },
             RENodeRef::Track(track, node_id) => {
                 let substate_id = match node_id {
                     RENodeId::System => SubstateId::System,
-                    _ => panic!("Unexpected"),
+                    _ => return Err(node_type_mismatch(*node_id)),
                 };
-                track.read_substate(substate_id).system()
+                match track.read_substate(substate_id.clone()) {
+                    Substate::System(system) => Ok(system),
+                    _ => Err(substate_type_mismatch(&substate_id)),
+                }
             }
         }
     }
 
-    pub fn resource_manager(&self) -> &ResourceManager {
+    pub fn resource_manager(&self) -> Result<&ResourceManager, RuntimeError> {
         match self {
-            RENodeRef::Stack(value, id) => id
-                .as_ref()
-                .map_or(value.root(), |v| value.non_root(v))
-                .resource_manager(),
+            RENodeRef::Stack(value, root_id, id) => match value.get_node(id.as_ref()) {
+                HeapRENode::Resource(resource_manager, ..) => Ok(resource_manager),
+                _ => Err(node_type_mismatch(id.unwrap_or(*root_id))),
+            },
             RENodeRef::Track(track, node_id) => {
                 let substate_id = match node_id {
                     RENodeId::ResourceManager(resource_address) => {
                         SubstateId::ResourceManager(*resource_address)
                     }
-                    _ => panic!("Unexpected"),
+                    _ => return Err(node_type_mismatch(*node_id)),
                 };
-                track.read_substate(substate_id).resource_manager()
+                match track.read_substate(substate_id.clone()) {
+                    Substate::Resource(resource_manager) => Ok(resource_manager),
+                    _ => Err(substate_type_mismatch(&substate_id)),
+                }
             }
         }
     }
 
-    pub fn component_state(&self) -> &ComponentState {
+    pub fn component_state(&self) -> Result<&ComponentState, RuntimeError> {
         match self {
-            RENodeRef::Stack(value, id) => id
-                .as_ref()
-                .map_or(value.root(), |v| value.non_root(v))
-                .component_state(),
+            RENodeRef::Stack(value, root_id, id) => match value.get_node(id.as_ref()) {
+                HeapRENode::Component(_, component_state, _) => Ok(component_state),
+                _ => Err(node_type_mismatch(id.unwrap_or(*root_id))),
+            },
             RENodeRef::Track(track, node_id) => {
                 let substate_id = match node_id {
                     RENodeId::Component(component_address) => {
                         SubstateId::ComponentState(*component_address)
                     }
-                    _ => panic!("Unexpected"),
+                    _ => return Err(node_type_mismatch(*node_id)),
                 };
-                track.read_substate(substate_id).component_state()
+                match track.read_substate(substate_id.clone()) {
+                    Substate::ComponentState(component_state) => Ok(component_state),
+                    _ => Err(substate_type_mismatch(&substate_id)),
+                }
             }
         }
     }
 
-    pub fn component_info(&self) -> &ComponentInfo {
+    pub fn component_info(&self) -> Result<&ComponentInfo, RuntimeError> {
         match self {
-            RENodeRef::Stack(value, id) => id
-                .as_ref()
-                .map_or(value.root(), |v| value.non_root(v))
-                .component_info(),
+            RENodeRef::Stack(value, root_id, id) => match value.get_node(id.as_ref()) {
+                HeapRENode::Component(component_info, ..) => Ok(component_info),
+                _ => Err(node_type_mismatch(id.unwrap_or(*root_id))),
+            },
             RENodeRef::Track(track, node_id) => {
                 let substate_id = match node_id {
                     RENodeId::Component(component_address) => {
                         SubstateId::ComponentInfo(*component_address)
                     }
-                    _ => panic!("Unexpected"),
+                    _ => return Err(node_type_mismatch(*node_id)),
                 };
-                track.read_substate(substate_id).component_info()
+                match track.read_substate(substate_id.clone()) {
+                    Substate::ComponentInfo(component_info) => Ok(component_info),
+                    _ => Err(substate_type_mismatch(&substate_id)),
+                }
             }
         }
     }
 
-    pub fn package(&self) -> &Package {
+    pub fn component_access_rules(&self) -> Result<&ComponentAccessRules, RuntimeError> {
         match self {
-            RENodeRef::Stack(value, id) => id
-                .as_ref()
-                .map_or(value.root(), |v| value.non_root(v))
-                .package(),
+            RENodeRef::Stack(value, root_id, id) => match value.get_node(id.as_ref()) {
+                HeapRENode::Component(.., access_rules) => Ok(access_rules),
+                _ => Err(node_type_mismatch(id.unwrap_or(*root_id))),
+            },
+            RENodeRef::Track(track, node_id) => {
+                let substate_id = match node_id {
+                    RENodeId::Component(component_address) => {
+                        SubstateId::ComponentAccessRules(*component_address)
+                    }
+                    _ => return Err(node_type_mismatch(*node_id)),
+                };
+                match track.read_substate(substate_id.clone()) {
+                    Substate::ComponentAccessRules(access_rules) => Ok(access_rules),
+                    _ => Err(substate_type_mismatch(&substate_id)),
+                }
+            }
+        }
+    }
+
+    pub fn package(&self) -> Result<&Package, RuntimeError> {
+        match self {
+            RENodeRef::Stack(value, root_id, id) => match value.get_node(id.as_ref()) {
+                HeapRENode::Package(package) => Ok(package),
+                _ => Err(node_type_mismatch(id.unwrap_or(*root_id))),
+            },
             RENodeRef::Track(track, node_id) => {
                 let substate_id = match node_id {
                     RENodeId::Package(package_address) => SubstateId::Package(*package_address),
-                    _ => panic!("Unexpected"),
+                    _ => return Err(node_type_mismatch(*node_id)),
                 };
-                track.read_substate(substate_id).package()
+                match track.read_substate(substate_id.clone()) {
+                    Substate::Package(package) => Ok(package),
+                    _ => Err(substate_type_mismatch(&substate_id)),
+                }
+            }
+        }
+    }
+
+    pub fn access_controller(&self) -> Result<&AccessController, RuntimeError> {
+        match self {
+            RENodeRef::Stack(value, root_id, id) => match value.get_node(id.as_ref()) {
+                HeapRENode::AccessController(access_controller) => Ok(access_controller),
+                _ => Err(node_type_mismatch(id.unwrap_or(*root_id))),
+            },
+            RENodeRef::Track(track, node_id) => {
+                let substate_id = match node_id {
+                    RENodeId::AccessController(component_address) => {
+                        SubstateId::AccessController(*component_address)
+                    }
+                    _ => return Err(node_type_mismatch(*node_id)),
+                };
+                match track.read_substate(substate_id.clone()) {
+                    Substate::AccessController(access_controller) => Ok(access_controller),
+                    _ => Err(substate_type_mismatch(&substate_id)),
+                }
             }
         }
     }
@@ -352,44 +467,51 @@ impl<'f, 's, R: FeeReserve> RENodeRefMut<'f, 's, R> {
     ) -> Result<ScryptoValue, RuntimeError> {
         match substate_id {
             SubstateId::ComponentInfo(..) => {
-                Ok(ScryptoValue::from_typed(&self.component_info().info()))
+                Ok(ScryptoValue::from_typed(&self.component_info()?.info()))
             }
             SubstateId::ComponentState(..) => {
-                Ok(ScryptoValue::from_slice(self.component_state().state())
-                    .expect("Failed to decode component state"))
+                ScryptoValue::from_slice(self.component_state()?.state())
+                    .map_err(|e| RuntimeError::KernelError(KernelError::DecodeError(e)))
             }
-            SubstateId::NonFungible(.., id) => Ok(self.non_fungible_get(id)),
-            SubstateId::KeyValueStoreEntry(.., key) => Ok(self.kv_store_get(key)),
-            SubstateId::NonFungibleSpace(..)
+            SubstateId::NonFungible(.., id) => self.non_fungible_get(id),
+            SubstateId::KeyValueStoreEntry(.., key) => self.kv_store_get(key),
+            SubstateId::ComponentAccessRules(..)
+            | SubstateId::NonFungibleSpace(..)
             | SubstateId::Vault(..)
             | SubstateId::KeyValueStoreSpace(..)
             | SubstateId::Package(..)
             | SubstateId::ResourceManager(..)
+            | SubstateId::AccessController(..)
             | SubstateId::System
             | SubstateId::Bucket(..)
             | SubstateId::Proof(..)
-            | SubstateId::Worktop => {
-                panic!("Should never have received permissions to read this native type.");
-            }
+            | SubstateId::Worktop => Err(RuntimeError::KernelError(
+                KernelError::SubstateReadNotSupported(substate_id.clone()),
+            )),
         }
     }
 
-    pub fn replace_value_with_default(&mut self, substate_id: &SubstateId) {
+    pub fn replace_value_with_default(
+        &mut self,
+        substate_id: &SubstateId,
+    ) -> Result<(), RuntimeError> {
         match substate_id {
             SubstateId::ComponentInfo(..)
             | SubstateId::ComponentState(..)
+            | SubstateId::ComponentAccessRules(..)
             | SubstateId::NonFungibleSpace(..)
             | SubstateId::KeyValueStoreSpace(..)
             | SubstateId::KeyValueStoreEntry(..)
             | SubstateId::Vault(..)
             | SubstateId::Package(..)
             | SubstateId::ResourceManager(..)
+            | SubstateId::AccessController(..)
             | SubstateId::System
             | SubstateId::Bucket(..)
             | SubstateId::Proof(..)
-            | SubstateId::Worktop => {
-                panic!("Should not get here");
-            }
+            | SubstateId::Worktop => Err(RuntimeError::KernelError(
+                KernelError::SubstateWriteNotSupported(substate_id.clone()),
+            )),
             SubstateId::NonFungible(.., id) => self.non_fungible_remove(&id),
         }
     }
@@ -399,45 +521,25 @@ impl<'f, 's, R: FeeReserve> RENodeRefMut<'f, 's, R> {
         substate_id: SubstateId,
         value: ScryptoValue,
         child_nodes: HashMap<RENodeId, HeapRootRENode>,
-    ) {
+    ) -> Result<(), RuntimeError> {
         match substate_id {
-            SubstateId::ComponentInfo(..) => {
-                panic!("Should not get here");
-            }
-            SubstateId::ComponentState(..) => {
-                self.component_state_set(value, child_nodes);
-            }
-            SubstateId::KeyValueStoreSpace(..) => {
-                panic!("Should not get here");
-            }
-            SubstateId::KeyValueStoreEntry(.., key) => {
-                self.kv_store_put(key, value, child_nodes);
-            }
-            SubstateId::NonFungibleSpace(..) => {
-                panic!("Should not get here");
-            }
+            SubstateId::ComponentState(..) => self.component_state_set(value, child_nodes),
+            SubstateId::KeyValueStoreEntry(.., key) => self.kv_store_put(key, value, child_nodes),
             SubstateId::NonFungible(.., id) => self.non_fungible_put(id, value),
-            SubstateId::Vault(..) => {
-                panic!("Should not get here");
-            }
-            SubstateId::Package(..) => {
-                panic!("Should not get here");
-            }
-            SubstateId::ResourceManager(..) => {
-                panic!("Should not get here");
-            }
-            SubstateId::System => {
-                panic!("Should not get here");
-            }
-            SubstateId::Bucket(..) => {
-                panic!("Should not get here");
-            }
-            SubstateId::Proof(..) => {
-                panic!("Should not get here");
-            }
-            SubstateId::Worktop => {
-                panic!("Should not get here");
-            }
+            SubstateId::ComponentInfo(..)
+            | SubstateId::ComponentAccessRules(..)
+            | SubstateId::KeyValueStoreSpace(..)
+            | SubstateId::NonFungibleSpace(..)
+            | SubstateId::Vault(..)
+            | SubstateId::Package(..)
+            | SubstateId::ResourceManager(..)
+            | SubstateId::AccessController(..)
+            | SubstateId::System
+            | SubstateId::Bucket(..)
+            | SubstateId::Proof(..)
+            | SubstateId::Worktop => Err(RuntimeError::KernelError(
+                KernelError::SubstateWriteNotSupported(substate_id),
+            )),
         }
     }
 
@@ -446,7 +548,7 @@ impl<'f, 's, R: FeeReserve> RENodeRefMut<'f, 's, R> {
         key: Vec<u8>,
         value: ScryptoValue,
         to_store: HashMap<RENodeId, HeapRootRENode>,
-    ) {
+    ) -> Result<(), RuntimeError> {
         match self {
             RENodeRefMut::Stack(re_value, id) => {
                 re_value
@@ -462,119 +564,112 @@ impl<'f, 's, R: FeeReserve> RENodeRefMut<'f, 's, R> {
                     RENodeId::KeyValueStore(kv_store_id) => {
                         SubstateId::KeyValueStoreSpace(*kv_store_id)
                     }
-                    _ => panic!("Unexpeceted"),
+                    _ => return Err(node_type_mismatch(*node_id)),
                 };
-                track.set_key_value(
-                    parent_substate_id,
-                    key,
-                    Substate::KeyValueStoreEntry(KeyValueStoreEntryWrapper(Some(value.raw))),
-                );
+                track
+                    .set_key_value(
+                        parent_substate_id.clone(),
+                        key.clone(),
+                        Substate::KeyValueStoreEntry(Some(value)),
+                    )
+                    .map_err(|_| key_value_not_declared(&parent_substate_id, &key))?;
                 for (id, val) in to_store {
-                    insert_non_root_nodes(track, val.to_nodes(id));
+                    insert_non_root_nodes(track, val.to_nodes(id))?;
                 }
             }
         }
+        Ok(())
     }
 
-    pub fn kv_store_get(&mut self, key: &[u8]) -> ScryptoValue {
-        let wrapper = match self {
+    pub fn kv_store_get(&mut self, key: &[u8]) -> Result<ScryptoValue, RuntimeError> {
+        let entry: Option<ScryptoValue> = match self {
             RENodeRefMut::Stack(re_value, id) => {
                 let store = re_value.get_node_mut(id.as_ref()).kv_store_mut();
-                store
-                    .get(key)
-                    .map(|v| KeyValueStoreEntryWrapper(Some(v.raw)))
-                    .unwrap_or(KeyValueStoreEntryWrapper(None))
+                store.get(key)
             }
             RENodeRefMut::Track(track, node_id) => {
                 let parent_substate_id = match node_id {
                     RENodeId::KeyValueStore(kv_store_id) => {
                         SubstateId::KeyValueStoreSpace(*kv_store_id)
                     }
-                    _ => panic!("Unexpeceted"),
+                    _ => return Err(node_type_mismatch(*node_id)),
                 };
-                let substate_value = track.read_key_value(parent_substate_id, key.to_vec());
+                let substate_value = track
+                    .read_key_value(parent_substate_id.clone(), key.to_vec())
+                    .map_err(|_| key_value_not_declared(&parent_substate_id, key))?;
                 substate_value.into()
             }
         };
 
-        // TODO: Cleanup after adding polymorphism support for SBOR
-        // For now, we have to use `Vec<u8>` within `KeyValueStoreEntryWrapper`
-        // and apply the following ugly conversion.
-        let value = wrapper.0.map_or(
-            Value::Option {
-                value: Box::new(Option::None),
-            },
-            |v| Value::Option {
-                value: Box::new(Some(
-                    decode_any(&v).expect("Failed to decode the value in NonFungibleWrapper"),
-                )),
-            },
-        );
-
-        ScryptoValue::from_value(value)
-            .expect("Failed to convert non-fungible value to Scrypto value")
+        Ok(ScryptoValue::from_typed(&entry))
     }
 
-    pub fn non_fungible_get(&mut self, id: &NonFungibleId) -> ScryptoValue {
-        let wrapper = match self {
+    pub fn non_fungible_get(&mut self, id: &NonFungibleId) -> Result<ScryptoValue, RuntimeError> {
+        let non_fungible: Option<NonFungible> = match self {
             RENodeRefMut::Stack(value, re_id) => {
                 let non_fungible_set = re_id
                     .as_ref()
                     .map_or(value.root(), |v| value.non_root(v))
                     .non_fungibles();
-                non_fungible_set
-                    .get(id)
-                    .cloned()
-                    .map(|v| NonFungibleWrapper(Some(v)))
-                    .unwrap_or(NonFungibleWrapper(None))
+                non_fungible_set.get(id).cloned()
             }
             RENodeRefMut::Track(track, node_id) => {
                 let parent_substate_id = match node_id {
                     RENodeId::ResourceManager(resource_address) => {
                         SubstateId::NonFungibleSpace(*resource_address)
                     }
-                    _ => panic!("Unexpeceted"),
+                    _ => return Err(node_type_mismatch(*node_id)),
                 };
-                let substate_value = track.read_key_value(parent_substate_id, id.to_vec());
+                let substate_value = track
+                    .read_key_value(parent_substate_id.clone(), id.to_vec())
+                    .map_err(|_| key_value_not_declared(&parent_substate_id, &id.to_vec()))?;
                 substate_value.into()
             }
         };
 
-        ScryptoValue::from_typed(&wrapper)
+        Ok(ScryptoValue::from_typed(&non_fungible))
     }
 
-    pub fn non_fungible_remove(&mut self, id: &NonFungibleId) {
+    pub fn non_fungible_remove(&mut self, id: &NonFungibleId) -> Result<(), RuntimeError> {
         match self {
-            RENodeRefMut::Stack(..) => {
-                panic!("Not supported");
-            }
+            RENodeRefMut::Stack(..) => Err(RuntimeError::KernelError(
+                KernelError::NonFungibleRemoveNotSupported(id.clone()),
+            )),
             RENodeRefMut::Track(track, node_id) => {
                 let parent_substate_id = match node_id {
                     RENodeId::ResourceManager(resource_address) => {
                         SubstateId::NonFungibleSpace(*resource_address)
                     }
-                    _ => panic!("Unexpeceted"),
+                    _ => return Err(node_type_mismatch(*node_id)),
                 };
-                track.set_key_value(
-                    parent_substate_id,
-                    id.to_vec(),
-                    Substate::NonFungible(NonFungibleWrapper(None)),
-                );
+                track
+                    .set_key_value(
+                        parent_substate_id.clone(),
+                        id.to_vec(),
+                        Substate::NonFungible(None),
+                    )
+                    .map_err(|_| key_value_not_declared(&parent_substate_id, &id.to_vec()))
             }
         }
     }
 
-    pub fn non_fungible_put(&mut self, id: NonFungibleId, value: ScryptoValue) {
+    pub fn non_fungible_put(
+        &mut self,
+        id: NonFungibleId,
+        value: ScryptoValue,
+    ) -> Result<(), RuntimeError> {
+        let non_fungible: Option<NonFungible> = scrypto_decode(&value.raw)
+            .map_err(|e| RuntimeError::KernelError(KernelError::DecodeError(e)))?;
         match self {
             RENodeRefMut::Stack(re_value, re_id) => {
-                let wrapper: NonFungibleWrapper = scrypto_decode(&value.raw)
-                    .expect("Attempted to put non-NonFungibleWrapper for non-fungible.");
-
                 let non_fungible_set = re_value.get_node_mut(re_id.as_ref()).non_fungibles_mut();
-                if let Some(non_fungible) = wrapper.0 {
+                if let Some(non_fungible) = non_fungible {
                     non_fungible_set.insert(id, non_fungible);
                 } else {
-                    panic!("TODO: invalidate this code path and possibly consolidate `non_fungible_remove` and `non_fungible_put`")
+                    // TODO: possibly consolidate `non_fungible_remove` and `non_fungible_put`
+                    return Err(RuntimeError::KernelError(
+                        KernelError::NonFungibleRemoveNotSupported(id),
+                    ));
                 }
             }
             RENodeRefMut::Track(track, node_id) => {
@@ -582,24 +677,25 @@ impl<'f, 's, R: FeeReserve> RENodeRefMut<'f, 's, R> {
                     RENodeId::ResourceManager(resource_address) => {
                         SubstateId::NonFungibleSpace(*resource_address)
                     }
-                    _ => panic!("Unexpeceted"),
+                    _ => return Err(node_type_mismatch(*node_id)),
                 };
-                let wrapper: NonFungibleWrapper = scrypto_decode(&value.raw)
-                    .expect("Attempted to put non-NonFungibleWrapper for non-fungible.");
-                track.set_key_value(
-                    parent_substate_id,
-                    id.to_vec(),
-                    Substate::NonFungible(wrapper),
-                );
+                track
+                    .set_key_value(
+                        parent_substate_id.clone(),
+                        id.to_vec(),
+                        Substate::NonFungible(non_fungible),
+                    )
+                    .map_err(|_| key_value_not_declared(&parent_substate_id, &id.to_vec()))?;
             }
         }
+        Ok(())
     }
 
     pub fn component_state_set(
         &mut self,
         value: ScryptoValue,
         to_store: HashMap<RENodeId, HeapRootRENode>,
-    ) {
+    ) -> Result<(), RuntimeError> {
         match self {
             RENodeRefMut::Stack(re_value, id) => {
                 let component_state = re_value.get_node_mut(id.as_ref()).component_state_mut();
@@ -613,53 +709,62 @@ impl<'f, 's, R: FeeReserve> RENodeRefMut<'f, 's, R> {
                     RENodeId::Component(component_address) => {
                         SubstateId::ComponentState(*component_address)
                     }
-                    _ => panic!("Unexpeceted"),
+                    _ => return Err(node_type_mismatch(*node_id)),
                 };
                 track.write_substate(substate_id, ComponentState::new(value.raw));
                 for (id, val) in to_store {
-                    insert_non_root_nodes(track, val.to_nodes(id));
+                    insert_non_root_nodes(track, val.to_nodes(id))?;
                 }
             }
         }
+        Ok(())
     }
 
-    pub fn component_info(&mut self) -> &ComponentInfo {
+    pub fn component_info(&mut self) -> Result<&ComponentInfo, RuntimeError> {
         match self {
             RENodeRefMut::Stack(re_value, id) => {
-                re_value.get_node_mut(id.as_ref()).component_info()
+                Ok(re_value.get_node_mut(id.as_ref()).component_info())
             }
             RENodeRefMut::Track(track, node_id) => {
                 let substate_id = match node_id {
                     RENodeId::Component(component_address) => {
                         SubstateId::ComponentInfo(*component_address)
                     }
-                    _ => panic!("Unexpeceted"),
+                    _ => return Err(node_type_mismatch(*node_id)),
                 };
                 let component_val = track.read_substate(substate_id);
-                component_val.component_info()
+                Ok(component_val.component_info())
             }
         }
     }
 
-    pub fn component_state(&mut self) -> &ComponentState {
+    pub fn component_state(&mut self) -> Result<&ComponentState, RuntimeError> {
         match self {
             RENodeRefMut::Stack(re_value, id) => {
-                re_value.get_node_mut(id.as_ref()).component_state()
+                Ok(re_value.get_node_mut(id.as_ref()).component_state())
             }
             RENodeRefMut::Track(track, node_id) => {
                 let substate_id = match node_id {
                     RENodeId::Component(component_address) => {
                         SubstateId::ComponentState(*component_address)
                     }
-                    _ => panic!("Unexpeceted"),
+                    _ => return Err(node_type_mismatch(*node_id)),
                 };
                 let component_val = track.read_substate(substate_id);
-                component_val.component_state()
+                Ok(component_val.component_state())
             }
         }
     }
 }
 
+fn node_type_mismatch(node_id: RENodeId) -> RuntimeError {
+    RuntimeError::KernelError(KernelError::RENodeTypeMismatch(node_id))
+}
+
+fn substate_type_mismatch(substate_id: &SubstateId) -> RuntimeError {
+    RuntimeError::KernelError(KernelError::SubstateTypeMismatch(substate_id.clone()))
+}
+
 pub fn verify_stored_value_update(
     old: &HashSet<RENodeId>,
     missing: &HashSet<RENodeId>,
@@ -687,14 +792,14 @@ pub fn verify_stored_value_update(
 pub fn insert_non_root_nodes<'s, R: FeeReserve>(
     track: &mut Track<'s, R>,
     values: HashMap<RENodeId, HeapRENode>,
-) {
+) -> Result<(), RuntimeError> {
     for (id, node) in values {
         match node {
             HeapRENode::Vault(vault) => {
                 let addr = SubstateId::Vault(id.into());
                 track.create_uuid_substate(addr, vault, false);
             }
-            HeapRENode::Component(component, component_state) => {
+            HeapRENode::Component(component, component_state, access_rules) => {
                 let component_address = id.into();
                 track.create_uuid_substate(
                     SubstateId::ComponentInfo(component_address),
@@ -706,19 +811,34 @@ pub fn insert_non_root_nodes<'s, R: FeeReserve>(
                     component_state,
                     false,
                 );
+                track.create_uuid_substate(
+                    SubstateId::ComponentAccessRules(component_address),
+                    access_rules,
+                    false,
+                );
             }
             HeapRENode::KeyValueStore(store) => {
                 let id = id.into();
                 let substate_id = SubstateId::KeyValueStoreSpace(id);
                 for (k, v) in store.store {
-                    track.set_key_value(
-                        substate_id.clone(),
-                        k,
-                        KeyValueStoreEntryWrapper(Some(v.raw)),
-                    );
+                    track
+                        .set_key_value(
+                            substate_id.clone(),
+                            k.clone(),
+                            Substate::KeyValueStoreEntry(Some(v)),
+                        )
+                        .map_err(|_| key_value_not_declared(&substate_id, &k))?;
                 }
             }
-            _ => panic!("Invalid node being persisted: {:?}", node),
+            _ => return Err(node_type_mismatch(id)),
         }
     }
+    Ok(())
+}
+
+fn key_value_not_declared(parent_substate_id: &SubstateId, key: &[u8]) -> RuntimeError {
+    RuntimeError::KernelError(KernelError::SubstateNotDeclared(key_value_substate_id(
+        parent_substate_id,
+        key.to_vec(),
+    )))
 }
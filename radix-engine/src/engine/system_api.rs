@@ -3,6 +3,7 @@ use crate::engine::*;
 use crate::fee::FeeReserve;
 use crate::model::AuthZone;
 use crate::model::ResourceContainer;
+use crate::model::Worktop;
 use crate::types::*;
 use crate::wasm::*;
 
@@ -15,6 +16,9 @@ where
     // TODO: possible to consider AuthZone as a RENode?
     fn auth_zone(&mut self, frame_id: usize) -> &mut AuthZone;
 
+    /// Returns the worktop owned by the current frame, if any, without charging for the read.
+    fn worktop(&mut self) -> Option<&Worktop>;
+
     fn consume_cost_units(&mut self, units: u32) -> Result<(), RuntimeError>;
 
     fn lock_fee(
@@ -67,17 +71,65 @@ where
     ) -> Result<(), RuntimeError>;
     fn substate_take(&mut self, substate_id: SubstateId) -> Result<ScryptoValue, RuntimeError>;
 
+    /// Writes a key value store entry unless one already exists, returning whether it was written
+    fn kv_store_put_if_absent(
+        &mut self,
+        kv_store_id: KeyValueStoreId,
+        key: Vec<u8>,
+        value: ScryptoValue,
+    ) -> Result<bool, RuntimeError>;
+
     fn transaction_hash(&mut self) -> Result<Hash, RuntimeError>;
 
+    /// Returns the non-fungible addresses the initial auth zone was populated with
+    fn transaction_signers(&mut self) -> Result<Vec<NonFungibleAddress>, RuntimeError>;
+
     fn read_blob(&mut self, blob_hash: &Hash) -> Result<&[u8], RuntimeError>;
 
     fn generate_uuid(&mut self) -> Result<u128, RuntimeError>;
 
+    /// Records the index of the manifest instruction the transaction processor is about to run
+    fn set_instruction_index(&mut self, index: u32) -> Result<(), RuntimeError>;
+
+    /// Generates pseudo-random bytes, deterministically derived from the transaction hash, the
+    /// index of the running instruction and the number of calls made before this one
+    fn generate_random_bytes(&mut self, len: u32) -> Result<Vec<u8>, RuntimeError>;
+
+    /// Returns the cost units left, after charging for this call
+    fn remaining_cost_units(&mut self) -> Result<u32, RuntimeError>;
+
+    fn compute_hash(&mut self, algorithm: HashAlgorithm, data: &[u8])
+        -> Result<Hash, RuntimeError>;
+
+    fn verify_signature(
+        &mut self,
+        message: &[u8],
+        public_key: PublicKey,
+        signature: Signature,
+    ) -> Result<bool, RuntimeError>;
+
     fn emit_log(&mut self, level: Level, message: String) -> Result<(), RuntimeError>;
 
+    /// Emits an event on behalf of the current actor
+    fn emit_event(&mut self, name: String, data: Vec<u8>) -> Result<(), RuntimeError>;
+
     fn check_access_rule(
         &mut self,
         access_rule: AccessRule,
         proof_ids: Vec<ProofId>,
     ) -> Result<bool, RuntimeError>;
+
+    /// Creates a checkpoint which the state changes of the current frame can be rolled back to
+    fn execution_checkpoint(&mut self) -> Result<CheckpointId, RuntimeError>;
+
+    /// Rolls back all state changes made since a checkpoint of the current frame
+    fn rollback_to(&mut self, checkpoint: CheckpointId) -> Result<(), RuntimeError>;
+
+    /// Sets aside the proofs of all auth zones on the call stack, so that the instructions of a
+    /// subintent run by the current frame only see the badges it pushes afterwards.
+    fn auth_zone_stash(&mut self) -> Result<(), RuntimeError>;
+
+    /// Drops the proofs left in the current auth zone and restores the ones set aside by the
+    /// last stash of the current frame.
+    fn auth_zone_unstash(&mut self) -> Result<(), RuntimeError>;
 }
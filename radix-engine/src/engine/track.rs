@@ -1,5 +1,6 @@
 use transaction::model::ExecutableTransaction;
 
+use crate::constants::SYS_TREASURY_VAULT;
 use crate::engine::AppStateTrack;
 use crate::engine::BaseStateTrack;
 use crate::engine::StateTrackError;
@@ -10,8 +11,7 @@ use crate::fee::FeeSummary;
 use crate::fee::FeeTable;
 use crate::ledger::*;
 use crate::model::Bucket;
-use crate::model::KeyValueStoreEntryWrapper;
-use crate::model::NonFungibleWrapper;
+use crate::model::InstructionOutput;
 use crate::model::ResourceContainer;
 use crate::transaction::CommitResult;
 use crate::transaction::EntityChanges;
@@ -37,12 +37,38 @@ impl BorrowedSubstate {
     }
 }
 
+/// The track state captured by an execution checkpoint
+struct TrackCheckpoint {
+    /// Encoded copies of the mutably borrowed substates at the checkpoint
+    borrowed_substates: HashMap<SubstateId, Vec<u8>>,
+    /// The number of new substates at the checkpoint
+    new_substates_len: usize,
+    /// The number of application logs at the checkpoint
+    application_logs_len: usize,
+    /// The number of events at the checkpoint
+    events_len: usize,
+}
+
+/// An event emitted by a blueprint
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode)]
+pub struct ApplicationEvent {
+    /// The component, or the package when called as a function, which emitted the event
+    pub emitter: Option<CallerOrigin>,
+    pub name: String,
+    /// The SBOR-encoded event data
+    pub data: Vec<u8>,
+}
+
 /// Transaction-wide states and side effects
 pub struct Track<'s, R: FeeReserve> {
     application_logs: Vec<(Level, String)>,
+    events: Vec<ApplicationEvent>,
     new_substates: Vec<SubstateId>,
     state_track: AppStateTrack<'s>,
     borrowed_substates: HashMap<SubstateId, BorrowedSubstate>,
+    checkpoints: Vec<TrackCheckpoint>,
+    /// The pre-existing substates which may be locked, or `None` if unrestricted
+    declared_substates: Option<HashSet<SubstateId>>,
     pub fee_reserve: R,
     pub fee_table: FeeTable,
 }
@@ -51,12 +77,23 @@ pub struct Track<'s, R: FeeReserve> {
 pub enum TrackError {
     Reentrancy,
     NotFound,
+    NotDeclared,
     StateTrackError(StateTrackError),
 }
 
+#[derive(Debug)]
+pub enum CheckpointError {
+    NotFound,
+    /// The borrowed substates don't allow taking or rolling back to the checkpoint.
+    NotAllowed,
+}
+
 pub struct TrackReceipt {
     pub fee_summary: FeeSummary,
     pub application_logs: Vec<(Level, String)>,
+    /// The events emitted, which are dropped unless the transaction succeeds
+    pub events: Vec<ApplicationEvent>,
+    pub substate_cache_stats: SubstateCacheStats,
     pub result: TransactionResult,
 }
 
@@ -76,19 +113,51 @@ impl<'s, R: FeeReserve> Track<'s, R> {
 
         Self {
             application_logs: Vec::new(),
+            events: Vec::new(),
             new_substates: Vec::new(),
             state_track,
             borrowed_substates: HashMap::new(),
+            checkpoints: Vec::new(),
+            declared_substates: None,
             fee_reserve,
             fee_table,
         }
     }
 
+    /// Restricts the pre-existing substates which can be locked to the declared ones.
+    ///
+    /// Substates created during the transaction are always accessible.
+    pub fn declare_substates(&mut self, substate_ids: HashSet<SubstateId>) {
+        self.declared_substates = Some(substate_ids);
+    }
+
+    /// Fails if the substate pre-exists in the substate store without having been declared,
+    /// either by itself or through the key value space it belongs to.
+    fn check_declared(
+        &self,
+        substate_id: &SubstateId,
+        space_id: Option<&SubstateId>,
+    ) -> Result<(), TrackError> {
+        if let Some(declared_substates) = &self.declared_substates {
+            let is_declared = declared_substates.contains(substate_id)
+                || space_id.map_or(false, |id| declared_substates.contains(id));
+            if !is_declared && self.state_track.is_in_substate_store(substate_id) {
+                return Err(TrackError::NotDeclared);
+            }
+        }
+        Ok(())
+    }
+
     /// Adds a log message.
     pub fn add_log(&mut self, level: Level, message: String) {
         self.application_logs.push((level, message));
     }
 
+    /// Adds an event.
+    pub fn add_event(&mut self, event: ApplicationEvent) {
+        self.events.push(event);
+    }
+
     /// Creates a row with the given key/value
     pub fn create_uuid_substate<V: Into<Substate>>(
         &mut self,
@@ -109,6 +178,75 @@ impl<'s, R: FeeReserve> Track<'s, R> {
         self.state_track.is_root(substate_id)
     }
 
+    /// Creates a checkpoint which subsequent state changes can be rolled back to.
+    ///
+    /// Substates which are borrowed at the time of checkpoint are expected to remain borrowed
+    /// until the checkpoint is either rolled back or released.
+    pub fn checkpoint(&mut self) -> Result<CheckpointId, CheckpointError> {
+        let mut borrowed_substates = HashMap::new();
+        for (substate_id, borrowed) in &self.borrowed_substates {
+            match borrowed {
+                BorrowedSubstate::LoadedMut(substate) => {
+                    borrowed_substates.insert(substate_id.clone(), scrypto_encode(substate));
+                }
+                BorrowedSubstate::Loaded(..) => {}
+                BorrowedSubstate::Taken => return Err(CheckpointError::NotAllowed),
+            }
+        }
+        self.checkpoints.push(TrackCheckpoint {
+            borrowed_substates,
+            new_substates_len: self.new_substates.len(),
+            application_logs_len: self.application_logs.len(),
+            events_len: self.events.len(),
+        });
+        let checkpoint = self.state_track.checkpoint();
+        assert_eq!(checkpoint as usize, self.checkpoints.len() - 1);
+        Ok(checkpoint)
+    }
+
+    /// Rolls back all state changes, application logs and events made since the given checkpoint.
+    pub fn rollback_to(&mut self, checkpoint: CheckpointId) -> Result<(), CheckpointError> {
+        let track_checkpoint = self
+            .checkpoints
+            .get(checkpoint as usize)
+            .ok_or(CheckpointError::NotFound)?;
+        for substate_id in track_checkpoint.borrowed_substates.keys() {
+            if !matches!(
+                self.borrowed_substates.get(substate_id),
+                Some(BorrowedSubstate::LoadedMut(..))
+            ) {
+                return Err(CheckpointError::NotAllowed);
+            }
+        }
+
+        let track_checkpoint = self
+            .checkpoints
+            .drain(checkpoint as usize..)
+            .nth(0)
+            .expect("Checkpoint should exist");
+        for (substate_id, substate) in track_checkpoint.borrowed_substates {
+            self.borrowed_substates.insert(
+                substate_id,
+                BorrowedSubstate::LoadedMut(
+                    scrypto_decode(&substate).expect("Failed to decode checkpoint substate"),
+                ),
+            );
+        }
+        self.new_substates
+            .truncate(track_checkpoint.new_substates_len);
+        self.application_logs
+            .truncate(track_checkpoint.application_logs_len);
+        self.events.truncate(track_checkpoint.events_len);
+        self.state_track.rollback_to(checkpoint);
+        Ok(())
+    }
+
+    /// Discards the given checkpoint and the ones created after it, keeping all state changes.
+    pub fn release_checkpoint(&mut self, checkpoint: CheckpointId) {
+        self.checkpoints.truncate(checkpoint as usize);
+        self.state_track.release_checkpoint(checkpoint);
+    }
+
     // TODO: to read/write a value owned by track requires three coordinated steps:
     // 1. Attempt to acquire the lock
     // 2. Apply the operation
@@ -125,6 +263,8 @@ impl<'s, R: FeeReserve> Track<'s, R> {
         mutable: bool,
         write_through: bool,
     ) -> Result<(), TrackError> {
+        self.check_declared(&substate_id, None)?;
+
         if let Some(current) = self.borrowed_substates.get_mut(&substate_id) {
             if mutable {
                 return Err(TrackError::Reentrancy);
@@ -158,6 +298,8 @@ impl<'s, R: FeeReserve> Track<'s, R> {
                     | SubstateId::Vault(..)
                     | SubstateId::Package(..)
                     | SubstateId::ComponentState(..)
+                    | SubstateId::ComponentAccessRules(..)
+                    | SubstateId::AccessController(..)
                     | SubstateId::System => substate,
                     _ => panic!(
                         "Attempting to borrow unsupported substate {:?}",
@@ -258,31 +400,27 @@ impl<'s, R: FeeReserve> Track<'s, R> {
     }
 
     /// Returns the value of a key value pair
-    pub fn read_key_value(&mut self, parent_address: SubstateId, key: Vec<u8>) -> Substate {
+    pub fn read_key_value(
+        &mut self,
+        parent_address: SubstateId,
+        key: Vec<u8>,
+    ) -> Result<Substate, TrackError> {
         // TODO: consider using a single address as function input
-        let substate_id = match parent_address {
-            SubstateId::NonFungibleSpace(resource_address) => {
-                SubstateId::NonFungible(resource_address, NonFungibleId(key))
-            }
-            SubstateId::KeyValueStoreSpace(kv_store_id) => {
-                SubstateId::KeyValueStoreEntry(kv_store_id, key)
-            }
-            _ => panic!("Unsupported key value"),
-        };
+        let substate_id = key_value_substate_id(&parent_address, key);
+        self.check_declared(&substate_id, Some(&parent_address))?;
 
-        match parent_address {
+        let substate = match parent_address {
             SubstateId::NonFungibleSpace(_) => self
                 .state_track
                 .get_substate(&substate_id)
-                .unwrap_or(Substate::NonFungible(NonFungibleWrapper(None))),
+                .unwrap_or(Substate::NonFungible(None)),
             SubstateId::KeyValueStoreSpace(..) => self
                 .state_track
                 .get_substate(&substate_id)
-                .unwrap_or(Substate::KeyValueStoreEntry(KeyValueStoreEntryWrapper(
-                    None,
-                ))),
+                .unwrap_or(Substate::KeyValueStoreEntry(None)),
             _ => panic!("Invalid keyed value address {:?}", parent_address),
-        }
+        };
+        Ok(substate)
     }
 
     /// Sets a key value
@@ -291,19 +429,13 @@ impl<'s, R: FeeReserve> Track<'s, R> {
         parent_substate_id: SubstateId,
         key: Vec<u8>,
         value: V,
-    ) {
+    ) -> Result<(), TrackError> {
         // TODO: consider using a single address as function input
-        let substate_id = match parent_substate_id {
-            SubstateId::NonFungibleSpace(resource_address) => {
-                SubstateId::NonFungible(resource_address, NonFungibleId(key.clone()))
-            }
-            SubstateId::KeyValueStoreSpace(kv_store_id) => {
-                SubstateId::KeyValueStoreEntry(kv_store_id, key.clone())
-            }
-            _ => panic!("Unsupported key value"),
-        };
+        let substate_id = key_value_substate_id(&parent_substate_id, key);
+        self.check_declared(&substate_id, Some(&parent_substate_id))?;
 
         self.state_track.put_substate(substate_id, value.into());
+        Ok(())
     }
 
     pub fn apply_pre_execution_costs<T: ExecutableTransaction>(
@@ -357,7 +489,7 @@ impl<'s, R: FeeReserve> Track<'s, R> {
 
     pub fn finalize(
         mut self,
-        invoke_result: Result<Vec<Vec<u8>>, RuntimeError>,
+        invoke_result: Result<Vec<InstructionOutput>, RuntimeError>,
         resource_changes: Vec<ResourceChange>, // TODO: wrong abstraction, resource change should be derived from track instead of kernel
     ) -> TrackReceipt {
         let is_success = invoke_result.is_ok();
@@ -371,39 +503,28 @@ impl<'s, R: FeeReserve> Track<'s, R> {
             self.borrowed_substates.clear();
             self.new_substates.clear();
         }
+        self.checkpoints.clear();
 
         // Close fee reserve
-        let fee_summary = self.fee_reserve.finalize();
+        let mut fee_summary = self.fee_reserve.finalize();
         let is_rejection = !fee_summary.loan_fully_repaid;
+        let mut substate_cache_stats = self.state_track.substate_cache_stats();
 
         // Commit fee state changes
         let result = if is_rejection {
-            TransactionResult::Reject(RejectResult {
-                error: match invoke_result {
-                    Ok(..) => RejectionError::SuccessButFeeLoanNotRepaid,
-                    Err(error) => RejectionError::ErrorBeforeFeeLoanRepaid(error),
-                },
-            })
+            TransactionResult::Reject(RejectResult::new(match invoke_result {
+                Ok(..) => RejectionError::SuccessButFeeLoanNotRepaid,
+                Err(error) => RejectionError::ErrorBeforeFeeLoanRepaid(error),
+            }))
         } else {
-            let mut required = fee_summary.burned + fee_summary.tipped;
             let mut collector = ResourceContainer::new_empty(
                 RADIX_TOKEN,
                 ResourceType::Fungible { divisibility: 18 },
             );
-            for (vault_id, mut locked, contingent) in fee_summary.payments.iter().cloned().rev() {
-                let amount = if contingent {
-                    if is_success {
-                        Decimal::min(locked.liquid_amount(), required)
-                    } else {
-                        Decimal::zero()
-                    }
-                } else {
-                    Decimal::min(locked.liquid_amount(), required)
-                };
-
-                // Deduct fee required
-                required = required - amount;
-
+            let amounts = fee_summary.payment_amounts(is_success);
+            for ((vault_id, mut locked, _), amount) in
+                fee_summary.payments.iter().cloned().zip(amounts)
+            {
                 // Collect fees into collector
                 collector
                     .put(
@@ -427,15 +548,43 @@ impl<'s, R: FeeReserve> Track<'s, R> {
                 self.state_track.put_substate_to_base(substate_id, substate);
             }
 
+            // Pay network fee to the treasury, out of the fees actually collected; the share
+            // covered by free credit is never paid, and is reported as burned instead
+            let collected = collector.liquid_amount();
+            if fee_summary.network_fee > collected {
+                fee_summary.burned = fee_summary.burned + fee_summary.network_fee - collected;
+                fee_summary.network_fee = collected;
+            }
+            if fee_summary.network_fee.is_positive() {
+                let network_fee = collector
+                    .take_by_amount(fee_summary.network_fee)
+                    .expect("Failed to extract network fee from fee collector");
+                let substate_id = SubstateId::Vault(SYS_TREASURY_VAULT);
+                let mut substate = self
+                    .state_track
+                    .get_substate_from_base(&substate_id)
+                    .expect("Failed to fetch the treasury vault")
+                    .expect("Treasury vault not found");
+                substate
+                    .vault_mut()
+                    .put(Bucket::new(network_fee))
+                    .expect("Failed to put network fee into the treasury vault");
+                self.state_track.put_substate_to_base(substate_id, substate);
+            }
+
+            substate_cache_stats = self.state_track.substate_cache_stats();
+
             // TODO: update XRD supply or disable it
             // TODO: pay tips to the lead validator
+            // TODO: pay royalties to components
 
             let mut new_component_addresses = Vec::new();
             let mut new_resource_addresses = Vec::new();
             let mut new_package_addresses = Vec::new();
             for substate_id in self.new_substates {
                 match substate_id {
-                    SubstateId::ComponentInfo(component_address) => {
+                    SubstateId::ComponentInfo(component_address)
+                    | SubstateId::AccessController(component_address) => {
                         new_component_addresses.push(component_address)
                     }
                     SubstateId::ResourceManager(resource_address) => {
@@ -448,11 +597,24 @@ impl<'s, R: FeeReserve> Track<'s, R> {
                 }
             }
 
+            let error_code = invoke_result.as_ref().err().map(RuntimeError::error_code);
+            let (outcome, instruction_outputs) = match invoke_result {
+                Ok(instruction_outputs) => (
+                    TransactionOutcome::Success(
+                        instruction_outputs
+                            .iter()
+                            .map(|o| o.output.clone())
+                            .collect(),
+                    ),
+                    instruction_outputs,
+                ),
+                Err(error) => (TransactionOutcome::Failure(error), Vec::new()),
+            };
+
             TransactionResult::Commit(CommitResult {
-                outcome: match invoke_result {
-                    Ok(output) => TransactionOutcome::Success(output),
-                    Err(error) => TransactionOutcome::Failure(error),
-                },
+                error_code,
+                outcome,
+                instruction_outputs,
                 state_updates: self.state_track.into_base().generate_diff(),
                 entity_changes: EntityChanges {
                     new_package_addresses,
@@ -463,10 +625,34 @@ impl<'s, R: FeeReserve> Track<'s, R> {
             })
         };
 
+        // Events only describe the effects of committed, successful transactions
+        let events = match &result {
+            TransactionResult::Commit(CommitResult {
+                outcome: TransactionOutcome::Success(..),
+                ..
+            }) => self.events,
+            _ => Vec::new(),
+        };
+
         TrackReceipt {
             fee_summary,
             application_logs: self.application_logs,
+            events,
+            substate_cache_stats,
             result,
         }
     }
 }
+
+/// Returns the id of the substate holding the value of a key in a key value space.
+pub fn key_value_substate_id(parent_substate_id: &SubstateId, key: Vec<u8>) -> SubstateId {
+    match parent_substate_id {
+        SubstateId::NonFungibleSpace(resource_address) => {
+            SubstateId::NonFungible(*resource_address, NonFungibleId(key))
+        }
+        SubstateId::KeyValueStoreSpace(kv_store_id) => {
+            SubstateId::KeyValueStoreEntry(*kv_store_id, key)
+        }
+        _ => panic!("Unsupported key value"),
+    }
+}
@@ -10,6 +10,17 @@ use crate::state_manager::StateDiff;
 use crate::state_manager::VirtualSubstateId;
 use crate::types::*;
 
+/// Statistics of the substate cache of the state track
+#[derive(Debug, Clone, Default, PartialEq, Eq, TypeId, Encode, Decode)]
+pub struct SubstateCacheStats {
+    /// The number of substate reads served by the state track
+    pub hits: u32,
+    /// The number of substate reads which had to load from the substate store
+    pub misses: u32,
+    /// The number of distinct substates written to
+    pub dirty: u32,
+}
+
 /// Keeps track of state changes that that are non-reversible, such as fee payments
 pub struct BaseStateTrack<'s> {
     /// The parent state track
@@ -22,6 +33,10 @@ pub struct BaseStateTrack<'s> {
     ///
     substates: IndexMap<SubstateId, Option<Vec<u8>>>,
     new_root_substates: IndexSet<SubstateId>,
+    /// Substates written to, including those committed from the app state track
+    dirty_substates: IndexSet<SubstateId>,
+    cache_hits: u32,
+    cache_misses: u32,
 }
 
 impl<'s> BaseStateTrack<'s> {
@@ -30,6 +45,25 @@ impl<'s> BaseStateTrack<'s> {
             substate_store,
             new_root_substates: IndexSet::new(),
             substates: IndexMap::new(),
+            dirty_substates: IndexSet::new(),
+            cache_hits: 0,
+            cache_misses: 0,
+        }
+    }
+
+    /// Loads a substate from the substate store, counting it as a cache miss
+    fn load_substate(&mut self, substate_id: &SubstateId) -> Option<Vec<u8>> {
+        self.cache_misses += 1;
+        self.substate_store
+            .get_substate(substate_id)
+            .map(|s| scrypto_encode(&s.substate))
+    }
+
+    pub fn substate_cache_stats(&self) -> SubstateCacheStats {
+        SubstateCacheStats {
+            hits: self.cache_hits,
+            misses: self.cache_misses,
+            dirty: self.dirty_substates.len() as u32,
         }
     }
 
@@ -71,7 +105,7 @@ impl<'s> BaseStateTrack<'s> {
 
                         let output_value = OutputValue {
                             substate: scrypto_decode(&substate)
-                                .expect("Failed to decode NonFungibleWrapper substate"),
+                                .expect("Failed to decode NonFungible substate"),
                             version: next_version,
                         };
                         diff.up_substates.insert(substate_id.clone(), output_value);
@@ -92,7 +126,7 @@ impl<'s> BaseStateTrack<'s> {
 
                         let output_value = OutputValue {
                             substate: scrypto_decode(&substate)
-                                .expect("Failed to decode KeyValueStoreEntryWrapper substate"),
+                                .expect("Failed to decode KeyValueStoreEntry substate"),
                             version: next_version,
                         };
                         diff.up_substates.insert(substate_id.clone(), output_value);
@@ -162,6 +196,17 @@ pub enum StateTrackError {
     RENodeAlreadyTouched,
 }
 
+/// The undo log of a checkpoint within the app state track
+struct AppStateCheckpoint {
+    /// The values of the substates changed since the checkpoint, as they were before the first change.
+    /// `None` means the substate was never touched before the checkpoint.
+    substates: IndexMap<SubstateId, Option<Option<Vec<u8>>>>,
+    /// The number of new root substates at the checkpoint
+    new_root_substates_len: usize,
+    /// The number of dirty substates at the checkpoint
+    dirty_substates_len: usize,
+}
+
 /// Keeps track of state changes that may be rolled back according to transaction status
 pub struct AppStateTrack<'s> {
     /// The parent state track
@@ -169,6 +214,10 @@ pub struct AppStateTrack<'s> {
     /// Substates either created during the transaction or loaded from the base state track
     substates: IndexMap<SubstateId, Option<Vec<u8>>>,
     new_root_substates: IndexSet<SubstateId>,
+    /// Substates written to, in the order they were first written
+    dirty_substates: IndexSet<SubstateId>,
+    /// Checkpoints which state changes can be rolled back to, indexed by checkpoint id
+    checkpoints: Vec<AppStateCheckpoint>,
 }
 
 impl<'s> AppStateTrack<'s> {
@@ -177,6 +226,8 @@ impl<'s> AppStateTrack<'s> {
             base_state_track,
             substates: IndexMap::new(),
             new_root_substates: IndexSet::new(),
+            dirty_substates: IndexSet::new(),
+            checkpoints: Vec::new(),
         }
     }
 
@@ -200,24 +251,34 @@ impl<'s> AppStateTrack<'s> {
         self.new_root_substates.insert(substate_id);
     }
 
+    /// Returns whether the substate existed in the substate store before the transaction
+    pub fn is_in_substate_store(&self, substate_id: &SubstateId) -> bool {
+        self.base_state_track
+            .substate_store
+            .get_substate(substate_id)
+            .is_some()
+    }
+
     /// Returns a copy of the substate associated with the given address, if exists
     pub fn get_substate(&mut self, substate_id: &SubstateId) -> Option<Substate> {
+        if !self.substates.contains_key(substate_id) {
+            // First, try to copy it from the base track
+            let substate = match self.base_state_track.substates.get(substate_id) {
+                Some(substate) => {
+                    self.base_state_track.cache_hits += 1;
+                    substate.clone()
+                }
+                // If not found, load from the substate store
+                None => self.base_state_track.load_substate(substate_id),
+            };
+            self.substates.insert(substate_id.clone(), substate);
+        } else {
+            self.base_state_track.cache_hits += 1;
+        }
+
         self.substates
-            .entry(substate_id.clone())
-            .or_insert_with(|| {
-                // First, try to copy it from the base track
-                self.base_state_track
-                    .substates
-                    .get(substate_id)
-                    .cloned()
-                    .unwrap_or_else(|| {
-                        // If not found, load from the substate store
-                        self.base_state_track
-                            .substate_store
-                            .get_substate(substate_id)
-                            .map(|s| scrypto_encode(&s.substate))
-                    })
-            })
+            .get(substate_id)
+            .expect("Substate should be cached")
             .as_ref()
             .map(|x| {
                 scrypto_decode(x).expect(&format!("Failed to decode substate {:?}", substate_id))
@@ -233,17 +294,21 @@ impl<'s> AppStateTrack<'s> {
             return Err(StateTrackError::RENodeAlreadyTouched);
         }
 
+        if !self.base_state_track.substates.contains_key(substate_id) {
+            // Load from the substate store
+            let substate = self.base_state_track.load_substate(substate_id);
+            self.base_state_track
+                .substates
+                .insert(substate_id.clone(), substate);
+        } else {
+            self.base_state_track.cache_hits += 1;
+        }
+
         Ok(self
             .base_state_track
             .substates
-            .entry(substate_id.clone())
-            .or_insert_with(|| {
-                // Load from the substate store
-                self.base_state_track
-                    .substate_store
-                    .get_substate(substate_id)
-                    .map(|s| scrypto_encode(&s.substate))
-            })
+            .get(substate_id)
+            .expect("Substate should be cached")
             .as_ref()
             .map(|x| {
                 scrypto_decode(x).expect(&format!("Failed to decode substate {:?}", substate_id))
@@ -252,6 +317,14 @@ impl<'s> AppStateTrack<'s> {
 
     /// Creates a new substate and updates an existing one
     pub fn put_substate(&mut self, substate_id: SubstateId, substate: Substate) {
+        if let Some(checkpoint) = self.checkpoints.last_mut() {
+            if !checkpoint.substates.contains_key(&substate_id) {
+                let prior = self.substates.get(&substate_id).cloned();
+                checkpoint.substates.insert(substate_id.clone(), prior);
+            }
+        }
+
+        self.dirty_substates.insert(substate_id.clone());
         self.substates
             .insert(substate_id, Some(scrypto_encode(&substate)));
     }
@@ -260,11 +333,58 @@ impl<'s> AppStateTrack<'s> {
     pub fn put_substate_to_base(&mut self, substate_id: SubstateId, substate: Substate) {
         assert!(!self.substates.contains_key(&substate_id));
 
+        self.base_state_track
+            .dirty_substates
+            .insert(substate_id.clone());
         self.base_state_track
             .substates
             .insert(substate_id, Some(scrypto_encode(&substate)));
     }
 
+    /// Creates a checkpoint which subsequent state changes can be rolled back to
+    pub fn checkpoint(&mut self) -> CheckpointId {
+        self.checkpoints.push(AppStateCheckpoint {
+            substates: IndexMap::new(),
+            new_root_substates_len: self.new_root_substates.len(),
+            dirty_substates_len: self.dirty_substates.len(),
+        });
+        (self.checkpoints.len() - 1) as CheckpointId
+    }
+
+    /// Rolls back all state changes made since the given checkpoint, and discards the checkpoint
+    /// along with all the checkpoints created after it.
+    pub fn rollback_to(&mut self, checkpoint: CheckpointId) {
+        while self.checkpoints.len() > checkpoint as usize {
+            let layer = self.checkpoints.pop().expect("Checkpoint should exist");
+            for (substate_id, prior) in layer.substates {
+                match prior {
+                    Some(value) => {
+                        self.substates.insert(substate_id, value);
+                    }
+                    None => {
+                        self.substates.shift_remove(&substate_id);
+                    }
+                }
+            }
+            self.new_root_substates
+                .truncate(layer.new_root_substates_len);
+            self.dirty_substates.truncate(layer.dirty_substates_len);
+        }
+    }
+
+    /// Discards the given checkpoint along with all the checkpoints created after it, keeping
+    /// the state changes made since.
+    pub fn release_checkpoint(&mut self, checkpoint: CheckpointId) {
+        while self.checkpoints.len() > checkpoint as usize {
+            let layer = self.checkpoints.pop().expect("Checkpoint should exist");
+            if let Some(parent) = self.checkpoints.last_mut() {
+                for (substate_id, prior) in layer.substates {
+                    parent.substates.entry(substate_id).or_insert(prior);
+                }
+            }
+        }
+    }
+
     /// Commit all state changes into base state track
     pub fn commit(&mut self) {
         self.base_state_track
@@ -273,12 +393,29 @@ impl<'s> AppStateTrack<'s> {
         self.base_state_track
             .new_root_substates
             .extend(self.new_root_substates.drain(RangeFull));
+        self.base_state_track
+            .dirty_substates
+            .extend(self.dirty_substates.drain(RangeFull));
+        self.checkpoints.clear();
     }
 
     /// Rollback all state changes
     pub fn rollback(&mut self) {
         self.substates.clear();
         self.new_root_substates.clear();
+        self.dirty_substates.clear();
+        self.checkpoints.clear();
+    }
+
+    /// Returns the statistics of the substate cache, including uncommitted writes
+    pub fn substate_cache_stats(&self) -> SubstateCacheStats {
+        let mut stats = self.base_state_track.substate_cache_stats();
+        stats.dirty += self
+            .dirty_substates
+            .iter()
+            .filter(|id| !self.base_state_track.dirty_substates.contains(*id))
+            .count() as u32;
+        stats
     }
 
     /// Unwraps into the base state track
@@ -1,7 +1,10 @@
+use crate::constants::DEFAULT_DECODE_LIMITS;
 use crate::engine::RuntimeError;
 use crate::engine::{HeapRENode, SystemApi};
 use crate::fee::*;
-use crate::model::{ComponentInfo, ComponentState, HeapKeyValueStore, InvokeError};
+use crate::model::{
+    ComponentAccessRules, ComponentInfo, ComponentState, HeapKeyValueStore, InvokeError,
+};
 use crate::types::*;
 use crate::wasm::*;
 
@@ -57,7 +60,7 @@ where
         fn_identifier: FnIdentifier,
         input: Vec<u8>,
     ) -> Result<ScryptoValue, RuntimeError> {
-        let call_data = ScryptoValue::from_slice(&input)
+        let call_data = ScryptoValue::from_slice_with_limits(&input, &DEFAULT_DECODE_LIMITS)
             .map_err(|e| RuntimeError::KernelError(KernelError::DecodeError(e)))?;
         self.system_api.invoke_function(fn_identifier, call_data)
     }
@@ -68,7 +71,7 @@ where
         fn_identifier: FnIdentifier,
         input: Vec<u8>,
     ) -> Result<ScryptoValue, RuntimeError> {
-        let call_data = ScryptoValue::from_slice(&input)
+        let call_data = ScryptoValue::from_slice_with_limits(&input, &DEFAULT_DECODE_LIMITS)
             .map_err(|e| RuntimeError::KernelError(KernelError::DecodeError(e)))?;
         self.system_api
             .invoke_method(receiver, fn_identifier, call_data)
@@ -95,10 +98,10 @@ where
                 // TODO: Check state against blueprint schema
 
                 // Create component
-                let component_info =
-                    ComponentInfo::new(package_address, blueprint_name, Vec::new());
+                let component_info = ComponentInfo::new(package_address, blueprint_name);
                 let component_state = ComponentState::new(state);
-                HeapRENode::Component(component_info, component_state)
+                let access_rules = ComponentAccessRules::new(Vec::new());
+                HeapRENode::Component(component_info, component_state, access_rules)
             }
             ScryptoRENode::KeyValueStore => HeapRENode::KeyValueStore(HeapKeyValueStore::new()),
         };
@@ -137,8 +140,9 @@ where
     ) -> Result<ScryptoValue, RuntimeError> {
         match &substate_id {
             SubstateId::KeyValueStoreEntry(_kv_store_id, key_bytes) => {
-                let key_data = ScryptoValue::from_slice(&key_bytes)
-                    .map_err(|e| RuntimeError::KernelError(KernelError::DecodeError(e)))?;
+                let key_data =
+                    ScryptoValue::from_slice_with_limits(&key_bytes, &DEFAULT_DECODE_LIMITS)
+                        .map_err(|e| RuntimeError::KernelError(KernelError::DecodeError(e)))?;
                 Self::verify_stored_key(&key_data)?;
             }
             _ => {}
@@ -154,18 +158,34 @@ where
     ) -> Result<ScryptoValue, RuntimeError> {
         match &substate_id {
             SubstateId::KeyValueStoreEntry(_kv_store_id, key_bytes) => {
-                let key_data = ScryptoValue::from_slice(&key_bytes)
-                    .map_err(|e| RuntimeError::KernelError(KernelError::DecodeError(e)))?;
+                let key_data =
+                    ScryptoValue::from_slice_with_limits(&key_bytes, &DEFAULT_DECODE_LIMITS)
+                        .map_err(|e| RuntimeError::KernelError(KernelError::DecodeError(e)))?;
                 Self::verify_stored_key(&key_data)?;
             }
             _ => {}
         }
-        let scrypto_value = ScryptoValue::from_slice(&value)
+        let scrypto_value = ScryptoValue::from_slice_with_limits(&value, &DEFAULT_DECODE_LIMITS)
             .map_err(|e| RuntimeError::KernelError(KernelError::DecodeError(e)))?;
         self.system_api.substate_write(substate_id, scrypto_value)?;
         Ok(ScryptoValue::unit())
     }
 
+    fn handle_kv_store_put_if_absent(
+        &mut self,
+        kv_store_id: KeyValueStoreId,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<bool, RuntimeError> {
+        let key_data = ScryptoValue::from_slice_with_limits(&key, &DEFAULT_DECODE_LIMITS)
+            .map_err(|e| RuntimeError::KernelError(KernelError::DecodeError(e)))?;
+        Self::verify_stored_key(&key_data)?;
+        let scrypto_value = ScryptoValue::from_slice_with_limits(&value, &DEFAULT_DECODE_LIMITS)
+            .map_err(|e| RuntimeError::KernelError(KernelError::DecodeError(e)))?;
+        self.system_api
+            .kv_store_put_if_absent(kv_store_id, key, scrypto_value)
+    }
+
     fn handle_get_actor(&mut self) -> Result<ScryptoActor, RuntimeError> {
         return Ok(self.actor.clone());
     }
@@ -174,10 +194,40 @@ where
         self.system_api.generate_uuid()
     }
 
+    fn handle_generate_random_bytes(&mut self, len: u32) -> Result<Vec<u8>, RuntimeError> {
+        self.system_api.generate_random_bytes(len)
+    }
+
+    fn handle_get_remaining_cost_units(&mut self) -> Result<u32, RuntimeError> {
+        self.system_api.remaining_cost_units()
+    }
+
+    fn handle_hash(
+        &mut self,
+        algorithm: HashAlgorithm,
+        data: Vec<u8>,
+    ) -> Result<Hash, RuntimeError> {
+        self.system_api.compute_hash(algorithm, &data)
+    }
+
+    fn handle_verify_signature(
+        &mut self,
+        message: Vec<u8>,
+        public_key: PublicKey,
+        signature: Signature,
+    ) -> Result<bool, RuntimeError> {
+        self.system_api
+            .verify_signature(&message, public_key, signature)
+    }
+
     fn handle_emit_log(&mut self, level: Level, message: String) -> Result<(), RuntimeError> {
         self.system_api.emit_log(level, message)
     }
 
+    fn handle_emit_event(&mut self, name: String, data: Vec<u8>) -> Result<(), RuntimeError> {
+        self.system_api.emit_event(name, data)
+    }
+
     fn handle_check_access_rule(
         &mut self,
         access_rule: AccessRule,
@@ -185,22 +235,46 @@ where
     ) -> Result<bool, RuntimeError> {
         self.system_api.check_access_rule(access_rule, proof_ids)
     }
-}
 
-fn encode<T: Encode>(output: T) -> ScryptoValue {
-    ScryptoValue::from_typed(&output)
-}
+    fn handle_execution_checkpoint(&mut self) -> Result<CheckpointId, RuntimeError> {
+        self.system_api.execution_checkpoint()
+    }
 
-impl<'y, 's, Y, W, I, R> WasmRuntime for RadixEngineWasmRuntime<'y, 's, Y, W, I, R>
-where
-    Y: SystemApi<'s, W, I, R>,
-    W: WasmEngine<I>,
-    I: WasmInstance,
-    R: FeeReserve,
-{
-    fn main(&mut self, input: ScryptoValue) -> Result<ScryptoValue, InvokeError<WasmError>> {
-        let input: RadixEngineInput = scrypto_decode(&input.raw)
-            .map_err(|_| InvokeError::Error(WasmError::InvalidRadixEngineInput))?;
+    fn handle_rollback_to(&mut self, checkpoint: CheckpointId) -> Result<(), RuntimeError> {
+        self.system_api.rollback_to(checkpoint)
+    }
+
+    fn handle_batch(
+        &mut self,
+        inputs: Vec<RadixEngineInput>,
+    ) -> Result<Vec<Vec<u8>>, InvokeError<WasmError>> {
+        // Reject the whole batch upfront, so that nothing is executed if any input is invalid
+        if !inputs.iter().all(Self::is_batchable) {
+            return Err(InvokeError::Error(WasmError::InvalidBatchInput));
+        }
+
+        let mut outputs = Vec::new();
+        for input in inputs {
+            outputs.push(self.handle(input)?.raw);
+        }
+        Ok(outputs)
+    }
+
+    fn is_batchable(input: &RadixEngineInput) -> bool {
+        matches!(
+            input,
+            RadixEngineInput::SubstateRead(..)
+                | RadixEngineInput::GetActor()
+                | RadixEngineInput::EmitLog(..)
+                | RadixEngineInput::GenerateUuid()
+                | RadixEngineInput::GenerateRandomBytes(..)
+                | RadixEngineInput::GetRemainingCostUnits()
+                | RadixEngineInput::Hash(..)
+                | RadixEngineInput::VerifySignature(..)
+        )
+    }
+
+    fn handle(&mut self, input: RadixEngineInput) -> Result<ScryptoValue, InvokeError<WasmError>> {
         match input {
             RadixEngineInput::InvokeFunction(fn_identifier, input_bytes) => {
                 self.handle_invoke_function(fn_identifier, input_bytes)
@@ -214,17 +288,60 @@ where
             RadixEngineInput::SubstateWrite(substate_id, value) => {
                 self.handle_substate_write(substate_id, value)
             }
+            RadixEngineInput::KeyValueStorePutIfAbsent(kv_store_id, key, value) => self
+                .handle_kv_store_put_if_absent(kv_store_id, key, value)
+                .map(encode),
             RadixEngineInput::GetActor() => self.handle_get_actor().map(encode),
             RadixEngineInput::GenerateUuid() => self.handle_generate_uuid().map(encode),
+            RadixEngineInput::GenerateRandomBytes(len) => {
+                self.handle_generate_random_bytes(len).map(encode)
+            }
+            RadixEngineInput::GetRemainingCostUnits() => {
+                self.handle_get_remaining_cost_units().map(encode)
+            }
+            RadixEngineInput::Hash(algorithm, data) => {
+                self.handle_hash(algorithm, data).map(encode)
+            }
+            RadixEngineInput::VerifySignature(message, public_key, signature) => self
+                .handle_verify_signature(message, public_key, signature)
+                .map(encode),
             RadixEngineInput::EmitLog(level, message) => {
                 self.handle_emit_log(level, message).map(encode)
             }
+            RadixEngineInput::EmitEvent(name, data) => {
+                self.handle_emit_event(name, data).map(encode)
+            }
             RadixEngineInput::CheckAccessRule(rule, proof_ids) => {
                 self.handle_check_access_rule(rule, proof_ids).map(encode)
             }
+            RadixEngineInput::ExecutionCheckpoint() => {
+                self.handle_execution_checkpoint().map(encode)
+            }
+            RadixEngineInput::RollbackTo(checkpoint) => {
+                self.handle_rollback_to(checkpoint).map(encode)
+            }
+            RadixEngineInput::Batch(inputs) => return self.handle_batch(inputs).map(encode),
         }
         .map_err(InvokeError::downstream)
     }
+}
+
+fn encode<T: Encode>(output: T) -> ScryptoValue {
+    ScryptoValue::from_typed(&output)
+}
+
+impl<'y, 's, Y, W, I, R> WasmRuntime for RadixEngineWasmRuntime<'y, 's, Y, W, I, R>
+where
+    Y: SystemApi<'s, W, I, R>,
+    W: WasmEngine<I>,
+    I: WasmInstance,
+    R: FeeReserve,
+{
+    fn main(&mut self, input: ScryptoValue) -> Result<ScryptoValue, InvokeError<WasmError>> {
+        let input: RadixEngineInput = scrypto_decode(&input.raw)
+            .map_err(|_| InvokeError::Error(WasmError::InvalidRadixEngineInput))?;
+        self.handle(input)
+    }
 
     fn consume_cost_units(&mut self, n: u32) -> Result<(), InvokeError<WasmError>> {
         self.system_api
@@ -1,5 +1,5 @@
 use crate::constants::{DEFAULT_COST_UNIT_LIMIT, DEFAULT_COST_UNIT_PRICE, DEFAULT_SYSTEM_LOAN};
-use crate::fee::FeeSummary;
+use crate::fee::{CallFrameCost, FeeSummary};
 use crate::model::ResourceContainer;
 use crate::types::*;
 use sbor::rust::cmp::min;
@@ -10,6 +10,40 @@ pub enum FeeReserveError {
     Overflow,
     LimitExceeded,
     SystemLoanNotCleared,
+    InvalidDistribution,
+}
+
+/// How the base fee, i.e. the fee excluding tips, is split. Tips always go to validators.
+///
+/// The percentages must add up to 100.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, TypeId)]
+pub struct FeeDistribution {
+    /// The percentage of base fee burned
+    pub burn_percentage: u8,
+    /// The percentage of base fee paid into the network treasury
+    pub network_percentage: u8,
+    /// The percentage of base fee paid as royalties to components, pro rata to the cost units
+    /// consumed while executing them. Any share not attributable to a component is burned.
+    pub royalty_percentage: u8,
+}
+
+impl FeeDistribution {
+    pub fn is_valid(&self) -> bool {
+        self.burn_percentage as u32
+            + self.network_percentage as u32
+            + self.royalty_percentage as u32
+            == 100
+    }
+}
+
+impl Default for FeeDistribution {
+    fn default() -> Self {
+        Self {
+            burn_percentage: 100,
+            network_percentage: 0,
+            royalty_percentage: 0,
+        }
+    }
 }
 
 pub trait FeeReserve {
@@ -27,6 +61,12 @@ pub trait FeeReserve {
         contingent: bool,
     ) -> Result<ResourceContainer, FeeReserveError>;
 
+    /// Attributes already consumed cost units to a component, for royalty distribution.
+    fn attribute_to_component(&mut self, n: u32, component_address: ComponentAddress);
+
+    /// Attributes already consumed cost units to a call frame, for the cost breakdown.
+    fn attribute_to_call_frame(&mut self, n: u32, depth: usize, fn_identifier: &FnIdentifier);
+
     fn finalize(self) -> FeeSummary;
 
     fn limit(&self) -> u32;
@@ -38,6 +78,14 @@ pub trait FeeReserve {
     fn balance(&self) -> u32;
 
     fn owed(&self) -> u32;
+
+    /// Returns the cost units that can still be consumed, bounded by both the limit and the balance.
+    fn remaining(&self) -> u32 {
+        self.limit()
+            .saturating_sub(self.consumed_instant())
+            .saturating_sub(self.consumed_deferred())
+            .min(self.balance())
+    }
 }
 
 pub struct SystemLoanFeeReserve {
@@ -61,6 +109,14 @@ pub struct SystemLoanFeeReserve {
     check_point: u32,
     /// Cost breakdown
     cost_breakdown: HashMap<String, u32>,
+    /// How the base fee is distributed
+    distribution: FeeDistribution,
+    /// The cost units consumed while executing each component
+    attributions: HashMap<ComponentAddress, u32>,
+    /// The cost units consumed by each call frame
+    call_frame_breakdown: Vec<CallFrameCost>,
+    /// The free credit granted, in cost units
+    credited: u32,
 }
 
 impl SystemLoanFeeReserve {
@@ -81,10 +137,26 @@ impl SystemLoanFeeReserve {
             limit: cost_unit_limit,
             check_point: system_loan,
             cost_breakdown: HashMap::new(),
+            distribution: FeeDistribution::default(),
+            attributions: HashMap::new(),
+            call_frame_breakdown: Vec::new(),
+            credited: 0,
         }
     }
 
-    /// Adds free credit.
+    pub fn with_distribution(
+        mut self,
+        distribution: FeeDistribution,
+    ) -> Result<Self, FeeReserveError> {
+        if !distribution.is_valid() {
+            return Err(FeeReserveError::InvalidDistribution);
+        }
+        self.distribution = distribution;
+        Ok(self)
+    }
+
+    /// Adds credit, which is not reported in the fee summary, e.g. to preview a transaction
+    /// without locking fee.
     ///
     /// Note that overflow is not checked.
     pub fn credit(&mut self, n: u32) {
@@ -92,6 +164,15 @@ impl SystemLoanFeeReserve {
         self.owed = self.owed - repay;
         self.balance = self.balance + (n - repay);
     }
+
+    /// Adds free credit, which is reported in the fee summary as covering fee in place of
+    /// fee payments.
+    ///
+    /// Note that overflow is not checked.
+    pub fn credit_free(&mut self, n: u32) {
+        self.credited = self.credited + n;
+        self.credit(n);
+    }
 }
 
 impl FeeReserve for SystemLoanFeeReserve {
@@ -182,6 +263,28 @@ impl FeeReserve for SystemLoanFeeReserve {
         Ok(fee)
     }
 
+    fn attribute_to_component(&mut self, n: u32, component_address: ComponentAddress) {
+        self.attributions
+            .entry(component_address)
+            .or_default()
+            .add_assign(n);
+    }
+
+    fn attribute_to_call_frame(&mut self, n: u32, depth: usize, fn_identifier: &FnIdentifier) {
+        match self
+            .call_frame_breakdown
+            .iter_mut()
+            .find(|c| c.depth == depth && c.fn_identifier.eq(fn_identifier))
+        {
+            Some(call_frame_cost) => call_frame_cost.cost_units += n,
+            None => self.call_frame_breakdown.push(CallFrameCost {
+                depth,
+                fn_identifier: fn_identifier.clone(),
+                cost_units: n,
+            }),
+        }
+    }
+
     fn finalize(mut self) -> FeeSummary {
         if self.owed > 0 && self.balance != 0 {
             let n = u32::min(self.owed, self.balance);
@@ -190,16 +293,36 @@ impl FeeReserve for SystemLoanFeeReserve {
         }
 
         let consumed = self.consumed_instant + self.consumed_deferred;
+        let base_fee = self.cost_unit_price * consumed;
+        let network_fee = base_fee * self.distribution.network_percentage / 100;
+        let royalty_price = self.cost_unit_price * self.distribution.royalty_percentage / 100;
+        let royalties: HashMap<ComponentAddress, Decimal> = self
+            .attributions
+            .into_iter()
+            .map(|(component_address, n)| (component_address, royalty_price * n))
+            .collect();
+        let royalty_total = royalties
+            .values()
+            .fold(Decimal::zero(), |total, royalty| total + *royalty);
+
+        let effective_cost_unit_price =
+            self.cost_unit_price + self.cost_unit_price * self.tip_percentage / 100;
+        let free_credit = effective_cost_unit_price * u32::min(self.credited, consumed);
+
         FeeSummary {
             loan_fully_repaid: self.owed == 0,
             cost_unit_limit: self.limit,
             cost_unit_consumed: consumed,
             cost_unit_price: self.cost_unit_price,
             tip_percentage: self.tip_percentage,
-            burned: self.cost_unit_price * consumed,
+            burned: base_fee - network_fee - royalty_total,
             tipped: self.cost_unit_price * self.tip_percentage / 100 * consumed,
+            network_fee,
+            royalties,
+            free_credit,
             payments: self.payments,
             cost_breakdown: self.cost_breakdown,
+            call_frame_breakdown: self.call_frame_breakdown,
         }
     }
 
@@ -240,9 +363,12 @@ impl Default for SystemLoanFeeReserve {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fee::CostCategory;
     use scrypto::{constants::RADIX_TOKEN, crypto::Hash};
 
     const TEST_VAULT_ID: VaultId = (Hash([0u8; 32]), 1);
+    const TEST_VAULT_ID_2: VaultId = (Hash([0u8; 32]), 2);
+    const TEST_COMPONENT_ADDRESS: ComponentAddress = ComponentAddress::Normal([1u8; 26]);
 
     fn xrd<T: Into<Decimal>>(amount: T) -> ResourceContainer {
         ResourceContainer::new_fungible(RADIX_TOKEN, 18, amount.into())
@@ -288,6 +414,129 @@ mod tests {
         assert_eq!(400, fee_reserve.owed());
     }
 
+    #[test]
+    fn test_tip_percentage() {
+        let mut fee_reserve = SystemLoanFeeReserve::new(100, 10, 1.into(), 0);
+        fee_reserve.repay(TEST_VAULT_ID, xrd(22), false).unwrap();
+        assert_eq!(20, fee_reserve.balance());
+        fee_reserve.consume(20, "test", false).unwrap();
+        let summary = fee_reserve.finalize();
+        assert_eq!(Decimal::from(20), summary.base_fee());
+        assert_eq!(Decimal::from(2), summary.tipped);
+        assert_eq!(Decimal::from(22), summary.total_fee());
+    }
+
+    #[test]
+    fn test_cost_breakdown() {
+        let fn_identifier = FnIdentifier::Native(NativeFnIdentifier::TransactionProcessor(
+            TransactionProcessorFnIdentifier::Run,
+        ));
+        let mut fee_reserve = SystemLoanFeeReserve::new(100, 0, 1.into(), 100);
+        fee_reserve.consume(10, "base_fee", false).unwrap();
+        fee_reserve.consume(20, "run_wasm", false).unwrap();
+        fee_reserve.consume(5, "read_substate", false).unwrap();
+        fee_reserve.attribute_to_call_frame(20, 0, &fn_identifier);
+        fee_reserve.attribute_to_call_frame(5, 0, &fn_identifier);
+        let summary = fee_reserve.finalize();
+        assert_eq!(
+            BTreeMap::from([
+                (CostCategory::Transaction, 10),
+                (CostCategory::WasmExecution, 20),
+                (CostCategory::SubstateRead, 5),
+            ]),
+            summary.cost_breakdown_by_category()
+        );
+        assert_eq!(
+            vec![CallFrameCost {
+                depth: 0,
+                fn_identifier,
+                cost_units: 25
+            }],
+            summary.call_frame_breakdown
+        );
+    }
+
+    #[test]
+    fn test_fee_distribution() {
+        let mut fee_reserve = SystemLoanFeeReserve::new(100, 10, 1.into(), 500)
+            .with_distribution(FeeDistribution {
+                burn_percentage: 50,
+                network_percentage: 30,
+                royalty_percentage: 20,
+            })
+            .unwrap();
+        fee_reserve.consume(100, "test", false).unwrap();
+        fee_reserve.attribute_to_component(40, TEST_COMPONENT_ADDRESS);
+        let summary = fee_reserve.finalize();
+        assert_eq!(Decimal::from(10), summary.tipped);
+        assert_eq!(Decimal::from(30), summary.network_fee);
+        assert_eq!(Decimal::from(8), summary.royalties[&TEST_COMPONENT_ADDRESS]);
+        assert_eq!(Decimal::from(62), summary.burned);
+        assert_eq!(Decimal::from(110), summary.total_fee());
+    }
+
+    #[test]
+    fn test_invalid_fee_distribution() {
+        let result =
+            SystemLoanFeeReserve::new(100, 10, 1.into(), 500).with_distribution(FeeDistribution {
+                burn_percentage: 50,
+                network_percentage: 30,
+                royalty_percentage: 30,
+            });
+        assert!(matches!(result, Err(FeeReserveError::InvalidDistribution)));
+    }
+
+    #[test]
+    fn test_contingent_repay_does_not_repay_loan() {
+        let mut fee_reserve = SystemLoanFeeReserve::new(100, 0, 1.into(), 10);
+        fee_reserve.consume(5, "test", false).unwrap();
+        fee_reserve.repay(TEST_VAULT_ID, xrd(10), true).unwrap();
+        assert_eq!(5, fee_reserve.balance());
+        assert_eq!(10, fee_reserve.owed());
+        let summary = fee_reserve.finalize();
+        assert!(!summary.loan_fully_repaid);
+        assert_eq!(vec![(TEST_VAULT_ID, xrd(10), true)], summary.payments);
+    }
+
+    #[test]
+    fn test_free_credit() {
+        let mut fee_reserve = SystemLoanFeeReserve::new(100, 0, 1.into(), 10);
+        fee_reserve.credit_free(15);
+        fee_reserve.repay(TEST_VAULT_ID, xrd(10), false).unwrap();
+        fee_reserve.consume(20, "test", false).unwrap();
+        let summary = fee_reserve.finalize();
+        assert!(summary.loan_fully_repaid);
+        assert_eq!(Decimal::from(15), summary.free_credit);
+        assert_eq!(vec![Decimal::from(5)], summary.payment_amounts(true));
+    }
+
+    #[test]
+    fn test_remaining() {
+        let mut fee_reserve = SystemLoanFeeReserve::new(20, 0, 1.into(), 5);
+        fee_reserve.consume(2, "test", false).unwrap();
+        assert_eq!(3, fee_reserve.remaining());
+        fee_reserve.repay(TEST_VAULT_ID, xrd(30), false).unwrap();
+        assert_eq!(18, fee_reserve.remaining());
+    }
+
+    #[test]
+    fn test_payments_are_charged_pro_rata() {
+        let mut fee_reserve = SystemLoanFeeReserve::new(100, 0, 1.into(), 0);
+        fee_reserve.repay(TEST_VAULT_ID, xrd(30), false).unwrap();
+        fee_reserve.repay(TEST_VAULT_ID_2, xrd(10), false).unwrap();
+        fee_reserve.repay(TEST_VAULT_ID_2, xrd(10), true).unwrap();
+        fee_reserve.consume(20, "test", false).unwrap();
+        let summary = fee_reserve.finalize();
+        assert_eq!(
+            vec![Decimal::from(15), Decimal::from(5), Decimal::zero()],
+            summary.payment_amounts(false)
+        );
+        assert_eq!(
+            vec![Decimal::from(12), Decimal::from(4), Decimal::from(4)],
+            summary.payment_amounts(true)
+        );
+    }
+
     #[test]
     fn test_xrd_cost_unit_conversion() {
         let mut fee_reserve = SystemLoanFeeReserve::new(100, 0, 5.into(), 500);
@@ -1,6 +1,50 @@
 use crate::model::ResourceContainer;
 use crate::types::*;
 
+/// A coarse category of costs, derived from the reason given when consuming cost units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, TypeId, Encode, Decode)]
+pub enum CostCategory {
+    /// Base fee, manifest decoding and verification, signatures and blobs
+    Transaction,
+    /// Invocation overhead of functions and methods
+    Invocation,
+    /// WASM instantiation and execution
+    WasmExecution,
+    /// Reading substates and borrowing nodes
+    SubstateRead,
+    /// Writing substates
+    SubstateWrite,
+    /// Everything else, e.g. node lifecycle and system calls
+    Other,
+}
+
+impl CostCategory {
+    pub fn from_reason(reason: &str) -> Self {
+        match reason {
+            "base_fee" | "decode_manifest" | "verify_manifest" | "verify_signatures" | "blobs" => {
+                CostCategory::Transaction
+            }
+            "invoke_function" | "run_function" | "invoke_method" | "run_method" => {
+                CostCategory::Invocation
+            }
+            "instantiate_wasm" | "run_wasm" => CostCategory::WasmExecution,
+            "borrow_node" | "borrow_substate" | "read_substate" => CostCategory::SubstateRead,
+            "return_substate" | "write_substate" | "kv_store_put_if_absent" => {
+                CostCategory::SubstateWrite
+            }
+            _ => CostCategory::Other,
+        }
+    }
+}
+
+/// The cost units consumed by the call frames at a given depth running a given function.
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode)]
+pub struct CallFrameCost {
+    pub depth: usize,
+    pub fn_identifier: FnIdentifier,
+    pub cost_units: u32,
+}
+
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct FeeSummary {
     /// Whether the system loan is fully repaid
@@ -13,12 +57,97 @@ pub struct FeeSummary {
     pub cost_unit_price: Decimal,
     /// The tip percentage
     pub tip_percentage: u32,
-    /// The total amount of XRD burned.
+    /// The total amount of XRD burned, including any network share covered by free credit.
     pub burned: Decimal,
     /// The total amount of XRD tipped to validators.
     pub tipped: Decimal,
+    /// The total amount of XRD paid into the network treasury.
+    pub network_fee: Decimal,
+    /// The amount of XRD paid as royalties to each component.
+    pub royalties: HashMap<ComponentAddress, Decimal>,
+    /// The amount of XRD covered by free credit rather than fee payments.
+    pub free_credit: Decimal,
     /// The fee payments
     pub payments: Vec<(VaultId, ResourceContainer, bool)>,
     /// The cost breakdown
     pub cost_breakdown: HashMap<String, u32>,
+    /// The cost units consumed by each call frame, in order of first consumption
+    pub call_frame_breakdown: Vec<CallFrameCost>,
+}
+
+impl FeeSummary {
+    /// Returns the cost units consumed, grouped by category.
+    pub fn cost_breakdown_by_category(&self) -> BTreeMap<CostCategory, u32> {
+        let mut breakdown = BTreeMap::new();
+        for (reason, n) in &self.cost_breakdown {
+            *breakdown
+                .entry(CostCategory::from_reason(reason))
+                .or_default() += *n;
+        }
+        breakdown
+    }
+
+    /// Returns the amount of XRD charged for the consumed cost units, excluding tip.
+    pub fn base_fee(&self) -> Decimal {
+        self.cost_unit_price * self.cost_unit_consumed
+    }
+
+    /// Returns the total amount of XRD paid as royalties.
+    pub fn total_royalties(&self) -> Decimal {
+        self.royalties
+            .values()
+            .fold(Decimal::zero(), |total, royalty| total + *royalty)
+    }
+
+    /// Returns the total amount of XRD charged, across all buckets.
+    pub fn total_fee(&self) -> Decimal {
+        self.burned + self.tipped + self.network_fee + self.total_royalties()
+    }
+
+    /// Returns the amount of XRD to be paid by the fee payments, i.e. the total fee less free
+    /// credit.
+    pub fn fee_after_free_credit(&self) -> Decimal {
+        if self.free_credit < self.total_fee() {
+            self.total_fee() - self.free_credit
+        } else {
+            Decimal::zero()
+        }
+    }
+
+    /// Returns the amount of XRD to collect from each fee payment, in payment order.
+    ///
+    /// The total fee less free credit is split between the payments pro rata to their locked
+    /// amounts, with contingent payments only taking part if the transaction succeeded. The last
+    /// eligible payment absorbs any rounding dust.
+    pub fn payment_amounts(&self, is_success: bool) -> Vec<Decimal> {
+        let is_eligible = |contingent: bool| !contingent || is_success;
+        let eligible_total = self
+            .payments
+            .iter()
+            .filter(|(_, _, contingent)| is_eligible(*contingent))
+            .fold(Decimal::zero(), |total, (_, locked, _)| {
+                total + locked.liquid_amount()
+            });
+        let last_eligible = self
+            .payments
+            .iter()
+            .rposition(|(_, _, contingent)| is_eligible(*contingent));
+
+        let required = self.fee_after_free_credit();
+        let mut remaining = required;
+        let mut amounts = Vec::new();
+        for (i, (_, locked, contingent)) in self.payments.iter().enumerate() {
+            let locked = locked.liquid_amount();
+            let amount = if !is_eligible(*contingent) {
+                Decimal::zero()
+            } else if Some(i) == last_eligible || eligible_total.is_zero() {
+                Decimal::min(locked, remaining)
+            } else {
+                Decimal::min(locked, required * locked / eligible_total)
+            };
+            remaining = remaining - amount;
+            amounts.push(amount);
+        }
+        amounts
+    }
 }
@@ -18,7 +18,7 @@ pub enum SystemApiCostingEntry<'a> {
     /*
      * RENode
      */
-    /// Creates a RENode.
+    /// Creates a RENode, charged per KiB retained on heap.
     CreateNode { size: u32 },
     /// Drops a RENode
     DropNode { size: u32 },
@@ -40,6 +40,8 @@ pub enum SystemApiCostingEntry<'a> {
     ReadSubstate { size: u32 },
     /// Updates the data of a Substate
     WriteSubstate { size: u32 },
+    /// Writes a key value store entry unless it already exists
+    KeyValueStorePutIfAbsent { size: u32 },
 
     /*
      * Misc
@@ -48,17 +50,40 @@ pub enum SystemApiCostingEntry<'a> {
     ReadEpoch,
     /// Reads the transaction hash.
     ReadTransactionHash,
+    /// Reads the transaction signers.
+    ReadTransactionSigners,
     /// Reads blob in transaction
     ReadBlob { size: u32 },
     /// Generates a UUID.
     GenerateUuid,
+    /// Generates pseudo-random bytes.
+    GenerateRandomBytes { size: u32 },
+    /// Reads the remaining cost units.
+    ReadRemainingCostUnits,
+    /// Computes the hash of some data.
+    ComputeHash { size: u32 },
+    /// Verifies a signature of a message.
+    VerifySignature { size: u32 },
     /// Emits a log.
     EmitLog { size: u32 },
+    /// Emits an event.
+    EmitEvent { size: u32 },
     /// Checks if an access rule can be satisfied by the given proofs.
     CheckAccessRule { size: u32 },
+
+    /*
+     * Checkpoint
+     */
+    /// Creates an execution checkpoint.
+    ExecutionCheckpoint,
+    /// Rolls back to an execution checkpoint.
+    RollbackTo,
 }
 
+/// The fee schedule, stored in the System substate so that it can be updated by governance.
+#[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
 pub struct FeeTable {
+    version: u32,
     tx_base_fee: u32,
     tx_manifest_decoding_per_byte: u32,
     tx_manifest_verification_per_byte: u32,
@@ -73,6 +98,7 @@ pub struct FeeTable {
 impl FeeTable {
     pub fn new() -> Self {
         Self {
+            version: 1,
             tx_base_fee: 10_000,
             tx_manifest_decoding_per_byte: 3,
             tx_manifest_verification_per_byte: 1,
@@ -85,6 +111,16 @@ impl FeeTable {
         }
     }
 
+    /// Returns a copy of this fee table with the given version.
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
     pub fn tx_base_fee(&self) -> u32 {
         self.tx_base_fee
     }
@@ -124,7 +160,10 @@ impl FeeTable {
                         }
                     }
                     NativeFnIdentifier::Package(package_fn) => match package_fn {
-                        PackageFnIdentifier::Publish => self.fixed_low + input.raw.len() as u32 * 2,
+                        PackageFnIdentifier::Publish | PackageFnIdentifier::PublishWithOwner => {
+                            self.fixed_low + input.raw.len() as u32 * 2
+                        }
+                        PackageFnIdentifier::UpdateMetadata => self.fixed_medium,
                     },
                     NativeFnIdentifier::AuthZone(auth_zone_ident) => {
                         match auth_zone_ident {
@@ -139,7 +178,11 @@ impl FeeTable {
                     NativeFnIdentifier::System(system_ident) => match system_ident {
                         SystemFnIdentifier::GetCurrentEpoch => self.fixed_low,
                         SystemFnIdentifier::GetTransactionHash => self.fixed_low,
+                        SystemFnIdentifier::GetTransactionSigners => self.fixed_low,
                         SystemFnIdentifier::SetEpoch => self.fixed_low,
+                        SystemFnIdentifier::SetFeeTable => self.fixed_medium,
+                        SystemFnIdentifier::GetCostUnitPrice => self.fixed_low,
+                        SystemFnIdentifier::SetCostUnitPrice => self.fixed_low,
                     },
                     NativeFnIdentifier::Bucket(bucket_ident) => match bucket_ident {
                         BucketFnIdentifier::Take => self.fixed_medium,
@@ -184,9 +227,12 @@ impl FeeTable {
                         WorktopFnIdentifier::AssertContainsAmount => self.fixed_low,
                         WorktopFnIdentifier::AssertContainsNonFungibles => self.fixed_low,
                         WorktopFnIdentifier::Drain => self.fixed_low,
+                        WorktopFnIdentifier::Contents => self.fixed_low,
                     },
                     NativeFnIdentifier::Component(component_ident) => match component_ident {
                         ComponentFnIdentifier::AddAccessCheck => self.fixed_medium,
+                        ComponentFnIdentifier::SetAccessRule => self.fixed_medium,
+                        ComponentFnIdentifier::SetRoleRule => self.fixed_medium,
                     },
                     NativeFnIdentifier::Vault(vault_ident) => {
                         match vault_ident {
@@ -203,6 +249,18 @@ impl FeeTable {
                             VaultFnIdentifier::LockContingentFee => self.fixed_medium,
                         }
                     }
+                    NativeFnIdentifier::AccessController(access_controller_ident) => {
+                        match access_controller_ident {
+                            AccessControllerFnIdentifier::Create => self.fixed_high,
+                            AccessControllerFnIdentifier::CreateProof => self.fixed_high,
+                            AccessControllerFnIdentifier::InitiateRecovery => self.fixed_medium,
+                            AccessControllerFnIdentifier::QuickConfirmRecovery => self.fixed_medium,
+                            AccessControllerFnIdentifier::TimedConfirmRecovery => self.fixed_medium,
+                            AccessControllerFnIdentifier::CancelRecovery => self.fixed_medium,
+                            AccessControllerFnIdentifier::LockPrimaryRole => self.fixed_low,
+                            AccessControllerFnIdentifier::UnlockPrimaryRole => self.fixed_low,
+                        }
+                    }
                 }
             }
             FnIdentifier::Scrypto { .. } => {
@@ -223,7 +281,7 @@ impl FeeTable {
                 self.fixed_low + (5 * input.raw.len() + 10 * input.value_count()) as u32
             }
 
-            SystemApiCostingEntry::CreateNode { .. } => self.fixed_medium,
+            SystemApiCostingEntry::CreateNode { size } => self.fixed_medium + 100 * (size / 1024),
             SystemApiCostingEntry::DropNode { .. } => self.fixed_medium,
             SystemApiCostingEntry::GlobalizeNode { size } => self.fixed_high + 200 * size,
             SystemApiCostingEntry::BorrowNode { loaded, size } => {
@@ -245,13 +303,25 @@ impl FeeTable {
             SystemApiCostingEntry::TakeSubstate { .. } => self.fixed_medium,
             SystemApiCostingEntry::ReadSubstate { .. } => self.fixed_medium,
             SystemApiCostingEntry::WriteSubstate { .. } => self.fixed_medium,
+            SystemApiCostingEntry::KeyValueStorePutIfAbsent { .. } => self.fixed_medium,
 
             SystemApiCostingEntry::ReadEpoch => self.fixed_low,
             SystemApiCostingEntry::ReadTransactionHash => self.fixed_low,
+            SystemApiCostingEntry::ReadTransactionSigners => self.fixed_low,
             SystemApiCostingEntry::ReadBlob { size } => self.fixed_low + size,
             SystemApiCostingEntry::GenerateUuid => self.fixed_low,
+            SystemApiCostingEntry::GenerateRandomBytes { size } => {
+                self.fixed_low.saturating_add(size.saturating_mul(10))
+            }
+            SystemApiCostingEntry::ReadRemainingCostUnits => self.fixed_low,
+            SystemApiCostingEntry::ComputeHash { size } => self.fixed_low + size,
+            SystemApiCostingEntry::VerifySignature { size } => self.fixed_high + size,
             SystemApiCostingEntry::EmitLog { size } => self.fixed_low + 10 * size,
+            SystemApiCostingEntry::EmitEvent { size } => self.fixed_low + 10 * size,
             SystemApiCostingEntry::CheckAccessRule { .. } => self.fixed_medium,
+
+            SystemApiCostingEntry::ExecutionCheckpoint => self.fixed_medium,
+            SystemApiCostingEntry::RollbackTo => self.fixed_high,
         }
     }
 }
@@ -1,4 +1,4 @@
-use crate::constants::GENESIS_CREATION_CREDIT;
+use crate::constants::{DEFAULT_COST_UNIT_PRICE, GENESIS_CREATION_CREDIT, SYS_TREASURY_VAULT};
 use crate::engine::ResourceChange;
 use crate::engine::Track;
 use crate::engine::TrackReceipt;
@@ -7,6 +7,7 @@ use crate::fee::FeeTable;
 use crate::fee::SystemLoanFeeReserve;
 use crate::ledger::{ReadableSubstateStore, WriteableSubstateStore};
 use crate::model::Package;
+use crate::state_manager::StateDiff;
 use crate::transaction::TransactionResult;
 use crate::types::ResourceMethodAuthKey::Withdraw;
 use crate::types::*;
@@ -15,6 +16,7 @@ use crate::types::*;
 struct SystemComponentState {
     vault: scrypto::resource::Vault,
     transactions: scrypto::component::KeyValueStore<Hash, u64>,
+    epoch_payouts: scrypto::component::KeyValueStore<u64, Decimal>,
 }
 
 const XRD_SYMBOL: &str = "XRD";
@@ -26,6 +28,7 @@ const XRD_VAULT_ID: VaultId = (Hash([0u8; 32]), 0);
 
 const SYS_FAUCET_COMPONENT_NAME: &str = "SysFaucet";
 const SYS_FAUCET_KEY_VALUE_STORE_ID: KeyValueStoreId = (Hash([0u8; 32]), 1);
+const SYS_FAUCET_EPOCH_PAYOUTS_KEY_VALUE_STORE_ID: KeyValueStoreId = (Hash([0u8; 32]), 3);
 
 use crate::model::*;
 
@@ -74,19 +77,21 @@ pub fn execute_genesis<'s, R: FeeReserve>(mut track: Track<'s, R>) -> TrackRecei
         true,
     );
 
-    let mut ecdsa_secp256k1_resource_auth = HashMap::new();
-    ecdsa_secp256k1_resource_auth.insert(Withdraw, (rule!(allow_all), LOCKED));
-    let ecdsa_secp256k1_token = ResourceManager::new(
-        ResourceType::NonFungible,
-        HashMap::new(),
-        ecdsa_secp256k1_resource_auth,
-    )
-    .expect("Failed to construct ECDSA resource manager");
-    track.create_uuid_substate(
-        SubstateId::ResourceManager(ECDSA_TOKEN),
-        ecdsa_secp256k1_token,
-        true,
-    );
+    for virtual_badge_resource in VIRTUAL_BADGE_RESOURCES {
+        let mut virtual_badge_resource_auth = HashMap::new();
+        virtual_badge_resource_auth.insert(Withdraw, (rule!(allow_all), LOCKED));
+        let virtual_badge_token = ResourceManager::new(
+            ResourceType::NonFungible,
+            HashMap::new(),
+            virtual_badge_resource_auth,
+        )
+        .expect("Failed to construct virtual badge resource manager");
+        track.create_uuid_substate(
+            SubstateId::ResourceManager(virtual_badge_resource),
+            virtual_badge_token,
+            true,
+        );
+    }
 
     let system_token =
         ResourceManager::new(ResourceType::NonFungible, HashMap::new(), HashMap::new())
@@ -107,11 +112,14 @@ pub fn execute_genesis<'s, R: FeeReserve>(mut track: Track<'s, R>) -> TrackRecei
     let system_vault = Vault::new(minted_xrd);
     track.create_uuid_substate(SubstateId::Vault(XRD_VAULT_ID), system_vault, false);
 
-    let sys_faucet_component_info = ComponentInfo::new(
-        SYS_FAUCET_PACKAGE,
-        SYS_FAUCET_COMPONENT_NAME.to_owned(),
-        vec![],
-    );
+    let treasury_vault = Vault::new(ResourceContainer::new_empty(
+        RADIX_TOKEN,
+        ResourceType::Fungible { divisibility: 18 },
+    ));
+    track.create_uuid_substate(SubstateId::Vault(SYS_TREASURY_VAULT), treasury_vault, false);
+
+    let sys_faucet_component_info =
+        ComponentInfo::new(SYS_FAUCET_PACKAGE, SYS_FAUCET_COMPONENT_NAME.to_owned());
     let sys_faucet_component_state = ComponentState::new(scrypto_encode(&SystemComponentState {
         vault: scrypto::resource::Vault(XRD_VAULT_ID),
         transactions: scrypto::component::KeyValueStore {
@@ -119,6 +127,11 @@ pub fn execute_genesis<'s, R: FeeReserve>(mut track: Track<'s, R>) -> TrackRecei
             key: PhantomData,
             value: PhantomData,
         },
+        epoch_payouts: scrypto::component::KeyValueStore {
+            id: SYS_FAUCET_EPOCH_PAYOUTS_KEY_VALUE_STORE_ID,
+            key: PhantomData,
+            value: PhantomData,
+        },
     }));
     track.create_uuid_substate(
         SubstateId::ComponentInfo(SYS_FAUCET_COMPONENT),
@@ -130,29 +143,54 @@ pub fn execute_genesis<'s, R: FeeReserve>(mut track: Track<'s, R>) -> TrackRecei
         sys_faucet_component_state,
         true,
     );
+    track.create_uuid_substate(
+        SubstateId::ComponentAccessRules(SYS_FAUCET_COMPONENT),
+        ComponentAccessRules::new(vec![]),
+        true,
+    );
 
-    track.create_uuid_substate(SubstateId::System, System { epoch: 0 }, true);
+    track.create_uuid_substate(
+        SubstateId::System,
+        System {
+            epoch: 0,
+            fee_table: FeeTable::new(),
+            cost_unit_price: DEFAULT_COST_UNIT_PRICE
+                .parse()
+                .expect("Invalid DEFAULT_COST_UNIT_PRICE"),
+        },
+        true,
+    );
 
     track.finalize(Ok(Vec::new()), vec![initial_xrd])
 }
 
+/// Returns the state changes which bootstrap the given store, or `None` if it has already been
+/// bootstrapped.
+pub fn genesis_state_diff<S: ReadableSubstateStore>(substate_store: &S) -> Option<StateDiff> {
+    if substate_store
+        .get_substate(&SubstateId::Package(SYS_FAUCET_PACKAGE))
+        .is_some()
+    {
+        return None;
+    }
+
+    let mut fee_reserve = SystemLoanFeeReserve::default();
+    fee_reserve.credit_free(GENESIS_CREATION_CREDIT);
+    let track = Track::new(substate_store, fee_reserve, FeeTable::new());
+    let receipt = execute_genesis(track);
+    if let TransactionResult::Commit(c) = receipt.result {
+        Some(c.state_updates)
+    } else {
+        panic!("Failed to bootstrap")
+    }
+}
+
 pub fn bootstrap<S>(mut substate_store: S) -> S
 where
     S: ReadableSubstateStore + WriteableSubstateStore,
 {
-    if substate_store
-        .get_substate(&SubstateId::Package(SYS_FAUCET_PACKAGE))
-        .is_none()
-    {
-        let mut fee_reserve = SystemLoanFeeReserve::default();
-        fee_reserve.credit(GENESIS_CREATION_CREDIT);
-        let track = Track::new(&substate_store, fee_reserve, FeeTable::new());
-        let receipt = execute_genesis(track);
-        if let TransactionResult::Commit(c) = receipt.result {
-            c.state_updates.commit(&mut substate_store);
-        } else {
-            panic!("Failed to bootstrap")
-        }
+    if let Some(state_diff) = genesis_state_diff(&substate_store) {
+        state_diff.commit(&mut substate_store);
     }
     substate_store
 }
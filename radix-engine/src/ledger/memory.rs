@@ -22,6 +22,27 @@ impl TypedInMemorySubstateStore {
         let substate_store = Self::new();
         bootstrap(substate_store)
     }
+
+    pub fn to_snapshot(&self, nonce: u64) -> StateSnapshot {
+        StateSnapshot::new(
+            self.substates.clone(),
+            self.root_substates.iter().cloned(),
+            nonce,
+        )
+    }
+
+    /// Writes all substates and roots, along with the given nonce, to the given file.
+    #[cfg(not(feature = "alloc"))]
+    pub fn export_state(&self, path: &std::path::Path, nonce: u64) -> Result<(), SnapshotError> {
+        self.to_snapshot(nonce).write_to(path)
+    }
+
+    /// Loads the substates and roots of the given file, on top of the existing ones, and returns
+    /// the nonce.
+    #[cfg(not(feature = "alloc"))]
+    pub fn import_state(&mut self, path: &std::path::Path) -> Result<u64, SnapshotError> {
+        Ok(StateSnapshot::read_from(path)?.restore(self))
+    }
 }
 
 impl Default for TypedInMemorySubstateStore {
@@ -70,4 +91,41 @@ impl QueryableSubstateStore for TypedInMemorySubstateStore {
             })
             .collect()
     }
+
+    fn get_non_fungibles(
+        &self,
+        resource_address: &ResourceAddress,
+    ) -> HashMap<NonFungibleId, Substate> {
+        self.substates
+            .iter()
+            .filter_map(|(key, value)| {
+                if let SubstateId::NonFungible(address, id) = key {
+                    if address == resource_address {
+                        Some((id.clone(), value.substate.clone()))
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn get_vaults_holding(&self, resource_address: &ResourceAddress) -> HashMap<VaultId, Substate> {
+        self.substates
+            .iter()
+            .filter_map(|(key, value)| {
+                if let (SubstateId::Vault(id), Substate::Vault(vault)) = (key, &value.substate) {
+                    if vault.resource_address() == *resource_address {
+                        Some((*id, value.substate.clone()))
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
 }
@@ -1,7 +1,9 @@
 mod bootstrap;
 mod memory;
+mod snapshot;
 mod traits;
 
-pub use bootstrap::{bootstrap, execute_genesis};
+pub use bootstrap::{bootstrap, execute_genesis, genesis_state_diff};
 pub use memory::TypedInMemorySubstateStore;
+pub use snapshot::*;
 pub use traits::*;
@@ -0,0 +1,164 @@
+use crate::ledger::*;
+use crate::types::*;
+
+/// The version of the state snapshot format
+pub const STATE_SNAPSHOT_VERSION: u32 = 3;
+
+/// A dump of all the substates and roots of a substate store, along with the transaction nonce.
+///
+/// Substates and roots are sorted, so that the same state always produces the same snapshot.
+/// The epoch is part of the system substate, and is restored along with it. The nonce isn't
+/// kept by substate stores, so it's provided on export and handed back on import.
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode)]
+pub struct StateSnapshot {
+    pub version: u32,
+    /// The `SUBSTATE_FORMAT_VERSION` the substates are encoded with
+    pub substate_format_version: u32,
+    pub substates: Vec<(SubstateId, OutputValue)>,
+    pub roots: Vec<SubstateId>,
+    pub nonce: u64,
+}
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    #[cfg(not(feature = "alloc"))]
+    IOError(std::io::Error),
+    DecodeError(DecodeError),
+    UnsupportedVersion(u32),
+    UnsupportedSubstateFormat(u32),
+}
+
+impl StateSnapshot {
+    pub fn new<I, R>(substates: I, roots: R, nonce: u64) -> Self
+    where
+        I: IntoIterator<Item = (SubstateId, OutputValue)>,
+        R: IntoIterator<Item = SubstateId>,
+    {
+        let mut substates: Vec<(SubstateId, OutputValue)> = substates.into_iter().collect();
+        substates.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut roots: Vec<SubstateId> = roots.into_iter().collect();
+        roots.sort();
+
+        Self {
+            version: STATE_SNAPSHOT_VERSION,
+            substate_format_version: SUBSTATE_FORMAT_VERSION,
+            substates,
+            roots,
+            nonce,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        scrypto_encode(self)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let snapshot: Self = scrypto_decode(bytes).map_err(SnapshotError::DecodeError)?;
+        if snapshot.version != STATE_SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(snapshot.version));
+        }
+        if snapshot.substate_format_version != SUBSTATE_FORMAT_VERSION {
+            return Err(SnapshotError::UnsupportedSubstateFormat(
+                snapshot.substate_format_version,
+            ));
+        }
+        Ok(snapshot)
+    }
+
+    /// Writes the substates and roots of this snapshot into the given store, returning the nonce.
+    pub fn restore<S: WriteableSubstateStore>(self, store: &mut S) -> u64 {
+        for (substate_id, output_value) in self.substates {
+            store.put_substate(substate_id, output_value);
+        }
+        for substate_id in self.roots {
+            store.set_root(substate_id);
+        }
+        self.nonce
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn write_to(&self, path: &std::path::Path) -> Result<(), SnapshotError> {
+        std::fs::write(path, self.to_bytes()).map_err(SnapshotError::IOError)
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn read_from(path: &std::path::Path) -> Result<Self, SnapshotError> {
+        let bytes = std::fs::read(path).map_err(SnapshotError::IOError)?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::Substate;
+
+    fn substate_id(n: u8) -> SubstateId {
+        SubstateId::KeyValueStoreEntry((Hash([0u8; 32]), 0), vec![n])
+    }
+
+    fn output_value(n: u8) -> OutputValue {
+        OutputValue {
+            substate: Substate::KeyValueStoreEntry(Some(ScryptoValue::from_typed(&n))),
+            version: 0,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_is_deterministic() {
+        let snapshot = StateSnapshot::new(
+            vec![
+                (substate_id(1), output_value(1)),
+                (substate_id(0), output_value(0)),
+            ],
+            vec![substate_id(1), substate_id(0)],
+            5,
+        );
+        let reordered = StateSnapshot::new(
+            vec![
+                (substate_id(0), output_value(0)),
+                (substate_id(1), output_value(1)),
+            ],
+            vec![substate_id(0), substate_id(1)],
+            5,
+        );
+
+        assert_eq!(snapshot.to_bytes(), reordered.to_bytes());
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let mut store = TypedInMemorySubstateStore::new();
+        store.put_substate(substate_id(0), output_value(0));
+        store.set_root(substate_id(0));
+
+        let snapshot = StateSnapshot::from_bytes(&store.to_snapshot(5).to_bytes()).unwrap();
+        let mut restored = TypedInMemorySubstateStore::new();
+        let nonce = snapshot.restore(&mut restored);
+
+        assert_eq!(store, restored);
+        assert_eq!(nonce, 5);
+    }
+
+    #[test]
+    fn test_unsupported_version_is_rejected() {
+        let mut snapshot = StateSnapshot::new(vec![], vec![], 0);
+        snapshot.version = STATE_SNAPSHOT_VERSION + 1;
+
+        assert!(matches!(
+            StateSnapshot::from_bytes(&snapshot.to_bytes()),
+            Err(SnapshotError::UnsupportedVersion(_))
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_substate_format_is_rejected() {
+        let mut snapshot = StateSnapshot::new(vec![], vec![], 0);
+        snapshot.substate_format_version = SUBSTATE_FORMAT_VERSION + 1;
+
+        assert!(matches!(
+            StateSnapshot::from_bytes(&snapshot.to_bytes()),
+            Err(SnapshotError::UnsupportedSubstateFormat(_))
+        ));
+    }
+}
@@ -3,6 +3,27 @@ use crate::types::*;
 
 pub trait QueryableSubstateStore {
     fn get_kv_store_entries(&self, kv_store_id: &KeyValueStoreId) -> HashMap<Vec<u8>, Substate>;
+
+    /// Returns the non-fungibles of the given resource, by id.
+    fn get_non_fungibles(
+        &self,
+        resource_address: &ResourceAddress,
+    ) -> HashMap<NonFungibleId, Substate>;
+
+    /// Returns the vaults of the given resource, including the empty ones.
+    fn get_vaults_holding(&self, resource_address: &ResourceAddress) -> HashMap<VaultId, Substate>;
+}
+
+/// A store which can look up the resources owned by a component without traversing its state.
+pub trait AccountQueryStore {
+    /// Returns the vaults owned by the given component, directly or through its key value stores.
+    fn get_component_vaults(&self, component_address: &ComponentAddress) -> HashSet<VaultId>;
+
+    /// Returns the balances of the given component, by resource.
+    fn get_component_balances(
+        &self,
+        component_address: &ComponentAddress,
+    ) -> HashMap<ResourceAddress, Decimal>;
 }
 
 #[derive(Debug, Clone, Hash, TypeId, Encode, Decode, PartialEq, Eq)]
@@ -12,6 +33,12 @@ pub struct OutputId {
     pub version: u32,
 }
 
+/// The version of the encoding of `OutputValue`, to be bumped whenever a change to any substate
+/// makes previously persisted substates undecodable or ambiguous.
+///
+/// Persistent stores and snapshots record it, and refuse to load substates of another version.
+pub const SUBSTATE_FORMAT_VERSION: u32 = 1;
+
 #[derive(Debug, Clone, Encode, Decode, TypeId, PartialEq, Eq)]
 pub struct OutputValue {
     pub substate: Substate,
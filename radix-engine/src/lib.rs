@@ -27,3 +27,7 @@ pub mod wasm;
 
 /// Scrypto/SBOR types required by Radix Engine.
 pub mod types;
+
+/// Ledger simulator and helpers for blueprint tests.
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
@@ -0,0 +1,407 @@
+use crate::engine::{HeapRENode, SystemApi};
+use crate::fee::FeeReserve;
+use crate::model::{convert, Bucket, InvokeError, MethodAuthorization, Vault};
+use crate::types::*;
+use crate::wasm::*;
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub enum AccessControllerError {
+    InvalidRequestData(DecodeError),
+    InvalidMethod,
+    /// Only the primary and recovery roles may propose a recovery.
+    InvalidProposer(Role),
+    /// The proposer already has a recovery in progress.
+    RecoveryAlreadyInProgress(Role),
+    /// The proposer has no recovery in progress.
+    NoRecoveryInProgress(Role),
+    /// The proposal being confirmed is not the one which was initiated.
+    RecoveryProposalMismatch,
+    /// The recovery proposed by the recovery role can't be confirmed without the other roles.
+    TimedRecoveryNotAvailable,
+    /// The timed recovery can only be confirmed from the given epoch onwards.
+    TimedRecoveryDelayHasNotElapsed {
+        allowed_from_epoch: u64,
+    },
+    /// The primary role is locked and can't create proofs.
+    PrimaryRoleLocked,
+}
+
+/// A recovery proposal of the recovery role, along with the epoch from which it may be confirmed
+/// without the other roles.
+#[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
+pub struct RecoveryRoleProposal {
+    pub proposal: RecoveryProposal,
+    pub timed_recovery_allowed_from_epoch: Option<u64>,
+}
+
+/// A native component which holds a badge and hands out proofs of it to whoever satisfies the
+/// primary role, with the recovery and confirmation roles able to replace the rule set.
+#[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
+pub struct AccessController {
+    vault_id: VaultId,
+    rule_set: RuleSet,
+    timed_recovery_delay_in_epochs: Option<u64>,
+    primary_role_proposal: Option<RecoveryProposal>,
+    recovery_role_proposal: Option<RecoveryRoleProposal>,
+    is_primary_role_locked: bool,
+}
+
+impl AccessController {
+    pub fn new(
+        vault_id: VaultId,
+        rule_set: RuleSet,
+        timed_recovery_delay_in_epochs: Option<u64>,
+    ) -> Self {
+        Self {
+            vault_id,
+            rule_set,
+            timed_recovery_delay_in_epochs,
+            primary_role_proposal: None,
+            recovery_role_proposal: None,
+            is_primary_role_locked: false,
+        }
+    }
+
+    pub fn vault_id(&self) -> VaultId {
+        self.vault_id
+    }
+
+    pub fn rule_set(&self) -> &RuleSet {
+        &self.rule_set
+    }
+
+    pub fn is_primary_role_locked(&self) -> bool {
+        self.is_primary_role_locked
+    }
+
+    fn role_rule(&self, role: Role) -> &AccessRule {
+        match role {
+            Role::Primary => &self.rule_set.primary_role,
+            Role::Recovery => &self.rule_set.recovery_role,
+            Role::Confirmation => &self.rule_set.confirmation_role,
+        }
+    }
+
+    /// Returns the authorization which is satisfied when the rule of any of the roles is.
+    fn any_role_authorization(&self, roles: &[Role]) -> MethodAuthorization {
+        MethodAuthorization::any_of(
+            roles
+                .iter()
+                .map(|role| convert(&Type::Unit, &ScryptoValue::unit(), self.role_rule(*role)))
+                .collect(),
+        )
+    }
+
+    pub fn get_auth(
+        &self,
+        access_controller_fn: AccessControllerFnIdentifier,
+        args: &ScryptoValue,
+    ) -> MethodAuthorization {
+        match access_controller_fn {
+            AccessControllerFnIdentifier::Create => MethodAuthorization::DenyAll,
+            AccessControllerFnIdentifier::CreateProof => {
+                if self.is_primary_role_locked {
+                    MethodAuthorization::DenyAll
+                } else {
+                    self.any_role_authorization(&[Role::Primary])
+                }
+            }
+            AccessControllerFnIdentifier::InitiateRecovery => {
+                match scrypto_decode::<AccessControllerInitiateRecoveryInput>(&args.raw) {
+                    Ok(input) => self.any_role_authorization(&[input.proposer]),
+                    Err(_) => MethodAuthorization::Unsupported,
+                }
+            }
+            AccessControllerFnIdentifier::QuickConfirmRecovery => {
+                match scrypto_decode::<AccessControllerQuickConfirmRecoveryInput>(&args.raw) {
+                    Ok(input) => {
+                        let confirmers: Vec<Role> =
+                            [Role::Primary, Role::Recovery, Role::Confirmation]
+                                .into_iter()
+                                .filter(|role| *role != input.proposer)
+                                .collect();
+                        self.any_role_authorization(&confirmers)
+                    }
+                    Err(_) => MethodAuthorization::Unsupported,
+                }
+            }
+            AccessControllerFnIdentifier::CancelRecovery => {
+                match scrypto_decode::<AccessControllerCancelRecoveryInput>(&args.raw) {
+                    Ok(input) => self.any_role_authorization(&[input.proposer]),
+                    Err(_) => MethodAuthorization::Unsupported,
+                }
+            }
+            AccessControllerFnIdentifier::TimedConfirmRecovery
+            | AccessControllerFnIdentifier::LockPrimaryRole
+            | AccessControllerFnIdentifier::UnlockPrimaryRole => {
+                self.any_role_authorization(&[Role::Recovery])
+            }
+        }
+    }
+
+    fn proposal_of(&self, proposer: Role) -> Option<&RecoveryProposal> {
+        match proposer {
+            Role::Primary => self.primary_role_proposal.as_ref(),
+            Role::Recovery => self
+                .recovery_role_proposal
+                .as_ref()
+                .map(|recovery_role_proposal| &recovery_role_proposal.proposal),
+            Role::Confirmation => None,
+        }
+    }
+
+    fn initiate_recovery(
+        &mut self,
+        proposer: Role,
+        proposal: RecoveryProposal,
+        current_epoch: u64,
+    ) -> Result<(), AccessControllerError> {
+        if self.proposal_of(proposer).is_some() {
+            return Err(AccessControllerError::RecoveryAlreadyInProgress(proposer));
+        }
+
+        match proposer {
+            Role::Primary => self.primary_role_proposal = Some(proposal),
+            Role::Recovery => {
+                let timed_recovery_allowed_from_epoch = self
+                    .timed_recovery_delay_in_epochs
+                    .map(|delay| current_epoch.saturating_add(delay));
+                self.recovery_role_proposal = Some(RecoveryRoleProposal {
+                    proposal,
+                    timed_recovery_allowed_from_epoch,
+                });
+            }
+            Role::Confirmation => return Err(AccessControllerError::InvalidProposer(proposer)),
+        }
+
+        Ok(())
+    }
+
+    fn confirm_recovery(
+        &mut self,
+        proposer: Role,
+        proposal: &RecoveryProposal,
+    ) -> Result<(), AccessControllerError> {
+        match self.proposal_of(proposer) {
+            Some(initiated) if initiated.eq(proposal) => {}
+            Some(..) => return Err(AccessControllerError::RecoveryProposalMismatch),
+            None => return Err(AccessControllerError::NoRecoveryInProgress(proposer)),
+        }
+
+        // A successful recovery replaces the rule set, and discards all other proposals
+        self.rule_set = proposal.rule_set.clone();
+        self.timed_recovery_delay_in_epochs = proposal.timed_recovery_delay_in_epochs;
+        self.primary_role_proposal = None;
+        self.recovery_role_proposal = None;
+        self.is_primary_role_locked = false;
+
+        Ok(())
+    }
+
+    fn timed_confirm_recovery(
+        &mut self,
+        proposal: &RecoveryProposal,
+        current_epoch: u64,
+    ) -> Result<(), AccessControllerError> {
+        let allowed_from_epoch = self
+            .recovery_role_proposal
+            .as_ref()
+            .ok_or(AccessControllerError::NoRecoveryInProgress(Role::Recovery))?
+            .timed_recovery_allowed_from_epoch
+            .ok_or(AccessControllerError::TimedRecoveryNotAvailable)?;
+        if current_epoch < allowed_from_epoch {
+            return Err(AccessControllerError::TimedRecoveryDelayHasNotElapsed {
+                allowed_from_epoch,
+            });
+        }
+
+        self.confirm_recovery(Role::Recovery, proposal)
+    }
+
+    fn cancel_recovery(&mut self, proposer: Role) -> Result<(), AccessControllerError> {
+        if self.proposal_of(proposer).is_none() {
+            return Err(AccessControllerError::NoRecoveryInProgress(proposer));
+        }
+
+        match proposer {
+            Role::Primary => self.primary_role_proposal = None,
+            Role::Recovery => self.recovery_role_proposal = None,
+            Role::Confirmation => {}
+        }
+
+        Ok(())
+    }
+
+    fn current_epoch<'s, Y, W, I, R>(
+        system_api: &mut Y,
+    ) -> Result<u64, InvokeError<AccessControllerError>>
+    where
+        Y: SystemApi<'s, W, I, R>,
+        W: WasmEngine<I>,
+        I: WasmInstance,
+        R: FeeReserve,
+    {
+        let rtn = system_api
+            .invoke_method(
+                Receiver::Ref(RENodeId::System),
+                FnIdentifier::Native(NativeFnIdentifier::System(
+                    SystemFnIdentifier::GetCurrentEpoch,
+                )),
+                ScryptoValue::from_typed(&SystemGetCurrentEpochInput {}),
+            )
+            .map_err(InvokeError::Downstream)?;
+        Ok(scrypto_decode(&rtn.raw).expect("System returned an invalid epoch"))
+    }
+
+    pub fn static_main<'s, Y, W, I, R>(
+        access_controller_fn: AccessControllerFnIdentifier,
+        args: ScryptoValue,
+        system_api: &mut Y,
+    ) -> Result<ScryptoValue, InvokeError<AccessControllerError>>
+    where
+        Y: SystemApi<'s, W, I, R>,
+        W: WasmEngine<I>,
+        I: WasmInstance,
+        R: FeeReserve,
+    {
+        match access_controller_fn {
+            AccessControllerFnIdentifier::Create => {
+                let input: AccessControllerCreateInput =
+                    scrypto_decode(&args.raw).map_err(|e| {
+                        InvokeError::Error(AccessControllerError::InvalidRequestData(e))
+                    })?;
+
+                let bucket: Bucket = system_api
+                    .node_drop(&RENodeId::Bucket(input.controlled_asset.0))
+                    .map_err(InvokeError::Downstream)?
+                    .into();
+                let container = bucket
+                    .into_container()
+                    .expect("Bucket moved into the access controller is locked");
+                let vault_id = system_api
+                    .node_create(HeapRENode::Vault(Vault::new(container)))
+                    .map_err(InvokeError::Downstream)?
+                    .into();
+
+                let node_id = system_api
+                    .node_create(HeapRENode::AccessController(AccessController::new(
+                        vault_id,
+                        input.rule_set,
+                        input.timed_recovery_delay_in_epochs,
+                    )))
+                    .map_err(InvokeError::Downstream)?;
+                system_api
+                    .node_globalize(node_id)
+                    .map_err(InvokeError::Downstream)?;
+
+                let component_address: ComponentAddress = node_id.into();
+                Ok(ScryptoValue::from_typed(&component_address))
+            }
+            _ => Err(InvokeError::Error(AccessControllerError::InvalidMethod)),
+        }
+    }
+
+    pub fn main<'s, Y, W, I, R>(
+        component_address: ComponentAddress,
+        access_controller_fn: AccessControllerFnIdentifier,
+        args: ScryptoValue,
+        system_api: &mut Y,
+    ) -> Result<ScryptoValue, InvokeError<AccessControllerError>>
+    where
+        Y: SystemApi<'s, W, I, R>,
+        W: WasmEngine<I>,
+        I: WasmInstance,
+        R: FeeReserve,
+    {
+        let node_id = RENodeId::AccessController(component_address);
+        let substate_id = SubstateId::AccessController(component_address);
+
+        if let AccessControllerFnIdentifier::CreateProof = access_controller_fn {
+            let _: AccessControllerCreateProofInput = scrypto_decode(&args.raw)
+                .map_err(|e| InvokeError::Error(AccessControllerError::InvalidRequestData(e)))?;
+            let vault_id = {
+                let node_ref = system_api
+                    .borrow_node(&node_id)
+                    .map_err(InvokeError::Downstream)?;
+                let access_controller = node_ref
+                    .access_controller()
+                    .map_err(InvokeError::Downstream)?;
+                if access_controller.is_primary_role_locked() {
+                    return Err(InvokeError::Error(AccessControllerError::PrimaryRoleLocked));
+                }
+                access_controller.vault_id()
+            };
+            return system_api
+                .invoke_method(
+                    Receiver::Ref(RENodeId::Vault(vault_id)),
+                    FnIdentifier::Native(NativeFnIdentifier::Vault(VaultFnIdentifier::CreateProof)),
+                    ScryptoValue::from_typed(&VaultCreateProofInput {}),
+                )
+                .map_err(InvokeError::Downstream);
+        }
+
+        // Read the epoch before borrowing the substate, as it requires a call into the system
+        let current_epoch = match access_controller_fn {
+            AccessControllerFnIdentifier::InitiateRecovery
+            | AccessControllerFnIdentifier::TimedConfirmRecovery => {
+                Self::current_epoch(system_api)?
+            }
+            _ => 0,
+        };
+
+        let mut ref_mut = system_api
+            .substate_borrow_mut(&substate_id)
+            .map_err(InvokeError::Downstream)?;
+        let access_controller = ref_mut
+            .access_controller()
+            .map_err(InvokeError::Downstream)?;
+
+        let result = match access_controller_fn {
+            AccessControllerFnIdentifier::InitiateRecovery => scrypto_decode::<
+                AccessControllerInitiateRecoveryInput,
+            >(&args.raw)
+            .map_err(AccessControllerError::InvalidRequestData)
+            .and_then(|input| {
+                access_controller.initiate_recovery(input.proposer, input.proposal, current_epoch)
+            }),
+            AccessControllerFnIdentifier::QuickConfirmRecovery => scrypto_decode::<
+                AccessControllerQuickConfirmRecoveryInput,
+            >(&args.raw)
+            .map_err(AccessControllerError::InvalidRequestData)
+            .and_then(|input| access_controller.confirm_recovery(input.proposer, &input.proposal)),
+            AccessControllerFnIdentifier::TimedConfirmRecovery => {
+                scrypto_decode::<AccessControllerTimedConfirmRecoveryInput>(&args.raw)
+                    .map_err(AccessControllerError::InvalidRequestData)
+                    .and_then(|input| {
+                        access_controller.timed_confirm_recovery(&input.proposal, current_epoch)
+                    })
+            }
+            AccessControllerFnIdentifier::CancelRecovery => {
+                scrypto_decode::<AccessControllerCancelRecoveryInput>(&args.raw)
+                    .map_err(AccessControllerError::InvalidRequestData)
+                    .and_then(|input| access_controller.cancel_recovery(input.proposer))
+            }
+            AccessControllerFnIdentifier::LockPrimaryRole => {
+                scrypto_decode::<AccessControllerLockPrimaryRoleInput>(&args.raw)
+                    .map_err(AccessControllerError::InvalidRequestData)
+                    .map(|_| access_controller.is_primary_role_locked = true)
+            }
+            AccessControllerFnIdentifier::UnlockPrimaryRole => {
+                scrypto_decode::<AccessControllerUnlockPrimaryRoleInput>(&args.raw)
+                    .map_err(AccessControllerError::InvalidRequestData)
+                    .map(|_| access_controller.is_primary_role_locked = false)
+            }
+            AccessControllerFnIdentifier::Create | AccessControllerFnIdentifier::CreateProof => {
+                Err(AccessControllerError::InvalidMethod)
+            }
+        };
+
+        system_api
+            .substate_return_mut(ref_mut)
+            .map_err(InvokeError::Downstream)?;
+
+        result
+            .map(|_| ScryptoValue::from_typed(&()))
+            .map_err(InvokeError::Error)
+    }
+}
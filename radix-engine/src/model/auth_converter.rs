@@ -219,6 +219,9 @@ fn soft_to_hard_proof_rule(
             let hard_resources = soft_to_hard_resource_list(schema, resources, value);
             HardProofRule::CountOf(hard_count, hard_resources)
         }
+        ProofRule::RequireCallerOrigin(origin) => {
+            HardProofRule::RequireCallerOrigin(origin.clone())
+        }
     }
 }
 
@@ -227,23 +230,33 @@ fn soft_to_hard_auth_rule(
     auth_rule: &AccessRuleNode,
     value: &ScryptoValue,
 ) -> HardAuthRule {
-    match auth_rule {
-        AccessRuleNode::ProofRule(proof_rule) => {
-            HardAuthRule::ProofRule(soft_to_hard_proof_rule(schema, proof_rule, value))
-        }
-        AccessRuleNode::AnyOf(rules) => {
-            let hard_rules = rules
-                .iter()
-                .map(|r| soft_to_hard_auth_rule(schema, r, value))
-                .collect();
-            HardAuthRule::AnyOf(hard_rules)
+    // Converted bottom-up with an explicit stack so rule depth is not bounded by the native stack
+    let mut stack: Vec<(&AccessRuleNode, Vec<HardAuthRule>)> = vec![(auth_rule, Vec::new())];
+    loop {
+        let (node, converted) = stack.last().expect("Conversion stack is empty");
+        let children = match node {
+            AccessRuleNode::ProofRule(..) => None,
+            AccessRuleNode::AnyOf(rules)
+            | AccessRuleNode::AllOf(rules)
+            | AccessRuleNode::CountOf(_, rules) => Some(rules),
+        };
+        if let Some(next) = children.and_then(|rules| rules.get(converted.len())) {
+            stack.push((next, Vec::new()));
+            continue;
         }
-        AccessRuleNode::AllOf(rules) => {
-            let hard_rules = rules
-                .iter()
-                .map(|r| soft_to_hard_auth_rule(schema, r, value))
-                .collect();
-            HardAuthRule::AllOf(hard_rules)
+
+        let (node, converted) = stack.pop().expect("Conversion stack is empty");
+        let hard_rule = match node {
+            AccessRuleNode::ProofRule(proof_rule) => {
+                HardAuthRule::ProofRule(soft_to_hard_proof_rule(schema, proof_rule, value))
+            }
+            AccessRuleNode::AnyOf(..) => HardAuthRule::AnyOf(converted),
+            AccessRuleNode::AllOf(..) => HardAuthRule::AllOf(converted),
+            AccessRuleNode::CountOf(count, ..) => HardAuthRule::CountOf(*count, converted),
+        };
+        match stack.last_mut() {
+            Some((_, parent)) => parent.push(hard_rule),
+            None => return hard_rule,
         }
     }
 }
@@ -259,5 +272,9 @@ pub fn convert(
         }
         AccessRule::AllowAll => MethodAuthorization::AllowAll,
         AccessRule::DenyAll => MethodAuthorization::DenyAll,
+        AccessRule::RequireMembership(kv_store_id) => MethodAuthorization::RequireMembership {
+            kv_store_ids: vec![*kv_store_id],
+            alternatives: vec![],
+        },
     }
 }
@@ -1,6 +1,6 @@
 use crate::engine::{HeapRENode, SystemApi};
 use crate::fee::FeeReserve;
-use crate::model::{InvokeError, Proof, ProofError};
+use crate::model::{HardResourceOrNonFungible, InvokeError, Proof, ProofError};
 use crate::types::*;
 use crate::wasm::*;
 
@@ -19,15 +19,40 @@ pub enum AuthZoneError {
 #[derive(Debug)]
 pub struct AuthZone {
     pub proofs: Vec<Proof>,
+    /// Resources of which any non-fungible is assumed to be proven, without an actual proof
+    pub virtual_proof_resources: BTreeSet<ResourceAddress>,
+    /// The requirements met only by the virtual proof resources, in the order they were checked
+    pub virtually_proven: RefCell<Vec<HardResourceOrNonFungible>>,
+    /// Whether this zone belongs to a call into a global component, which hides the zones
+    /// of the frames beneath it from authorization checks
+    pub barrier: bool,
+    /// The package and component of the code running in the frame owning this zone
+    pub origins: Vec<CallerOrigin>,
 }
 
 impl AuthZone {
     pub fn new_with_proofs(proofs: Vec<Proof>) -> Self {
-        Self { proofs }
+        Self {
+            proofs,
+            virtual_proof_resources: BTreeSet::new(),
+            virtually_proven: RefCell::new(Vec::new()),
+            barrier: false,
+            origins: Vec::new(),
+        }
     }
 
     pub fn new() -> Self {
-        Self { proofs: Vec::new() }
+        Self::new_with_proofs(Vec::new())
+    }
+
+    pub fn new_for_frame(origins: Vec<CallerOrigin>, barrier: bool) -> Self {
+        Self {
+            proofs: Vec::new(),
+            virtual_proof_resources: BTreeSet::new(),
+            virtually_proven: RefCell::new(Vec::new()),
+            barrier,
+            origins,
+        }
     }
 
     fn pop(&mut self) -> Result<Proof, InvokeError<AuthZoneError>> {
@@ -131,7 +156,8 @@ impl AuthZone {
                     let value = system_api
                         .borrow_node(&RENodeId::ResourceManager(input.resource_address))
                         .map_err(InvokeError::Downstream)?;
-                    let resource_manager = value.resource_manager();
+                    let resource_manager =
+                        value.resource_manager().map_err(InvokeError::Downstream)?;
                     resource_manager.resource_type()
                 };
                 let auth_zone = system_api.auth_zone(auth_zone_frame_id);
@@ -151,7 +177,8 @@ impl AuthZone {
                     let value = system_api
                         .borrow_node(&RENodeId::ResourceManager(input.resource_address))
                         .map_err(InvokeError::Downstream)?;
-                    let resource_manager = value.resource_manager();
+                    let resource_manager =
+                        value.resource_manager().map_err(InvokeError::Downstream)?;
                     resource_manager.resource_type()
                 };
                 let auth_zone = system_api.auth_zone(auth_zone_frame_id);
@@ -175,7 +202,8 @@ impl AuthZone {
                     let value = system_api
                         .borrow_node(&RENodeId::ResourceManager(input.resource_address))
                         .map_err(InvokeError::Downstream)?;
-                    let resource_manager = value.resource_manager();
+                    let resource_manager =
+                        value.resource_manager().map_err(InvokeError::Downstream)?;
                     resource_manager.resource_type()
                 };
                 let auth_zone = system_api.auth_zone(auth_zone_frame_id);
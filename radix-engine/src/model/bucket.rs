@@ -135,6 +135,11 @@ impl Bucket {
         self.borrow_container().is_empty()
     }
 
+    /// Returns the serialized size of the underlying container.
+    pub fn size(&self) -> usize {
+        scrypto_encode(&*self.borrow_container()).len()
+    }
+
     pub fn into_container(self) -> Result<ResourceContainer, ResourceContainerError> {
         Rc::try_unwrap(self.container)
             .map_err(|_| ResourceContainerError::ContainerLocked)
@@ -165,7 +170,7 @@ impl Bucket {
         let mut node_ref = system_api
             .substate_borrow_mut(&substate_id)
             .map_err(InvokeError::Downstream)?;
-        let bucket0 = node_ref.bucket();
+        let bucket0 = node_ref.bucket().map_err(InvokeError::Downstream)?;
 
         let rtn = match bucket_fn {
             BucketFnIdentifier::Take => {
@@ -278,7 +283,7 @@ impl Bucket {
                 let mut value = system_api
                     .substate_borrow_mut(&resource_substate_id)
                     .map_err(InvokeError::Downstream)?;
-                let resource_manager = value.resource_manager();
+                let resource_manager = value.resource_manager().map_err(InvokeError::Downstream)?;
                 resource_manager.burn(bucket.total_amount());
                 if matches!(resource_manager.resource_type(), ResourceType::NonFungible) {
                     for id in bucket
@@ -8,6 +8,7 @@ use crate::wasm::{WasmEngine, WasmInstance};
 pub enum ComponentError {
     InvalidRequestData(DecodeError),
     BlueprintFunctionNotFound(String),
+    AccessRulesNotFound(u32),
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
@@ -29,25 +30,16 @@ impl ComponentState {
     }
 }
 
-/// A component is an instance of blueprint.
+/// The method access rules of a component, kept apart from its info so that they can be
+/// updated after instantiation.
 #[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
-pub struct ComponentInfo {
-    package_address: PackageAddress,
-    blueprint_name: String,
+pub struct ComponentAccessRules {
     access_rules: Vec<AccessRules>,
 }
 
-impl ComponentInfo {
-    pub fn new(
-        package_address: PackageAddress,
-        blueprint_name: String,
-        access_rules: Vec<AccessRules>,
-    ) -> Self {
-        Self {
-            package_address,
-            blueprint_name,
-            access_rules,
-        }
+impl ComponentAccessRules {
+    pub fn new(access_rules: Vec<AccessRules>) -> Self {
+        Self { access_rules }
     }
 
     pub fn method_authorization(
@@ -55,27 +47,88 @@ impl ComponentInfo {
         component_state: &ComponentState,
         schema: &Type,
         method_name: &str,
+        method_roles: &[String],
     ) -> Vec<MethodAuthorization> {
         let data = ScryptoValue::from_slice(&component_state.state)
             .expect("Failed to decode component state");
 
         let mut authorizations = Vec::new();
         for auth in &self.access_rules {
-            let method_auth = auth.get(method_name);
-            let authorization = convert(schema, &data, method_auth);
+            let authorization = match auth.get_method_roles(method_name, method_roles) {
+                Some(roles) => Self::role_authorization(auth, &roles, schema, &data),
+                None => convert(schema, &data, auth.get(method_name)),
+            };
             authorizations.push(authorization);
         }
 
         authorizations
     }
 
-    pub fn info(&self) -> (PackageAddress, String) {
-        (self.package_address.clone(), self.blueprint_name.clone())
+    /// Resolves the roles a method is tagged with into a single authorization, which is satisfied
+    /// when the rule of any of the roles is. Roles which have not been declared deny access.
+    fn role_authorization(
+        access_rules: &AccessRules,
+        roles: &[String],
+        schema: &Type,
+        data: &ScryptoValue,
+    ) -> MethodAuthorization {
+        let authorizations = roles
+            .iter()
+            .filter_map(|role| access_rules.get_role(role))
+            .map(|role_auth| convert(schema, data, role_auth))
+            .collect();
+        MethodAuthorization::any_of(authorizations)
+    }
+
+    /// Returns the authorization required to update the access rule of a method.
+    pub fn set_access_rule_authorization(
+        &self,
+        index: u32,
+        method_name: &str,
+    ) -> MethodAuthorization {
+        match self.access_rules.get(index as usize) {
+            None => MethodAuthorization::Unsupported,
+            Some(access_rules) => match access_rules.get_mutability(method_name) {
+                Mutability::LOCKED => MethodAuthorization::DenyAll,
+                Mutability::MUTABLE(rule) => convert(&Type::Unit, &ScryptoValue::unit(), rule),
+            },
+        }
+    }
+
+    /// Returns the authorization required to update the rule of a role.
+    pub fn set_role_rule_authorization(&self, index: u32, role_name: &str) -> MethodAuthorization {
+        match self.access_rules.get(index as usize) {
+            None => MethodAuthorization::Unsupported,
+            Some(access_rules) => match access_rules.get_role_mutability(role_name) {
+                Mutability::LOCKED => MethodAuthorization::DenyAll,
+                Mutability::MUTABLE(rule) => convert(&Type::Unit, &ScryptoValue::unit(), rule),
+            },
+        }
     }
 
     pub fn authorization(&self) -> &[AccessRules] {
         &self.access_rules
     }
+}
+
+/// A component is an instance of blueprint.
+#[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
+pub struct ComponentInfo {
+    package_address: PackageAddress,
+    blueprint_name: String,
+}
+
+impl ComponentInfo {
+    pub fn new(package_address: PackageAddress, blueprint_name: String) -> Self {
+        Self {
+            package_address,
+            blueprint_name,
+        }
+    }
+
+    pub fn info(&self) -> (PackageAddress, String) {
+        (self.package_address.clone(), self.blueprint_name.clone())
+    }
 
     pub fn package_address(&self) -> PackageAddress {
         self.package_address.clone()
@@ -97,7 +150,7 @@ impl ComponentInfo {
         I: WasmInstance,
         R: FeeReserve,
     {
-        let substate_id = SubstateId::ComponentInfo(component_address);
+        let substate_id = SubstateId::ComponentAccessRules(component_address);
         let node_id = RENodeId::Component(component_address);
 
         let rtn = match component_fn {
@@ -111,7 +164,9 @@ impl ComponentInfo {
                         let component_ref = system_api
                             .borrow_node(&node_id)
                             .map_err(InvokeError::Downstream)?;
-                        let component = component_ref.component_info();
+                        let component = component_ref
+                            .component_info()
+                            .map_err(InvokeError::Downstream)?;
                         let blueprint_name = component.blueprint_name().to_owned();
                         (
                             RENodeId::Package(component.package_address.clone()),
@@ -122,7 +177,7 @@ impl ComponentInfo {
                     let package_ref = system_api
                         .borrow_node(&package_id)
                         .map_err(InvokeError::Downstream)?;
-                    let package = package_ref.package();
+                    let package = package_ref.package().map_err(InvokeError::Downstream)?;
                     let blueprint_abi = package.blueprint_abi(&blueprint_name).expect(&format!(
                         "Blueprint {} is not found in package node {:?}",
                         blueprint_name, package_id
@@ -139,14 +194,72 @@ impl ComponentInfo {
                 let mut ref_mut = system_api
                     .substate_borrow_mut(&substate_id)
                     .map_err(InvokeError::Downstream)?;
-                let component_info = ref_mut.component_info();
-                component_info.access_rules.push(input.access_rules);
+                let component_access_rules = ref_mut
+                    .component_access_rules()
+                    .map_err(InvokeError::Downstream)?;
+                component_access_rules.access_rules.push(input.access_rules);
                 system_api
                     .substate_return_mut(ref_mut)
                     .map_err(InvokeError::Downstream)?;
 
                 Ok(ScryptoValue::from_typed(&()))
             }
+            ComponentFnIdentifier::SetAccessRule => {
+                let input: ComponentSetAccessRuleInput = scrypto_decode(&args.raw)
+                    .map_err(|e| InvokeError::Error(ComponentError::InvalidRequestData(e)))?;
+
+                let mut ref_mut = system_api
+                    .substate_borrow_mut(&substate_id)
+                    .map_err(InvokeError::Downstream)?;
+                let component_access_rules = ref_mut
+                    .component_access_rules()
+                    .map_err(InvokeError::Downstream)?;
+                let result = match component_access_rules
+                    .access_rules
+                    .get_mut(input.index as usize)
+                {
+                    Some(access_rules) => {
+                        access_rules.set_method_auth(&input.method, input.access_rule);
+                        Ok(ScryptoValue::from_typed(&()))
+                    }
+                    None => Err(InvokeError::Error(ComponentError::AccessRulesNotFound(
+                        input.index,
+                    ))),
+                };
+                system_api
+                    .substate_return_mut(ref_mut)
+                    .map_err(InvokeError::Downstream)?;
+
+                result
+            }
+            ComponentFnIdentifier::SetRoleRule => {
+                let input: ComponentSetRoleRuleInput = scrypto_decode(&args.raw)
+                    .map_err(|e| InvokeError::Error(ComponentError::InvalidRequestData(e)))?;
+
+                let mut ref_mut = system_api
+                    .substate_borrow_mut(&substate_id)
+                    .map_err(InvokeError::Downstream)?;
+                let component_access_rules = ref_mut
+                    .component_access_rules()
+                    .map_err(InvokeError::Downstream)?;
+                let result = match component_access_rules
+                    .access_rules
+                    .get_mut(input.index as usize)
+                {
+                    Some(access_rules) => {
+                        access_rules.set_role(&input.role, input.access_rule);
+                        Ok(ScryptoValue::from_typed(&()))
+                    }
+                    None => Err(InvokeError::Error(ComponentError::AccessRulesNotFound(
+                        input.index,
+                    ))),
+                };
+                system_api
+                    .substate_return_mut(ref_mut)
+                    .map_err(InvokeError::Downstream)?;
+
+                result
+            }
         }?;
 
         Ok(rtn)
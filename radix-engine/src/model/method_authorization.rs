@@ -1,3 +1,4 @@
+use crate::constants::MAX_MEMBERSHIP_LOOKUPS;
 use crate::model::method_authorization::MethodAuthorizationError::NotAuthorized;
 use crate::model::{AuthZone, Proof};
 use crate::types::*;
@@ -46,24 +47,45 @@ impl HardResourceOrNonFungible {
         }
     }
 
+    /// Whether this is met by the virtual proof resources of an auth zone, in which case it's
+    /// recorded there.
+    fn is_virtually_proven(&self, auth_zone: &AuthZone) -> bool {
+        let is_proven = match self {
+            HardResourceOrNonFungible::NonFungible(non_fungible_address) => auth_zone
+                .virtual_proof_resources
+                .contains(&non_fungible_address.resource_address()),
+            HardResourceOrNonFungible::Resource(resource_address) => {
+                auth_zone.virtual_proof_resources.contains(resource_address)
+            }
+            HardResourceOrNonFungible::SoftResourceNotFound => false,
+        };
+        if is_proven {
+            let mut virtually_proven = auth_zone.virtually_proven.borrow_mut();
+            if !virtually_proven.contains(self) {
+                virtually_proven.push(self.clone());
+            }
+        }
+        is_proven
+    }
+
     pub fn check_has_amount(&self, amount: Decimal, auth_zones: &[&AuthZone]) -> bool {
+        let mut proofs = Vec::new();
         for auth_zone in auth_zones {
-            // FIXME: Need to check the composite max amount rather than just each proof individually
-            if auth_zone
-                .proofs
-                .iter()
-                .any(|p| self.proof_matches(p) && p.total_amount() >= amount)
-            {
+            if self.is_virtually_proven(auth_zone) {
                 return true;
             }
+            proofs.extend(auth_zone.proofs.iter().filter(|p| self.proof_matches(p)));
         }
 
-        false
+        // The amount may be spread over several proofs, e.g. from different vaults
+        !proofs.is_empty() && Proof::compute_total_amount(proofs) >= amount
     }
 
     pub fn check(&self, auth_zones: &[&AuthZone]) -> bool {
         for auth_zone in auth_zones {
-            if auth_zone.proofs.iter().any(|p| self.proof_matches(p)) {
+            if self.is_virtually_proven(auth_zone)
+                || auth_zone.proofs.iter().any(|p| self.proof_matches(p))
+            {
                 return true;
             }
         }
@@ -97,6 +119,7 @@ pub enum HardProofRule {
     AllOf(HardProofRuleResourceList),
     AnyOf(HardProofRuleResourceList),
     CountOf(HardCount, HardProofRuleResourceList),
+    RequireCallerOrigin(CallerOrigin),
 }
 
 impl HardProofRule {
@@ -149,6 +172,13 @@ impl HardProofRule {
                 }
                 Err(NotAuthorized)
             }
+            HardProofRule::RequireCallerOrigin(origin) => {
+                // Only the zone of the direct caller identifies where the call comes from
+                match auth_zones.first() {
+                    Some(auth_zone) if auth_zone.origins.contains(origin) => Ok(()),
+                    _ => Err(NotAuthorized),
+                }
+            }
             _ => Err(NotAuthorized),
         }
     }
@@ -159,23 +189,64 @@ pub enum HardAuthRule {
     ProofRule(HardProofRule),
     AnyOf(Vec<HardAuthRule>),
     AllOf(Vec<HardAuthRule>),
+    CountOf(u8, Vec<HardAuthRule>),
+}
+
+/// A composite rule under evaluation, satisfied once `required` of its rules have passed
+struct PendingRule<'a> {
+    rules: &'a [HardAuthRule],
+    next: usize,
+    required: usize,
+    passed: usize,
 }
 
 impl HardAuthRule {
+    /// Checks a proof rule directly, or pushes a composite rule onto the evaluation stack.
+    fn enter<'a>(
+        &'a self,
+        stack: &mut Vec<PendingRule<'a>>,
+        auth_zones: &[&AuthZone],
+    ) -> Option<bool> {
+        let (rules, required) = match self {
+            HardAuthRule::ProofRule(rule) => return Some(rule.check(auth_zones).is_ok()),
+            HardAuthRule::AnyOf(rules) => (rules, 1),
+            HardAuthRule::AllOf(rules) => (rules, rules.len()),
+            HardAuthRule::CountOf(count, rules) => (rules, *count as usize),
+        };
+        stack.push(PendingRule {
+            rules,
+            next: 0,
+            required,
+            passed: 0,
+        });
+        None
+    }
+
     fn check(&self, auth_zones: &[&AuthZone]) -> Result<(), MethodAuthorizationError> {
-        match self {
-            HardAuthRule::ProofRule(rule) => rule.check(auth_zones),
-            HardAuthRule::AnyOf(rules) => {
-                if !rules.iter().any(|r| r.check(auth_zones).is_ok()) {
-                    return Err(NotAuthorized);
+        // Evaluated with an explicit stack so rule depth is not bounded by the native stack
+        let mut stack = Vec::new();
+        let mut result = self.enter(&mut stack, auth_zones);
+        loop {
+            if let Some(passed) = result.take() {
+                match stack.last_mut() {
+                    Some(pending) => pending.passed += passed as usize,
+                    None if passed => return Ok(()),
+                    None => return Err(NotAuthorized),
                 }
-                Ok(())
             }
-            HardAuthRule::AllOf(rules) => {
-                if rules.iter().any(|r| r.check(auth_zones).is_err()) {
-                    return Err(NotAuthorized);
-                }
-                Ok(())
+
+            let pending = stack.last_mut().expect("Evaluation stack is empty");
+            let remaining = pending.rules.len() - pending.next;
+            if pending.passed >= pending.required {
+                stack.pop();
+                result = Some(true);
+            } else if pending.passed + remaining < pending.required {
+                stack.pop();
+                result = Some(false);
+            } else {
+                let rule = &pending.rules[pending.next];
+                pending.next += 1;
+                result = rule.enter(&mut stack, auth_zones);
             }
         }
     }
@@ -188,15 +259,114 @@ pub enum MethodAuthorization {
     AllowAll,
     DenyAll,
     Unsupported,
+    /// Requires a proof of any non-fungible listed in one of the given key value stores, unless
+    /// one of the alternative rules is satisfied
+    RequireMembership {
+        kv_store_ids: Vec<KeyValueStoreId>,
+        alternatives: Vec<HardAuthRule>,
+    },
 }
 
 impl MethodAuthorization {
+    /// Combines authorizations into one which is satisfied when any of them is.
+    pub fn any_of(authorizations: Vec<MethodAuthorization>) -> Self {
+        let mut kv_store_ids = Vec::new();
+        let mut rules = Vec::new();
+        for authorization in authorizations {
+            match authorization {
+                MethodAuthorization::AllowAll => return MethodAuthorization::AllowAll,
+                MethodAuthorization::Protected(rule) => rules.push(rule),
+                MethodAuthorization::RequireMembership {
+                    kv_store_ids: ids,
+                    alternatives,
+                } => {
+                    kv_store_ids.extend(ids);
+                    rules.extend(alternatives);
+                }
+                MethodAuthorization::DenyAll | MethodAuthorization::Unsupported => {}
+            }
+        }
+
+        if !kv_store_ids.is_empty() {
+            MethodAuthorization::RequireMembership {
+                kv_store_ids,
+                alternatives: rules,
+            }
+        } else if rules.is_empty() {
+            MethodAuthorization::DenyAll
+        } else {
+            MethodAuthorization::Protected(HardAuthRule::AnyOf(rules))
+        }
+    }
+
+    /// Resolves an allow-list requirement into a rule requiring any of the presented
+    /// non-fungibles which are currently listed, by looking them up with `is_member`.
+    ///
+    /// At most `MAX_MEMBERSHIP_LOOKUPS` lookups are made, so only the first non-fungibles
+    /// presented can grant access.
+    pub fn resolve_membership<F, E>(
+        &self,
+        auth_zones: &[&AuthZone],
+        mut is_member: F,
+    ) -> Result<Self, E>
+    where
+        F: FnMut(KeyValueStoreId, &NonFungibleAddress) -> Result<bool, E>,
+    {
+        let (kv_store_ids, alternatives) = match self {
+            MethodAuthorization::RequireMembership {
+                kv_store_ids,
+                alternatives,
+            } => (kv_store_ids, alternatives),
+            _ => return Ok(self.clone()),
+        };
+
+        let mut lookups = 0;
+        let mut members = Vec::new();
+        'lookups: for auth_zone in auth_zones {
+            for proof in &auth_zone.proofs {
+                let ids = match proof.total_ids() {
+                    Ok(ids) => ids,
+                    Err(_) => continue,
+                };
+                for id in ids {
+                    let address = NonFungibleAddress::new(proof.resource_address(), id);
+                    for kv_store_id in kv_store_ids {
+                        if lookups == MAX_MEMBERSHIP_LOOKUPS {
+                            break 'lookups;
+                        }
+                        lookups += 1;
+                        if is_member(*kv_store_id, &address)? {
+                            members.push(HardResourceOrNonFungible::NonFungible(address.clone()));
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut rules = alternatives.clone();
+        if !members.is_empty() {
+            rules.push(HardAuthRule::ProofRule(HardProofRule::AnyOf(
+                HardProofRuleResourceList::List(members),
+            )));
+        }
+        Ok(if rules.is_empty() {
+            MethodAuthorization::DenyAll
+        } else {
+            MethodAuthorization::Protected(HardAuthRule::AnyOf(rules))
+        })
+    }
+
     pub fn check(&self, auth_zones: &[&AuthZone]) -> Result<(), MethodAuthorizationError> {
         match self {
             MethodAuthorization::Protected(rule) => rule.check(auth_zones),
             MethodAuthorization::AllowAll => Ok(()),
             MethodAuthorization::DenyAll => Err(MethodAuthorizationError::NotAuthorized),
             MethodAuthorization::Unsupported => Err(MethodAuthorizationError::UnsupportedMethod),
+            // Must be resolved against the ledger before being checked
+            MethodAuthorization::RequireMembership { .. } => {
+                Err(MethodAuthorizationError::NotAuthorized)
+            }
         }
     }
 }
@@ -1,4 +1,5 @@
 mod abi_extractor;
+mod access_controller;
 mod auth_converter;
 mod auth_zone;
 mod bucket;
@@ -14,18 +15,19 @@ mod resource_manager;
 mod system;
 mod transaction_processor;
 mod vault;
+mod virtual_proofs;
 mod worktop;
-mod wrappers;
 
 pub use crate::engine::InvokeError;
 pub use abi_extractor::*;
+pub use access_controller::{AccessController, AccessControllerError, RecoveryRoleProposal};
 pub use auth_converter::convert;
 pub use auth_zone::{AuthZone, AuthZoneError};
 pub use bucket::{Bucket, BucketError};
-pub use component::{ComponentError, ComponentInfo, ComponentState};
+pub use component::{ComponentAccessRules, ComponentError, ComponentInfo, ComponentState};
 pub use method_authorization::{
-    HardAuthRule, HardProofRule, HardResourceOrNonFungible, MethodAuthorization,
-    MethodAuthorizationError,
+    HardAuthRule, HardCount, HardDecimal, HardProofRule, HardProofRuleResourceList,
+    HardResourceOrNonFungible, MethodAuthorization, MethodAuthorizationError,
 };
 pub use non_fungible::NonFungible;
 pub use package::{Package, PackageError};
@@ -34,10 +36,11 @@ pub use precommitted_kv_store::HeapKeyValueStore;
 pub use proof::*;
 pub use resource::*;
 pub use resource_manager::{ResourceManager, ResourceManagerError};
-pub use system::{System, SystemError};
+pub use system::{System, SystemError, SystemSetFeeTableInput};
 pub use transaction_processor::{
-    TransactionProcessor, TransactionProcessorError, TransactionProcessorRunInput,
+    InstructionOutput, TransactionProcessor, TransactionProcessorError,
+    TransactionProcessorRunInput,
 };
 pub use vault::{Vault, VaultError};
-pub use worktop::{Worktop, WorktopError};
-pub use wrappers::*;
+pub use virtual_proofs::{VirtualProofs, VIRTUAL_BADGE_RESOURCES};
+pub use worktop::{Worktop, WorktopError, WorktopResource};
@@ -2,6 +2,7 @@ use core::fmt::Debug;
 
 use crate::engine::*;
 use crate::fee::FeeReserve;
+use crate::model::{convert, MethodAuthorization};
 use crate::types::*;
 use crate::wasm::*;
 
@@ -10,6 +11,9 @@ use crate::wasm::*;
 pub struct Package {
     code: Vec<u8>,
     blueprint_abis: HashMap<String, BlueprintAbi>,
+    metadata: HashMap<String, String>,
+    /// The badge whose proof is required for package operations, if any.
+    owner_badge: Option<NonFungibleAddress>,
 }
 
 #[derive(Debug, TypeId, Encode, Decode)]
@@ -19,6 +23,7 @@ pub enum PackageError {
     InvalidWasm(PrepareError),
     BlueprintNotFound,
     MethodNotFound(String),
+    InvalidMethod,
 }
 
 impl Package {
@@ -28,6 +33,8 @@ impl Package {
         Ok(Self {
             code: code,
             blueprint_abis: abi,
+            metadata: HashMap::new(),
+            owner_badge: None,
         })
     }
 
@@ -35,10 +42,122 @@ impl Package {
         &self.code
     }
 
+    /// Returns the approximate serialized size of the code and ABIs.
+    pub fn size(&self) -> usize {
+        self.code.len() + scrypto_encode(&self.blueprint_abis).len()
+    }
+
     pub fn blueprint_abi(&self, blueprint_name: &str) -> Option<&BlueprintAbi> {
         self.blueprint_abis.get(blueprint_name)
     }
 
+    pub fn metadata(&self) -> &HashMap<String, String> {
+        &self.metadata
+    }
+
+    pub fn owner_badge(&self) -> Option<&NonFungibleAddress> {
+        self.owner_badge.as_ref()
+    }
+
+    /// Packages published without an owner badge can't be modified.
+    pub fn get_auth(&self, package_fn: PackageFnIdentifier) -> MethodAuthorization {
+        match (package_fn, &self.owner_badge) {
+            (PackageFnIdentifier::UpdateMetadata, Some(owner_badge)) => convert(
+                &Type::Unit,
+                &ScryptoValue::unit(),
+                &rule!(require(owner_badge.clone())),
+            ),
+            _ => MethodAuthorization::DenyAll,
+        }
+    }
+
+    fn read_package<'s, Y, W, I, R>(
+        code: &Blob,
+        abi: &Blob,
+        system_api: &mut Y,
+    ) -> Result<Package, InvokeError<PackageError>>
+    where
+        Y: SystemApi<'s, W, I, R>,
+        W: WasmEngine<I>,
+        I: WasmInstance,
+        R: FeeReserve,
+    {
+        let code = system_api
+            .read_blob(&code.0)
+            .map_err(InvokeError::Downstream)?
+            .to_vec();
+        let abi = system_api
+            .read_blob(&abi.0)
+            .map_err(InvokeError::Downstream)
+            .and_then(|blob| {
+                scrypto_decode::<HashMap<String, BlueprintAbi>>(blob)
+                    .map_err(|e| InvokeError::Error(PackageError::InvalidAbi(e)))
+            })?;
+        Package::new(code, abi).map_err(|e| InvokeError::Error(PackageError::InvalidWasm(e)))
+    }
+
+    fn globalize_package<'s, Y, W, I, R>(
+        package: Package,
+        system_api: &mut Y,
+    ) -> Result<PackageAddress, InvokeError<PackageError>>
+    where
+        Y: SystemApi<'s, W, I, R>,
+        W: WasmEngine<I>,
+        I: WasmInstance,
+        R: FeeReserve,
+    {
+        let node_id = system_api
+            .node_create(HeapRENode::Package(package))
+            .map_err(InvokeError::Downstream)?;
+        system_api
+            .node_globalize(node_id)
+            .map_err(InvokeError::Downstream)?;
+        Ok(node_id.into())
+    }
+
+    fn mint_owner_badge<'s, Y, W, I, R>(
+        system_api: &mut Y,
+    ) -> Result<(NonFungibleAddress, scrypto::resource::Bucket), InvokeError<PackageError>>
+    where
+        Y: SystemApi<'s, W, I, R>,
+        W: WasmEngine<I>,
+        I: WasmInstance,
+        R: FeeReserve,
+    {
+        let mut metadata = HashMap::new();
+        metadata.insert("name".to_string(), "Package Owner Badge".to_string());
+        let mut access_rules = HashMap::new();
+        access_rules.insert(ResourceMethodAuthKey::Withdraw, (rule!(allow_all), LOCKED));
+        access_rules.insert(ResourceMethodAuthKey::Deposit, (rule!(allow_all), LOCKED));
+        let non_fungible_id = NonFungibleId::from_u32(1);
+        let mut entries = HashMap::new();
+        entries.insert(
+            non_fungible_id.clone(),
+            (scrypto_encode(&()), scrypto_encode(&())),
+        );
+
+        let result = system_api
+            .invoke_function(
+                FnIdentifier::Native(NativeFnIdentifier::ResourceManager(
+                    ResourceManagerFnIdentifier::Create,
+                )),
+                ScryptoValue::from_typed(&ResourceManagerCreateInput {
+                    resource_type: ResourceType::NonFungible,
+                    metadata,
+                    access_rules,
+                    mint_params: Some(MintParams::NonFungible { entries }),
+                }),
+            )
+            .map_err(InvokeError::Downstream)?;
+        let (resource_address, bucket): (ResourceAddress, Option<scrypto::resource::Bucket>) =
+            scrypto_decode(&result.raw).expect("Failed to decode ResourceManager Create output");
+
+        Ok((
+            NonFungibleAddress::new(resource_address, non_fungible_id),
+            bucket.expect("Owner badge should have been minted"),
+        ))
+    }
+
     pub fn static_main<'s, Y, W, I, R>(
         package_fn: PackageFnIdentifier,
         call_data: ScryptoValue,
@@ -54,27 +173,58 @@ impl Package {
             PackageFnIdentifier::Publish => {
                 let input: PackagePublishInput = scrypto_decode(&call_data.raw)
                     .map_err(|e| InvokeError::Error(PackageError::InvalidRequestData(e)))?;
-                let code = system_api
-                    .read_blob(&input.code.0)
-                    .map_err(InvokeError::Downstream)?
-                    .to_vec();
-                let abi = system_api
-                    .read_blob(&input.abi.0)
-                    .map_err(InvokeError::Downstream)
-                    .and_then(|blob| {
-                        scrypto_decode::<HashMap<String, BlueprintAbi>>(blob)
-                            .map_err(|e| InvokeError::Error(PackageError::InvalidAbi(e)))
-                    })?;
-                let package = Package::new(code, abi)
-                    .map_err(|e| InvokeError::Error(PackageError::InvalidWasm(e)))?;
-                let node_id = system_api
-                    .node_create(HeapRENode::Package(package))
+                let package = Self::read_package(&input.code, &input.abi, system_api)?;
+                let package_address = Self::globalize_package(package, system_api)?;
+                Ok(ScryptoValue::from_typed(&package_address))
+            }
+            PackageFnIdentifier::PublishWithOwner => {
+                let input: PackagePublishWithOwnerInput = scrypto_decode(&call_data.raw)
+                    .map_err(|e| InvokeError::Error(PackageError::InvalidRequestData(e)))?;
+                let mut package = Self::read_package(&input.code, &input.abi, system_api)?;
+                let (owner_badge, bucket) = match input.owner_badge {
+                    Some(owner_badge) => (owner_badge, None),
+                    None => {
+                        let (owner_badge, bucket) = Self::mint_owner_badge(system_api)?;
+                        (owner_badge, Some(bucket))
+                    }
+                };
+                package.owner_badge = Some(owner_badge);
+                let package_address = Self::globalize_package(package, system_api)?;
+                Ok(ScryptoValue::from_typed(&(package_address, bucket)))
+            }
+            PackageFnIdentifier::UpdateMetadata => {
+                Err(InvokeError::Error(PackageError::InvalidMethod))
+            }
+        }
+    }
+
+    pub fn main<'s, Y, W, I, R>(
+        package_address: PackageAddress,
+        package_fn: PackageFnIdentifier,
+        call_data: ScryptoValue,
+        system_api: &mut Y,
+    ) -> Result<ScryptoValue, InvokeError<PackageError>>
+    where
+        Y: SystemApi<'s, W, I, R>,
+        W: WasmEngine<I>,
+        I: WasmInstance,
+        R: FeeReserve,
+    {
+        match package_fn {
+            PackageFnIdentifier::UpdateMetadata => {
+                let input: PackageUpdateMetadataInput = scrypto_decode(&call_data.raw)
+                    .map_err(|e| InvokeError::Error(PackageError::InvalidRequestData(e)))?;
+                let mut ref_mut = system_api
+                    .substate_borrow_mut(&SubstateId::Package(package_address))
                     .map_err(InvokeError::Downstream)?;
+                ref_mut.package().map_err(InvokeError::Downstream)?.metadata = input.metadata;
                 system_api
-                    .node_globalize(node_id)
+                    .substate_return_mut(ref_mut)
                     .map_err(InvokeError::Downstream)?;
-                let package_address: PackageAddress = node_id.into();
-                Ok(ScryptoValue::from_typed(&package_address))
+                Ok(ScryptoValue::from_typed(&()))
+            }
+            PackageFnIdentifier::Publish | PackageFnIdentifier::PublishWithOwner => {
+                Err(InvokeError::Error(PackageError::InvalidMethod))
             }
         }
     }
@@ -85,6 +235,8 @@ impl Debug for Package {
         f.debug_struct("Package")
             .field("code_len", &self.code.len())
             .field("blueprint_abis", &self.blueprint_abis)
+            .field("metadata", &self.metadata)
+            .field("owner_badge", &self.owner_badge)
             .finish()
     }
 }
@@ -68,61 +68,71 @@ impl Proof {
         HashMap<ResourceContainerId, LockedAmountOrIds>,
     ) {
         // filter proofs by resource address and restricted flag
-        let proofs: Vec<&Proof> = proofs
+        let proofs = proofs
             .iter()
-            .filter(|p| p.resource_address() == resource_address && !p.is_restricted())
-            .collect();
+            .filter(|p| p.resource_address() == resource_address && !p.is_restricted());
 
         // calculate the max locked amount (or ids) of each container
-        match resource_type {
-            ResourceType::Fungible { .. } => {
-                let mut max = HashMap::<ResourceContainerId, Decimal>::new();
-                for proof in &proofs {
-                    for (container_id, (_, locked_amount_or_ids)) in &proof.evidence {
-                        let new_amount = locked_amount_or_ids.amount();
-                        if let Some(existing) = max.get_mut(&container_id) {
-                            *existing = Decimal::max(*existing, new_amount);
-                        } else {
-                            max.insert(container_id.clone(), new_amount);
-                        }
-                    }
-                }
-                let total = max
+        let per_container = Self::max_locked_per_container(proofs);
+        let total = match resource_type {
+            ResourceType::Fungible { .. } => LockedAmountOrIds::Amount(
+                per_container
                     .values()
-                    .cloned()
+                    .map(LockedAmountOrIds::amount)
                     .reduce(|a, b| a + b)
-                    .unwrap_or_default();
-                let per_container = max
-                    .into_iter()
-                    .map(|(k, v)| (k, LockedAmountOrIds::Amount(v)))
-                    .collect();
-                (LockedAmountOrIds::Amount(total), per_container)
-            }
+                    .unwrap_or_default(),
+            ),
             ResourceType::NonFungible => {
-                let mut max = HashMap::<ResourceContainerId, BTreeSet<NonFungibleId>>::new();
-                for proof in &proofs {
-                    for (container_id, (_, locked_amount_or_ids)) in &proof.evidence {
-                        let new_ids = locked_amount_or_ids
+                let mut total = BTreeSet::<NonFungibleId>::new();
+                for value in per_container.values() {
+                    total.extend(
+                        value
                             .ids()
-                            .expect("Failed to list non-fungible IDS on non-fungible proof");
-                        if let Some(ids) = max.get_mut(&container_id) {
-                            ids.extend(new_ids);
-                        } else {
-                            max.insert(container_id.clone(), new_ids);
-                        }
-                    }
+                            .expect("Failed to list non-fungible IDS on non-fungible proof"),
+                    );
                 }
-                let mut total = BTreeSet::<NonFungibleId>::new();
-                for value in max.values() {
-                    total.extend(value.clone());
+                LockedAmountOrIds::Ids(total)
+            }
+        };
+        (total, per_container)
+    }
+
+    /// Computes the amount proven by the given proofs altogether. Proofs supported by the same
+    /// resource container, such as clones, are not counted twice.
+    pub fn compute_total_amount<'a, I: IntoIterator<Item = &'a Proof>>(proofs: I) -> Decimal {
+        Self::max_locked_per_container(proofs)
+            .values()
+            .map(LockedAmountOrIds::amount)
+            .reduce(|a, b| a + b)
+            .unwrap_or_default()
+    }
+
+    fn max_locked_per_container<'a, I: IntoIterator<Item = &'a Proof>>(
+        proofs: I,
+    ) -> HashMap<ResourceContainerId, LockedAmountOrIds> {
+        let mut max = HashMap::<ResourceContainerId, LockedAmountOrIds>::new();
+        for proof in proofs {
+            for (container_id, (_, locked_amount_or_ids)) in &proof.evidence {
+                match (max.get_mut(container_id), locked_amount_or_ids) {
+                    (
+                        Some(LockedAmountOrIds::Amount(existing)),
+                        LockedAmountOrIds::Amount(new_amount),
+                    ) => {
+                        *existing = Decimal::max(*existing, *new_amount);
+                    }
+                    (Some(LockedAmountOrIds::Ids(ids)), LockedAmountOrIds::Ids(new_ids)) => {
+                        ids.extend(new_ids.iter().cloned());
+                    }
+                    (Some(_), _) => {
+                        panic!("Inconsistent locked amount or ids within a resource container")
+                    }
+                    (None, _) => {
+                        max.insert(container_id.clone(), locked_amount_or_ids.clone());
+                    }
                 }
-                let per_container = max
-                    .into_iter()
-                    .map(|(k, v)| (k, LockedAmountOrIds::Ids(v)))
-                    .collect();
-                (LockedAmountOrIds::Ids(total), per_container)
             }
         }
+        max
     }
 
     /// Creates a composite proof from proofs. This method will generate a max proof.
@@ -324,6 +334,15 @@ impl Proof {
         self.restricted
     }
 
+    /// Returns the serialized size of the locked amount or ids, excluding the supporting
+    /// containers which are accounted for by their owners.
+    pub fn size(&self) -> usize {
+        match &self.total_locked {
+            LockedAmountOrIds::Amount(amount) => scrypto_encode(amount).len(),
+            LockedAmountOrIds::Ids(ids) => scrypto_encode(ids).len(),
+        }
+    }
+
     pub fn main<'s, Y, W, I, R>(
         proof_id: ProofId,
         proof_fn: ProofFnIdentifier,
@@ -340,7 +359,7 @@ impl Proof {
         let mut node_ref = system_api
             .substate_borrow_mut(&substate_id)
             .map_err(InvokeError::Downstream)?;
-        let proof = node_ref.proof();
+        let proof = node_ref.proof().map_err(InvokeError::Downstream)?;
 
         let rtn = match proof_fn {
             ProofFnIdentifier::GetAmount => {
@@ -1,10 +1,10 @@
 use crate::engine::{HeapRENode, SystemApi};
 use crate::fee::FeeReserve;
 use crate::model::resource_manager::ResourceMethodRule::{Protected, Public};
+use crate::model::InvokeError;
 use crate::model::ResourceManagerError::InvalidMethod;
 use crate::model::{convert, MethodAuthorization, ResourceContainer};
 use crate::model::{Bucket, NonFungible, Vault};
-use crate::model::{InvokeError, NonFungibleWrapper};
 use crate::types::AccessRule::*;
 use crate::types::ResourceMethodAuthKey::*;
 use crate::types::*;
@@ -335,9 +335,9 @@ impl ResourceManager {
             let value = system_api
                 .substate_read(SubstateId::NonFungible(self_address, id.clone()))
                 .map_err(InvokeError::Downstream)?;
-            let wrapper: NonFungibleWrapper =
-                scrypto_decode(&value.raw).expect("Failed to decode NonFungibleWrapper substate");
-            if wrapper.0.is_some() {
+            let non_fungible: Option<NonFungible> =
+                scrypto_decode(&value.raw).expect("Failed to decode NonFungible substate");
+            if non_fungible.is_some() {
                 return Err(InvokeError::Error(
                     ResourceManagerError::NonFungibleAlreadyExists(NonFungibleAddress::new(
                         self_address,
@@ -350,7 +350,7 @@ impl ResourceManager {
             system_api
                 .substate_write(
                     SubstateId::NonFungible(self_address, id.clone()),
-                    ScryptoValue::from_typed(&NonFungibleWrapper(Some(non_fungible))),
+                    ScryptoValue::from_typed(&Some(non_fungible)),
                 )
                 .map_err(InvokeError::Downstream)?;
             ids.insert(id);
@@ -494,7 +494,9 @@ impl ResourceManager {
         let mut ref_mut = system_api
             .substate_borrow_mut(&substate_id)
             .map_err(InvokeError::Downstream)?;
-        let resource_manager = ref_mut.resource_manager();
+        let resource_manager = ref_mut
+            .resource_manager()
+            .map_err(InvokeError::Downstream)?;
 
         let rtn = match resource_manager_fn {
             ResourceManagerFnIdentifier::UpdateAuth => {
@@ -596,16 +598,16 @@ impl ResourceManager {
                         input.id.clone(),
                     ))
                     .map_err(InvokeError::Downstream)?;
-                let wrapper: NonFungibleWrapper = scrypto_decode(&value.raw)
-                    .expect("Failed to decode NonFungibleWrapper substate");
+                let non_fungible: Option<NonFungible> =
+                    scrypto_decode(&value.raw).expect("Failed to decode NonFungible substate");
 
                 // Write new value
-                if let Some(mut non_fungible) = wrapper.0 {
+                if let Some(mut non_fungible) = non_fungible {
                     non_fungible.set_mutable_data(input.data);
                     system_api
                         .substate_write(
                             SubstateId::NonFungible(resource_address.clone(), input.id.clone()),
-                            ScryptoValue::from_typed(&NonFungibleWrapper(Some(non_fungible))),
+                            ScryptoValue::from_typed(&Some(non_fungible)),
                         )
                         .map_err(InvokeError::Downstream)?;
                 } else {
@@ -625,9 +627,9 @@ impl ResourceManager {
                 let value = system_api
                     .substate_read(SubstateId::NonFungible(resource_address.clone(), input.id))
                     .map_err(InvokeError::Downstream)?;
-                let wrapper: NonFungibleWrapper = scrypto_decode(&value.raw)
-                    .expect("Failed to decode NonFungibleWrapper substate");
-                Ok(ScryptoValue::from_typed(&wrapper.0.is_some()))
+                let non_fungible: Option<NonFungible> =
+                    scrypto_decode(&value.raw).expect("Failed to decode NonFungible substate");
+                Ok(ScryptoValue::from_typed(&non_fungible.is_some()))
             }
             ResourceManagerFnIdentifier::GetNonFungible => {
                 let input: ResourceManagerGetNonFungibleInput = scrypto_decode(&args.raw)
@@ -637,9 +639,9 @@ impl ResourceManager {
                 let value = system_api
                     .substate_read(SubstateId::NonFungible(resource_address.clone(), input.id))
                     .map_err(InvokeError::Downstream)?;
-                let wrapper: NonFungibleWrapper = scrypto_decode(&value.raw)
-                    .expect("Failed to decode NonFungibleWrapper substate");
-                let non_fungible = wrapper.0.ok_or(InvokeError::Error(
+                let non_fungible: Option<NonFungible> =
+                    scrypto_decode(&value.raw).expect("Failed to decode NonFungible substate");
+                let non_fungible = non_fungible.ok_or(InvokeError::Error(
                     ResourceManagerError::NonFungibleNotFound(non_fungible_address),
                 ))?;
                 Ok(ScryptoValue::from_typed(&[
@@ -1,5 +1,7 @@
+use crate::constants::DEFAULT_COST_UNIT_PRICE;
 use crate::engine::SystemApi;
-use crate::fee::FeeReserve;
+use crate::fee::{FeeReserve, FeeTable};
+use crate::ledger::ReadableSubstateStore;
 use crate::model::InvokeError;
 use crate::types::*;
 use crate::wasm::*;
@@ -7,14 +9,50 @@ use crate::wasm::*;
 #[derive(Debug, TypeId, Encode, Decode)]
 pub enum SystemError {
     InvalidRequestData(DecodeError),
+    /// The new fee table version is not greater than the current one.
+    InvalidFeeTableVersion {
+        current: u32,
+        new: u32,
+    },
+    /// The cost unit price must be positive.
+    InvalidCostUnitPrice(Decimal),
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
 pub struct System {
     pub epoch: u64,
+    pub fee_table: FeeTable,
+    pub cost_unit_price: Decimal,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct SystemSetFeeTableInput {
+    pub fee_table: FeeTable,
 }
 
 impl System {
+    /// Loads the fee table in effect, falling back to the default one if the store
+    /// has not been bootstrapped.
+    pub fn load_fee_table<S: ReadableSubstateStore>(substate_store: &S) -> FeeTable {
+        substate_store
+            .get_substate(&SubstateId::System)
+            .map(|output| output.substate.system().fee_table.clone())
+            .unwrap_or_else(FeeTable::new)
+    }
+
+    /// Loads the cost unit price in effect, falling back to the default one if the store
+    /// has not been bootstrapped.
+    pub fn load_cost_unit_price<S: ReadableSubstateStore>(substate_store: &S) -> Decimal {
+        substate_store
+            .get_substate(&SubstateId::System)
+            .map(|output| output.substate.system().cost_unit_price)
+            .unwrap_or_else(|| {
+                DEFAULT_COST_UNIT_PRICE
+                    .parse()
+                    .expect("Invalid DEFAULT_COST_UNIT_PRICE")
+            })
+    }
+
     pub fn main<'s, Y, W, I, R>(
         system_fn: SystemFnIdentifier,
         args: ScryptoValue,
@@ -33,7 +71,9 @@ impl System {
                 let node_ref = system_api
                     .borrow_node(&RENodeId::System)
                     .map_err(InvokeError::Downstream)?;
-                Ok(ScryptoValue::from_typed(&node_ref.system().epoch))
+                Ok(ScryptoValue::from_typed(
+                    &node_ref.system().map_err(InvokeError::Downstream)?.epoch,
+                ))
             }
             SystemFnIdentifier::SetEpoch => {
                 let SystemSetEpochInput { epoch } = scrypto_decode(&args.raw)
@@ -41,7 +81,71 @@ impl System {
                 let mut system_node_ref = system_api
                     .substate_borrow_mut(&SubstateId::System)
                     .map_err(InvokeError::Downstream)?;
-                system_node_ref.system().epoch = epoch;
+                system_node_ref
+                    .system()
+                    .map_err(InvokeError::Downstream)?
+                    .epoch = epoch;
+                system_api
+                    .substate_return_mut(system_node_ref)
+                    .map_err(InvokeError::Downstream)?;
+                Ok(ScryptoValue::from_typed(&()))
+            }
+            SystemFnIdentifier::SetFeeTable => {
+                let SystemSetFeeTableInput { fee_table } = scrypto_decode(&args.raw)
+                    .map_err(|e| InvokeError::Error(SystemError::InvalidRequestData(e)))?;
+                let current = system_api
+                    .borrow_node(&RENodeId::System)
+                    .map_err(InvokeError::Downstream)?
+                    .system()
+                    .map_err(InvokeError::Downstream)?
+                    .fee_table
+                    .version();
+                if fee_table.version() <= current {
+                    return Err(InvokeError::Error(SystemError::InvalidFeeTableVersion {
+                        current,
+                        new: fee_table.version(),
+                    }));
+                }
+                let mut system_node_ref = system_api
+                    .substate_borrow_mut(&SubstateId::System)
+                    .map_err(InvokeError::Downstream)?;
+                system_node_ref
+                    .system()
+                    .map_err(InvokeError::Downstream)?
+                    .fee_table = fee_table;
+                system_api
+                    .substate_return_mut(system_node_ref)
+                    .map_err(InvokeError::Downstream)?;
+                Ok(ScryptoValue::from_typed(&()))
+            }
+            SystemFnIdentifier::GetCostUnitPrice => {
+                let _: SystemGetCostUnitPriceInput = scrypto_decode(&args.raw)
+                    .map_err(|e| InvokeError::Error(SystemError::InvalidRequestData(e)))?;
+                let node_ref = system_api
+                    .borrow_node(&RENodeId::System)
+                    .map_err(InvokeError::Downstream)?;
+                Ok(ScryptoValue::from_typed(
+                    &node_ref
+                        .system()
+                        .map_err(InvokeError::Downstream)?
+                        .cost_unit_price,
+                ))
+            }
+            SystemFnIdentifier::SetCostUnitPrice => {
+                let SystemSetCostUnitPriceInput { cost_unit_price } = scrypto_decode(&args.raw)
+                    .map_err(|e| InvokeError::Error(SystemError::InvalidRequestData(e)))?;
+                if !cost_unit_price.is_positive() {
+                    return Err(InvokeError::Error(SystemError::InvalidCostUnitPrice(
+                        cost_unit_price,
+                    )));
+                }
+                let mut system_node_ref = system_api
+                    .substate_borrow_mut(&SubstateId::System)
+                    .map_err(InvokeError::Downstream)?;
+                system_node_ref
+                    .system()
+                    .map_err(InvokeError::Downstream)?
+                    .cost_unit_price = cost_unit_price;
                 system_api
                     .substate_return_mut(system_node_ref)
                     .map_err(InvokeError::Downstream)?;
@@ -56,6 +160,15 @@ impl System {
                         .map_err(InvokeError::Downstream)?,
                 ))
             }
+            SystemFnIdentifier::GetTransactionSigners => {
+                let _: SystemGetTransactionSignersInput = scrypto_decode(&args.raw)
+                    .map_err(|e| InvokeError::Error(SystemError::InvalidRequestData(e)))?;
+                Ok(ScryptoValue::from_typed(
+                    &system_api
+                        .transaction_signers()
+                        .map_err(InvokeError::Downstream)?,
+                ))
+            }
         }
     }
 }
@@ -6,10 +6,10 @@ use crate::engine::{HeapRENode, SystemApi};
 use crate::fee::FeeReserve;
 use crate::model::worktop::{
     WorktopAssertContainsAmountInput, WorktopAssertContainsInput,
-    WorktopAssertContainsNonFungiblesInput, WorktopDrainInput, WorktopPutInput,
-    WorktopTakeAllInput, WorktopTakeAmountInput, WorktopTakeNonFungiblesInput,
+    WorktopAssertContainsNonFungiblesInput, WorktopContentsInput, WorktopDrainInput,
+    WorktopPutInput, WorktopTakeAllInput, WorktopTakeAmountInput, WorktopTakeNonFungiblesInput,
 };
-use crate::model::InvokeError;
+use crate::model::{Bucket, InvokeError, VirtualProofs};
 use crate::types::*;
 use crate::wasm::*;
 
@@ -18,6 +18,7 @@ use super::Worktop;
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct TransactionProcessorRunInput {
     pub instructions: Vec<Instruction>,
+    pub subintents: Vec<ExecutableSubintent>,
 }
 
 #[derive(Debug, TypeId, Encode, Decode)]
@@ -27,6 +28,20 @@ pub enum TransactionProcessorError {
     BucketNotFound(BucketId),
     ProofNotFound(ProofId),
     IdAllocationError(IdAllocationError),
+    SubintentNotFound(u32),
+}
+
+/// The result of a single manifest instruction.
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode)]
+pub struct InstructionOutput {
+    /// The value returned by the instruction
+    pub output: Vec<u8>,
+    /// The buckets the instruction created, by their id in the manifest
+    pub new_bucket_ids: Vec<BucketId>,
+    /// The proofs the instruction created, by their id in the manifest
+    pub new_proof_ids: Vec<ProofId>,
+    /// The net change of each resource on the worktop
+    pub worktop_changes: Vec<(ResourceAddress, Decimal)>,
 }
 
 pub struct TransactionProcessor {}
@@ -106,6 +121,11 @@ impl TransactionProcessor {
         I: WasmInstance,
         R: FeeReserve,
     {
+        // Leave the value and its index untouched unless there is something to replace
+        if args.expressions.is_empty() {
+            return Ok(args);
+        }
+
         let mut value = args.dom;
         for (expression, path) in args.expressions {
             match expression.0.as_str() {
@@ -184,8 +204,43 @@ impl TransactionProcessor {
             .0
     }
 
-    pub fn static_main<'s, Y, W, I, R>(
-        transaction_processor_fn: TransactionProcessorFnIdentifier,
+    fn worktop_amounts<'s, Y, W, I, R>(system_api: &mut Y) -> BTreeMap<ResourceAddress, Decimal>
+    where
+        Y: SystemApi<'s, W, I, R>,
+        W: WasmEngine<I>,
+        I: WasmInstance,
+        R: FeeReserve,
+    {
+        system_api
+            .worktop()
+            .map(|worktop| {
+                worktop
+                    .resource_addresses()
+                    .into_iter()
+                    .map(|resource_address| {
+                        (resource_address, worktop.total_amount(resource_address))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn worktop_changes(
+        before: BTreeMap<ResourceAddress, Decimal>,
+        mut after: BTreeMap<ResourceAddress, Decimal>,
+    ) -> Vec<(ResourceAddress, Decimal)> {
+        for (resource_address, amount) in before {
+            *after.entry(resource_address).or_default() -= amount;
+        }
+        after
+            .into_iter()
+            .filter(|(_, change)| !change.is_zero())
+            .collect()
+    }
+
+    fn invoke_scrypto_method<'s, Y, W, I, R>(
+        component_address: ComponentAddress,
+        ident: &str,
         call_data: ScryptoValue,
         system_api: &mut Y,
     ) -> Result<ScryptoValue, InvokeError<TransactionProcessorError>>
@@ -195,553 +250,707 @@ impl TransactionProcessor {
         I: WasmInstance,
         R: FeeReserve,
     {
-        match transaction_processor_fn {
-            TransactionProcessorFnIdentifier::Run => {
-                let input: TransactionProcessorRunInput =
-                    scrypto_decode(&call_data.raw).map_err(|e| {
-                        InvokeError::Error(TransactionProcessorError::InvalidRequestData(e))
-                    })?;
+        let component_info = system_api
+            .substate_read(SubstateId::ComponentInfo(component_address))
+            .map_err(InvokeError::Downstream)?;
+        let (package_address, blueprint_name): (PackageAddress, String) =
+            scrypto_decode(&component_info.raw).expect("Failed to decode ComponentInfo substate");
 
-                let mut proof_id_mapping = HashMap::new();
-                let mut bucket_id_mapping = HashMap::new();
-                let mut outputs = Vec::new();
-                let mut id_allocator = IdAllocator::new(IdSpace::Transaction);
+        system_api
+            .invoke_method(
+                Receiver::Ref(RENodeId::Component(component_address)),
+                FnIdentifier::Scrypto {
+                    ident: ident.to_string(),
+                    package_address,
+                    blueprint_name,
+                },
+                call_data,
+            )
+            .map_err(InvokeError::Downstream)
+    }
 
-                let _worktop_id = system_api
-                    .node_create(HeapRENode::Worktop(Worktop::new()))
-                    .map_err(InvokeError::Downstream)?;
+    /// Moves the proofs returned by a call into the auth zone, and the buckets onto the worktop.
+    fn move_returned_resources<'s, Y, W, I, R>(
+        result: ScryptoValue,
+        system_api: &mut Y,
+    ) -> Result<ScryptoValue, InvokeError<TransactionProcessorError>>
+    where
+        Y: SystemApi<'s, W, I, R>,
+        W: WasmEngine<I>,
+        I: WasmInstance,
+        R: FeeReserve,
+    {
+        for (proof_id, _) in &result.proof_ids {
+            system_api
+                .invoke_method(
+                    Receiver::CurrentAuthZone,
+                    FnIdentifier::Native(NativeFnIdentifier::AuthZone(AuthZoneFnIdentifier::Push)),
+                    ScryptoValue::from_typed(&AuthZonePushInput {
+                        proof: scrypto::resource::Proof(*proof_id),
+                    }),
+                )
+                .map_err(InvokeError::Downstream)?;
+        }
+        for (bucket_id, _) in &result.bucket_ids {
+            system_api
+                .invoke_method(
+                    Receiver::Ref(RENodeId::Worktop),
+                    FnIdentifier::Native(NativeFnIdentifier::Worktop(WorktopFnIdentifier::Put)),
+                    ScryptoValue::from_typed(&WorktopPutInput {
+                        bucket: scrypto::resource::Bucket(*bucket_id),
+                    }),
+                )
+                .map_err(InvokeError::downstream)?;
+        }
+        Ok(result)
+    }
 
-                for inst in &input.instructions.clone() {
-                    let result = match inst {
-                        Instruction::TakeFromWorktop { resource_address } => id_allocator
-                            .new_bucket_id()
-                            .map_err(|e| {
-                                InvokeError::Error(TransactionProcessorError::IdAllocationError(e))
-                            })
-                            .and_then(|new_id| {
-                                system_api
-                                    .invoke_method(
-                                        Receiver::Ref(RENodeId::Worktop),
-                                        FnIdentifier::Native(NativeFnIdentifier::Worktop(
-                                            WorktopFnIdentifier::TakeAll,
-                                        )),
-                                        ScryptoValue::from_typed(&WorktopTakeAllInput {
-                                            resource_address: *resource_address,
-                                        }),
-                                    )
-                                    .map_err(InvokeError::Downstream)
-                                    .map(|rtn| {
-                                        let bucket_id = Self::first_bucket(&rtn);
-                                        bucket_id_mapping.insert(new_id, bucket_id);
-                                        ScryptoValue::from_typed(&scrypto::resource::Bucket(new_id))
-                                    })
-                            }),
-                        Instruction::TakeFromWorktopByAmount {
-                            amount,
-                            resource_address,
-                        } => id_allocator
-                            .new_bucket_id()
-                            .map_err(|e| {
-                                InvokeError::Error(TransactionProcessorError::IdAllocationError(e))
-                            })
-                            .and_then(|new_id| {
-                                system_api
-                                    .invoke_method(
-                                        Receiver::Ref(RENodeId::Worktop),
-                                        FnIdentifier::Native(NativeFnIdentifier::Worktop(
-                                            WorktopFnIdentifier::TakeAmount,
-                                        )),
-                                        ScryptoValue::from_typed(&WorktopTakeAmountInput {
-                                            amount: *amount,
-                                            resource_address: *resource_address,
-                                        }),
-                                    )
-                                    .map_err(InvokeError::Downstream)
-                                    .map(|rtn| {
-                                        let bucket_id = Self::first_bucket(&rtn);
-                                        bucket_id_mapping.insert(new_id, bucket_id);
-                                        ScryptoValue::from_typed(&scrypto::resource::Bucket(new_id))
-                                    })
-                            }),
-                        Instruction::TakeFromWorktopByIds {
-                            ids,
-                            resource_address,
-                        } => id_allocator
-                            .new_bucket_id()
-                            .map_err(|e| {
-                                InvokeError::Error(TransactionProcessorError::IdAllocationError(e))
-                            })
-                            .and_then(|new_id| {
-                                system_api
-                                    .invoke_method(
-                                        Receiver::Ref(RENodeId::Worktop),
-                                        FnIdentifier::Native(NativeFnIdentifier::Worktop(
-                                            WorktopFnIdentifier::TakeNonFungibles,
-                                        )),
-                                        ScryptoValue::from_typed(&WorktopTakeNonFungiblesInput {
-                                            ids: ids.clone(),
-                                            resource_address: *resource_address,
-                                        }),
-                                    )
-                                    .map_err(InvokeError::Downstream)
-                                    .map(|rtn| {
-                                        let bucket_id = Self::first_bucket(&rtn);
-                                        bucket_id_mapping.insert(new_id, bucket_id);
-                                        ScryptoValue::from_typed(&scrypto::resource::Bucket(new_id))
-                                    })
-                            }),
-                        Instruction::ReturnToWorktop { bucket_id } => bucket_id_mapping
-                            .remove(bucket_id)
-                            .map(|real_id| {
-                                system_api
-                                    .invoke_method(
-                                        Receiver::Ref(RENodeId::Worktop),
-                                        FnIdentifier::Native(NativeFnIdentifier::Worktop(
-                                            WorktopFnIdentifier::Put,
-                                        )),
-                                        ScryptoValue::from_typed(&WorktopPutInput {
-                                            bucket: scrypto::resource::Bucket(real_id),
-                                        }),
-                                    )
-                                    .map_err(InvokeError::Downstream)
-                            })
-                            .unwrap_or(Err(InvokeError::Error(
-                                TransactionProcessorError::BucketNotFound(*bucket_id),
-                            ))),
-                        Instruction::AssertWorktopContains { resource_address } => system_api
+    fn run_instructions<'s, Y, W, I, R>(
+        instructions: &[Instruction],
+        subintents: &[ExecutableSubintent],
+        system_api: &mut Y,
+    ) -> Result<Vec<InstructionOutput>, InvokeError<TransactionProcessorError>>
+    where
+        Y: SystemApi<'s, W, I, R>,
+        W: WasmEngine<I>,
+        I: WasmInstance,
+        R: FeeReserve,
+    {
+        let mut proof_id_mapping = HashMap::new();
+        let mut bucket_id_mapping = HashMap::new();
+        let mut outputs = Vec::new();
+        let mut id_allocator = IdAllocator::new(IdSpace::Transaction);
+
+        for (index, inst) in instructions.iter().enumerate() {
+            system_api
+                .set_instruction_index(index as u32)
+                .map_err(InvokeError::Downstream)?;
+            let buckets_before: HashSet<BucketId> = bucket_id_mapping.keys().cloned().collect();
+            let proofs_before: HashSet<ProofId> = proof_id_mapping.keys().cloned().collect();
+            let worktop_before = Self::worktop_amounts(system_api);
+
+            let result = match inst {
+                Instruction::TakeFromWorktop { resource_address } => id_allocator
+                    .new_bucket_id()
+                    .map_err(|e| {
+                        InvokeError::Error(TransactionProcessorError::IdAllocationError(e))
+                    })
+                    .and_then(|new_id| {
+                        system_api
                             .invoke_method(
                                 Receiver::Ref(RENodeId::Worktop),
                                 FnIdentifier::Native(NativeFnIdentifier::Worktop(
-                                    WorktopFnIdentifier::AssertContains,
+                                    WorktopFnIdentifier::TakeAll,
                                 )),
-                                ScryptoValue::from_typed(&WorktopAssertContainsInput {
+                                ScryptoValue::from_typed(&WorktopTakeAllInput {
                                     resource_address: *resource_address,
                                 }),
                             )
-                            .map_err(InvokeError::Downstream),
-                        Instruction::AssertWorktopContainsByAmount {
-                            amount,
-                            resource_address,
-                        } => system_api
+                            .map_err(InvokeError::Downstream)
+                            .map(|rtn| {
+                                let bucket_id = Self::first_bucket(&rtn);
+                                bucket_id_mapping.insert(new_id, bucket_id);
+                                ScryptoValue::from_typed(&scrypto::resource::Bucket(new_id))
+                            })
+                    }),
+                Instruction::TakeFromWorktopByAmount {
+                    amount,
+                    resource_address,
+                } => id_allocator
+                    .new_bucket_id()
+                    .map_err(|e| {
+                        InvokeError::Error(TransactionProcessorError::IdAllocationError(e))
+                    })
+                    .and_then(|new_id| {
+                        system_api
                             .invoke_method(
                                 Receiver::Ref(RENodeId::Worktop),
                                 FnIdentifier::Native(NativeFnIdentifier::Worktop(
-                                    WorktopFnIdentifier::AssertContainsAmount,
+                                    WorktopFnIdentifier::TakeAmount,
                                 )),
-                                ScryptoValue::from_typed(&WorktopAssertContainsAmountInput {
+                                ScryptoValue::from_typed(&WorktopTakeAmountInput {
                                     amount: *amount,
                                     resource_address: *resource_address,
                                 }),
                             )
-                            .map_err(InvokeError::Downstream),
-                        Instruction::AssertWorktopContainsByIds {
-                            ids,
-                            resource_address,
-                        } => system_api
+                            .map_err(InvokeError::Downstream)
+                            .map(|rtn| {
+                                let bucket_id = Self::first_bucket(&rtn);
+                                bucket_id_mapping.insert(new_id, bucket_id);
+                                ScryptoValue::from_typed(&scrypto::resource::Bucket(new_id))
+                            })
+                    }),
+                Instruction::TakeFromWorktopByIds {
+                    ids,
+                    resource_address,
+                } => id_allocator
+                    .new_bucket_id()
+                    .map_err(|e| {
+                        InvokeError::Error(TransactionProcessorError::IdAllocationError(e))
+                    })
+                    .and_then(|new_id| {
+                        system_api
                             .invoke_method(
                                 Receiver::Ref(RENodeId::Worktop),
                                 FnIdentifier::Native(NativeFnIdentifier::Worktop(
-                                    WorktopFnIdentifier::AssertContainsNonFungibles,
+                                    WorktopFnIdentifier::TakeNonFungibles,
                                 )),
-                                ScryptoValue::from_typed(&WorktopAssertContainsNonFungiblesInput {
+                                ScryptoValue::from_typed(&WorktopTakeNonFungiblesInput {
                                     ids: ids.clone(),
                                     resource_address: *resource_address,
                                 }),
                             )
-                            .map_err(InvokeError::Downstream),
-
-                        Instruction::PopFromAuthZone {} => id_allocator
-                            .new_proof_id()
-                            .map_err(|e| {
-                                InvokeError::Error(TransactionProcessorError::IdAllocationError(e))
-                            })
-                            .and_then(|new_id| {
-                                system_api
-                                    .invoke_method(
-                                        Receiver::CurrentAuthZone,
-                                        FnIdentifier::Native(NativeFnIdentifier::AuthZone(
-                                            AuthZoneFnIdentifier::Pop,
-                                        )),
-                                        ScryptoValue::from_typed(&AuthZonePopInput {}),
-                                    )
-                                    .map_err(InvokeError::Downstream)
-                                    .map(|rtn| {
-                                        let proof_id = Self::first_proof(&rtn);
-                                        proof_id_mapping.insert(new_id, proof_id);
-                                        ScryptoValue::from_typed(&scrypto::resource::Proof(new_id))
-                                    })
-                            }),
-                        Instruction::ClearAuthZone => {
-                            proof_id_mapping.clear();
-                            system_api
-                                .invoke_method(
-                                    Receiver::CurrentAuthZone,
-                                    FnIdentifier::Native(NativeFnIdentifier::AuthZone(
-                                        AuthZoneFnIdentifier::Clear,
-                                    )),
-                                    ScryptoValue::from_typed(&AuthZoneClearInput {}),
-                                )
-                                .map_err(InvokeError::Downstream)
-                        }
-                        Instruction::PushToAuthZone { proof_id } => proof_id_mapping
-                            .remove(proof_id)
-                            .ok_or(InvokeError::Error(
-                                TransactionProcessorError::ProofNotFound(*proof_id),
-                            ))
-                            .and_then(|real_id| {
-                                system_api
-                                    .invoke_method(
-                                        Receiver::CurrentAuthZone,
-                                        FnIdentifier::Native(NativeFnIdentifier::AuthZone(
-                                            AuthZoneFnIdentifier::Push,
-                                        )),
-                                        ScryptoValue::from_typed(&AuthZonePushInput {
-                                            proof: scrypto::resource::Proof(real_id),
-                                        }),
-                                    )
-                                    .map_err(InvokeError::Downstream)
-                            }),
-                        Instruction::CreateProofFromAuthZone { resource_address } => id_allocator
-                            .new_proof_id()
-                            .map_err(|e| {
-                                InvokeError::Error(TransactionProcessorError::IdAllocationError(e))
+                            .map_err(InvokeError::Downstream)
+                            .map(|rtn| {
+                                let bucket_id = Self::first_bucket(&rtn);
+                                bucket_id_mapping.insert(new_id, bucket_id);
+                                ScryptoValue::from_typed(&scrypto::resource::Bucket(new_id))
                             })
-                            .and_then(|new_id| {
-                                system_api
-                                    .invoke_method(
-                                        Receiver::CurrentAuthZone,
-                                        FnIdentifier::Native(NativeFnIdentifier::AuthZone(
-                                            AuthZoneFnIdentifier::CreateProof,
-                                        )),
-                                        ScryptoValue::from_typed(&AuthZoneCreateProofInput {
-                                            resource_address: *resource_address,
-                                        }),
-                                    )
-                                    .map_err(InvokeError::Downstream)
-                                    .map(|rtn| {
-                                        let proof_id = Self::first_proof(&rtn);
-                                        proof_id_mapping.insert(new_id, proof_id);
-                                        ScryptoValue::from_typed(&scrypto::resource::Proof(new_id))
-                                    })
-                            }),
-                        Instruction::CreateProofFromAuthZoneByAmount {
-                            amount,
-                            resource_address,
-                        } => id_allocator
-                            .new_proof_id()
-                            .map_err(|e| {
-                                InvokeError::Error(TransactionProcessorError::IdAllocationError(e))
+                    }),
+                Instruction::ReturnToWorktop { bucket_id } => bucket_id_mapping
+                    .remove(bucket_id)
+                    .map(|real_id| {
+                        system_api
+                            .invoke_method(
+                                Receiver::Ref(RENodeId::Worktop),
+                                FnIdentifier::Native(NativeFnIdentifier::Worktop(
+                                    WorktopFnIdentifier::Put,
+                                )),
+                                ScryptoValue::from_typed(&WorktopPutInput {
+                                    bucket: scrypto::resource::Bucket(real_id),
+                                }),
+                            )
+                            .map_err(InvokeError::Downstream)
+                    })
+                    .unwrap_or(Err(InvokeError::Error(
+                        TransactionProcessorError::BucketNotFound(*bucket_id),
+                    ))),
+                Instruction::AssertWorktopContains { resource_address } => system_api
+                    .invoke_method(
+                        Receiver::Ref(RENodeId::Worktop),
+                        FnIdentifier::Native(NativeFnIdentifier::Worktop(
+                            WorktopFnIdentifier::AssertContains,
+                        )),
+                        ScryptoValue::from_typed(&WorktopAssertContainsInput {
+                            resource_address: *resource_address,
+                        }),
+                    )
+                    .map_err(InvokeError::Downstream),
+                Instruction::AssertWorktopContainsByAmount {
+                    amount,
+                    resource_address,
+                } => system_api
+                    .invoke_method(
+                        Receiver::Ref(RENodeId::Worktop),
+                        FnIdentifier::Native(NativeFnIdentifier::Worktop(
+                            WorktopFnIdentifier::AssertContainsAmount,
+                        )),
+                        ScryptoValue::from_typed(&WorktopAssertContainsAmountInput {
+                            amount: *amount,
+                            resource_address: *resource_address,
+                        }),
+                    )
+                    .map_err(InvokeError::Downstream),
+                Instruction::AssertWorktopContainsByIds {
+                    ids,
+                    resource_address,
+                } => system_api
+                    .invoke_method(
+                        Receiver::Ref(RENodeId::Worktop),
+                        FnIdentifier::Native(NativeFnIdentifier::Worktop(
+                            WorktopFnIdentifier::AssertContainsNonFungibles,
+                        )),
+                        ScryptoValue::from_typed(&WorktopAssertContainsNonFungiblesInput {
+                            ids: ids.clone(),
+                            resource_address: *resource_address,
+                        }),
+                    )
+                    .map_err(InvokeError::Downstream),
+                Instruction::ReturnWorktopContents => system_api
+                    .invoke_method(
+                        Receiver::Ref(RENodeId::Worktop),
+                        FnIdentifier::Native(NativeFnIdentifier::Worktop(
+                            WorktopFnIdentifier::Contents,
+                        )),
+                        ScryptoValue::from_typed(&WorktopContentsInput {}),
+                    )
+                    .map_err(InvokeError::Downstream),
+                Instruction::CreateProofFromAccountByAmount {
+                    account,
+                    amount,
+                    resource_address,
+                } => Self::invoke_scrypto_method(
+                    *account,
+                    "create_proof_by_amount",
+                    ScryptoValue::from_slice(&args!(*amount, *resource_address))
+                        .expect("Failed to encode create_proof_by_amount arguments"),
+                    system_api,
+                )
+                .and_then(|result| Self::move_returned_resources(result, system_api)),
+                Instruction::CreateProofFromAccountByIds {
+                    account,
+                    ids,
+                    resource_address,
+                } => Self::invoke_scrypto_method(
+                    *account,
+                    "create_proof_by_ids",
+                    ScryptoValue::from_slice(&args!(ids.clone(), *resource_address))
+                        .expect("Failed to encode create_proof_by_ids arguments"),
+                    system_api,
+                )
+                .and_then(|result| Self::move_returned_resources(result, system_api)),
+
+                Instruction::PopFromAuthZone {} => id_allocator
+                    .new_proof_id()
+                    .map_err(|e| {
+                        InvokeError::Error(TransactionProcessorError::IdAllocationError(e))
+                    })
+                    .and_then(|new_id| {
+                        system_api
+                            .invoke_method(
+                                Receiver::CurrentAuthZone,
+                                FnIdentifier::Native(NativeFnIdentifier::AuthZone(
+                                    AuthZoneFnIdentifier::Pop,
+                                )),
+                                ScryptoValue::from_typed(&AuthZonePopInput {}),
+                            )
+                            .map_err(InvokeError::Downstream)
+                            .map(|rtn| {
+                                let proof_id = Self::first_proof(&rtn);
+                                proof_id_mapping.insert(new_id, proof_id);
+                                ScryptoValue::from_typed(&scrypto::resource::Proof(new_id))
                             })
-                            .and_then(|new_id| {
-                                system_api
-                                    .invoke_method(
-                                        Receiver::CurrentAuthZone,
-                                        FnIdentifier::Native(NativeFnIdentifier::AuthZone(
-                                            AuthZoneFnIdentifier::CreateProofByAmount,
-                                        )),
-                                        ScryptoValue::from_typed(
-                                            &AuthZoneCreateProofByAmountInput {
-                                                amount: *amount,
-                                                resource_address: *resource_address,
-                                            },
-                                        ),
-                                    )
-                                    .map_err(InvokeError::Downstream)
-                                    .map(|rtn| {
-                                        let proof_id = Self::first_proof(&rtn);
-                                        proof_id_mapping.insert(new_id, proof_id);
-                                        ScryptoValue::from_typed(&scrypto::resource::Proof(new_id))
-                                    })
-                            }),
-                        Instruction::CreateProofFromAuthZoneByIds {
-                            ids,
-                            resource_address,
-                        } => id_allocator
-                            .new_proof_id()
-                            .map_err(|e| {
-                                InvokeError::Error(TransactionProcessorError::IdAllocationError(e))
+                    }),
+                Instruction::ClearAuthZone => {
+                    proof_id_mapping.clear();
+                    system_api
+                        .invoke_method(
+                            Receiver::CurrentAuthZone,
+                            FnIdentifier::Native(NativeFnIdentifier::AuthZone(
+                                AuthZoneFnIdentifier::Clear,
+                            )),
+                            ScryptoValue::from_typed(&AuthZoneClearInput {}),
+                        )
+                        .map_err(InvokeError::Downstream)
+                }
+                Instruction::PushToAuthZone { proof_id } => proof_id_mapping
+                    .remove(proof_id)
+                    .ok_or(InvokeError::Error(
+                        TransactionProcessorError::ProofNotFound(*proof_id),
+                    ))
+                    .and_then(|real_id| {
+                        system_api
+                            .invoke_method(
+                                Receiver::CurrentAuthZone,
+                                FnIdentifier::Native(NativeFnIdentifier::AuthZone(
+                                    AuthZoneFnIdentifier::Push,
+                                )),
+                                ScryptoValue::from_typed(&AuthZonePushInput {
+                                    proof: scrypto::resource::Proof(real_id),
+                                }),
+                            )
+                            .map_err(InvokeError::Downstream)
+                    }),
+                Instruction::CreateProofFromAuthZone { resource_address } => id_allocator
+                    .new_proof_id()
+                    .map_err(|e| {
+                        InvokeError::Error(TransactionProcessorError::IdAllocationError(e))
+                    })
+                    .and_then(|new_id| {
+                        system_api
+                            .invoke_method(
+                                Receiver::CurrentAuthZone,
+                                FnIdentifier::Native(NativeFnIdentifier::AuthZone(
+                                    AuthZoneFnIdentifier::CreateProof,
+                                )),
+                                ScryptoValue::from_typed(&AuthZoneCreateProofInput {
+                                    resource_address: *resource_address,
+                                }),
+                            )
+                            .map_err(InvokeError::Downstream)
+                            .map(|rtn| {
+                                let proof_id = Self::first_proof(&rtn);
+                                proof_id_mapping.insert(new_id, proof_id);
+                                ScryptoValue::from_typed(&scrypto::resource::Proof(new_id))
                             })
-                            .and_then(|new_id| {
-                                system_api
-                                    .invoke_method(
-                                        Receiver::CurrentAuthZone,
-                                        FnIdentifier::Native(NativeFnIdentifier::AuthZone(
-                                            AuthZoneFnIdentifier::CreateProofByIds,
-                                        )),
-                                        ScryptoValue::from_typed(&AuthZoneCreateProofByIdsInput {
-                                            ids: ids.clone(),
-                                            resource_address: *resource_address,
-                                        }),
-                                    )
-                                    .map_err(InvokeError::Downstream)
-                                    .map(|rtn| {
-                                        let proof_id = Self::first_proof(&rtn);
-                                        proof_id_mapping.insert(new_id, proof_id);
-                                        ScryptoValue::from_typed(&scrypto::resource::Proof(new_id))
-                                    })
-                            }),
-                        Instruction::CreateProofFromBucket { bucket_id } => id_allocator
-                            .new_proof_id()
-                            .map_err(|e| {
-                                InvokeError::Error(TransactionProcessorError::IdAllocationError(e))
+                    }),
+                Instruction::CreateProofFromAuthZoneByAmount {
+                    amount,
+                    resource_address,
+                } => id_allocator
+                    .new_proof_id()
+                    .map_err(|e| {
+                        InvokeError::Error(TransactionProcessorError::IdAllocationError(e))
+                    })
+                    .and_then(|new_id| {
+                        system_api
+                            .invoke_method(
+                                Receiver::CurrentAuthZone,
+                                FnIdentifier::Native(NativeFnIdentifier::AuthZone(
+                                    AuthZoneFnIdentifier::CreateProofByAmount,
+                                )),
+                                ScryptoValue::from_typed(&AuthZoneCreateProofByAmountInput {
+                                    amount: *amount,
+                                    resource_address: *resource_address,
+                                }),
+                            )
+                            .map_err(InvokeError::Downstream)
+                            .map(|rtn| {
+                                let proof_id = Self::first_proof(&rtn);
+                                proof_id_mapping.insert(new_id, proof_id);
+                                ScryptoValue::from_typed(&scrypto::resource::Proof(new_id))
                             })
-                            .and_then(|new_id| {
-                                bucket_id_mapping
-                                    .get(bucket_id)
-                                    .cloned()
-                                    .map(|real_bucket_id| (new_id, real_bucket_id))
-                                    .ok_or(InvokeError::Error(
-                                        TransactionProcessorError::BucketNotFound(new_id),
-                                    ))
+                    }),
+                Instruction::CreateProofFromAuthZoneByIds {
+                    ids,
+                    resource_address,
+                } => id_allocator
+                    .new_proof_id()
+                    .map_err(|e| {
+                        InvokeError::Error(TransactionProcessorError::IdAllocationError(e))
+                    })
+                    .and_then(|new_id| {
+                        system_api
+                            .invoke_method(
+                                Receiver::CurrentAuthZone,
+                                FnIdentifier::Native(NativeFnIdentifier::AuthZone(
+                                    AuthZoneFnIdentifier::CreateProofByIds,
+                                )),
+                                ScryptoValue::from_typed(&AuthZoneCreateProofByIdsInput {
+                                    ids: ids.clone(),
+                                    resource_address: *resource_address,
+                                }),
+                            )
+                            .map_err(InvokeError::Downstream)
+                            .map(|rtn| {
+                                let proof_id = Self::first_proof(&rtn);
+                                proof_id_mapping.insert(new_id, proof_id);
+                                ScryptoValue::from_typed(&scrypto::resource::Proof(new_id))
                             })
-                            .and_then(|(new_id, real_bucket_id)| {
-                                system_api
-                                    .invoke_method(
-                                        Receiver::Ref(RENodeId::Bucket(real_bucket_id)),
-                                        FnIdentifier::Native(NativeFnIdentifier::Bucket(
-                                            BucketFnIdentifier::CreateProof,
-                                        )),
-                                        ScryptoValue::from_typed(&BucketCreateProofInput {}),
-                                    )
-                                    .map_err(InvokeError::Downstream)
-                                    .map(|rtn| {
-                                        let proof_id = Self::first_proof(&rtn);
-                                        proof_id_mapping.insert(new_id, proof_id);
-                                        ScryptoValue::from_typed(&scrypto::resource::Proof(new_id))
-                                    })
-                            }),
-                        Instruction::CloneProof { proof_id } => id_allocator
-                            .new_proof_id()
-                            .map_err(|e| {
-                                InvokeError::Error(TransactionProcessorError::IdAllocationError(e))
+                    }),
+                Instruction::CreateProofFromBucket { bucket_id } => id_allocator
+                    .new_proof_id()
+                    .map_err(|e| {
+                        InvokeError::Error(TransactionProcessorError::IdAllocationError(e))
+                    })
+                    .and_then(|new_id| {
+                        bucket_id_mapping
+                            .get(bucket_id)
+                            .cloned()
+                            .map(|real_bucket_id| (new_id, real_bucket_id))
+                            .ok_or(InvokeError::Error(
+                                TransactionProcessorError::BucketNotFound(new_id),
+                            ))
+                    })
+                    .and_then(|(new_id, real_bucket_id)| {
+                        system_api
+                            .invoke_method(
+                                Receiver::Ref(RENodeId::Bucket(real_bucket_id)),
+                                FnIdentifier::Native(NativeFnIdentifier::Bucket(
+                                    BucketFnIdentifier::CreateProof,
+                                )),
+                                ScryptoValue::from_typed(&BucketCreateProofInput {}),
+                            )
+                            .map_err(InvokeError::Downstream)
+                            .map(|rtn| {
+                                let proof_id = Self::first_proof(&rtn);
+                                proof_id_mapping.insert(new_id, proof_id);
+                                ScryptoValue::from_typed(&scrypto::resource::Proof(new_id))
                             })
-                            .and_then(|new_id| {
-                                proof_id_mapping
-                                    .get(proof_id)
-                                    .cloned()
-                                    .map(|real_id| {
-                                        system_api
-                                            .invoke_method(
-                                                Receiver::Ref(RENodeId::Proof(real_id)),
-                                                FnIdentifier::Native(NativeFnIdentifier::Proof(
-                                                    ProofFnIdentifier::Clone,
-                                                )),
-                                                ScryptoValue::from_typed(&ProofCloneInput {}),
-                                            )
-                                            .map_err(InvokeError::Downstream)
-                                            .map(|v| {
-                                                let cloned_proof_id = Self::first_proof(&v);
-                                                proof_id_mapping.insert(new_id, cloned_proof_id);
-                                                ScryptoValue::from_typed(&scrypto::resource::Proof(
-                                                    new_id,
-                                                ))
-                                            })
-                                    })
-                                    .unwrap_or(Err(InvokeError::Error(
-                                        TransactionProcessorError::ProofNotFound(*proof_id),
-                                    )))
-                            }),
-                        Instruction::DropProof { proof_id } => proof_id_mapping
-                            .remove(proof_id)
+                    }),
+                Instruction::CloneProof { proof_id } => id_allocator
+                    .new_proof_id()
+                    .map_err(|e| {
+                        InvokeError::Error(TransactionProcessorError::IdAllocationError(e))
+                    })
+                    .and_then(|new_id| {
+                        proof_id_mapping
+                            .get(proof_id)
+                            .cloned()
                             .map(|real_id| {
                                 system_api
                                     .invoke_method(
-                                        Receiver::Consumed(RENodeId::Proof(real_id)),
+                                        Receiver::Ref(RENodeId::Proof(real_id)),
                                         FnIdentifier::Native(NativeFnIdentifier::Proof(
-                                            ProofFnIdentifier::Drop,
+                                            ProofFnIdentifier::Clone,
                                         )),
-                                        ScryptoValue::from_typed(&ConsumingProofDropInput {}),
+                                        ScryptoValue::from_typed(&ProofCloneInput {}),
                                     )
                                     .map_err(InvokeError::Downstream)
+                                    .map(|v| {
+                                        let cloned_proof_id = Self::first_proof(&v);
+                                        proof_id_mapping.insert(new_id, cloned_proof_id);
+                                        ScryptoValue::from_typed(&scrypto::resource::Proof(new_id))
+                                    })
                             })
                             .unwrap_or(Err(InvokeError::Error(
                                 TransactionProcessorError::ProofNotFound(*proof_id),
-                            ))),
-                        Instruction::DropAllProofs => {
-                            for (_, real_id) in proof_id_mapping.drain() {
-                                system_api
-                                    .invoke_method(
-                                        Receiver::Consumed(RENodeId::Proof(real_id)),
-                                        FnIdentifier::Native(NativeFnIdentifier::Proof(
-                                            ProofFnIdentifier::Drop,
-                                        )),
-                                        ScryptoValue::from_typed(&ConsumingProofDropInput {}),
-                                    )
-                                    .map_err(InvokeError::Downstream)?;
-                            }
+                            )))
+                    }),
+                Instruction::DropProof { proof_id } => proof_id_mapping
+                    .remove(proof_id)
+                    .map(|real_id| {
+                        system_api
+                            .invoke_method(
+                                Receiver::Consumed(RENodeId::Proof(real_id)),
+                                FnIdentifier::Native(NativeFnIdentifier::Proof(
+                                    ProofFnIdentifier::Drop,
+                                )),
+                                ScryptoValue::from_typed(&ConsumingProofDropInput {}),
+                            )
+                            .map_err(InvokeError::Downstream)
+                    })
+                    .unwrap_or(Err(InvokeError::Error(
+                        TransactionProcessorError::ProofNotFound(*proof_id),
+                    ))),
+                Instruction::DropAllProofs => {
+                    for (_, real_id) in proof_id_mapping.drain() {
+                        system_api
+                            .invoke_method(
+                                Receiver::Consumed(RENodeId::Proof(real_id)),
+                                FnIdentifier::Native(NativeFnIdentifier::Proof(
+                                    ProofFnIdentifier::Drop,
+                                )),
+                                ScryptoValue::from_typed(&ConsumingProofDropInput {}),
+                            )
+                            .map_err(InvokeError::Downstream)?;
+                    }
+                    system_api
+                        .invoke_method(
+                            Receiver::CurrentAuthZone,
+                            FnIdentifier::Native(NativeFnIdentifier::AuthZone(
+                                AuthZoneFnIdentifier::Clear,
+                            )),
+                            ScryptoValue::from_typed(&AuthZoneClearInput {}),
+                        )
+                        .map_err(InvokeError::Downstream)
+                }
+                Instruction::CallFunction {
+                    fn_identifier,
+                    args,
+                } => {
+                    Self::replace_ids(
+                        &mut proof_id_mapping,
+                        &mut bucket_id_mapping,
+                        ScryptoValue::from_slice(args).expect("Invalid CALL_FUNCTION arguments"),
+                    )
+                    .and_then(|call_data| Self::process_expressions(call_data, system_api))
+                    .and_then(|call_data| {
+                        system_api
+                            .invoke_function(fn_identifier.clone(), call_data)
+                            .map_err(InvokeError::Downstream)
+                    })
+                    .and_then(|result| {
+                        // Auto move into auth_zone
+                        for (proof_id, _) in &result.proof_ids {
                             system_api
                                 .invoke_method(
                                     Receiver::CurrentAuthZone,
                                     FnIdentifier::Native(NativeFnIdentifier::AuthZone(
-                                        AuthZoneFnIdentifier::Clear,
+                                        AuthZoneFnIdentifier::Push,
                                     )),
-                                    ScryptoValue::from_typed(&AuthZoneClearInput {}),
+                                    ScryptoValue::from_typed(&AuthZonePushInput {
+                                        proof: scrypto::resource::Proof(*proof_id),
+                                    }),
                                 )
-                                .map_err(InvokeError::Downstream)
+                                .map_err(InvokeError::Downstream)?;
                         }
-                        Instruction::CallFunction {
-                            fn_identifier,
-                            args,
-                        } => {
-                            Self::replace_ids(
-                                &mut proof_id_mapping,
-                                &mut bucket_id_mapping,
-                                ScryptoValue::from_slice(args)
-                                    .expect("Invalid CALL_FUNCTION arguments"),
-                            )
-                            .and_then(|call_data| Self::process_expressions(call_data, system_api))
-                            .and_then(|call_data| {
-                                system_api
-                                    .invoke_function(fn_identifier.clone(), call_data)
-                                    .map_err(InvokeError::Downstream)
-                            })
-                            .and_then(|result| {
-                                // Auto move into auth_zone
-                                for (proof_id, _) in &result.proof_ids {
-                                    system_api
-                                        .invoke_method(
-                                            Receiver::CurrentAuthZone,
-                                            FnIdentifier::Native(NativeFnIdentifier::AuthZone(
-                                                AuthZoneFnIdentifier::Push,
-                                            )),
-                                            ScryptoValue::from_typed(&AuthZonePushInput {
-                                                proof: scrypto::resource::Proof(*proof_id),
-                                            }),
-                                        )
-                                        .map_err(InvokeError::Downstream)?;
-                                }
-                                // Auto move into worktop
-                                for (bucket_id, _) in &result.bucket_ids {
-                                    system_api
-                                        .invoke_method(
-                                            Receiver::Ref(RENodeId::Worktop),
-                                            FnIdentifier::Native(NativeFnIdentifier::Worktop(
-                                                WorktopFnIdentifier::Put,
-                                            )),
-                                            ScryptoValue::from_typed(&WorktopPutInput {
-                                                bucket: scrypto::resource::Bucket(*bucket_id),
-                                            }),
-                                        )
-                                        .map_err(InvokeError::Downstream)?;
-                                }
-                                Ok(result)
-                            })
+                        // Auto move into worktop
+                        for (bucket_id, _) in &result.bucket_ids {
+                            system_api
+                                .invoke_method(
+                                    Receiver::Ref(RENodeId::Worktop),
+                                    FnIdentifier::Native(NativeFnIdentifier::Worktop(
+                                        WorktopFnIdentifier::Put,
+                                    )),
+                                    ScryptoValue::from_typed(&WorktopPutInput {
+                                        bucket: scrypto::resource::Bucket(*bucket_id),
+                                    }),
+                                )
+                                .map_err(InvokeError::Downstream)?;
                         }
-                        Instruction::CallMethod {
-                            method_identifier,
-                            args,
-                        } => {
-                            Self::replace_ids(
+                        Ok(result)
+                    })
+                }
+                Instruction::CallMethod {
+                    method_identifier,
+                    args,
+                } => {
+                    Self::replace_ids(
+                        &mut proof_id_mapping,
+                        &mut bucket_id_mapping,
+                        ScryptoValue::from_slice(args).expect("Invalid CALL_METHOD arguments"),
+                    )
+                    .and_then(|call_data| Self::process_expressions(call_data, system_api))
+                    .and_then(|call_data| {
+                        // TODO: Move this into preprocessor step
+                        match method_identifier {
+                            MethodIdentifier::Scrypto {
+                                component_address,
+                                ident,
+                            } => Self::invoke_scrypto_method(
+                                *component_address,
+                                ident,
+                                call_data,
+                                system_api,
+                            ),
+                            MethodIdentifier::Native {
+                                receiver,
+                                native_fn_identifier,
+                            } => Self::replace_receiver(
+                                receiver.clone(),
                                 &mut proof_id_mapping,
                                 &mut bucket_id_mapping,
-                                ScryptoValue::from_slice(args)
-                                    .expect("Invalid CALL_METHOD arguments"),
                             )
-                            .and_then(|call_data| Self::process_expressions(call_data, system_api))
-                            .and_then(|call_data| {
-                                // TODO: Move this into preprocessor step
-                                match method_identifier {
-                                    MethodIdentifier::Scrypto {
-                                        component_address,
-                                        ident,
-                                    } => system_api
-                                        .substate_read(SubstateId::ComponentInfo(
-                                            *component_address,
-                                        ))
-                                        .map_err(InvokeError::Downstream)
-                                        .and_then(|s| {
-                                            let (package_address, blueprint_name): (
-                                                PackageAddress,
-                                                String,
-                                            ) = scrypto_decode(&s.raw)
-                                                .expect("Failed to decode ComponentInfo substate");
-
-                                            system_api
-                                                .invoke_method(
-                                                    Receiver::Ref(RENodeId::Component(
-                                                        *component_address,
-                                                    )),
-                                                    FnIdentifier::Scrypto {
-                                                        ident: ident.to_string(),
-                                                        package_address,
-                                                        blueprint_name,
-                                                    },
-                                                    call_data,
-                                                )
-                                                .map_err(InvokeError::Downstream)
-                                        }),
-                                    MethodIdentifier::Native {
+                            .and_then(|receiver| {
+                                system_api
+                                    .invoke_method(
                                         receiver,
-                                        native_fn_identifier,
-                                    } => Self::replace_receiver(
-                                        receiver.clone(),
-                                        &mut proof_id_mapping,
-                                        &mut bucket_id_mapping,
+                                        FnIdentifier::Native(native_fn_identifier.clone()),
+                                        call_data,
                                     )
-                                    .and_then(|receiver| {
-                                        system_api
-                                            .invoke_method(
-                                                receiver,
-                                                FnIdentifier::Native(native_fn_identifier.clone()),
-                                                call_data,
-                                            )
-                                            .map_err(InvokeError::Downstream)
-                                    }),
-                                }
-                            })
-                            .and_then(|result| {
-                                // Auto move into auth_zone
-                                for (proof_id, _) in &result.proof_ids {
-                                    system_api
-                                        .invoke_method(
-                                            Receiver::CurrentAuthZone,
-                                            FnIdentifier::Native(NativeFnIdentifier::AuthZone(
-                                                AuthZoneFnIdentifier::Push,
-                                            )),
-                                            ScryptoValue::from_typed(&AuthZonePushInput {
-                                                proof: scrypto::resource::Proof(*proof_id),
-                                            }),
-                                        )
-                                        .map_err(InvokeError::Downstream)?;
-                                }
-                                // Auto move into worktop
-                                for (bucket_id, _) in &result.bucket_ids {
-                                    system_api
-                                        .invoke_method(
-                                            Receiver::Ref(RENodeId::Worktop),
-                                            FnIdentifier::Native(NativeFnIdentifier::Worktop(
-                                                WorktopFnIdentifier::Put,
-                                            )),
-                                            ScryptoValue::from_typed(&WorktopPutInput {
-                                                bucket: scrypto::resource::Bucket(*bucket_id),
-                                            }),
-                                        )
-                                        .map_err(InvokeError::downstream)?;
-                                }
-                                Ok(result)
-                            })
+                                    .map_err(InvokeError::Downstream)
+                            }),
                         }
-                        Instruction::PublishPackage { code, abi } => system_api
-                            .invoke_function(
-                                FnIdentifier::Native(NativeFnIdentifier::Package(
-                                    PackageFnIdentifier::Publish,
-                                )),
-                                ScryptoValue::from_typed(&PackagePublishInput {
-                                    code: code.clone(),
-                                    abi: abi.clone(),
-                                }),
-                            )
-                            .map_err(InvokeError::Downstream),
-                    }?;
-                    outputs.push(result);
+                    })
+                    .and_then(|result| Self::move_returned_resources(result, system_api))
                 }
+                Instruction::ExecuteSubintent { index } => subintents
+                    .get(*index as usize)
+                    .ok_or(InvokeError::Error(
+                        TransactionProcessorError::SubintentNotFound(*index),
+                    ))
+                    .and_then(|subintent| Self::run_subintent(subintent, system_api)),
+                Instruction::PublishPackage { code, abi } => system_api
+                    .invoke_function(
+                        FnIdentifier::Native(NativeFnIdentifier::Package(
+                            PackageFnIdentifier::Publish,
+                        )),
+                        ScryptoValue::from_typed(&PackagePublishInput {
+                            code: code.clone(),
+                            abi: abi.clone(),
+                        }),
+                    )
+                    .map_err(InvokeError::Downstream),
+            }?;
+
+            let mut new_bucket_ids: Vec<BucketId> = bucket_id_mapping
+                .keys()
+                .filter(|id| !buckets_before.contains(id))
+                .cloned()
+                .collect();
+            new_bucket_ids.sort();
+            let mut new_proof_ids: Vec<ProofId> = proof_id_mapping
+                .keys()
+                .filter(|id| !proofs_before.contains(id))
+                .cloned()
+                .collect();
+            new_proof_ids.sort();
+            let worktop_after = Self::worktop_amounts(system_api);
+
+            outputs.push(InstructionOutput {
+                output: result.raw,
+                new_bucket_ids,
+                new_proof_ids,
+                worktop_changes: Self::worktop_changes(worktop_before, worktop_after),
+            });
+        }
+
+        Ok(outputs)
+    }
+
+    /// Runs the instructions of a subintent, with the badges of its signers in the auth zone.
+    ///
+    /// The proofs in the auth zones of the call stack, which hold the transaction signer
+    /// badges, are stashed by the kernel meanwhile, so that the subintent and the transaction
+    /// manifest can't use each other's proofs. The worktop is shared.
+    fn run_subintent<'s, Y, W, I, R>(
+        subintent: &ExecutableSubintent,
+        system_api: &mut Y,
+    ) -> Result<ScryptoValue, InvokeError<TransactionProcessorError>>
+    where
+        Y: SystemApi<'s, W, I, R>,
+        W: WasmEngine<I>,
+        I: WasmInstance,
+        R: FeeReserve,
+    {
+        system_api
+            .auth_zone_stash()
+            .map_err(InvokeError::Downstream)?;
+
+        let badges = VirtualProofs::signer_badges(subintent.initial_proofs.clone());
+        let mut badge_buckets = Vec::new();
+        for container in VirtualProofs::create_containers(badges) {
+            let node_id = system_api
+                .node_create(HeapRENode::Bucket(Bucket::new(container)))
+                .map_err(InvokeError::Downstream)?;
+            let rtn = system_api
+                .invoke_method(
+                    Receiver::Ref(node_id),
+                    FnIdentifier::Native(NativeFnIdentifier::Bucket(
+                        BucketFnIdentifier::CreateProof,
+                    )),
+                    ScryptoValue::from_typed(&BucketCreateProofInput {}),
+                )
+                .map_err(InvokeError::Downstream)?;
+            system_api
+                .invoke_method(
+                    Receiver::CurrentAuthZone,
+                    FnIdentifier::Native(NativeFnIdentifier::AuthZone(AuthZoneFnIdentifier::Push)),
+                    ScryptoValue::from_typed(&AuthZonePushInput {
+                        proof: scrypto::resource::Proof(Self::first_proof(&rtn)),
+                    }),
+                )
+                .map_err(InvokeError::Downstream)?;
+            badge_buckets.push(node_id);
+        }
+
+        let outputs = Self::run_instructions(&subintent.instructions, &[], system_api)?;
+
+        // Drop the signer proofs, along with the ones the subintent left in the auth zone
+        system_api
+            .auth_zone_unstash()
+            .map_err(InvokeError::Downstream)?;
+        for node_id in badge_buckets {
+            system_api
+                .node_drop(&node_id)
+                .map_err(InvokeError::Downstream)?;
+        }
+
+        Ok(ScryptoValue::from_typed(
+            &outputs
+                .into_iter()
+                .map(|o| o.output)
+                .collect::<Vec<Vec<u8>>>(),
+        ))
+    }
+
+    pub fn static_main<'s, Y, W, I, R>(
+        transaction_processor_fn: TransactionProcessorFnIdentifier,
+        call_data: ScryptoValue,
+        system_api: &mut Y,
+    ) -> Result<ScryptoValue, InvokeError<TransactionProcessorError>>
+    where
+        Y: SystemApi<'s, W, I, R>,
+        W: WasmEngine<I>,
+        I: WasmInstance,
+        R: FeeReserve,
+    {
+        match transaction_processor_fn {
+            TransactionProcessorFnIdentifier::Run => {
+                let input: TransactionProcessorRunInput =
+                    scrypto_decode(&call_data.raw).map_err(|e| {
+                        InvokeError::Error(TransactionProcessorError::InvalidRequestData(e))
+                    })?;
+
+                let _worktop_id = system_api
+                    .node_create(HeapRENode::Worktop(Worktop::new()))
+                    .map_err(InvokeError::Downstream)?;
+
+                let outputs =
+                    Self::run_instructions(&input.instructions, &input.subintents, system_api)?;
 
-                Ok(ScryptoValue::from_typed(
-                    &outputs
-                        .into_iter()
-                        .map(|sv| sv.raw)
-                        .collect::<Vec<Vec<u8>>>(),
-                ))
+                Ok(ScryptoValue::from_typed(&outputs))
             }
         }
     }
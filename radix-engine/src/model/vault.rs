@@ -168,7 +168,7 @@ impl Vault {
         let mut ref_mut = system_api
             .substate_borrow_mut(&substate_id)
             .map_err(InvokeError::Downstream)?;
-        let vault = ref_mut.vault();
+        let vault = ref_mut.vault().map_err(InvokeError::Downstream)?;
 
         let rtn = match vault_fn {
             VaultFnIdentifier::Put => {
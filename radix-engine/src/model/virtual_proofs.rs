@@ -0,0 +1,58 @@
+use crate::model::ResourceContainer;
+use crate::types::*;
+
+/// The resources whose badges are never held in a vault, but minted on the fly for the
+/// signers of a transaction.
+pub const VIRTUAL_BADGE_RESOURCES: [ResourceAddress; 3] =
+    [ECDSA_TOKEN, ED25519_TOKEN, PUBLIC_KEY_HASH_TOKEN];
+
+/// Mints the virtual badges backing the signature proofs of a transaction.
+pub struct VirtualProofs;
+
+impl VirtualProofs {
+    pub fn is_virtual_badge_resource(resource_address: ResourceAddress) -> bool {
+        VIRTUAL_BADGE_RESOURCES.contains(&resource_address)
+    }
+
+    /// Returns every badge a set of signers is entitled to, which is the badge of each public key
+    /// along with the badge of its hash, so that access rules can be written against a signer
+    /// regardless of its signature scheme.
+    pub fn signer_badges(signer_badges: Vec<NonFungibleAddress>) -> Vec<NonFungibleAddress> {
+        let mut badges = Vec::new();
+        for badge in signer_badges {
+            if let Some(key_hash_badge) = Self::public_key_hash_badge(&badge) {
+                badges.push(key_hash_badge);
+            }
+            badges.push(badge);
+        }
+        badges
+    }
+
+    /// Groups badges by resource into the containers the signature proofs are created from.
+    pub fn create_containers(badges: Vec<NonFungibleAddress>) -> Vec<ResourceContainer> {
+        let mut ids_by_resource = BTreeMap::<ResourceAddress, BTreeSet<NonFungibleId>>::new();
+        for badge in badges {
+            ids_by_resource
+                .entry(badge.resource_address())
+                .or_insert(BTreeSet::new())
+                .insert(badge.non_fungible_id());
+        }
+        ids_by_resource
+            .into_iter()
+            .map(|(resource_address, non_fungible_ids)| {
+                ResourceContainer::new_non_fungible(resource_address, non_fungible_ids)
+            })
+            .collect()
+    }
+
+    fn public_key_hash_badge(badge: &NonFungibleAddress) -> Option<NonFungibleAddress> {
+        let resource_address = badge.resource_address();
+        if resource_address != ECDSA_TOKEN && resource_address != ED25519_TOKEN {
+            return None;
+        }
+        let public_key_bytes: Vec<u8> = scrypto_decode(&badge.non_fungible_id().to_vec()).ok()?;
+        Some(NonFungibleAddress::from_public_key_bytes_hash(
+            &public_key_bytes,
+        ))
+    }
+}
@@ -46,6 +46,18 @@ pub struct WorktopAssertContainsNonFungiblesInput {
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct WorktopDrainInput {}
 
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct WorktopContentsInput {}
+
+/// A resource on the worktop, as returned by `RETURN_WORKTOP_CONTENTS`.
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode)]
+pub struct WorktopResource {
+    pub resource_address: ResourceAddress,
+    pub amount: Decimal,
+    /// The non-fungible ids, if the resource is non-fungible
+    pub ids: Option<BTreeSet<NonFungibleId>>,
+}
+
 /// Worktop collects resources from function or method returns.
 #[derive(Debug)]
 pub struct Worktop {
@@ -153,6 +165,36 @@ impl Worktop {
         }
     }
 
+    /// Returns the resources on the worktop, ordered by resource address.
+    pub fn contents(&self) -> Result<Vec<WorktopResource>, ResourceContainerError> {
+        let mut contents = Vec::new();
+        for (resource_address, container) in &self.containers {
+            let container = container.borrow();
+            if container.is_empty() {
+                continue;
+            }
+            let ids = match container.resource_type() {
+                ResourceType::Fungible { .. } => None,
+                ResourceType::NonFungible => Some(container.total_ids()?),
+            };
+            contents.push(WorktopResource {
+                resource_address: *resource_address,
+                amount: container.total_amount(),
+                ids,
+            });
+        }
+        contents.sort_by_key(|resource| resource.resource_address);
+        Ok(contents)
+    }
+
+    /// Returns the serialized size of all containers on the worktop.
+    pub fn size(&self) -> usize {
+        self.containers
+            .values()
+            .map(|container| scrypto_encode(&*container.borrow()).len())
+            .sum()
+    }
+
     pub fn is_locked(&self) -> bool {
         for resource_address in self.resource_addresses() {
             if let Some(container) = self.borrow_container(resource_address) {
@@ -218,7 +260,7 @@ impl Worktop {
         let mut node_ref = system_api
             .substate_borrow_mut(&SubstateId::Worktop)
             .map_err(InvokeError::downstream)?;
-        let worktop = node_ref.worktop();
+        let worktop = node_ref.worktop().map_err(InvokeError::Downstream)?;
 
         let rtn = match worktop_fn {
             WorktopFnIdentifier::Put => {
@@ -246,7 +288,9 @@ impl Worktop {
                         let node_ref = system_api
                             .borrow_node(&RENodeId::ResourceManager(input.resource_address))
                             .map_err(|e| InvokeError::Downstream(e))?;
-                        let resource_manager = node_ref.resource_manager();
+                        let resource_manager = node_ref
+                            .resource_manager()
+                            .map_err(InvokeError::Downstream)?;
                         resource_manager.resource_type()
                     };
 
@@ -273,7 +317,9 @@ impl Worktop {
                         let node_ref = system_api
                             .borrow_node(&RENodeId::ResourceManager(input.resource_address))
                             .map_err(|e| InvokeError::Downstream(e))?;
-                        let resource_manager = node_ref.resource_manager();
+                        let resource_manager = node_ref
+                            .resource_manager()
+                            .map_err(InvokeError::Downstream)?;
                         resource_manager.resource_type()
                     };
 
@@ -301,7 +347,9 @@ impl Worktop {
                         let node_ref = system_api
                             .borrow_node(&RENodeId::ResourceManager(input.resource_address))
                             .map_err(|e| InvokeError::Downstream(e))?;
-                        let resource_manager = node_ref.resource_manager();
+                        let resource_manager = node_ref
+                            .resource_manager()
+                            .map_err(InvokeError::Downstream)?;
                         resource_manager.resource_type()
                     };
 
@@ -366,6 +414,14 @@ impl Worktop {
                 }
                 Ok(ScryptoValue::from_typed(&buckets))
             }
+            WorktopFnIdentifier::Contents => {
+                let _: WorktopContentsInput = scrypto_decode(&args.raw)
+                    .map_err(|e| InvokeError::Error(WorktopError::InvalidRequestData(e)))?;
+                let contents = worktop
+                    .contents()
+                    .map_err(|e| InvokeError::Error(WorktopError::ResourceContainerError(e)))?;
+                Ok(ScryptoValue::from_typed(&contents))
+            }
         }?;
 
         system_api
@@ -2,6 +2,7 @@ use crate::ledger::*;
 use crate::state_manager::VirtualSubstateId;
 use crate::types::*;
 
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode)]
 pub struct CommitReceipt {
     pub virtual_inputs: Vec<VirtualSubstateId>,
     pub inputs: Vec<OutputId>,
@@ -25,6 +25,19 @@ impl StateDiff {
 
     /// Applies the state changes to some substate store.
     pub fn commit<S: WriteableSubstateStore>(&self, store: &mut S) -> CommitReceipt {
+        for (substate_id, output_value) in &self.up_substates {
+            store.put_substate(substate_id.clone(), output_value.clone());
+        }
+
+        for substate_id in &self.new_roots {
+            store.set_root(substate_id.clone());
+        }
+
+        self.to_commit_receipt()
+    }
+
+    /// Returns the substates consumed and produced by the state changes.
+    pub fn to_commit_receipt(&self) -> CommitReceipt {
         let mut receipt = CommitReceipt::new();
 
         for virtual_substate_id in &self.down_virtual_substates {
@@ -41,11 +54,6 @@ impl StateDiff {
                 version: output_value.version,
             };
             receipt.up(output_id);
-            store.put_substate(substate_id.clone(), output_value.clone());
-        }
-
-        for substate_id in &self.new_roots {
-            store.set_root(substate_id.clone());
         }
 
         receipt
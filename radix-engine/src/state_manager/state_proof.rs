@@ -0,0 +1,80 @@
+use crate::engine::Substate;
+use crate::types::*;
+
+/// The hash of an empty subtree of the state tree.
+pub const EMPTY_SUBTREE_HASH: Hash = Hash([0u8; Hash::LENGTH]);
+
+/// The depth of the state tree when every key bit is used, which bounds the length of a proof.
+pub const MAX_TREE_DEPTH: usize = Hash::LENGTH * 8;
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+/// A proof that a substate is part of the state tree with a given root.
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode)]
+pub struct StateProof {
+    pub key: Hash,
+    pub value_hash: Hash,
+    /// The hashes of the siblings along the path to the leaf, from the root down
+    pub siblings: Vec<Hash>,
+}
+
+impl StateProof {
+    /// Checks this proof against the given state root.
+    pub fn verify(&self, state_root: Hash) -> bool {
+        if self.siblings.len() > MAX_TREE_DEPTH {
+            return false;
+        }
+
+        let mut hash = leaf_hash(&self.key, &self.value_hash);
+        for (depth, sibling) in self.siblings.iter().enumerate().rev() {
+            hash = if key_bit(&self.key, depth) {
+                node_hash(sibling, &hash)
+            } else {
+                node_hash(&hash, sibling)
+            };
+        }
+        hash == state_root
+    }
+}
+
+/// Verifies that the given substate is the value of `substate_id` in the state with the given root.
+pub fn verify_substate_proof(
+    state_root: Hash,
+    substate_id: &SubstateId,
+    substate: &Substate,
+    proof: &StateProof,
+) -> bool {
+    proof.key == leaf_key(substate_id)
+        && proof.value_hash == value_hash(substate)
+        && proof.verify(state_root)
+}
+
+/// Returns the key of the leaf holding the given substate.
+pub fn leaf_key(substate_id: &SubstateId) -> Hash {
+    hash(scrypto_encode(substate_id))
+}
+
+/// Returns the hash a leaf holds for the given substate.
+pub fn value_hash(substate: &Substate) -> Hash {
+    hash(scrypto_encode(substate))
+}
+
+pub fn leaf_hash(key: &Hash, value_hash: &Hash) -> Hash {
+    let mut bytes = vec![LEAF_PREFIX];
+    bytes.extend_from_slice(&key.0);
+    bytes.extend_from_slice(&value_hash.0);
+    hash(bytes)
+}
+
+pub fn node_hash(left: &Hash, right: &Hash) -> Hash {
+    let mut bytes = vec![NODE_PREFIX];
+    bytes.extend_from_slice(&left.0);
+    bytes.extend_from_slice(&right.0);
+    hash(bytes)
+}
+
+/// Returns the bit of the key at the given depth, most significant bit first.
+pub fn key_bit(key: &Hash, depth: usize) -> bool {
+    (key.0[depth / 8] >> (7 - depth % 8)) & 1 == 1
+}
@@ -0,0 +1,395 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use transaction::builder::ManifestBuilder;
+use transaction::model::{
+    ExecutableTransaction, PreviewIntent, TestTransaction, TransactionManifest,
+};
+use transaction::signing::EcdsaSecp256k1PrivateKey;
+use transaction::validation::IntentHashManager;
+
+use crate::constants::*;
+use crate::engine::{
+    ExecutionTrace, Kernel, KernelError, ModuleError, NativeFnRegistry, RuntimeError, Track,
+};
+use crate::fee::{FeeDistribution, FeeTable, SystemLoanFeeReserve};
+use crate::ledger::*;
+use crate::model::{extract_abi, ComponentState, MethodAuthorizationError};
+use crate::transaction::{
+    ExecutionConfig, FeeReserveConfig, PreviewError, PreviewExecutor, PreviewResult,
+    TransactionExecutor, TransactionReceipt, TransactionResult,
+};
+use crate::types::*;
+use crate::wasm::{
+    DefaultWasmEngine, DefaultWasmInstance, PackageModuleCache, WasmInstrumenter,
+    WasmMeteringParams,
+};
+
+/// Executes test transactions against any substate store, signing them with keys derived from a
+/// counter and numbering them with increasing nonces.
+///
+/// Test runners own one alongside their store, so that they all execute transactions alike.
+pub struct TestExecutor {
+    wasm_engine: DefaultWasmEngine,
+    wasm_instrumenter: WasmInstrumenter,
+    next_private_key: u64,
+    next_transaction_nonce: u64,
+    trace: bool,
+}
+
+impl TestExecutor {
+    pub fn new(trace: bool) -> Self {
+        Self {
+            wasm_engine: DefaultWasmEngine::new(),
+            wasm_instrumenter: WasmInstrumenter::new(),
+            next_private_key: 1, // 0 is invalid
+            next_transaction_nonce: 0,
+            trace,
+        }
+    }
+
+    pub fn next_transaction_nonce(&self) -> u64 {
+        self.next_transaction_nonce
+    }
+
+    pub fn new_key_pair(&mut self) -> (EcdsaSecp256k1PublicKey, EcdsaSecp256k1PrivateKey) {
+        let private_key = EcdsaSecp256k1PrivateKey::from_u64(self.next_private_key).unwrap();
+        let public_key = private_key.public_key();
+
+        self.next_private_key += 1;
+        (public_key, private_key)
+    }
+
+    pub fn fee_reserve_config(&self) -> FeeReserveConfig {
+        FeeReserveConfig {
+            system_loan: DEFAULT_SYSTEM_LOAN,
+            distribution: FeeDistribution::default(),
+            free_credit: 0,
+        }
+    }
+
+    pub fn execution_config(&self) -> ExecutionConfig {
+        ExecutionConfig {
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            max_heap_size: DEFAULT_MAX_HEAP_SIZE,
+            trace: self.trace,
+            wasm_metering_params: WasmMeteringParams::standard(),
+        }
+    }
+
+    /// Wraps a manifest signed by the given keys into a transaction with the next nonce.
+    pub fn new_transaction(
+        &mut self,
+        manifest: TransactionManifest,
+        signer_public_keys: Vec<PublicKey>,
+    ) -> TestTransaction {
+        let transaction =
+            TestTransaction::new(manifest, self.next_transaction_nonce, signer_public_keys);
+        self.next_transaction_nonce += 1;
+        transaction
+    }
+
+    /// Executes a manifest signed by the given keys and commits the outcome to the store.
+    pub fn execute_manifest<S: ReadableSubstateStore + WriteableSubstateStore>(
+        &mut self,
+        substate_store: &mut S,
+        manifest: TransactionManifest,
+        signer_public_keys: Vec<PublicKey>,
+    ) -> TransactionReceipt {
+        let transaction = self.new_transaction(manifest, signer_public_keys);
+        let fee_reserve_config = self.fee_reserve_config();
+        let execution_config = self.execution_config();
+
+        TransactionExecutor::new(
+            substate_store,
+            &mut self.wasm_engine,
+            &mut self.wasm_instrumenter,
+        )
+        .execute_and_commit(&transaction, &fee_reserve_config, &execution_config)
+    }
+
+    /// Executes a transaction without committing its outcome.
+    pub fn execute_transaction<S: ReadableSubstateStore, T: ExecutableTransaction>(
+        &mut self,
+        substate_store: &mut S,
+        transaction: &T,
+        fee_reserve_config: &FeeReserveConfig,
+        execution_config: &ExecutionConfig,
+    ) -> TransactionReceipt {
+        TransactionExecutor::new(
+            substate_store,
+            &mut self.wasm_engine,
+            &mut self.wasm_instrumenter,
+        )
+        .execute(transaction, fee_reserve_config, execution_config)
+    }
+
+    pub fn execute_preview<S: ReadableSubstateStore, IHM: IntentHashManager>(
+        &mut self,
+        substate_store: &mut S,
+        intent_hash_manager: &IHM,
+        preview_intent: PreviewIntent,
+        network: &NetworkDefinition,
+    ) -> Result<PreviewResult, PreviewError> {
+        PreviewExecutor::new(
+            substate_store,
+            &mut self.wasm_engine,
+            &mut self.wasm_instrumenter,
+            intent_hash_manager,
+            network,
+        )
+        .execute(preview_intent)
+    }
+
+    /// Performs a kernel call through a kernel with `is_system = true`, committing the state
+    /// changes to the store.
+    pub fn kernel_call<S, F, T>(
+        &mut self,
+        substate_store: &mut S,
+        initial_proofs: Vec<NonFungibleAddress>,
+        fun: F,
+    ) -> T
+    where
+        S: ReadableSubstateStore + WriteableSubstateStore,
+        F: FnOnce(&mut Kernel<DefaultWasmEngine, DefaultWasmInstance, SystemLoanFeeReserve>) -> T,
+    {
+        let tx_hash = hash(self.next_transaction_nonce.to_string());
+        let blobs = HashMap::new();
+        let mut track = Track::new(
+            substate_store,
+            SystemLoanFeeReserve::default(),
+            FeeTable::new(),
+        );
+        let mut execution_trace = ExecutionTrace::new();
+        let mut package_module_cache = PackageModuleCache::new();
+
+        let mut kernel = Kernel::new(
+            tx_hash,
+            initial_proofs,
+            &blobs,
+            DEFAULT_MAX_CALL_DEPTH,
+            DEFAULT_MAX_HEAP_SIZE,
+            &mut track,
+            &mut self.wasm_engine,
+            &mut self.wasm_instrumenter,
+            &mut package_module_cache,
+            WasmMeteringParams::standard(),
+            &mut execution_trace,
+            Vec::new(),
+            NativeFnRegistry::standard(),
+        );
+
+        // Invoke the system
+        let output = fun(&mut kernel);
+
+        // Commit
+        self.next_transaction_nonce += 1;
+        let receipt = track.finalize(Ok(Vec::new()), Vec::new());
+        if let TransactionResult::Commit(c) = receipt.result {
+            c.state_updates.commit(substate_store);
+        }
+
+        output
+    }
+}
+
+/// Builds a manifest creating an account funded by the system faucet.
+pub fn new_account_manifest(withdraw_auth: &AccessRule) -> TransactionManifest {
+    ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(100.into(), SYS_FAUCET_COMPONENT)
+        .call_method(SYS_FAUCET_COMPONENT, "free_xrd", args!())
+        .take_from_worktop(RADIX_TOKEN, |builder, bucket_id| {
+            builder.new_account_with_resource(withdraw_auth, bucket_id)
+        })
+        .build()
+}
+
+/// Builds a manifest publishing a package, with the fee paid by the system faucet.
+pub fn publish_package_manifest(
+    code: Vec<u8>,
+    abi: HashMap<String, BlueprintAbi>,
+) -> TransactionManifest {
+    ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(100.into(), SYS_FAUCET_COMPONENT)
+        .publish_package(code, abi)
+        .build()
+}
+
+/// A ledger simulator for blueprint tests, backed by a bootstrapped in-memory substate store.
+pub struct TestRunner {
+    substate_store: TypedInMemorySubstateStore,
+    executor: TestExecutor,
+}
+
+impl TestRunner {
+    pub fn new(trace: bool) -> Self {
+        Self {
+            substate_store: TypedInMemorySubstateStore::with_bootstrap(),
+            executor: TestExecutor::new(trace),
+        }
+    }
+
+    pub fn substate_store(&self) -> &TypedInMemorySubstateStore {
+        &self.substate_store
+    }
+
+    pub fn substate_store_mut(&mut self) -> &mut TypedInMemorySubstateStore {
+        &mut self.substate_store
+    }
+
+    pub fn new_key_pair(&mut self) -> (EcdsaSecp256k1PublicKey, EcdsaSecp256k1PrivateKey) {
+        self.executor.new_key_pair()
+    }
+
+    /// Creates an account funded by the system faucet, withdrawable with the signature of a new key.
+    pub fn create_account(
+        &mut self,
+    ) -> (
+        EcdsaSecp256k1PublicKey,
+        EcdsaSecp256k1PrivateKey,
+        ComponentAddress,
+    ) {
+        let (public_key, private_key) = self.new_key_pair();
+        let withdraw_auth = rule!(require(NonFungibleAddress::from_public_key(&public_key)));
+
+        let receipt = self.call(new_account_manifest(&withdraw_auth), vec![]);
+        receipt.expect_commit_success();
+        (
+            public_key,
+            private_key,
+            receipt.new_component_addresses()[0],
+        )
+    }
+
+    /// Publishes a compiled package, extracting the ABI from its code.
+    pub fn publish_package(&mut self, code: Vec<u8>) -> PackageAddress {
+        let abi = extract_abi(&code).expect("Failed to extract ABI from the package code");
+
+        let receipt = self.call(publish_package_manifest(code, abi), vec![]);
+        receipt.expect_commit_success();
+        receipt.new_package_addresses()[0]
+    }
+
+    /// Compiles the package in the given directory to WASM and publishes it.
+    pub fn compile_and_publish<P: AsRef<Path>>(&mut self, package_dir: P) -> PackageAddress {
+        let code = compile_package(package_dir);
+        self.publish_package(code)
+    }
+
+    /// Executes a manifest signed by the given keys and commits the outcome.
+    pub fn call(
+        &mut self,
+        manifest: TransactionManifest,
+        signer_public_keys: Vec<PublicKey>,
+    ) -> TransactionReceipt {
+        self.executor
+            .execute_manifest(&mut self.substate_store, manifest, signer_public_keys)
+    }
+
+    /// Returns the balance of an account in the given resource, without changing the ledger.
+    pub fn get_balance(
+        &mut self,
+        account: ComponentAddress,
+        resource_address: ResourceAddress,
+    ) -> Decimal {
+        let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+            .lock_fee(100.into(), SYS_FAUCET_COMPONENT)
+            .call_method(account, "balance", args!(resource_address))
+            .build();
+        let transaction = self.executor.new_transaction(manifest, Vec::new());
+        let fee_reserve_config = self.executor.fee_reserve_config();
+        let execution_config = self.executor.execution_config();
+
+        let receipt = self.executor.execute_transaction(
+            &mut self.substate_store,
+            &transaction,
+            &fee_reserve_config,
+            &execution_config,
+        );
+        receipt.expect_commit_success();
+        receipt.output(1)
+    }
+
+    /// Decodes the committed state of a component into the given type.
+    pub fn get_component_state<T: Decode>(&self, component_address: ComponentAddress) -> T {
+        let component_state: ComponentState = self
+            .substate_store
+            .get_substate(&SubstateId::ComponentState(component_address))
+            .map(|output| output.substate.into())
+            .expect("Component state not found");
+        scrypto_decode(component_state.state()).expect("Failed to decode component state")
+    }
+}
+
+// Naive pattern matching to find the crate name.
+fn extract_crate_name(mut content: &str) -> Result<String, ()> {
+    let idx = content.find("name").ok_or(())?;
+    content = &content[idx + 4..];
+
+    let idx = content.find('"').ok_or(())?;
+    content = &content[idx + 1..];
+
+    let end = content.find('"').ok_or(())?;
+    Ok(content[..end].to_string())
+}
+
+/// Builds the package in the given directory for `wasm32-unknown-unknown` and returns its code.
+pub fn compile_package<P: AsRef<Path>>(package_dir: P) -> Vec<u8> {
+    // Build
+    let status = Command::new("cargo")
+        .current_dir(package_dir.as_ref())
+        .args(["build", "--target", "wasm32-unknown-unknown", "--release"])
+        .status()
+        .unwrap();
+    if !status.success() {
+        panic!("Failed to compile package: {:?}", package_dir.as_ref());
+    }
+
+    // Find wasm path
+    let mut cargo = package_dir.as_ref().to_owned();
+    cargo.push("Cargo.toml");
+    let wasm_name = if cargo.exists() {
+        let content = fs::read_to_string(cargo).expect("Failed to read the Cargo.toml file");
+        extract_crate_name(&content)
+            .expect("Failed to extract crate name from the Cargo.toml file")
+            .replace('-', "_")
+    } else {
+        // file name
+        package_dir
+            .as_ref()
+            .file_name()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_owned()
+            .replace('-', "_")
+    };
+    let mut path = PathBuf::from(package_dir.as_ref());
+    path.push("target");
+    path.push("wasm32-unknown-unknown");
+    path.push("release");
+    path.push(wasm_name);
+    path.set_extension("wasm");
+
+    fs::read(path).expect("Failed to read the compiled package")
+}
+
+pub fn is_auth_error(e: &RuntimeError) -> bool {
+    matches!(
+        e,
+        RuntimeError::ModuleError(ModuleError::AuthorizationError {
+            authorization: _,
+            function: _,
+            error: MethodAuthorizationError::NotAuthorized
+        })
+    )
+}
+
+pub fn is_costing_error(e: &RuntimeError) -> bool {
+    matches!(e, RuntimeError::ModuleError(ModuleError::CostingError(_)))
+}
+
+pub fn is_wasm_error(e: &RuntimeError) -> bool {
+    matches!(e, RuntimeError::KernelError(KernelError::WasmError(_)))
+}
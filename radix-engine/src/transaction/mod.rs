@@ -1,7 +1,9 @@
 mod preview_executor;
+mod receipt_json;
 mod transaction_executor;
 mod transaction_receipt;
 
 pub use preview_executor::*;
+pub use receipt_json::*;
 pub use transaction_executor::*;
 pub use transaction_receipt::*;
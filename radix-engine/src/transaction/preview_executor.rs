@@ -1,14 +1,15 @@
 use scrypto::core::NetworkDefinition;
 use transaction::errors::TransactionValidationError;
-use transaction::model::PreviewIntent;
+use transaction::model::{ExecutableTransaction, PreviewIntent};
 use transaction::validation::IntentHashManager;
 use transaction::validation::NotarizedTransactionValidator;
 use transaction::validation::ValidationConfig;
 
 use crate::constants::DEFAULT_MAX_COST_UNIT_LIMIT;
 use crate::constants::PREVIEW_CREDIT;
-use crate::fee::SystemLoanFeeReserve;
+use crate::fee::{FeeReserveError, SystemLoanFeeReserve};
 use crate::ledger::*;
+use crate::model::System;
 use crate::transaction::TransactionReceipt;
 use crate::transaction::*;
 use crate::types::*;
@@ -20,9 +21,23 @@ pub struct PreviewResult {
     pub receipt: TransactionReceipt,
 }
 
+impl PreviewResult {
+    /// Returns the number of cost units the transaction is estimated to consume.
+    pub fn estimated_cost_units(&self) -> u32 {
+        self.receipt.execution.fee_summary.cost_unit_consumed
+    }
+
+    /// Returns the XRD fee the transaction is estimated to be charged, tip included and free
+    /// credit excluded.
+    pub fn estimated_fee(&self) -> Decimal {
+        self.receipt.execution.fee_summary.fee_after_free_credit()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PreviewError {
     TransactionValidationError(TransactionValidationError),
+    FeeReserveError(FeeReserveError),
 }
 
 pub struct PreviewExecutor<'s, 'w, 'n, S, W, I, IHM>
@@ -82,20 +97,28 @@ where
             .validate_preview_intent(preview_intent.clone(), self.intent_hash_manager)
             .map_err(PreviewError::TransactionValidationError)?;
 
+        let fee_reserve_config = FeeReserveConfig::standard();
+        let mut fee_reserve = SystemLoanFeeReserve::new(
+            validated_preview_transaction.cost_unit_limit(),
+            validated_preview_transaction.tip_percentage(),
+            System::load_cost_unit_price(self.substate_store),
+            fee_reserve_config.system_loan,
+        )
+        .with_distribution(fee_reserve_config.distribution)
+        .map_err(PreviewError::FeeReserveError)?;
+        if preview_intent.flags.unlimited_loan {
+            fee_reserve.credit(PREVIEW_CREDIT);
+        }
         let mut transaction_executor = TransactionExecutor::new(
             self.substate_store,
             self.wasm_engine,
             self.wasm_instrumenter,
         );
 
-        let mut fee_reserve = SystemLoanFeeReserve::default();
-        if preview_intent.flags.unlimited_loan {
-            fee_reserve.credit(PREVIEW_CREDIT);
-        }
         let receipt = transaction_executor.execute_with_fee_reserve(
             &validated_preview_transaction,
             &execution_params,
-            SystemLoanFeeReserve::default(),
+            fee_reserve,
         );
 
         Ok(PreviewResult {
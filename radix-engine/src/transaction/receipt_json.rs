@@ -0,0 +1,195 @@
+use scrypto::core::NetworkDefinition;
+use serde_json::{json, Value};
+
+use crate::transaction::*;
+use crate::types::*;
+
+/// The version of the JSON receipt schema, bumped on any breaking change.
+pub const RECEIPT_JSON_VERSION: u32 = 4;
+
+impl TransactionReceipt {
+    /// Serializes the receipt into a JSON value, with addresses encoded for the given network.
+    ///
+    /// The schema (version [`RECEIPT_JSON_VERSION`]) is:
+    ///
+    /// ```text
+    /// {
+    ///   "version": 4,
+    ///   "status": "CommittedSuccess" | "CommittedFailure" | "Rejected",
+    ///   "error": { "code": u32, "cause_code": u32 | null, "message": string } | null,
+    ///   "fee_summary": {
+    ///     "cost_unit_limit": u32, "cost_unit_consumed": u32, "cost_unit_price": decimal,
+    ///     "tip_percentage": u32, "base_fee": decimal, "tipped": decimal, "burned": decimal,
+    ///     "network_fee": decimal, "royalties": decimal, "free_credit": decimal,
+    ///     "total_fee": decimal, "fee_payer": non_fungible_address | null,
+    ///     "cost_breakdown": { cost_category: u32 }
+    ///   },
+    ///   "outputs": [value | null],
+    ///   "state_updates": {
+    ///     "up_substates": [{ "substate_id": hex, "version": u32 }],
+    ///     "down_substates": [{ "substate_id": hex, "substate_hash": hex, "version": u32 }],
+    ///     "down_virtual_substates": [{ "substate_id": hex, "key": hex }]
+    ///   },
+    ///   "new_entities": { "packages": [address], "components": [address], "resources": [address] },
+    ///   "resource_changes": [
+    ///     { "component_address": address, "vault_id": hex, "resource_address": address, "amount": decimal }
+    ///   ],
+    ///   "logs": [{ "level": "ERROR" | "WARN" | "INFO" | "DEBUG" | "TRACE", "message": string }],
+    ///   "events": [{ "emitter": address | null, "name": string, "data": value | null }]
+    /// }
+    /// ```
+    ///
+    /// Decimals are strings, outputs and event data are in the JSON form of
+    /// [`ScryptoValue::to_json`] (`null` if undecodable), substate and vault ids are hex-encoded
+    /// SBOR, `outputs`, `state_updates`, `new_entities` and `resource_changes` are empty unless
+    /// committed, and `events` is empty unless committed successfully.
+    pub fn to_json(&self, network: &NetworkDefinition) -> Value {
+        let bech32_encoder = Bech32Encoder::new(network);
+        let fee_summary = &self.execution.fee_summary;
+
+        let (status, error) = match &self.result {
+            TransactionResult::Commit(c) => match &c.outcome {
+                TransactionOutcome::Success(_) => ("CommittedSuccess", Value::Null),
+                TransactionOutcome::Failure(e) => (
+                    "CommittedFailure",
+                    json!({ "code": e.error_code(), "message": e.to_string() }),
+                ),
+            },
+            TransactionResult::Reject(r) => (
+                "Rejected",
+                json!({
+                    "code": r.error_code,
+                    "cause_code": r.error.cause_error_code(),
+                    "message": r.error.to_string()
+                }),
+            ),
+        };
+
+        let mut outputs = Vec::new();
+        let mut up_substates = Vec::new();
+        let mut down_substates = Vec::new();
+        let mut down_virtual_substates = Vec::new();
+        let mut packages = Vec::new();
+        let mut components = Vec::new();
+        let mut resources = Vec::new();
+        let mut resource_changes = Vec::new();
+        if let TransactionResult::Commit(c) = &self.result {
+            if let TransactionOutcome::Success(o) = &c.outcome {
+                outputs = o
+                    .iter()
+                    .map(|output| {
+                        ScryptoValue::from_slice(output)
+                            .map(|value| value.to_json(network))
+                            .unwrap_or(Value::Null)
+                    })
+                    .collect();
+            }
+            for (substate_id, output) in &c.state_updates.up_substates {
+                up_substates.push(json!({
+                    "substate_id": hex::encode(scrypto_encode(substate_id)),
+                    "version": output.version,
+                }));
+            }
+            for output_id in &c.state_updates.down_substates {
+                down_substates.push(json!({
+                    "substate_id": hex::encode(scrypto_encode(&output_id.substate_id)),
+                    "substate_hash": output_id.substate_hash.to_string(),
+                    "version": output_id.version,
+                }));
+            }
+            for virtual_substate_id in &c.state_updates.down_virtual_substates {
+                down_virtual_substates.push(json!({
+                    "substate_id": hex::encode(scrypto_encode(&virtual_substate_id.0)),
+                    "key": hex::encode(&virtual_substate_id.1),
+                }));
+            }
+            for package_address in &c.entity_changes.new_package_addresses {
+                packages.push(bech32_encoder.encode_package_address(package_address));
+            }
+            for component_address in &c.entity_changes.new_component_addresses {
+                components.push(bech32_encoder.encode_component_address(component_address));
+            }
+            for resource_address in &c.entity_changes.new_resource_addresses {
+                resources.push(bech32_encoder.encode_resource_address(resource_address));
+            }
+            for change in &c.resource_changes {
+                resource_changes.push(json!({
+                    "component_address":
+                        bech32_encoder.encode_component_address(&change.component_address),
+                    "vault_id": hex::encode(scrypto_encode(&change.vault_id)),
+                    "resource_address":
+                        bech32_encoder.encode_resource_address(&change.resource_address),
+                    "amount": change.amount.to_string(),
+                }));
+            }
+        }
+
+        let cost_breakdown: serde_json::Map<String, Value> = fee_summary
+            .cost_breakdown_by_category()
+            .into_iter()
+            .map(|(category, cost_units)| (format!("{:?}", category), cost_units.into()))
+            .collect();
+
+        let logs: Vec<Value> = self
+            .execution
+            .application_logs
+            .iter()
+            .map(|(level, message)| json!({ "level": level.to_string(), "message": message }))
+            .collect();
+
+        let events: Vec<Value> = self
+            .execution
+            .events
+            .iter()
+            .map(|event| {
+                let emitter = event.emitter.as_ref().map(|emitter| match emitter {
+                    CallerOrigin::Package(package_address) => {
+                        bech32_encoder.encode_package_address(package_address)
+                    }
+                    CallerOrigin::Component(component_address) => {
+                        bech32_encoder.encode_component_address(component_address)
+                    }
+                });
+                let data = ScryptoValue::from_slice(&event.data)
+                    .map(|value| value.to_json(network))
+                    .unwrap_or(Value::Null);
+                json!({ "emitter": emitter, "name": event.name, "data": data })
+            })
+            .collect();
+
+        json!({
+            "version": RECEIPT_JSON_VERSION,
+            "status": status,
+            "error": error,
+            "fee_summary": {
+                "cost_unit_limit": fee_summary.cost_unit_limit,
+                "cost_unit_consumed": fee_summary.cost_unit_consumed,
+                "cost_unit_price": fee_summary.cost_unit_price.to_string(),
+                "tip_percentage": fee_summary.tip_percentage,
+                "base_fee": fee_summary.base_fee().to_string(),
+                "tipped": fee_summary.tipped.to_string(),
+                "burned": fee_summary.burned.to_string(),
+                "network_fee": fee_summary.network_fee.to_string(),
+                "royalties": fee_summary.total_royalties().to_string(),
+                "free_credit": fee_summary.free_credit.to_string(),
+                "total_fee": fee_summary.total_fee().to_string(),
+                "fee_payer": self.execution.fee_payer.as_ref().map(|a| a.to_string()),
+                "cost_breakdown": cost_breakdown,
+            },
+            "outputs": outputs,
+            "state_updates": {
+                "up_substates": up_substates,
+                "down_substates": down_substates,
+                "down_virtual_substates": down_virtual_substates,
+            },
+            "new_entities": {
+                "packages": packages,
+                "components": components,
+                "resources": resources,
+            },
+            "resource_changes": resource_changes,
+            "logs": logs,
+            "events": events,
+        })
+    }
+}
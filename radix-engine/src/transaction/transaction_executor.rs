@@ -1,9 +1,9 @@
 use transaction::model::*;
 
-use crate::constants::{DEFAULT_COST_UNIT_PRICE, DEFAULT_MAX_CALL_DEPTH, DEFAULT_SYSTEM_LOAN};
+use crate::constants::{DEFAULT_MAX_CALL_DEPTH, DEFAULT_MAX_HEAP_SIZE, DEFAULT_SYSTEM_LOAN};
 use crate::engine::Track;
 use crate::engine::*;
-use crate::fee::{FeeReserve, FeeTable, SystemLoanFeeReserve};
+use crate::fee::{FeeDistribution, FeeReserve, FeeReserveError, FeeSummary, SystemLoanFeeReserve};
 use crate::ledger::{ReadableSubstateStore, WriteableSubstateStore};
 use crate::model::*;
 use crate::transaction::*;
@@ -11,24 +11,27 @@ use crate::types::*;
 use crate::wasm::*;
 
 pub struct FeeReserveConfig {
-    pub cost_unit_price: Decimal,
     pub system_loan: u32,
+    pub distribution: FeeDistribution,
+    /// Free credit, in cost units, granted to the transaction before execution
+    pub free_credit: u32,
 }
 
 impl FeeReserveConfig {
     pub fn standard() -> Self {
         Self {
-            cost_unit_price: DEFAULT_COST_UNIT_PRICE
-                .parse()
-                .expect("Invalid cost unit price"),
             system_loan: DEFAULT_SYSTEM_LOAN,
+            distribution: FeeDistribution::default(),
+            free_credit: 0,
         }
     }
 }
 
 pub struct ExecutionConfig {
     pub max_call_depth: usize,
+    pub max_heap_size: usize,
     pub trace: bool,
+    pub wasm_metering_params: WasmMeteringParams,
 }
 
 impl Default for ExecutionConfig {
@@ -41,28 +44,35 @@ impl ExecutionConfig {
     pub fn standard() -> Self {
         Self {
             max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            max_heap_size: DEFAULT_MAX_HEAP_SIZE,
             trace: false,
+            wasm_metering_params: WasmMeteringParams::standard(),
         }
     }
 
     pub fn debug() -> Self {
         Self {
             max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            max_heap_size: DEFAULT_MAX_HEAP_SIZE,
             trace: true,
+            wasm_metering_params: WasmMeteringParams::standard(),
         }
     }
 }
 
 /// An executor that runs transactions.
-pub struct TransactionExecutor<'s, 'w, S, W, I>
+pub struct TransactionExecutor<'s, 'w, S, W, I, N = ()>
 where
     S: ReadableSubstateStore,
     W: WasmEngine<I>,
     I: WasmInstance,
+    N: NativeFnProvider,
 {
     substate_store: &'s mut S,
     wasm_engine: &'w mut W,
     wasm_instrumenter: &'w mut WasmInstrumenter,
+    package_module_cache: PackageModuleCache,
+    native_fns: N,
     phantom: PhantomData<I>,
 }
 
@@ -76,11 +86,32 @@ where
         substate_store: &'s mut S,
         wasm_engine: &'w mut W,
         wasm_instrumenter: &'w mut WasmInstrumenter,
+    ) -> Self {
+        Self::with_native_fns(substate_store, wasm_engine, wasm_instrumenter, ())
+    }
+}
+
+impl<'s, 'w, S, W, I, N> TransactionExecutor<'s, 'w, S, W, I, N>
+where
+    S: ReadableSubstateStore,
+    W: WasmEngine<I>,
+    I: WasmInstance,
+    N: NativeFnProvider,
+{
+    /// Creates an executor which registers the given native functions on top of the
+    /// standard ones.
+    pub fn with_native_fns(
+        substate_store: &'s mut S,
+        wasm_engine: &'w mut W,
+        wasm_instrumenter: &'w mut WasmInstrumenter,
+        native_fns: N,
     ) -> Self {
         Self {
             substate_store,
             wasm_engine,
             wasm_instrumenter,
+            package_module_cache: PackageModuleCache::new(),
+            native_fns,
             phantom: PhantomData,
         }
     }
@@ -94,9 +125,23 @@ where
         let fee_reserve = SystemLoanFeeReserve::new(
             transaction.cost_unit_limit(),
             transaction.tip_percentage(),
-            fee_reserve_config.cost_unit_price,
+            System::load_cost_unit_price(self.substate_store),
             fee_reserve_config.system_loan,
         );
+        let mut fee_reserve =
+            match fee_reserve.with_distribution(fee_reserve_config.distribution.clone()) {
+                Ok(fee_reserve) => fee_reserve,
+                Err(error) => {
+                    return rejection_receipt(
+                        transaction,
+                        SystemLoanFeeReserve::default().finalize(),
+                        error,
+                    )
+                }
+            };
+        if fee_reserve_config.free_credit > 0 {
+            fee_reserve.credit_free(fee_reserve_config.free_credit);
+        }
 
         self.execute_with_fee_reserve(transaction, execution_config, fee_reserve)
     }
@@ -110,9 +155,15 @@ where
         let transaction_hash = transaction.transaction_hash();
         let initial_proofs = transaction.initial_proofs();
         let instructions = transaction.instructions().to_vec();
+        let subintents = transaction.subintents();
         let blobs: HashMap<Hash, Vec<u8>> = transaction
             .blobs()
             .iter()
+            .chain(
+                subintents
+                    .iter()
+                    .flat_map(|subintent| subintent.blobs.iter()),
+            )
             .map(|b| (hash(b), b.clone()))
             .collect();
 
@@ -127,72 +178,86 @@ where
         }
 
         // Prepare state track and execution trace
-        let track = Track::new(self.substate_store, fee_reserve, FeeTable::new());
+        let fee_table = System::load_fee_table(self.substate_store);
+        let mut track = Track::new(self.substate_store, fee_reserve, fee_table);
+        if let Some(declared_substates) = transaction.declared_substates() {
+            track.declare_substates(declared_substates.iter().cloned().collect());
+        }
 
         // Apply pre execution costing
         let pre_execution_result = track.apply_pre_execution_costs(transaction);
         let mut track = match pre_execution_result {
             Ok(track) => track,
-            Err(err) => {
-                return TransactionReceipt {
-                    contents: TransactionContents { instructions },
-                    execution: TransactionExecution {
-                        fee_summary: err.fee_summary,
-                        application_logs: vec![],
-                    },
-                    result: TransactionResult::Reject(RejectResult {
-                        error: RejectionError::ErrorBeforeFeeLoanRepaid(RuntimeError::ModuleError(
-                            ModuleError::CostingError(err.error),
-                        )),
-                    }),
-                };
-            }
+            Err(err) => return rejection_receipt(transaction, err.fee_summary, err.error),
         };
 
         // Invoke the function/method
         let mut execution_trace = ExecutionTrace::new();
-        let invoke_result = {
+        let (invoke_result, required_badges) = {
             let mut modules = Vec::<Box<dyn Module<R>>>::new();
             if execution_config.trace {
                 modules.push(Box::new(LoggerModule::new()));
             }
             modules.push(Box::new(CostingModule::default()));
+            let mut native_fns = NativeFnRegistry::standard();
+            self.native_fns.register(&mut native_fns);
             let mut kernel = Kernel::new(
                 transaction_hash,
                 initial_proofs,
                 &blobs,
                 execution_config.max_call_depth,
+                execution_config.max_heap_size,
                 &mut track,
                 self.wasm_engine,
                 self.wasm_instrumenter,
-                WasmMeteringParams::new(InstructionCostRules::tiered(1, 5, 10, 5000), 512), // TODO: add to ExecutionConfig
+                &mut self.package_module_cache,
+                execution_config.wasm_metering_params.clone(),
                 &mut execution_trace,
                 modules,
+                native_fns,
             );
-            kernel
+            kernel.add_virtual_proof_resources(transaction.virtual_proof_resources());
+            if let Some(fee_payer) = transaction.fee_payer() {
+                kernel.set_fee_payer(fee_payer);
+            }
+            let invoke_result = kernel
                 .invoke_function(
                     FnIdentifier::Native(NativeFnIdentifier::TransactionProcessor(
                         TransactionProcessorFnIdentifier::Run,
                     )),
                     ScryptoValue::from_typed(&TransactionProcessorRunInput {
                         instructions: instructions.clone(),
+                        subintents,
                     }),
                 )
                 .map(|o| {
-                    scrypto_decode::<Vec<Vec<u8>>>(&o.raw)
+                    scrypto_decode::<Vec<InstructionOutput>>(&o.raw)
                         .expect("TransactionProcessor returned data of unexpected type")
-                })
+                });
+            (invoke_result, kernel.virtually_proven())
         };
 
         // Produce the final transaction receipt
         let execution_trace_receipt = execution_trace.to_receipt();
         let track_receipt = track.finalize(invoke_result, execution_trace_receipt.resource_changes);
+        if let TransactionResult::Commit(commit) = &track_receipt.result {
+            for substate_id in commit.state_updates.up_substates.keys() {
+                if let SubstateId::Package(package_address) = substate_id {
+                    self.package_module_cache.invalidate(*package_address);
+                }
+            }
+        }
 
         let receipt = TransactionReceipt {
             contents: TransactionContents { instructions },
             execution: TransactionExecution {
                 fee_summary: track_receipt.fee_summary,
                 application_logs: track_receipt.application_logs,
+                events: track_receipt.events,
+                wasm_memory_usage: execution_trace_receipt.wasm_memory_usage,
+                substate_cache_stats: track_receipt.substate_cache_stats,
+                fee_payer: transaction.fee_payer(),
+                required_badges,
             },
             result: track_receipt.result,
         };
@@ -209,6 +274,16 @@ where
                 println!("{:<30}: {:>8}", k, v);
             }
 
+            println!("{:-^80}", "Cost By Category");
+            for (k, v) in receipt.execution.fee_summary.cost_breakdown_by_category() {
+                println!("{:<30}: {:>8}", format!("{:?}", k), v);
+            }
+
+            println!("{:-^80}", "Cost By Call Frame");
+            for c in &receipt.execution.fee_summary.call_frame_breakdown {
+                println!("[{}] {:?}: {}", c.depth, c.fn_identifier, c.cost_units);
+            }
+
             println!("{:-^80}", "Application Logs");
             for (level, message) in &receipt.execution.application_logs {
                 println!("[{}] {}", level, message);
@@ -216,16 +291,34 @@ where
             if receipt.execution.application_logs.is_empty() {
                 println!("None");
             }
+
+            println!("{:-^80}", "WASM Memory Usage");
+            for m in &receipt.execution.wasm_memory_usage {
+                println!(
+                    "[{}] {:?}: {} pages",
+                    m.depth, m.fn_identifier, m.peak_memory_pages
+                );
+            }
+            if receipt.execution.wasm_memory_usage.is_empty() {
+                println!("None");
+            }
+
+            println!("{:-^80}", "Substate Cache");
+            let stats = &receipt.execution.substate_cache_stats;
+            println!("{:<30}: {:>8}", "Hits", stats.hits);
+            println!("{:<30}: {:>8}", "Misses", stats.misses);
+            println!("{:<30}: {:>8}", "Dirty", stats.dirty);
         }
         receipt
     }
 }
 
-impl<'s, 'w, S, W, I> TransactionExecutor<'s, 'w, S, W, I>
+impl<'s, 'w, S, W, I, N> TransactionExecutor<'s, 'w, S, W, I, N>
 where
     S: ReadableSubstateStore + WriteableSubstateStore,
     W: WasmEngine<I>,
     I: WasmInstance,
+    N: NativeFnProvider,
 {
     pub fn execute_and_commit<T: ExecutableTransaction>(
         &mut self,
@@ -240,3 +333,30 @@ where
         receipt
     }
 }
+
+/// Builds the receipt of a transaction rejected before its fee loan was repaid.
+fn rejection_receipt<T: ExecutableTransaction>(
+    transaction: &T,
+    fee_summary: FeeSummary,
+    error: FeeReserveError,
+) -> TransactionReceipt {
+    TransactionReceipt {
+        contents: TransactionContents {
+            instructions: transaction.instructions().to_vec(),
+        },
+        execution: TransactionExecution {
+            fee_summary,
+            application_logs: vec![],
+            events: vec![],
+            wasm_memory_usage: vec![],
+            substate_cache_stats: SubstateCacheStats::default(),
+            fee_payer: transaction.fee_payer(),
+            required_badges: vec![],
+        },
+        result: TransactionResult::Reject(RejectResult::new(
+            RejectionError::ErrorBeforeFeeLoanRepaid(RuntimeError::ModuleError(
+                ModuleError::CostingError(error),
+            )),
+        )),
+    }
+}
@@ -2,8 +2,12 @@ use colored::*;
 use scrypto::core::NetworkDefinition;
 use transaction::model::*;
 
-use crate::engine::{RejectionError, ResourceChange, RuntimeError};
+use crate::engine::{
+    ApplicationEvent, ErrorCode, RejectionError, ResourceChange, RuntimeError, SubstateCacheStats,
+    WasmMemoryUsage,
+};
 use crate::fee::FeeSummary;
+use crate::model::{HardResourceOrNonFungible, InstructionOutput};
 use crate::state_manager::StateDiff;
 use crate::types::*;
 
@@ -16,6 +20,16 @@ pub struct TransactionContents {
 pub struct TransactionExecution {
     pub fee_summary: FeeSummary,
     pub application_logs: Vec<(Level, String)>,
+    /// The events emitted, if the transaction succeeded
+    pub events: Vec<ApplicationEvent>,
+    /// The peak memory used by each WASM invocation, in pages of 64 KiB
+    pub wasm_memory_usage: Vec<WasmMemoryUsage>,
+    pub substate_cache_stats: SubstateCacheStats,
+    /// The signature badge of the signer who sponsored the fee, if not the intent signers
+    pub fee_payer: Option<NonFungibleAddress>,
+    /// The badges which were assumed rather than proven to pass authorization, when previewing
+    /// with all signature proofs assumed
+    pub required_badges: Vec<HardResourceOrNonFungible>,
 }
 
 /// Captures whether a transaction should be committed, and its other results
@@ -28,6 +42,10 @@ pub enum TransactionResult {
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct CommitResult {
     pub outcome: TransactionOutcome,
+    /// The result of each instruction, if the outcome is a success
+    pub instruction_outputs: Vec<InstructionOutput>,
+    /// The code of the failure, if the outcome is a failure
+    pub error_code: Option<ErrorCode>,
     pub state_updates: StateDiff,
     pub entity_changes: EntityChanges,
     pub resource_changes: Vec<ResourceChange>,
@@ -50,6 +68,16 @@ pub struct EntityChanges {
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct RejectResult {
     pub error: RejectionError,
+    pub error_code: ErrorCode,
+}
+
+impl RejectResult {
+    pub fn new(error: RejectionError) -> Self {
+        Self {
+            error_code: error.error_code(),
+            error,
+        }
+    }
 }
 
 /// Represents a transaction receipt.
@@ -83,6 +111,14 @@ impl TransactionReceipt {
         }
     }
 
+    /// Returns the code of the error which caused the transaction to fail or be rejected.
+    pub fn error_code(&self) -> Option<ErrorCode> {
+        match &self.result {
+            TransactionResult::Commit(c) => c.error_code,
+            TransactionResult::Reject(r) => Some(r.error_code),
+        }
+    }
+
     pub fn expect_commit_success(&self) -> &Vec<Vec<u8>> {
         match &self.result {
             TransactionResult::Commit(c) => match &c.outcome {
@@ -132,6 +168,11 @@ impl TransactionReceipt {
             .expect("Wrong instruction output type!")
     }
 
+    pub fn instruction_output(&self, nth: usize) -> &InstructionOutput {
+        self.expect_commit_success();
+        &self.expect_commit().instruction_outputs[nth]
+    }
+
     pub fn new_package_addresses(&self) -> &Vec<PackageAddress> {
         let commit = self.expect_commit();
         &commit.entity_changes.new_package_addresses
@@ -171,20 +212,60 @@ impl fmt::Debug for TransactionReceipt {
             match result {
                 TransactionResult::Commit(c) => match &c.outcome {
                     TransactionOutcome::Success(_) => "COMMITTED SUCCESS".green(),
-                    TransactionOutcome::Failure(e) => format!("COMMITTED FAILURE: {}", e).red(),
+                    TransactionOutcome::Failure(e) => {
+                        format!("COMMITTED FAILURE [{}]: {}", e.error_code(), e).red()
+                    }
                 },
-                TransactionResult::Reject(r) => format!("REJECTED: {}", r.error).red(),
+                TransactionResult::Reject(r) => {
+                    format!("REJECTED [{}]: {}", r.error_code, r.error).red()
+                }
             },
         )?;
 
         write!(
             f,
-            "\n{} {} XRD burned, {} XRD tipped to validators",
+            "\n{} {} XRD base fee, {} XRD tip ({}%), {} XRD covered by free credit",
             "Transaction Fee:".bold().green(),
-            execution.fee_summary.burned,
+            execution.fee_summary.base_fee(),
             execution.fee_summary.tipped,
+            execution.fee_summary.tip_percentage,
+            execution.fee_summary.free_credit,
+        )?;
+
+        write!(
+            f,
+            "\n{} {} XRD burned, {} XRD paid to network, {} XRD paid as royalties",
+            "Fee Distribution:".bold().green(),
+            execution.fee_summary.burned,
+            execution.fee_summary.network_fee,
+            execution.fee_summary.total_royalties(),
         )?;
 
+        if let Some(fee_payer) = &execution.fee_payer {
+            write!(f, "\n{} {}", "Fee Payer:".bold().green(), fee_payer)?;
+        }
+
+        if !execution.required_badges.is_empty() {
+            write!(
+                f,
+                "\n{} {}",
+                "Required Badges:".bold().green(),
+                execution.required_badges.len()
+            )?;
+            for (i, badge) in execution.required_badges.iter().enumerate() {
+                let badge = match badge {
+                    HardResourceOrNonFungible::NonFungible(non_fungible_address) => {
+                        non_fungible_address.to_string()
+                    }
+                    HardResourceOrNonFungible::Resource(resource_address) => {
+                        format!("any of {}", resource_address)
+                    }
+                    HardResourceOrNonFungible::SoftResourceNotFound => continue,
+                };
+                write!(f, "\n{} {}", prefix!(i, execution.required_badges), badge)?;
+            }
+        }
+
         write!(
             f,
             "\n{} {} limit, {} consumed, {} XRD per cost unit",
@@ -220,6 +301,35 @@ impl fmt::Debug for TransactionReceipt {
         // TODO - Need to fix the hardcoding of local simulator HRPs for transaction receipts, and for address formatting
         let bech32_encoder = Bech32Encoder::new(&NetworkDefinition::simulator());
 
+        write!(
+            f,
+            "\n{} {}",
+            "Events:".bold().green(),
+            execution.events.len()
+        )?;
+        for (i, event) in execution.events.iter().enumerate() {
+            let emitter = match &event.emitter {
+                Some(CallerOrigin::Component(component_address)) => {
+                    bech32_encoder.encode_component_address(component_address)
+                }
+                Some(CallerOrigin::Package(package_address)) => {
+                    bech32_encoder.encode_package_address(package_address)
+                }
+                None => "-".to_owned(),
+            };
+            let data = ScryptoValue::from_slice(&event.data)
+                .map(|value| value.to_string())
+                .unwrap_or_else(|_| hex::encode(&event.data));
+            write!(
+                f,
+                "\n{} {} from {}: {}",
+                prefix!(i, execution.events),
+                event.name,
+                emitter,
+                data
+            )?;
+        }
+
         write!(f, "\n{}", "Instructions:".bold().green())?;
         for (i, inst) in contents.instructions.iter().enumerate() {
             write!(
@@ -274,15 +384,33 @@ impl fmt::Debug for TransactionReceipt {
         }
 
         if let TransactionResult::Commit(c) = &result {
-            if let TransactionOutcome::Success(outputs) = &c.outcome {
+            if let TransactionOutcome::Success(..) = &c.outcome {
+                let outputs = &c.instruction_outputs;
                 write!(f, "\n{}", "Instruction Outputs:".bold().green())?;
                 for (i, output) in outputs.iter().enumerate() {
                     write!(
                         f,
                         "\n{} {:?}",
                         prefix!(i, outputs),
-                        ScryptoValue::from_slice(output).expect("Failed to parse return data")
+                        ScryptoValue::from_slice(&output.output)
+                            .expect("Failed to parse return data")
                     )?;
+                    let indent = if i == outputs.len() - 1 { " " } else { "│" };
+                    if !output.new_bucket_ids.is_empty() {
+                        write!(f, "\n{}  New Buckets: {:?}", indent, output.new_bucket_ids)?;
+                    }
+                    if !output.new_proof_ids.is_empty() {
+                        write!(f, "\n{}  New Proofs: {:?}", indent, output.new_proof_ids)?;
+                    }
+                    for (resource_address, change) in &output.worktop_changes {
+                        write!(
+                            f,
+                            "\n{}  Worktop: {}, {}",
+                            indent,
+                            bech32_encoder.encode_resource_address(resource_address),
+                            change
+                        )?;
+                    }
                 }
             }
         }
@@ -13,23 +13,30 @@ pub use sbor::rust::string::String;
 pub use sbor::rust::string::ToString;
 pub use sbor::rust::vec;
 pub use sbor::rust::vec::Vec;
-pub use sbor::{Decode, DecodeError, Encode, Type, TypeId, Value};
+pub use sbor::{Decode, DecodeError, DecodeLimits, Encode, Type, TypeId, Value};
 pub use scrypto::abi::{BlueprintAbi, Fn, ScryptoType};
 pub use scrypto::address::{AddressError, Bech32Decoder, Bech32Encoder};
 pub use scrypto::component::{
-    ComponentAddAccessCheckInput, ComponentAddress, PackageAddress, PackagePublishInput,
+    AccessControllerCancelRecoveryInput, AccessControllerCreateInput,
+    AccessControllerCreateProofInput, AccessControllerInitiateRecoveryInput,
+    AccessControllerLockPrimaryRoleInput, AccessControllerQuickConfirmRecoveryInput,
+    AccessControllerTimedConfirmRecoveryInput, AccessControllerUnlockPrimaryRoleInput,
+    ComponentAddAccessCheckInput, ComponentAddress, ComponentSetAccessRuleInput,
+    ComponentSetRoleRuleInput, PackageAddress, PackagePublishInput, PackagePublishWithOwnerInput,
+    PackageUpdateMetadataInput, RecoveryProposal, Role, RuleSet,
 };
 pub use scrypto::constants::*;
 pub use scrypto::core::{
-    AuthZoneFnIdentifier, BucketFnIdentifier, ComponentFnIdentifier, Expression, FnIdentifier,
-    Level, NativeFnIdentifier, NetworkDefinition, PackageFnIdentifier, ProofFnIdentifier, Receiver,
-    ResourceManagerFnIdentifier, ScryptoActor, ScryptoRENode, SystemFnIdentifier,
-    SystemGetCurrentEpochInput, SystemGetTransactionHashInput, SystemSetEpochInput,
-    TransactionProcessorFnIdentifier, VaultFnIdentifier, WorktopFnIdentifier,
+    AccessControllerFnIdentifier, AuthZoneFnIdentifier, Blob, BucketFnIdentifier,
+    ComponentFnIdentifier, Expression, FnIdentifier, Level, NativeFnIdentifier, NetworkDefinition,
+    PackageFnIdentifier, ProofFnIdentifier, Receiver, ResourceManagerFnIdentifier, ScryptoActor,
+    ScryptoRENode, SystemFnIdentifier, SystemGetCostUnitPriceInput, SystemGetCurrentEpochInput,
+    SystemGetTransactionHashInput, SystemGetTransactionSignersInput, SystemSetCostUnitPriceInput,
+    SystemSetEpochInput, TransactionProcessorFnIdentifier, VaultFnIdentifier, WorktopFnIdentifier,
 };
 pub use scrypto::crypto::{
     EcdsaSecp256k1PublicKey, EcdsaSecp256k1Signature, EddsaEd25519PublicKey, EddsaEd25519Signature,
-    Hash, PublicKey, Signature,
+    Hash, HashAlgorithm, PublicKey, Signature,
 };
 pub use scrypto::engine::{api::RadixEngineInput, types::*};
 pub use scrypto::math::{Decimal, RoundingMode, I256};
@@ -38,19 +45,19 @@ pub use scrypto::resource::{
     AuthZoneCreateProofByIdsInput, AuthZoneCreateProofInput, AuthZonePopInput, AuthZonePushInput,
     BucketCreateProofInput, BucketGetAmountInput, BucketGetNonFungibleIdsInput,
     BucketGetResourceAddressInput, BucketPutInput, BucketTakeInput, BucketTakeNonFungiblesInput,
-    ConsumingBucketBurnInput, ConsumingProofDropInput, MintParams, Mutability, NonFungibleAddress,
-    NonFungibleId, ProofCloneInput, ProofGetAmountInput, ProofGetNonFungibleIdsInput,
-    ProofGetResourceAddressInput, ProofRule, ResourceAddress, ResourceManagerCreateBucketInput,
-    ResourceManagerCreateInput, ResourceManagerCreateVaultInput, ResourceManagerGetMetadataInput,
-    ResourceManagerGetNonFungibleInput, ResourceManagerGetResourceTypeInput,
-    ResourceManagerGetTotalSupplyInput, ResourceManagerLockAuthInput, ResourceManagerMintInput,
-    ResourceManagerNonFungibleExistsInput, ResourceManagerUpdateAuthInput,
-    ResourceManagerUpdateMetadataInput, ResourceManagerUpdateNonFungibleDataInput,
-    ResourceMethodAuthKey, ResourceType, SoftCount, SoftDecimal, SoftResource,
-    SoftResourceOrNonFungible, SoftResourceOrNonFungibleList, VaultCreateProofByAmountInput,
-    VaultCreateProofByIdsInput, VaultCreateProofInput, VaultGetAmountInput,
-    VaultGetNonFungibleIdsInput, VaultGetResourceAddressInput, VaultLockFeeInput, VaultPutInput,
-    VaultTakeInput, VaultTakeNonFungiblesInput, LOCKED, MUTABLE,
+    CallerOrigin, ConsumingBucketBurnInput, ConsumingProofDropInput, MintParams, Mutability,
+    NonFungibleAddress, NonFungibleId, ProofCloneInput, ProofGetAmountInput,
+    ProofGetNonFungibleIdsInput, ProofGetResourceAddressInput, ProofRule, ResourceAddress,
+    ResourceManagerCreateBucketInput, ResourceManagerCreateInput, ResourceManagerCreateVaultInput,
+    ResourceManagerGetMetadataInput, ResourceManagerGetNonFungibleInput,
+    ResourceManagerGetResourceTypeInput, ResourceManagerGetTotalSupplyInput,
+    ResourceManagerLockAuthInput, ResourceManagerMintInput, ResourceManagerNonFungibleExistsInput,
+    ResourceManagerUpdateAuthInput, ResourceManagerUpdateMetadataInput,
+    ResourceManagerUpdateNonFungibleDataInput, ResourceMethodAuthKey, ResourceType, SoftCount,
+    SoftDecimal, SoftResource, SoftResourceOrNonFungible, SoftResourceOrNonFungibleList,
+    VaultCreateProofByAmountInput, VaultCreateProofByIdsInput, VaultCreateProofInput,
+    VaultGetAmountInput, VaultGetNonFungibleIdsInput, VaultGetResourceAddressInput,
+    VaultLockFeeInput, VaultPutInput, VaultTakeInput, VaultTakeNonFungiblesInput, LOCKED, MUTABLE,
 };
 pub use scrypto::values::{ScryptoValue, ScryptoValueReplaceError};
 
@@ -59,6 +66,6 @@ pub use sbor::decode_any;
 pub use scrypto::buffer::{scrypto_decode, scrypto_encode};
 pub use scrypto::crypto::hash;
 pub use scrypto::resource::{
-    require, require_all_of, require_amount, require_any_of, require_n_of,
+    require, require_all_of, require_amount, require_any_of, require_caller_origin, require_n_of,
 };
 pub use scrypto::{access_and_or, access_rule_node, args, dec, pdec, rule};
@@ -8,10 +8,15 @@ pub const MODULE_ENV_NAME: &str = "env";
 pub const EXPORT_MEMORY: &str = "memory";
 pub const EXPORT_SCRYPTO_ALLOC: &str = "scrypto_alloc";
 pub const EXPORT_SCRYPTO_FREE: &str = "scrypto_free";
+/// Added by stack metering, returns whether the stack limit has been exceeded
+pub const EXPORT_STACK_OVERFLOWED: &str = "__stack_overflowed";
 
 /// The maximum initial memory size: `64 Pages * 64 KiB per Page = 4 MiB`
 pub const DEFAULT_MAX_INITIAL_MEMORY_SIZE_PAGES: u32 = 64;
 
+/// The maximum memory size a module can grow to: `512 Pages * 64 KiB per Page = 32 MiB`
+pub const DEFAULT_MAX_MEMORY_SIZE_PAGES: u32 = 512;
+
 /// The maximum initial table size
 pub const DEFAULT_MAX_INITIAL_TABLE_SIZE: u32 = 1024;
 
@@ -23,3 +28,9 @@ pub const DEFAULT_MAX_NUMBER_OF_GLOBALS: u32 = 512;
 
 /// The max number of functions
 pub const DEFAULT_MAX_NUMBER_OF_FUNCTIONS: u32 = 64 * 1024;
+
+/// The max number of locals declared by a function, excluding parameters
+pub const DEFAULT_MAX_NUMBER_OF_LOCALS: u32 = 256;
+
+/// The max total size of instrumented code cached by the instrumenter: 200 MiB
+pub const DEFAULT_MAX_INSTRUMENTER_CACHE_SIZE: usize = 200 * 1024 * 1024;
@@ -6,7 +6,7 @@ use wasm_instrument::gas_metering::Rules;
 
 use crate::types::*;
 
-#[derive(Debug, Clone, TypeId, Encode, Decode)]
+#[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
 pub struct InstructionCostRules {
     tier_1_cost: u32,
     tier_2_cost: u32,
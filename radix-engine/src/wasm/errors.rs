@@ -31,10 +31,14 @@ pub enum PrepareError {
     TooManyFunctions,
     /// Too many globals
     TooManyGlobals,
+    /// Too many locals in a function
+    TooManyLocals,
     /// No export section
     NoExportSection,
     /// Missing export
     MissingExport { export_name: String },
+    /// The export name is reserved for functions injected by the engine
+    ReservedExport { export_name: String },
     /// The wasm module does not have the `scrypto_alloc` export.
     NoScryptoAllocExport,
     /// The wasm module does not have the `scrypto_free` export.
@@ -65,6 +69,8 @@ pub enum InvalidMemory {
     TooManyMemories,
     /// The initial memory size is too large.
     InitialMemorySizeLimitExceeded,
+    /// The initial memory size is above the cap enforced by instrumentation.
+    MemorySizeLimitExceeded,
     /// The wasm module does not have the `memory` export.
     MemoryNotExported,
 }
@@ -86,8 +92,11 @@ pub enum WasmError {
     WasmError(String),
     FunctionNotFound,
     InvalidRadixEngineInput,
+    InvalidBatchInput,
     MissingReturnData,
     InvalidReturnData,
+    /// The stack limit enforced by stack metering has been exceeded
+    StackOverflow,
     CostingError(FeeReserveError),
 }
 
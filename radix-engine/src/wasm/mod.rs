@@ -1,6 +1,7 @@
 mod constants;
 mod cost_rules;
 mod errors;
+mod package_module_cache;
 mod prepare;
 mod traits;
 mod wasm_instrumenter;
@@ -9,13 +10,18 @@ mod wasm_validator;
 #[cfg(feature = "wasmer")]
 mod wasmer;
 mod wasmi;
+#[cfg(feature = "wasmtime")]
+mod wasmtime;
 
 #[cfg(feature = "wasmer")]
 pub use self::wasmer::*;
 pub use self::wasmi::*;
+#[cfg(feature = "wasmtime")]
+pub use self::wasmtime::*;
 pub use constants::*;
 pub use cost_rules::*;
 pub use errors::*;
+pub use package_module_cache::*;
 pub use prepare::*;
 pub use traits::*;
 pub use wasm_instrumenter::*;
@@ -27,7 +33,12 @@ pub type DefaultWasmEngine = WasmerEngine;
 #[cfg(feature = "wasmer")]
 pub type DefaultWasmInstance = WasmerInstance;
 
-#[cfg(not(feature = "wasmer"))]
+#[cfg(all(feature = "wasmtime", not(feature = "wasmer")))]
+pub type DefaultWasmEngine = WasmtimeEngine;
+#[cfg(all(feature = "wasmtime", not(feature = "wasmer")))]
+pub type DefaultWasmInstance = WasmtimeInstance;
+
+#[cfg(not(any(feature = "wasmer", feature = "wasmtime")))]
 pub type DefaultWasmEngine = WasmiEngine;
-#[cfg(not(feature = "wasmer"))]
+#[cfg(not(any(feature = "wasmer", feature = "wasmtime")))]
 pub type DefaultWasmInstance = WasmiInstance;
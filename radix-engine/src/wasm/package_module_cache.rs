@@ -0,0 +1,73 @@
+use crate::types::*;
+
+/// Remembers which compiled module each package instantiates, so that invoking a package again
+/// skips instrumenting and hashing its code.
+///
+/// Entries are keyed by package address and metering params, and map to the hash of the
+/// instrumented code, by which the WASM engine caches the compiled module. Package code never
+/// changes, but the entry of a package is dropped whenever its substate is updated.
+#[derive(Debug, Default)]
+pub struct PackageModuleCache {
+    code_hashes: HashMap<(PackageAddress, Hash), Hash>,
+}
+
+impl PackageModuleCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the hash of the instrumented code of a package, if cached.
+    pub fn get(&self, package_address: PackageAddress, metering_params_id: Hash) -> Option<Hash> {
+        self.code_hashes
+            .get(&(package_address, metering_params_id))
+            .cloned()
+    }
+
+    pub fn insert(
+        &mut self,
+        package_address: PackageAddress,
+        metering_params_id: Hash,
+        code_hash: Hash,
+    ) {
+        self.code_hashes
+            .insert((package_address, metering_params_id), code_hash);
+    }
+
+    /// Drops the entries of a package, for all metering params.
+    pub fn invalidate(&mut self, package_address: PackageAddress) {
+        self.code_hashes
+            .retain(|(address, _), _| *address != package_address);
+    }
+
+    pub fn len(&self) -> usize {
+        self.code_hashes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.code_hashes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalidate_drops_all_entries_of_package() {
+        let package_address = PackageAddress::Normal([1u8; 26]);
+        let other_package_address = PackageAddress::Normal([2u8; 26]);
+        let mut cache = PackageModuleCache::new();
+        cache.insert(package_address, hash("a"), hash("code"));
+        cache.insert(package_address, hash("b"), hash("code"));
+        cache.insert(other_package_address, hash("a"), hash("other code"));
+
+        cache.invalidate(package_address);
+
+        assert_eq!(cache.get(package_address, hash("a")), None);
+        assert_eq!(cache.get(package_address, hash("b")), None);
+        assert_eq!(
+            cache.get(other_package_address, hash("a")),
+            Some(hash("other code"))
+        );
+    }
+}
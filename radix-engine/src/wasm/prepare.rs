@@ -1,7 +1,7 @@
 use parity_wasm::elements::{
-    External, FunctionType,
+    ExportEntry, External, Func, FuncBody, FunctionType,
     Instruction::{self, *},
-    Internal, Module, Type, ValueType,
+    Instructions, Internal, MemoryType, Module, Type, ValueType,
 };
 use wasm_instrument::{
     gas_metering::{self, Rules},
@@ -279,12 +279,22 @@ impl WasmModule {
     ) -> Result<Self, PrepareError> {
         if let Some(section) = self.module.function_section() {
             if section.entries().len() > max_number_of_functions as usize {
-                return Err(PrepareError::TooManyGlobals);
+                return Err(PrepareError::TooManyFunctions);
             }
         }
 
-        // TODO: do we need to enforce limit on the number of locals and parameters?
+        Ok(self)
+    }
 
+    pub fn enforce_local_limit(self, max_number_of_locals: u32) -> Result<Self, PrepareError> {
+        if let Some(section) = self.module.code_section() {
+            for body in section.bodies() {
+                let number_of_locals: u64 = body.locals().iter().map(|l| l.count() as u64).sum();
+                if number_of_locals > max_number_of_locals as u64 {
+                    return Err(PrepareError::TooManyLocals);
+                }
+            }
+        }
         Ok(self)
     }
 
@@ -342,12 +352,103 @@ impl WasmModule {
         Ok(self)
     }
 
+    /// Caps the maximum size of the memory, so that `memory.grow` fails beyond the limit.
+    pub fn inject_memory_limit(mut self, max_memory_size_pages: u32) -> Result<Self, PrepareError> {
+        if let Some(section) = self.module.memory_section_mut() {
+            for entry in section.entries_mut() {
+                let limits = entry.limits();
+                if limits.initial() > max_memory_size_pages {
+                    return Err(PrepareError::InvalidMemory(
+                        InvalidMemory::MemorySizeLimitExceeded,
+                    ));
+                }
+                let maximum = limits
+                    .maximum()
+                    .map_or(max_memory_size_pages, |m| m.min(max_memory_size_pages));
+                *entry = MemoryType::new(limits.initial(), Some(maximum));
+            }
+        }
+
+        Ok(self)
+    }
+
     pub fn inject_stack_metering(mut self, wasm_max_stack_size: u32) -> Result<Self, PrepareError> {
+        if let Some(exports) = self.module.export_section() {
+            if exports
+                .entries()
+                .iter()
+                .any(|x| x.field() == EXPORT_STACK_OVERFLOWED)
+            {
+                return Err(PrepareError::ReservedExport {
+                    export_name: EXPORT_STACK_OVERFLOWED.to_string(),
+                });
+            }
+        }
+
+        // The stack limiter appends its stack height global to the global index space
+        let stack_height_global_index = self.module.globals_space() as u32;
+
         self.module = inject_stack_limiter(self.module, wasm_max_stack_size)
             .map_err(|_| PrepareError::RejectedByStackMetering)?;
+
+        self.export_stack_overflowed(stack_height_global_index, wasm_max_stack_size);
         Ok(self)
     }
 
+    /// Exports a function telling whether the stack limit has been exceeded.
+    ///
+    /// The stack limiter traps with `unreachable`, just like a panic does. As the stack height
+    /// only goes above the limit right before such a trap, engines can call this function to
+    /// report stack overflows deterministically.
+    fn export_stack_overflowed(
+        &mut self,
+        stack_height_global_index: u32,
+        wasm_max_stack_size: u32,
+    ) {
+        if self.module.type_section().is_none()
+            || self.module.function_section().is_none()
+            || self.module.code_section().is_none()
+            || self.module.export_section().is_none()
+        {
+            return;
+        }
+
+        let types = self.module.type_section_mut().unwrap().types_mut();
+        types.push(Type::Function(FunctionType::new(
+            vec![],
+            vec![ValueType::I32],
+        )));
+        let type_index = (types.len() - 1) as u32;
+
+        let func_index = self.module.functions_space() as u32;
+        self.module
+            .function_section_mut()
+            .unwrap()
+            .entries_mut()
+            .push(Func::new(type_index));
+        self.module
+            .code_section_mut()
+            .unwrap()
+            .bodies_mut()
+            .push(FuncBody::new(
+                vec![],
+                Instructions::new(vec![
+                    GetGlobal(stack_height_global_index),
+                    I32Const(wasm_max_stack_size as i32),
+                    I32GtU,
+                    End,
+                ]),
+            ));
+        self.module
+            .export_section_mut()
+            .unwrap()
+            .entries_mut()
+            .push(ExportEntry::new(
+                EXPORT_STACK_OVERFLOWED.to_string(),
+                Internal::Function(func_index),
+            ));
+    }
+
     pub fn ensure_instantiatable(self) -> Result<Self, PrepareError> {
         // During instantiation time, the following procedures are applied:
 
@@ -569,6 +670,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_memory_limit() {
+        assert_invalid_wasm!(
+            r#"
+            (module
+                (memory 6)
+            )
+            "#,
+            PrepareError::InvalidMemory(InvalidMemory::MemorySizeLimitExceeded),
+            |x| WasmModule::inject_memory_limit(x, 5)
+        );
+
+        for (wat, expected) in [("(module (memory 2))", 5), ("(module (memory 2 3))", 3)] {
+            let code = wat2wasm(wat).unwrap();
+            let module = WasmModule::init(&code)
+                .and_then(|m| m.inject_memory_limit(5))
+                .unwrap()
+                .module;
+            let limits = module.memory_section().unwrap().entries()[0].limits();
+            assert_eq!(Some(expected), limits.maximum());
+        }
+    }
+
     #[test]
     fn test_table() {
         assert_invalid_wasm!(
@@ -604,6 +728,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_stack_metering_exports_stack_overflowed() {
+        let code = wat2wasm(
+            r#"
+            (module
+                (func $f)
+                (export "f" (func $f))
+            )
+            "#,
+        )
+        .unwrap();
+        let (_, exports) = WasmModule::init(&code)
+            .and_then(|x| x.inject_stack_metering(100))
+            .and_then(WasmModule::to_bytes)
+            .unwrap();
+        assert!(exports.contains(&EXPORT_STACK_OVERFLOWED.to_string()));
+    }
+
+    #[test]
+    fn test_stack_metering_rejects_reserved_export() {
+        assert_invalid_wasm!(
+            r#"
+            (module
+                (func $f (result i32)
+                    (i32.const 0)
+                )
+                (export "__stack_overflowed" (func $f))
+            )
+            "#,
+            PrepareError::ReservedExport {
+                export_name: EXPORT_STACK_OVERFLOWED.to_string()
+            },
+            |x| WasmModule::inject_stack_metering(x, 100)
+        );
+    }
+
+    #[test]
+    fn test_functions() {
+        assert_invalid_wasm!(
+            r#"
+            (module
+                (func (result i32) (i32.const 1))
+                (func (result i32) (i32.const 2))
+                (func (result i32) (i32.const 3))
+            )
+            "#,
+            PrepareError::TooManyFunctions,
+            |x| WasmModule::enforce_function_limit(x, 2)
+        );
+    }
+
+    #[test]
+    fn test_locals() {
+        assert_invalid_wasm!(
+            r#"
+            (module
+                (func (result i32)
+                    (local i32 i64)
+                    (local i32)
+                    (i32.const 1)
+                )
+            )
+            "#,
+            PrepareError::TooManyLocals,
+            |x| WasmModule::enforce_local_limit(x, 2)
+        );
+    }
+
     #[test]
     fn test_blueprint_constraints() {
         let mut blueprint_abis = HashMap::new();
@@ -620,6 +812,7 @@ mod tests {
                     },
                     output: sbor::Type::Unit,
                     export_name: "Test_f".to_string(),
+                    roles: vec![],
                 }],
             },
         );
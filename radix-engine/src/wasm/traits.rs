@@ -1,5 +1,6 @@
 use crate::model::InvokeError;
 use sbor::rust::boxed::Box;
+use scrypto::crypto::Hash;
 use scrypto::values::ScryptoValue;
 
 use crate::wasm::errors::*;
@@ -26,10 +27,23 @@ pub trait WasmInstance {
         args: &ScryptoValue,
         runtime: &mut Box<dyn WasmRuntime + 'r>,
     ) -> Result<ScryptoValue, InvokeError<WasmError>>;
+
+    /// Returns the peak number of memory pages used by this instance.
+    ///
+    /// WASM memory never shrinks, so this is the current memory size.
+    fn peak_memory_pages(&self) -> u32;
 }
 
 /// A Scrypto WASM engine validates, instruments and runs Scrypto modules.
 pub trait WasmEngine<I: WasmInstance> {
     /// Instantiate a Scrypto module.
+    ///
+    /// Implementations should cache compiled modules by code hash, so that repeated
+    /// invocations of a package, within and across transactions, only pay for instantiation.
+    /// As the key is derived from the code, a cached module never outlives the code it was
+    /// compiled from. Instances themselves are not reused, as their memory is not reset.
     fn instantiate(&mut self, code: &[u8]) -> I;
+
+    /// Instantiate a Scrypto module compiled earlier, by the hash of its code, if it's cached.
+    fn instantiate_cached(&mut self, code_hash: &Hash) -> Option<I>;
 }
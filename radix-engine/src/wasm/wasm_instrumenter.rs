@@ -1,31 +1,124 @@
 use crate::types::*;
-use crate::wasm::{WasmMeteringParams, WasmModule};
+use crate::wasm::{WasmMeteringParams, WasmModule, DEFAULT_MAX_INSTRUMENTER_CACHE_SIZE};
 
+/// Instruments WASM code with metering, caching the result by code hash and metering params.
+///
+/// The cache is bounded by the total size of the instrumented code it holds, evicting the
+/// least recently used entries first.
 pub struct WasmInstrumenter {
-    cache: HashMap<(Hash, Hash), Vec<u8>>,
+    cache: HashMap<(Hash, Hash), (Vec<u8>, u64)>,
+    /// Cache keys, indexed by the tick they were last used at
+    lru: BTreeMap<u64, (Hash, Hash)>,
+    tick: u64,
+    cache_size: usize,
+    max_cache_size: usize,
 }
 
 impl WasmInstrumenter {
     pub fn new() -> Self {
+        Self::with_max_cache_size(DEFAULT_MAX_INSTRUMENTER_CACHE_SIZE)
+    }
+
+    pub fn with_max_cache_size(max_cache_size: usize) -> Self {
         Self {
-            // TODO: introduce a proper cache that supports replacement policy
             cache: HashMap::new(),
+            lru: BTreeMap::new(),
+            tick: 0,
+            cache_size: 0,
+            max_cache_size,
         }
     }
 
     pub fn instrument(&mut self, code: &[u8], wasm_metering_params: &WasmMeteringParams) -> &[u8] {
-        let code_hash = hash(code);
-        self.cache
-            .entry((code_hash, wasm_metering_params.identifier()))
-            .or_insert_with(|| {
-                WasmModule::init(code)
-                    .and_then(|m| {
-                        m.inject_instruction_metering(wasm_metering_params.instruction_cost_rules())
-                    })
-                    .and_then(|m| m.inject_stack_metering(wasm_metering_params.max_stack_size()))
-                    .and_then(|m| m.to_bytes())
-                    .expect("Failed to instrument WASM module")
-                    .0
-            })
+        let key = (hash(code), wasm_metering_params.identifier());
+        self.tick += 1;
+
+        if let Some((_, last_used)) = self.cache.get_mut(&key) {
+            self.lru.remove(last_used);
+            *last_used = self.tick;
+        } else {
+            let instrumented_code = WasmModule::init(code)
+                .and_then(|m| {
+                    m.inject_instruction_metering(wasm_metering_params.instruction_cost_rules())
+                })
+                .and_then(|m| m.inject_stack_metering(wasm_metering_params.max_stack_size()))
+                .and_then(|m| m.inject_memory_limit(wasm_metering_params.max_memory_size_pages()))
+                .and_then(|m| m.to_bytes())
+                .expect("Failed to instrument WASM module")
+                .0;
+            self.cache_size += instrumented_code.len();
+            self.cache.insert(key, (instrumented_code, self.tick));
+            self.evict();
+        }
+        self.lru.insert(self.tick, key);
+
+        &self
+            .cache
+            .get(&key)
+            .expect("Instrumented code not cached")
+            .0
+    }
+
+    /// Evicts least recently used entries until the cache fits, always keeping the newest one.
+    fn evict(&mut self) {
+        while self.cache_size > self.max_cache_size {
+            let oldest = match self.lru.keys().next() {
+                Some(tick) => *tick,
+                None => break,
+            };
+            let key = self.lru.remove(&oldest).expect("LRU entry not found");
+            let (code, _) = self.cache.remove(&key).expect("Cache entry not found");
+            self.cache_size -= code.len();
+        }
+    }
+
+    pub fn cache_size(&self) -> usize {
+        self.cache_size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wabt::wat2wasm;
+
+    fn code(n: u32) -> Vec<u8> {
+        wat2wasm(format!(
+            r#"(module (func (export "f") (result i32) i32.const {}))"#,
+            n
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_least_recently_used_code_is_evicted() {
+        let params = WasmMeteringParams::standard();
+        let mut instrumenter = WasmInstrumenter::new();
+        let size = instrumenter.instrument(&code(1), &params).len();
+        let mut instrumenter = WasmInstrumenter::with_max_cache_size(2 * size);
+
+        instrumenter.instrument(&code(1), &params);
+        instrumenter.instrument(&code(2), &params);
+        instrumenter.instrument(&code(1), &params);
+        instrumenter.instrument(&code(3), &params);
+
+        assert_eq!(2 * size, instrumenter.cache_size());
+        assert!(instrumenter
+            .cache
+            .contains_key(&(hash(code(1)), params.identifier())));
+        assert!(!instrumenter
+            .cache
+            .contains_key(&(hash(code(2)), params.identifier())));
+    }
+
+    #[test]
+    fn test_new_params_version_invalidates_cached_code() {
+        let params = WasmMeteringParams::standard();
+        let mut instrumenter = WasmInstrumenter::new();
+        let size = instrumenter.instrument(&code(1), &params).len();
+
+        instrumenter.instrument(&code(1), &params.clone().with_version(2));
+
+        assert_eq!(2 * size, instrumenter.cache_size());
     }
 }
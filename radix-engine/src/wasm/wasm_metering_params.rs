@@ -1,27 +1,57 @@
-use super::InstructionCostRules;
+use super::{InstructionCostRules, DEFAULT_MAX_MEMORY_SIZE_PAGES};
 use crate::types::*;
 
-#[derive(Debug, Clone, TypeId, Encode, Decode)]
+#[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
 pub struct WasmMeteringParams {
+    version: u32,
     instruction_cost_rules: InstructionCostRules,
     max_stack_size: u32,
+    max_memory_size_pages: u32,
+}
+
+impl Default for WasmMeteringParams {
+    fn default() -> Self {
+        WasmMeteringParams::standard()
+    }
 }
 
 impl WasmMeteringParams {
     pub fn new(instruction_cost_rules: InstructionCostRules, max_stack_size: u32) -> Self {
         Self {
+            version: 1,
             instruction_cost_rules,
             max_stack_size,
+            max_memory_size_pages: DEFAULT_MAX_MEMORY_SIZE_PAGES,
         }
     }
 
+    pub fn standard() -> Self {
+        Self::new(InstructionCostRules::tiered(1, 5, 10, 5000), 512)
+    }
+
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    pub fn with_max_memory_size_pages(mut self, max_memory_size_pages: u32) -> Self {
+        self.max_memory_size_pages = max_memory_size_pages;
+        self
+    }
+
     /// Wasm fee table is statically applied to the wasm code.
     /// This identifier helps decide whether or not re-instrumentation is required.
+    ///
+    /// The version is part of the identifier, so bumping it invalidates any cached code.
     pub fn identifier(&self) -> Hash {
         let encoded = scrypto_encode(self);
         hash(encoded)
     }
 
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
     pub fn instruction_cost_rules(&self) -> &InstructionCostRules {
         &self.instruction_cost_rules
     }
@@ -29,4 +59,8 @@ impl WasmMeteringParams {
     pub fn max_stack_size(&self) -> u32 {
         self.max_stack_size
     }
+
+    pub fn max_memory_size_pages(&self) -> u32 {
+        self.max_memory_size_pages
+    }
 }
@@ -7,6 +7,7 @@ pub struct WasmValidator {
     pub max_number_of_br_table_targets: u32,
     pub max_number_of_functions: u32,
     pub max_number_of_globals: u32,
+    pub max_number_of_locals: u32,
 }
 
 impl Default for WasmValidator {
@@ -17,6 +18,7 @@ impl Default for WasmValidator {
             max_number_of_br_table_targets: DEFAULT_MAX_NUMBER_OF_BR_TABLE_TARGETS,
             max_number_of_functions: DEFAULT_MAX_NUMBER_OF_FUNCTIONS,
             max_number_of_globals: DEFAULT_MAX_NUMBER_OF_GLOBALS,
+            max_number_of_locals: DEFAULT_MAX_NUMBER_OF_LOCALS,
         }
     }
 }
@@ -42,9 +44,11 @@ impl WasmValidator {
             .enforce_br_table_limit(self.max_number_of_br_table_targets)?
             .enforce_function_limit(self.max_number_of_functions)?
             .enforce_global_limit(self.max_number_of_globals)?
+            .enforce_local_limit(self.max_number_of_locals)?
             .enforce_export_constraints(blueprints)?
             .inject_instruction_metering(mocked_wasm_metering_params.instruction_cost_rules())?
             .inject_stack_metering(mocked_wasm_metering_params.max_stack_size())?
+            .inject_memory_limit(mocked_wasm_metering_params.max_memory_size_pages())?
             .ensure_instantiatable()?
             .ensure_compilable()?
             .to_bytes()?;
@@ -1,9 +1,10 @@
 use std::sync::{Arc, Mutex};
 
+use crate::constants::DEFAULT_DECODE_LIMITS;
 use crate::model::InvokeError;
 use wasmer::{
-    imports, Function, HostEnvInitError, Instance, LazyInit, Module, RuntimeError, Store,
-    Universal, Val, WasmerEnv,
+    imports, CompilerConfig, Features, Function, HostEnvInitError, Instance, LazyInit, Module,
+    RuntimeError, Store, Universal, Val, WasmerEnv,
 };
 use wasmer_compiler_singlepass::Singlepass;
 
@@ -96,7 +97,8 @@ pub fn read_value(instance: &Instance, ptr: usize) -> Result<ScryptoValue, WasmE
                 temp.set_len(n);
             }
 
-            return ScryptoValue::from_slice(&temp).map_err(WasmError::InvalidScryptoValue);
+            return ScryptoValue::from_slice_with_limits(&temp, &DEFAULT_DECODE_LIMITS)
+                .map_err(WasmError::InvalidScryptoValue);
         }
     }
 
@@ -214,16 +216,42 @@ impl WasmInstance for WasmerInstance {
                     .ok_or(InvokeError::Error(WasmError::InvalidReturnData))?;
                 read_value(&self.instance, ptr as usize).map_err(InvokeError::Error)
             }
+            Err(_) if self.stack_overflowed() => Err(InvokeError::Error(WasmError::StackOverflow)),
             Err(e) => Err(e.into()),
         }
     }
+
+    fn peak_memory_pages(&self) -> u32 {
+        self.instance
+            .exports
+            .get_memory(EXPORT_MEMORY)
+            .map(|memory| memory.size().0)
+            .unwrap_or(0)
+    }
+}
+
+impl WasmerInstance {
+    fn stack_overflowed(&self) -> bool {
+        matches!(
+            self.instance
+                .exports
+                .get_function(EXPORT_STACK_OVERFLOWED)
+                .map(|f| f.call(&[])),
+            Ok(Ok(result)) if matches!(result.as_ref().get(0), Some(Val::I32(1)))
+        )
+    }
 }
 
 impl WasmerEngine {
     pub fn new() -> Self {
-        let compiler = Singlepass::new();
+        // Configured for deterministic execution: floats are rejected when a package is
+        // published, but NaNs are canonicalized should any get through
+        let mut compiler = Singlepass::new();
+        compiler.canonicalize_nans(true);
+        let mut features = Features::new();
+        features.threads(false).simd(false);
         Self {
-            store: Store::new(&Universal::new(compiler).engine()),
+            store: Store::new(&Universal::new(compiler).features(features).engine()),
             modules: HashMap::new(),
         }
     }
@@ -239,4 +267,10 @@ impl WasmEngine<WasmerInstance> for WasmerEngine {
             })
             .instantiate()
     }
+
+    fn instantiate_cached(&mut self, code_hash: &Hash) -> Option<WasmerInstance> {
+        self.modules
+            .get(code_hash)
+            .map(|module| module.instantiate())
+    }
 }
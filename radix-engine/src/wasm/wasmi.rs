@@ -1,5 +1,6 @@
 use wasmi::*;
 
+use crate::constants::DEFAULT_DECODE_LIMITS;
 use crate::model::InvokeError;
 use crate::types::{format, hash, Box, Hash, HashMap, ScryptoValue};
 use crate::wasm::constants::*;
@@ -145,7 +146,8 @@ impl<'a, 'b, 'r> WasmiExternals<'a, 'b, 'r> {
             return Err(WasmError::MemoryAccessError);
         }
 
-        ScryptoValue::from_slice(&buffer[start..end]).map_err(WasmError::InvalidScryptoValue)
+        ScryptoValue::from_slice_with_limits(&buffer[start..end], &DEFAULT_DECODE_LIMITS)
+            .map_err(WasmError::InvalidScryptoValue)
     }
 }
 
@@ -196,6 +198,9 @@ impl WasmInstance for WasmiInstance {
 
         let rtn = result
             .map_err(|e| {
+                if self.stack_overflowed() {
+                    return InvokeError::Error(WasmError::StackOverflow);
+                }
                 let err: InvokeError<WasmError> = e.into();
                 err
             })?
@@ -207,6 +212,20 @@ impl WasmInstance for WasmiInstance {
             _ => Err(InvokeError::Error(WasmError::InvalidReturnData)),
         }
     }
+
+    fn peak_memory_pages(&self) -> u32 {
+        self.memory_ref.current_size().0 as u32
+    }
+}
+
+impl WasmiInstance {
+    fn stack_overflowed(&self) -> bool {
+        matches!(
+            self.module_ref
+                .invoke_export(EXPORT_STACK_OVERFLOWED, &[], &mut NopExternals),
+            Ok(Some(RuntimeValue::I32(1)))
+        )
+    }
 }
 
 impl WasmiEngine {
@@ -227,4 +246,10 @@ impl WasmEngine<WasmiInstance> for WasmiEngine {
             })
             .instantiate()
     }
+
+    fn instantiate_cached(&mut self, code_hash: &Hash) -> Option<WasmiInstance> {
+        self.modules
+            .get(code_hash)
+            .map(|module| module.instantiate())
+    }
 }
@@ -0,0 +1,383 @@
+use wasmtime::{
+    AsContextMut, Caller, Config, Engine, Func, Instance, Linker, Memory, Module, Store, Trap,
+};
+
+use crate::constants::DEFAULT_DECODE_LIMITS;
+use crate::model::InvokeError;
+use crate::types::*;
+use crate::wasm::constants::*;
+use crate::wasm::errors::*;
+use crate::wasm::traits::*;
+
+pub struct WasmtimeModule {
+    module: Module,
+}
+
+pub struct WasmtimeInstance {
+    store: Store<WasmtimeInstanceEnv>,
+    instance: Instance,
+    memory: Memory,
+}
+
+pub struct WasmtimeInstanceEnv {
+    // Runtime pointer, updated every time the `invoke_export` is called.
+    runtime_ptr: usize,
+    // Wasmtime traps can't be downcast, so the error raised by the runtime is kept here
+    // and passed through once the trap reaches `invoke_export`.
+    error: Option<InvokeError<WasmError>>,
+}
+
+pub struct WasmtimeEngine {
+    engine: Engine,
+    modules: HashMap<Hash, WasmtimeModule>,
+}
+
+pub fn send_value(
+    mut store: impl AsContextMut<Data = WasmtimeInstanceEnv>,
+    memory: Memory,
+    alloc: Func,
+    value: &ScryptoValue,
+) -> Result<i32, InvokeError<WasmError>> {
+    let ptr = alloc
+        .typed::<i32, i32, _>(&store)
+        .map_err(|_| InvokeError::Error(WasmError::MemoryAllocError))?
+        .call(&mut store, value.raw.len() as i32)
+        .map_err(|e| pass_through(&mut store, e))?;
+
+    let offset = (ptr as usize)
+        .checked_add(4)
+        .ok_or(InvokeError::Error(WasmError::MemoryAllocError))?;
+    memory
+        .write(&mut store, offset, &value.raw)
+        .map_err(|_| InvokeError::Error(WasmError::MemoryAllocError))?;
+
+    Ok(ptr)
+}
+
+pub fn read_value(memory: &[u8], ptr: usize) -> Result<ScryptoValue, WasmError> {
+    let start = ptr.checked_add(4).ok_or(WasmError::MemoryAccessError)?;
+    if start > memory.len() {
+        return Err(WasmError::MemoryAccessError);
+    }
+    let mut temp = [0u8; 4];
+    temp.copy_from_slice(&memory[ptr..start]);
+    let n = u32::from_le_bytes(temp) as usize;
+
+    let end = start.checked_add(n).ok_or(WasmError::MemoryAccessError)?;
+    if end > memory.len() {
+        return Err(WasmError::MemoryAccessError);
+    }
+
+    ScryptoValue::from_slice_with_limits(&memory[start..end], &DEFAULT_DECODE_LIMITS)
+        .map_err(WasmError::InvalidScryptoValue)
+}
+
+/// Takes the error raised by the runtime, if any, falling back to the trap itself.
+fn pass_through(
+    mut store: impl AsContextMut<Data = WasmtimeInstanceEnv>,
+    trap: Trap,
+) -> InvokeError<WasmError> {
+    store
+        .as_context_mut()
+        .data_mut()
+        .error
+        .take()
+        .unwrap_or_else(|| InvokeError::Error(WasmError::WasmError(format!("{:?}", trap))))
+}
+
+fn into_trap(caller: &mut Caller<'_, WasmtimeInstanceEnv>, error: InvokeError<WasmError>) -> Trap {
+    let trap = Trap::new(error.to_string());
+    caller.data_mut().error = Some(error);
+    trap
+}
+
+fn runtime<'a>(caller: &Caller<'_, WasmtimeInstanceEnv>) -> &'a mut Box<dyn WasmRuntime> {
+    unsafe { &mut *(caller.data().runtime_ptr as *mut _) }
+}
+
+impl WasmtimeModule {
+    fn instantiate(&self, engine: &Engine) -> WasmtimeInstance {
+        // native functions
+        fn radix_engine(
+            caller: &mut Caller<'_, WasmtimeInstanceEnv>,
+            input_ptr: i32,
+        ) -> Result<i32, InvokeError<WasmError>> {
+            let memory = caller
+                .get_export(EXPORT_MEMORY)
+                .and_then(|e| e.into_memory())
+                .ok_or(InvokeError::Error(WasmError::MemoryAccessError))?;
+            let input =
+                read_value(memory.data(&caller), input_ptr as usize).map_err(InvokeError::Error)?;
+
+            let output = runtime(caller).main(input)?;
+
+            let alloc = caller
+                .get_export(EXPORT_SCRYPTO_ALLOC)
+                .and_then(|e| e.into_func())
+                .ok_or(InvokeError::Error(WasmError::MemoryAllocError))?;
+            send_value(caller, memory, alloc, &output)
+        }
+
+        fn consume_cost_units(
+            caller: &mut Caller<'_, WasmtimeInstanceEnv>,
+            cost_unit: i32,
+        ) -> Result<(), InvokeError<WasmError>> {
+            runtime(caller).consume_cost_units(cost_unit as u32)
+        }
+
+        // imports
+        let mut linker = Linker::new(engine);
+        linker
+            .func_wrap(
+                MODULE_ENV_NAME,
+                RADIX_ENGINE_FUNCTION_NAME,
+                |mut caller: Caller<'_, WasmtimeInstanceEnv>, input_ptr: i32| {
+                    radix_engine(&mut caller, input_ptr).map_err(|e| into_trap(&mut caller, e))
+                },
+            )
+            .expect("Failed to define radix_engine function");
+        linker
+            .func_wrap(
+                MODULE_ENV_NAME,
+                CONSUME_COST_UNITS_FUNCTION_NAME,
+                |mut caller: Caller<'_, WasmtimeInstanceEnv>, cost_unit: i32| {
+                    consume_cost_units(&mut caller, cost_unit)
+                        .map_err(|e| into_trap(&mut caller, e))
+                },
+            )
+            .expect("Failed to define consume_cost_units function");
+
+        // instantiate
+        let mut store = Store::new(
+            engine,
+            WasmtimeInstanceEnv {
+                runtime_ptr: 0,
+                error: None,
+            },
+        );
+        let instance = linker
+            .instantiate(&mut store, &self.module)
+            .expect("Failed to instantiate WASM module");
+        let memory = instance
+            .get_memory(&mut store, EXPORT_MEMORY)
+            .expect("Failed to find memory export");
+
+        WasmtimeInstance {
+            store,
+            instance,
+            memory,
+        }
+    }
+}
+
+impl WasmInstance for WasmtimeInstance {
+    fn invoke_export<'r>(
+        &mut self,
+        func_name: &str,
+        args: &ScryptoValue,
+        runtime: &mut Box<dyn WasmRuntime + 'r>,
+    ) -> Result<ScryptoValue, InvokeError<WasmError>> {
+        // set up runtime pointer
+        self.store.data_mut().runtime_ptr = runtime as *mut _ as usize;
+
+        let alloc = self
+            .instance
+            .get_func(&mut self.store, EXPORT_SCRYPTO_ALLOC)
+            .ok_or(InvokeError::Error(WasmError::MemoryAllocError))?;
+        let pointer = send_value(&mut self.store, self.memory, alloc, args)?;
+
+        let ptr = self
+            .instance
+            .get_func(&mut self.store, func_name)
+            .ok_or(InvokeError::Error(WasmError::FunctionNotFound))?
+            .typed::<i32, i32, _>(&self.store)
+            .map_err(|_| InvokeError::Error(WasmError::InvalidReturnData))?
+            .call(&mut self.store, pointer)
+            .map_err(|e| {
+                if self.stack_overflowed() {
+                    return InvokeError::Error(WasmError::StackOverflow);
+                }
+                pass_through(&mut self.store, e)
+            })?;
+
+        read_value(self.memory.data(&self.store), ptr as usize).map_err(InvokeError::Error)
+    }
+
+    fn peak_memory_pages(&self) -> u32 {
+        self.memory.size(&self.store) as u32
+    }
+}
+
+impl WasmtimeInstance {
+    fn stack_overflowed(&mut self) -> bool {
+        matches!(
+            self.instance
+                .get_typed_func::<(), i32, _>(&mut self.store, EXPORT_STACK_OVERFLOWED)
+                .map(|f| f.call(&mut self.store, ())),
+            Ok(Ok(1))
+        )
+    }
+}
+
+impl WasmtimeEngine {
+    pub fn new() -> Self {
+        // Configured for deterministic execution: floats are rejected when a package is
+        // published, but NaNs are canonicalized should any get through
+        let mut config = Config::new();
+        config
+            .cranelift_nan_canonicalization(true)
+            .wasm_threads(false)
+            .wasm_simd(false);
+        Self {
+            engine: Engine::new(&config).expect("Failed to create WASM engine"),
+            modules: HashMap::new(),
+        }
+    }
+}
+
+impl WasmEngine<WasmtimeInstance> for WasmtimeEngine {
+    fn instantiate(&mut self, code: &[u8]) -> WasmtimeInstance {
+        let code_hash = hash(code);
+        let engine = &self.engine;
+        self.modules
+            .entry(code_hash)
+            .or_insert_with(|| WasmtimeModule {
+                module: Module::new(engine, code).expect("Failed to parse WASM module"),
+            })
+            .instantiate(engine)
+    }
+
+    fn instantiate_cached(&mut self, code_hash: &Hash) -> Option<WasmtimeInstance> {
+        self.modules
+            .get(code_hash)
+            .map(|module| module.instantiate(&self.engine))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wasm::{WasmInstrumenter, WasmMeteringParams, WasmModule, WasmiEngine};
+    use wabt::wat2wasm;
+
+    const MODULE: &str = r#"
+        (module
+            (import "env" "radix_engine" (func $radix_engine (param i32) (result i32)))
+            (memory $0 1)
+            (global $heap (mut i32) (i32.const 1024))
+            (func $scrypto_alloc (param $n i32) (result i32)
+                (local $ptr i32)
+                (local.set $ptr (global.get $heap))
+                (i32.store (local.get $ptr) (local.get $n))
+                (global.set $heap (i32.add (local.get $ptr) (i32.add (local.get $n) (i32.const 4))))
+                (local.get $ptr)
+            )
+            (func $Test_call (param $args i32) (result i32)
+                (call $radix_engine (local.get $args))
+            )
+            (func $Test_recurse (param $args i32) (result i32)
+                (call $Test_recurse (local.get $args))
+            )
+            (export "memory" (memory $0))
+            (export "scrypto_alloc" (func $scrypto_alloc))
+            (export "Test_call" (func $Test_call))
+            (export "Test_recurse" (func $Test_recurse))
+        )
+    "#;
+
+    /// Echoes the input back, counting the cost units consumed.
+    struct EchoWasmRuntime<'a> {
+        consumed: &'a mut u32,
+    }
+
+    impl<'a> WasmRuntime for EchoWasmRuntime<'a> {
+        fn main(&mut self, input: ScryptoValue) -> Result<ScryptoValue, InvokeError<WasmError>> {
+            Ok(input)
+        }
+
+        fn consume_cost_units(&mut self, n: u32) -> Result<(), InvokeError<WasmError>> {
+            *self.consumed += n;
+            Ok(())
+        }
+    }
+
+    fn invoke<W: WasmEngine<I>, I: WasmInstance>(
+        mut engine: W,
+        code: &[u8],
+        func_name: &str,
+        args: &ScryptoValue,
+    ) -> Result<(Vec<u8>, u32), InvokeError<WasmError>> {
+        let mut consumed = 0;
+        let mut runtime: Box<dyn WasmRuntime> = Box::new(EchoWasmRuntime {
+            consumed: &mut consumed,
+        });
+        let output = engine
+            .instantiate(code)
+            .invoke_export(func_name, args, &mut runtime)?;
+        drop(runtime);
+        Ok((output.raw, consumed))
+    }
+
+    fn instrumented_code() -> Vec<u8> {
+        let code = wat2wasm(MODULE).unwrap();
+        WasmInstrumenter::new()
+            .instrument(&code, &WasmMeteringParams::standard())
+            .to_vec()
+    }
+
+    #[test]
+    fn test_wasmtime_conforms_to_wasmi() {
+        let code = instrumented_code();
+        let args = ScryptoValue::from_typed(&("hello".to_string(), 5u32));
+
+        let wasmi_result = invoke(WasmiEngine::new(), &code, "Test_call", &args).unwrap();
+        let wasmtime_result = invoke(WasmtimeEngine::new(), &code, "Test_call", &args).unwrap();
+
+        assert_eq!(wasmi_result, wasmtime_result);
+        assert_eq!(args.raw, wasmtime_result.0);
+    }
+
+    #[test]
+    fn test_stack_overflow_is_reported_by_both_engines() {
+        let code = instrumented_code();
+        let args = ScryptoValue::unit();
+
+        assert!(matches!(
+            invoke(WasmiEngine::new(), &code, "Test_recurse", &args),
+            Err(InvokeError::Error(WasmError::StackOverflow))
+        ));
+        assert!(matches!(
+            invoke(WasmtimeEngine::new(), &code, "Test_recurse", &args),
+            Err(InvokeError::Error(WasmError::StackOverflow))
+        ));
+    }
+
+    #[test]
+    fn test_wasmtime_conforms_to_wasmi_on_compiled_packages() {
+        let packages: [&[u8]; 2] = [
+            include_bytes!("../../../assets/account.wasm"),
+            include_bytes!("../../../assets/sys_faucet.wasm"),
+        ];
+        for package in packages {
+            let code = WasmInstrumenter::new()
+                .instrument(package, &WasmMeteringParams::standard())
+                .to_vec();
+            let abi_exports: Vec<String> = WasmModule::init(package)
+                .and_then(WasmModule::to_bytes)
+                .unwrap()
+                .1
+                .into_iter()
+                .filter(|export| export.ends_with("_abi"))
+                .collect();
+            assert!(!abi_exports.is_empty());
+
+            for export in abi_exports {
+                let args = ScryptoValue::unit();
+                let wasmi_result = invoke(WasmiEngine::new(), &code, &export, &args).unwrap();
+                let wasmtime_result = invoke(WasmtimeEngine::new(), &code, &export, &args).unwrap();
+
+                assert_eq!(wasmi_result, wasmtime_result, "{}", export);
+            }
+        }
+    }
+}
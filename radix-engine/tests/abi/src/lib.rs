@@ -44,6 +44,7 @@ pub extern "C" fn AbiComponent2_abi(_input: *mut u8) -> *mut u8 {
                 input: Type::Unit,
                 output: Type::U8,
                 export_name: "AbiComponent2_main".to_string(),
+                roles: vec![],
             },
             Fn {
                 ident: "unit".to_string(),
@@ -51,6 +52,7 @@ pub extern "C" fn AbiComponent2_abi(_input: *mut u8) -> *mut u8 {
                 input: Type::Unit,
                 output: Type::Unit,
                 export_name: "AbiComponent2_main".to_string(),
+                roles: vec![],
             },
             Fn {
                 ident: "bool".to_string(),
@@ -58,6 +60,7 @@ pub extern "C" fn AbiComponent2_abi(_input: *mut u8) -> *mut u8 {
                 input: Type::Bool,
                 output: Type::Unit,
                 export_name: "AbiComponent2_main".to_string(),
+                roles: vec![],
             },
             Fn {
                 ident: "i8".to_string(),
@@ -65,6 +68,7 @@ pub extern "C" fn AbiComponent2_abi(_input: *mut u8) -> *mut u8 {
                 input: Type::I8,
                 output: Type::Unit,
                 export_name: "AbiComponent2_main".to_string(),
+                roles: vec![],
             },
             Fn {
                 ident: "i16".to_string(),
@@ -72,6 +76,7 @@ pub extern "C" fn AbiComponent2_abi(_input: *mut u8) -> *mut u8 {
                 input: Type::I16,
                 output: Type::Unit,
                 export_name: "AbiComponent2_main".to_string(),
+                roles: vec![],
             },
             Fn {
                 ident: "i32".to_string(),
@@ -79,6 +84,7 @@ pub extern "C" fn AbiComponent2_abi(_input: *mut u8) -> *mut u8 {
                 input: Type::I32,
                 output: Type::Unit,
                 export_name: "AbiComponent2_main".to_string(),
+                roles: vec![],
             },
             Fn {
                 ident: "i64".to_string(),
@@ -86,6 +92,7 @@ pub extern "C" fn AbiComponent2_abi(_input: *mut u8) -> *mut u8 {
                 input: Type::I64,
                 output: Type::Unit,
                 export_name: "AbiComponent2_main".to_string(),
+                roles: vec![],
             },
             Fn {
                 ident: "i128".to_string(),
@@ -93,6 +100,7 @@ pub extern "C" fn AbiComponent2_abi(_input: *mut u8) -> *mut u8 {
                 input: Type::I128,
                 output: Type::Unit,
                 export_name: "AbiComponent2_main".to_string(),
+                roles: vec![],
             },
             Fn {
                 ident: "u8".to_string(),
@@ -100,6 +108,7 @@ pub extern "C" fn AbiComponent2_abi(_input: *mut u8) -> *mut u8 {
                 input: Type::U8,
                 output: Type::Unit,
                 export_name: "AbiComponent2_main".to_string(),
+                roles: vec![],
             },
             Fn {
                 ident: "u16".to_string(),
@@ -107,6 +116,7 @@ pub extern "C" fn AbiComponent2_abi(_input: *mut u8) -> *mut u8 {
                 input: Type::U16,
                 output: Type::Unit,
                 export_name: "AbiComponent2_main".to_string(),
+                roles: vec![],
             },
             Fn {
                 ident: "u32".to_string(),
@@ -114,6 +124,7 @@ pub extern "C" fn AbiComponent2_abi(_input: *mut u8) -> *mut u8 {
                 input: Type::U32,
                 output: Type::Unit,
                 export_name: "AbiComponent2_main".to_string(),
+                roles: vec![],
             },
             Fn {
                 ident: "u64".to_string(),
@@ -121,6 +132,7 @@ pub extern "C" fn AbiComponent2_abi(_input: *mut u8) -> *mut u8 {
                 input: Type::U64,
                 output: Type::Unit,
                 export_name: "AbiComponent2_main".to_string(),
+                roles: vec![],
             },
             Fn {
                 ident: "u128".to_string(),
@@ -128,6 +140,7 @@ pub extern "C" fn AbiComponent2_abi(_input: *mut u8) -> *mut u8 {
                 input: Type::U128,
                 output: Type::Unit,
                 export_name: "AbiComponent2_main".to_string(),
+                roles: vec![],
             },
             Fn {
                 ident: "result".to_string(),
@@ -138,6 +151,7 @@ pub extern "C" fn AbiComponent2_abi(_input: *mut u8) -> *mut u8 {
                 },
                 output: Type::Unit,
                 export_name: "AbiComponent2_main".to_string(),
+                roles: vec![],
             },
             Fn {
                 ident: "tree_map".to_string(),
@@ -148,6 +162,7 @@ pub extern "C" fn AbiComponent2_abi(_input: *mut u8) -> *mut u8 {
                 },
                 output: Type::Unit,
                 export_name: "AbiComponent2_main".to_string(),
+                roles: vec![],
             },
             Fn {
                 ident: "hash_set".to_string(),
@@ -157,6 +172,7 @@ pub extern "C" fn AbiComponent2_abi(_input: *mut u8) -> *mut u8 {
                 },
                 output: Type::Unit,
                 export_name: "AbiComponent2_main".to_string(),
+                roles: vec![],
             },
         ],
     };
@@ -0,0 +1,366 @@
+use radix_engine::engine::{ApplicationError, RuntimeError};
+use radix_engine::ledger::{
+    ReadableSubstateStore, TypedInMemorySubstateStore, WriteableSubstateStore,
+};
+use radix_engine::model::AccessControllerError;
+use radix_engine::transaction::TransactionReceipt;
+use radix_engine::types::*;
+use scrypto_unit::*;
+use transaction::builder::ManifestBuilder;
+use transaction::model::Instruction;
+
+struct AccessControllerTest {
+    access_controller: ComponentAddress,
+    primary: PublicKey,
+    recovery: PublicKey,
+    confirmation: PublicKey,
+}
+
+fn rule_set(primary: &PublicKey, recovery: &PublicKey, confirmation: &PublicKey) -> RuleSet {
+    RuleSet {
+        primary_role: rule!(require(NonFungibleAddress::from_public_key(primary))),
+        recovery_role: rule!(require(NonFungibleAddress::from_public_key(recovery))),
+        confirmation_role: rule!(require(NonFungibleAddress::from_public_key(confirmation))),
+    }
+}
+
+fn create_access_controller<'s, S: ReadableSubstateStore + WriteableSubstateStore>(
+    test_runner: &mut TestRunner<'s, S>,
+    timed_recovery_delay_in_epochs: Option<u64>,
+) -> AccessControllerTest {
+    let (pk, _, account) = test_runner.new_account();
+    let badge = test_runner.create_non_fungible_resource(account);
+    let primary: PublicKey = test_runner.new_key_pair().0.into();
+    let recovery: PublicKey = test_runner.new_key_pair().0.into();
+    let confirmation: PublicKey = test_runner.new_key_pair().0.into();
+
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .withdraw_from_account(badge, account)
+        .take_from_worktop(badge, |builder, bucket_id| {
+            builder
+                .add_instruction(Instruction::CallFunction {
+                    fn_identifier: FnIdentifier::Native(NativeFnIdentifier::AccessController(
+                        AccessControllerFnIdentifier::Create,
+                    )),
+                    args: scrypto_encode(&AccessControllerCreateInput {
+                        controlled_asset: scrypto::resource::Bucket(bucket_id),
+                        rule_set: rule_set(&primary, &recovery, &confirmation),
+                        timed_recovery_delay_in_epochs,
+                    }),
+                })
+                .0
+        })
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![pk.into()]);
+    receipt.expect_commit_success();
+
+    AccessControllerTest {
+        access_controller: receipt.new_component_addresses()[0],
+        primary,
+        recovery,
+        confirmation,
+    }
+}
+
+fn call_access_controller<'s, S: ReadableSubstateStore + WriteableSubstateStore>(
+    test_runner: &mut TestRunner<'s, S>,
+    access_controller: ComponentAddress,
+    access_controller_fn: AccessControllerFnIdentifier,
+    args: Vec<u8>,
+    signer: &PublicKey,
+) -> TransactionReceipt {
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_native_method(
+            Receiver::Ref(RENodeId::AccessController(access_controller)),
+            NativeFnIdentifier::AccessController(access_controller_fn),
+            args,
+        )
+        .build();
+    test_runner.execute_manifest(manifest, vec![*signer])
+}
+
+fn create_proof<'s, S: ReadableSubstateStore + WriteableSubstateStore>(
+    test_runner: &mut TestRunner<'s, S>,
+    access_controller: ComponentAddress,
+    signer: &PublicKey,
+) -> TransactionReceipt {
+    call_access_controller(
+        test_runner,
+        access_controller,
+        AccessControllerFnIdentifier::CreateProof,
+        scrypto_encode(&AccessControllerCreateProofInput {}),
+        signer,
+    )
+}
+
+fn is_access_controller_error<F: FnOnce(&AccessControllerError) -> bool>(
+    e: &RuntimeError,
+    f: F,
+) -> bool {
+    match e {
+        RuntimeError::ApplicationError(ApplicationError::AccessControllerError(e)) => f(e),
+        _ => false,
+    }
+}
+
+#[test]
+fn only_primary_role_can_create_proof() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let test = create_access_controller(&mut test_runner, None);
+
+    // Act
+    let primary_receipt = create_proof(&mut test_runner, test.access_controller, &test.primary);
+    let recovery_receipt = create_proof(&mut test_runner, test.access_controller, &test.recovery);
+
+    // Assert
+    primary_receipt.expect_commit_success();
+    recovery_receipt.expect_specific_failure(is_auth_error);
+}
+
+#[test]
+fn locked_primary_role_cannot_create_proof() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let test = create_access_controller(&mut test_runner, None);
+    call_access_controller(
+        &mut test_runner,
+        test.access_controller,
+        AccessControllerFnIdentifier::LockPrimaryRole,
+        scrypto_encode(&AccessControllerLockPrimaryRoleInput {}),
+        &test.recovery,
+    )
+    .expect_commit_success();
+
+    // Act
+    let locked_receipt = create_proof(&mut test_runner, test.access_controller, &test.primary);
+    call_access_controller(
+        &mut test_runner,
+        test.access_controller,
+        AccessControllerFnIdentifier::UnlockPrimaryRole,
+        scrypto_encode(&AccessControllerUnlockPrimaryRoleInput {}),
+        &test.recovery,
+    )
+    .expect_commit_success();
+    let unlocked_receipt = create_proof(&mut test_runner, test.access_controller, &test.primary);
+
+    // Assert
+    locked_receipt.expect_specific_failure(is_auth_error);
+    unlocked_receipt.expect_commit_success();
+}
+
+#[test]
+fn recovery_confirmed_by_another_role_replaces_rule_set() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let test = create_access_controller(&mut test_runner, None);
+    let new_primary: PublicKey = test_runner.new_key_pair().0.into();
+    let proposal = RecoveryProposal {
+        rule_set: rule_set(&new_primary, &test.recovery, &test.confirmation),
+        timed_recovery_delay_in_epochs: None,
+    };
+    call_access_controller(
+        &mut test_runner,
+        test.access_controller,
+        AccessControllerFnIdentifier::InitiateRecovery,
+        scrypto_encode(&AccessControllerInitiateRecoveryInput {
+            proposer: Role::Recovery,
+            proposal: proposal.clone(),
+        }),
+        &test.recovery,
+    )
+    .expect_commit_success();
+
+    // Act
+    let self_confirm_receipt = call_access_controller(
+        &mut test_runner,
+        test.access_controller,
+        AccessControllerFnIdentifier::QuickConfirmRecovery,
+        scrypto_encode(&AccessControllerQuickConfirmRecoveryInput {
+            proposer: Role::Recovery,
+            proposal: proposal.clone(),
+        }),
+        &test.recovery,
+    );
+    let confirm_receipt = call_access_controller(
+        &mut test_runner,
+        test.access_controller,
+        AccessControllerFnIdentifier::QuickConfirmRecovery,
+        scrypto_encode(&AccessControllerQuickConfirmRecoveryInput {
+            proposer: Role::Recovery,
+            proposal,
+        }),
+        &test.confirmation,
+    );
+
+    // Assert
+    self_confirm_receipt.expect_specific_failure(is_auth_error);
+    confirm_receipt.expect_commit_success();
+    create_proof(&mut test_runner, test.access_controller, &test.primary)
+        .expect_specific_failure(is_auth_error);
+    create_proof(&mut test_runner, test.access_controller, &new_primary).expect_commit_success();
+}
+
+#[test]
+fn confirming_a_different_proposal_fails() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let test = create_access_controller(&mut test_runner, None);
+    let new_primary: PublicKey = test_runner.new_key_pair().0.into();
+    call_access_controller(
+        &mut test_runner,
+        test.access_controller,
+        AccessControllerFnIdentifier::InitiateRecovery,
+        scrypto_encode(&AccessControllerInitiateRecoveryInput {
+            proposer: Role::Primary,
+            proposal: RecoveryProposal {
+                rule_set: rule_set(&new_primary, &test.recovery, &test.confirmation),
+                timed_recovery_delay_in_epochs: None,
+            },
+        }),
+        &test.primary,
+    )
+    .expect_commit_success();
+
+    // Act
+    let receipt = call_access_controller(
+        &mut test_runner,
+        test.access_controller,
+        AccessControllerFnIdentifier::QuickConfirmRecovery,
+        scrypto_encode(&AccessControllerQuickConfirmRecoveryInput {
+            proposer: Role::Primary,
+            proposal: RecoveryProposal {
+                rule_set: rule_set(&test.confirmation, &test.recovery, &test.confirmation),
+                timed_recovery_delay_in_epochs: None,
+            },
+        }),
+        &test.confirmation,
+    );
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        is_access_controller_error(e, |e| {
+            matches!(e, AccessControllerError::RecoveryProposalMismatch)
+        })
+    });
+}
+
+#[test]
+fn timed_recovery_can_only_be_confirmed_after_delay() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let test = create_access_controller(&mut test_runner, Some(10));
+    let new_primary: PublicKey = test_runner.new_key_pair().0.into();
+    let proposal = RecoveryProposal {
+        rule_set: rule_set(&new_primary, &test.recovery, &test.confirmation),
+        timed_recovery_delay_in_epochs: Some(10),
+    };
+    test_runner.set_current_epoch(5);
+    call_access_controller(
+        &mut test_runner,
+        test.access_controller,
+        AccessControllerFnIdentifier::InitiateRecovery,
+        scrypto_encode(&AccessControllerInitiateRecoveryInput {
+            proposer: Role::Recovery,
+            proposal: proposal.clone(),
+        }),
+        &test.recovery,
+    )
+    .expect_commit_success();
+
+    // Act
+    test_runner.set_current_epoch(14);
+    let early_receipt = call_access_controller(
+        &mut test_runner,
+        test.access_controller,
+        AccessControllerFnIdentifier::TimedConfirmRecovery,
+        scrypto_encode(&AccessControllerTimedConfirmRecoveryInput {
+            proposal: proposal.clone(),
+        }),
+        &test.recovery,
+    );
+    test_runner.set_current_epoch(15);
+    let receipt = call_access_controller(
+        &mut test_runner,
+        test.access_controller,
+        AccessControllerFnIdentifier::TimedConfirmRecovery,
+        scrypto_encode(&AccessControllerTimedConfirmRecoveryInput { proposal }),
+        &test.recovery,
+    );
+
+    // Assert
+    early_receipt.expect_specific_failure(|e| {
+        is_access_controller_error(e, |e| {
+            matches!(
+                e,
+                AccessControllerError::TimedRecoveryDelayHasNotElapsed {
+                    allowed_from_epoch: 15
+                }
+            )
+        })
+    });
+    receipt.expect_commit_success();
+    create_proof(&mut test_runner, test.access_controller, &new_primary).expect_commit_success();
+}
+
+#[test]
+fn cancelled_recovery_cannot_be_confirmed() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let test = create_access_controller(&mut test_runner, None);
+    let proposal = RecoveryProposal {
+        rule_set: rule_set(&test.recovery, &test.recovery, &test.confirmation),
+        timed_recovery_delay_in_epochs: None,
+    };
+    call_access_controller(
+        &mut test_runner,
+        test.access_controller,
+        AccessControllerFnIdentifier::InitiateRecovery,
+        scrypto_encode(&AccessControllerInitiateRecoveryInput {
+            proposer: Role::Recovery,
+            proposal: proposal.clone(),
+        }),
+        &test.recovery,
+    )
+    .expect_commit_success();
+    call_access_controller(
+        &mut test_runner,
+        test.access_controller,
+        AccessControllerFnIdentifier::CancelRecovery,
+        scrypto_encode(&AccessControllerCancelRecoveryInput {
+            proposer: Role::Recovery,
+        }),
+        &test.recovery,
+    )
+    .expect_commit_success();
+
+    // Act
+    let receipt = call_access_controller(
+        &mut test_runner,
+        test.access_controller,
+        AccessControllerFnIdentifier::QuickConfirmRecovery,
+        scrypto_encode(&AccessControllerQuickConfirmRecoveryInput {
+            proposer: Role::Recovery,
+            proposal,
+        }),
+        &test.primary,
+    );
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        is_access_controller_error(e, |e| {
+            matches!(
+                e,
+                AccessControllerError::NoRecoveryInProgress(Role::Recovery)
+            )
+        })
+    });
+}
@@ -6,6 +6,7 @@ use radix_engine::ledger::{
 use radix_engine::types::*;
 use scrypto_unit::*;
 use transaction::builder::ManifestBuilder;
+use transaction::signing::EddsaEd25519PrivateKey;
 
 fn test_auth_rule<'s, S: ReadableSubstateStore + WriteableSubstateStore>(
     test_runner: &mut TestRunner<'s, S>,
@@ -37,6 +38,39 @@ fn test_auth_rule<'s, S: ReadableSubstateStore + WriteableSubstateStore>(
     }
 }
 
+#[test]
+fn can_withdraw_from_my_account_with_ed25519_key_sign() {
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let pk = EddsaEd25519PrivateKey::from_u64(1).unwrap().public_key();
+    let (other_pk, _, _) = test_runner.new_key_pair();
+    let auth = rule!(require(NonFungibleAddress::from_public_key(&pk)));
+
+    test_auth_rule(&mut test_runner, &auth, &[pk.into()], true);
+    test_auth_rule(&mut test_runner, &auth, &[other_pk.into()], false);
+}
+
+#[test]
+fn can_withdraw_from_my_public_key_hash_account_with_any_key_scheme_sign() {
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (ecdsa_pk, _) = test_runner.new_key_pair();
+    let eddsa_pk = EddsaEd25519PrivateKey::from_u64(1).unwrap().public_key();
+    let signers: [PublicKey; 2] = [ecdsa_pk.into(), eddsa_pk.into()];
+
+    for signer in signers {
+        let auth = rule!(require(NonFungibleAddress::from_public_key_hash(&signer)));
+        for other_signer in signers {
+            test_auth_rule(
+                &mut test_runner,
+                &auth,
+                &[other_signer],
+                signer == other_signer,
+            );
+        }
+    }
+}
+
 #[test]
 fn can_withdraw_from_my_1_of_2_account_with_either_key_sign() {
     let mut store = TypedInMemorySubstateStore::with_bootstrap();
@@ -231,6 +265,54 @@ fn cannot_withdraw_from_my_complex_account_2() {
     }
 }
 
+#[test]
+fn can_withdraw_from_my_composite_account_built_at_runtime() {
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (pk0, _, auth0) = test_runner.new_key_pair_with_auth_address();
+    let (pk1, _, auth1) = test_runner.new_key_pair_with_auth_address();
+    let (pk2, _, auth2) = test_runner.new_key_pair_with_auth_address();
+    let (pk3, _, auth3) = test_runner.new_key_pair_with_auth_address();
+    let auth: AccessRule =
+        AccessRuleNode::n_of(2, [require(auth0), require(auth1), require(auth2)])
+            .and(require(auth3).into())
+            .into();
+
+    let signer_public_keys_list = [
+        vec![pk0.into(), pk1.into(), pk3.into()],
+        vec![pk1.into(), pk2.into(), pk3.into()],
+        vec![pk0.into(), pk1.into(), pk2.into(), pk3.into()],
+    ];
+    for signer_public_keys in &signer_public_keys_list {
+        test_auth_rule(&mut test_runner, &auth, signer_public_keys, true);
+    }
+
+    let signer_public_keys_list = [
+        vec![pk0.into(), pk1.into()],
+        vec![pk0.into(), pk3.into()],
+        vec![pk0.into(), pk1.into(), pk2.into()],
+    ];
+    for signer_public_keys in &signer_public_keys_list {
+        test_auth_rule(&mut test_runner, &auth, signer_public_keys, false);
+    }
+}
+
+#[test]
+fn can_withdraw_from_my_deeply_nested_account() {
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (pk0, _, auth0) = test_runner.new_key_pair_with_auth_address();
+    let (pk1, _, auth1) = test_runner.new_key_pair_with_auth_address();
+    let mut node: AccessRuleNode = require(auth0).into();
+    for _ in 0..10 {
+        node = AccessRuleNode::all_of([node]);
+    }
+    let auth: AccessRule = AccessRuleNode::any_of([node, require(auth1).into()]).into();
+
+    test_auth_rule(&mut test_runner, &auth, &[pk0.into()], true);
+    test_auth_rule(&mut test_runner, &auth, &[pk1.into()], true);
+}
+
 #[test]
 fn can_withdraw_from_my_any_xrd_auth_account_with_no_signature() {
     // Arrange
@@ -335,3 +417,82 @@ fn cannot_withdraw_from_my_any_xrd_auth_account_with_less_than_amount_of_proof()
     // Assert
     receipt.expect_specific_failure(is_auth_error)
 }
+
+#[test]
+fn can_withdraw_from_my_any_xrd_auth_account_with_amount_spread_over_proofs() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let xrd_auth = rule!(require_amount(Decimal::from(100), RADIX_TOKEN));
+    let account = test_runner.new_account_with_auth_rule(&xrd_auth);
+    let (_, _, other_account) = test_runner.new_account();
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_method(SYS_FAUCET_COMPONENT, "free_xrd", args!())
+        .take_from_worktop_by_amount(Decimal::from(60), RADIX_TOKEN, |builder, bucket_id| {
+            builder.create_proof_from_bucket(bucket_id, |builder, proof_id| {
+                builder.push_to_auth_zone(proof_id)
+            });
+            builder.take_from_worktop_by_amount(
+                Decimal::from(40),
+                RADIX_TOKEN,
+                |builder, other_bucket_id| {
+                    builder.create_proof_from_bucket(other_bucket_id, |builder, proof_id| {
+                        builder.push_to_auth_zone(proof_id)
+                    });
+                    builder.withdraw_from_account(RADIX_TOKEN, account);
+                    builder.clear_auth_zone();
+                    builder.return_to_worktop(other_bucket_id)
+                },
+            );
+            builder.return_to_worktop(bucket_id)
+        })
+        .call_method(
+            other_account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_commit_success();
+}
+
+#[test]
+fn cannot_withdraw_from_my_any_xrd_auth_account_with_cloned_proofs() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let xrd_auth = rule!(require_amount(Decimal::from(100), RADIX_TOKEN));
+    let account = test_runner.new_account_with_auth_rule(&xrd_auth);
+    let (_, _, other_account) = test_runner.new_account();
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_method(SYS_FAUCET_COMPONENT, "free_xrd", args!())
+        .take_from_worktop_by_amount(Decimal::from(60), RADIX_TOKEN, |builder, bucket_id| {
+            builder.create_proof_from_bucket(bucket_id, |builder, proof_id| {
+                builder.clone_proof(proof_id, |builder, cloned_proof_id| {
+                    builder.push_to_auth_zone(cloned_proof_id)
+                });
+                builder.push_to_auth_zone(proof_id);
+                builder.withdraw_from_account(RADIX_TOKEN, account);
+                builder.clear_auth_zone()
+            });
+            builder.return_to_worktop(bucket_id)
+        })
+        .call_method(
+            other_account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_specific_failure(is_auth_error)
+}
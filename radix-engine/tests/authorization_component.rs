@@ -1,4 +1,7 @@
-use radix_engine::ledger::TypedInMemorySubstateStore;
+use radix_engine::ledger::{
+    ReadableSubstateStore, TypedInMemorySubstateStore, WriteableSubstateStore,
+};
+use radix_engine::transaction::TransactionReceipt;
 use radix_engine::types::*;
 use scrypto_unit::*;
 use transaction::builder::ManifestBuilder;
@@ -134,3 +137,512 @@ fn can_make_cross_component_call_with_authorization() {
     // Assert
     receipt.expect_commit_success();
 }
+
+fn create_mutable_auth_component<'s, S: ReadableSubstateStore + WriteableSubstateStore>(
+    test_runner: &mut TestRunner<'s, S>,
+    admin: NonFungibleAddress,
+) -> ComponentAddress {
+    let package_address = test_runner.compile_and_publish("./tests/component");
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_function(
+            package_address,
+            "MutableAuthComponent",
+            "create_component",
+            args!(admin),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success();
+    receipt
+        .expect_commit()
+        .entity_changes
+        .new_component_addresses[0]
+}
+
+#[test]
+fn can_update_method_auth_with_mutability_auth() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (public_key, _, account) = test_runner.new_account();
+    let auth = test_runner.create_non_fungible_resource(account);
+    let admin = NonFungibleAddress::new(auth, NonFungibleId::from_u32(1));
+    let component = create_mutable_auth_component(&mut test_runner, admin);
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_method(component, "get_secret", args!())
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    receipt.expect_specific_failure(is_auth_error);
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .create_proof_from_account(auth, account)
+        .call_method(
+            component,
+            "set_rule",
+            args!("get_secret".to_string(), rule!(allow_all)),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![public_key.into()]);
+    receipt.expect_commit_success();
+
+    // Assert
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_method(component, "get_secret", args!())
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success();
+}
+
+#[test]
+fn cannot_update_method_auth_without_mutability_auth() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (_, _, account) = test_runner.new_account();
+    let auth = test_runner.create_non_fungible_resource(account);
+    let admin = NonFungibleAddress::new(auth, NonFungibleId::from_u32(1));
+    let component = create_mutable_auth_component(&mut test_runner, admin);
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_method(
+            component,
+            "set_rule",
+            args!("get_secret".to_string(), rule!(allow_all)),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_specific_failure(is_auth_error);
+}
+
+#[test]
+fn cannot_update_locked_method_auth() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (public_key, _, account) = test_runner.new_account();
+    let auth = test_runner.create_non_fungible_resource(account);
+    let admin = NonFungibleAddress::new(auth, NonFungibleId::from_u32(1));
+    let component = create_mutable_auth_component(&mut test_runner, admin);
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .create_proof_from_account(auth, account)
+        .call_method(
+            component,
+            "set_rule",
+            args!("set_rule".to_string(), rule!(deny_all)),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![public_key.into()]);
+
+    // Assert
+    receipt.expect_specific_failure(is_auth_error);
+}
+
+fn call_role_auth_component_method<'s, S: ReadableSubstateStore + WriteableSubstateStore>(
+    test_runner: &mut TestRunner<'s, S>,
+    component: ComponentAddress,
+    method: &str,
+    badge: Option<(ResourceAddress, ComponentAddress, EcdsaSecp256k1PublicKey)>,
+) -> TransactionReceipt {
+    let mut builder = ManifestBuilder::new(&NetworkDefinition::simulator());
+    builder.lock_fee(10.into(), SYS_FAUCET_COMPONENT);
+    let mut signers: Vec<PublicKey> = Vec::new();
+    if let Some((badge, account, public_key)) = badge {
+        builder.create_proof_from_account(badge, account);
+        signers.push(public_key.into());
+    }
+    let manifest = builder.call_method(component, method, args!()).build();
+    test_runner.execute_manifest(manifest, signers)
+}
+
+fn create_role_auth_component<'s, S: ReadableSubstateStore + WriteableSubstateStore>(
+    test_runner: &mut TestRunner<'s, S>,
+    admin: NonFungibleAddress,
+    operator: NonFungibleAddress,
+) -> ComponentAddress {
+    let package_address = test_runner.compile_and_publish("./tests/component");
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_function(
+            package_address,
+            "RoleAuthComponent",
+            "create_component",
+            args!(admin, operator),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success();
+    receipt
+        .expect_commit()
+        .entity_changes
+        .new_component_addresses[0]
+}
+
+#[test]
+fn methods_tagged_with_roles_require_any_of_the_roles() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (public_key, _, account) = test_runner.new_account();
+    let admin_badge = test_runner.create_non_fungible_resource(account);
+    let operator_badge = test_runner.create_non_fungible_resource(account);
+    let component = create_role_auth_component(
+        &mut test_runner,
+        NonFungibleAddress::new(admin_badge, NonFungibleId::from_u32(1)),
+        NonFungibleAddress::new(operator_badge, NonFungibleId::from_u32(1)),
+    );
+    let admin = Some((admin_badge, account, public_key));
+    let operator = Some((operator_badge, account, public_key));
+
+    // Act & Assert
+    for (method, badge, is_authorized) in [
+        ("configure", admin, true),
+        ("configure", operator, false),
+        ("configure", None, false),
+        ("operate", admin, true),
+        ("operate", operator, true),
+        ("operate", None, false),
+        ("audit", admin, false),
+        ("read", None, true),
+    ] {
+        let receipt = call_role_auth_component_method(&mut test_runner, component, method, badge);
+        if is_authorized {
+            receipt.expect_commit_success();
+        } else {
+            receipt.expect_specific_failure(is_auth_error);
+        }
+    }
+}
+
+fn set_role_rule<'s, S: ReadableSubstateStore + WriteableSubstateStore>(
+    test_runner: &mut TestRunner<'s, S>,
+    component: ComponentAddress,
+    role: &str,
+    rule: AccessRule,
+    badge: Option<(ResourceAddress, ComponentAddress, EcdsaSecp256k1PublicKey)>,
+) -> TransactionReceipt {
+    let mut builder = ManifestBuilder::new(&NetworkDefinition::simulator());
+    builder.lock_fee(10.into(), SYS_FAUCET_COMPONENT);
+    let mut signers: Vec<PublicKey> = Vec::new();
+    if let Some((badge, account, public_key)) = badge {
+        builder.create_proof_from_account(badge, account);
+        signers.push(public_key.into());
+    }
+    let manifest = builder
+        .call_method(component, "set_role_rule", args!(role.to_string(), rule))
+        .build();
+    test_runner.execute_manifest(manifest, signers)
+}
+
+#[test]
+fn role_rule_can_be_updated_only_as_allowed_by_its_mutability() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (public_key, _, account) = test_runner.new_account();
+    let admin_badge = test_runner.create_non_fungible_resource(account);
+    let operator_badge = test_runner.create_non_fungible_resource(account);
+    let component = create_role_auth_component(
+        &mut test_runner,
+        NonFungibleAddress::new(admin_badge, NonFungibleId::from_u32(1)),
+        NonFungibleAddress::new(operator_badge, NonFungibleId::from_u32(1)),
+    );
+    let admin = Some((admin_badge, account, public_key));
+
+    // Act
+    let unauthorized_receipt = set_role_rule(
+        &mut test_runner,
+        component,
+        "operator",
+        rule!(allow_all),
+        None,
+    );
+    let locked_receipt = set_role_rule(
+        &mut test_runner,
+        component,
+        "admin",
+        rule!(allow_all),
+        admin,
+    );
+    let authorized_receipt = set_role_rule(
+        &mut test_runner,
+        component,
+        "operator",
+        rule!(allow_all),
+        admin,
+    );
+    let operate_receipt =
+        call_role_auth_component_method(&mut test_runner, component, "operate", None);
+    let configure_receipt =
+        call_role_auth_component_method(&mut test_runner, component, "configure", None);
+
+    // Assert
+    unauthorized_receipt.expect_specific_failure(is_auth_error);
+    locked_receipt.expect_specific_failure(is_auth_error);
+    authorized_receipt.expect_commit_success();
+    operate_receipt.expect_commit_success();
+    configure_receipt.expect_specific_failure(is_auth_error);
+}
+
+fn create_cross_components<'s, S: ReadableSubstateStore + WriteableSubstateStore>(
+    test_runner: &mut TestRunner<'s, S>,
+    package_address: PackageAddress,
+    authorization: AccessRules,
+) -> (ComponentAddress, ComponentAddress) {
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_function(
+            package_address,
+            "CrossComponent",
+            "create_component_with_auth",
+            args!(authorization),
+        )
+        .call_function(
+            package_address,
+            "CrossComponent",
+            "create_component",
+            args!(),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success();
+    let new_component_addresses = receipt.new_component_addresses();
+    (new_component_addresses[0], new_component_addresses[1])
+}
+
+#[test]
+fn cross_component_call_cannot_use_proofs_of_transaction_auth_zone() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (public_key, _, account) = test_runner.new_account();
+    let auth = test_runner.create_non_fungible_resource(account);
+    let auth_address = NonFungibleAddress::new(auth, NonFungibleId::from_u32(1));
+    let authorization =
+        AccessRules::new().method("get_component_state", rule!(require(auth_address)));
+    let package_address = test_runner.compile_and_publish("./tests/component");
+    let (secured_component, my_component) =
+        create_cross_components(&mut test_runner, package_address, authorization);
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .create_proof_from_account(auth, account)
+        .call_method(
+            my_component,
+            "cross_component_call",
+            args!(secured_component),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![public_key.into()]);
+
+    // Assert
+    receipt.expect_specific_failure(is_auth_error);
+}
+
+#[test]
+fn method_requiring_caller_origin_can_only_be_called_from_that_package() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let package_address = test_runner.compile_and_publish("./tests/component");
+    let authorization = AccessRules::new().method(
+        "get_component_state",
+        rule!(require_caller_origin(package_address)),
+    );
+    let (secured_component, my_component) =
+        create_cross_components(&mut test_runner, package_address, authorization);
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_method(secured_component, "get_component_state", args!())
+        .build();
+    let direct_receipt = test_runner.execute_manifest(manifest, vec![]);
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_method(
+            my_component,
+            "cross_component_call",
+            args!(secured_component),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    direct_receipt.expect_specific_failure(is_auth_error);
+    receipt.expect_commit_success();
+}
+
+#[test]
+fn method_requiring_caller_origin_rejects_other_components() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let package_address = test_runner.compile_and_publish("./tests/component");
+    let (_, allowed_component) =
+        create_cross_components(&mut test_runner, package_address, AccessRules::new());
+    let authorization = AccessRules::new().method(
+        "get_component_state",
+        rule!(require_caller_origin(allowed_component)),
+    );
+    let (secured_component, other_component) =
+        create_cross_components(&mut test_runner, package_address, authorization);
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_method(
+            other_component,
+            "cross_component_call",
+            args!(secured_component),
+        )
+        .build();
+    let rejected_receipt = test_runner.execute_manifest(manifest, vec![]);
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_method(
+            allowed_component,
+            "cross_component_call",
+            args!(secured_component),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    rejected_receipt.expect_specific_failure(is_auth_error);
+    receipt.expect_commit_success();
+}
+
+fn set_allow_list_member<'s, S: ReadableSubstateStore + WriteableSubstateStore>(
+    test_runner: &mut TestRunner<'s, S>,
+    component: ComponentAddress,
+    admin: (ResourceAddress, ComponentAddress, EcdsaSecp256k1PublicKey),
+    member: NonFungibleAddress,
+    is_member: bool,
+) {
+    let (admin_badge, account, public_key) = admin;
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .create_proof_from_account(admin_badge, account)
+        .call_method(component, "set_member", args!(member, is_member))
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![public_key.into()]);
+    receipt.expect_commit_success();
+}
+
+#[test]
+fn method_requiring_membership_follows_updates_of_allow_list() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (public_key, _, account) = test_runner.new_account();
+    let admin_badge = test_runner.create_non_fungible_resource(account);
+    let member_badge = test_runner.create_non_fungible_resource(account);
+    let member = NonFungibleAddress::new(member_badge, NonFungibleId::from_u32(1));
+    let package_address = test_runner.compile_and_publish("./tests/component");
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_function(
+            package_address,
+            "AllowListComponent",
+            "create_component",
+            args!(NonFungibleAddress::new(
+                admin_badge,
+                NonFungibleId::from_u32(1)
+            )),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success();
+    let component = receipt.new_component_addresses()[0];
+    let admin = (admin_badge, account, public_key);
+    let member_proof = Some((member_badge, account, public_key));
+
+    // Act
+    let unlisted_receipt =
+        call_role_auth_component_method(&mut test_runner, component, "get_secret", member_proof);
+    set_allow_list_member(&mut test_runner, component, admin, member.clone(), true);
+    let listed_receipt =
+        call_role_auth_component_method(&mut test_runner, component, "get_secret", member_proof);
+    let other_badge_receipt =
+        call_role_auth_component_method(&mut test_runner, component, "get_secret", Some(admin));
+    set_allow_list_member(&mut test_runner, component, admin, member, false);
+    let revoked_receipt =
+        call_role_auth_component_method(&mut test_runner, component, "get_secret", member_proof);
+
+    // Assert
+    unlisted_receipt.expect_specific_failure(is_auth_error);
+    listed_receipt.expect_commit_success();
+    other_badge_receipt.expect_specific_failure(is_auth_error);
+    revoked_receipt.expect_specific_failure(is_auth_error);
+}
+
+#[test]
+fn component_cannot_use_transaction_signer_badge_for_nested_native_calls() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (public_key, _, account) = test_runner.new_account();
+    let package_address = test_runner.compile_and_publish("./tests/component");
+    let (_, my_component) =
+        create_cross_components(&mut test_runner, package_address, AccessRules::new());
+    let mut access_rules = HashMap::new();
+    access_rules.insert(
+        ResourceMethodAuthKey::Mint,
+        (
+            rule!(require(NonFungibleAddress::from_public_key(&public_key))),
+            LOCKED,
+        ),
+    );
+    access_rules.insert(ResourceMethodAuthKey::Withdraw, (rule!(allow_all), LOCKED));
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .create_resource(
+            ResourceType::Fungible { divisibility: 0 },
+            HashMap::new(),
+            access_rules,
+            None,
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success();
+    let resource_address = receipt.new_resource_addresses()[0];
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .mint(1.into(), resource_address)
+        .call_method(
+            account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let direct_receipt = test_runner.execute_manifest(manifest, vec![public_key.into()]);
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_method(my_component, "mint", args!(resource_address))
+        .call_method(
+            account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let nested_receipt = test_runner.execute_manifest(manifest, vec![public_key.into()]);
+
+    // Assert
+    direct_receipt.expect_commit_success();
+    nested_receipt.expect_specific_failure(is_auth_error);
+}
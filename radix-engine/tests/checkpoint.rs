@@ -0,0 +1,94 @@
+use radix_engine::engine::{KernelError, RuntimeError};
+use radix_engine::ledger::TypedInMemorySubstateStore;
+use radix_engine::types::*;
+use scrypto_unit::*;
+use transaction::builder::ManifestBuilder;
+
+fn instantiate(test_runner: &mut TestRunner<TypedInMemorySubstateStore>) -> ComponentAddress {
+    let package_address = test_runner.compile_and_publish("./tests/checkpoint");
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_function(package_address, "CheckpointTest", "new", args!())
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    receipt
+        .expect_commit()
+        .entity_changes
+        .new_component_addresses[0]
+}
+
+#[test]
+fn rollback_to_checkpoint_discards_state_changes() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let component_address = instantiate(&mut test_runner);
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_method(component_address, "insert_and_rollback", args!(1u32, 2u32))
+        .call_method(component_address, "get", args!(1u32))
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let outputs = receipt.expect_commit_success();
+    let rolled_back: Option<u32> = scrypto_decode(&outputs[1]).unwrap();
+    let stored: Option<u32> = scrypto_decode(&outputs[2]).unwrap();
+    assert_eq!(rolled_back, None);
+    assert_eq!(stored, None);
+    assert_eq!(
+        receipt.execution.application_logs,
+        vec![(Level::Info, "Rolled back".to_string())]
+    );
+}
+
+#[test]
+fn unused_checkpoint_keeps_state_changes() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let component_address = instantiate(&mut test_runner);
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_method(component_address, "insert_and_keep", args!(1u32, 2u32))
+        .call_method(component_address, "get", args!(1u32))
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let outputs = receipt.expect_commit_success();
+    let stored: Option<u32> = scrypto_decode(&outputs[2]).unwrap();
+    assert_eq!(stored, Some(2u32));
+}
+
+#[test]
+fn cannot_checkpoint_while_holding_bucket() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let package_address = test_runner.compile_and_publish("./tests/checkpoint");
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_function(
+            package_address,
+            "CheckpointTest",
+            "checkpoint_while_holding_bucket",
+            args!(),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::KernelError(KernelError::CheckpointNotAllowed)
+        )
+    });
+}
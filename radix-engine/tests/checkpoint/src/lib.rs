@@ -0,0 +1,41 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct CheckpointTest {
+        values: KeyValueStore<u32, u32>,
+    }
+
+    impl CheckpointTest {
+        pub fn new() -> ComponentAddress {
+            Self {
+                values: KeyValueStore::new(),
+            }
+            .instantiate()
+            .globalize()
+        }
+
+        pub fn insert_and_rollback(&mut self, key: u32, value: u32) -> Option<u32> {
+            let checkpoint = Runtime::execution_checkpoint();
+            info!("Inserting {}", key);
+            self.values.insert(key, value);
+            Runtime::rollback_to(checkpoint);
+            info!("Rolled back");
+            self.values.get(&key).map(|value| *value)
+        }
+
+        pub fn insert_and_keep(&mut self, key: u32, value: u32) {
+            Runtime::execution_checkpoint();
+            self.values.insert(key, value);
+        }
+
+        pub fn get(&self, key: u32) -> Option<u32> {
+            self.values.get(&key).map(|value| *value)
+        }
+
+        pub fn checkpoint_while_holding_bucket() -> Bucket {
+            let bucket = ResourceBuilder::new_fungible().initial_supply(1);
+            Runtime::execution_checkpoint();
+            bucket
+        }
+    }
+}
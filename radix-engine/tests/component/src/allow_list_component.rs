@@ -0,0 +1,28 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct AllowListComponent {
+        allow_list: KeyValueStore<NonFungibleAddress, bool>,
+    }
+
+    impl AllowListComponent {
+        pub fn create_component(admin: NonFungibleAddress) -> ComponentAddress {
+            let allow_list = KeyValueStore::new();
+            let access_rules = AccessRules::new()
+                .method("set_member", rule!(require(admin)))
+                .method("get_secret", rule!(require_membership(&allow_list)))
+                .default(rule!(deny_all));
+            let mut component = Self { allow_list }.instantiate();
+            component.add_access_check(access_rules);
+            component.globalize()
+        }
+
+        pub fn set_member(&mut self, member: NonFungibleAddress, is_member: bool) {
+            self.allow_list.insert(member, is_member);
+        }
+
+        pub fn get_secret(&self) -> String {
+            "Secret".to_owned()
+        }
+    }
+}
@@ -44,6 +44,10 @@ blueprint! {
             }
         }
 
+        pub fn mint(&mut self, resource_address: ResourceAddress) -> Bucket {
+            borrow_resource_manager!(resource_address).mint(1)
+        }
+
         pub fn get_component_state(&self) -> String {
             self.secret.clone()
         }
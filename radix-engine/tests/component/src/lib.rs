@@ -1,7 +1,10 @@
+pub mod allow_list_component;
 pub mod auth_component;
 pub mod auth_list_component;
 pub mod chess;
 pub mod component;
 pub mod cross_component;
 pub mod external_blueprint_target;
+pub mod mutable_auth_component;
 pub mod reentrant_component;
+pub mod role_auth_component;
@@ -0,0 +1,27 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct MutableAuthComponent {}
+
+    impl MutableAuthComponent {
+        pub fn create_component(admin: NonFungibleAddress) -> ComponentAddress {
+            let mut component = Self {}.instantiate();
+            component.add_access_check(
+                AccessRules::new()
+                    .method("get_secret", rule!(deny_all))
+                    .mutability("get_secret", MUTABLE(rule!(require(admin))))
+                    .default(rule!(allow_all)),
+            );
+            component.globalize()
+        }
+
+        pub fn get_secret(&self) -> String {
+            "Secret".to_owned()
+        }
+
+        pub fn set_rule(&self, method: String, rule: AccessRule) {
+            let (component_address, ..) = Runtime::actor().as_component();
+            borrow_component!(component_address).set_access_rule(0, &method, rule);
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct RoleAuthComponent {}
+
+    impl RoleAuthComponent {
+        pub fn create_component(
+            admin: NonFungibleAddress,
+            operator: NonFungibleAddress,
+        ) -> ComponentAddress {
+            let mut component = Self {}.instantiate();
+            component.add_access_check(
+                AccessRules::new()
+                    .role("admin", rule!(require(admin.clone())))
+                    .role("operator", rule!(require(operator)))
+                    .role_mutability("operator", MUTABLE(rule!(require(admin))))
+                    .default(rule!(allow_all)),
+            );
+            component.globalize()
+        }
+
+        #[role("admin")]
+        pub fn configure(&self) {}
+
+        #[role("admin", "operator")]
+        pub fn operate(&self) {}
+
+        #[role("auditor")]
+        pub fn audit(&self) {}
+
+        pub fn read(&self) {}
+
+        pub fn set_role_rule(&self, role: String, rule: AccessRule) {
+            let (component_address, ..) = Runtime::actor().as_component();
+            borrow_component!(component_address).set_role_rule(0, &role, rule);
+        }
+    }
+}
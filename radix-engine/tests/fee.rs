@@ -2,7 +2,6 @@ use radix_engine::engine::ApplicationError;
 use radix_engine::engine::RuntimeError;
 use radix_engine::ledger::TypedInMemorySubstateStore;
 use radix_engine::ledger::WriteableSubstateStore;
-use radix_engine::model::KeyValueStoreEntryWrapper;
 use radix_engine::model::WorktopError;
 use radix_engine::transaction::TransactionReceipt;
 use radix_engine::types::*;
@@ -166,10 +165,9 @@ where
     if let Some(account_comp) = test_runner.inspect_component_state(account_address) {
         let account_comp_state = ScryptoValue::from_slice(account_comp.state()).unwrap();
         if let Some(kv_store_id) = account_comp_state.kv_store_ids.iter().next() {
-            if let Some(KeyValueStoreEntryWrapper(Some(value))) = test_runner
+            if let Some(kv_entry_value) = test_runner
                 .inspect_key_value_entry(kv_store_id.clone(), scrypto_encode(&resource_address))
             {
-                let kv_entry_value = ScryptoValue::from_slice(&value).unwrap();
                 let vault_id = kv_entry_value.vault_ids.iter().next().unwrap();
                 let vault = test_runner.inspect_vault(vault_id.clone()).unwrap();
                 return vault.total_amount();
@@ -305,13 +303,49 @@ fn test_contingent_fee_accounting_success() {
     let summary = &receipt.execution.fee_summary;
     let effective_price =
         summary.cost_unit_price + summary.cost_unit_price * summary.tip_percentage / 100;
-    let contingent_fee =
+    let locked1 =
+        (dec!("10") / effective_price).round(0, RoundingMode::TowardsZero) * effective_price;
+    let locked2 =
         (dec!("0.001") / effective_price).round(0, RoundingMode::TowardsZero) * effective_price;
-    assert_eq!(
-        account1_new_balance,
-        account1_balance - effective_price * summary.cost_unit_consumed + contingent_fee
-    );
-    assert_eq!(account2_new_balance, account2_balance - contingent_fee);
+    let total_fee = effective_price * summary.cost_unit_consumed;
+    let fee1 = total_fee * locked1 / (locked1 + locked2);
+    assert_eq!(account1_new_balance, account1_balance - fee1);
+    assert_eq!(account2_new_balance, account2_balance - (total_fee - fee1));
+}
+
+#[test]
+fn test_fee_accounting_with_multiple_vaults() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (public_key1, _, account1) = test_runner.new_account();
+    let (public_key2, _, account2) = test_runner.new_account();
+    let account1_balance = query_account_balance(&mut test_runner, account1, RADIX_TOKEN);
+    let account2_balance = query_account_balance(&mut test_runner, account2, RADIX_TOKEN);
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(dec!("3"), account1)
+        .lock_fee(dec!("1"), account2)
+        .build();
+    let receipt =
+        test_runner.execute_manifest(manifest, vec![public_key1.into(), public_key2.into()]);
+
+    // Assert
+    receipt.expect_commit_success();
+    let account1_new_balance = query_account_balance(&mut test_runner, account1, RADIX_TOKEN);
+    let account2_new_balance = query_account_balance(&mut test_runner, account2, RADIX_TOKEN);
+    let summary = &receipt.execution.fee_summary;
+    let effective_price =
+        summary.cost_unit_price + summary.cost_unit_price * summary.tip_percentage / 100;
+    let locked1 =
+        (dec!("3") / effective_price).round(0, RoundingMode::TowardsZero) * effective_price;
+    let locked2 =
+        (dec!("1") / effective_price).round(0, RoundingMode::TowardsZero) * effective_price;
+    let total_fee = effective_price * summary.cost_unit_consumed;
+    let fee1 = total_fee * locked1 / (locked1 + locked2);
+    assert_eq!(account1_new_balance, account1_balance - fee1);
+    assert_eq!(account2_new_balance, account2_balance - (total_fee - fee1));
 }
 
 #[test]
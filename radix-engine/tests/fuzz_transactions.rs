@@ -1,11 +1,12 @@
 use radix_engine::constants::{
-    DEFAULT_COST_UNIT_LIMIT, DEFAULT_COST_UNIT_PRICE, DEFAULT_MAX_CALL_DEPTH, DEFAULT_SYSTEM_LOAN,
+    DEFAULT_COST_UNIT_LIMIT, DEFAULT_MAX_CALL_DEPTH, DEFAULT_MAX_HEAP_SIZE, DEFAULT_SYSTEM_LOAN,
 };
+use radix_engine::fee::FeeDistribution;
 use radix_engine::ledger::TypedInMemorySubstateStore;
 use radix_engine::state_manager::StagedSubstateStoreManager;
 use radix_engine::transaction::{ExecutionConfig, FeeReserveConfig, TransactionExecutor};
 use radix_engine::types::*;
-use radix_engine::wasm::{DefaultWasmEngine, WasmInstrumenter};
+use radix_engine::wasm::{DefaultWasmEngine, WasmInstrumenter, WasmMeteringParams};
 use rand::Rng;
 use rand_chacha;
 use rand_chacha::rand_core::SeedableRng;
@@ -35,11 +36,14 @@ fn execute_single_transaction(transaction: NotarizedTransaction) {
     let mut wasm_instrumenter = WasmInstrumenter::new();
     let execution_config = ExecutionConfig {
         max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+        max_heap_size: DEFAULT_MAX_HEAP_SIZE,
         trace: false,
+        wasm_metering_params: WasmMeteringParams::standard(),
     };
     let fee_reserve_config = FeeReserveConfig {
-        cost_unit_price: DEFAULT_COST_UNIT_PRICE.parse().unwrap(),
         system_loan: DEFAULT_SYSTEM_LOAN,
+        distribution: FeeDistribution::default(),
+        free_credit: 0,
     };
 
     let mut staged_store_manager = StagedSubstateStoreManager::new(&mut store);
@@ -114,6 +118,8 @@ impl TransactionFuzzer {
             notary_as_signatory: false,
             cost_unit_limit: 10_000_000,
             tip_percentage: 0,
+            declared_substates: None,
+            fee_payer: None,
         };
 
         TransactionBuilder::new()
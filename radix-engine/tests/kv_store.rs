@@ -381,3 +381,38 @@ fn multiple_reads_should_work() {
     // Assert
     receipt.expect_commit_success();
 }
+
+#[test]
+fn put_if_absent_only_writes_missing_entries() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let package_address = test_runner.compile_and_publish("./tests/kv_store");
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_function(package_address, "PutIfAbsent", "new", args!())
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    let component_address = receipt
+        .expect_commit()
+        .entity_changes
+        .new_component_addresses[0];
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_method(component_address, "claim", args!("alice".to_string(), 1u32))
+        .call_method(component_address, "claim", args!("alice".to_string(), 2u32))
+        .call_method(component_address, "owner", args!("alice".to_string()))
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let outputs = receipt.expect_commit_success();
+    let first_claim: bool = scrypto_decode(&outputs[1]).unwrap();
+    let second_claim: bool = scrypto_decode(&outputs[2]).unwrap();
+    let owner: Option<u32> = scrypto_decode(&outputs[3]).unwrap();
+    assert!(first_claim);
+    assert!(!second_claim);
+    assert_eq!(owner, Some(1u32));
+}
@@ -0,0 +1,25 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct PutIfAbsent {
+        owners: KeyValueStore<String, u32>,
+    }
+
+    impl PutIfAbsent {
+        pub fn new() -> ComponentAddress {
+            PutIfAbsent {
+                owners: KeyValueStore::new(),
+            }
+            .instantiate()
+            .globalize()
+        }
+
+        pub fn claim(&mut self, name: String, owner: u32) -> bool {
+            self.owners.insert_if_absent(name, owner)
+        }
+
+        pub fn owner(&self, name: String) -> Option<u32> {
+            self.owners.get(&name).map(|owner| *owner)
+        }
+    }
+}
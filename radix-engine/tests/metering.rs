@@ -1,5 +1,7 @@
+use radix_engine::engine::{KernelError, RuntimeError};
 use radix_engine::ledger::TypedInMemorySubstateStore;
 use radix_engine::types::*;
+use radix_engine::wasm::WasmError;
 use scrypto::args;
 use scrypto_unit::*;
 use transaction::builder::ManifestBuilder;
@@ -78,7 +80,12 @@ fn test_recursion_stack_overflow() {
     let receipt = test_runner.execute_manifest(manifest, vec![]);
 
     // Assert
-    receipt.expect_specific_failure(is_wasm_error)
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::KernelError(KernelError::WasmError(WasmError::StackOverflow))
+        )
+    })
 }
 
 #[test]
@@ -98,6 +105,43 @@ fn test_grow_memory() {
 
     // Assert
     receipt.expect_commit_success();
+    assert_eq!(
+        vec![101],
+        receipt
+            .execution
+            .wasm_memory_usage
+            .iter()
+            .map(|m| m.peak_memory_pages)
+            .collect::<Vec<u32>>()
+    );
+}
+
+#[test]
+fn test_grow_memory_beyond_limit() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+
+    // Act
+    let code = wat2wasm(&include_str!("wasm/memory.wat").replace("${n}", "600"));
+    let package_address = test_runner.publish_package(code, test_abi_any_in_void_out("Test", "f"));
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_function(package_address, "Test", "f", args!())
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(
+        vec![1],
+        receipt
+            .execution
+            .wasm_memory_usage
+            .iter()
+            .map(|m| m.peak_memory_pages)
+            .collect::<Vec<u32>>()
+    );
 }
 
 #[test]
@@ -193,5 +237,5 @@ fn test_publish_large_package() {
     receipt.expect_commit_success();
 
     // Assert
-    assert_eq!(4271555, receipt.execution.fee_summary.cost_unit_consumed);
+    assert_eq!(4681155, receipt.execution.fee_summary.cost_unit_consumed);
 }
@@ -6,6 +6,7 @@ use radix_engine::wasm::*;
 use sbor::Type;
 use scrypto_unit::*;
 use transaction::builder::ManifestBuilder;
+use transaction::model::TransactionManifest;
 
 #[ignore] // TODO: enable this after allowing dynamic creation of blobs
 #[test]
@@ -160,6 +161,7 @@ fn test_basic_package_missing_export() {
                 input: Type::Unit,
                 output: Type::Unit,
                 export_name: "f".to_string(),
+                roles: vec![],
             }],
         },
     );
@@ -182,3 +184,118 @@ fn test_basic_package_missing_export() {
         )
     });
 }
+
+fn update_package_metadata_manifest(
+    package_address: PackageAddress,
+    owner_proof: Option<(ResourceAddress, ComponentAddress)>,
+) -> TransactionManifest {
+    let mut builder = ManifestBuilder::new(&NetworkDefinition::simulator());
+    builder.lock_fee(10.into(), SYS_FAUCET_COMPONENT);
+    if let Some((badge, account)) = owner_proof {
+        builder.create_proof_from_account(badge, account);
+    }
+    let mut metadata = HashMap::new();
+    metadata.insert("name".to_string(), "Basic Package".to_string());
+    builder
+        .call_native_method(
+            Receiver::Ref(RENodeId::Package(package_address)),
+            NativeFnIdentifier::Package(PackageFnIdentifier::UpdateMetadata),
+            scrypto_encode(&PackageUpdateMetadataInput { metadata }),
+        )
+        .build()
+}
+
+#[test]
+fn package_metadata_can_only_be_updated_with_minted_owner_badge() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (public_key, _, account) = test_runner.new_account();
+    let code = wat2wasm(include_str!("wasm/basic_package.wat"));
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .publish_package_with_owner(code, HashMap::new(), None)
+        .call_method(
+            account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success();
+    let package_address = receipt.new_package_addresses()[0];
+    let owner_badge = receipt.new_resource_addresses()[0];
+
+    // Act
+    let unauthorized_receipt = test_runner.execute_manifest(
+        update_package_metadata_manifest(package_address, None),
+        vec![public_key.into()],
+    );
+    let receipt = test_runner.execute_manifest(
+        update_package_metadata_manifest(package_address, Some((owner_badge, account))),
+        vec![public_key.into()],
+    );
+
+    // Assert
+    unauthorized_receipt.expect_specific_failure(is_auth_error);
+    receipt.expect_commit_success();
+}
+
+#[test]
+fn package_metadata_can_be_updated_with_given_owner_badge() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (public_key, _) = test_runner.new_key_pair();
+    let code = wat2wasm(include_str!("wasm/basic_package.wat"));
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .publish_package_with_owner(
+            code,
+            HashMap::new(),
+            Some(NonFungibleAddress::from_public_key(&public_key)),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success();
+    let package_address = receipt.new_package_addresses()[0];
+
+    // Act
+    let unauthorized_receipt = test_runner.execute_manifest(
+        update_package_metadata_manifest(package_address, None),
+        vec![],
+    );
+    let receipt = test_runner.execute_manifest(
+        update_package_metadata_manifest(package_address, None),
+        vec![public_key.into()],
+    );
+
+    // Assert
+    unauthorized_receipt.expect_specific_failure(is_auth_error);
+    receipt.expect_commit_success();
+}
+
+#[test]
+fn package_published_without_owner_cannot_be_updated() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (public_key, _) = test_runner.new_key_pair();
+    let code = wat2wasm(include_str!("wasm/basic_package.wat"));
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .publish_package(code, HashMap::new())
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success();
+    let package_address = receipt.new_package_addresses()[0];
+
+    // Act
+    let receipt = test_runner.execute_manifest(
+        update_package_metadata_manifest(package_address, None),
+        vec![public_key.into()],
+    );
+
+    // Assert
+    receipt.expect_specific_failure(is_auth_error);
+}
@@ -39,6 +39,7 @@ pub extern "C" fn LargeReturnSize_abi(_input: *mut u8) -> *mut u8 {
             },
             output: Type::Unit,
             export_name: "LargeReturnSize_f_main".to_string(),
+            roles: vec![],
         }],
     };
     ::scrypto::buffer::scrypto_encode_to_buffer(&abi)
@@ -61,6 +62,7 @@ pub extern "C" fn MaxReturnSize_abi(_input: *mut u8) -> *mut u8 {
             },
             output: Type::Unit,
             export_name: "MaxReturnSize_f_main".to_string(),
+            roles: vec![],
         }],
     };
 
@@ -84,6 +86,7 @@ pub extern "C" fn ZeroReturnSize_abi(_input: *mut u8) -> *mut u8 {
             },
             output: Type::Unit,
             export_name: "ZeroReturnSize_f_main".to_string(),
+            roles: vec![],
         }],
     };
 
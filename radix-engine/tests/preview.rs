@@ -1,4 +1,5 @@
 use radix_engine::ledger::TypedInMemorySubstateStore;
+use radix_engine::model::HardResourceOrNonFungible;
 use radix_engine::transaction::{ExecutionConfig, FeeReserveConfig};
 use radix_engine::types::*;
 use scrypto_unit::*;
@@ -37,9 +38,127 @@ fn test_transaction_preview_cost_estimate() {
     );
 }
 
+#[test]
+fn test_transaction_preview_fee_estimate() {
+    // Arrange
+    let mut substate_store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut substate_store);
+    let network = NetworkDefinition::simulator();
+    let (validated_transaction, preview_intent) =
+        prepare_test_tx_and_preview_intent(&test_runner, &network);
+
+    // Act
+    let preview_result = test_runner
+        .execute_preview(preview_intent, &network)
+        .unwrap();
+    let receipt = test_runner.execute_transaction(
+        &validated_transaction,
+        &FeeReserveConfig::standard(),
+        &ExecutionConfig::standard(),
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(
+        preview_result.estimated_cost_units(),
+        receipt.execution.fee_summary.cost_unit_consumed
+    );
+    assert_eq!(
+        preview_result.estimated_fee(),
+        receipt.execution.fee_summary.total_fee()
+    );
+}
+
+#[test]
+fn test_transaction_preview_with_unlimited_loan_does_not_require_lock_fee() {
+    // Arrange
+    let mut substate_store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut substate_store);
+    let network = NetworkDefinition::simulator();
+    let manifest = ManifestBuilder::new(&network).clear_auth_zone().build();
+    let (_, mut preview_intent) =
+        prepare_test_tx_and_preview_intent_with_manifest(&test_runner, &network, manifest);
+
+    // Act
+    let preview_result = test_runner
+        .execute_preview(preview_intent.clone(), &network)
+        .unwrap();
+    preview_intent.flags.unlimited_loan = false;
+    let limited_preview_result = test_runner
+        .execute_preview(preview_intent, &network)
+        .unwrap();
+
+    // Assert
+    preview_result.receipt.expect_commit_success();
+    assert!(preview_result.estimated_fee() > Decimal::zero());
+    assert_eq!(
+        preview_result.receipt.execution.fee_summary.free_credit,
+        Decimal::zero()
+    );
+    limited_preview_result.receipt.expect_rejection();
+}
+
+#[test]
+fn test_transaction_preview_can_assume_all_signature_proofs() {
+    // Arrange
+    let mut substate_store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut substate_store);
+    let network = NetworkDefinition::simulator();
+    let (public_key, _, account) = test_runner.new_account();
+    let manifest = ManifestBuilder::new(&network)
+        .withdraw_from_account_by_amount(1.into(), RADIX_TOKEN, account)
+        .call_method(
+            account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let (_, mut preview_intent) =
+        prepare_test_tx_and_preview_intent_with_manifest(&test_runner, &network, manifest);
+    preview_intent.signer_public_keys.clear();
+
+    // Act
+    let unsigned_preview_result = test_runner
+        .execute_preview(preview_intent.clone(), &network)
+        .unwrap();
+    preview_intent.flags.assume_all_signature_proofs = true;
+    let preview_result = test_runner
+        .execute_preview(preview_intent, &network)
+        .unwrap();
+
+    // Assert
+    unsigned_preview_result
+        .receipt
+        .expect_specific_failure(is_auth_error);
+    preview_result.receipt.expect_commit_success();
+    assert_eq!(
+        preview_result.receipt.execution.required_badges,
+        vec![HardResourceOrNonFungible::NonFungible(
+            NonFungibleAddress::from_public_key(&public_key)
+        )]
+    );
+    assert!(unsigned_preview_result
+        .receipt
+        .execution
+        .required_badges
+        .is_empty());
+}
+
 fn prepare_test_tx_and_preview_intent(
     test_runner: &TestRunner<TypedInMemorySubstateStore>,
     network: &NetworkDefinition,
+) -> (Validated<NotarizedTransaction>, PreviewIntent) {
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .clear_auth_zone()
+        .build();
+    prepare_test_tx_and_preview_intent_with_manifest(test_runner, network, manifest)
+}
+
+fn prepare_test_tx_and_preview_intent_with_manifest(
+    test_runner: &TestRunner<TypedInMemorySubstateStore>,
+    network: &NetworkDefinition,
+    manifest: TransactionManifest,
 ) -> (Validated<NotarizedTransaction>, PreviewIntent) {
     let notary_priv_key = EcdsaSecp256k1PrivateKey::from_u64(2).unwrap();
     let tx_signer_priv_key = EcdsaSecp256k1PrivateKey::from_u64(3).unwrap();
@@ -55,13 +174,10 @@ fn prepare_test_tx_and_preview_intent(
             notary_as_signatory: false,
             cost_unit_limit: 10_000_000,
             tip_percentage: 0,
+            declared_substates: None,
+            fee_payer: None,
         })
-        .manifest(
-            ManifestBuilder::new(&NetworkDefinition::simulator())
-                .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
-                .clear_auth_zone()
-                .build(),
-        )
+        .manifest(manifest)
         .sign(&tx_signer_priv_key)
         .notarize(&notary_priv_key)
         .build();
@@ -82,6 +198,7 @@ fn prepare_test_tx_and_preview_intent(
         signer_public_keys: vec![tx_signer_priv_key.public_key().into()],
         flags: PreviewFlags {
             unlimited_loan: true,
+            assume_all_signature_proofs: false,
         },
     };
 
@@ -0,0 +1,157 @@
+use radix_engine::ledger::TypedInMemorySubstateStore;
+use radix_engine::transaction::{ExecutionConfig, FeeReserveConfig};
+use radix_engine::types::*;
+use scrypto_unit::*;
+use transaction::builder::ManifestBuilder;
+use transaction::model::{SignedSubintent, Subintent, TestTransaction, TransactionManifest};
+use transaction::signing::EcdsaSecp256k1PrivateKey;
+
+fn signed_subintent(
+    manifest: TransactionManifest,
+    signer: &EcdsaSecp256k1PrivateKey,
+) -> SignedSubintent {
+    SignedSubintent::new(Subintent {
+        network_id: NetworkDefinition::simulator().id,
+        start_epoch_inclusive: 0,
+        end_epoch_exclusive: 100,
+        nonce: 0,
+        manifest,
+    })
+    .sign(signer)
+}
+
+#[test]
+fn subintent_can_withdraw_with_its_signer_badge() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (_, maker_private_key, maker_account) = test_runner.new_account();
+    let (taker_public_key, _, taker_account) = test_runner.new_account();
+
+    // Act
+    let subintent = signed_subintent(
+        ManifestBuilder::new(&NetworkDefinition::simulator())
+            .withdraw_from_account_by_amount(dec!("10"), RADIX_TOKEN, maker_account)
+            .build(),
+        &maker_private_key,
+    );
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(dec!("10"), taker_account)
+        .execute_subintent(0)
+        .call_method(
+            taker_account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let mut transaction = TestTransaction::new(
+        manifest,
+        test_runner.next_transaction_nonce(),
+        vec![taker_public_key.into()],
+    );
+    transaction
+        .transaction
+        .signed_intent
+        .intent
+        .subintents
+        .push(subintent);
+    let receipt = test_runner.execute_transaction(
+        &transaction,
+        &FeeReserveConfig::standard(),
+        &ExecutionConfig::standard(),
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+}
+
+#[test]
+fn transaction_manifest_cannot_use_subintent_signer_badge() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (_, maker_private_key, maker_account) = test_runner.new_account();
+    let (taker_public_key, _, taker_account) = test_runner.new_account();
+
+    // Act
+    let subintent = signed_subintent(
+        ManifestBuilder::new(&NetworkDefinition::simulator())
+            .clear_auth_zone()
+            .build(),
+        &maker_private_key,
+    );
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(dec!("10"), taker_account)
+        .execute_subintent(0)
+        .withdraw_from_account_by_amount(dec!("10"), RADIX_TOKEN, maker_account)
+        .call_method(
+            taker_account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let mut transaction = TestTransaction::new(
+        manifest,
+        test_runner.next_transaction_nonce(),
+        vec![taker_public_key.into()],
+    );
+    transaction
+        .transaction
+        .signed_intent
+        .intent
+        .subintents
+        .push(subintent);
+    let receipt = test_runner.execute_transaction(
+        &transaction,
+        &FeeReserveConfig::standard(),
+        &ExecutionConfig::standard(),
+    );
+
+    // Assert
+    receipt.expect_specific_failure(is_auth_error);
+}
+
+#[test]
+fn subintent_cannot_use_transaction_signer_badge() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (_, maker_private_key, maker_account) = test_runner.new_account();
+    let (taker_public_key, _, taker_account) = test_runner.new_account();
+
+    // Act
+    let subintent = signed_subintent(
+        ManifestBuilder::new(&NetworkDefinition::simulator())
+            .withdraw_from_account_by_amount(dec!("10"), RADIX_TOKEN, taker_account)
+            .call_method(
+                maker_account,
+                "deposit_batch",
+                args!(Expression::entire_worktop()),
+            )
+            .build(),
+        &maker_private_key,
+    );
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(dec!("10"), taker_account)
+        .execute_subintent(0)
+        .build();
+    let mut transaction = TestTransaction::new(
+        manifest,
+        test_runner.next_transaction_nonce(),
+        vec![taker_public_key.into()],
+    );
+    transaction
+        .transaction
+        .signed_intent
+        .intent
+        .subintents
+        .push(subintent);
+    let receipt = test_runner.execute_transaction(
+        &transaction,
+        &FeeReserveConfig::standard(),
+        &ExecutionConfig::standard(),
+    );
+
+    // Assert
+    receipt.expect_specific_failure(is_auth_error);
+}
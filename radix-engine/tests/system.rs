@@ -1,8 +1,16 @@
-use radix_engine::engine::{ModuleError, RuntimeError};
+use radix_engine::constants::DEFAULT_COST_UNIT_PRICE;
+use radix_engine::engine::{
+    ApplicationError, ApplicationEvent, KernelError, ModuleError, RuntimeError,
+};
+use radix_engine::fee::FeeTable;
 use radix_engine::ledger::TypedInMemorySubstateStore;
+use radix_engine::model::SystemError;
 use radix_engine::types::*;
+use radix_engine::wasm::WasmError;
+use scrypto::crypto::{blake2b, sha256, sha3};
 use scrypto_unit::*;
 use transaction::builder::ManifestBuilder;
+use transaction::signing::{EcdsaSecp256k1PrivateKey, EddsaEd25519PrivateKey};
 
 #[test]
 fn test_get_epoch() {
@@ -24,6 +32,276 @@ fn test_get_epoch() {
     assert_eq!(epoch, 0);
 }
 
+#[test]
+fn test_get_cost_unit_price() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let package_address = test_runner.compile_and_publish("./tests/system");
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_function(
+            package_address,
+            "SystemTest",
+            "get_cost_unit_price",
+            args![],
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let outputs = receipt.expect_commit_success();
+    let cost_unit_price: Decimal = scrypto_decode(&outputs[1]).unwrap();
+    assert_eq!(
+        cost_unit_price,
+        receipt.execution.fee_summary.cost_unit_price
+    );
+    assert_eq!(
+        cost_unit_price,
+        Decimal::from_str(DEFAULT_COST_UNIT_PRICE).unwrap()
+    );
+}
+
+#[test]
+fn test_get_transaction_signers() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let package_address = test_runner.compile_and_publish("./tests/system");
+    let (public_key, _, _) = test_runner.new_account();
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_function(
+            package_address,
+            "SystemTest",
+            "get_transaction_signers",
+            args![],
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![public_key.into()]);
+
+    // Assert
+    let outputs = receipt.expect_commit_success();
+    let signers: Vec<NonFungibleAddress> = scrypto_decode(&outputs[1]).unwrap();
+    assert_eq!(
+        signers,
+        vec![NonFungibleAddress::from_public_key(&public_key)]
+    );
+}
+
+#[test]
+fn test_random_bytes() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let package_address = test_runner.compile_and_publish("./tests/system");
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_function(package_address, "SystemTest", "random_bytes", args![40u32])
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let outputs = receipt.expect_commit_success();
+    let (first, second): (Vec<u8>, Vec<u8>) = scrypto_decode(&outputs[1]).unwrap();
+    assert_eq!(first.len(), 40);
+    assert_eq!(second.len(), 40);
+    assert_ne!(first, second);
+}
+
+#[test]
+fn test_random_bytes_length_is_capped() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let package_address = test_runner.compile_and_publish("./tests/system");
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_function(
+            package_address,
+            "SystemTest",
+            "random_bytes",
+            args![u32::MAX],
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::KernelError(KernelError::RandomBytesLengthExceeded { .. })
+        )
+    });
+}
+
+#[test]
+fn test_emit_event() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let package_address = test_runner.compile_and_publish("./tests/system");
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_function(
+            package_address,
+            "SystemTest",
+            "emit_event",
+            args![5u32, false],
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(
+        receipt.execution.events,
+        vec![ApplicationEvent {
+            emitter: Some(CallerOrigin::Package(package_address)),
+            name: "ValueSet".to_owned(),
+            data: scrypto_encode(&5u32),
+        }]
+    );
+    let json = receipt.to_json(&NetworkDefinition::simulator());
+    assert_eq!(json["events"][0]["name"], "ValueSet");
+}
+
+#[test]
+fn test_events_are_dropped_on_failure() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let package_address = test_runner.compile_and_publish("./tests/system");
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_function(
+            package_address,
+            "SystemTest",
+            "emit_event",
+            args![5u32, true],
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_commit_failure();
+    assert!(receipt.execution.events.is_empty());
+}
+
+#[test]
+fn test_remaining_cost_units() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let package_address = test_runner.compile_and_publish("./tests/system");
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_function(
+            package_address,
+            "SystemTest",
+            "remaining_cost_units",
+            args![],
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let outputs = receipt.expect_commit_success();
+    let (first, second): (u32, u32) = scrypto_decode(&outputs[1]).unwrap();
+    assert!(first > second);
+    assert!(second > 0);
+}
+
+#[test]
+fn test_hash() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let package_address = test_runner.compile_and_publish("./tests/system");
+    let data = b"Hello Radix".to_vec();
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_function(package_address, "SystemTest", "hash", args![data.clone()])
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let outputs = receipt.expect_commit_success();
+    let (sha256_hash, sha3_hash, blake2b_hash): (Hash, Hash, Hash) =
+        scrypto_decode(&outputs[1]).unwrap();
+    assert_eq!(sha256_hash, sha256(&data));
+    assert_eq!(sha3_hash, sha3(&data));
+    assert_eq!(blake2b_hash, blake2b(&data));
+}
+
+#[test]
+fn test_verify_signature() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let package_address = test_runner.compile_and_publish("./tests/system");
+    let message = b"Hello Radix".to_vec();
+    let ecdsa_key = EcdsaSecp256k1PrivateKey::from_u64(1).unwrap();
+    let eddsa_key = EddsaEd25519PrivateKey::from_u64(1).unwrap();
+    let cases = vec![
+        (
+            PublicKey::EcdsaSecp256k1(ecdsa_key.public_key()),
+            Signature::EcdsaSecp256k1(ecdsa_key.sign(&message)),
+            true,
+        ),
+        (
+            PublicKey::EddsaEd25519(eddsa_key.public_key()),
+            Signature::EddsaEd25519(eddsa_key.sign(&message)),
+            true,
+        ),
+        (
+            PublicKey::EddsaEd25519(eddsa_key.public_key()),
+            Signature::EddsaEd25519(eddsa_key.sign(b"Other message")),
+            false,
+        ),
+        (
+            PublicKey::EcdsaSecp256k1(ecdsa_key.public_key()),
+            Signature::EddsaEd25519(eddsa_key.sign(&message)),
+            false,
+        ),
+    ];
+
+    for (public_key, signature, expected) in cases {
+        // Act
+        let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+            .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+            .call_function(
+                package_address,
+                "SystemTest",
+                "verify_signature",
+                args![message.clone(), public_key, signature],
+            )
+            .build();
+        let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+        // Assert
+        let outputs = receipt.expect_commit_success();
+        let is_valid: bool = scrypto_decode(&outputs[1]).unwrap();
+        assert_eq!(is_valid, expected);
+    }
+}
+
 #[test]
 fn test_set_epoch_without_system_auth_fails() {
     // Arrange
@@ -47,4 +325,86 @@ fn test_set_epoch_without_system_auth_fails() {
             RuntimeError::ModuleError(ModuleError::AuthorizationError { .. })
         )
     });
+    assert_eq!(receipt.error_code(), Some(2001));
+}
+
+#[test]
+fn test_batch() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let package_address = test_runner.compile_and_publish("./tests/system");
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_function(package_address, "SystemTest", "batch", args![])
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let outputs = receipt.expect_commit_success();
+    let (first, second): (u128, u128) = scrypto_decode(&outputs[1]).unwrap();
+    assert_ne!(first, second);
+    assert_eq!(receipt.execution.application_logs.len(), 1);
+}
+
+#[test]
+fn test_nested_batch_fails() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let package_address = test_runner.compile_and_publish("./tests/system");
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_function(package_address, "SystemTest", "nested_batch", args![])
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        if let RuntimeError::KernelError(KernelError::WasmError(b)) = e {
+            matches!(*b, WasmError::InvalidBatchInput)
+        } else {
+            false
+        }
+    });
+}
+
+#[test]
+fn test_set_fee_table() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let fee_table = FeeTable::new().with_version(2);
+
+    // Act
+    test_runner.set_fee_table(fee_table.clone()).unwrap();
+
+    // Assert
+    assert_eq!(test_runner.get_fee_table(), fee_table);
+}
+
+#[test]
+fn test_set_fee_table_with_stale_version_fails() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+
+    // Act
+    let result = test_runner.set_fee_table(FeeTable::new());
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(RuntimeError::ApplicationError(
+            ApplicationError::SystemError(SystemError::InvalidFeeTableVersion {
+                current: 1,
+                new: 1
+            })
+        ))
+    ));
+    assert_eq!(test_runner.get_fee_table().version(), 1);
 }
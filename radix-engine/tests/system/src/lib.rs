@@ -9,6 +9,64 @@ blueprint! {
             Runtime::current_epoch()
         }
 
+        pub fn get_cost_unit_price() -> Decimal {
+            Runtime::cost_unit_price()
+        }
+
+        pub fn get_transaction_signers() -> Vec<NonFungibleAddress> {
+            Runtime::transaction_signers()
+        }
+
+        pub fn remaining_cost_units() -> (u32, u32) {
+            (
+                Runtime::remaining_cost_units(),
+                Runtime::remaining_cost_units(),
+            )
+        }
+
+        pub fn hash(data: Vec<u8>) -> (Hash, Hash, Hash) {
+            (
+                Runtime::hash(HashAlgorithm::Sha256, &data),
+                Runtime::hash(HashAlgorithm::Sha3, &data),
+                Runtime::hash(HashAlgorithm::Blake2b, &data),
+            )
+        }
+
+        pub fn emit_event(value: u32, fail: bool) {
+            Runtime::emit_event("ValueSet", &value);
+            assert!(!fail, "Failing after emitting event");
+        }
+
+        pub fn verify_signature(
+            message: Vec<u8>,
+            public_key: PublicKey,
+            signature: Signature,
+        ) -> bool {
+            Runtime::verify_signature(message, public_key, signature)
+        }
+
+        pub fn random_bytes(len: u32) -> (Vec<u8>, Vec<u8>) {
+            (Runtime::random_bytes(len), Runtime::random_bytes(len))
+        }
+
+        pub fn batch() -> (u128, u128) {
+            let input = RadixEngineInput::Batch(vec![
+                RadixEngineInput::GenerateUuid(),
+                RadixEngineInput::EmitLog(Level::Info, "Batched".to_owned()),
+                RadixEngineInput::GenerateUuid(),
+            ]);
+            let outputs: Vec<Vec<u8>> = call_engine(input);
+            (
+                scrypto_decode(&outputs[0]).unwrap(),
+                scrypto_decode(&outputs[2]).unwrap(),
+            )
+        }
+
+        pub fn nested_batch() {
+            let input = RadixEngineInput::Batch(vec![RadixEngineInput::Batch(vec![])]);
+            let _: Vec<Vec<u8>> = call_engine(input);
+        }
+
         pub fn set_epoch(epoch: u64) {
             let input = RadixEngineInput::InvokeMethod(
                 Receiver::Ref(RENodeId::System),
@@ -0,0 +1,97 @@
+use radix_engine::test_utils::*;
+use radix_engine::types::*;
+use scrypto::resource::Vault;
+use transaction::builder::ManifestBuilder;
+
+#[derive(Decode)]
+struct ComponentTest {
+    _test_vault: Vault,
+    secret: String,
+}
+
+#[test]
+fn test_runner_tracks_account_balances() {
+    // Arrange
+    let mut test_runner = TestRunner::new(true);
+    let (public_key, _, account) = test_runner.create_account();
+    let (_, _, other_account) = test_runner.create_account();
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .withdraw_from_account_by_amount(100.into(), RADIX_TOKEN, account)
+        .call_method(
+            other_account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let receipt = test_runner.call(manifest, vec![public_key.into()]);
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(test_runner.get_balance(account, RADIX_TOKEN), dec!("900"));
+    assert_eq!(
+        test_runner.get_balance(other_account, RADIX_TOKEN),
+        dec!("1100")
+    );
+}
+
+#[test]
+fn test_runner_decodes_component_state() {
+    // Arrange
+    let mut test_runner = TestRunner::new(true);
+    let (_, _, account) = test_runner.create_account();
+    let package_address = test_runner.compile_and_publish("./tests/component");
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_function(
+            package_address,
+            "ComponentTest",
+            "create_component",
+            args!(),
+        )
+        .build();
+    let receipt = test_runner.call(manifest, vec![]);
+    receipt.expect_commit_success();
+    let component_address = receipt.new_component_addresses()[0];
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .call_method(component_address, "put_component_state", args!())
+        .call_method(
+            account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let receipt = test_runner.call(manifest, vec![]);
+
+    // Assert
+    receipt.expect_commit_success();
+    let state: ComponentTest = test_runner.get_component_state(component_address);
+    assert_eq!(state.secret, "New secret");
+}
+
+#[test]
+fn test_runner_reports_auth_errors() {
+    // Arrange
+    let mut test_runner = TestRunner::new(true);
+    let (_, _, account) = test_runner.create_account();
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method(
+            account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let receipt = test_runner.call(manifest, vec![]);
+
+    // Assert
+    receipt.expect_specific_failure(is_auth_error);
+}
@@ -67,3 +67,31 @@ fn test_state_track_failure() {
     ); // only the vault is down
     assert_eq!(1, receipt.expect_commit().state_updates.up_substates.len());
 }
+
+#[test]
+fn test_substate_cache_stats() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (public_key, _, account) = test_runner.new_account();
+    let (_, _, other_account) = test_runner.new_account();
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), account)
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method(
+            other_account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![public_key.into()]);
+
+    // Assert
+    receipt.expect_commit_success();
+    let stats = &receipt.execution.substate_cache_stats;
+    assert!(stats.hits > 0);
+    assert!(stats.misses > 0);
+    assert!(stats.dirty > 0);
+}
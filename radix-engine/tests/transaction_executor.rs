@@ -1,16 +1,21 @@
 use radix_engine::constants::*;
 use radix_engine::engine::RuntimeError;
-use radix_engine::engine::{ModuleError, RejectionError};
-use radix_engine::ledger::TypedInMemorySubstateStore;
-use radix_engine::transaction::TransactionExecutor;
+use radix_engine::engine::{
+    KernelError, ModuleError, NativeFnProvider, NativeFnRegistry, RejectionError, SystemApi,
+};
+use radix_engine::fee::{FeeDistribution, FeeReserve, FeeReserveError};
+use radix_engine::ledger::{ReadableSubstateStore, TypedInMemorySubstateStore};
 use radix_engine::transaction::{ExecutionConfig, FeeReserveConfig};
+use radix_engine::transaction::{TransactionExecutor, RECEIPT_JSON_VERSION};
 use radix_engine::types::*;
 use radix_engine::wasm::DefaultWasmEngine;
-use radix_engine::wasm::WasmInstrumenter;
+use radix_engine::wasm::{WasmEngine, WasmInstance, WasmInstrumenter};
 use scrypto_unit::*;
 use transaction::builder::ManifestBuilder;
 use transaction::builder::TransactionBuilder;
-use transaction::model::{NotarizedTransaction, TransactionHeader, Validated};
+use transaction::model::{
+    NotarizedTransaction, TestTransaction, TransactionHeader, TransactionManifest, Validated,
+};
 use transaction::signing::EcdsaSecp256k1PrivateKey;
 use transaction::validation::{
     NotarizedTransactionValidator, TestIntentHashManager, TransactionValidator, ValidationConfig,
@@ -21,7 +26,7 @@ fn pre_execution_rejection_should_return_rejected_receipt() {
     // Arrange
     let mut substate_store = TypedInMemorySubstateStore::with_bootstrap();
     let mut test_runner = TestRunner::new(true, &mut substate_store);
-    let executable_transaction = create_executable_transaction(1);
+    let executable_transaction = create_executable_transaction(1, None);
 
     // Act
     let receipt = test_runner.execute_transaction(
@@ -51,7 +56,7 @@ fn test_normal_transaction_flow() {
     let intent_hash_manager = TestIntentHashManager::new();
     let fee_reserve_config = FeeReserveConfig::standard();
     let execution_config = ExecutionConfig::debug();
-    let raw_transaction = create_notarized_transaction(1_000_000).to_bytes();
+    let raw_transaction = create_notarized_transaction(1_000_000, None).to_bytes();
 
     let validator = NotarizedTransactionValidator::new(ValidationConfig {
         network_id: NetworkDefinition::simulator().id,
@@ -80,8 +85,406 @@ fn test_normal_transaction_flow() {
     receipt.expect_commit_success();
 }
 
-fn create_executable_transaction(cost_unit_limit: u32) -> Validated<NotarizedTransaction> {
-    let notarized_transaction = create_notarized_transaction(cost_unit_limit);
+#[test]
+fn accessing_undeclared_substate_should_fail() {
+    // Arrange
+    let mut substate_store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut substate_store);
+    let executable_transaction = create_executable_transaction(1_000_000, Some(vec![]));
+
+    // Act
+    let receipt = test_runner.execute_transaction(
+        &executable_transaction,
+        &FeeReserveConfig::standard(),
+        &ExecutionConfig::standard(),
+    );
+
+    // Assert
+    let rejection_error = receipt.expect_rejection();
+    if !matches!(
+        rejection_error,
+        RejectionError::ErrorBeforeFeeLoanRepaid(RuntimeError::KernelError(
+            KernelError::SubstateNotDeclared(..)
+        ))
+    ) {
+        panic!(
+            "Expected undeclared substate error but was {}",
+            rejection_error
+        );
+    }
+}
+
+#[test]
+fn exceeding_max_heap_size_should_fail() {
+    // Arrange
+    let mut substate_store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut substate_store);
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .call_method(SYS_FAUCET_COMPONENT, "free_xrd", args!())
+        .build();
+    let executable_transaction = TestTransaction::new(manifest, 1, vec![]);
+
+    // Act
+    let receipt = test_runner.execute_transaction(
+        &executable_transaction,
+        &FeeReserveConfig::standard(),
+        &ExecutionConfig {
+            max_heap_size: 0,
+            ..ExecutionConfig::standard()
+        },
+    );
+
+    // Assert
+    let rejection_error = receipt.expect_rejection();
+    if !matches!(
+        rejection_error,
+        RejectionError::ErrorBeforeFeeLoanRepaid(RuntimeError::KernelError(
+            KernelError::MaxHeapSizeExceeded { .. }
+        ))
+    ) {
+        panic!("Expected heap size error but was {}", rejection_error);
+    }
+    assert_eq!(receipt.error_code(), Some(4002));
+    assert_eq!(rejection_error.cause_error_code(), Some(1006));
+}
+
+#[test]
+fn free_credit_should_pay_for_transaction_without_lock_fee() {
+    // Arrange
+    let mut substate_store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut substate_store);
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .clear_auth_zone()
+        .build();
+    let executable_transaction = TestTransaction::new(manifest, 1, vec![]);
+
+    // Act
+    let receipt = test_runner.execute_transaction(
+        &executable_transaction,
+        &FeeReserveConfig {
+            free_credit: DEFAULT_SYSTEM_LOAN,
+            ..FeeReserveConfig::standard()
+        },
+        &ExecutionConfig::standard(),
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+    let fee_summary = &receipt.execution.fee_summary;
+    assert!(fee_summary.payments.is_empty());
+    assert_eq!(fee_summary.free_credit, fee_summary.total_fee());
+}
+
+#[test]
+fn invalid_fee_distribution_should_return_rejected_receipt() {
+    // Arrange
+    let mut substate_store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut substate_store);
+    let executable_transaction = create_executable_transaction(1_000_000, None);
+
+    // Act
+    let receipt = test_runner.execute_transaction(
+        &executable_transaction,
+        &FeeReserveConfig {
+            distribution: FeeDistribution {
+                burn_percentage: 100,
+                network_percentage: 10,
+                royalty_percentage: 0,
+            },
+            ..FeeReserveConfig::standard()
+        },
+        &ExecutionConfig::standard(),
+    );
+
+    // Assert
+    let rejection_error = receipt.expect_rejection();
+    if !matches!(
+        rejection_error,
+        RejectionError::ErrorBeforeFeeLoanRepaid(RuntimeError::ModuleError(
+            ModuleError::CostingError(FeeReserveError::InvalidDistribution)
+        ))
+    ) {
+        panic!("Expected invalid distribution but was {}", rejection_error);
+    }
+}
+
+#[test]
+fn network_fee_should_be_paid_to_treasury() {
+    // Arrange
+    let mut substate_store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut wasm_engine = DefaultWasmEngine::new();
+    let mut wasm_instrumenter = WasmInstrumenter::new();
+    let fee_reserve_config = FeeReserveConfig {
+        distribution: FeeDistribution {
+            burn_percentage: 50,
+            network_percentage: 50,
+            royalty_percentage: 0,
+        },
+        ..FeeReserveConfig::standard()
+    };
+    let executable_transaction = create_executable_transaction(1_000_000, None);
+
+    // Act
+    let receipt = TransactionExecutor::new(
+        &mut substate_store,
+        &mut wasm_engine,
+        &mut wasm_instrumenter,
+    )
+    .execute_and_commit(
+        &executable_transaction,
+        &fee_reserve_config,
+        &ExecutionConfig::standard(),
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+    let network_fee = receipt.execution.fee_summary.network_fee;
+    assert!(network_fee.is_positive());
+    let treasury = substate_store
+        .get_substate(&SubstateId::Vault(SYS_TREASURY_VAULT))
+        .unwrap();
+    assert_eq!(treasury.substate.vault().total_amount(), network_fee);
+}
+
+#[test]
+fn network_fee_should_be_capped_at_fees_collected_beyond_free_credit() {
+    // Arrange
+    let mut substate_store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut wasm_engine = DefaultWasmEngine::new();
+    let mut wasm_instrumenter = WasmInstrumenter::new();
+    let fee_reserve_config = FeeReserveConfig {
+        distribution: FeeDistribution {
+            burn_percentage: 50,
+            network_percentage: 50,
+            royalty_percentage: 0,
+        },
+        // Covers all fees, so that nothing is collected for the network share
+        free_credit: 1_000_000,
+        ..FeeReserveConfig::standard()
+    };
+    let executable_transaction = create_executable_transaction(1_000_000, None);
+
+    // Act
+    let receipt = TransactionExecutor::new(
+        &mut substate_store,
+        &mut wasm_engine,
+        &mut wasm_instrumenter,
+    )
+    .execute_and_commit(
+        &executable_transaction,
+        &fee_reserve_config,
+        &ExecutionConfig::standard(),
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+    let fee_summary = &receipt.execution.fee_summary;
+    assert_eq!(fee_summary.free_credit, fee_summary.total_fee());
+    assert_eq!(fee_summary.network_fee, Decimal::zero());
+    let treasury = substate_store
+        .get_substate(&SubstateId::Vault(SYS_TREASURY_VAULT))
+        .unwrap();
+    assert_eq!(
+        treasury.substate.vault().total_amount(),
+        fee_summary.network_fee
+    );
+}
+
+/// Replaces resource creation with a handler which creates nothing.
+struct NoopResourceCreation;
+
+impl NativeFnProvider for NoopResourceCreation {
+    fn register<'s, Y, W, I, R>(&self, registry: &mut NativeFnRegistry<Y>)
+    where
+        Y: SystemApi<'s, W, I, R>,
+        W: WasmEngine<I>,
+        I: WasmInstance,
+        R: FeeReserve,
+    {
+        registry.register(
+            NativeFnIdentifier::ResourceManager(ResourceManagerFnIdentifier::Create),
+            |_, _, _, _, _| Ok(ScryptoValue::from_typed(&())),
+        );
+    }
+}
+
+#[test]
+fn custom_native_fn_should_replace_standard_one() {
+    // Arrange
+    let mut substate_store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut wasm_engine = DefaultWasmEngine::new();
+    let mut wasm_instrumenter = WasmInstrumenter::new();
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), SYS_FAUCET_COMPONENT)
+        .new_token_fixed(HashMap::new(), 100.into())
+        .build();
+    let executable_transaction = TestTransaction::new(manifest, 1, vec![]);
+
+    // Act
+    let receipt = TransactionExecutor::with_native_fns(
+        &mut substate_store,
+        &mut wasm_engine,
+        &mut wasm_instrumenter,
+        NoopResourceCreation,
+    )
+    .execute_and_commit(
+        &executable_transaction,
+        &FeeReserveConfig::standard(),
+        &ExecutionConfig::standard(),
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+    assert!(receipt.new_resource_addresses().is_empty());
+}
+
+#[test]
+fn fee_payer_can_sponsor_transaction_fee() {
+    // Arrange
+    let mut substate_store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut substate_store);
+    let (_, user_sk, user_account) = test_runner.new_account();
+    let (sponsor_pk, sponsor_sk, sponsor_account) = test_runner.new_account();
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), sponsor_account)
+        .withdraw_from_account_by_amount(1.into(), RADIX_TOKEN, user_account)
+        .call_method(
+            user_account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let transaction = create_sponsored_transaction(manifest, &user_sk, &sponsor_sk);
+
+    // Act
+    let receipt = test_runner.execute_transaction(
+        &transaction,
+        &FeeReserveConfig::standard(),
+        &ExecutionConfig::standard(),
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(
+        receipt.execution.fee_payer,
+        Some(NonFungibleAddress::from_public_key(&sponsor_pk))
+    );
+}
+
+#[test]
+fn fee_payer_badge_should_only_be_usable_to_lock_fee() {
+    // Arrange
+    let mut substate_store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut substate_store);
+    let (_, user_sk, user_account) = test_runner.new_account();
+    let (_, sponsor_sk, sponsor_account) = test_runner.new_account();
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), sponsor_account)
+        .withdraw_from_account_by_amount(1.into(), RADIX_TOKEN, sponsor_account)
+        .call_method(
+            user_account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let transaction = create_sponsored_transaction(manifest, &user_sk, &sponsor_sk);
+
+    // Act
+    let receipt = test_runner.execute_transaction(
+        &transaction,
+        &FeeReserveConfig::standard(),
+        &ExecutionConfig::standard(),
+    );
+
+    // Assert
+    receipt.expect_specific_failure(is_auth_error);
+}
+
+#[test]
+fn receipt_should_serialize_to_json() {
+    // Arrange
+    let mut substate_store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut substate_store);
+    let (public_key, _, account) = test_runner.new_account();
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), account)
+        .new_token_fixed(HashMap::new(), 100.into())
+        .call_method(
+            account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![public_key.into()]);
+
+    // Act
+    let json = receipt.to_json(&NetworkDefinition::simulator());
+
+    // Assert
+    let resource_address = receipt.new_resource_addresses()[0];
+    assert_eq!(json["version"], RECEIPT_JSON_VERSION);
+    assert_eq!(json["status"], "CommittedSuccess");
+    assert!(json["error"].is_null());
+    assert_eq!(
+        json["fee_summary"]["cost_unit_consumed"],
+        receipt.execution.fee_summary.cost_unit_consumed
+    );
+    assert_eq!(
+        json["fee_summary"]["total_fee"],
+        receipt.execution.fee_summary.total_fee().to_string()
+    );
+    assert_eq!(
+        json["new_entities"]["resources"][0],
+        Bech32Encoder::for_simulator().encode_resource_address(&resource_address)
+    );
+    assert_eq!(json["outputs"][0], serde_json::json!({ "type": "Unit" }));
+    assert!(!json["state_updates"]["up_substates"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+}
+
+fn create_sponsored_transaction(
+    manifest: TransactionManifest,
+    user_sk: &EcdsaSecp256k1PrivateKey,
+    sponsor_sk: &EcdsaSecp256k1PrivateKey,
+) -> Validated<NotarizedTransaction> {
+    let sk_notary = EcdsaSecp256k1PrivateKey::from_u64(100).unwrap();
+    let transaction = TransactionBuilder::new()
+        .header(TransactionHeader {
+            version: 1,
+            network_id: NetworkDefinition::simulator().id,
+            start_epoch_inclusive: 0,
+            end_epoch_exclusive: 100,
+            nonce: 5,
+            notary_public_key: sk_notary.public_key().into(),
+            notary_as_signatory: false,
+            cost_unit_limit: DEFAULT_COST_UNIT_LIMIT,
+            tip_percentage: 5,
+            declared_substates: None,
+            fee_payer: Some(sponsor_sk.public_key().into()),
+        })
+        .manifest(manifest)
+        .sign(user_sk)
+        .sign(sponsor_sk)
+        .notarize(&sk_notary)
+        .build();
+
+    NotarizedTransactionValidator::new(ValidationConfig {
+        network_id: NetworkDefinition::simulator().id,
+        current_epoch: 1,
+        max_cost_unit_limit: DEFAULT_COST_UNIT_LIMIT,
+        min_tip_percentage: 0,
+    })
+    .validate(transaction, &TestIntentHashManager::new())
+    .unwrap()
+}
+
+fn create_executable_transaction(
+    cost_unit_limit: u32,
+    declared_substates: Option<Vec<SubstateId>>,
+) -> Validated<NotarizedTransaction> {
+    let notarized_transaction = create_notarized_transaction(cost_unit_limit, declared_substates);
 
     let validator = NotarizedTransactionValidator::new(ValidationConfig {
         network_id: NetworkDefinition::simulator().id,
@@ -95,7 +498,10 @@ fn create_executable_transaction(cost_unit_limit: u32) -> Validated<NotarizedTra
         .unwrap()
 }
 
-fn create_notarized_transaction(cost_unit_limit: u32) -> NotarizedTransaction {
+fn create_notarized_transaction(
+    cost_unit_limit: u32,
+    declared_substates: Option<Vec<SubstateId>>,
+) -> NotarizedTransaction {
     // create key pairs
     let sk1 = EcdsaSecp256k1PrivateKey::from_u64(1).unwrap();
     let sk2 = EcdsaSecp256k1PrivateKey::from_u64(2).unwrap();
@@ -112,6 +518,8 @@ fn create_notarized_transaction(cost_unit_limit: u32) -> NotarizedTransaction {
             notary_as_signatory: false,
             cost_unit_limit,
             tip_percentage: 5,
+            declared_substates,
+            fee_payer: None,
         })
         .manifest(
             ManifestBuilder::new(&NetworkDefinition::simulator())
@@ -1,7 +1,9 @@
+use radix_engine::engine::ApplicationError;
 use radix_engine::engine::DropFailure;
 use radix_engine::engine::KernelError;
 use radix_engine::engine::RuntimeError;
 use radix_engine::ledger::TypedInMemorySubstateStore;
+use radix_engine::model::{WorktopError, WorktopResource};
 use radix_engine::types::*;
 use scrypto_unit::*;
 use transaction::builder::ManifestBuilder;
@@ -28,3 +30,214 @@ fn test_worktop_resource_leak() {
         )
     });
 }
+
+fn is_worktop_assertion_failure(e: &RuntimeError) -> bool {
+    matches!(
+        e,
+        RuntimeError::ApplicationError(ApplicationError::WorktopError(
+            WorktopError::AssertionFailed
+        ))
+    )
+}
+
+#[test]
+fn test_assert_worktop_contains_by_amount() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (public_key, _, account) = test_runner.new_account();
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), account)
+        .withdraw_from_account_by_amount(5.into(), RADIX_TOKEN, account)
+        .assert_worktop_contains(RADIX_TOKEN)
+        .assert_worktop_contains_by_amount(5.into(), RADIX_TOKEN)
+        .call_method(
+            account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![public_key.into()]);
+
+    // Assert
+    receipt.expect_commit_success();
+}
+
+#[test]
+fn test_assert_worktop_contains_by_amount_fails_on_shortfall() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (public_key, _, account) = test_runner.new_account();
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), account)
+        .withdraw_from_account_by_amount(5.into(), RADIX_TOKEN, account)
+        .assert_worktop_contains_by_amount(6.into(), RADIX_TOKEN)
+        .call_method(
+            account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![public_key.into()]);
+
+    // Assert
+    receipt.expect_specific_failure(is_worktop_assertion_failure);
+}
+
+#[test]
+fn test_assert_worktop_contains_fails_on_empty_worktop() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (public_key, _, account) = test_runner.new_account();
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), account)
+        .assert_worktop_contains(RADIX_TOKEN)
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![public_key.into()]);
+
+    // Assert
+    receipt.expect_specific_failure(is_worktop_assertion_failure);
+}
+
+#[test]
+fn test_assert_worktop_contains_by_ids() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (public_key, _, account) = test_runner.new_account();
+    let resource_address = test_runner.create_non_fungible_resource(account);
+    let mut ids = BTreeSet::new();
+    ids.insert(NonFungibleId::from_u32(1));
+    ids.insert(NonFungibleId::from_u32(2));
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), account)
+        .withdraw_from_account_by_ids(&ids, resource_address, account)
+        .assert_worktop_contains_by_ids(&ids, resource_address)
+        .call_method(
+            account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![public_key.into()]);
+
+    // Assert
+    receipt.expect_commit_success();
+}
+
+#[test]
+fn test_assert_worktop_contains_by_ids_fails_on_missing_id() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (public_key, _, account) = test_runner.new_account();
+    let resource_address = test_runner.create_non_fungible_resource(account);
+    let mut withdrawn_ids = BTreeSet::new();
+    withdrawn_ids.insert(NonFungibleId::from_u32(1));
+    let mut asserted_ids = withdrawn_ids.clone();
+    asserted_ids.insert(NonFungibleId::from_u32(2));
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), account)
+        .withdraw_from_account_by_ids(&withdrawn_ids, resource_address, account)
+        .assert_worktop_contains_by_ids(&asserted_ids, resource_address)
+        .call_method(
+            account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![public_key.into()]);
+
+    // Assert
+    receipt.expect_specific_failure(is_worktop_assertion_failure);
+}
+
+#[test]
+fn test_instruction_outputs_record_worktop_changes_and_new_ids() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (public_key, _, account) = test_runner.new_account();
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), account)
+        .withdraw_from_account_by_amount(5.into(), RADIX_TOKEN, account)
+        .take_from_worktop_by_amount(2.into(), RADIX_TOKEN, |builder, bucket_id| {
+            builder.create_proof_from_bucket(bucket_id, |builder, proof_id| {
+                builder.drop_proof(proof_id).return_to_worktop(bucket_id)
+            })
+        })
+        .call_method(
+            account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![public_key.into()]);
+
+    // Assert
+    receipt.expect_commit_success();
+    let withdraw = receipt.instruction_output(1);
+    assert_eq!(withdraw.worktop_changes, vec![(RADIX_TOKEN, 5.into())]);
+    let take = receipt.instruction_output(2);
+    assert_eq!(take.new_bucket_ids.len(), 1);
+    assert_eq!(take.worktop_changes, vec![(RADIX_TOKEN, (-2).into())]);
+    let create_proof = receipt.instruction_output(3);
+    assert_eq!(create_proof.new_proof_ids.len(), 1);
+    assert!(create_proof.worktop_changes.is_empty());
+    let return_to_worktop = receipt.instruction_output(5);
+    assert_eq!(
+        return_to_worktop.worktop_changes,
+        vec![(RADIX_TOKEN, 2.into())]
+    );
+    let deposit = receipt.instruction_output(6);
+    assert_eq!(deposit.worktop_changes, vec![(RADIX_TOKEN, (-5).into())]);
+}
+
+#[test]
+fn test_return_worktop_contents() {
+    // Arrange
+    let mut store = TypedInMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(true, &mut store);
+    let (public_key, _, account) = test_runner.new_account();
+
+    // Act
+    let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+        .lock_fee(10.into(), account)
+        .return_worktop_contents()
+        .withdraw_from_account_by_amount(5.into(), RADIX_TOKEN, account)
+        .return_worktop_contents()
+        .call_method(
+            account,
+            "deposit_batch",
+            args!(Expression::entire_worktop()),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![public_key.into()]);
+
+    // Assert
+    let empty: Vec<WorktopResource> = receipt.output(1);
+    assert!(empty.is_empty());
+    let contents: Vec<WorktopResource> = receipt.output(3);
+    assert_eq!(
+        contents,
+        vec![WorktopResource {
+            resource_address: RADIX_TOKEN,
+            amount: 5.into(),
+            ids: None,
+        }]
+    );
+}
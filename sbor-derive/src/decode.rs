@@ -1,5 +1,6 @@
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
+use syn::spanned::Spanned;
 use syn::*;
 
 use crate::utils::*;
@@ -14,57 +15,74 @@ macro_rules! trace {
 pub fn handle_decode(input: TokenStream) -> Result<TokenStream> {
     trace!("handle_decode() starts");
 
-    let DeriveInput { ident, data, .. } = parse2(input)?;
+    let DeriveInput {
+        attrs,
+        ident,
+        data,
+        generics,
+        ..
+    } = parse2(input)?;
+    let generics = add_trait_bounds(
+        generics,
+        &[parse_quote!(::sbor::Decode), parse_quote!(::sbor::TypeId)],
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     trace!("Decoding: {}", ident);
 
+    if let Some(TransparentField {
+        member,
+        ty,
+        skipped,
+    }) = transparent_field(&attrs, &data)?
+    {
+        return Ok(quote! {
+            impl #impl_generics ::sbor::Decode for #ident #ty_generics #where_clause {
+                #[inline]
+                fn check_type_id(decoder: &mut ::sbor::Decoder) -> Result<(), ::sbor::DecodeError> {
+                    <#ty as ::sbor::Decode>::check_type_id(decoder)
+                }
+                fn decode_value(decoder: &mut ::sbor::Decoder) -> Result<Self, ::sbor::DecodeError> {
+                    Ok(Self {
+                        #member: <#ty as ::sbor::Decode>::decode_value(decoder)?,
+                        #(#skipped: Default::default(),)*
+                    })
+                }
+            }
+        });
+    }
+
     let output = match data {
         Data::Struct(s) => match s.fields {
             syn::Fields::Named(FieldsNamed { named, .. }) => {
-                // ns: not skipped, s: skipped
-                let ns: Vec<&Field> = named.iter().filter(|f| !is_skipped(f)).collect();
-                let ns_len = Index::from(ns.len());
-                let ns_ids = ns.iter().map(|f| &f.ident);
-                let ns_types = ns.iter().map(|f| &f.ty);
-                let s: Vec<&Field> = named.iter().filter(|f| is_skipped(f)).collect();
-                let s_ids = s.iter().map(|f| &f.ident);
-                let s_types = s.iter().map(|f| &f.ty);
+                let (check_size, fields) = decode_fields(&named)?;
+                let ids = named.iter().map(|f| &f.ident);
                 quote! {
-                    impl ::sbor::Decode for #ident {
+                    impl #impl_generics ::sbor::Decode for #ident #ty_generics #where_clause {
                         #[inline]
                         fn check_type_id(decoder: &mut ::sbor::Decoder) -> Result<(), ::sbor::DecodeError> {
                             decoder.check_type_id(::sbor::type_id::TYPE_STRUCT)
                         }
                         fn decode_value(decoder: &mut ::sbor::Decoder) -> Result<Self, ::sbor::DecodeError> {
                             use ::sbor::{self, Decode};
-                            decoder.check_static_size(#ns_len)?;
+                            #check_size
                             Ok(Self {
-                                #(#ns_ids: <#ns_types>::decode(decoder)?,)*
-                                #(#s_ids: <#s_types>::default()),*
+                                #(#ids: #fields,)*
                             })
                         }
                     }
                 }
             }
             syn::Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => {
-                let mut fields = Vec::<Expr>::new();
-                for f in &unnamed {
-                    let ty = &f.ty;
-                    if is_skipped(f) {
-                        fields.push(parse_quote! {<#ty>::default()})
-                    } else {
-                        fields.push(parse_quote! {<#ty>::decode(decoder)?})
-                    }
-                }
-                let ns_len = Index::from(unnamed.iter().filter(|f| !is_skipped(f)).count());
+                let (check_size, fields) = decode_fields(&unnamed)?;
                 quote! {
-                    impl ::sbor::Decode for #ident {
+                    impl #impl_generics ::sbor::Decode for #ident #ty_generics #where_clause {
                         #[inline]
                         fn check_type_id(decoder: &mut ::sbor::Decoder) -> Result<(), ::sbor::DecodeError> {
                             decoder.check_type_id(::sbor::type_id::TYPE_STRUCT)
                         }
                         fn decode_value(decoder: &mut ::sbor::Decoder) -> Result<Self, ::sbor::DecodeError> {
                             use ::sbor::{self, Decode};
-                            decoder.check_static_size(#ns_len)?;
+                            #check_size
                             Ok(Self (
                                 #(#fields,)*
                             ))
@@ -74,7 +92,7 @@ pub fn handle_decode(input: TokenStream) -> Result<TokenStream> {
             }
             syn::Fields::Unit => {
                 quote! {
-                    impl ::sbor::Decode for #ident {
+                    impl #impl_generics ::sbor::Decode for #ident #ty_generics #where_clause {
                         #[inline]
                         fn check_type_id(decoder: &mut ::sbor::Decoder) -> Result<(), ::sbor::DecodeError> {
                             decoder.check_type_id(::sbor::type_id::TYPE_STRUCT)
@@ -88,63 +106,51 @@ pub fn handle_decode(input: TokenStream) -> Result<TokenStream> {
             }
         },
         Data::Enum(DataEnum { variants, .. }) => {
-            let match_arms = variants.iter().map(|v| {
-                let v_id = &v.ident;
-                let name_string = v_id.to_string();
-                let name: Expr = parse_quote! { #name_string };
-
-                match &v.fields {
-                    syn::Fields::Named(FieldsNamed { named, .. }) => {
-                        let ns: Vec<&Field> = named.iter().filter(|f| !is_skipped(f)).collect();
-                        let ns_len = Index::from(ns.len());
-                        let ns_ids = ns.iter().map(|f| &f.ident);
-                        let ns_types = ns.iter().map(|f| &f.ty);
-                        let s: Vec<&Field> = named.iter().filter(|f| is_skipped(f)).collect();
-                        let s_ids = s.iter().map(|f| &f.ident);
-                        let s_types = s.iter().map(|f| &f.ty);
-                        quote! {
-                            #name => {
-                                decoder.check_static_size(#ns_len)?;
-                                Ok(Self::#v_id {
-                                    #(#ns_ids: <#ns_types>::decode(decoder)?,)*
-                                    #(#s_ids: <#s_types>::default(),)*
-                                })
+            let match_arms = variants
+                .iter()
+                .map(|v| {
+                    let v_id = &v.ident;
+                    let name_string = variant_name(v);
+                    let name: Expr = parse_quote! { #name_string };
+
+                    Ok(match &v.fields {
+                        syn::Fields::Named(FieldsNamed { named, .. }) => {
+                            let (check_size, fields) = decode_fields(named)?;
+                            let ids = named.iter().map(|f| &f.ident);
+                            quote! {
+                                #name => {
+                                    #check_size
+                                    Ok(Self::#v_id {
+                                        #(#ids: #fields,)*
+                                    })
+                                }
                             }
                         }
-                    }
-                    syn::Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => {
-                        let mut fields = Vec::<Expr>::new();
-                        for f in unnamed {
-                            let ty = &f.ty;
-                            if is_skipped(f) {
-                                fields.push(parse_quote! {<#ty>::default()})
-                            } else {
-                                fields.push(parse_quote! {<#ty>::decode(decoder)?})
+                        syn::Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => {
+                            let (check_size, fields) = decode_fields(unnamed)?;
+                            quote! {
+                                #name => {
+                                    #check_size
+                                    Ok(Self::#v_id (
+                                        #(#fields),*
+                                    ))
+                                }
                             }
                         }
-                        let ns_len = Index::from(unnamed.iter().filter(|f| !is_skipped(f)).count());
-                        quote! {
-                            #name => {
-                                decoder.check_static_size(#ns_len)?;
-                                Ok(Self::#v_id (
-                                    #(#fields),*
-                                ))
+                        syn::Fields::Unit => {
+                            quote! {
+                                #name => {
+                                    decoder.check_static_size(0)?;
+                                    Ok(Self::#v_id)
+                                }
                             }
                         }
-                    }
-                    syn::Fields::Unit => {
-                        quote! {
-                            #name => {
-                                decoder.check_static_size(0)?;
-                                Ok(Self::#v_id)
-                            }
-                        }
-                    }
-                }
-            });
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
 
             quote! {
-                impl ::sbor::Decode for #ident {
+                impl #impl_generics ::sbor::Decode for #ident #ty_generics #where_clause {
                     #[inline]
                     fn check_type_id(decoder: &mut ::sbor::Decoder) -> Result<(), ::sbor::DecodeError> {
                         decoder.check_type_id(::sbor::type_id::TYPE_ENUM)
@@ -174,6 +180,49 @@ pub fn handle_decode(input: TokenStream) -> Result<TokenStream> {
     Ok(output)
 }
 
+/// Generates the statement that checks the number of encoded fields, and the expressions that
+/// produce each field. Fields marked `#[sbor(default)]` must come after all other encoded fields,
+/// and take their default value when absent, e.g. from a payload encoded by an older version of
+/// the type.
+fn decode_fields<'a>(
+    fields: impl IntoIterator<Item = &'a Field>,
+) -> Result<(TokenStream, Vec<Expr>)> {
+    let mut exprs = Vec::<Expr>::new();
+    let mut required = 0;
+    let mut encoded = 0;
+    for f in fields {
+        let ty = &f.ty;
+        if is_skipped(f) {
+            exprs.push(parse_quote! {<#ty>::default()});
+            continue;
+        }
+        if is_defaulted(f) {
+            let index = Index::from(encoded);
+            exprs.push(parse_quote! {
+                if len > #index { <#ty>::decode(decoder)? } else { <#ty>::default() }
+            });
+        } else if required < encoded {
+            return Err(Error::new(
+                f.span(),
+                "Fields with #[sbor(default)] must come after all other fields",
+            ));
+        } else {
+            exprs.push(parse_quote! {<#ty>::decode(decoder)?});
+            required += 1;
+        }
+        encoded += 1;
+    }
+
+    let encoded = Index::from(encoded);
+    let check_size = if required == encoded.index as usize {
+        quote! { decoder.check_static_size(#encoded)?; }
+    } else {
+        let required = Index::from(required);
+        quote! { let len = decoder.check_static_size_in_range(#required, #encoded)?; }
+    };
+    Ok((check_size, exprs))
+}
+
 #[cfg(test)]
 mod tests {
     use proc_macro2::TokenStream;
@@ -249,4 +298,36 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn test_decode_struct_with_default_field() {
+        let input = TokenStream::from_str("struct Test {a: u32, #[sbor(default)] b: u8}").unwrap();
+        let output = handle_decode(input).unwrap();
+
+        assert_code_eq(
+            output,
+            quote! {
+                impl ::sbor::Decode for Test {
+                    #[inline]
+                    fn check_type_id(decoder: &mut ::sbor::Decoder) -> Result<(), ::sbor::DecodeError> {
+                        decoder.check_type_id(::sbor::type_id::TYPE_STRUCT)
+                    }
+                    fn decode_value(decoder: &mut ::sbor::Decoder) -> Result<Self, ::sbor::DecodeError> {
+                        use ::sbor::{self, Decode};
+                        let len = decoder.check_static_size_in_range(1, 2)?;
+                        Ok(Self {
+                            a: <u32>::decode(decoder)?,
+                            b: if len > 1 { <u8>::decode(decoder)? } else { <u8>::default() },
+                        })
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn test_decode_struct_with_non_trailing_default_field() {
+        let input = TokenStream::from_str("struct Test {#[sbor(default)] a: u32, b: u8}").unwrap();
+        assert!(handle_decode(input).is_err());
+    }
 }
@@ -14,26 +14,39 @@ macro_rules! trace {
 pub fn handle_describe(input: TokenStream) -> Result<TokenStream> {
     trace!("handle_describe() starts");
 
-    let DeriveInput { ident, data, .. } = parse2(input)?;
+    let DeriveInput {
+        attrs,
+        ident,
+        data,
+        generics,
+        ..
+    } = parse2(input)?;
+    let generics = add_trait_bounds(generics, &[parse_quote!(::sbor::Describe)]);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let ident_str = ident.to_string();
     trace!("Describing: {}", ident);
 
+    if let Some(TransparentField { ty, .. }) = transparent_field(&attrs, &data)? {
+        return Ok(quote! {
+            impl #impl_generics ::sbor::Describe for #ident #ty_generics #where_clause {
+                fn describe() -> ::sbor::describe::Type {
+                    <#ty as ::sbor::Describe>::describe()
+                }
+            }
+        });
+    }
+
     let output = match data {
         Data::Struct(s) => match s.fields {
             syn::Fields::Named(FieldsNamed { named, .. }) => {
                 // ns: not skipped
                 let ns: Vec<&Field> = named.iter().filter(|f| !is_skipped(f)).collect();
 
-                let names = ns.iter().map(|f| {
-                    f.ident
-                        .clone()
-                        .expect("All fields must be named")
-                        .to_string()
-                });
+                let names = ns.iter().map(|f| field_name(f));
                 let types = ns.iter().map(|f| &f.ty);
 
                 quote! {
-                    impl ::sbor::Describe for #ident {
+                    impl #impl_generics ::sbor::Describe for #ident #ty_generics #where_clause {
                         fn describe() -> ::sbor::describe::Type {
                             use ::sbor::rust::borrow::ToOwned;
                             use ::sbor::rust::vec;
@@ -55,7 +68,7 @@ pub fn handle_describe(input: TokenStream) -> Result<TokenStream> {
                 let types = ns.iter().map(|f| &f.ty);
 
                 quote! {
-                    impl ::sbor::Describe for #ident {
+                    impl #impl_generics ::sbor::Describe for #ident #ty_generics #where_clause {
                         fn describe() -> ::sbor::describe::Type {
                             use ::sbor::rust::borrow::ToOwned;
                             use ::sbor::rust::vec;
@@ -73,7 +86,7 @@ pub fn handle_describe(input: TokenStream) -> Result<TokenStream> {
             }
             syn::Fields::Unit => {
                 quote! {
-                    impl ::sbor::Describe for #ident {
+                    impl #impl_generics ::sbor::Describe for #ident #ty_generics #where_clause {
                         fn describe() -> ::sbor::describe::Type {
                             use ::sbor::rust::borrow::ToOwned;
 
@@ -87,7 +100,7 @@ pub fn handle_describe(input: TokenStream) -> Result<TokenStream> {
             }
         },
         Data::Enum(DataEnum { variants, .. }) => {
-            let names = variants.iter().map(|v| v.ident.to_string());
+            let names = variants.iter().map(variant_name);
             let fields = variants.iter().map(|v| {
                 let f = &v.fields;
 
@@ -95,12 +108,7 @@ pub fn handle_describe(input: TokenStream) -> Result<TokenStream> {
                     syn::Fields::Named(FieldsNamed { named, .. }) => {
                         let ns: Vec<&Field> = named.iter().filter(|f| !is_skipped(f)).collect();
 
-                        let names = ns.iter().map(|f| {
-                            f.ident
-                                .clone()
-                                .expect("All fields must be named")
-                                .to_string()
-                        });
+                        let names = ns.iter().map(|f| field_name(f));
                         let types = ns.iter().map(|f| &f.ty);
 
                         quote! {
@@ -135,7 +143,7 @@ pub fn handle_describe(input: TokenStream) -> Result<TokenStream> {
             });
 
             quote! {
-                impl ::sbor::Describe for #ident {
+                impl #impl_generics ::sbor::Describe for #ident #ty_generics #where_clause {
                     fn describe() -> ::sbor::describe::Type {
                         use ::sbor::rust::borrow::ToOwned;
                         use ::sbor::rust::vec;
@@ -244,6 +252,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_describe_generic_struct() {
+        let input = TokenStream::from_str("struct Test<T> {a: T}").unwrap();
+        let output = handle_describe(input).unwrap();
+
+        assert_code_eq(
+            output,
+            quote! {
+                impl<T: ::sbor::Describe> ::sbor::Describe for Test<T> {
+                    fn describe() -> ::sbor::describe::Type {
+                        use ::sbor::rust::borrow::ToOwned;
+                        use ::sbor::rust::vec;
+                        use ::sbor::Describe;
+
+                        ::sbor::describe::Type::Struct {
+                            name: "Test".to_owned(),
+                            fields: ::sbor::describe::Fields::Named {
+                                named: vec![("a".to_owned(), <T>::describe())]
+                            },
+                        }
+                    }
+                }
+            },
+        );
+    }
+
     #[test]
     fn test_skip_field_1() {
         let input = TokenStream::from_str("struct Test {#[sbor(skip)] a: u32}").unwrap();
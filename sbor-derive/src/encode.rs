@@ -14,9 +14,35 @@ macro_rules! trace {
 pub fn handle_encode(input: TokenStream) -> Result<TokenStream> {
     trace!("handle_encode() starts");
 
-    let DeriveInput { ident, data, .. } = parse2(input)?;
+    let DeriveInput {
+        attrs,
+        ident,
+        data,
+        generics,
+        ..
+    } = parse2(input)?;
+    let generics = add_trait_bounds(
+        generics,
+        &[parse_quote!(::sbor::Encode), parse_quote!(::sbor::TypeId)],
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     trace!("Encoding: {}", ident);
 
+    if let Some(TransparentField { member, ty, .. }) = transparent_field(&attrs, &data)? {
+        return Ok(quote! {
+            impl #impl_generics ::sbor::Encode for #ident #ty_generics #where_clause {
+                #[inline]
+                fn encode_type_id(encoder: &mut ::sbor::Encoder) {
+                    <#ty as ::sbor::Encode>::encode_type_id(encoder);
+                }
+                #[inline]
+                fn encode_value(&self, encoder: &mut ::sbor::Encoder) {
+                    ::sbor::Encode::encode_value(&self.#member, encoder);
+                }
+            }
+        });
+    }
+
     let output = match data {
         Data::Struct(s) => match s.fields {
             syn::Fields::Named(FieldsNamed { named, .. }) => {
@@ -25,7 +51,7 @@ pub fn handle_encode(input: TokenStream) -> Result<TokenStream> {
                 let ns_ids = ns.iter().map(|f| &f.ident);
                 let ns_len = Index::from(ns_ids.len());
                 quote! {
-                    impl ::sbor::Encode for #ident {
+                    impl #impl_generics ::sbor::Encode for #ident #ty_generics #where_clause {
                         #[inline]
                         fn encode_type_id(encoder: &mut ::sbor::Encoder) {
                             encoder.write_type_id(::sbor::type_id::TYPE_STRUCT);
@@ -48,7 +74,7 @@ pub fn handle_encode(input: TokenStream) -> Result<TokenStream> {
                 }
                 let ns_len = Index::from(ns_indices.len());
                 quote! {
-                    impl ::sbor::Encode for #ident {
+                    impl #impl_generics ::sbor::Encode for #ident #ty_generics #where_clause {
                         #[inline]
                         fn encode_type_id(encoder: &mut ::sbor::Encoder) {
                             encoder.write_type_id(::sbor::type_id::TYPE_STRUCT);
@@ -64,7 +90,7 @@ pub fn handle_encode(input: TokenStream) -> Result<TokenStream> {
             }
             syn::Fields::Unit => {
                 quote! {
-                    impl ::sbor::Encode for #ident {
+                    impl #impl_generics ::sbor::Encode for #ident #ty_generics #where_clause {
                         #[inline]
                         fn encode_type_id(encoder: &mut ::sbor::Encoder) {
                             encoder.write_type_id(::sbor::type_id::TYPE_STRUCT);
@@ -80,7 +106,7 @@ pub fn handle_encode(input: TokenStream) -> Result<TokenStream> {
         Data::Enum(DataEnum { variants, .. }) => {
             let match_arms = variants.iter().map(|v| {
                 let v_id = &v.ident;
-                let name_string = v_id.to_string();
+                let name_string = variant_name(v);
                 let name: Expr = parse_quote! { #name_string };
 
                 match &v.fields {
@@ -127,7 +153,7 @@ pub fn handle_encode(input: TokenStream) -> Result<TokenStream> {
 
             if match_arms.len() == 0 {
                 quote! {
-                    impl ::sbor::Encode for #ident {
+                    impl #impl_generics ::sbor::Encode for #ident #ty_generics #where_clause {
                         #[inline]
                         fn encode_type_id(encoder: &mut ::sbor::Encoder) {
                             encoder.write_type_id(::sbor::type_id::TYPE_ENUM);
@@ -139,7 +165,7 @@ pub fn handle_encode(input: TokenStream) -> Result<TokenStream> {
                 }
             } else {
                 quote! {
-                    impl ::sbor::Encode for #ident {
+                    impl #impl_generics ::sbor::Encode for #ident #ty_generics #where_clause {
                         #[inline]
                         fn encode_type_id(encoder: &mut ::sbor::Encoder) {
                             encoder.write_type_id(::sbor::type_id::TYPE_ENUM);
@@ -240,4 +266,32 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn test_encode_transparent_struct() {
+        let input = TokenStream::from_str("#[sbor(transparent)] struct Test(u32);").unwrap();
+        let output = handle_encode(input).unwrap();
+
+        assert_code_eq(
+            output,
+            quote! {
+                impl ::sbor::Encode for Test {
+                    #[inline]
+                    fn encode_type_id(encoder: &mut ::sbor::Encoder) {
+                        <u32 as ::sbor::Encode>::encode_type_id(encoder);
+                    }
+                    #[inline]
+                    fn encode_value(&self, encoder: &mut ::sbor::Encoder) {
+                        ::sbor::Encode::encode_value(&self.0, encoder);
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn test_encode_transparent_enum() {
+        let input = TokenStream::from_str("#[sbor(transparent)] enum Test {A(u32)}").unwrap();
+        assert!(handle_encode(input).is_err());
+    }
 }
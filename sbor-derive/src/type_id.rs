@@ -2,6 +2,8 @@ use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::*;
 
+use crate::utils::*;
+
 macro_rules! trace {
     ($($arg:expr),*) => {{
         #[cfg(feature = "trace")]
@@ -12,12 +14,32 @@ macro_rules! trace {
 pub fn handle_type_id(input: TokenStream) -> Result<TokenStream> {
     trace!("handle_type_id() starts");
 
-    let DeriveInput { ident, data, .. } = parse2(input).expect("Unable to parse input");
+    let DeriveInput {
+        attrs,
+        ident,
+        data,
+        generics,
+        ..
+    } = parse2(input).expect("Unable to parse input");
     trace!("Encoding: {}", ident);
 
+    if let Some(TransparentField { ty, .. }) = transparent_field(&attrs, &data)? {
+        let generics = add_trait_bounds(generics, &[parse_quote!(::sbor::TypeId)]);
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        return Ok(quote! {
+            impl #impl_generics ::sbor::TypeId for #ident #ty_generics #where_clause {
+                #[inline]
+                fn type_id() -> u8 {
+                    <#ty as ::sbor::TypeId>::type_id()
+                }
+            }
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let output = match data {
         Data::Struct(_) => quote! {
-            impl ::sbor::TypeId for #ident {
+            impl #impl_generics ::sbor::TypeId for #ident #ty_generics #where_clause {
                 #[inline]
                 fn type_id() -> u8 {
                     ::sbor::type_id::TYPE_STRUCT
@@ -25,7 +47,7 @@ pub fn handle_type_id(input: TokenStream) -> Result<TokenStream> {
             }
         },
         Data::Enum(_) => quote! {
-            impl ::sbor::TypeId for #ident {
+            impl #impl_generics ::sbor::TypeId for #ident #ty_generics #where_clause {
                 #[inline]
                 fn type_id() -> u8 {
                     ::sbor::type_id::TYPE_ENUM
@@ -31,16 +31,119 @@ pub fn print_generated_code<S: ToString>(kind: &str, code: S) {
 }
 
 pub fn is_skipped(f: &syn::Field) -> bool {
-    let mut skipped = false;
-    for att in &f.attrs {
-        if att.path.is_ident("sbor")
+    has_sbor_flag(&f.attrs, "skip")
+}
+
+pub fn is_defaulted(f: &syn::Field) -> bool {
+    has_sbor_flag(&f.attrs, "default")
+}
+
+pub fn is_transparent(attrs: &[syn::Attribute]) -> bool {
+    has_sbor_flag(attrs, "transparent")
+}
+
+fn has_sbor_flag(attrs: &[syn::Attribute], flag: &str) -> bool {
+    attrs.iter().any(|att| {
+        att.path.is_ident("sbor")
             && att
                 .parse_args::<syn::Path>()
-                .map(|p| p.is_ident("skip"))
+                .map(|p| p.is_ident(flag))
                 .unwrap_or(false)
-        {
-            skipped = true;
+    })
+}
+
+/// Returns the name given by `#[sbor(rename = "...")]`, if any.
+fn renamed(attrs: &[syn::Attribute]) -> Option<String> {
+    attrs.iter().find_map(|att| {
+        if !att.path.is_ident("sbor") {
+            return None;
+        }
+        match att.parse_args::<syn::MetaNameValue>() {
+            Ok(syn::MetaNameValue {
+                path,
+                lit: syn::Lit::Str(name),
+                ..
+            }) if path.is_ident("rename") => Some(name.value()),
+            _ => None,
+        }
+    })
+}
+
+/// Returns the name of a field, as described in the ABI.
+pub fn field_name(f: &syn::Field) -> String {
+    renamed(&f.attrs).unwrap_or_else(|| {
+        f.ident
+            .as_ref()
+            .expect("All fields must be named")
+            .to_string()
+    })
+}
+
+/// Returns the name of an enum variant, as encoded and described in the ABI.
+pub fn variant_name(v: &syn::Variant) -> String {
+    renamed(&v.attrs).unwrap_or_else(|| v.ident.to_string())
+}
+
+/// The single encoded field of a `#[sbor(transparent)]` struct, which is encoded and described
+/// as if it were the struct itself.
+pub struct TransparentField<'a> {
+    pub member: syn::Member,
+    pub ty: &'a syn::Type,
+    pub skipped: Vec<syn::Member>,
+}
+
+pub fn transparent_field<'a>(
+    attrs: &[syn::Attribute],
+    data: &'a syn::Data,
+) -> syn::Result<Option<TransparentField<'a>>> {
+    if !is_transparent(attrs) {
+        return Ok(None);
+    }
+
+    let error = || {
+        syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "#[sbor(transparent)] requires a struct with exactly one encoded field",
+        )
+    };
+    let fields = match data {
+        syn::Data::Struct(s) => &s.fields,
+        _ => return Err(error()),
+    };
+    let members = fields.iter().enumerate().map(|(i, f)| match &f.ident {
+        Some(ident) => syn::Member::Named(ident.clone()),
+        None => syn::Member::Unnamed(syn::Index::from(i)),
+    });
+
+    let mut encoded = Vec::new();
+    let mut skipped = Vec::new();
+    for (member, f) in members.zip(fields.iter()) {
+        if is_skipped(f) {
+            skipped.push(member);
+        } else {
+            encoded.push((member, &f.ty));
+        }
+    }
+    match encoded.pop() {
+        Some((member, ty)) if encoded.is_empty() => Ok(Some(TransparentField {
+            member,
+            ty,
+            skipped,
+        })),
+        _ => Err(error()),
+    }
+}
+
+/// Adds the given bounds to every type parameter, so that a derived trait is only implemented
+/// when the type arguments implement what the fields need.
+pub fn add_trait_bounds(
+    mut generics: syn::Generics,
+    bounds: &[syn::TypeParamBound],
+) -> syn::Generics {
+    for param in &mut generics.params {
+        if let syn::GenericParam::Type(type_param) = param {
+            type_param.bounds.extend(bounds.iter().cloned());
         }
     }
-    skipped
+    generics
 }
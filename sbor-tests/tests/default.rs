@@ -0,0 +1,103 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sbor::rust::string::String;
+use sbor::rust::string::ToString;
+use sbor::rust::vec;
+use sbor::*;
+
+#[derive(Debug, PartialEq, TypeId, Encode, Decode)]
+pub struct TestStructV1 {
+    pub x: u32,
+}
+
+#[derive(Debug, PartialEq, TypeId, Encode, Decode)]
+pub struct TestStructV2 {
+    pub x: u32,
+    #[sbor(default)]
+    pub y: Option<u8>,
+    #[sbor(default)]
+    pub z: String,
+}
+
+#[derive(Debug, PartialEq, TypeId, Encode, Decode)]
+pub struct TestStructUnnamedV1(u32);
+
+#[derive(Debug, PartialEq, TypeId, Encode, Decode)]
+pub struct TestStructUnnamedV2(u32, #[sbor(skip)] u16, #[sbor(default)] u8);
+
+#[derive(Debug, PartialEq, TypeId, Encode, Decode)]
+pub enum TestEnumV1 {
+    A { x: u32 },
+}
+
+#[derive(Debug, PartialEq, TypeId, Encode, Decode)]
+pub enum TestEnumV2 {
+    A {
+        x: u32,
+        #[sbor(default)]
+        y: u8,
+    },
+}
+
+#[test]
+fn test_decode_old_payload_with_default_fields() {
+    let bytes = encode_with_static_info(&TestStructV1 { x: 1 });
+    assert_eq!(
+        decode_with_static_info::<TestStructV2>(&bytes),
+        Ok(TestStructV2 {
+            x: 1,
+            y: None,
+            z: String::new()
+        })
+    );
+
+    let bytes = encode_with_static_info(&TestStructUnnamedV1(1));
+    assert_eq!(
+        decode_with_static_info::<TestStructUnnamedV2>(&bytes),
+        Ok(TestStructUnnamedV2(1, 0, 0))
+    );
+
+    let bytes = encode_with_static_info(&TestEnumV1::A { x: 1 });
+    assert_eq!(
+        decode_with_static_info::<TestEnumV2>(&bytes),
+        Ok(TestEnumV2::A { x: 1, y: 0 })
+    );
+}
+
+#[test]
+fn test_decode_new_payload_with_default_fields() {
+    let value = TestStructV2 {
+        x: 1,
+        y: Some(2),
+        z: "z".to_string(),
+    };
+    let bytes = encode_with_static_info(&value);
+    assert_eq!(decode_with_static_info::<TestStructV2>(&bytes), Ok(value));
+
+    let value = TestStructUnnamedV2(1, 2, 3);
+    let bytes = encode_with_static_info(&value);
+    assert_eq!(
+        decode_with_static_info::<TestStructUnnamedV2>(&bytes),
+        Ok(TestStructUnnamedV2(1, 0, 3))
+    );
+
+    let value = TestStructV2 {
+        x: 1,
+        y: None,
+        z: "z".to_string(),
+    };
+    let bytes = encode_no_static_info(&value);
+    assert_eq!(decode_no_static_info::<TestStructV2>(&bytes), Ok(value));
+}
+
+#[test]
+fn test_decode_payload_missing_required_fields() {
+    let bytes = vec![type_id::TYPE_STRUCT, 0, 0, 0, 0];
+    assert_eq!(
+        decode_with_static_info::<TestStructV2>(&bytes),
+        Err(DecodeError::InvalidLength {
+            expected: 3,
+            actual: 0
+        })
+    );
+}
@@ -26,6 +26,16 @@ pub enum TestEnum {
     C { x: u32, y: u32 },
 }
 
+#[derive(Describe)]
+pub struct TestStructGeneric<T> {
+    pub value: T,
+}
+
+#[derive(Describe)]
+pub enum TestEnumGeneric<T> {
+    A { value: T },
+}
+
 #[test]
 fn test_describe_struct() {
     assert_json_eq(
@@ -124,3 +134,42 @@ fn test_describe_enum() {
         }),
     );
 }
+
+#[test]
+fn test_describe_generics() {
+    assert_json_eq(
+        TestStructGeneric::<TestEnumGeneric<u32>>::describe(),
+        json!({
+            "type": "Struct",
+            "name": "TestStructGeneric",
+            "fields": {
+                "type": "Named",
+                "named": [
+                    [
+                        "value",
+                        {
+                            "type": "Enum",
+                            "name": "TestEnumGeneric",
+                            "variants": [
+                                {
+                                    "name": "A",
+                                    "fields": {
+                                        "type": "Named",
+                                        "named": [
+                                            [
+                                                "value",
+                                                {
+                                                    "type": "U32"
+                                                }
+                                            ]
+                                        ]
+                                    }
+                                }
+                            ]
+                        }
+                    ]
+                ]
+            }
+        }),
+    );
+}
@@ -0,0 +1,69 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sbor::describe::*;
+use sbor::rust::string::ToString;
+use sbor::rust::vec;
+use sbor::*;
+
+#[derive(Debug, PartialEq, TypeId, Encode, Decode, Describe)]
+pub struct TestStruct {
+    #[sbor(rename = "renamed")]
+    pub x: u32,
+}
+
+#[derive(Debug, PartialEq, TypeId, Encode, Decode, Describe)]
+pub enum TestEnum {
+    #[sbor(rename = "B")]
+    A {
+        #[sbor(rename = "renamed")]
+        x: u32,
+    },
+}
+
+#[test]
+fn test_rename_field() {
+    let bytes = encode_with_static_info(&TestStruct { x: 1 });
+    assert_eq!(
+        decode_with_static_info::<TestStruct>(&bytes),
+        Ok(TestStruct { x: 1 })
+    );
+
+    assert_eq!(
+        TestStruct::describe(),
+        Type::Struct {
+            name: "TestStruct".to_string(),
+            fields: Fields::Named {
+                named: vec![("renamed".to_string(), Type::U32)]
+            }
+        }
+    );
+}
+
+#[test]
+fn test_rename_variant() {
+    let bytes = encode_with_static_info(&TestEnum::A { x: 1 });
+    assert_eq!(
+        decode_any(&bytes),
+        Ok(Value::Enum {
+            name: "B".to_string(),
+            fields: vec![Value::U32 { value: 1 }]
+        })
+    );
+    assert_eq!(
+        decode_with_static_info::<TestEnum>(&bytes),
+        Ok(TestEnum::A { x: 1 })
+    );
+
+    assert_eq!(
+        TestEnum::describe(),
+        Type::Enum {
+            name: "TestEnum".to_string(),
+            variants: vec![Variant {
+                name: "B".to_string(),
+                fields: Fields::Named {
+                    named: vec![("renamed".to_string(), Type::U32)]
+                }
+            }]
+        }
+    );
+}
@@ -0,0 +1,54 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sbor::describe::*;
+use sbor::rust::string::String;
+use sbor::rust::string::ToString;
+use sbor::*;
+
+#[derive(Debug, PartialEq, TypeId, Encode, Decode, Describe)]
+#[sbor(transparent)]
+pub struct TestStructNamed {
+    pub x: String,
+    #[sbor(skip)]
+    pub cache: u32,
+}
+
+#[derive(Debug, PartialEq, TypeId, Encode, Decode, Describe)]
+#[sbor(transparent)]
+pub struct TestStructUnnamed(u32);
+
+#[derive(Debug, PartialEq, TypeId, Encode, Decode, Describe)]
+#[sbor(transparent)]
+pub struct TestStructGeneric<T>(T);
+
+#[test]
+fn test_transparent_struct() {
+    let a = TestStructNamed {
+        x: "a".to_string(),
+        cache: 1,
+    };
+    let bytes = encode_with_static_info(&a);
+    assert_eq!(bytes, encode_with_static_info(&"a".to_string()));
+    assert_eq!(
+        decode_with_static_info::<TestStructNamed>(&bytes),
+        Ok(TestStructNamed {
+            x: "a".to_string(),
+            cache: 0
+        })
+    );
+
+    let bytes = encode_with_static_info(&TestStructUnnamed(5));
+    assert_eq!(bytes, encode_with_static_info(&5u32));
+    assert_eq!(
+        decode_with_static_info::<TestStructUnnamed>(&bytes),
+        Ok(TestStructUnnamed(5))
+    );
+
+    let bytes = encode_with_static_info(&TestStructGeneric(5u8));
+    assert_eq!(bytes, encode_with_static_info(&5u8));
+
+    assert_eq!(TestStructNamed::type_id(), type_id::TYPE_STRING);
+    assert_eq!(TestStructNamed::describe(), Type::String);
+    assert_eq!(TestStructUnnamed::describe(), Type::U32);
+    assert_eq!(TestStructGeneric::<u8>::describe(), Type::U8);
+}
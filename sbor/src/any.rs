@@ -97,6 +97,29 @@ pub enum Value {
     },
 }
 
+/// A `Value` writes its own type id as part of its value, so it can be nested wherever each value
+/// carries its type id, such as in an `Option`, a `Result` or a struct field, but not as the
+/// element of a collection. It requires static info to be included.
+impl Encode for Value {
+    #[inline]
+    fn encode_type_id(_encoder: &mut Encoder) {}
+
+    fn encode_value(&self, encoder: &mut Encoder) {
+        encode_any_internal(None, self, encoder);
+    }
+}
+
+impl Decode for Value {
+    #[inline]
+    fn check_type_id(_decoder: &mut Decoder) -> Result<(), DecodeError> {
+        Ok(())
+    }
+
+    fn decode_value(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+        decode_next(None, decoder)
+    }
+}
+
 /// Encodes any SBOR value into byte array.
 pub fn encode_any(value: &Value) -> Vec<u8> {
     let mut bytes = Vec::new();
@@ -262,13 +285,27 @@ fn encode_basic<T: Encode>(ty_ctx: Option<u8>, t: u8, v: &T, enc: &mut Encoder)
 
 /// Decode any SBOR data.
 pub fn decode_any(data: &[u8]) -> Result<Value, DecodeError> {
-    let mut decoder = Decoder::with_static_info(data);
-    let result = decode_next(None, &mut decoder);
+    decode_any_with_limits(data, &DecodeLimits::default())
+}
+
+pub fn decode_any_with_limits(data: &[u8], limits: &DecodeLimits) -> Result<Value, DecodeError> {
+    let mut decoder = Decoder::new_with_limits(data, true, limits)?;
+    let result = decode_next(None, &mut decoder)?;
     decoder.check_end()?;
-    result
+    Ok(result)
 }
 
 fn decode_next(ty_ctx: Option<u8>, dec: &mut Decoder) -> Result<Value, DecodeError> {
+    dec.enter()?;
+    let result = decode_next_value(ty_ctx, dec);
+    dec.exit();
+    result
+}
+
+pub(crate) fn decode_next_value(
+    ty_ctx: Option<u8>,
+    dec: &mut Decoder,
+) -> Result<Value, DecodeError> {
     let ty = match ty_ctx {
         Some(t) => t,
         None => dec.read_type()?,
@@ -462,6 +499,49 @@ fn decode_next(ty_ctx: Option<u8>, dec: &mut Decoder) -> Result<Value, DecodeErr
     }
 }
 
+/// Rewrites a value into its canonical form, in which the elements of sets and the entries of
+/// maps are sorted by their encoding, so that equal collections encode to the same bytes
+/// regardless of their original iteration order.
+pub fn canonicalize_any(value: &mut Value) {
+    match value {
+        Value::Struct { fields } | Value::Enum { fields, .. } => {
+            fields.iter_mut().for_each(canonicalize_any);
+        }
+        Value::Option { value } => {
+            if let Some(x) = value.as_mut() {
+                canonicalize_any(x);
+            }
+        }
+        Value::Result { value } => match value.as_mut() {
+            Ok(x) | Err(x) => canonicalize_any(x),
+        },
+        Value::Array { elements, .. }
+        | Value::Tuple { elements }
+        | Value::List { elements, .. } => {
+            elements.iter_mut().for_each(canonicalize_any);
+        }
+        Value::Set { elements, .. } => {
+            elements.iter_mut().for_each(canonicalize_any);
+            elements.sort_by_cached_key(encode_any);
+        }
+        Value::Map { elements, .. } => {
+            elements.iter_mut().for_each(canonicalize_any);
+            let mut entries = Vec::with_capacity(elements.len() / 2);
+            let mut iter = elements.drain(..);
+            while let (Some(k), Some(v)) = (iter.next(), iter.next()) {
+                entries.push((k, v));
+            }
+            drop(iter);
+            entries.sort_by_cached_key(|(k, _)| encode_any(k));
+            for (k, v) in entries {
+                elements.push(k);
+                elements.push(v);
+            }
+        }
+        _ => {}
+    }
+}
+
 pub fn traverse_any<V, E>(
     path: &mut MutableSborPath,
     value: &Value,
@@ -745,4 +825,85 @@ mod tests {
             value
         );
     }
+
+    #[test]
+    pub fn test_encode_nested_value() {
+        let value = Value::Struct {
+            fields: vec![Value::U32 { value: 1 }],
+        };
+
+        let bytes = crate::encode_with_static_info(&Some(value.clone()));
+
+        assert_eq!(
+            bytes,
+            crate::encode_with_static_info(&Some(TestStruct { x: 1 }))
+        );
+        assert_eq!(
+            crate::decode_with_static_info::<Option<Value>>(&bytes),
+            Ok(Some(value))
+        );
+    }
+
+    #[test]
+    pub fn test_decode_max_depth() {
+        let mut nested = Value::Unit;
+        for _ in 0..DEFAULT_MAX_DEPTH {
+            nested = Value::Option {
+                value: Box::new(Some(nested)),
+            };
+        }
+        let bytes = encode_any(&nested);
+
+        assert_eq!(
+            decode_any(&bytes),
+            Err(DecodeError::MaxDepthExceeded(DEFAULT_MAX_DEPTH))
+        );
+        let limits = DecodeLimits {
+            max_depth: DEFAULT_MAX_DEPTH + 1,
+            ..Default::default()
+        };
+        assert_eq!(decode_any_with_limits(&bytes, &limits), Ok(nested));
+    }
+
+    #[test]
+    pub fn test_decode_max_payload_length() {
+        let bytes = encode_any(&Value::U32 { value: 1 });
+        let limits = DecodeLimits {
+            max_payload_length: 4,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            decode_any_with_limits(&bytes, &limits),
+            Err(DecodeError::MaxPayloadLengthExceeded { max: 4, actual: 5 })
+        );
+    }
+
+    #[test]
+    pub fn test_canonicalize() {
+        let set = |elements: Vec<u8>| Value::Set {
+            element_type_id: TYPE_U8,
+            elements: elements
+                .into_iter()
+                .map(|value| Value::U8 { value })
+                .collect(),
+        };
+        let map = |entries: Vec<(u8, Value)>| Value::Map {
+            key_type_id: TYPE_U8,
+            value_type_id: TYPE_SET,
+            elements: entries
+                .into_iter()
+                .flat_map(|(k, v)| [Value::U8 { value: k }, v])
+                .collect(),
+        };
+
+        let mut a = map(vec![(2, set(vec![3, 1])), (1, set(vec![]))]);
+        let mut b = map(vec![(1, set(vec![])), (2, set(vec![1, 3]))]);
+        assert_ne!(encode_any(&a), encode_any(&b));
+
+        canonicalize_any(&mut a);
+        canonicalize_any(&mut b);
+        assert_eq!(a, b);
+        assert_eq!(a, map(vec![(1, set(vec![])), (2, set(vec![1, 3]))]));
+    }
 }
@@ -0,0 +1,409 @@
+use bumpalo::collections::Vec as BumpVec;
+pub use bumpalo::Bump;
+
+use crate::any::Value;
+use crate::decode::*;
+use crate::rust::boxed::Box;
+use crate::rust::string::ToString;
+use crate::rust::vec::Vec;
+use crate::type_id::*;
+
+/// A SBOR value whose nodes are allocated in a bump arena.
+///
+/// Strings and custom value bytes are borrowed from the payload, and all the nodes are
+/// released at once when the arena is dropped, which avoids one heap allocation per node
+/// when decoding large payloads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArenaValue<'a> {
+    Unit,
+    Bool {
+        value: bool,
+    },
+    I8 {
+        value: i8,
+    },
+    I16 {
+        value: i16,
+    },
+    I32 {
+        value: i32,
+    },
+    I64 {
+        value: i64,
+    },
+    I128 {
+        value: i128,
+    },
+    U8 {
+        value: u8,
+    },
+    U16 {
+        value: u16,
+    },
+    U32 {
+        value: u32,
+    },
+    U64 {
+        value: u64,
+    },
+    U128 {
+        value: u128,
+    },
+    String {
+        value: &'a str,
+    },
+
+    Struct {
+        fields: &'a [ArenaValue<'a>],
+    },
+    Enum {
+        name: &'a str,
+        fields: &'a [ArenaValue<'a>],
+    },
+    Option {
+        value: Option<&'a ArenaValue<'a>>,
+    },
+    Result {
+        value: Result<&'a ArenaValue<'a>, &'a ArenaValue<'a>>,
+    },
+
+    Array {
+        element_type_id: u8,
+        elements: &'a [ArenaValue<'a>],
+    },
+    Tuple {
+        elements: &'a [ArenaValue<'a>],
+    },
+
+    List {
+        element_type_id: u8,
+        elements: &'a [ArenaValue<'a>],
+    },
+    Set {
+        element_type_id: u8,
+        elements: &'a [ArenaValue<'a>],
+    },
+    Map {
+        key_type_id: u8,
+        value_type_id: u8,
+        elements: &'a [ArenaValue<'a>],
+    },
+
+    Custom {
+        type_id: u8,
+        bytes: &'a [u8],
+    },
+}
+
+impl<'a> ArenaValue<'a> {
+    /// Copies this value into a heap allocated `Value`.
+    pub fn to_value(&self) -> Value {
+        fn to_values(values: &[ArenaValue]) -> Vec<Value> {
+            values.iter().map(ArenaValue::to_value).collect()
+        }
+
+        match *self {
+            ArenaValue::Unit => Value::Unit,
+            ArenaValue::Bool { value } => Value::Bool { value },
+            ArenaValue::I8 { value } => Value::I8 { value },
+            ArenaValue::I16 { value } => Value::I16 { value },
+            ArenaValue::I32 { value } => Value::I32 { value },
+            ArenaValue::I64 { value } => Value::I64 { value },
+            ArenaValue::I128 { value } => Value::I128 { value },
+            ArenaValue::U8 { value } => Value::U8 { value },
+            ArenaValue::U16 { value } => Value::U16 { value },
+            ArenaValue::U32 { value } => Value::U32 { value },
+            ArenaValue::U64 { value } => Value::U64 { value },
+            ArenaValue::U128 { value } => Value::U128 { value },
+            ArenaValue::String { value } => Value::String {
+                value: value.to_string(),
+            },
+            ArenaValue::Struct { fields } => Value::Struct {
+                fields: to_values(fields),
+            },
+            ArenaValue::Enum { name, fields } => Value::Enum {
+                name: name.to_string(),
+                fields: to_values(fields),
+            },
+            ArenaValue::Option { value } => Value::Option {
+                value: Box::new(value.map(ArenaValue::to_value)),
+            },
+            ArenaValue::Result { value } => Value::Result {
+                value: Box::new(
+                    value
+                        .map(ArenaValue::to_value)
+                        .map_err(ArenaValue::to_value),
+                ),
+            },
+            ArenaValue::Array {
+                element_type_id,
+                elements,
+            } => Value::Array {
+                element_type_id,
+                elements: to_values(elements),
+            },
+            ArenaValue::Tuple { elements } => Value::Tuple {
+                elements: to_values(elements),
+            },
+            ArenaValue::List {
+                element_type_id,
+                elements,
+            } => Value::List {
+                element_type_id,
+                elements: to_values(elements),
+            },
+            ArenaValue::Set {
+                element_type_id,
+                elements,
+            } => Value::Set {
+                element_type_id,
+                elements: to_values(elements),
+            },
+            ArenaValue::Map {
+                key_type_id,
+                value_type_id,
+                elements,
+            } => Value::Map {
+                key_type_id,
+                value_type_id,
+                elements: to_values(elements),
+            },
+            ArenaValue::Custom { type_id, bytes } => Value::Custom {
+                type_id,
+                bytes: bytes.to_vec(),
+            },
+        }
+    }
+}
+
+/// Decodes any SBOR data into a value allocated in the given arena.
+pub fn decode_any_in<'a>(bump: &'a Bump, data: &'a [u8]) -> Result<ArenaValue<'a>, DecodeError> {
+    decode_any_in_with_limits(bump, data, &DecodeLimits::default())
+}
+
+/// Decodes any SBOR data into a value allocated in the given arena, enforcing the given limits.
+pub fn decode_any_in_with_limits<'a>(
+    bump: &'a Bump,
+    data: &'a [u8],
+    limits: &DecodeLimits,
+) -> Result<ArenaValue<'a>, DecodeError> {
+    let mut decoder = Decoder::new_with_limits(data, true, limits)?;
+    let result = decode_next(bump, None, &mut decoder)?;
+    decoder.check_end()?;
+    Ok(result)
+}
+
+fn decode_next<'a>(
+    bump: &'a Bump,
+    ty_ctx: Option<u8>,
+    dec: &mut Decoder<'a>,
+) -> Result<ArenaValue<'a>, DecodeError> {
+    dec.enter()?;
+    let result = decode_next_value(bump, ty_ctx, dec);
+    dec.exit();
+    result
+}
+
+fn decode_next_value<'a>(
+    bump: &'a Bump,
+    ty_ctx: Option<u8>,
+    dec: &mut Decoder<'a>,
+) -> Result<ArenaValue<'a>, DecodeError> {
+    let ty = match ty_ctx {
+        Some(t) => t,
+        None => dec.read_type()?,
+    };
+
+    match ty {
+        TYPE_STRING => Ok(ArenaValue::String {
+            value: decode_str(dec)?,
+        }),
+        // struct & enum
+        TYPE_STRUCT => {
+            let len = dec.read_dynamic_size()?;
+            Ok(ArenaValue::Struct {
+                fields: decode_elements(bump, None, len, dec)?,
+            })
+        }
+        TYPE_ENUM => {
+            let name = decode_str(dec)?;
+            let len = dec.read_dynamic_size()?;
+            Ok(ArenaValue::Enum {
+                name,
+                fields: decode_elements(bump, None, len, dec)?,
+            })
+        }
+        TYPE_OPTION => match dec.read_variant_index()? {
+            OPTION_VARIANT_SOME => Ok(ArenaValue::Option {
+                value: Some(bump.alloc(decode_next(bump, None, dec)?)),
+            }),
+            OPTION_VARIANT_NONE => Ok(ArenaValue::Option { value: None }),
+            index => Err(DecodeError::InvalidIndex(index)),
+        },
+        TYPE_RESULT => match dec.read_variant_index()? {
+            RESULT_VARIANT_OK => Ok(ArenaValue::Result {
+                value: Ok(bump.alloc(decode_next(bump, None, dec)?)),
+            }),
+            RESULT_VARIANT_ERR => Ok(ArenaValue::Result {
+                value: Err(bump.alloc(decode_next(bump, None, dec)?)),
+            }),
+            index => Err(DecodeError::InvalidIndex(index)),
+        },
+        // composite types
+        TYPE_ARRAY => {
+            let element_type_id = dec.read_type()?;
+            let len = dec.read_dynamic_size()?;
+            Ok(ArenaValue::Array {
+                element_type_id,
+                elements: decode_elements(bump, Some(element_type_id), len, dec)?,
+            })
+        }
+        TYPE_TUPLE => {
+            let len = dec.read_dynamic_size()?;
+            Ok(ArenaValue::Tuple {
+                elements: decode_elements(bump, None, len, dec)?,
+            })
+        }
+        // collections
+        TYPE_LIST => {
+            let element_type_id = dec.read_type()?;
+            let len = dec.read_dynamic_size()?;
+            Ok(ArenaValue::List {
+                element_type_id,
+                elements: decode_elements(bump, Some(element_type_id), len, dec)?,
+            })
+        }
+        TYPE_SET => {
+            let element_type_id = dec.read_type()?;
+            let len = dec.read_dynamic_size()?;
+            Ok(ArenaValue::Set {
+                element_type_id,
+                elements: decode_elements(bump, Some(element_type_id), len, dec)?,
+            })
+        }
+        TYPE_MAP => {
+            let key_type_id = dec.read_type()?;
+            let value_type_id = dec.read_type()?;
+            let len = dec.read_dynamic_size()?;
+            let mut elements = BumpVec::new_in(bump);
+            for _ in 0..len {
+                elements.push(decode_next(bump, Some(key_type_id), dec)?);
+                elements.push(decode_next(bump, Some(value_type_id), dec)?);
+            }
+            Ok(ArenaValue::Map {
+                key_type_id,
+                value_type_id,
+                elements: elements.into_bump_slice(),
+            })
+        }
+        // custom types
+        _ if ty >= TYPE_CUSTOM_START => {
+            let len = dec.read_dynamic_size()?;
+            Ok(ArenaValue::Custom {
+                type_id: ty,
+                bytes: dec.read_bytes(len)?,
+            })
+        }
+        // primitive types
+        _ => crate::any::decode_next_value(Some(ty), dec).map(|value| match value {
+            Value::Unit => ArenaValue::Unit,
+            Value::Bool { value } => ArenaValue::Bool { value },
+            Value::I8 { value } => ArenaValue::I8 { value },
+            Value::I16 { value } => ArenaValue::I16 { value },
+            Value::I32 { value } => ArenaValue::I32 { value },
+            Value::I64 { value } => ArenaValue::I64 { value },
+            Value::I128 { value } => ArenaValue::I128 { value },
+            Value::U8 { value } => ArenaValue::U8 { value },
+            Value::U16 { value } => ArenaValue::U16 { value },
+            Value::U32 { value } => ArenaValue::U32 { value },
+            Value::U64 { value } => ArenaValue::U64 { value },
+            Value::U128 { value } => ArenaValue::U128 { value },
+            _ => panic!("Non-primitive value decoded as primitive"),
+        }),
+    }
+}
+
+fn decode_elements<'a>(
+    bump: &'a Bump,
+    ty_ctx: Option<u8>,
+    len: usize,
+    dec: &mut Decoder<'a>,
+) -> Result<&'a [ArenaValue<'a>], DecodeError> {
+    // The length comes from the payload, so the slice is grown rather than pre-allocated
+    let mut elements = BumpVec::new_in(bump);
+    for _ in 0..len {
+        elements.push(decode_next(bump, ty_ctx, dec)?);
+    }
+    Ok(elements.into_bump_slice())
+}
+
+fn decode_str<'a>(dec: &mut Decoder<'a>) -> Result<&'a str, DecodeError> {
+    let len = dec.read_dynamic_size()?;
+    let slice = dec.read_bytes(len)?;
+    core::str::from_utf8(slice).map_err(|_| DecodeError::InvalidUtf8)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rust::collections::*;
+    use crate::rust::string::String;
+    use crate::rust::vec;
+    use crate::*;
+
+    use super::*;
+
+    #[derive(TypeId, Encode)]
+    struct TestStruct {
+        a: u32,
+        b: Option<String>,
+        c: Result<(), u8>,
+        d: BTreeMap<u8, Vec<String>>,
+        e: [u16; 2],
+        f: (bool, i128),
+    }
+
+    #[test]
+    pub fn test_decode_in_arena_matches_decode_any() {
+        let mut d = BTreeMap::new();
+        d.insert(1, vec!["x".to_string(), "y".to_string()]);
+        let bytes = encode_with_static_info(&TestStruct {
+            a: 1,
+            b: Some("b".to_string()),
+            c: Err(2),
+            d,
+            e: [3, 4],
+            f: (true, -5),
+        });
+
+        let bump = Bump::new();
+        let value = decode_any_in(&bump, &bytes).unwrap();
+        assert_eq!(value.to_value(), decode_any(&bytes).unwrap());
+    }
+
+    #[test]
+    pub fn test_decode_in_arena_borrows_payload() {
+        let bytes = encode_with_static_info(&"hello".to_string());
+        let bump = Bump::new();
+        match decode_any_in(&bump, &bytes).unwrap() {
+            ArenaValue::String { value } => {
+                assert_eq!(value, "hello");
+                assert_eq!(value.as_ptr(), bytes[5..].as_ptr());
+            }
+            _ => panic!("Unexpected value"),
+        }
+    }
+
+    #[test]
+    pub fn test_decode_in_arena_limits() {
+        let bytes = encode_with_static_info(&Some(Some(1u8)));
+        let bump = Bump::new();
+        let limits = DecodeLimits {
+            max_depth: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            decode_any_in_with_limits(&bump, &bytes, &limits),
+            Err(DecodeError::MaxDepthExceeded(2))
+        );
+    }
+}
@@ -34,9 +34,34 @@ pub enum DecodeError {
 
     NotAllBytesUsed(usize),
 
+    MaxDepthExceeded(usize),
+
+    MaxPayloadLengthExceeded { max: usize, actual: usize },
+
     CustomError(String),
 }
 
+/// The default max nesting depth of a decoded value.
+pub const DEFAULT_MAX_DEPTH: usize = 64;
+
+/// Limits enforced while decoding a payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeLimits {
+    /// The max nesting depth of a value, counting the root value as depth 1.
+    pub max_depth: usize,
+    /// The max length, in bytes, of the payload.
+    pub max_payload_length: usize,
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_payload_length: usize::MAX,
+        }
+    }
+}
+
 /// A data structure that can be decoded from a byte array using SBOR.
 pub trait Decode: Sized {
     fn decode(decoder: &mut Decoder) -> Result<Self, DecodeError> {
@@ -54,6 +79,8 @@ pub struct Decoder<'de> {
     input: &'de [u8],
     offset: usize,
     with_static_info: bool,
+    depth: usize,
+    max_depth: usize,
 }
 
 impl<'de> Decoder<'de> {
@@ -62,9 +89,29 @@ impl<'de> Decoder<'de> {
             input,
             offset: 0,
             with_static_info,
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
         }
     }
 
+    pub fn new_with_limits(
+        input: &'de [u8],
+        with_static_info: bool,
+        limits: &DecodeLimits,
+    ) -> Result<Self, DecodeError> {
+        if input.len() > limits.max_payload_length {
+            return Err(DecodeError::MaxPayloadLengthExceeded {
+                max: limits.max_payload_length,
+                actual: input.len(),
+            });
+        }
+
+        Ok(Self {
+            max_depth: limits.max_depth,
+            ..Self::new(input, with_static_info)
+        })
+    }
+
     pub fn with_static_info(input: &'de [u8]) -> Self {
         Self::new(input, true)
     }
@@ -157,6 +204,41 @@ impl<'de> Decoder<'de> {
         Ok(())
     }
 
+    /// Enters a nested value, failing if the max depth is exceeded.
+    pub fn enter(&mut self) -> Result<(), DecodeError> {
+        if self.depth >= self.max_depth {
+            return Err(DecodeError::MaxDepthExceeded(self.max_depth));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Leaves a value entered with [`Decoder::enter`].
+    pub fn exit(&mut self) {
+        self.depth -= 1;
+    }
+
+    /// Checks that the number of fields is within the given range and returns it, or `max` if
+    /// there is no static info.
+    pub fn check_static_size_in_range(
+        &mut self,
+        min: usize,
+        max: usize,
+    ) -> Result<usize, DecodeError> {
+        if self.with_static_info {
+            let len = self.read_dynamic_size()?;
+            if len < min || len > max {
+                return Err(DecodeError::InvalidLength {
+                    expected: max,
+                    actual: len,
+                });
+            }
+            Ok(len)
+        } else {
+            Ok(max)
+        }
+    }
+
     pub fn check_end(&self) -> Result<(), DecodeError> {
         let n = self.remaining();
         if n != 0 {
@@ -383,7 +465,7 @@ decode_tuple! { 8 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H }
 decode_tuple! { 9 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I }
 decode_tuple! { 10 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J }
 
-impl<T: Decode + TypeId, E: Decode + TypeId> Decode for Result<T, E> {
+impl<T: Decode, E: Decode> Decode for Result<T, E> {
     #[inline]
     fn check_type_id(decoder: &mut Decoder) -> Result<(), DecodeError> {
         decoder.check_type_id(Self::type_id())
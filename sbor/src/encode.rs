@@ -206,7 +206,7 @@ impl Encode for String {
     }
 }
 
-impl<T: Encode + TypeId> Encode for Option<T> {
+impl<T: Encode> Encode for Option<T> {
     #[inline]
     fn encode_type_id(encoder: &mut Encoder) {
         encoder.write_type_id(Self::type_id());
@@ -0,0 +1,511 @@
+use serde_json::{Map, Value as JsonValue};
+
+use crate::any::Value;
+use crate::rust::borrow::Borrow;
+use crate::rust::boxed::Box;
+use crate::rust::str::FromStr;
+use crate::rust::string::String;
+use crate::rust::string::ToString;
+use crate::rust::vec::Vec;
+
+/// Represents an error when converting JSON into a SBOR value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonError {
+    /// The JSON is not an object with a string `type` field.
+    NotATypedObject,
+    /// The `type` field names no known value type.
+    UnknownType(String),
+    /// A field required by the value type is missing.
+    MissingField(String),
+    /// A field has the wrong JSON type or an out-of-range value.
+    InvalidField(String),
+}
+
+/// Maps custom SBOR values to and from JSON.
+pub trait CustomJson {
+    /// Converts a custom value into a JSON object with a `type` field, or returns `None` to use
+    /// the generic `Custom` form.
+    fn custom_to_json(&self, type_id: u8, bytes: &[u8]) -> Option<JsonValue>;
+
+    /// Converts a JSON object with the given `type` back into a custom value, or returns `None`
+    /// if the type is not a custom one.
+    fn custom_from_json(
+        &self,
+        ty: &str,
+        object: &Map<String, JsonValue>,
+    ) -> Option<Result<(u8, Vec<u8>), JsonError>>;
+}
+
+/// A `CustomJson` that uses the generic `Custom` form for all custom values.
+pub struct NoCustomJson;
+
+impl CustomJson for NoCustomJson {
+    fn custom_to_json(&self, _type_id: u8, _bytes: &[u8]) -> Option<JsonValue> {
+        None
+    }
+
+    fn custom_from_json(
+        &self,
+        _ty: &str,
+        _object: &Map<String, JsonValue>,
+    ) -> Option<Result<(u8, Vec<u8>), JsonError>> {
+        None
+    }
+}
+
+/// Converts a SBOR value into its canonical JSON representation.
+///
+/// Every value is an object with a `type` field, so the conversion is lossless:
+///
+/// ```text
+/// { "type": "Unit" }
+/// { "type": "Bool" | "I8" | "I16" | "I32" | "U8" | "U16" | "U32", "value": bool | number }
+/// { "type": "I64" | "I128" | "U64" | "U128", "value": decimal string }
+/// { "type": "String", "value": string }
+/// { "type": "Struct", "fields": [value] }
+/// { "type": "Enum", "name": string, "fields": [value] }
+/// { "type": "Option", "value": value | null }
+/// { "type": "Result", "ok": value } | { "type": "Result", "err": value }
+/// { "type": "Array" | "List" | "Set", "element_type_id": u8, "elements": [value] }
+/// { "type": "Tuple", "elements": [value] }
+/// { "type": "Map", "key_type_id": u8, "value_type_id": u8, "entries": [[value, value]] }
+/// { "type": "Custom", "type_id": u8, "bytes": hex }
+/// ```
+///
+/// 64 and 128-bit integers are strings, as JSON tooling commonly loses precision beyond 53 bits.
+pub fn to_json_value(value: &Value) -> JsonValue {
+    to_json_value_with(value, &NoCustomJson)
+}
+
+/// Converts a SBOR value into JSON, mapping custom values with the given `CustomJson`.
+pub fn to_json_value_with<C: CustomJson>(value: &Value, custom: &C) -> JsonValue {
+    let mut object = Map::new();
+    let ty = match value {
+        Value::Unit => "Unit",
+        Value::Bool { value } => {
+            object.insert("value".to_string(), (*value).into());
+            "Bool"
+        }
+        Value::I8 { value } => {
+            object.insert("value".to_string(), (*value).into());
+            "I8"
+        }
+        Value::I16 { value } => {
+            object.insert("value".to_string(), (*value).into());
+            "I16"
+        }
+        Value::I32 { value } => {
+            object.insert("value".to_string(), (*value).into());
+            "I32"
+        }
+        Value::I64 { value } => {
+            object.insert("value".to_string(), value.to_string().into());
+            "I64"
+        }
+        Value::I128 { value } => {
+            object.insert("value".to_string(), value.to_string().into());
+            "I128"
+        }
+        Value::U8 { value } => {
+            object.insert("value".to_string(), (*value).into());
+            "U8"
+        }
+        Value::U16 { value } => {
+            object.insert("value".to_string(), (*value).into());
+            "U16"
+        }
+        Value::U32 { value } => {
+            object.insert("value".to_string(), (*value).into());
+            "U32"
+        }
+        Value::U64 { value } => {
+            object.insert("value".to_string(), value.to_string().into());
+            "U64"
+        }
+        Value::U128 { value } => {
+            object.insert("value".to_string(), value.to_string().into());
+            "U128"
+        }
+        Value::String { value } => {
+            object.insert("value".to_string(), value.clone().into());
+            "String"
+        }
+        Value::Struct { fields } => {
+            object.insert("fields".to_string(), elements_to_json(fields, custom));
+            "Struct"
+        }
+        Value::Enum { name, fields } => {
+            object.insert("name".to_string(), name.clone().into());
+            object.insert("fields".to_string(), elements_to_json(fields, custom));
+            "Enum"
+        }
+        Value::Option { value } => {
+            let value = match value.borrow() {
+                Some(x) => to_json_value_with(x, custom),
+                None => JsonValue::Null,
+            };
+            object.insert("value".to_string(), value);
+            "Option"
+        }
+        Value::Result { value } => {
+            match value.borrow() {
+                Ok(x) => object.insert("ok".to_string(), to_json_value_with(x, custom)),
+                Err(x) => object.insert("err".to_string(), to_json_value_with(x, custom)),
+            };
+            "Result"
+        }
+        Value::Array {
+            element_type_id,
+            elements,
+        } => {
+            object.insert("element_type_id".to_string(), (*element_type_id).into());
+            object.insert("elements".to_string(), elements_to_json(elements, custom));
+            "Array"
+        }
+        Value::Tuple { elements } => {
+            object.insert("elements".to_string(), elements_to_json(elements, custom));
+            "Tuple"
+        }
+        Value::List {
+            element_type_id,
+            elements,
+        } => {
+            object.insert("element_type_id".to_string(), (*element_type_id).into());
+            object.insert("elements".to_string(), elements_to_json(elements, custom));
+            "List"
+        }
+        Value::Set {
+            element_type_id,
+            elements,
+        } => {
+            object.insert("element_type_id".to_string(), (*element_type_id).into());
+            object.insert("elements".to_string(), elements_to_json(elements, custom));
+            "Set"
+        }
+        Value::Map {
+            key_type_id,
+            value_type_id,
+            elements,
+        } => {
+            let entries: Vec<JsonValue> = elements
+                .chunks(2)
+                .map(|entry| elements_to_json(entry, custom))
+                .collect();
+            object.insert("key_type_id".to_string(), (*key_type_id).into());
+            object.insert("value_type_id".to_string(), (*value_type_id).into());
+            object.insert("entries".to_string(), entries.into());
+            "Map"
+        }
+        Value::Custom { type_id, bytes } => {
+            if let Some(json) = custom.custom_to_json(*type_id, bytes) {
+                return json;
+            }
+            object.insert("type_id".to_string(), (*type_id).into());
+            object.insert("bytes".to_string(), hex::encode(bytes).into());
+            "Custom"
+        }
+    };
+    object.insert("type".to_string(), ty.into());
+    JsonValue::Object(object)
+}
+
+fn elements_to_json<C: CustomJson>(elements: &[Value], custom: &C) -> JsonValue {
+    elements
+        .iter()
+        .map(|e| to_json_value_with(e, custom))
+        .collect::<Vec<JsonValue>>()
+        .into()
+}
+
+/// Converts the canonical JSON representation produced by [`to_json_value`] back into a SBOR
+/// value.
+pub fn from_json_value(json: &JsonValue) -> Result<Value, JsonError> {
+    from_json_value_with(json, &NoCustomJson)
+}
+
+/// Converts JSON back into a SBOR value, mapping custom values with the given `CustomJson`.
+pub fn from_json_value_with<C: CustomJson>(
+    json: &JsonValue,
+    custom: &C,
+) -> Result<Value, JsonError> {
+    let object = json.as_object().ok_or(JsonError::NotATypedObject)?;
+    let ty = object
+        .get("type")
+        .and_then(JsonValue::as_str)
+        .ok_or(JsonError::NotATypedObject)?;
+    let value = match ty {
+        "Unit" => Value::Unit,
+        "Bool" => Value::Bool {
+            value: field(object, "value")?
+                .as_bool()
+                .ok_or_else(|| invalid("value"))?,
+        },
+        "I8" => Value::I8 {
+            value: number_field(object, "value")?,
+        },
+        "I16" => Value::I16 {
+            value: number_field(object, "value")?,
+        },
+        "I32" => Value::I32 {
+            value: number_field(object, "value")?,
+        },
+        "I64" => Value::I64 {
+            value: parse_field(object, "value")?,
+        },
+        "I128" => Value::I128 {
+            value: parse_field(object, "value")?,
+        },
+        "U8" => Value::U8 {
+            value: number_field(object, "value")?,
+        },
+        "U16" => Value::U16 {
+            value: number_field(object, "value")?,
+        },
+        "U32" => Value::U32 {
+            value: number_field(object, "value")?,
+        },
+        "U64" => Value::U64 {
+            value: parse_field(object, "value")?,
+        },
+        "U128" => Value::U128 {
+            value: parse_field(object, "value")?,
+        },
+        "String" => Value::String {
+            value: str_field(object, "value")?.to_string(),
+        },
+        "Struct" => Value::Struct {
+            fields: elements_field(object, "fields", custom)?,
+        },
+        "Enum" => Value::Enum {
+            name: str_field(object, "name")?.to_string(),
+            fields: elements_field(object, "fields", custom)?,
+        },
+        "Option" => Value::Option {
+            value: Box::new(match field(object, "value")? {
+                JsonValue::Null => None,
+                x => Some(from_json_value_with(x, custom)?),
+            }),
+        },
+        "Result" => Value::Result {
+            value: Box::new(match (object.get("ok"), object.get("err")) {
+                (Some(x), None) => Ok(from_json_value_with(x, custom)?),
+                (None, Some(x)) => Err(from_json_value_with(x, custom)?),
+                (Some(_), Some(_)) => return Err(invalid("err")),
+                (None, None) => return Err(JsonError::MissingField("ok".to_string())),
+            }),
+        },
+        "Array" => Value::Array {
+            element_type_id: number_field(object, "element_type_id")?,
+            elements: elements_field(object, "elements", custom)?,
+        },
+        "Tuple" => Value::Tuple {
+            elements: elements_field(object, "elements", custom)?,
+        },
+        "List" => Value::List {
+            element_type_id: number_field(object, "element_type_id")?,
+            elements: elements_field(object, "elements", custom)?,
+        },
+        "Set" => Value::Set {
+            element_type_id: number_field(object, "element_type_id")?,
+            elements: elements_field(object, "elements", custom)?,
+        },
+        "Map" => {
+            let mut elements = Vec::new();
+            for entry in array_field(object, "entries")? {
+                match entry.as_array().map(Vec::as_slice) {
+                    Some([key, value]) => {
+                        elements.push(from_json_value_with(key, custom)?);
+                        elements.push(from_json_value_with(value, custom)?);
+                    }
+                    _ => return Err(invalid("entries")),
+                }
+            }
+            Value::Map {
+                key_type_id: number_field(object, "key_type_id")?,
+                value_type_id: number_field(object, "value_type_id")?,
+                elements,
+            }
+        }
+        "Custom" => Value::Custom {
+            type_id: number_field(object, "type_id")?,
+            bytes: hex::decode(str_field(object, "bytes")?).map_err(|_| invalid("bytes"))?,
+        },
+        _ => match custom.custom_from_json(ty, object) {
+            Some(result) => {
+                let (type_id, bytes) = result?;
+                Value::Custom { type_id, bytes }
+            }
+            None => return Err(JsonError::UnknownType(ty.to_string())),
+        },
+    };
+    Ok(value)
+}
+
+fn invalid(name: &str) -> JsonError {
+    JsonError::InvalidField(name.to_string())
+}
+
+fn field<'a>(object: &'a Map<String, JsonValue>, name: &str) -> Result<&'a JsonValue, JsonError> {
+    object
+        .get(name)
+        .ok_or_else(|| JsonError::MissingField(name.to_string()))
+}
+
+/// Reads a string field, such as the value of a custom type.
+pub fn str_field<'a>(object: &'a Map<String, JsonValue>, name: &str) -> Result<&'a str, JsonError> {
+    field(object, name)?.as_str().ok_or_else(|| invalid(name))
+}
+
+fn number_field<T: TryFrom<i64>>(
+    object: &Map<String, JsonValue>,
+    name: &str,
+) -> Result<T, JsonError> {
+    field(object, name)?
+        .as_i64()
+        .and_then(|n| T::try_from(n).ok())
+        .ok_or_else(|| invalid(name))
+}
+
+fn parse_field<T: FromStr>(object: &Map<String, JsonValue>, name: &str) -> Result<T, JsonError> {
+    str_field(object, name)?.parse().map_err(|_| invalid(name))
+}
+
+fn array_field<'a>(
+    object: &'a Map<String, JsonValue>,
+    name: &str,
+) -> Result<&'a Vec<JsonValue>, JsonError> {
+    field(object, name)?.as_array().ok_or_else(|| invalid(name))
+}
+
+fn elements_field<C: CustomJson>(
+    object: &Map<String, JsonValue>,
+    name: &str,
+    custom: &C,
+) -> Result<Vec<Value>, JsonError> {
+    array_field(object, name)?
+        .iter()
+        .map(|e| from_json_value_with(e, custom))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust::vec;
+    use crate::type_id::*;
+    use serde_json::json;
+
+    fn test_value() -> Value {
+        Value::Struct {
+            fields: vec![
+                Value::Unit,
+                Value::Bool { value: true },
+                Value::I8 { value: -1 },
+                Value::U32 { value: 7 },
+                Value::I128 { value: i128::MIN },
+                Value::U64 { value: u64::MAX },
+                Value::String {
+                    value: "abc".to_string(),
+                },
+                Value::Enum {
+                    name: "A".to_string(),
+                    fields: vec![],
+                },
+                Value::Option {
+                    value: Box::new(Some(Value::Option {
+                        value: Box::new(None),
+                    })),
+                },
+                Value::Result {
+                    value: Box::new(Err(Value::U8 { value: 1 })),
+                },
+                Value::Array {
+                    element_type_id: TYPE_U8,
+                    elements: vec![Value::U8 { value: 2 }],
+                },
+                Value::Tuple { elements: vec![] },
+                Value::List {
+                    element_type_id: TYPE_U16,
+                    elements: vec![],
+                },
+                Value::Set {
+                    element_type_id: TYPE_U8,
+                    elements: vec![Value::U8 { value: 3 }],
+                },
+                Value::Map {
+                    key_type_id: TYPE_STRING,
+                    value_type_id: TYPE_U8,
+                    elements: vec![
+                        Value::String {
+                            value: "k".to_string(),
+                        },
+                        Value::U8 { value: 4 },
+                    ],
+                },
+                Value::Custom {
+                    type_id: 0xa1,
+                    bytes: vec![0xff, 0x00],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_to_json_value() {
+        let json = to_json_value(&test_value());
+        let fields = json["fields"].as_array().unwrap();
+
+        assert_eq!(fields[3], json!({ "type": "U32", "value": 7 }));
+        assert_eq!(
+            fields[5],
+            json!({ "type": "U64", "value": "18446744073709551615" })
+        );
+        assert_eq!(
+            fields[8],
+            json!({ "type": "Option", "value": { "type": "Option", "value": null } })
+        );
+        assert_eq!(
+            fields[9],
+            json!({ "type": "Result", "err": { "type": "U8", "value": 1 } })
+        );
+        assert_eq!(
+            fields[14],
+            json!({
+                "type": "Map",
+                "key_type_id": TYPE_STRING,
+                "value_type_id": TYPE_U8,
+                "entries": [[{ "type": "String", "value": "k" }, { "type": "U8", "value": 4 }]]
+            })
+        );
+        assert_eq!(
+            fields[15],
+            json!({ "type": "Custom", "type_id": 0xa1, "bytes": "ff00" })
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let value = test_value();
+        assert_eq!(from_json_value(&to_json_value(&value)), Ok(value));
+    }
+
+    #[test]
+    fn test_from_json_value_errors() {
+        assert_eq!(
+            from_json_value(&json!({ "type": "Decimal", "value": "1" })),
+            Err(JsonError::UnknownType("Decimal".to_string()))
+        );
+        assert_eq!(
+            from_json_value(&json!({ "type": "U8", "value": 256 })),
+            Err(JsonError::InvalidField("value".to_string()))
+        );
+        assert_eq!(
+            from_json_value(&json!({ "type": "Struct" })),
+            Err(JsonError::MissingField("fields".to_string()))
+        );
+        assert_eq!(
+            from_json_value(&json!([1])),
+            Err(JsonError::NotATypedObject)
+        );
+    }
+}
@@ -7,26 +7,44 @@ compile_error!("Feature `std` and `alloc` can't be enabled at the same time.");
 
 /// SBOR any data encoding and decoding.
 pub mod any;
+/// SBOR arena decoding.
+#[cfg(feature = "arena")]
+pub mod arena;
 /// SBOR decoding.
 pub mod decode;
 /// SBOR describing.
 pub mod describe;
 /// SBOR encoding.
 pub mod encode;
+/// SBOR JSON representation.
+#[cfg(feature = "json")]
+pub mod json;
 /// SBOR paths.
 pub mod path;
 /// A facade of Rust types.
 pub mod rust;
+/// SBOR streaming traversal.
+pub mod traverser;
 /// SBOR type ids.
 pub mod type_id;
 mod utils;
+/// SBOR payload validation.
+pub mod validate;
 
-pub use any::{decode_any, encode_any, encode_any_with_buffer, Value};
-pub use decode::{Decode, DecodeError, Decoder};
+pub use any::{
+    canonicalize_any, decode_any, decode_any_with_limits, encode_any, encode_any_with_buffer, Value,
+};
+#[cfg(feature = "arena")]
+pub use arena::{decode_any_in, decode_any_in_with_limits, ArenaValue};
+pub use decode::{Decode, DecodeError, DecodeLimits, Decoder};
 pub use describe::{Describe, Type};
 pub use encode::{Encode, Encoder};
+#[cfg(feature = "json")]
+pub use json::{from_json_value, to_json_value, JsonError};
+pub use traverser::{ContainerHeader, TraversalEvent, Traverser};
 pub use type_id::TypeId;
 pub use utils::*;
+pub use validate::{validate, ValidateError};
 
 // Re-export derives
 extern crate sbor_derive;
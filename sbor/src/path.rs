@@ -1,4 +1,7 @@
 use crate::any::Value;
+use crate::describe::{Fields, Type};
+use crate::rust::string::String;
+use crate::rust::string::ToString;
 use crate::rust::vec;
 use crate::rust::vec::Vec;
 
@@ -43,12 +46,90 @@ impl SborPath {
         let rel_path = SborValueRetriever(&self.0);
         rel_path.get_from_mut(value)
     }
+
+    /// Resolves a path of segments against a value. Each segment is either an index into the
+    /// children of a value or, if the type of the value is given, the name of a struct or enum
+    /// field.
+    pub fn resolve(
+        value: &Value,
+        ty: Option<&Type>,
+        segments: &[&str],
+    ) -> Result<Self, SborPathError> {
+        let mut path = Vec::new();
+        let mut value = value;
+        let mut ty = ty;
+        for segment in segments {
+            let index = match segment.parse::<usize>() {
+                Ok(index) => index,
+                Err(_) => ty
+                    .and_then(|ty| field_index(value, ty, segment))
+                    .ok_or_else(|| SborPathError::UnknownField(segment.to_string()))?,
+            };
+            ty = ty.and_then(|ty| child_type(value, ty, index));
+            value = SborValueRetriever(&[index])
+                .get_from(value)
+                .ok_or_else(|| SborPathError::NotFound(segment.to_string()))?;
+            path.push(index);
+        }
+        Ok(SborPath(path))
+    }
+}
+
+/// Represents an error when resolving a path of field names and indexes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SborPathError {
+    /// The segment is neither an index nor the name of a field.
+    UnknownField(String),
+    /// The segment does not refer to a child of the value.
+    NotFound(String),
+}
+
+fn fields_of<'t>(value: &Value, ty: &'t Type) -> Option<&'t Fields> {
+    match (ty, value) {
+        (Type::Struct { fields, .. }, _) => Some(fields),
+        (Type::Enum { variants, .. }, Value::Enum { name, .. }) => variants
+            .iter()
+            .find(|variant| &variant.name == name)
+            .map(|variant| &variant.fields),
+        _ => None,
+    }
+}
+
+fn field_index(value: &Value, ty: &Type, name: &str) -> Option<usize> {
+    match fields_of(value, ty)? {
+        Fields::Named { named } => named.iter().position(|(field, _)| field == name),
+        _ => None,
+    }
+}
+
+fn child_type<'t>(value: &Value, ty: &'t Type, index: usize) -> Option<&'t Type> {
+    match ty {
+        Type::Struct { .. } | Type::Enum { .. } => match fields_of(value, ty)? {
+            Fields::Named { named } => named.get(index).map(|(_, ty)| ty),
+            Fields::Unnamed { unnamed } => unnamed.get(index),
+            Fields::Unit => None,
+        },
+        Type::Option { value } => Some(value),
+        Type::Result { okay, error } => match value {
+            Value::Result { value } => Some(if value.is_ok() { okay } else { error }),
+            _ => None,
+        },
+        Type::Array { element, .. }
+        | Type::Vec { element }
+        | Type::TreeSet { element }
+        | Type::HashSet { element } => Some(element),
+        Type::Tuple { elements } => elements.get(index),
+        Type::TreeMap { key, value: v } | Type::HashMap { key, value: v } => {
+            Some(if index % 2 == 1 { v } else { key })
+        }
+        _ => None,
+    }
 }
 
 /// Helper structure which helps in retrieving a value given a root value and sbor path
-struct SborValueRetriever<'a>(&'a [usize]);
+struct SborValueRetriever<'p>(&'p [usize]);
 
-impl<'a> SborValueRetriever<'a> {
+impl<'p> SborValueRetriever<'p> {
     fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
@@ -59,14 +140,21 @@ impl<'a> SborValueRetriever<'a> {
         (index, SborValueRetriever(extended_path))
     }
 
-    fn get_from_vector(&self, values: &'a [Value]) -> Option<&'a Value> {
+    fn get_from_vector<'a>(&self, values: &'a [Value]) -> Option<&'a Value> {
         let (index, next_path) = self.pop();
         values
             .get(index)
             .and_then(|value| next_path.get_from(value))
     }
 
-    fn get_from(self, value: &'a Value) -> Option<&'a Value> {
+    fn get_from_single<'a>(&self, value: &'a Value) -> Option<&'a Value> {
+        match self.pop() {
+            (0, next_path) => next_path.get_from(value),
+            _ => Option::None,
+        }
+    }
+
+    fn get_from(self, value: &Value) -> Option<&Value> {
         if self.is_empty() {
             return Option::Some(value);
         }
@@ -80,24 +168,31 @@ impl<'a> SborValueRetriever<'a> {
             | Value::Set { elements: vec, .. }
             | Value::Map { elements: vec, .. } => self.get_from_vector(vec),
             Value::Option { value } => match value.as_ref() {
-                Option::Some(value) => Option::Some(value),
+                Option::Some(value) => self.get_from_single(value),
                 Option::None => Option::None,
             },
             Value::Result { value } => match value.as_ref() {
-                Ok(result) | Err(result) => Some(result),
+                Ok(result) | Err(result) => self.get_from_single(result),
             },
             _ => Option::None,
         }
     }
 
-    fn get_from_vector_mut(&self, values: &'a mut [Value]) -> Option<&'a mut Value> {
+    fn get_from_vector_mut<'a>(&self, values: &'a mut [Value]) -> Option<&'a mut Value> {
         let (index, next_path) = self.pop();
         values
             .get_mut(index)
             .and_then(|value| next_path.get_from_mut(value))
     }
 
-    fn get_from_mut(self, value: &'a mut Value) -> Option<&'a mut Value> {
+    fn get_from_single_mut<'a>(&self, value: &'a mut Value) -> Option<&'a mut Value> {
+        match self.pop() {
+            (0, next_path) => next_path.get_from_mut(value),
+            _ => Option::None,
+        }
+    }
+
+    fn get_from_mut(self, value: &mut Value) -> Option<&mut Value> {
         if self.is_empty() {
             return Option::Some(value);
         }
@@ -111,13 +206,89 @@ impl<'a> SborValueRetriever<'a> {
             | Value::Set { elements: vec, .. }
             | Value::Map { elements: vec, .. } => self.get_from_vector_mut(vec),
             Value::Option { value } => match value.as_mut() {
-                Option::Some(value) => Option::Some(value),
+                Option::Some(value) => self.get_from_single_mut(value),
                 Option::None => Option::None,
             },
             Value::Result { value } => match value.as_mut() {
-                Ok(result) | Err(result) => Some(result),
+                Ok(result) | Err(result) => self.get_from_single_mut(result),
             },
             _ => Option::None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::rust::boxed::Box;
+    use crate::rust::string::String;
+    use crate::rust::vec;
+    use crate::*;
+
+    use super::*;
+
+    #[derive(TypeId, Encode, Describe)]
+    struct Inner {
+        x: u8,
+        y: Option<(u8, String)>,
+    }
+
+    #[derive(TypeId, Encode, Describe)]
+    struct Outer {
+        a: u32,
+        inner: Inner,
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        let value = decode_any(&encode_with_static_info(&Outer {
+            a: 1,
+            inner: Inner {
+                x: 2,
+                y: Some((3, "s".to_string())),
+            },
+        }))
+        .unwrap();
+        let ty = Outer::describe();
+
+        let path = SborPath::resolve(&value, Some(&ty), &["inner", "y", "0", "1"]).unwrap();
+        assert_eq!(path, SborPath::new(vec![1, 1, 0, 1]));
+        assert_eq!(
+            path.get_from_value(&value),
+            Some(&Value::String {
+                value: "s".to_string()
+            })
+        );
+
+        assert_eq!(
+            SborPath::resolve(&value, None, &["1", "0"]),
+            Ok(SborPath::new(vec![1, 0]))
+        );
+        assert_eq!(
+            SborPath::resolve(&value, None, &["inner"]),
+            Err(SborPathError::UnknownField("inner".to_string()))
+        );
+        assert_eq!(
+            SborPath::resolve(&value, Some(&ty), &["inner", "z"]),
+            Err(SborPathError::UnknownField("z".to_string()))
+        );
+        assert_eq!(
+            SborPath::resolve(&value, Some(&ty), &["inner", "5"]),
+            Err(SborPathError::NotFound("5".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_get_from_value_through_option() {
+        let value = Value::Option {
+            value: Box::new(Some(Value::Tuple {
+                elements: vec![Value::U8 { value: 1 }, Value::U8 { value: 2 }],
+            })),
+        };
+
+        assert_eq!(
+            SborPath::new(vec![0, 1]).get_from_value(&value),
+            Some(&Value::U8 { value: 2 })
+        );
+        assert_eq!(SborPath::new(vec![1]).get_from_value(&value), None);
+    }
+}
@@ -0,0 +1,406 @@
+use crate::any::{decode_next_value, Value};
+use crate::decode::*;
+use crate::path::MutableSborPath;
+use crate::rust::string::String;
+use crate::rust::vec::Vec;
+use crate::type_id::*;
+
+/// The header of a container value, emitted when the traverser enters it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContainerHeader {
+    Struct {
+        len: usize,
+    },
+    Enum {
+        name: String,
+        len: usize,
+    },
+    Option {
+        is_some: bool,
+    },
+    Result {
+        is_ok: bool,
+    },
+    Array {
+        element_type_id: u8,
+        len: usize,
+    },
+    Tuple {
+        len: usize,
+    },
+    List {
+        element_type_id: u8,
+        len: usize,
+    },
+    Set {
+        element_type_id: u8,
+        len: usize,
+    },
+    /// A map with `len` entries; its children alternate between keys and values.
+    Map {
+        key_type_id: u8,
+        value_type_id: u8,
+        len: usize,
+    },
+}
+
+/// An event emitted by a [`Traverser`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraversalEvent<'de> {
+    /// The start of a container, followed by the events of its children and a `ContainerEnd`.
+    ContainerStart(ContainerHeader),
+    /// The end of the innermost open container.
+    ContainerEnd,
+    /// A primitive value.
+    Terminal(Value),
+    /// A custom value, borrowed from the payload.
+    Custom { type_id: u8, bytes: &'de [u8] },
+}
+
+/// The type ids of the children of a container, when fixed by its header.
+enum ChildTypes {
+    Any,
+    All(u8),
+    KeyValue(u8, u8),
+}
+
+struct Frame {
+    child_types: ChildTypes,
+    next_index: usize,
+    len: usize,
+}
+
+/// A pull-based SBOR decoder which walks a payload and emits events, without building
+/// the `Value` tree in memory.
+///
+/// Paths reported by [`Traverser::path`] follow the same indexing as `traverse_any`.
+pub struct Traverser<'de> {
+    decoder: Decoder<'de>,
+    path: MutableSborPath,
+    stack: Vec<Frame>,
+    started: bool,
+    pop_path: bool,
+}
+
+impl<'de> Traverser<'de> {
+    pub fn new(payload: &'de [u8]) -> Self {
+        Self::from_decoder(Decoder::with_static_info(payload))
+    }
+
+    pub fn new_with_limits(payload: &'de [u8], limits: &DecodeLimits) -> Result<Self, DecodeError> {
+        Decoder::new_with_limits(payload, true, limits).map(Self::from_decoder)
+    }
+
+    fn from_decoder(decoder: Decoder<'de>) -> Self {
+        Self {
+            decoder,
+            path: MutableSborPath::new(),
+            stack: Vec::new(),
+            started: false,
+            pop_path: false,
+        }
+    }
+
+    /// The path of the value of the last emitted event.
+    pub fn path(&self) -> &MutableSborPath {
+        &self.path
+    }
+
+    /// Returns the next event, or `None` once the whole payload has been traversed.
+    pub fn next_event(&mut self) -> Result<Option<TraversalEvent<'de>>, DecodeError> {
+        if self.pop_path {
+            self.path.pop();
+            self.pop_path = false;
+        }
+
+        let ty_ctx = if !self.started {
+            self.started = true;
+            None
+        } else {
+            match self.stack.last_mut() {
+                None => {
+                    self.decoder.check_end()?;
+                    return Ok(None);
+                }
+                Some(frame) if frame.next_index == frame.len => {
+                    self.stack.pop();
+                    self.decoder.exit();
+                    self.pop_path = !self.stack.is_empty();
+                    return Ok(Some(TraversalEvent::ContainerEnd));
+                }
+                Some(frame) => {
+                    let index = frame.next_index;
+                    frame.next_index += 1;
+                    self.path.push(index);
+                    match frame.child_types {
+                        ChildTypes::Any => None,
+                        ChildTypes::All(ty) => Some(ty),
+                        ChildTypes::KeyValue(key, value) => {
+                            Some(if index % 2 == 0 { key } else { value })
+                        }
+                    }
+                }
+            }
+        };
+
+        self.decoder.enter()?;
+        let event = self.read_value(ty_ctx)?;
+        match event {
+            TraversalEvent::ContainerStart(_) => {}
+            _ => {
+                self.decoder.exit();
+                self.pop_path = !self.stack.is_empty();
+            }
+        }
+        Ok(Some(event))
+    }
+
+    fn read_value(&mut self, ty_ctx: Option<u8>) -> Result<TraversalEvent<'de>, DecodeError> {
+        let dec = &mut self.decoder;
+        let ty = match ty_ctx {
+            Some(t) => t,
+            None => dec.read_type()?,
+        };
+
+        let (header, child_types, len) = match ty {
+            // struct & enum
+            TYPE_STRUCT => {
+                let len = dec.read_dynamic_size()?;
+                (ContainerHeader::Struct { len }, ChildTypes::Any, len)
+            }
+            TYPE_ENUM => {
+                let name = <String>::decode_value(dec)?;
+                let len = dec.read_dynamic_size()?;
+                (ContainerHeader::Enum { name, len }, ChildTypes::Any, len)
+            }
+            TYPE_OPTION => match dec.read_variant_index()? {
+                OPTION_VARIANT_SOME => (
+                    ContainerHeader::Option { is_some: true },
+                    ChildTypes::Any,
+                    1,
+                ),
+                OPTION_VARIANT_NONE => (
+                    ContainerHeader::Option { is_some: false },
+                    ChildTypes::Any,
+                    0,
+                ),
+                index => return Err(DecodeError::InvalidIndex(index)),
+            },
+            TYPE_RESULT => match dec.read_variant_index()? {
+                RESULT_VARIANT_OK => (ContainerHeader::Result { is_ok: true }, ChildTypes::Any, 1),
+                RESULT_VARIANT_ERR => {
+                    (ContainerHeader::Result { is_ok: false }, ChildTypes::Any, 1)
+                }
+                index => return Err(DecodeError::InvalidIndex(index)),
+            },
+            // composite types
+            TYPE_ARRAY => {
+                let element_type_id = dec.read_type()?;
+                let len = dec.read_dynamic_size()?;
+                (
+                    ContainerHeader::Array {
+                        element_type_id,
+                        len,
+                    },
+                    ChildTypes::All(element_type_id),
+                    len,
+                )
+            }
+            TYPE_TUPLE => {
+                let len = dec.read_dynamic_size()?;
+                (ContainerHeader::Tuple { len }, ChildTypes::Any, len)
+            }
+            // collections
+            TYPE_LIST => {
+                let element_type_id = dec.read_type()?;
+                let len = dec.read_dynamic_size()?;
+                (
+                    ContainerHeader::List {
+                        element_type_id,
+                        len,
+                    },
+                    ChildTypes::All(element_type_id),
+                    len,
+                )
+            }
+            TYPE_SET => {
+                let element_type_id = dec.read_type()?;
+                let len = dec.read_dynamic_size()?;
+                (
+                    ContainerHeader::Set {
+                        element_type_id,
+                        len,
+                    },
+                    ChildTypes::All(element_type_id),
+                    len,
+                )
+            }
+            TYPE_MAP => {
+                let key_type_id = dec.read_type()?;
+                let value_type_id = dec.read_type()?;
+                let len = dec.read_dynamic_size()?;
+                (
+                    ContainerHeader::Map {
+                        key_type_id,
+                        value_type_id,
+                        len,
+                    },
+                    ChildTypes::KeyValue(key_type_id, value_type_id),
+                    len * 2,
+                )
+            }
+            // custom types
+            _ if ty >= TYPE_CUSTOM_START => {
+                let len = dec.read_dynamic_size()?;
+                let bytes = dec.read_bytes(len)?;
+                return Ok(TraversalEvent::Custom { type_id: ty, bytes });
+            }
+            // primitive types
+            _ => return decode_next_value(Some(ty), dec).map(TraversalEvent::Terminal),
+        };
+
+        self.stack.push(Frame {
+            child_types,
+            next_index: 0,
+            len,
+        });
+        Ok(TraversalEvent::ContainerStart(header))
+    }
+}
+
+impl<'de> Iterator for Traverser<'de> {
+    type Item = Result<TraversalEvent<'de>, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_event().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::path::SborPath;
+    use crate::rust::collections::*;
+    use crate::rust::string::ToString;
+    use crate::rust::vec;
+    use crate::*;
+
+    use super::*;
+
+    #[derive(TypeId, Encode)]
+    struct TestStruct {
+        a: u32,
+        b: Option<String>,
+    }
+
+    #[test]
+    pub fn test_traverse_struct() {
+        let bytes = encode_with_static_info(&TestStruct {
+            a: 1,
+            b: Some("x".to_string()),
+        });
+        let events: Vec<TraversalEvent> = Traverser::new(&bytes).map(|e| e.unwrap()).collect();
+        assert_eq!(
+            events,
+            vec![
+                TraversalEvent::ContainerStart(ContainerHeader::Struct { len: 2 }),
+                TraversalEvent::Terminal(Value::U32 { value: 1 }),
+                TraversalEvent::ContainerStart(ContainerHeader::Option { is_some: true }),
+                TraversalEvent::Terminal(Value::String {
+                    value: "x".to_string()
+                }),
+                TraversalEvent::ContainerEnd,
+                TraversalEvent::ContainerEnd,
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_traverse_paths_match_traverse_any() {
+        let mut map = BTreeMap::new();
+        map.insert(1u8, vec![(), ()]);
+        map.insert(2u8, vec![()]);
+        let bytes = encode_with_static_info(&(5u8, map));
+
+        let mut traverser = Traverser::new(&bytes);
+        let mut paths = Vec::new();
+        while let Some(event) = traverser.next_event().unwrap() {
+            if let TraversalEvent::Terminal(Value::Unit) = event {
+                paths.push(SborPath::from(traverser.path().clone()));
+            }
+        }
+        assert_eq!(
+            paths,
+            vec![
+                SborPath::new(vec![1, 1, 0]),
+                SborPath::new(vec![1, 1, 1]),
+                SborPath::new(vec![1, 3, 0]),
+            ]
+        );
+
+        let value = decode_any(&bytes).unwrap();
+        for path in paths {
+            assert_eq!(path.get_from_value(&value), Some(&Value::Unit));
+        }
+    }
+
+    #[test]
+    pub fn test_traverse_custom() {
+        let bytes = vec![
+            TYPE_STRUCT,
+            1,
+            0,
+            0,
+            0,
+            TYPE_CUSTOM_START,
+            2,
+            0,
+            0,
+            0,
+            0xaa,
+            0xbb,
+        ];
+        let mut traverser = Traverser::new(&bytes);
+        traverser.next_event().unwrap();
+        assert_eq!(
+            traverser.next_event().unwrap(),
+            Some(TraversalEvent::Custom {
+                type_id: TYPE_CUSTOM_START,
+                bytes: &[0xaa, 0xbb]
+            })
+        );
+        assert_eq!(
+            SborPath::from(traverser.path().clone()),
+            SborPath::new(vec![0])
+        );
+        assert_eq!(
+            traverser.next_event().unwrap(),
+            Some(TraversalEvent::ContainerEnd)
+        );
+        assert_eq!(traverser.next_event().unwrap(), None);
+    }
+
+    #[test]
+    pub fn test_traverse_max_depth() {
+        let bytes = encode_with_static_info(&Some(Some(1u8)));
+        let limits = DecodeLimits {
+            max_depth: 2,
+            ..Default::default()
+        };
+
+        let result: Result<Vec<TraversalEvent>, DecodeError> =
+            Traverser::new_with_limits(&bytes, &limits)
+                .unwrap()
+                .collect();
+        assert_eq!(result, Err(DecodeError::MaxDepthExceeded(2)));
+        assert!(Traverser::new(&bytes).all(|e| e.is_ok()));
+    }
+
+    #[test]
+    pub fn test_traverse_trailing_bytes() {
+        let mut bytes = encode_with_static_info(&1u8);
+        bytes.push(0);
+        let mut traverser = Traverser::new(&bytes);
+        traverser.next_event().unwrap();
+        assert!(traverser.next_event().is_err());
+    }
+}
@@ -0,0 +1,81 @@
+use crate::any::decode_any;
+use crate::decode::DecodeError;
+use crate::describe::Type;
+
+/// Represents an error occurred when validating a payload against a type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidateError {
+    /// The payload is not valid SBOR.
+    DecodeError(DecodeError),
+    /// The payload is valid SBOR but does not conform to the type.
+    TypeMismatch,
+}
+
+/// Checks that a payload, with type info included, conforms to a type description.
+///
+/// The payload is decoded into a `Value`, so no Rust type for it needs to exist.
+pub fn validate(payload: &[u8], ty: &Type) -> Result<(), ValidateError> {
+    let value = decode_any(payload).map_err(ValidateError::DecodeError)?;
+    if ty.matches(&value) {
+        Ok(())
+    } else {
+        Err(ValidateError::TypeMismatch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::describe::Fields;
+    use crate::encode_with_static_info;
+    use crate::rust::boxed::Box;
+    use crate::rust::string::String;
+    use crate::rust::string::ToString;
+    use crate::rust::vec;
+    use crate::Describe;
+
+    #[derive(crate::TypeId, crate::Encode, crate::Describe)]
+    struct TestStruct {
+        a: u32,
+        b: String,
+    }
+
+    #[test]
+    fn test_validate() {
+        let payload = encode_with_static_info(&TestStruct {
+            a: 1,
+            b: "b".to_string(),
+        });
+
+        assert_eq!(validate(&payload, &TestStruct::describe()), Ok(()));
+        assert_eq!(
+            validate(
+                &payload,
+                &Type::Struct {
+                    name: "TestStruct".to_string(),
+                    fields: Fields::Named {
+                        named: vec![("a".to_string(), Type::U32), ("b".to_string(), Type::U32)]
+                    }
+                }
+            ),
+            Err(ValidateError::TypeMismatch)
+        );
+        assert_eq!(
+            validate(
+                &encode_with_static_info(&vec![1u8, 2u8]),
+                &Type::Vec {
+                    element: Box::new(Type::U8)
+                }
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_validate_invalid_payload() {
+        assert!(matches!(
+            validate(&[0xff], &Type::U8),
+            Err(ValidateError::DecodeError(_))
+        ));
+    }
+}
@@ -48,6 +48,9 @@ pub struct Fn {
     pub input: Type,
     pub output: Type,
     pub export_name: String,
+    /// The roles a method is tagged with, any of which may call it
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub roles: Vec<String>,
 }
 
 /// Whether a method is going to change the component state.
@@ -74,6 +74,12 @@ pub enum ScryptoType {
     // math
     Decimal,
     PreciseDecimal,
+    I256,
+    I384,
+    I512,
+    U256,
+    U384,
+    U512,
 
     // resource,
     Bucket,
@@ -88,7 +94,7 @@ pub enum ScryptoType {
 }
 
 // Need to update `scrypto-derive/src/import.rs` after changing the table below
-const MAPPING: [(ScryptoType, u8, &str); 19] = [
+const MAPPING: [(ScryptoType, u8, &str); 25] = [
     (ScryptoType::PackageAddress, 0x80, "PackageAddress"), // 128
     (ScryptoType::ComponentAddress, 0x81, "ComponentAddress"), // 129
     (ScryptoType::Component, 0x82, "ComponentAddress"),    // 130
@@ -116,6 +122,12 @@ const MAPPING: [(ScryptoType, u8, &str); 19] = [
     ), // 148
     (ScryptoType::Decimal, 0xa1, "Decimal"),               // 161
     (ScryptoType::PreciseDecimal, 0xa2, "PreciseDecimal"), // 162
+    (ScryptoType::I256, 0xa3, "I256"),                     // 163
+    (ScryptoType::I384, 0xa4, "I384"),                     // 164
+    (ScryptoType::I512, 0xa5, "I512"),                     // 165
+    (ScryptoType::U256, 0xa6, "U256"),                     // 166
+    (ScryptoType::U384, 0xa7, "U384"),                     // 167
+    (ScryptoType::U512, 0xa8, "U512"),                     // 168
     (ScryptoType::Bucket, 0xb1, "Bucket"),                 // 177
     (ScryptoType::Proof, 0xb2, "Proof"),                   // 178
     (ScryptoType::Vault, 0xb3, "Vault"),                   // 179
@@ -23,7 +23,9 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
     let bp_semi_token = &bp_strut.semi_token;
     let bp_impl = &bp.implementation;
     let bp_ident = &bp_strut.ident;
-    let bp_items = &bp_impl.items;
+    #[cfg_attr(feature = "no-abi-gen", allow(unused_variables))]
+    let (bp_items, bp_roles) = extract_method_roles(&bp_impl.items)?;
+    let bp_items = &bp_items;
     let bp_name = bp_ident.to_string();
     trace!("Blueprint name: {}", bp_name);
 
@@ -86,7 +88,7 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
     #[cfg(not(feature = "no-abi-gen"))]
     let output_abi = {
         let abi_ident = format_ident!("{}_abi", bp_ident);
-        let abi_functions = generate_abi(bp_ident, bp_items)?;
+        let abi_functions = generate_abi(bp_ident, bp_items, &bp_roles)?;
         quote! {
             #[no_mangle]
             pub extern "C" fn #abi_ident(input: *mut u8) -> *mut u8 {
@@ -131,6 +133,52 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
     Ok(output)
 }
 
+type MethodRoles = Vec<(String, Vec<String>)>;
+
+// Strips `#[role(..)]` attributes from methods and returns the remaining items, along with
+// the roles each tagged method requires.
+fn extract_method_roles(items: &[ImplItem]) -> Result<(Vec<ImplItem>, MethodRoles)> {
+    let mut stripped_items = Vec::new();
+    let mut method_roles = Vec::new();
+
+    for item in items {
+        let mut item = item.clone();
+        if let ImplItem::Method(ref mut m) = item {
+            let mut roles = Vec::new();
+            let mut attrs = Vec::new();
+            for attr in m.attrs.drain(..) {
+                if !attr.path.is_ident("role") {
+                    attrs.push(attr);
+                    continue;
+                }
+                let is_method = matches!(m.vis, Visibility::Public(_))
+                    && matches!(m.sig.inputs.first(), Some(FnArg::Receiver(_)));
+                if !is_method {
+                    return Err(Error::new(
+                        attr.span(),
+                        "Roles can only be assigned to public methods.",
+                    ));
+                }
+                let names = attr.parse_args_with(
+                    punctuated::Punctuated::<LitStr, Token![,]>::parse_terminated,
+                )?;
+                if names.is_empty() {
+                    return Err(Error::new(attr.span(), "Expected at least one role."));
+                }
+                roles.extend(names.iter().map(|name| name.value()));
+            }
+            m.attrs = attrs;
+
+            if !roles.is_empty() {
+                method_roles.push((m.sig.ident.to_string(), roles));
+            }
+        }
+        stripped_items.push(item);
+    }
+
+    Ok((stripped_items, method_roles))
+}
+
 fn generate_method_input_structs(bp_ident: &Ident, items: &[ImplItem]) -> Vec<ItemStruct> {
     let mut method_input_structs = Vec::new();
 
@@ -287,7 +335,11 @@ fn generate_dispatcher(
 
 // Parses function items of an `Impl` and returns ABI of functions.
 #[allow(dead_code)]
-fn generate_abi(bp_ident: &Ident, items: &[ImplItem]) -> Result<Vec<Expr>> {
+fn generate_abi(
+    bp_ident: &Ident,
+    items: &[ImplItem],
+    method_roles: &[(String, Vec<String>)],
+) -> Result<Vec<Expr>> {
     let mut fns = Vec::<Expr>::new();
 
     for item in items {
@@ -339,6 +391,11 @@ fn generate_abi(bp_ident: &Ident, items: &[ImplItem]) -> Result<Vec<Expr>> {
                         }
                     };
                     let export_name = format!("{}_{}", bp_ident, m.sig.ident);
+                    let roles = method_roles
+                        .iter()
+                        .find(|(method, _)| method.eq(&name))
+                        .map(|(_, roles)| roles.clone())
+                        .unwrap_or_default();
 
                     if mutability.is_none() {
                         fns.push(parse_quote! {
@@ -348,6 +405,7 @@ fn generate_abi(bp_ident: &Ident, items: &[ImplItem]) -> Result<Vec<Expr>> {
                                 input: #input,
                                 output: #output,
                                 export_name: #export_name.to_string(),
+                                roles: vec![],
                             }
                         });
                     } else {
@@ -358,6 +416,7 @@ fn generate_abi(bp_ident: &Ident, items: &[ImplItem]) -> Result<Vec<Expr>> {
                                 input: #input,
                                 output: #output,
                                 export_name: #export_name.to_string(),
+                                roles: vec![#(#roles.to_owned()),*],
                             }
                         });
                     }
@@ -636,6 +695,7 @@ mod tests {
                             input: Test_x_Input::describe(),
                             output: <u32>::describe(),
                             export_name: "Test_x".to_string(),
+                            roles: vec![],
                         },
                         ::scrypto::abi::Fn {
                             ident: "y".to_owned(),
@@ -643,6 +703,7 @@ mod tests {
                             input: Test_y_Input::describe(),
                             output: <u32>::describe(),
                             export_name: "Test_y".to_string(),
+                            roles: vec![],
                         }
                     ];
                     let structure: Type = Test_impl::Test::describe();
@@ -778,4 +839,27 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn test_blueprint_with_roles() {
+        let input = TokenStream::from_str(
+            "struct Test {} impl Test { #[role(\"admin\", \"operator\")] pub fn x(&self) {} #[role(\"admin\")] pub fn y(&mut self) {} }",
+        )
+        .unwrap();
+        let output = handle_blueprint(input).unwrap().to_string();
+
+        assert!(!output.contains("# [role"));
+        assert!(output.contains(
+            &quote! { roles: vec!["admin".to_owned(), "operator".to_owned()], }.to_string()
+        ));
+        assert!(output.contains(&quote! { roles: vec!["admin".to_owned()], }.to_string()));
+    }
+
+    #[test]
+    fn test_role_on_function_should_fail() {
+        let input =
+            TokenStream::from_str("struct Test {} impl Test { #[role(\"admin\")] pub fn x() {} }")
+                .unwrap();
+        assert!(handle_blueprint(input).is_err());
+    }
 }
@@ -326,6 +326,12 @@ fn get_native_type(ty: &des::Type) -> Result<(Type, Vec<Item>)> {
                 ScryptoType::EddsaEd25519Signature => "::scrypto::crypto::EddsaEd25519Signature",
                 ScryptoType::Decimal => "::scrypto::math::Decimal",
                 ScryptoType::PreciseDecimal => "::scrypto::math::PreciseDecimal",
+                ScryptoType::I256 => "::scrypto::math::I256",
+                ScryptoType::I384 => "::scrypto::math::I384",
+                ScryptoType::I512 => "::scrypto::math::I512",
+                ScryptoType::U256 => "::scrypto::math::U256",
+                ScryptoType::U384 => "::scrypto::math::U384",
+                ScryptoType::U512 => "::scrypto::math::U512",
                 ScryptoType::Bucket => "::scrypto::resource::Bucket",
                 ScryptoType::Proof => "::scrypto::resource::Proof",
                 ScryptoType::Vault => "::scrypto::resource::Vault",
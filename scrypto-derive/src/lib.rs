@@ -16,6 +16,10 @@ use proc_macro::TokenStream;
 /// This macro will derive the dispatcher method responsible for handling invocation
 /// according to Scrypto ABI.
 ///
+/// Public methods can be tagged with the roles allowed to call them, using
+/// `#[role("admin", "operator")]`. The rule of each role is declared once, through
+/// `AccessRules::role`, when adding an access check to the component.
+///
 /// # Example
 /// ```ignore
 /// use scrypto::prelude::*;
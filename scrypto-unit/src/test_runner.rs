@@ -1,65 +1,53 @@
-use std::fs;
-use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::path::Path;
 
-use radix_engine::constants::*;
-use radix_engine::engine::{ExecutionTrace, Kernel, KernelError, ModuleError, SystemApi};
-use radix_engine::engine::{RuntimeError, Track};
+use radix_engine::engine::{Kernel, RuntimeError, SystemApi};
 use radix_engine::fee::{FeeTable, SystemLoanFeeReserve};
 use radix_engine::ledger::*;
-use radix_engine::model::{export_abi, export_abi_by_component, extract_abi};
+use radix_engine::model::{
+    export_abi, export_abi_by_component, extract_abi, System, SystemSetFeeTableInput,
+};
 use radix_engine::state_manager::StagedSubstateStoreManager;
+use radix_engine::test_utils::{
+    compile_package, new_account_manifest, publish_package_manifest, TestExecutor,
+};
+pub use radix_engine::test_utils::{is_auth_error, is_costing_error, is_wasm_error};
 use radix_engine::transaction::{
-    ExecutionConfig, FeeReserveConfig, PreviewError, PreviewExecutor, PreviewResult,
-    TransactionExecutor, TransactionReceipt, TransactionResult,
+    ExecutionConfig, FeeReserveConfig, PreviewError, PreviewResult, TransactionReceipt,
 };
 use radix_engine::types::*;
-use radix_engine::wasm::{
-    DefaultWasmEngine, DefaultWasmInstance, InstructionCostRules, WasmInstrumenter,
-    WasmMeteringParams,
-};
+use radix_engine::wasm::{DefaultWasmEngine, DefaultWasmInstance};
 use sbor::describe::*;
 use scrypto::dec;
 use scrypto::math::Decimal;
 use transaction::builder::ManifestBuilder;
+use transaction::model::PreviewIntent;
 use transaction::model::{ExecutableTransaction, MethodIdentifier, TransactionManifest};
-use transaction::model::{PreviewIntent, TestTransaction};
 use transaction::signing::EcdsaSecp256k1PrivateKey;
 use transaction::validation::TestIntentHashManager;
 
+/// A test runner over a caller-provided substate store, which executes transactions through
+/// the engine's [`TestExecutor`] and stages them in nodes which can be merged or discarded.
 pub struct TestRunner<'s, S: ReadableSubstateStore + WriteableSubstateStore> {
     execution_stores: StagedSubstateStoreManager<'s, S>,
-    wasm_engine: DefaultWasmEngine,
-    wasm_instrumenter: WasmInstrumenter,
+    executor: TestExecutor,
     intent_hash_manager: TestIntentHashManager,
-    next_private_key: u64,
-    next_transaction_nonce: u64,
-    trace: bool,
 }
 
 impl<'s, S: ReadableSubstateStore + WriteableSubstateStore> TestRunner<'s, S> {
     pub fn new(trace: bool, substate_store: &'s mut S) -> Self {
         Self {
             execution_stores: StagedSubstateStoreManager::new(substate_store),
-            wasm_engine: DefaultWasmEngine::new(),
-            wasm_instrumenter: WasmInstrumenter::new(),
+            executor: TestExecutor::new(trace),
             intent_hash_manager: TestIntentHashManager::new(),
-            next_private_key: 1, // 0 is invalid
-            next_transaction_nonce: 0,
-            trace,
         }
     }
 
     pub fn next_transaction_nonce(&self) -> u64 {
-        self.next_transaction_nonce
+        self.executor.next_transaction_nonce()
     }
 
     pub fn new_key_pair(&mut self) -> (EcdsaSecp256k1PublicKey, EcdsaSecp256k1PrivateKey) {
-        let private_key = EcdsaSecp256k1PrivateKey::from_u64(self.next_private_key).unwrap();
-        let public_key = private_key.public_key();
-
-        self.next_private_key += 1;
-        (public_key, private_key)
+        self.executor.new_key_pair()
     }
 
     pub fn new_key_pair_with_auth_address(
@@ -97,15 +85,25 @@ impl<'s, S: ReadableSubstateStore + WriteableSubstateStore> TestRunner<'s, S> {
             .map(|output| output.substate.into())
     }
 
+    pub fn inspect_component_access_rules(
+        &mut self,
+        component_address: ComponentAddress,
+    ) -> Option<radix_engine::model::ComponentAccessRules> {
+        self.execution_stores
+            .get_root_store()
+            .get_substate(&SubstateId::ComponentAccessRules(component_address))
+            .map(|output| output.substate.into())
+    }
+
     pub fn inspect_key_value_entry(
         &mut self,
         kv_store_id: KeyValueStoreId,
         key: Vec<u8>,
-    ) -> Option<radix_engine::model::KeyValueStoreEntryWrapper> {
+    ) -> Option<ScryptoValue> {
         self.execution_stores
             .get_root_store()
             .get_substate(&SubstateId::KeyValueStoreEntry(kv_store_id, key))
-            .map(|output| output.substate.into())
+            .and_then(|output| output.substate.into())
     }
 
     pub fn inspect_vault(&mut self, vault_id: VaultId) -> Option<radix_engine::model::Vault> {
@@ -116,15 +114,7 @@ impl<'s, S: ReadableSubstateStore + WriteableSubstateStore> TestRunner<'s, S> {
     }
 
     pub fn new_account_with_auth_rule(&mut self, withdraw_auth: &AccessRule) -> ComponentAddress {
-        let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
-            .lock_fee(100.into(), SYS_FAUCET_COMPONENT)
-            .call_method(SYS_FAUCET_COMPONENT, "free_xrd", args!())
-            .take_from_worktop(RADIX_TOKEN, |builder, bucket_id| {
-                builder.new_account_with_resource(withdraw_auth, bucket_id)
-            })
-            .build();
-
-        let receipt = self.execute_manifest(manifest, vec![]);
+        let receipt = self.execute_manifest(new_account_manifest(withdraw_auth), vec![]);
         receipt.expect_commit_success();
 
         receipt
@@ -151,69 +141,14 @@ impl<'s, S: ReadableSubstateStore + WriteableSubstateStore> TestRunner<'s, S> {
         code: Vec<u8>,
         abi: HashMap<String, BlueprintAbi>,
     ) -> PackageAddress {
-        let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
-            .lock_fee(100.into(), SYS_FAUCET_COMPONENT)
-            .publish_package(code, abi)
-            .build();
-
-        let receipt = self.execute_manifest(manifest, vec![]);
+        let receipt = self.execute_manifest(publish_package_manifest(code, abi), vec![]);
         receipt.expect_commit_success();
         receipt.expect_commit().entity_changes.new_package_addresses[0]
     }
 
-    // Naive pattern matching to find the crate name.
-    fn extract_crate_name(mut content: &str) -> Result<String, ()> {
-        let idx = content.find("name").ok_or(())?;
-        content = &content[idx + 4..];
-
-        let idx = content.find('"').ok_or(())?;
-        content = &content[idx + 1..];
-
-        let end = content.find('"').ok_or(())?;
-        Ok(content[..end].to_string())
-    }
-
     pub fn compile_and_publish<P: AsRef<Path>>(&mut self, package_dir: P) -> PackageAddress {
-        // Build
-        let status = Command::new("cargo")
-            .current_dir(package_dir.as_ref())
-            .args(["build", "--target", "wasm32-unknown-unknown", "--release"])
-            .status()
-            .unwrap();
-        if !status.success() {
-            panic!("Failed to compile package: {:?}", package_dir.as_ref());
-        }
-
-        // Find wasm path
-        let mut cargo = package_dir.as_ref().to_owned();
-        cargo.push("Cargo.toml");
-        let wasm_name = if cargo.exists() {
-            let content = fs::read_to_string(cargo).expect("Failed to read the Cargo.toml file");
-            Self::extract_crate_name(&content)
-                .expect("Failed to extract crate name from the Cargo.toml file")
-                .replace("-", "_")
-        } else {
-            // file name
-            package_dir
-                .as_ref()
-                .file_name()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .to_owned()
-                .replace("-", "_")
-        };
-        let mut path = PathBuf::from(package_dir.as_ref());
-        path.push("target");
-        path.push("wasm32-unknown-unknown");
-        path.push("release");
-        path.push(wasm_name);
-        path.set_extension("wasm");
-
-        // Extract ABI
-        let code = fs::read(path).unwrap();
+        let code = compile_package(package_dir);
         let abi = extract_abi(&code).unwrap();
-
         self.publish_package(code, abi)
     }
 
@@ -256,12 +191,12 @@ impl<'s, S: ReadableSubstateStore + WriteableSubstateStore> TestRunner<'s, S> {
         let node_id = self.create_child_node(0);
         let substate_store = &mut self.execution_stores.get_output_store(node_id);
 
-        TransactionExecutor::new(
+        self.executor.execute_transaction(
             substate_store,
-            &mut self.wasm_engine,
-            &mut self.wasm_instrumenter,
+            transaction,
+            fee_reserve_config,
+            execution_config,
         )
-        .execute(transaction, fee_reserve_config, execution_config)
     }
 
     pub fn execute_preview(
@@ -272,14 +207,12 @@ impl<'s, S: ReadableSubstateStore + WriteableSubstateStore> TestRunner<'s, S> {
         let node_id = self.create_child_node(0);
         let substate_store = &mut self.execution_stores.get_output_store(node_id);
 
-        PreviewExecutor::new(
+        self.executor.execute_preview(
             substate_store,
-            &mut self.wasm_engine,
-            &mut self.wasm_instrumenter,
             &self.intent_hash_manager,
+            preview_intent,
             network,
         )
-        .execute(preview_intent)
     }
 
     pub fn execute_batch(
@@ -304,25 +237,9 @@ impl<'s, S: ReadableSubstateStore + WriteableSubstateStore> TestRunner<'s, S> {
         let mut store = self.execution_stores.get_output_store(node_id);
         let mut receipts = Vec::new();
         for (manifest, signer_public_keys) in manifests {
-            let transaction =
-                TestTransaction::new(manifest, self.next_transaction_nonce, signer_public_keys);
-            self.next_transaction_nonce += 1;
-            let receipt = TransactionExecutor::new(
-                &mut store,
-                &mut self.wasm_engine,
-                &mut self.wasm_instrumenter,
-            )
-            .execute_and_commit(
-                &transaction,
-                &FeeReserveConfig {
-                    cost_unit_price: DEFAULT_COST_UNIT_PRICE.parse().unwrap(),
-                    system_loan: DEFAULT_SYSTEM_LOAN,
-                },
-                &ExecutionConfig {
-                    max_call_depth: DEFAULT_MAX_CALL_DEPTH,
-                    trace: self.trace,
-                },
-            );
+            let receipt = self
+                .executor
+                .execute_manifest(&mut store, manifest, signer_public_keys);
             receipts.push(receipt);
         }
 
@@ -645,6 +562,31 @@ impl<'s, S: ReadableSubstateStore + WriteableSubstateStore> TestRunner<'s, S> {
         );
     }
 
+    /// Replaces the fee table through a system transaction, as governance would.
+    pub fn set_fee_table(&mut self, fee_table: FeeTable) -> Result<(), RuntimeError> {
+        self.kernel_call(
+            vec![NonFungibleAddress::new(
+                SYSTEM_TOKEN,
+                NonFungibleId::from_u32(0),
+            )],
+            |kernel| {
+                kernel
+                    .invoke_method(
+                        Receiver::Ref(RENodeId::System),
+                        FnIdentifier::Native(NativeFnIdentifier::System(
+                            SystemFnIdentifier::SetFeeTable,
+                        )),
+                        ScryptoValue::from_typed(&SystemSetFeeTableInput { fee_table }),
+                    )
+                    .map(|_| ())
+            },
+        )
+    }
+
+    pub fn get_fee_table(&mut self) -> FeeTable {
+        System::load_fee_table(self.execution_stores.get_root_store())
+    }
+
     pub fn get_current_epoch(&mut self) -> u64 {
         let current_epoch: ScryptoValue = self.kernel_call(vec![], |kernel| {
             kernel
@@ -661,68 +603,16 @@ impl<'s, S: ReadableSubstateStore + WriteableSubstateStore> TestRunner<'s, S> {
     }
 
     /// Performs a kernel call through a kernel with `is_system = true`.
-    fn kernel_call<F>(&mut self, initial_proofs: Vec<NonFungibleAddress>, fun: F) -> ScryptoValue
+    fn kernel_call<F, T>(&mut self, initial_proofs: Vec<NonFungibleAddress>, fun: F) -> T
     where
-        F: FnOnce(
-            &mut Kernel<DefaultWasmEngine, DefaultWasmInstance, SystemLoanFeeReserve>,
-        ) -> ScryptoValue,
+        F: FnOnce(&mut Kernel<DefaultWasmEngine, DefaultWasmInstance, SystemLoanFeeReserve>) -> T,
     {
-        let tx_hash = hash(self.next_transaction_nonce.to_string());
-        let blobs = HashMap::new();
         let substate_store = self.execution_stores.get_root_store();
-        let mut track = Track::new(
-            substate_store,
-            SystemLoanFeeReserve::default(),
-            FeeTable::new(),
-        );
-        let mut execution_trace = ExecutionTrace::new();
-
-        let mut kernel = Kernel::new(
-            tx_hash,
-            initial_proofs,
-            &blobs,
-            DEFAULT_MAX_CALL_DEPTH,
-            &mut track,
-            &mut self.wasm_engine,
-            &mut self.wasm_instrumenter,
-            WasmMeteringParams::new(InstructionCostRules::tiered(1, 5, 10, 5000), 512), // TODO: add to ExecutionConfig
-            &mut execution_trace,
-            Vec::new(),
-        );
-
-        // Invoke the system
-        let output: ScryptoValue = fun(&mut kernel);
-
-        // Commit
-        self.next_transaction_nonce += 1;
-        let receipt = track.finalize(Ok(Vec::new()), Vec::new());
-        if let TransactionResult::Commit(c) = receipt.result {
-            c.state_updates.commit(substate_store);
-        }
-
-        output
+        self.executor
+            .kernel_call(substate_store, initial_proofs, fun)
     }
 }
 
-pub fn is_auth_error(e: &RuntimeError) -> bool {
-    matches!(
-        e,
-        RuntimeError::ModuleError(ModuleError::AuthorizationError {
-            authorization: _,
-            function: _,
-            error: ::radix_engine::model::MethodAuthorizationError::NotAuthorized
-        })
-    )
-}
-
-pub fn is_costing_error(e: &RuntimeError) -> bool {
-    matches!(e, RuntimeError::ModuleError(ModuleError::CostingError(_)))
-}
-
-pub fn is_wasm_error(e: &RuntimeError) -> bool {
-    matches!(e, RuntimeError::KernelError(KernelError::WasmError(_)))
-}
-
 pub fn wat2wasm(wat: &str) -> Vec<u8> {
     wabt::wat2wasm(
         wat.replace("${memcpy}", include_str!("snippets/memcpy.wat"))
@@ -751,6 +641,7 @@ pub fn test_abi_any_in_void_out(
                 },
                 output: Type::Unit,
                 export_name: format!("{}_{}", blueprint_name, function_name),
+                roles: vec![],
             }],
         },
     );
@@ -40,7 +40,8 @@ impl Bech32Encoder {
         match component_address {
             ComponentAddress::Normal(data)
             | ComponentAddress::Account(data)
-            | ComponentAddress::System(data) => {
+            | ComponentAddress::System(data)
+            | ComponentAddress::AccessController(data) => {
                 self.encode(EntityType::component(component_address), data)
             }
         }
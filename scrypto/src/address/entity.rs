@@ -16,6 +16,9 @@ pub const ACCOUNT_COMPONENT_ADDRESS_ENTITY_ID: u8 = 0x03;
 /// A unique identifier used in the addressing of System Component Addresses.
 pub const SYSTEM_COMPONENT_ADDRESS_ENTITY_ID: u8 = 0x04;
 
+/// A unique identifier used in the addressing of Access Controller Component Addresses.
+pub const ACCESS_CONTROLLER_COMPONENT_ADDRESS_ENTITY_ID: u8 = 0x05;
+
 /// An enum which represents the different addressable entities.
 #[derive(PartialEq, Eq)]
 pub enum EntityType {
@@ -24,6 +27,7 @@ pub enum EntityType {
     NormalComponent,
     AccountComponent,
     SystemComponent,
+    AccessControllerComponent,
 }
 
 impl EntityType {
@@ -38,6 +42,7 @@ impl EntityType {
             ComponentAddress::Normal(_) => Self::NormalComponent,
             ComponentAddress::Account(_) => Self::AccountComponent,
             ComponentAddress::System(_) => Self::SystemComponent,
+            ComponentAddress::AccessController(_) => Self::AccessControllerComponent,
         }
     }
 
@@ -48,6 +53,7 @@ impl EntityType {
             Self::NormalComponent => NORMAL_COMPONENT_ADDRESS_ENTITY_ID,
             Self::AccountComponent => ACCOUNT_COMPONENT_ADDRESS_ENTITY_ID,
             Self::SystemComponent => SYSTEM_COMPONENT_ADDRESS_ENTITY_ID,
+            Self::AccessControllerComponent => ACCESS_CONTROLLER_COMPONENT_ADDRESS_ENTITY_ID,
         }
     }
 }
@@ -62,6 +68,7 @@ impl TryFrom<u8> for EntityType {
             NORMAL_COMPONENT_ADDRESS_ENTITY_ID => Ok(Self::NormalComponent),
             ACCOUNT_COMPONENT_ADDRESS_ENTITY_ID => Ok(Self::AccountComponent),
             SYSTEM_COMPONENT_ADDRESS_ENTITY_ID => Ok(Self::SystemComponent),
+            ACCESS_CONTROLLER_COMPONENT_ADDRESS_ENTITY_ID => Ok(Self::AccessControllerComponent),
             _ => Err(EntityTypeError::InvalidEntityTypeId(value)),
         }
     }
@@ -13,6 +13,7 @@ pub struct HrpSet {
     normal_component: String,
     account_component: String,
     system_component: String,
+    access_controller_component: String,
 }
 
 impl HrpSet {
@@ -24,6 +25,7 @@ impl HrpSet {
             EntityType::NormalComponent => &self.normal_component,
             EntityType::AccountComponent => &self.account_component,
             EntityType::SystemComponent => &self.system_component,
+            EntityType::AccessControllerComponent => &self.access_controller_component,
         }
     }
 }
@@ -35,6 +37,7 @@ impl From<&NetworkDefinition> for HrpSet {
             normal_component: format!("component_{}", suffix),
             account_component: format!("account_{}", suffix),
             system_component: format!("system_{}", suffix),
+            access_controller_component: format!("accesscontroller_{}", suffix),
             package: format!("package_{}", suffix),
             resource: format!("resource_{}", suffix),
         }
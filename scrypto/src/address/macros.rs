@@ -15,6 +15,9 @@ macro_rules! construct_address {
     (EntityType::SystemComponent, $($bytes:expr),*) => {
         ::scrypto::component::ComponentAddress::System([$($bytes),*])
     };
+    (EntityType::AccessControllerComponent, $($bytes:expr),*) => {
+        ::scrypto::component::ComponentAddress::AccessController([$($bytes),*])
+    };
 }
 
 #[macro_export]
@@ -0,0 +1,141 @@
+use sbor::*;
+
+use crate::buffer::scrypto_encode;
+use crate::component::ComponentAddress;
+use crate::core::{AccessControllerFnIdentifier, FnIdentifier, NativeFnIdentifier, Receiver};
+use crate::engine::types::RENodeId;
+use crate::engine::{api::*, call_engine};
+use crate::native_functions;
+use crate::resource::{AccessRule, Bucket, Proof};
+
+/// The rules of the roles of an access controller.
+#[derive(Debug, Clone, PartialEq, Eq, Describe, TypeId, Encode, Decode)]
+pub struct RuleSet {
+    /// The role which is allowed to create proofs of the controlled badge.
+    pub primary_role: AccessRule,
+    /// The role which is allowed to propose a new rule set and to lock the primary role.
+    pub recovery_role: AccessRule,
+    /// The role which is allowed to confirm recoveries proposed by the other roles.
+    pub confirmation_role: AccessRule,
+}
+
+/// A role of an access controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Describe, TypeId, Encode, Decode)]
+pub enum Role {
+    Primary,
+    Recovery,
+    Confirmation,
+}
+
+/// A recovery which was initiated by either the primary or the recovery role.
+#[derive(Debug, Clone, PartialEq, Eq, Describe, TypeId, Encode, Decode)]
+pub struct RecoveryProposal {
+    pub rule_set: RuleSet,
+    pub timed_recovery_delay_in_epochs: Option<u64>,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct AccessControllerCreateInput {
+    pub controlled_asset: Bucket,
+    pub rule_set: RuleSet,
+    pub timed_recovery_delay_in_epochs: Option<u64>,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct AccessControllerCreateProofInput {}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct AccessControllerInitiateRecoveryInput {
+    pub proposer: Role,
+    pub proposal: RecoveryProposal,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct AccessControllerQuickConfirmRecoveryInput {
+    pub proposer: Role,
+    pub proposal: RecoveryProposal,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct AccessControllerTimedConfirmRecoveryInput {
+    pub proposal: RecoveryProposal,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct AccessControllerCancelRecoveryInput {
+    pub proposer: Role,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct AccessControllerLockPrimaryRoleInput {}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct AccessControllerUnlockPrimaryRoleInput {}
+
+/// A native component which holds a badge in a vault, and hands out proofs of it to the primary
+/// role, while allowing the recovery and confirmation roles to replace the rule set.
+///
+/// A recovery proposed by one role is confirmed immediately by any other role, or, if the
+/// controller has a timed recovery delay, by the recovery role once the delay has elapsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AccessController(pub ComponentAddress);
+
+impl AccessController {
+    /// Creates an access controller which holds the given badge, and returns its address.
+    pub fn create(
+        controlled_asset: Bucket,
+        rule_set: RuleSet,
+        timed_recovery_delay_in_epochs: Option<u64>,
+    ) -> ComponentAddress {
+        let input = RadixEngineInput::InvokeFunction(
+            FnIdentifier::Native(NativeFnIdentifier::AccessController(
+                AccessControllerFnIdentifier::Create,
+            )),
+            scrypto_encode(&AccessControllerCreateInput {
+                controlled_asset,
+                rule_set,
+                timed_recovery_delay_in_epochs,
+            }),
+        );
+        call_engine(input)
+    }
+
+    native_functions! {
+        Receiver::Ref(RENodeId::AccessController(self.0)), NativeFnIdentifier::AccessController => {
+            pub fn create_proof(&self) -> Proof {
+                AccessControllerFnIdentifier::CreateProof,
+                AccessControllerCreateProofInput {}
+            }
+
+            pub fn initiate_recovery(&self, proposer: Role, proposal: RecoveryProposal) -> () {
+                AccessControllerFnIdentifier::InitiateRecovery,
+                AccessControllerInitiateRecoveryInput { proposer, proposal }
+            }
+
+            pub fn quick_confirm_recovery(&self, proposer: Role, proposal: RecoveryProposal) -> () {
+                AccessControllerFnIdentifier::QuickConfirmRecovery,
+                AccessControllerQuickConfirmRecoveryInput { proposer, proposal }
+            }
+
+            pub fn timed_confirm_recovery(&self, proposal: RecoveryProposal) -> () {
+                AccessControllerFnIdentifier::TimedConfirmRecovery,
+                AccessControllerTimedConfirmRecoveryInput { proposal }
+            }
+
+            pub fn cancel_recovery(&self, proposer: Role) -> () {
+                AccessControllerFnIdentifier::CancelRecovery,
+                AccessControllerCancelRecoveryInput { proposer }
+            }
+
+            pub fn lock_primary_role(&self) -> () {
+                AccessControllerFnIdentifier::LockPrimaryRole,
+                AccessControllerLockPrimaryRoleInput {}
+            }
+
+            pub fn unlock_primary_role(&self) -> () {
+                AccessControllerFnIdentifier::UnlockPrimaryRole,
+                AccessControllerUnlockPrimaryRoleInput {}
+            }
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use sbor::rust::fmt;
 use sbor::rust::str::FromStr;
 use sbor::rust::string::String;
+use sbor::rust::string::ToString;
 use sbor::rust::vec::Vec;
 use sbor::*;
 
@@ -12,13 +13,27 @@ use crate::core::*;
 use crate::engine::types::{RENodeId, SubstateId};
 use crate::engine::{api::*, call_engine};
 use crate::misc::*;
-use crate::resource::AccessRules;
+use crate::resource::{AccessRule, AccessRules};
 
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct ComponentAddAccessCheckInput {
     pub access_rules: AccessRules,
 }
 
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct ComponentSetAccessRuleInput {
+    pub index: u32,
+    pub method: String,
+    pub access_rule: AccessRule,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct ComponentSetRoleRuleInput {
+    pub index: u32,
+    pub role: String,
+    pub access_rule: AccessRule,
+}
+
 /// Represents the state of a component.
 pub trait ComponentState<C: LocalComponent>: Encode + Decode {
     /// Instantiates a component from this data structure.
@@ -71,6 +86,42 @@ impl Component {
         self
     }
 
+    /// Updates the access rule of a method in the `index`-th set of access rules.
+    ///
+    /// The update must satisfy the method's mutability rule, and fails if it is `LOCKED`.
+    pub fn set_access_rule(&self, index: usize, method_name: &str, access_rule: AccessRule) {
+        let input = RadixEngineInput::InvokeMethod(
+            Receiver::Ref(RENodeId::Component(self.0)),
+            FnIdentifier::Native(NativeFnIdentifier::Component(
+                ComponentFnIdentifier::SetAccessRule,
+            )),
+            scrypto_encode(&ComponentSetAccessRuleInput {
+                index: index as u32,
+                method: method_name.to_string(),
+                access_rule,
+            }),
+        );
+        call_engine(input)
+    }
+
+    /// Updates the rule of a role in the `index`-th set of access rules.
+    ///
+    /// The update must satisfy the role's mutability rule, and fails if it is `LOCKED`.
+    pub fn set_role_rule(&self, index: usize, role_name: &str, access_rule: AccessRule) {
+        let input = RadixEngineInput::InvokeMethod(
+            Receiver::Ref(RENodeId::Component(self.0)),
+            FnIdentifier::Native(NativeFnIdentifier::Component(
+                ComponentFnIdentifier::SetRoleRule,
+            )),
+            scrypto_encode(&ComponentSetRoleRuleInput {
+                index: index as u32,
+                role: role_name.to_string(),
+                access_rule,
+            }),
+        );
+        call_engine(input)
+    }
+
     pub fn globalize(self) -> ComponentAddress {
         let input = RadixEngineInput::RENodeGlobalize(RENodeId::Component(self.0));
         let _: () = call_engine(input);
@@ -128,6 +179,7 @@ pub enum ComponentAddress {
     Normal([u8; 26]),
     Account([u8; 26]),
     System([u8; 26]),
+    AccessController([u8; 26]),
 }
 
 impl ComponentAddress {}
@@ -147,6 +199,9 @@ impl TryFrom<&[u8]> for ComponentAddress {
                 EntityType::NormalComponent => Ok(Self::Normal(copy_u8_array(&slice[1..]))),
                 EntityType::AccountComponent => Ok(Self::Account(copy_u8_array(&slice[1..]))),
                 EntityType::SystemComponent => Ok(Self::System(copy_u8_array(&slice[1..]))),
+                EntityType::AccessControllerComponent => {
+                    Ok(Self::AccessController(copy_u8_array(&slice[1..])))
+                }
                 _ => Err(AddressError::InvalidEntityTypeId(slice[0])),
             },
             _ => Err(AddressError::InvalidLength(slice.len())),
@@ -159,7 +214,9 @@ impl ComponentAddress {
         let mut buf = Vec::new();
         buf.push(EntityType::component(self).id());
         match self {
-            Self::Normal(v) | Self::Account(v) | Self::System(v) => buf.extend(v),
+            Self::Normal(v) | Self::Account(v) | Self::System(v) | Self::AccessController(v) => {
+                buf.extend(v)
+            }
         }
         buf
     }
@@ -57,6 +57,18 @@ impl<K: Encode + Decode, V: 'static + Encode + Decode + TypeId> KeyValueStore<K,
         let input = RadixEngineInput::SubstateWrite(substate_id, scrypto_encode(&value));
         call_engine(input)
     }
+
+    /// Inserts a new key-value pair into this map, unless the key is already present.
+    ///
+    /// Returns whether the value was inserted.
+    pub fn insert_if_absent(&self, key: K, value: V) -> bool {
+        let input = RadixEngineInput::KeyValueStorePutIfAbsent(
+            self.id,
+            scrypto_encode(&key),
+            scrypto_encode(&value),
+        );
+        call_engine(input)
+    }
 }
 
 //========
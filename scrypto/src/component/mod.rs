@@ -1,9 +1,14 @@
+mod access_controller;
 mod component;
 mod kv_store;
 mod package;
 mod system;
 
+pub use access_controller::*;
 pub use component::*;
 pub use kv_store::{KeyValueStore, ParseKeyValueStoreError};
-pub use package::{BorrowedPackage, PackageAddress, PackagePublishInput};
+pub use package::{
+    BorrowedPackage, PackageAddress, PackagePublishInput, PackagePublishWithOwnerInput,
+    PackageUpdateMetadataInput,
+};
 pub use system::{component_system, init_component_system, ComponentSystem};
@@ -1,5 +1,7 @@
+use sbor::rust::collections::HashMap;
 use sbor::rust::fmt;
 use sbor::rust::str::FromStr;
+use sbor::rust::string::String;
 use sbor::rust::vec::Vec;
 use sbor::*;
 
@@ -7,6 +9,7 @@ use crate::abi::*;
 use crate::address::{AddressError, EntityType, BECH32_DECODER, BECH32_ENCODER};
 use crate::core::*;
 use crate::misc::*;
+use crate::resource::NonFungibleAddress;
 
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct PackagePublishInput {
@@ -14,6 +17,19 @@ pub struct PackagePublishInput {
     pub abi: Blob,
 }
 
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct PackagePublishWithOwnerInput {
+    pub code: Blob,
+    pub abi: Blob,
+    /// The badge required for package operations; a new one is minted if `None`.
+    pub owner_badge: Option<NonFungibleAddress>,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct PackageUpdateMetadataInput {
+    pub metadata: HashMap<String, String>,
+}
+
 /// A collection of blueprints, compiled and published as a single unit.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum PackageAddress {
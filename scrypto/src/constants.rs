@@ -23,3 +23,7 @@ pub const ED25519_TOKEN: ResourceAddress = address!(EntityType::Resource, 3u8);
 
 /// The XRD resource address.
 pub const RADIX_TOKEN: ResourceAddress = address!(EntityType::Resource, 4u8);
+
+/// The public key hash virtual resource address, whose badges are held by signers of any
+/// supported signature scheme.
+pub const PUBLIC_KEY_HASH_TOKEN: ResourceAddress = address!(EntityType::Resource, 5u8);
@@ -36,6 +36,7 @@ pub enum NativeFnIdentifier {
     Worktop(WorktopFnIdentifier),
     Package(PackageFnIdentifier),
     TransactionProcessor(TransactionProcessorFnIdentifier),
+    AccessController(AccessControllerFnIdentifier),
 }
 
 #[derive(
@@ -43,6 +44,8 @@ pub enum NativeFnIdentifier {
 )]
 pub enum ComponentFnIdentifier {
     AddAccessCheck,
+    SetAccessRule,
+    SetRoleRule,
 }
 
 #[derive(
@@ -52,6 +55,10 @@ pub enum SystemFnIdentifier {
     GetTransactionHash,
     GetCurrentEpoch,
     SetEpoch,
+    GetTransactionSigners,
+    SetFeeTable,
+    GetCostUnitPrice,
+    SetCostUnitPrice,
 }
 
 #[derive(
@@ -139,6 +146,7 @@ pub enum WorktopFnIdentifier {
     AssertContainsAmount,
     AssertContainsNonFungibles,
     Drain,
+    Contents,
 }
 
 #[derive(
@@ -146,6 +154,8 @@ pub enum WorktopFnIdentifier {
 )]
 pub enum PackageFnIdentifier {
     Publish,
+    PublishWithOwner,
+    UpdateMetadata,
 }
 
 #[derive(
@@ -155,6 +165,20 @@ pub enum TransactionProcessorFnIdentifier {
     Run,
 }
 
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, TypeId, Encode, Decode, Describe, PartialOrd, Ord,
+)]
+pub enum AccessControllerFnIdentifier {
+    Create,
+    CreateProof,
+    InitiateRecovery,
+    QuickConfirmRecovery,
+    TimedConfirmRecovery,
+    CancelRecovery,
+    LockPrimaryRole,
+    UnlockPrimaryRole,
+}
+
 // TODO: Remove and replace with real HeapRENodes
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub enum ScryptoRENode {
@@ -6,6 +6,7 @@ mod invocation;
 mod level;
 mod logger;
 mod network;
+mod random;
 mod runtime;
 
 pub use actor::ScryptoActor;
@@ -16,6 +17,9 @@ pub use invocation::*;
 pub use level::Level;
 pub use logger::Logger;
 pub use network::{NetworkDefinition, ParseNetworkError};
+pub use random::FromRandomBytes;
 pub use runtime::{
-    Runtime, SystemGetCurrentEpochInput, SystemGetTransactionHashInput, SystemSetEpochInput,
+    Runtime, SystemGetCostUnitPriceInput, SystemGetCurrentEpochInput,
+    SystemGetTransactionHashInput, SystemGetTransactionSignersInput, SystemSetCostUnitPriceInput,
+    SystemSetEpochInput,
 };
@@ -0,0 +1,45 @@
+/// A type which can be constructed from uniformly random bytes.
+pub trait FromRandomBytes: Sized {
+    /// The number of random bytes needed to construct a value.
+    const SIZE: usize;
+
+    /// Constructs a value from exactly `Self::SIZE` random bytes.
+    fn from_random_bytes(bytes: &[u8]) -> Self;
+}
+
+macro_rules! from_random_bytes_int {
+    ($($t:ident),*) => {
+        $(
+            impl FromRandomBytes for $t {
+                const SIZE: usize = core::mem::size_of::<$t>();
+
+                fn from_random_bytes(bytes: &[u8]) -> Self {
+                    $t::from_le_bytes(bytes.try_into().expect("Invalid random bytes length"))
+                }
+            }
+        )*
+    };
+}
+
+from_random_bytes_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl FromRandomBytes for bool {
+    const SIZE: usize = 1;
+
+    fn from_random_bytes(bytes: &[u8]) -> Self {
+        bytes[0] & 1 == 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_random_bytes() {
+        assert_eq!(u32::from_random_bytes(&[1, 0, 0, 0]), 1u32);
+        assert_eq!(i16::from_random_bytes(&[0xff, 0xff]), -1i16);
+        assert_eq!(bool::from_random_bytes(&[3]), true);
+        assert_eq!(bool::from_random_bytes(&[2]), false);
+    }
+}
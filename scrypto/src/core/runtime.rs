@@ -7,8 +7,10 @@ use crate::buffer::scrypto_encode;
 use crate::component::*;
 use crate::core::*;
 use crate::crypto::*;
-use crate::engine::types::{RENodeId, SubstateId};
+use crate::engine::types::{CheckpointId, RENodeId, SubstateId};
 use crate::engine::{api::*, call_engine};
+use crate::math::Decimal;
+use crate::resource::NonFungibleAddress;
 
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct SystemGetCurrentEpochInput {}
@@ -21,6 +23,17 @@ pub struct SystemSetEpochInput {
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct SystemGetTransactionHashInput {}
 
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct SystemGetTransactionSignersInput {}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct SystemGetCostUnitPriceInput {}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct SystemSetCostUnitPriceInput {
+    pub cost_unit_price: Decimal,
+}
+
 /// The transaction runtime.
 #[derive(Debug)]
 pub struct Runtime {}
@@ -49,6 +62,58 @@ impl Runtime {
         output
     }
 
+    /// Generates pseudo-random bytes, deterministically derived from the transaction hash.
+    ///
+    /// The bytes are unpredictable before the transaction hash is known, but anyone who knows
+    /// the transaction can reproduce them; they are not suitable for secrets.
+    pub fn random_bytes(len: u32) -> Vec<u8> {
+        let input = RadixEngineInput::GenerateRandomBytes(len);
+        call_engine(input)
+    }
+
+    /// Returns the number of cost units left before the transaction runs out, either by
+    /// reaching its cost unit limit or by exhausting the fees locked so far.
+    ///
+    /// Blueprints can use this to stop a long-running loop gracefully, instead of failing
+    /// the whole transaction.
+    pub fn remaining_cost_units() -> u32 {
+        let input = RadixEngineInput::GetRemainingCostUnits();
+        call_engine(input)
+    }
+
+    /// Emits an event with the given name and SBOR-encoded data, which is reported in the
+    /// transaction receipt if the transaction succeeds.
+    pub fn emit_event<T: Encode>(name: &str, event: &T) {
+        let input = RadixEngineInput::EmitEvent(name.to_owned(), scrypto_encode(event));
+        let _: () = call_engine(input);
+    }
+
+    /// Computes the hash digest of a message natively, which is much cheaper than hashing
+    /// within WASM.
+    pub fn hash<T: AsRef<[u8]>>(algorithm: HashAlgorithm, data: T) -> Hash {
+        let input = RadixEngineInput::Hash(algorithm, data.as_ref().to_vec());
+        call_engine(input)
+    }
+
+    /// Verifies a signature of a message, using the same schemes as transaction signing.
+    ///
+    /// Returns `false` if the signature is invalid or of a different scheme than the public key.
+    pub fn verify_signature<T: AsRef<[u8]>>(
+        message: T,
+        public_key: PublicKey,
+        signature: Signature,
+    ) -> bool {
+        let input =
+            RadixEngineInput::VerifySignature(message.as_ref().to_vec(), public_key, signature);
+        call_engine(input)
+    }
+
+    /// Generates a pseudo-random value, see [`Runtime::random_bytes`].
+    pub fn random<T: FromRandomBytes>() -> T {
+        let bytes = Self::random_bytes(T::SIZE as u32);
+        T::from_random_bytes(&bytes)
+    }
+
     /// Invokes a function on a blueprint.
     pub fn call_function<S: AsRef<str>, T: Decode>(
         package_address: PackageAddress,
@@ -111,4 +176,45 @@ impl Runtime {
         );
         call_engine(input)
     }
+
+    /// Returns the price of a cost unit in XRD, as currently set in the ledger.
+    pub fn cost_unit_price() -> Decimal {
+        let input = RadixEngineInput::InvokeMethod(
+            Receiver::Ref(RENodeId::System),
+            FnIdentifier::Native(NativeFnIdentifier::System(
+                SystemFnIdentifier::GetCostUnitPrice,
+            )),
+            scrypto_encode(&SystemGetCostUnitPriceInput {}),
+        );
+        call_engine(input)
+    }
+
+    /// Returns the non-fungible addresses of the transaction signers, which are the proofs
+    /// the initial auth zone was populated with.
+    pub fn transaction_signers() -> Vec<NonFungibleAddress> {
+        let input = RadixEngineInput::InvokeMethod(
+            Receiver::Ref(RENodeId::System),
+            FnIdentifier::Native(NativeFnIdentifier::System(
+                SystemFnIdentifier::GetTransactionSigners,
+            )),
+            scrypto_encode(&SystemGetTransactionSignersInput {}),
+        );
+        call_engine(input)
+    }
+
+    /// Marks a point which the state changes made by the current function or method can be
+    /// rolled back to.
+    ///
+    /// Checkpoints can only be taken while the caller holds no buckets or proofs.
+    pub fn execution_checkpoint() -> CheckpointId {
+        let input = RadixEngineInput::ExecutionCheckpoint();
+        call_engine(input)
+    }
+
+    /// Rolls back all state changes made since the given checkpoint, without aborting the
+    /// transaction.
+    pub fn rollback_to(checkpoint: CheckpointId) {
+        let input = RadixEngineInput::RollbackTo(checkpoint);
+        call_engine(input)
+    }
 }
@@ -0,0 +1,30 @@
+use ::blake2::digest::consts::U32;
+use ::blake2::{Blake2b, Digest};
+
+use crate::crypto::*;
+
+/// Computes the BLAKE2b-256 digest of a message.
+pub fn blake2b<T: AsRef<[u8]>>(data: T) -> Hash {
+    let mut instance = Blake2b::<U32>::new();
+    instance.update(data);
+    let result = instance.finalize();
+
+    Hash(result.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sbor::rust::str::FromStr;
+
+    #[test]
+    fn test_blake2b() {
+        let data = "Hello Radix";
+        let hash = blake2b(data);
+        assert_eq!(
+            hash,
+            Hash::from_str("48f1bd08444b5e713db9e14caac2faae71836786ac94d645b00679728202a935")
+                .unwrap()
+        );
+    }
+}
@@ -37,6 +37,25 @@ impl AsRef<[u8]> for Hash {
     }
 }
 
+/// Represents a hash algorithm natively supported by the engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TypeId, Encode, Decode)]
+pub enum HashAlgorithm {
+    Sha256,
+    Sha3,
+    Blake2b,
+}
+
+impl HashAlgorithm {
+    /// Computes the hash digest of a message with this algorithm.
+    pub fn hash<T: AsRef<[u8]>>(&self, data: T) -> Hash {
+        match self {
+            HashAlgorithm::Sha256 => crate::crypto::sha256(data),
+            HashAlgorithm::Sha3 => crate::crypto::sha3(data),
+            HashAlgorithm::Blake2b => crate::crypto::blake2b(data),
+        }
+    }
+}
+
 /// Computes the hash digest of a message.
 pub fn hash<T: AsRef<[u8]>>(data: T) -> Hash {
     // TODO: replace with whatever hash algorithm we eventually agrees on
@@ -3,6 +3,7 @@ use sbor::rust::vec::Vec;
 use sbor::{Decode, Encode, TypeId};
 
 use crate::core::{FnIdentifier, Level, Receiver, ScryptoRENode};
+use crate::crypto::{HashAlgorithm, PublicKey, Signature};
 use crate::engine::types::*;
 use crate::resource::AccessRule;
 
@@ -36,8 +37,19 @@ pub enum RadixEngineInput {
     RENodeGlobalize(RENodeId),
     SubstateRead(SubstateId),
     SubstateWrite(SubstateId, Vec<u8>),
+    KeyValueStorePutIfAbsent(KeyValueStoreId, Vec<u8>, Vec<u8>),
     GetActor(),
     EmitLog(Level, String),
+    EmitEvent(String, Vec<u8>),
     GenerateUuid(),
+    GenerateRandomBytes(u32),
+    GetRemainingCostUnits(),
+    Hash(HashAlgorithm, Vec<u8>),
+    VerifySignature(Vec<u8>, PublicKey, Signature),
     CheckAccessRule(AccessRule, Vec<ProofId>),
+    ExecutionCheckpoint(),
+    RollbackTo(CheckpointId),
+    /// Executes a sequence of reads, log emissions and ID generations in a single call,
+    /// returning the encoded output of each.
+    Batch(Vec<RadixEngineInput>),
 }
@@ -9,6 +9,7 @@ pub type KeyValueStoreId = (Hash, u32);
 pub type VaultId = (Hash, u32);
 pub type BucketId = u32;
 pub type ProofId = u32;
+pub type CheckpointId = u32;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Encode, Decode)]
 pub enum RENodeId {
@@ -20,6 +21,7 @@ pub enum RENodeId {
     Vault(VaultId),
     ResourceManager(ResourceAddress),
     Package(PackageAddress),
+    AccessController(ComponentAddress),
     System,
 }
 
@@ -46,7 +48,8 @@ impl Into<u32> for RENodeId {
 impl Into<ComponentAddress> for RENodeId {
     fn into(self) -> ComponentAddress {
         match self {
-            RENodeId::Component(component_address) => component_address,
+            RENodeId::Component(component_address)
+            | RENodeId::AccessController(component_address) => component_address,
             _ => panic!("Not a component address"),
         }
     }
@@ -82,6 +85,8 @@ pub enum SubstateId {
     KeyValueStoreEntry(KeyValueStoreId, Vec<u8>),
     Vault(VaultId),
     ComponentState(ComponentAddress),
+    ComponentAccessRules(ComponentAddress),
+    AccessController(ComponentAddress),
     System,
     Bucket(BucketId),
     Proof(ProofId),
@@ -92,7 +97,9 @@ impl Into<ComponentAddress> for SubstateId {
     fn into(self) -> ComponentAddress {
         match self {
             SubstateId::ComponentInfo(component_address)
-            | SubstateId::ComponentState(component_address) => component_address,
+            | SubstateId::ComponentState(component_address)
+            | SubstateId::ComponentAccessRules(component_address)
+            | SubstateId::AccessController(component_address) => component_address,
             _ => panic!("Address is not a component address"),
         }
     }
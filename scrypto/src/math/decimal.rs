@@ -1,8 +1,10 @@
 use core::ops::*;
+use num_bigint::{BigInt, Sign};
 use num_traits::{One, Pow, ToPrimitive, Zero};
 use paste::paste;
 use sbor::rust::convert::{TryFrom, TryInto};
 use sbor::rust::fmt;
+use sbor::rust::format;
 use sbor::rust::iter;
 use sbor::rust::str::FromStr;
 use sbor::rust::string::{String, ToString};
@@ -194,6 +196,29 @@ impl Decimal {
             );
         }
     }
+
+    /// Calculates the square root, rounded towards zero.
+    ///
+    /// Returns `None` if the number is negative.
+    pub fn sqrt(&self) -> Option<Self> {
+        self.nth_root(2)
+    }
+
+    /// Calculates the nth root, rounded towards zero.
+    ///
+    /// Returns `None` if `n` is zero, or if `n` is even and the number is negative.
+    pub fn nth_root(&self, n: u32) -> Option<Self> {
+        if n == 0 || (n % 2 == 0 && self.is_negative()) {
+            return None;
+        }
+        let m = BigInt::from(self.0);
+        let root = if self.is_negative() {
+            -scaled_nth_root(&-m, Self::SCALE, n, I256::BITS)?
+        } else {
+            scaled_nth_root(&m, Self::SCALE, n, I256::BITS)?
+        };
+        I256::try_from(root).ok().map(Self)
+    }
 }
 
 macro_rules! from_int {
@@ -348,6 +373,360 @@ where
     }
 }
 
+//========
+// checked and saturating arithmetic
+//========
+
+impl Decimal {
+    fn from_bigint(value: BigInt) -> Result<Self, ArithmeticError> {
+        I256::try_from(value)
+            .map(Self)
+            .map_err(|_| ArithmeticError::Overflow)
+    }
+
+    fn saturate(value: BigInt) -> Self {
+        let is_negative = value.sign() == Sign::Minus;
+        Self::from_bigint(value).unwrap_or(if is_negative { Self::MIN } else { Self::MAX })
+    }
+
+    fn bigint_add(self, other: Self) -> BigInt {
+        BigInt::from(self.0) + BigInt::from(other.0)
+    }
+
+    fn bigint_sub(self, other: Self) -> BigInt {
+        BigInt::from(self.0) - BigInt::from(other.0)
+    }
+
+    fn bigint_mul(self, other: Self) -> BigInt {
+        BigInt::from(self.0) * BigInt::from(other.0) / BigInt::from(Self::ONE.0)
+    }
+
+    fn bigint_div(self, other: Self) -> Result<BigInt, ArithmeticError> {
+        if other.is_zero() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        Ok(BigInt::from(self.0) * BigInt::from(Self::ONE.0) / BigInt::from(other.0))
+    }
+
+    /// Adds two decimals, returning an error on overflow.
+    pub fn try_add(self, other: Self) -> Result<Self, ArithmeticError> {
+        Self::from_bigint(self.bigint_add(other))
+    }
+
+    /// Subtracts two decimals, returning an error on overflow.
+    pub fn try_sub(self, other: Self) -> Result<Self, ArithmeticError> {
+        Self::from_bigint(self.bigint_sub(other))
+    }
+
+    /// Multiplies two decimals, returning an error on overflow.
+    ///
+    /// Unlike `*`, the intermediate product is not limited to 256 bits.
+    pub fn try_mul(self, other: Self) -> Result<Self, ArithmeticError> {
+        Self::from_bigint(self.bigint_mul(other))
+    }
+
+    /// Divides two decimals, returning an error on overflow or division by zero.
+    pub fn try_div(self, other: Self) -> Result<Self, ArithmeticError> {
+        Self::from_bigint(self.bigint_div(other)?)
+    }
+
+    /// Adds two decimals, clamping the result to `[MIN, MAX]`.
+    pub fn saturating_add(self, other: Self) -> Self {
+        Self::saturate(self.bigint_add(other))
+    }
+
+    /// Subtracts two decimals, clamping the result to `[MIN, MAX]`.
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self::saturate(self.bigint_sub(other))
+    }
+
+    /// Multiplies two decimals, clamping the result to `[MIN, MAX]`.
+    pub fn saturating_mul(self, other: Self) -> Self {
+        Self::saturate(self.bigint_mul(other))
+    }
+
+    /// Divides two decimals, clamping the result to `[MIN, MAX]`.
+    ///
+    /// # Panics
+    /// Panics if `other` is zero.
+    pub fn saturating_div(self, other: Self) -> Self {
+        Self::saturate(self.bigint_div(other).expect("Division by zero"))
+    }
+}
+
+impl CheckedAdd for Decimal {
+    fn checked_add(self, other: Self) -> Option<Self> {
+        self.try_add(other).ok()
+    }
+}
+
+impl CheckedSub for Decimal {
+    fn checked_sub(self, other: Self) -> Option<Self> {
+        self.try_sub(other).ok()
+    }
+}
+
+impl CheckedMul for Decimal {
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        self.try_mul(other).ok()
+    }
+}
+
+impl CheckedDiv for Decimal {
+    fn checked_div(self, other: Self) -> Option<Self> {
+        self.try_div(other).ok()
+    }
+}
+
+//========
+// nth root
+//========
+
+/// Computes `(m / 10^scale)^(1/n)` at the given scale, rounded down, for `m >= 0` and `n >= 1`.
+///
+/// Square and cube roots are taken directly on `m * 10^(scale * (n - 1))`. Higher roots are found
+/// by bisection instead, so that the cost grows with `log(n)` rather than with the size of that
+/// power. `max_exact_n` is the bit width of the backing integer: no value other than 0 and 1 has an
+/// exact root of a higher degree.
+///
+/// Returns `None` if `scale * (n - 1)` overflows.
+pub(crate) fn scaled_nth_root(m: &BigInt, scale: u32, n: u32, max_exact_n: u32) -> Option<BigInt> {
+    let one = BigInt::from(10u8).pow(scale);
+    if m.is_zero() || m == &one || n == 1 {
+        return Some(m.clone());
+    }
+    if n <= 3 {
+        // (m / 10^s)^(1/n) = (m * 10^(s * (n - 1)))^(1/n) / 10^s
+        let exp = scale.checked_mul(n - 1)?;
+        return Some((m * BigInt::from(10u8).pow(exp)).nth_root(n));
+    }
+    // The root lies in [m, 1) below one, and in (1, 2^k) above, where m < 2^(k * n) * 10^scale
+    let (mut lo, mut hi) = if m < &one {
+        (m.clone(), one.clone())
+    } else {
+        let k = (m.bits() - one.bits() + u64::from(n)) / u64::from(n);
+        (one.clone(), &one << k)
+    };
+    while &hi - &lo > BigInt::one() {
+        let mid = (&lo + &hi) / 2u8;
+        if power_at_most(&mid, n, m, scale, max_exact_n) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Some(lo)
+}
+
+/// Decides whether `(c / 10^scale)^n <= m / 10^scale`, for `c > 0` other than one.
+///
+/// The power is bracketed by exponentiation by squaring in fixed point, rounding down for the
+/// lower bound and up for the upper one. If the bracket straddles `m`, the powers are compared
+/// exactly when `n <= max_exact_n`, and otherwise the precision is doubled, which terminates as
+/// `c^n` can't be exactly `m` then.
+fn power_at_most(c: &BigInt, n: u32, m: &BigInt, scale: u32, max_exact_n: u32) -> bool {
+    // Enough digits to tell apart consecutive candidates, even for `m` down to `10^-scale`
+    let mut extra = scale * 2 + 20;
+    loop {
+        let shift = BigInt::from(10u8).pow(extra);
+        let one = BigInt::from(10u8).pow(scale) * &shift;
+        let target = m * &shift;
+        let growing = c * &shift > one;
+
+        let mut lower = one.clone();
+        let mut upper = one.clone();
+        let mut base_lower = c * &shift;
+        let mut base_upper = base_lower.clone();
+        let mut exp = n;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                lower = lower * &base_lower / &one;
+                upper = (upper * &base_upper + &one - 1u8) / &one;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base_lower = &base_lower * &base_lower / &one;
+                base_upper = (&base_upper * &base_upper + &one - 1u8) / &one;
+            }
+            // Above one, every factor still to come is at least one, so the power already
+            // exceeds `m` and the bounds need not grow any further
+            if growing && (lower > target || base_lower > target) {
+                return false;
+            }
+        }
+
+        if lower > target {
+            return false;
+        }
+        if upper <= target {
+            return true;
+        }
+        if n <= max_exact_n {
+            return c.pow(n) <= m * BigInt::from(10u8).pow(scale * (n - 1));
+        }
+        extra *= 2;
+    }
+}
+
+//========
+// exponential and logarithm
+//========
+
+/// The number of decimal places of the intermediate results of `ln`, `exp` and `powf`.
+const WORKING_SCALE: u32 = 38;
+
+/// `exp(x)` overflows `Decimal` for any `x` above this bound.
+const EXP_UPPER_BOUND: i64 = 136;
+
+/// `exp(x)` rounds to zero for any `x` below this bound.
+const EXP_LOWER_BOUND: i64 = -43;
+
+fn working_one() -> BigInt {
+    BigInt::from(10u8).pow(WORKING_SCALE)
+}
+
+/// Computes `atanh(z) = z + z^3/3 + z^5/5 + ...` at the working scale, for `|z| <= 1/3`.
+fn working_atanh(z: &BigInt, one: &BigInt) -> BigInt {
+    let z2 = z * z / one;
+    let mut term = z.clone();
+    let mut sum = BigInt::zero();
+    let mut i = 1u32;
+    while !term.is_zero() {
+        sum += &term / i;
+        term = term * &z2 / one;
+        i += 2;
+    }
+    sum
+}
+
+/// Computes `ln(2) = 2 * atanh(1/3)` at the working scale.
+fn working_ln2(one: &BigInt) -> BigInt {
+    working_atanh(&(one / 3u8), one) * 2u8
+}
+
+/// Computes `ln(x)` at the working scale, for `x > 0`.
+fn working_ln(x: &BigInt, one: &BigInt) -> BigInt {
+    // Reduce to x = m * 2^k with m in [1, 2), then ln(m) = 2 * atanh((m - 1) / (m + 1))
+    let mut k = x.bits() as i64 - one.bits() as i64;
+    let mut m = if k >= 0 { x >> k } else { x << -k };
+    while m >= one * 2u8 {
+        m >>= 1;
+        k += 1;
+    }
+    while &m < one {
+        m <<= 1;
+        k -= 1;
+    }
+    let z = (&m - one) * one / (&m + one);
+    working_atanh(&z, one) * 2u8 + working_ln2(one) * k
+}
+
+/// Computes `exp(x)` at the working scale, for `EXP_LOWER_BOUND <= x <= EXP_UPPER_BOUND`.
+fn working_exp(x: &BigInt, one: &BigInt) -> BigInt {
+    // Reduce to x = k * ln(2) + r with |r| < ln(2), then exp(x) = exp(r) * 2^k
+    let ln2 = working_ln2(one);
+    let k = x / &ln2;
+    let r = x - &k * &ln2;
+    let mut term = one.clone();
+    let mut sum = BigInt::zero();
+    let mut i = 1u32;
+    while !term.is_zero() {
+        sum += &term;
+        term = term * &r / one / i;
+        i += 1;
+    }
+    let k = k.to_i64().unwrap();
+    if k >= 0 {
+        sum << k
+    } else {
+        sum >> -k
+    }
+}
+
+impl Decimal {
+    fn to_working(self) -> BigInt {
+        BigInt::from(self.0) * BigInt::from(10u8).pow(WORKING_SCALE - Self::SCALE)
+    }
+
+    fn from_working(value: BigInt) -> Option<Self> {
+        // Round half away from zero
+        let divisor = BigInt::from(10u8).pow(WORKING_SCALE - Self::SCALE);
+        let half = &divisor / 2u8;
+        let value = if value.sign() == Sign::Minus {
+            (value - half) / divisor
+        } else {
+            (value + half) / divisor
+        };
+        I256::try_from(value).ok().map(Self)
+    }
+
+    fn exp_working(x: BigInt, one: &BigInt) -> Option<Self> {
+        if x > one * EXP_UPPER_BOUND {
+            None
+        } else if x < one * EXP_LOWER_BOUND {
+            Some(Self::ZERO)
+        } else {
+            Self::from_working(working_exp(&x, one))
+        }
+    }
+
+    /// Calculates the cube root, rounded towards zero.
+    pub fn cbrt(&self) -> Self {
+        self.nth_root(3)
+            .expect("Cube root is defined for all numbers")
+    }
+
+    /// Calculates the natural logarithm.
+    ///
+    /// The result is computed with 38 decimal places and then rounded half away from zero, so it
+    /// is deterministic and, barring ties at the 38th place, the correctly rounded value.
+    ///
+    /// Returns `None` if the number is not positive.
+    pub fn ln(&self) -> Option<Self> {
+        if !self.is_positive() {
+            return None;
+        }
+        let one = working_one();
+        Self::from_working(working_ln(&self.to_working(), &one))
+    }
+
+    /// Calculates `e` raised to the power of this number.
+    ///
+    /// The result is computed with 38 decimal places and then rounded half away from zero, so it
+    /// is deterministic and, barring ties at the 38th place, the correctly rounded value.
+    ///
+    /// Returns `None` if the result overflows.
+    pub fn exp(&self) -> Option<Self> {
+        let one = working_one();
+        Self::exp_working(self.to_working(), &one)
+    }
+
+    /// Approximates this number raised to a decimal power, as `exp(exp * ln(self))`.
+    ///
+    /// The intermediate logarithm is not rounded to 18 decimal places, so the result has the same
+    /// rounding as `exp`.
+    ///
+    /// Returns `None` if the number is negative, if the number is zero and the exponent is
+    /// negative, or if the result overflows.
+    pub fn powf(&self, exp: Decimal) -> Option<Self> {
+        if self.is_negative() {
+            return None;
+        }
+        if self.is_zero() {
+            return if exp.is_negative() {
+                None
+            } else if exp.is_zero() {
+                Some(Self::ONE)
+            } else {
+                Some(Self::ZERO)
+            };
+        }
+        let one = working_one();
+        let y = exp.to_working() * working_ln(&self.to_working(), &one) / &one;
+        Self::exp_working(y, &one)
+    }
+}
+
 //========
 // binary
 //========
@@ -382,53 +761,37 @@ scrypto_type!(Decimal, ScryptoType::Decimal, Vec::new());
 impl FromStr for Decimal {
     type Err = ParseDecimalError;
 
+    /// Parses a decimal, see `parse_scaled` for the accepted grammar.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut sign = I256::from(1u8);
-        let mut value = I256::from(0u8);
-
-        let chars: Vec<char> = s.chars().collect();
-        let mut p = 0;
-
-        // read sign
-        if chars[p] == '-' {
-            sign = I256::from(-1i8);
-            p += 1;
-        }
-
-        // read integral
-        while p < chars.len() && chars[p] != '.' {
-            let digit = read_digitdecimal(chars[p]);
-            match digit {
-                Ok(dig) => value = value * I256::from(10u8) + I256::from(dig) * sign,
-                Err(e) => return Err(e),
-            }
-            p += 1;
-        }
-
-        // read radix point
-        if p < chars.len() {
-            read_dotdecimal(chars[p])?;
-            p += 1;
-        }
-
-        // read fraction
-        for _ in 0..Self::SCALE {
-            if p < chars.len() {
-                let digit = read_digitdecimal(chars[p]);
-                match digit {
-                    Ok(dig) => value = value * I256::from(10u8) + I256::from(dig) * sign,
-                    Err(e) => return Err(e),
-                }
-                p += 1;
-            } else {
-                value *= I256::from(10u8);
-            }
-        }
+        let value = parse_scaled(s, Self::SCALE)?;
+        I256::try_from(value)
+            .map(Self)
+            .map_err(|_| ParseDecimalError::Overflow)
+    }
+}
 
-        if p < chars.len() {
-            Err(ParseDecimalError::UnsupportedDecimalPlace)
+impl Decimal {
+    /// Formats this number with exactly `decimal_places` digits after the decimal point,
+    /// rounding to the nearest and half away from zero, e.g. `1.005` as `"1.01"` with 2 places.
+    ///
+    /// # Panics
+    /// Panics if `decimal_places` is greater than `Decimal::SCALE`.
+    pub fn to_fixed_string(&self, decimal_places: u32) -> String {
+        let rounded = self.round(
+            decimal_places,
+            RoundingMode::TowardsNearestAndHalfAwayFromZero,
+        );
+        let s = rounded.to_string();
+        let (integral, fraction) = s.split_once('.').unwrap_or((&s, ""));
+        if decimal_places == 0 {
+            integral.to_string()
         } else {
-            Ok(Self(value))
+            format!(
+                "{}.{:0<width$}",
+                integral,
+                fraction,
+                width = decimal_places as usize
+            )
         }
     }
 }
@@ -476,21 +839,120 @@ impl fmt::Debug for Decimal {
         write!(f, "{}", self.to_string())
     }
 }
-fn read_digitdecimal(c: char) -> Result<U8, ParseDecimalError> {
-    let n = U8::from(c as u8);
-    if n >= U8(48u8) && n <= U8(48u8 + 9u8) {
-        Ok(n - U8(48u8))
+/// Parses `[+-]digits[.digits][(e|E)[+-]digits]` into an integer scaled by `10^scale`.
+///
+/// Digits may be grouped with underscores, e.g. `1_000.5`, and either the integral or the
+/// fractional part may be omitted, e.g. `.5` or `5.`. The grammar is independent of locale.
+///
+/// Returns `UnsupportedDecimalPlace` if the number has non-zero digits beyond `scale` decimal
+/// places, and `Overflow` if it is too large to be stored by any decimal type.
+pub(crate) fn parse_scaled(s: &str, scale: u32) -> Result<BigInt, ParseDecimalError> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut p = 0;
+
+    // read mantissa
+    let negative = read_sign(&chars, &mut p);
+    let mut digits = Vec::new();
+    let integral_len = read_digits(&chars, &mut p, &mut digits);
+    let mut fraction_len = 0;
+    if p < chars.len() && chars[p] == '.' {
+        p += 1;
+        fraction_len = read_digits(&chars, &mut p, &mut digits);
+    }
+
+    // read exponent
+    let mut exponent = 0i64;
+    if p < chars.len() && (chars[p] == 'e' || chars[p] == 'E') {
+        p += 1;
+        let exponent_negative = read_sign(&chars, &mut p);
+        let mut exponent_digits = Vec::new();
+        if read_digits(&chars, &mut p, &mut exponent_digits) == 0 {
+            return Err(ParseDecimalError::InvalidDecimal(s.to_string()));
+        }
+        for digit in exponent_digits {
+            exponent = exponent
+                .checked_mul(10)
+                .and_then(|e| e.checked_add(digit as i64))
+                .ok_or(if exponent_negative {
+                    ParseDecimalError::UnsupportedDecimalPlace
+                } else {
+                    ParseDecimalError::Overflow
+                })?;
+        }
+        if exponent_negative {
+            exponent = -exponent;
+        }
+    }
+
+    if p < chars.len() {
+        return Err(ParseDecimalError::InvalidChar(chars[p]));
+    }
+    if integral_len + fraction_len == 0 {
+        return Err(ParseDecimalError::InvalidDecimal(s.to_string()));
+    }
+
+    let mantissa = digits.iter().fold(BigInt::zero(), |m, d| m * 10u8 + *d);
+    if mantissa.is_zero() {
+        return Ok(mantissa);
+    }
+
+    // value = mantissa * 10^shift
+    let shift = (scale as i64)
+        .checked_add(exponent)
+        .and_then(|shift| shift.checked_sub(fraction_len as i64))
+        .ok_or(if exponent < 0 {
+            ParseDecimalError::UnsupportedDecimalPlace
+        } else {
+            ParseDecimalError::Overflow
+        })?;
+    let value = if shift >= 0 {
+        // A non-zero mantissa times 10^160 exceeds 512 bits
+        if shift > 160 {
+            return Err(ParseDecimalError::Overflow);
+        }
+        mantissa * BigInt::from(10u8).pow(shift as u32)
     } else {
-        Err(ParseDecimalError::InvalidChar(c))
+        // A non-zero mantissa with n digits is never a multiple of 10^(n + 1)
+        if shift < -(digits.len() as i64) {
+            return Err(ParseDecimalError::UnsupportedDecimalPlace);
+        }
+        let divisor = BigInt::from(10u8).pow(-shift as u32);
+        if !(&mantissa % &divisor).is_zero() {
+            return Err(ParseDecimalError::UnsupportedDecimalPlace);
+        }
+        mantissa / divisor
+    };
+
+    Ok(if negative { -value } else { value })
+}
+
+fn read_sign(chars: &[char], p: &mut usize) -> bool {
+    match chars.get(*p) {
+        Some('-') => {
+            *p += 1;
+            true
+        }
+        Some('+') => {
+            *p += 1;
+            false
+        }
+        _ => false,
     }
 }
 
-fn read_dotdecimal(c: char) -> Result<(), ParseDecimalError> {
-    if c == '.' {
-        Ok(())
-    } else {
-        Err(ParseDecimalError::InvalidChar(c))
+/// Reads decimal digits, which may be separated by single underscores, and returns how many.
+fn read_digits(chars: &[char], p: &mut usize, digits: &mut Vec<u8>) -> usize {
+    let start = digits.len();
+    while *p < chars.len() {
+        match chars[*p] {
+            c @ '0'..='9' => digits.push(c as u8 - b'0'),
+            '_' if digits.len() > start
+                && matches!(chars.get(*p + 1), Some(c) if c.is_ascii_digit()) => {}
+            _ => break,
+        }
+        *p += 1;
     }
+    digits.len() - start
 }
 
 //========
@@ -517,6 +979,23 @@ impl fmt::Display for ParseDecimalError {
     }
 }
 
+/// Represents an error in a checked arithmetic operation on Decimal or PreciseDecimal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArithmeticError {
+    Overflow,
+    DivisionByZero,
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for ArithmeticError {}
+
+#[cfg(not(feature = "alloc"))]
+impl fmt::Display for ArithmeticError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
 macro_rules! from_integer {
     ($($t:ident),*) => {
         $(
@@ -540,7 +1019,11 @@ macro_rules! try_from_integer {
                     type Error = ParseDecimalError;
 
                     fn try_from(val: $t) -> Result<Self, Self::Error> {
-                        Ok(Self(I256::try_from(val).map_err(|_| ParseDecimalError::Overflow).unwrap() * Self::ONE.0))
+                        I256::try_from(val)
+                            .ok()
+                            .and_then(|v| v.checked_mul(Self::ONE.0))
+                            .map(Self)
+                            .ok_or(ParseDecimalError::Overflow)
                     }
                 }
             )*
@@ -1064,4 +1547,267 @@ mod tests {
         let dec = Decimal::from_str("non_decimal_value");
         assert_eq!(dec, Err(ParseDecimalError::InvalidChar('n')));
     }
+
+    #[test]
+    fn test_checked_arithmetic_decimal() {
+        let a = Decimal::from(5u8);
+        let b = Decimal::from(2u8);
+        assert_eq!(a.checked_add(b), Some(Decimal::from(7u8)));
+        assert_eq!(a.checked_sub(b), Some(Decimal::from(3u8)));
+        assert_eq!(a.checked_mul(b), Some(Decimal::from(10u8)));
+        assert_eq!(a.checked_div(b), Some(Decimal::from("2.5")));
+        assert_eq!(Decimal::MAX.checked_add(Decimal::ONE), None);
+        assert_eq!(Decimal::MIN.checked_sub(Decimal::ONE), None);
+        assert_eq!(Decimal::MAX.try_mul(b), Err(ArithmeticError::Overflow));
+        assert_eq!(
+            a.try_div(Decimal::ZERO),
+            Err(ArithmeticError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_checked_mul_does_not_overflow_intermediate_decimal() {
+        let a = Decimal::MAX.try_div(Decimal::from(4u8)).unwrap();
+        assert_eq!(a.checked_mul(Decimal::from(2u8)), Some(a + a));
+    }
+
+    #[test]
+    fn test_saturating_arithmetic_decimal() {
+        let a = Decimal::from(5u8);
+        let b = Decimal::from(2u8);
+        assert_eq!(a.saturating_add(b), Decimal::from(7u8));
+        assert_eq!(Decimal::MAX.saturating_add(Decimal::ONE), Decimal::MAX);
+        assert_eq!(Decimal::MIN.saturating_sub(Decimal::ONE), Decimal::MIN);
+        assert_eq!(Decimal::MAX.saturating_mul(-b), Decimal::MIN);
+        assert_eq!(
+            Decimal::MAX.saturating_div(Decimal::from("0.5")),
+            Decimal::MAX
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_saturating_div_by_zero_decimal() {
+        let _ = Decimal::ONE.saturating_div(Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_sqrt_decimal() {
+        assert_eq!(Decimal::from(16u8).sqrt(), Some(Decimal::from(4u8)));
+        assert_eq!(Decimal::from("0.25").sqrt(), Some(Decimal::from("0.5")));
+        assert_eq!(Decimal::ZERO.sqrt(), Some(Decimal::ZERO));
+        assert_eq!(Decimal::from(-4i8).sqrt(), None);
+        assert_eq!(
+            Decimal::from(2u8).sqrt().unwrap().to_string()[..10],
+            *"1.41421356"
+        );
+    }
+
+    #[test]
+    fn test_nth_root_decimal() {
+        assert_eq!(Decimal::from(27u8).nth_root(3), Some(Decimal::from(3u8)));
+        assert_eq!(Decimal::from(-8i8).nth_root(3), Some(Decimal::from(-2i8)));
+        assert_eq!(Decimal::from(5u8).nth_root(1), Some(Decimal::from(5u8)));
+        assert_eq!(Decimal::from(5u8).nth_root(0), None);
+        assert_eq!(
+            Decimal::from(-1i8).nth_root(u32::MAX),
+            Some(Decimal::from(-1i8))
+        );
+        assert_eq!(Decimal::ZERO.nth_root(u32::MAX), Some(Decimal::ZERO));
+        assert_eq!(
+            Decimal::from(2u8).nth_root(u32::MAX),
+            Some(dec!("1.000000000161385904"))
+        );
+        assert_eq!(
+            dec!("0.5").nth_root(1_000_000),
+            Some(dec!("0.999999306853059666"))
+        );
+        assert_eq!(
+            Decimal::MAX.nth_root(1_000_000),
+            Some(dec!("1.000135315153638497"))
+        );
+    }
+
+    #[test]
+    fn test_cbrt_decimal() {
+        assert_eq!(Decimal::from(27u8).cbrt(), Decimal::from(3u8));
+        assert_eq!(Decimal::from(-64i8).cbrt(), Decimal::from(-4i8));
+        assert_eq!(Decimal::from(2u8).cbrt(), dec!("1.259921049894873164"));
+    }
+
+    #[test]
+    fn test_ln_decimal() {
+        assert_eq!(Decimal::ONE.ln(), Some(Decimal::ZERO));
+        assert_eq!(Decimal::from(2u8).ln(), Some(dec!("0.693147180559945309")));
+        assert_eq!(Decimal::from(10u8).ln(), Some(dec!("2.302585092994045684")));
+        assert_eq!(dec!("0.5").ln(), Some(dec!("-0.693147180559945309")));
+        assert_eq!(
+            Decimal(1i128.into()).ln(),
+            Some(dec!("-41.446531673892822312"))
+        );
+        assert_eq!(Decimal::MAX.ln(), Some(dec!("135.305999368893231589")));
+        assert_eq!(Decimal::ZERO.ln(), None);
+        assert_eq!(Decimal::from(-1i8).ln(), None);
+    }
+
+    #[test]
+    fn test_exp_decimal() {
+        assert_eq!(Decimal::ZERO.exp(), Some(Decimal::ONE));
+        assert_eq!(Decimal::ONE.exp(), Some(dec!("2.718281828459045235")));
+        assert_eq!(
+            Decimal::from(-1i8).exp(),
+            Some(dec!("0.367879441171442322"))
+        );
+        assert_eq!(
+            Decimal::from(10u8).exp(),
+            Some(dec!("22026.465794806716516958"))
+        );
+        assert_eq!(Decimal::from(-50i8).exp(), Some(Decimal::ZERO));
+        assert!(Decimal::from(135u8).exp().is_some());
+        assert_eq!(Decimal::from(136u8).exp(), None);
+        assert_eq!(Decimal::MAX.exp(), None);
+    }
+
+    #[test]
+    fn test_powf_decimal() {
+        assert_eq!(
+            Decimal::from(2u8).powf(dec!("0.5")),
+            Some(dec!("1.414213562373095049"))
+        );
+        assert_eq!(
+            Decimal::from(9u8).powf(dec!("1.5")),
+            Some(Decimal::from(27u8))
+        );
+        assert_eq!(Decimal::from(10u8).powf(dec!("-2")), Some(dec!("0.01")));
+        assert_eq!(Decimal::ZERO.powf(dec!("2")), Some(Decimal::ZERO));
+        assert_eq!(Decimal::ZERO.powf(Decimal::ZERO), Some(Decimal::ONE));
+        assert_eq!(Decimal::ZERO.powf(dec!("-1")), None);
+        assert_eq!(Decimal::from(-2i8).powf(dec!("0.5")), None);
+        assert_eq!(Decimal::from(10u8).powf(dec!("60")), None);
+    }
+
+    #[test]
+    fn test_try_from_wide_integer_decimal() {
+        assert_eq!(Decimal::try_from(U256::by(5u8)), Ok(Decimal::from(5u8)));
+        assert_eq!(
+            Decimal::try_from(I256::MAX),
+            Err(ParseDecimalError::Overflow)
+        );
+        assert_eq!(
+            Decimal::try_from(U512::MAX),
+            Err(ParseDecimalError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_from_str_grammar_decimal() {
+        assert_eq!(dec!("1_000.000_5"), dec!("1000.0005"));
+        assert_eq!(dec!("+1.5"), dec!("1.5"));
+        assert_eq!(dec!(".5"), dec!("0.5"));
+        assert_eq!(dec!("5."), dec!("5"));
+        assert_eq!(dec!("1e3"), dec!("1000"));
+        assert_eq!(dec!("1.5E-3"), dec!("0.0015"));
+        assert_eq!(dec!("-2.5e+2"), dec!("-250"));
+        assert_eq!(dec!("1e-18"), Decimal(1i128.into()));
+        assert_eq!(dec!("100e-20"), Decimal(1i128.into()));
+        assert_eq!(dec!("1.0000000000000000000"), dec!("1"));
+        assert_eq!(dec!("0e1000000"), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_from_str_grammar_failures_decimal() {
+        assert_eq!(
+            Decimal::from_str(""),
+            Err(ParseDecimalError::InvalidDecimal("".to_string()))
+        );
+        assert_eq!(
+            Decimal::from_str("-"),
+            Err(ParseDecimalError::InvalidDecimal("-".to_string()))
+        );
+        assert_eq!(
+            Decimal::from_str("1e"),
+            Err(ParseDecimalError::InvalidDecimal("1e".to_string()))
+        );
+        assert_eq!(
+            Decimal::from_str("_1"),
+            Err(ParseDecimalError::InvalidChar('_'))
+        );
+        assert_eq!(
+            Decimal::from_str("1__0"),
+            Err(ParseDecimalError::InvalidChar('_'))
+        );
+        assert_eq!(
+            Decimal::from_str("1_"),
+            Err(ParseDecimalError::InvalidChar('_'))
+        );
+        assert_eq!(
+            Decimal::from_str("1,5"),
+            Err(ParseDecimalError::InvalidChar(','))
+        );
+        assert_eq!(
+            Decimal::from_str("1.2.3"),
+            Err(ParseDecimalError::InvalidChar('.'))
+        );
+        assert_eq!(
+            Decimal::from_str("1e-19"),
+            Err(ParseDecimalError::UnsupportedDecimalPlace)
+        );
+        assert_eq!(
+            Decimal::from_str("0.0000000000000000001"),
+            Err(ParseDecimalError::UnsupportedDecimalPlace)
+        );
+        assert_eq!(Decimal::from_str("1e59"), Err(ParseDecimalError::Overflow));
+        assert_eq!(
+            Decimal::from_str("1e99999999999999999999"),
+            Err(ParseDecimalError::Overflow)
+        );
+        assert_eq!(
+            Decimal::from_str("1e9223372036854775807"),
+            Err(ParseDecimalError::Overflow)
+        );
+        assert_eq!(
+            Decimal::from_str("1.5e-9223372036854775807"),
+            Err(ParseDecimalError::UnsupportedDecimalPlace)
+        );
+    }
+
+    #[test]
+    fn test_to_fixed_string_decimal() {
+        assert_eq!(dec!("1.005").to_fixed_string(2), "1.01");
+        assert_eq!(dec!("-1.005").to_fixed_string(2), "-1.01");
+        assert_eq!(dec!("1.5").to_fixed_string(0), "2");
+        assert_eq!(dec!("1").to_fixed_string(3), "1.000");
+        assert_eq!(dec!("-0.0001").to_fixed_string(2), "0.00");
+        assert_eq!(
+            dec!("123.456").to_fixed_string(18),
+            "123.456000000000000000"
+        );
+    }
+
+    #[test]
+    fn test_from_str_round_trip_fuzz_decimal() {
+        // A deterministic xorshift generator, so failures are reproducible
+        let mut state = 0x2545f4914f6cdd1du64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..1000 {
+            let mut bytes = [0u8; 32];
+            for chunk in bytes.chunks_mut(8) {
+                chunk.copy_from_slice(&next().to_le_bytes());
+            }
+            // Vary the magnitude as well as the digits
+            let shift = (next() % 256) as usize;
+            let value = Decimal(I256::from_le_bytes(bytes) >> I256::from(shift as u32));
+
+            assert_eq!(Decimal::from_str(&value.to_string()), Ok(value));
+            let fixed = value.to_fixed_string(18);
+            assert_eq!(Decimal::from_str(&fixed), Ok(value));
+            let scientific = format!("{}e-18", fixed.replace('.', ""));
+            assert_eq!(Decimal::from_str(&scientific), Ok(value), "{}", scientific);
+        }
+    }
 }
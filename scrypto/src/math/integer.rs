@@ -5,7 +5,7 @@ use num_traits::FromPrimitive;
 use num_traits::{One, Pow, Signed, ToPrimitive, Zero};
 use paste::paste;
 use sbor::rust::cmp::{Ord, Ordering, PartialEq, PartialOrd};
-use sbor::rust::convert::{From, TryFrom};
+use sbor::rust::convert::{From, TryFrom, TryInto};
 use sbor::rust::fmt;
 use sbor::rust::ops::{Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign};
 use sbor::rust::ops::{BitXor, BitXorAssign, Div, DivAssign};
@@ -18,6 +18,8 @@ use sbor::rust::vec::Vec;
 use sbor::type_id::*;
 use sbor::*;
 
+use crate::abi::*;
+
 pub mod basic;
 pub mod bits;
 pub mod convert;
@@ -332,6 +334,74 @@ sbor_codec!(U32, TYPE_U32, U32);
 sbor_codec!(U64, TYPE_U64, U64);
 sbor_codec!(U128, TYPE_U128, U128);
 
+/// Integers wider than 128 bits have no SBOR type of their own, so they are encoded as
+/// Scrypto custom types with a fixed-length little-endian payload.
+macro_rules! scrypto_codec {
+    ($($t:ident),*) => {
+        paste! {
+            $(
+                impl $t {
+                    /// Parses the SBOR payload of this type, which must be exactly `BITS / 8` bytes.
+                    pub fn from_custom_slice(slice: &[u8]) -> Result<Self, [<Parse $t Error>]> {
+                        let bytes: [u8; (<$t>::BITS / 8) as usize] = slice
+                            .try_into()
+                            .map_err(|_| [<Parse $t Error>]::InvalidLength)?;
+                        Ok(Self::from_le_bytes(bytes))
+                    }
+                }
+
+                impl TypeId for $t {
+                    #[inline]
+                    fn type_id() -> u8 {
+                        ScryptoType::$t.id()
+                    }
+                }
+
+                impl Encode for $t {
+                    #[inline]
+                    fn encode_type_id(encoder: &mut Encoder) {
+                        encoder.write_type_id(Self::type_id());
+                    }
+                    #[inline]
+                    fn encode_value(&self, encoder: &mut Encoder) {
+                        let bytes = self.to_le_bytes();
+                        encoder.write_dynamic_size(bytes.len());
+                        encoder.write_slice(&bytes);
+                    }
+                }
+
+                impl Decode for $t {
+                    #[inline]
+                    fn check_type_id(decoder: &mut Decoder) -> Result<(), DecodeError> {
+                        decoder.check_type_id(Self::type_id())
+                    }
+                    fn decode_value(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+                        let len = decoder.read_dynamic_size()?;
+                        let slice = decoder.read_bytes(len)?;
+                        Self::from_custom_slice(slice).map_err(|err| {
+                            DecodeError::CustomError(::sbor::rust::format!(
+                                "Failed to decode {}: {:?}",
+                                stringify!($t),
+                                err
+                            ))
+                        })
+                    }
+                }
+
+                impl Describe for $t {
+                    fn describe() -> Type {
+                        Type::Custom {
+                            type_id: ScryptoType::$t.id(),
+                            generics: Vec::new(),
+                        }
+                    }
+                }
+            )*
+        }
+    };
+}
+scrypto_codec!(I256, I384, I512, U256, U384, U512);
+
 fn fmt<
     T: fmt::Display
         + Copy
@@ -917,6 +987,7 @@ checked_int_impl_unsigned_small! { U8, U16, U32, U64, U128 }
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::buffer::{scrypto_decode, scrypto_encode};
 
     fn encode_integers(enc: &mut Encoder) {
         I8::by(1i8).encode(enc);
@@ -972,4 +1043,62 @@ mod tests {
         assert_eq!(U64::by(1u8), <U64>::decode(&mut dec).unwrap());
         assert_eq!(U128::by(1u8), <U128>::decode(&mut dec).unwrap());
     }
+
+    #[test]
+    fn test_wide_integer_encoding() {
+        let bytes = scrypto_encode(&I256::by(-1i8));
+        let mut expected = vec![ScryptoType::I256.id(), 32, 0, 0, 0];
+        expected.extend([0xff; 32]);
+        assert_eq!(bytes, expected);
+
+        assert_eq!(scrypto_decode::<I256>(&bytes), Ok(I256::by(-1i8)));
+        assert_eq!(
+            scrypto_decode::<I384>(&scrypto_encode(&I384::MIN)),
+            Ok(I384::MIN)
+        );
+        assert_eq!(
+            scrypto_decode::<I512>(&scrypto_encode(&I512::MAX)),
+            Ok(I512::MAX)
+        );
+        assert_eq!(
+            scrypto_decode::<U256>(&scrypto_encode(&U256::MAX)),
+            Ok(U256::MAX)
+        );
+        assert_eq!(
+            scrypto_decode::<U384>(&scrypto_encode(&U384::MAX)),
+            Ok(U384::MAX)
+        );
+        assert_eq!(
+            scrypto_decode::<U512>(&scrypto_encode(&U512::MAX)),
+            Ok(U512::MAX)
+        );
+    }
+
+    #[test]
+    fn test_wide_integer_decoding_rejects_wrong_length() {
+        let bytes = vec![ScryptoType::U256.id(), 1, 0, 0, 0, 1];
+        assert!(matches!(
+            scrypto_decode::<U256>(&bytes),
+            Err(DecodeError::CustomError(_))
+        ));
+        assert_eq!(
+            U256::from_custom_slice(&[1]),
+            Err(ParseU256Error::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn test_try_from_large_into_signed() {
+        let max = I512::from(I256::MAX);
+        assert_eq!(I256::try_from(max), Ok(I256::MAX));
+        assert_eq!(
+            I256::try_from(max + I512::one()),
+            Err(ParseI256Error::Overflow)
+        );
+        assert_eq!(I256::try_from(U256::MAX), Err(ParseI256Error::Overflow));
+        assert_eq!(U256::try_from(U512::from(U256::MAX)), Ok(U256::MAX));
+        assert_eq!(I8::try_from(I256::from(127u8)), Ok(I8::by(127i8)));
+        assert_eq!(I8::try_from(I256::from(200u8)), Err(ParseI8Error::Overflow));
+        assert_eq!(i8::try_from(I256::from(200u8)), Err(Parsei8Error::Overflow));
+    }
 }
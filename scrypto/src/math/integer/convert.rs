@@ -91,7 +91,9 @@ macro_rules! try_from_large_into_large{
                                 other = <$o>::zero() - other;
                             }
                         }
-                        if (other.leading_zeros() as i32) < <$o>::BITS as i32 - <$t>::BITS as i32 {
+                        // A signed target also needs its sign bit to be clear
+                        let sign_bit = if <$t>::MIN == <$t>::zero() { 0 } else { 1 };
+                        if (other.leading_zeros() as i32) < <$o>::BITS as i32 - <$t>::BITS as i32 + sign_bit {
                             return Err([<Parse $t Error>]::Overflow);
                         }
                         let mut other_vec = other.0.to_vec();
@@ -172,7 +174,9 @@ macro_rules! try_from_large_into_small{
                                 other = <$o>::zero() - other;
                             }
                         }
-                        if (other.leading_zeros() as i32) < <$o>::BITS as i32 - <$t>::BITS as i32 {
+                        // A signed target also needs its sign bit to be clear
+                        let sign_bit = if <$t>::MIN == <$t>::zero() { 0 } else { 1 };
+                        if (other.leading_zeros() as i32) < <$o>::BITS as i32 - <$t>::BITS as i32 + sign_bit {
                             return Err([<Parse $t Error>]::Overflow);
                         }
                         let mut other_vec = other.0.to_vec();
@@ -363,7 +367,9 @@ macro_rules! try_from_large_into_builtin{
                                 other = <$o>::zero() - other;
                             }
                         }
-                        if (other.leading_zeros() as i32) < <$o>::BITS as i32 - <$t>::BITS as i32 {
+                        // A signed target also needs its sign bit to be clear
+                        let sign_bit = if <$t>::MIN == <$t>::zero() { 0 } else { 1 };
+                        if (other.leading_zeros() as i32) < <$o>::BITS as i32 - <$t>::BITS as i32 + sign_bit {
                             return Err([<Parse $t Error>]::Overflow);
                         }
                         let mut other_vec = other.0.to_vec();
@@ -0,0 +1,149 @@
+//! Deterministic interest accrual over epochs.
+//!
+//! All calculations are carried out in `PreciseDecimal` and the results are rounded towards
+//! zero when converted back to `Decimal`. Rates are expressed per epoch, e.g. `dec!("0.01")`
+//! for 1% per epoch.
+
+use num_traits::Pow;
+
+use crate::math::*;
+
+/// Returns the growth factor `(1 + rate)^epochs` of compound interest.
+///
+/// The factor is kept in `PreciseDecimal`, so it can be stored as a cumulative index and
+/// applied to many balances without accumulating rounding errors.
+pub fn compound_factor(rate: Decimal, epochs: u64) -> Result<PreciseDecimal, ArithmeticError> {
+    let mut base = PreciseDecimal::ONE.try_add(rate.into())?;
+    let mut factor = PreciseDecimal::ONE;
+    let mut n = epochs;
+    while n > 0 {
+        if n % 2 == 1 {
+            factor = factor.try_mul(base)?;
+        }
+        n /= 2;
+        if n > 0 {
+            base = base.try_mul(base)?;
+        }
+    }
+    Ok(factor)
+}
+
+/// Returns the growth factor `1 + rate * epochs` of simple interest.
+pub fn linear_factor(rate: Decimal, epochs: u64) -> Result<PreciseDecimal, ArithmeticError> {
+    PreciseDecimal::from(rate)
+        .try_mul(PreciseDecimal::from(epochs))?
+        .try_add(PreciseDecimal::ONE)
+}
+
+/// Applies a growth factor to an amount, rounding towards zero.
+pub fn apply_factor(amount: Decimal, factor: PreciseDecimal) -> Result<Decimal, ArithmeticError> {
+    to_decimal(PreciseDecimal::from(amount).try_mul(factor)?)
+}
+
+/// Returns `principal` grown by compound interest at `rate` per epoch over `epochs` epochs.
+pub fn compound_accrual(
+    principal: Decimal,
+    rate: Decimal,
+    epochs: u64,
+) -> Result<Decimal, ArithmeticError> {
+    apply_factor(principal, compound_factor(rate, epochs)?)
+}
+
+/// Returns `principal` grown by simple interest at `rate` per epoch over `epochs` epochs.
+pub fn linear_accrual(
+    principal: Decimal,
+    rate: Decimal,
+    epochs: u64,
+) -> Result<Decimal, ArithmeticError> {
+    apply_factor(principal, linear_factor(rate, epochs)?)
+}
+
+/// Returns the interest earned on `principal`, compounded at `rate` per epoch over `epochs`
+/// epochs.
+pub fn compound_interest(
+    principal: Decimal,
+    rate: Decimal,
+    epochs: u64,
+) -> Result<Decimal, ArithmeticError> {
+    compound_accrual(principal, rate, epochs)?.try_sub(principal)
+}
+
+/// Returns the simple interest earned on `principal` at `rate` per epoch over `epochs` epochs.
+pub fn linear_interest(
+    principal: Decimal,
+    rate: Decimal,
+    epochs: u64,
+) -> Result<Decimal, ArithmeticError> {
+    linear_accrual(principal, rate, epochs)?.try_sub(principal)
+}
+
+fn to_decimal(value: PreciseDecimal) -> Result<Decimal, ArithmeticError> {
+    let divisor = I512::from(10i8).pow(PreciseDecimal::SCALE - Decimal::SCALE);
+    I256::try_from(value.0 / divisor)
+        .map(Decimal)
+        .map_err(|_| ArithmeticError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dec, pdec};
+
+    #[test]
+    fn test_compound_factor() {
+        assert_eq!(compound_factor(dec!("0.1"), 0), Ok(PreciseDecimal::ONE));
+        assert_eq!(compound_factor(dec!("0.1"), 1), Ok(pdec!("1.1")));
+        assert_eq!(compound_factor(dec!("0.1"), 3), Ok(pdec!("1.331")));
+        assert_eq!(compound_factor(dec!("-0.5"), 2), Ok(pdec!("0.25")));
+        assert_eq!(
+            compound_factor(dec!("1"), 1000),
+            Err(ArithmeticError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_compound_accrual() {
+        assert_eq!(
+            compound_accrual(dec!("1000"), dec!("0.05"), 2),
+            Ok(dec!("1102.5"))
+        );
+        assert_eq!(
+            compound_interest(dec!("1000"), dec!("0.05"), 2),
+            Ok(dec!("102.5"))
+        );
+        // 1% per epoch over a year of daily epochs, rounded towards zero
+        assert_eq!(
+            compound_accrual(dec!("1"), dec!("0.01"), 365),
+            Ok(dec!("37.783434332887158877"))
+        );
+        assert_eq!(
+            compound_accrual(Decimal::MAX, dec!("0.01"), 1),
+            Err(ArithmeticError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_linear_accrual() {
+        assert_eq!(
+            linear_accrual(dec!("1000"), dec!("0.05"), 2),
+            Ok(dec!("1100"))
+        );
+        assert_eq!(
+            linear_interest(dec!("1000"), dec!("0.05"), 2),
+            Ok(dec!("100"))
+        );
+        assert_eq!(linear_accrual(dec!("1"), dec!("0"), 100), Ok(dec!("1")));
+    }
+
+    #[test]
+    fn test_apply_factor_rounds_towards_zero() {
+        assert_eq!(
+            apply_factor(Decimal(1i128.into()), pdec!("1.9")),
+            Ok(Decimal(1i128.into()))
+        );
+        assert_eq!(
+            apply_factor(Decimal((-1i128).into()), pdec!("1.9")),
+            Ok(Decimal((-1i128).into()))
+        );
+    }
+}
@@ -1,5 +1,6 @@
 pub mod decimal;
 pub mod integer;
+pub mod interest;
 pub mod precise_decimal;
 pub mod rounding_mode;
 
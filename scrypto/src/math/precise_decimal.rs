@@ -1,4 +1,5 @@
 use core::ops::*;
+use num_bigint::{BigInt, Sign};
 use num_traits::{One, Pow, ToPrimitive, Zero};
 use sbor::rust::convert::{TryFrom, TryInto};
 use sbor::rust::fmt;
@@ -200,6 +201,29 @@ impl PreciseDecimal {
             );
         }
     }
+
+    /// Calculates the square root, rounded towards zero.
+    ///
+    /// Returns `None` if the number is negative.
+    pub fn sqrt(&self) -> Option<Self> {
+        self.nth_root(2)
+    }
+
+    /// Calculates the nth root, rounded towards zero.
+    ///
+    /// Returns `None` if `n` is zero, or if `n` is even and the number is negative.
+    pub fn nth_root(&self, n: u32) -> Option<Self> {
+        if n == 0 || (n % 2 == 0 && self.is_negative()) {
+            return None;
+        }
+        let m = BigInt::from(self.0);
+        let root = if self.is_negative() {
+            -scaled_nth_root(&-m, Self::SCALE, n, I512::BITS)?
+        } else {
+            scaled_nth_root(&m, Self::SCALE, n, I512::BITS)?
+        };
+        I512::try_from(root).ok().map(Self)
+    }
 }
 
 macro_rules! from_int {
@@ -354,6 +378,111 @@ where
     }
 }
 
+//========
+// checked and saturating arithmetic
+//========
+
+impl PreciseDecimal {
+    fn from_bigint(value: BigInt) -> Result<Self, ArithmeticError> {
+        I512::try_from(value)
+            .map(Self)
+            .map_err(|_| ArithmeticError::Overflow)
+    }
+
+    fn saturate(value: BigInt) -> Self {
+        let is_negative = value.sign() == Sign::Minus;
+        Self::from_bigint(value).unwrap_or(if is_negative { Self::MIN } else { Self::MAX })
+    }
+
+    fn bigint_add(self, other: Self) -> BigInt {
+        BigInt::from(self.0) + BigInt::from(other.0)
+    }
+
+    fn bigint_sub(self, other: Self) -> BigInt {
+        BigInt::from(self.0) - BigInt::from(other.0)
+    }
+
+    fn bigint_mul(self, other: Self) -> BigInt {
+        BigInt::from(self.0) * BigInt::from(other.0) / BigInt::from(Self::ONE.0)
+    }
+
+    fn bigint_div(self, other: Self) -> Result<BigInt, ArithmeticError> {
+        if other.is_zero() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        Ok(BigInt::from(self.0) * BigInt::from(Self::ONE.0) / BigInt::from(other.0))
+    }
+
+    /// Adds two precise decimals, returning an error on overflow.
+    pub fn try_add(self, other: Self) -> Result<Self, ArithmeticError> {
+        Self::from_bigint(self.bigint_add(other))
+    }
+
+    /// Subtracts two precise decimals, returning an error on overflow.
+    pub fn try_sub(self, other: Self) -> Result<Self, ArithmeticError> {
+        Self::from_bigint(self.bigint_sub(other))
+    }
+
+    /// Multiplies two precise decimals, returning an error on overflow.
+    ///
+    /// Unlike `*`, the intermediate product is not limited to 512 bits.
+    pub fn try_mul(self, other: Self) -> Result<Self, ArithmeticError> {
+        Self::from_bigint(self.bigint_mul(other))
+    }
+
+    /// Divides two precise decimals, returning an error on overflow or division by zero.
+    pub fn try_div(self, other: Self) -> Result<Self, ArithmeticError> {
+        Self::from_bigint(self.bigint_div(other)?)
+    }
+
+    /// Adds two precise decimals, clamping the result to `[MIN, MAX]`.
+    pub fn saturating_add(self, other: Self) -> Self {
+        Self::saturate(self.bigint_add(other))
+    }
+
+    /// Subtracts two precise decimals, clamping the result to `[MIN, MAX]`.
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self::saturate(self.bigint_sub(other))
+    }
+
+    /// Multiplies two precise decimals, clamping the result to `[MIN, MAX]`.
+    pub fn saturating_mul(self, other: Self) -> Self {
+        Self::saturate(self.bigint_mul(other))
+    }
+
+    /// Divides two precise decimals, clamping the result to `[MIN, MAX]`.
+    ///
+    /// # Panics
+    /// Panics if `other` is zero.
+    pub fn saturating_div(self, other: Self) -> Self {
+        Self::saturate(self.bigint_div(other).expect("Division by zero"))
+    }
+}
+
+impl CheckedAdd for PreciseDecimal {
+    fn checked_add(self, other: Self) -> Option<Self> {
+        self.try_add(other).ok()
+    }
+}
+
+impl CheckedSub for PreciseDecimal {
+    fn checked_sub(self, other: Self) -> Option<Self> {
+        self.try_sub(other).ok()
+    }
+}
+
+impl CheckedMul for PreciseDecimal {
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        self.try_mul(other).ok()
+    }
+}
+
+impl CheckedDiv for PreciseDecimal {
+    fn checked_div(self, other: Self) -> Option<Self> {
+        self.try_div(other).ok()
+    }
+}
+
 //========
 // binary
 //========
@@ -388,54 +517,20 @@ scrypto_type!(PreciseDecimal, ScryptoType::PreciseDecimal, Vec::new());
 impl FromStr for PreciseDecimal {
     type Err = ParsePreciseDecimalError;
 
+    /// Parses a precise decimal, accepting the same grammar as `Decimal`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut sign = I512::from(1u8);
-        let mut value = I512::from(0u8);
-
-        let chars: Vec<char> = s.chars().collect();
-        let mut p = 0;
-
-        // read sign
-        if chars[p] == '-' {
-            sign = I512::from(-1i8);
-            p += 1;
-        }
-
-        // read integral
-        while p < chars.len() && chars[p] != '.' {
-            let digit = read_digitprecesedecimal(chars[p]);
-            match digit {
-                Ok(dig) => value = value * I512::from(10u8) + I512::from(dig) * sign,
-                Err(e) => return Err(e),
-            }
-            p += 1;
-        }
-
-        // read radix point
-        if p < chars.len() {
-            read_dotprecesedecimal(chars[p])?;
-            p += 1;
-        }
-
-        // read fraction
-        for _ in 0..Self::SCALE {
-            if p < chars.len() {
-                let digit = read_digitprecesedecimal(chars[p]);
-                match digit {
-                    Ok(dig) => value = value * I512::from(10u8) + I512::from(dig) * sign,
-                    Err(e) => return Err(e),
-                }
-                p += 1;
-            } else {
-                value *= I512::from(10i8);
+        let value = parse_scaled(s, Self::SCALE).map_err(|err| match err {
+            ParseDecimalError::InvalidDecimal(s) => ParsePreciseDecimalError::InvalidDecimal(s),
+            ParseDecimalError::InvalidChar(c) => ParsePreciseDecimalError::InvalidChar(c),
+            ParseDecimalError::UnsupportedDecimalPlace => {
+                ParsePreciseDecimalError::UnsupportedDecimalPlace
             }
-        }
-
-        if p < chars.len() {
-            Err(ParsePreciseDecimalError::UnsupportedDecimalPlace)
-        } else {
-            Ok(Self(value))
-        }
+            ParseDecimalError::InvalidLength(l) => ParsePreciseDecimalError::InvalidLength(l),
+            ParseDecimalError::Overflow => ParsePreciseDecimalError::Overflow,
+        })?;
+        I512::try_from(value)
+            .map(Self)
+            .map_err(|_| ParsePreciseDecimalError::Overflow)
     }
 }
 
@@ -482,23 +577,6 @@ impl fmt::Debug for PreciseDecimal {
         write!(f, "{}", self.to_string())
     }
 }
-fn read_digitprecesedecimal(c: char) -> Result<U8, ParsePreciseDecimalError> {
-    let n = U8::from(c as u8);
-    if n >= U8(48u8) && n <= U8(48u8 + 9u8) {
-        Ok(n - U8(48u8))
-    } else {
-        Err(ParsePreciseDecimalError::InvalidChar(c))
-    }
-}
-
-fn read_dotprecesedecimal(c: char) -> Result<(), ParsePreciseDecimalError> {
-    if c == '.' {
-        Ok(())
-    } else {
-        Err(ParsePreciseDecimalError::InvalidChar(c))
-    }
-}
-
 //========
 // ParseDecimalError, ParsePreciseDecimalError
 //========
@@ -525,7 +603,7 @@ impl fmt::Display for ParsePreciseDecimalError {
 
 impl From<Decimal> for PreciseDecimal {
     fn from(val: Decimal) -> Self {
-        Self(val.0.into())
+        Self(I512::from(val.0) * I512::from(10i8).pow(Self::SCALE - Decimal::SCALE))
     }
 }
 
@@ -539,7 +617,7 @@ impl Truncate<Decimal> for PreciseDecimal {
 
     fn truncate(self) -> Self::Output {
         Decimal(
-            (self.0 / I512::from(10i8).pow(PreciseDecimal::SCALE - PreciseDecimal::SCALE))
+            (self.0 / I512::from(10i8).pow(PreciseDecimal::SCALE - Decimal::SCALE))
                 .try_into()
                 .expect("Overflow"),
         )
@@ -568,14 +646,18 @@ macro_rules! try_from_integer {
                 type Error = ParsePreciseDecimalError;
 
                 fn try_from(val: $t) -> Result<Self, Self::Error> {
-                    Ok(Self(I512::try_from(val).map_err(|_| ParsePreciseDecimalError::Overflow).unwrap() * Self::ONE.0))
+                    I512::try_from(val)
+                        .ok()
+                        .and_then(|v| v.checked_mul(Self::ONE.0))
+                        .map(Self)
+                        .ok_or(ParsePreciseDecimalError::Overflow)
                 }
             }
         )*
     };
 }
 
-try_from_integer!(U512, I512);
+try_from_integer!(U256, U384, U512, I256, I384, I512);
 
 #[cfg(test)]
 mod tests {
@@ -1116,4 +1198,126 @@ mod tests {
         let pdec = PreciseDecimal::from_str("non_decimal_value");
         assert_eq!(pdec, Err(ParsePreciseDecimalError::InvalidChar('n')));
     }
+
+    #[test]
+    fn test_checked_arithmetic_precise_decimal() {
+        let a = PreciseDecimal::from(5u8);
+        let b = PreciseDecimal::from(2u8);
+        assert_eq!(a.checked_add(b), Some(PreciseDecimal::from(7u8)));
+        assert_eq!(a.checked_sub(b), Some(PreciseDecimal::from(3u8)));
+        assert_eq!(a.checked_mul(b), Some(PreciseDecimal::from(10u8)));
+        assert_eq!(a.checked_div(b), Some(PreciseDecimal::from("2.5")));
+        assert_eq!(PreciseDecimal::MAX.checked_add(PreciseDecimal::ONE), None);
+        assert_eq!(PreciseDecimal::MIN.checked_sub(PreciseDecimal::ONE), None);
+        assert_eq!(
+            PreciseDecimal::MAX.try_mul(b),
+            Err(ArithmeticError::Overflow)
+        );
+        assert_eq!(
+            a.try_div(PreciseDecimal::ZERO),
+            Err(ArithmeticError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_checked_mul_does_not_overflow_intermediate_precise_decimal() {
+        let a = PreciseDecimal::MAX
+            .try_div(PreciseDecimal::from(4u8))
+            .unwrap();
+        assert_eq!(a.checked_mul(PreciseDecimal::from(2u8)), Some(a + a));
+    }
+
+    #[test]
+    fn test_saturating_arithmetic_precise_decimal() {
+        let a = PreciseDecimal::from(5u8);
+        let b = PreciseDecimal::from(2u8);
+        assert_eq!(a.saturating_add(b), PreciseDecimal::from(7u8));
+        assert_eq!(
+            PreciseDecimal::MAX.saturating_add(PreciseDecimal::ONE),
+            PreciseDecimal::MAX
+        );
+        assert_eq!(
+            PreciseDecimal::MIN.saturating_sub(PreciseDecimal::ONE),
+            PreciseDecimal::MIN
+        );
+        assert_eq!(PreciseDecimal::MAX.saturating_mul(-b), PreciseDecimal::MIN);
+        assert_eq!(
+            PreciseDecimal::MAX.saturating_div(PreciseDecimal::from("0.5")),
+            PreciseDecimal::MAX
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_saturating_div_by_zero_precise_decimal() {
+        let _ = PreciseDecimal::ONE.saturating_div(PreciseDecimal::ZERO);
+    }
+
+    #[test]
+    fn test_sqrt_precise_decimal() {
+        assert_eq!(
+            PreciseDecimal::from(16u8).sqrt(),
+            Some(PreciseDecimal::from(4u8))
+        );
+        assert_eq!(
+            PreciseDecimal::from("0.25").sqrt(),
+            Some(PreciseDecimal::from("0.5"))
+        );
+        assert_eq!(PreciseDecimal::ZERO.sqrt(), Some(PreciseDecimal::ZERO));
+        assert_eq!(PreciseDecimal::from(-4i8).sqrt(), None);
+        assert_eq!(
+            PreciseDecimal::from(2u8).sqrt().unwrap().to_string()[..10],
+            *"1.41421356"
+        );
+    }
+
+    #[test]
+    fn test_nth_root_precise_decimal() {
+        assert_eq!(
+            PreciseDecimal::from(27u8).nth_root(3),
+            Some(PreciseDecimal::from(3u8))
+        );
+        assert_eq!(
+            PreciseDecimal::from(-8i8).nth_root(3),
+            Some(PreciseDecimal::from(-2i8))
+        );
+        assert_eq!(
+            PreciseDecimal::from(5u8).nth_root(1),
+            Some(PreciseDecimal::from(5u8))
+        );
+        assert_eq!(PreciseDecimal::from(5u8).nth_root(0), None);
+        assert_eq!(
+            PreciseDecimal::from(-1i8).nth_root(u32::MAX),
+            Some(PreciseDecimal::from(-1i8))
+        );
+        assert_eq!(
+            PreciseDecimal::from(2u8).nth_root(u32::MAX),
+            Some(pdec!(
+                "1.0000000001613859042472353451695542936078162660119428952369620553"
+            ))
+        );
+        assert_eq!(
+            pdec!("0.5").nth_root(1_000_000),
+            Some(pdec!(
+                "0.9999993068530596665061455844335182874062695476405194749148885261"
+            ))
+        );
+        assert_eq!(
+            PreciseDecimal::MAX.nth_root(1_000_000),
+            Some(pdec!(
+                "1.0002068541546852899773535706499200870393400403494418101315397549"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_from_and_truncate_decimal_precise_decimal() {
+        assert_eq!(PreciseDecimal::from(Decimal::ONE), PreciseDecimal::ONE);
+        assert_eq!(PreciseDecimal::from(Decimal::from("-1.5")), pdec!("-1.5"));
+        assert_eq!(pdec!("1.5").truncate(), Decimal::from("1.5"));
+        assert_eq!(
+            pdec!("0.0000000000000000019").truncate(),
+            Decimal::from("0.000000000000000001")
+        );
+    }
 }
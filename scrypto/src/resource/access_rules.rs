@@ -3,6 +3,7 @@ use sbor::rust::collections::HashMap;
 use sbor::rust::str;
 use sbor::rust::string::String;
 use sbor::rust::string::ToString;
+use sbor::rust::vec::Vec;
 use sbor::*;
 
 use crate::resource::*;
@@ -12,6 +13,10 @@ use crate::resource::*;
 pub struct AccessRules {
     method_auth: HashMap<String, AccessRule>,
     default_auth: AccessRule,
+    method_mutability: HashMap<String, Mutability>,
+    roles: HashMap<String, AccessRule>,
+    role_mutability: HashMap<String, Mutability>,
+    method_roles: HashMap<String, Vec<String>>,
 }
 
 impl AccessRules {
@@ -19,6 +24,10 @@ impl AccessRules {
         Self {
             method_auth: HashMap::new(),
             default_auth: AccessRule::DenyAll,
+            method_mutability: HashMap::new(),
+            roles: HashMap::new(),
+            role_mutability: HashMap::new(),
+            method_roles: HashMap::new(),
         }
     }
 
@@ -32,12 +41,95 @@ impl AccessRules {
         &self.default_auth
     }
 
+    /// Returns the rule guarding updates to a method's access rule, which is `LOCKED` unless
+    /// specified otherwise.
+    pub fn get_mutability(&self, method_name: &str) -> &Mutability {
+        self.method_mutability
+            .get(method_name)
+            .unwrap_or(&Mutability::LOCKED)
+    }
+
+    /// Returns the rule of a role, if it has been declared.
+    pub fn get_role(&self, role_name: &str) -> Option<&AccessRule> {
+        self.roles.get(role_name)
+    }
+
+    /// Returns the rule guarding updates to a role's rule, which is `LOCKED` unless specified
+    /// otherwise.
+    pub fn get_role_mutability(&self, role_name: &str) -> &Mutability {
+        self.role_mutability
+            .get(role_name)
+            .unwrap_or(&Mutability::LOCKED)
+    }
+
+    /// Returns the roles a method is tagged with, either by its blueprint or by these rules,
+    /// unless the method has been given its own rule, which always takes precedence.
+    pub fn get_method_roles(
+        &self,
+        method_name: &str,
+        blueprint_roles: &[String],
+    ) -> Option<Vec<String>> {
+        if self.method_auth.contains_key(method_name) {
+            return None;
+        }
+        let mut roles = blueprint_roles.to_vec();
+        for role in self.method_roles.get(method_name).into_iter().flatten() {
+            if !roles.contains(role) {
+                roles.push(role.clone());
+            }
+        }
+        if roles.is_empty() {
+            None
+        } else {
+            Some(roles)
+        }
+    }
+
     pub fn method(mut self, method_name: &str, method_auth: AccessRule) -> Self {
         self.method_auth
             .insert(method_name.to_string(), method_auth);
         self
     }
 
+    pub fn mutability(mut self, method_name: &str, mutability: Mutability) -> Self {
+        self.method_mutability
+            .insert(method_name.to_string(), mutability);
+        self
+    }
+
+    /// Declares a role and the rule a caller must satisfy to act in it.
+    pub fn role(mut self, role_name: &str, role_auth: AccessRule) -> Self {
+        self.roles.insert(role_name.to_string(), role_auth);
+        self
+    }
+
+    pub fn role_mutability(mut self, role_name: &str, mutability: Mutability) -> Self {
+        self.role_mutability
+            .insert(role_name.to_string(), mutability);
+        self
+    }
+
+    /// Tags a method with a role. A method tagged with several roles can be called by any of them.
+    pub fn method_role(mut self, method_name: &str, role_name: &str) -> Self {
+        let roles = self
+            .method_roles
+            .entry(method_name.to_string())
+            .or_insert_with(Vec::new);
+        if !roles.iter().any(|r| r == role_name) {
+            roles.push(role_name.to_string());
+        }
+        self
+    }
+
+    pub fn set_role(&mut self, role_name: &str, role_auth: AccessRule) {
+        self.roles.insert(role_name.to_string(), role_auth);
+    }
+
+    pub fn set_method_auth(&mut self, method_name: &str, method_auth: AccessRule) {
+        self.method_auth
+            .insert(method_name.to_string(), method_auth);
+    }
+
     pub fn default(mut self, method_auth: AccessRule) -> Self {
         self.default_auth = method_auth;
         self
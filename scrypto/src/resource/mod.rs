@@ -25,9 +25,9 @@ pub use non_fungible_data::NonFungibleData;
 pub use non_fungible_id::{NonFungibleId, ParseNonFungibleIdError};
 pub use proof::*;
 pub use proof_rule::{
-    require, require_all_of, require_amount, require_any_of, require_n_of, AccessRule,
-    AccessRuleNode, ProofRule, SoftCount, SoftDecimal, SoftResource, SoftResourceOrNonFungible,
-    SoftResourceOrNonFungibleList,
+    require, require_all_of, require_amount, require_any_of, require_caller_origin, require_n_of,
+    AccessRule, AccessRuleNode, CallerOrigin, ProofRule, SoftCount, SoftDecimal, SoftResource,
+    SoftResourceOrNonFungible, SoftResourceOrNonFungibleList,
 };
 pub use resource_builder::{ResourceBuilder, DIVISIBILITY_MAXIMUM, DIVISIBILITY_NONE};
 pub use resource_manager::Mutability::*;
@@ -6,8 +6,8 @@ use sbor::rust::vec::Vec;
 use sbor::*;
 
 use crate::abi::*;
-use crate::constants::{ECDSA_TOKEN, ED25519_TOKEN};
-use crate::crypto::PublicKey;
+use crate::constants::{ECDSA_TOKEN, ED25519_TOKEN, PUBLIC_KEY_HASH_TOKEN};
+use crate::crypto::{hash, PublicKey};
 use crate::resource::*;
 
 /// Identifier for a non-fungible unit.
@@ -38,6 +38,25 @@ impl NonFungibleAddress {
         }
     }
 
+    /// Returns the badge of a public key hash, which is held by the signers of a transaction
+    /// alongside the badge of their public key.
+    pub fn from_public_key_hash<P: Into<PublicKey> + Clone>(public_key: &P) -> Self {
+        let public_key: PublicKey = public_key.clone().into();
+        let public_key_bytes = match public_key {
+            PublicKey::EcdsaSecp256k1(public_key) => public_key.to_vec(),
+            PublicKey::EddsaEd25519(public_key) => public_key.to_vec(),
+        };
+        Self::from_public_key_bytes_hash(&public_key_bytes)
+    }
+
+    /// Returns the badge of the hash of some public key bytes.
+    pub fn from_public_key_bytes_hash(public_key_bytes: &[u8]) -> Self {
+        NonFungibleAddress::new(
+            PUBLIC_KEY_HASH_TOKEN,
+            NonFungibleId::from_bytes(hash(public_key_bytes).lower_26_bytes().to_vec()),
+        )
+    }
+
     /// Returns the resource address.
     pub fn resource_address(&self) -> ResourceAddress {
         self.resource_address
@@ -147,6 +166,7 @@ impl fmt::Debug for NonFungibleAddress {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::{EcdsaSecp256k1PublicKey, EddsaEd25519PublicKey};
     use crate::sbor::rust::string::ToString;
 
     #[test]
@@ -169,4 +189,30 @@ mod tests {
         // Assert
         assert_eq!(converted_non_fungible_address, Ok(non_fungible_address));
     }
+
+    #[test]
+    pub fn public_key_hash_badges_are_scheme_independent() {
+        // Arrange
+        let ecdsa_public_key = EcdsaSecp256k1PublicKey::from_str(
+            "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        )
+        .unwrap();
+        let eddsa_public_key = EddsaEd25519PublicKey::from_str(
+            "4cb5abf6ad79fbf5abbccafcc269d85cd2651ed4b885b5869f241aedf0a5ba29",
+        )
+        .unwrap();
+
+        // Act
+        let ecdsa_badge = NonFungibleAddress::from_public_key_hash(&ecdsa_public_key);
+        let eddsa_badge = NonFungibleAddress::from_public_key_hash(&eddsa_public_key);
+
+        // Assert
+        assert_eq!(ecdsa_badge.resource_address(), PUBLIC_KEY_HASH_TOKEN);
+        assert_eq!(eddsa_badge.resource_address(), PUBLIC_KEY_HASH_TOKEN);
+        assert_eq!(
+            ecdsa_badge.non_fungible_id(),
+            NonFungibleId::from_bytes(hash(ecdsa_public_key.to_vec()).lower_26_bytes().to_vec())
+        );
+        assert_ne!(ecdsa_badge, eddsa_badge);
+    }
 }
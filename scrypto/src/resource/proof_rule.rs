@@ -7,10 +7,11 @@ use sbor::rust::vec::Vec;
 use sbor::*;
 use scrypto::math::Decimal;
 
+use crate::component::{ComponentAddress, KeyValueStore, PackageAddress};
 use crate::engine::api::RadixEngineInput;
 use crate::engine::call_engine;
-use crate::engine::types::ProofId;
-use crate::resource::AccessRuleNode::{AllOf, AnyOf};
+use crate::engine::types::{KeyValueStoreId, ProofId};
+use crate::resource::AccessRuleNode::{AllOf, AnyOf, CountOf};
 use crate::resource::*;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Describe, TypeId, Encode, Decode, Ord, PartialOrd)]
@@ -148,6 +149,25 @@ where
     }
 }
 
+/// The package or component whose code is making a call.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Describe, TypeId, Encode, Decode, Ord, PartialOrd)]
+pub enum CallerOrigin {
+    Package(PackageAddress),
+    Component(ComponentAddress),
+}
+
+impl From<PackageAddress> for CallerOrigin {
+    fn from(package_address: PackageAddress) -> Self {
+        CallerOrigin::Package(package_address)
+    }
+}
+
+impl From<ComponentAddress> for CallerOrigin {
+    fn from(component_address: ComponentAddress) -> Self {
+        CallerOrigin::Component(component_address)
+    }
+}
+
 /// Resource Proof Rules
 #[derive(Debug, Clone, PartialEq, Eq, Hash, TypeId, Encode, Decode, Describe, Ord, PartialOrd)]
 pub enum ProofRule {
@@ -156,6 +176,8 @@ pub enum ProofRule {
     CountOf(SoftCount, SoftResourceOrNonFungibleList),
     AllOf(SoftResourceOrNonFungibleList),
     AnyOf(SoftResourceOrNonFungibleList),
+    /// Satisfied only when the direct caller is the given package or component.
+    RequireCallerOrigin(CallerOrigin),
 }
 
 impl From<NonFungibleAddress> for ProofRule {
@@ -186,6 +208,7 @@ pub enum AccessRuleNode {
     ProofRule(ProofRule),
     AnyOf(Vec<AccessRuleNode>),
     AllOf(Vec<AccessRuleNode>),
+    CountOf(u8, Vec<AccessRuleNode>),
 }
 
 // FIXME: describe types with cycles
@@ -216,12 +239,56 @@ impl Describe for AccessRuleNode {
                         }],
                     },
                 },
+                Variant {
+                    name: "CountOf".to_string(),
+                    fields: Fields::Unnamed {
+                        unnamed: vec![
+                            Type::U8,
+                            Type::Vec {
+                                element: Box::new(Type::Any),
+                            },
+                        ],
+                    },
+                },
             ],
         }
     }
 }
 
+impl From<ProofRule> for AccessRuleNode {
+    fn from(proof_rule: ProofRule) -> Self {
+        AccessRuleNode::ProofRule(proof_rule)
+    }
+}
+
 impl AccessRuleNode {
+    /// Creates a node which is satisfied when any of the given nodes is satisfied.
+    pub fn any_of<I, T>(nodes: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<AccessRuleNode>,
+    {
+        AnyOf(nodes.into_iter().map(Into::into).collect())
+    }
+
+    /// Creates a node which is satisfied when all of the given nodes are satisfied.
+    pub fn all_of<I, T>(nodes: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<AccessRuleNode>,
+    {
+        AllOf(nodes.into_iter().map(Into::into).collect())
+    }
+
+    /// Creates a node which is satisfied when at least `count` of the given nodes are satisfied.
+    pub fn n_of<I, T>(count: u8, nodes: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<AccessRuleNode>,
+    {
+        CountOf(count, nodes.into_iter().map(Into::into).collect())
+    }
+
     pub fn or(self, other: AccessRuleNode) -> Self {
         match self {
             AccessRuleNode::AnyOf(mut rules) => {
@@ -280,6 +347,13 @@ where
     ProofRule::AmountOf(amount.into(), resource.into())
 }
 
+pub fn require_caller_origin<T>(origin: T) -> ProofRule
+where
+    T: Into<CallerOrigin>,
+{
+    ProofRule::RequireCallerOrigin(origin.into())
+}
+
 // TODO: Move this logic into preprocessor. It probably needs to be implemented as a procedural macro.
 #[macro_export]
 macro_rules! access_and_or {
@@ -366,6 +440,28 @@ pub enum AccessRule {
     AllowAll,
     DenyAll,
     Protected(AccessRuleNode),
+    /// Satisfied by a proof of any non-fungible mapped to `true` in the given on-ledger
+    /// `KeyValueStore<NonFungibleAddress, bool>`, so that the allow-list can change without
+    /// updating the rule.
+    RequireMembership(KeyValueStoreId),
+}
+
+impl From<AccessRuleNode> for AccessRule {
+    fn from(node: AccessRuleNode) -> Self {
+        AccessRule::Protected(node)
+    }
+}
+
+impl From<ProofRule> for AccessRule {
+    fn from(proof_rule: ProofRule) -> Self {
+        AccessRule::Protected(proof_rule.into())
+    }
+}
+
+impl From<&KeyValueStore<NonFungibleAddress, bool>> for AccessRule {
+    fn from(allow_list: &KeyValueStore<NonFungibleAddress, bool>) -> Self {
+        AccessRule::RequireMembership(allow_list.id)
+    }
 }
 
 impl AccessRule {
@@ -387,6 +483,9 @@ macro_rules! rule {
     (deny_all) => {{
         ::scrypto::resource::AccessRule::DenyAll
     }};
+    (require_membership($allow_list:expr)) => {{
+        ::scrypto::resource::AccessRule::from($allow_list)
+    }};
     ($($tt:tt)+) => {{
         ::scrypto::resource::AccessRule::Protected(access_rule_node!($($tt)+))
     }};
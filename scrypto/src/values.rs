@@ -1,9 +1,11 @@
-use sbor::path::{MutableSborPath, SborPath};
+use sbor::path::{MutableSborPath, SborPath, SborPathError};
 use sbor::rust::borrow::Borrow;
 use sbor::rust::collections::HashMap;
 use sbor::rust::collections::HashSet;
 use sbor::rust::fmt;
 use sbor::rust::format;
+#[cfg(feature = "json")]
+use sbor::rust::str::FromStr;
 use sbor::rust::string::String;
 use sbor::rust::string::ToString;
 use sbor::rust::vec::Vec;
@@ -12,6 +14,8 @@ use sbor::{any::*, *};
 
 use crate::abi::*;
 use crate::address::AddressError;
+#[cfg(feature = "json")]
+use crate::address::{Bech32Decoder, Bech32Encoder};
 use crate::buffer::*;
 use crate::component::*;
 use crate::core::*;
@@ -39,6 +43,7 @@ pub struct ScryptoValue {
     pub owned_component_addresses: HashSet<ComponentAddress>,
     pub refed_component_addresses: HashSet<ComponentAddress>,
     pub resource_addresses: HashSet<ResourceAddress>,
+    pub blob_hashes: HashSet<Hash>,
 }
 
 impl ScryptoValue {
@@ -52,8 +57,30 @@ impl ScryptoValue {
     }
 
     pub fn from_slice(slice: &[u8]) -> Result<Self, DecodeError> {
-        let value = decode_any(slice)?;
-        Self::from_value(value)
+        Self::from_slice_with_limits(slice, &DecodeLimits::default())
+    }
+
+    pub fn from_slice_with_limits(
+        slice: &[u8],
+        limits: &DecodeLimits,
+    ) -> Result<Self, DecodeError> {
+        // Check custom values while streaming through the payload, so that invalid payloads are
+        // rejected before the value tree is built and the raw bytes need not be re-encoded.
+        let mut checker = ScryptoCustomValueChecker::new();
+        let mut traverser = Traverser::new_with_limits(slice, limits)?;
+        while let Some(event) = traverser.next_event()? {
+            if let TraversalEvent::Custom { type_id, bytes } = event {
+                checker
+                    .visit(&mut traverser.path().clone(), type_id, bytes)
+                    .map_err(|e| DecodeError::CustomError(format!("{:?}", e)))?;
+            }
+        }
+
+        Ok(Self::from_checked(
+            slice.to_vec(),
+            decode_any_with_limits(slice, limits)?,
+            checker,
+        ))
     }
 
     pub fn from_value(value: Value) -> Result<Self, DecodeError> {
@@ -61,8 +88,12 @@ impl ScryptoValue {
         traverse_any(&mut MutableSborPath::new(), &value, &mut checker)
             .map_err(|e| DecodeError::CustomError(format!("{:?}", e)))?;
 
-        Ok(Self {
-            raw: encode_any(&value),
+        Ok(Self::from_checked(encode_any(&value), value, checker))
+    }
+
+    fn from_checked(raw: Vec<u8>, value: Value, mut checker: ScryptoCustomValueChecker) -> Self {
+        Self {
+            raw,
             dom: value,
             expressions: checker.expressions,
             bucket_ids: checker
@@ -80,7 +111,8 @@ impl ScryptoValue {
             owned_component_addresses: checker.components.iter().map(|e| e.0).collect(),
             refed_component_addresses: checker.ref_components,
             resource_addresses: checker.resource_addresses,
-        })
+            blob_hashes: checker.blobs.iter().map(|e| e.0).collect(),
+        }
     }
 
     pub fn from_slice_no_custom_values(slice: &[u8]) -> Result<Self, DecodeError> {
@@ -99,6 +131,7 @@ impl ScryptoValue {
             owned_component_addresses: HashSet::new(),
             refed_component_addresses: HashSet::new(),
             resource_addresses: HashSet::new(),
+            blob_hashes: HashSet::new(),
         })
     }
 
@@ -141,6 +174,10 @@ impl ScryptoValue {
         proof_replacements: &mut HashMap<ProofId, ProofId>,
         bucket_replacements: &mut HashMap<BucketId, BucketId>,
     ) -> Result<(), ScryptoValueReplaceError> {
+        if self.proof_ids.is_empty() && self.bucket_ids.is_empty() {
+            return Ok(());
+        }
+
         let mut new_proof_ids = HashMap::new();
         for (proof_id, path) in self.proof_ids.drain() {
             let next_id = proof_replacements
@@ -186,6 +223,53 @@ impl ScryptoValue {
         Ok(())
     }
 
+    /// Returns the canonical encoding of this value, in which set elements and map entries are
+    /// sorted by their encoding.
+    pub fn to_canonical_bytes(&self) -> Vec<u8> {
+        let mut value = self.dom.clone();
+        canonicalize_any(&mut value);
+        encode_any(&value)
+    }
+
+    /// Returns the hash of the canonical encoding of this value, which is the same for values
+    /// that only differ in the iteration order of their sets and maps.
+    pub fn hash(&self) -> Hash {
+        hash(self.to_canonical_bytes())
+    }
+
+    /// Returns whether this value is structurally equal to another, ignoring the iteration order
+    /// of sets and maps.
+    pub fn canonical_eq(&self, other: &Self) -> bool {
+        self.raw == other.raw || self.to_canonical_bytes() == other.to_canonical_bytes()
+    }
+
+    /// Extracts the sub-value at a path of indexes, e.g. `&["1", "0"]`.
+    pub fn get_path(&self, path: &[&str]) -> Result<ScryptoValue, SborPathError> {
+        self.resolve_path(None, path)
+    }
+
+    /// Extracts the sub-value at a path of field names and indexes, e.g. `&["field", "0", "inner"]`,
+    /// resolving field names against the type of this value.
+    pub fn get_path_with_type(
+        &self,
+        ty: &Type,
+        path: &[&str],
+    ) -> Result<ScryptoValue, SborPathError> {
+        self.resolve_path(Some(ty), path)
+    }
+
+    fn resolve_path(
+        &self,
+        ty: Option<&Type>,
+        path: &[&str],
+    ) -> Result<ScryptoValue, SborPathError> {
+        let value = SborPath::resolve(&self.dom, ty, path)?
+            .get_from_value(&self.dom)
+            .expect("Resolved path should exist")
+            .clone();
+        Ok(Self::from_value(value).expect("Sub-value of a valid value should be valid"))
+    }
+
     pub fn value_count(&self) -> usize {
         self.bucket_ids.len()
             + self.proof_ids.len()
@@ -204,6 +288,24 @@ impl ScryptoValue {
     ) -> String {
         ScryptoValueFormatter::format_value(&self.dom, bucket_ids, proof_ids)
     }
+
+    /// Converts this value into its canonical JSON representation, with addresses encoded for
+    /// the given network. See [`ScryptoCustomJson`] for how custom values are mapped.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self, network: &NetworkDefinition) -> serde_json::Value {
+        sbor::json::to_json_value_with(&self.dom, &ScryptoCustomJson::new(network))
+    }
+
+    /// Converts the JSON produced by [`ScryptoValue::to_json`] back into a value.
+    #[cfg(feature = "json")]
+    pub fn from_json(
+        json: &serde_json::Value,
+        network: &NetworkDefinition,
+    ) -> Result<Self, DecodeError> {
+        let value = sbor::json::from_json_value_with(json, &ScryptoCustomJson::new(network))
+            .map_err(|e| DecodeError::CustomError(format!("{:?}", e)))?;
+        Self::from_value(value)
+    }
 }
 
 impl fmt::Debug for ScryptoValue {
@@ -218,6 +320,28 @@ impl fmt::Display for ScryptoValue {
     }
 }
 
+/// Like `Value`, a `ScryptoValue` is encoded with its own type id, so it can be nested directly
+/// in an `Option`, a `Result` or a struct field.
+impl Encode for ScryptoValue {
+    #[inline]
+    fn encode_type_id(_encoder: &mut Encoder) {}
+
+    fn encode_value(&self, encoder: &mut Encoder) {
+        encoder.write_slice(&self.raw);
+    }
+}
+
+impl Decode for ScryptoValue {
+    #[inline]
+    fn check_type_id(_decoder: &mut Decoder) -> Result<(), DecodeError> {
+        Ok(())
+    }
+
+    fn decode_value(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+        Self::from_value(Value::decode_value(decoder)?)
+    }
+}
+
 /// Represents an error when validating a Scrypto-specific value.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ScryptoNoCustomValuesCheckError {
@@ -252,6 +376,7 @@ pub struct ScryptoCustomValueChecker {
     pub components: HashSet<Component>,
     pub ref_components: HashSet<ComponentAddress>,
     pub resource_addresses: HashSet<ResourceAddress>,
+    pub blobs: HashSet<Blob>,
 }
 
 /// Represents an error when validating a Scrypto-specific value.
@@ -260,6 +385,12 @@ pub enum ScryptoCustomValueCheckError {
     UnknownTypeId(u8),
     InvalidDecimal(ParseDecimalError),
     InvalidPreciseDecimal(ParsePreciseDecimalError),
+    InvalidI256(ParseI256Error),
+    InvalidI384(ParseI384Error),
+    InvalidI512(ParseI512Error),
+    InvalidU256(ParseU256Error),
+    InvalidU384(ParseU384Error),
+    InvalidU512(ParseU512Error),
     InvalidPackageAddress(AddressError),
     InvalidComponentAddress(AddressError),
     InvalidResourceAddress(AddressError),
@@ -290,6 +421,7 @@ impl ScryptoCustomValueChecker {
             components: HashSet::new(),
             ref_components: HashSet::new(),
             resource_addresses: HashSet::new(),
+            blobs: HashSet::new(),
         }
     }
 }
@@ -363,6 +495,24 @@ impl CustomValueVisitor for ScryptoCustomValueChecker {
                 PreciseDecimal::try_from(data)
                     .map_err(ScryptoCustomValueCheckError::InvalidPreciseDecimal)?;
             }
+            ScryptoType::I256 => {
+                I256::from_custom_slice(data).map_err(ScryptoCustomValueCheckError::InvalidI256)?;
+            }
+            ScryptoType::I384 => {
+                I384::from_custom_slice(data).map_err(ScryptoCustomValueCheckError::InvalidI384)?;
+            }
+            ScryptoType::I512 => {
+                I512::from_custom_slice(data).map_err(ScryptoCustomValueCheckError::InvalidI512)?;
+            }
+            ScryptoType::U256 => {
+                U256::from_custom_slice(data).map_err(ScryptoCustomValueCheckError::InvalidU256)?;
+            }
+            ScryptoType::U384 => {
+                U384::from_custom_slice(data).map_err(ScryptoCustomValueCheckError::InvalidU384)?;
+            }
+            ScryptoType::U512 => {
+                U512::from_custom_slice(data).map_err(ScryptoCustomValueCheckError::InvalidU512)?;
+            }
             ScryptoType::Bucket => {
                 let bucket =
                     Bucket::try_from(data).map_err(ScryptoCustomValueCheckError::InvalidBucket)?;
@@ -403,7 +553,9 @@ impl CustomValueVisitor for ScryptoCustomValueChecker {
                 self.expressions.push((expression, path.clone().into()));
             }
             ScryptoType::Blob => {
-                Blob::try_from(data).map_err(ScryptoCustomValueCheckError::InvalidBlob)?;
+                let blob =
+                    Blob::try_from(data).map_err(ScryptoCustomValueCheckError::InvalidBlob)?;
+                self.blobs.insert(blob);
             }
         }
         Ok(())
@@ -572,6 +724,12 @@ impl ScryptoValueFormatter {
                     PreciseDecimal::try_from(data).unwrap()
                 )
             }
+            ScryptoType::I256 => format!("I256(\"{}\")", I256::from_custom_slice(data).unwrap()),
+            ScryptoType::I384 => format!("I384(\"{}\")", I384::from_custom_slice(data).unwrap()),
+            ScryptoType::I512 => format!("I512(\"{}\")", I512::from_custom_slice(data).unwrap()),
+            ScryptoType::U256 => format!("U256(\"{}\")", U256::from_custom_slice(data).unwrap()),
+            ScryptoType::U384 => format!("U384(\"{}\")", U384::from_custom_slice(data).unwrap()),
+            ScryptoType::U512 => format!("U512(\"{}\")", U512::from_custom_slice(data).unwrap()),
             ScryptoType::PackageAddress => {
                 format!(
                     "PackageAddress(\"{}\")",
@@ -655,11 +813,118 @@ impl ScryptoValueFormatter {
     }
 }
 
+/// Maps Scrypto custom values to and from JSON.
+///
+/// Each custom value becomes `{ "type": <type name>, "value": string }`, where the value is
+/// - the decimal string for `Decimal` and `PreciseDecimal`,
+/// - the Bech32 address for `PackageAddress`, `ComponentAddress` and `ResourceAddress`,
+/// - the hex-encoded bytes for all other types, which for `NonFungibleId` is its usual text form.
+#[cfg(feature = "json")]
+pub struct ScryptoCustomJson {
+    bech32_encoder: Bech32Encoder,
+    bech32_decoder: Bech32Decoder,
+}
+
+#[cfg(feature = "json")]
+impl ScryptoCustomJson {
+    pub fn new(network: &NetworkDefinition) -> Self {
+        Self {
+            bech32_encoder: Bech32Encoder::new(network),
+            bech32_decoder: Bech32Decoder::new(network),
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl sbor::json::CustomJson for ScryptoCustomJson {
+    fn custom_to_json(&self, type_id: u8, bytes: &[u8]) -> Option<serde_json::Value> {
+        let ty = ScryptoType::from_id(type_id)?;
+        let value = match ty {
+            ScryptoType::Decimal => Decimal::try_from(bytes).ok()?.to_string(),
+            ScryptoType::PreciseDecimal => PreciseDecimal::try_from(bytes).ok()?.to_string(),
+            ScryptoType::I256 => I256::from_custom_slice(bytes).ok()?.to_string(),
+            ScryptoType::I384 => I384::from_custom_slice(bytes).ok()?.to_string(),
+            ScryptoType::I512 => I512::from_custom_slice(bytes).ok()?.to_string(),
+            ScryptoType::U256 => U256::from_custom_slice(bytes).ok()?.to_string(),
+            ScryptoType::U384 => U384::from_custom_slice(bytes).ok()?.to_string(),
+            ScryptoType::U512 => U512::from_custom_slice(bytes).ok()?.to_string(),
+            ScryptoType::PackageAddress => self
+                .bech32_encoder
+                .encode_package_address(&PackageAddress::try_from(bytes).ok()?),
+            ScryptoType::ComponentAddress => self
+                .bech32_encoder
+                .encode_component_address(&ComponentAddress::try_from(bytes).ok()?),
+            ScryptoType::ResourceAddress => self
+                .bech32_encoder
+                .encode_resource_address(&ResourceAddress::try_from(bytes).ok()?),
+            _ => hex::encode(bytes),
+        };
+        let mut object = serde_json::Map::new();
+        object.insert("type".to_string(), ty.name().into());
+        object.insert("value".to_string(), value.into());
+        Some(object.into())
+    }
+
+    fn custom_from_json(
+        &self,
+        ty: &str,
+        object: &serde_json::Map<String, serde_json::Value>,
+    ) -> Option<Result<(u8, Vec<u8>), sbor::json::JsonError>> {
+        let ty = ScryptoType::from_name(ty)?;
+        let invalid = || sbor::json::JsonError::InvalidField("value".to_string());
+        let bytes = sbor::json::str_field(object, "value").and_then(|value| match ty {
+            ScryptoType::Decimal => Decimal::from_str(value)
+                .map(|d| d.to_vec())
+                .map_err(|_| invalid()),
+            ScryptoType::PreciseDecimal => PreciseDecimal::from_str(value)
+                .map(|d| d.to_vec())
+                .map_err(|_| invalid()),
+            ScryptoType::I256 => I256::from_str(value)
+                .map(|i| i.to_vec())
+                .map_err(|_| invalid()),
+            ScryptoType::I384 => I384::from_str(value)
+                .map(|i| i.to_vec())
+                .map_err(|_| invalid()),
+            ScryptoType::I512 => I512::from_str(value)
+                .map(|i| i.to_vec())
+                .map_err(|_| invalid()),
+            ScryptoType::U256 => U256::from_str(value)
+                .map(|i| i.to_vec())
+                .map_err(|_| invalid()),
+            ScryptoType::U384 => U384::from_str(value)
+                .map(|i| i.to_vec())
+                .map_err(|_| invalid()),
+            ScryptoType::U512 => U512::from_str(value)
+                .map(|i| i.to_vec())
+                .map_err(|_| invalid()),
+            ScryptoType::PackageAddress => self
+                .bech32_decoder
+                .validate_and_decode_package_address(value)
+                .map(|a| a.to_vec())
+                .map_err(|_| invalid()),
+            ScryptoType::ComponentAddress => self
+                .bech32_decoder
+                .validate_and_decode_component_address(value)
+                .map(|a| a.to_vec())
+                .map_err(|_| invalid()),
+            ScryptoType::ResourceAddress => self
+                .bech32_decoder
+                .validate_and_decode_resource_address(value)
+                .map(|a| a.to_vec())
+                .map_err(|_| invalid()),
+            _ => hex::decode(value).map_err(|_| invalid()),
+        });
+        Some(bytes.map(|bytes| (ty.id(), bytes)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::rust::vec;
     use super::*;
+    #[cfg(feature = "json")]
+    use crate::constants::RADIX_TOKEN;
 
     #[test]
     fn should_reject_duplicate_ids() {
@@ -670,4 +935,122 @@ mod tests {
         let error = ScryptoValue::from_slice(&buckets).expect_err("Should be an error");
         assert_eq!(error, DecodeError::CustomError("DuplicateIds".to_string()));
     }
+
+    #[test]
+    fn test_from_slice_matches_from_value() {
+        let mut map = HashMap::new();
+        map.insert(1u8, scrypto::resource::Bucket(1));
+        let bytes = scrypto_encode(&(Some(scrypto::resource::Proof(2)), map));
+
+        let from_slice = ScryptoValue::from_slice(&bytes).unwrap();
+        let from_value = ScryptoValue::from_value(decode_any(&bytes).unwrap()).unwrap();
+
+        assert_eq!(from_slice, from_value);
+        assert_eq!(
+            from_slice.bucket_ids,
+            HashMap::from([(1, SborPath::new(vec![1, 1]))])
+        );
+        assert_eq!(
+            from_slice.proof_ids,
+            HashMap::from([(2, SborPath::new(vec![0, 0]))])
+        );
+    }
+
+    #[test]
+    fn test_canonical_hash_and_eq() {
+        let value = |entries: Vec<(u32, Decimal)>| {
+            let mut bytes = Vec::new();
+            let mut enc = Encoder::with_static_info(&mut bytes);
+            enc.write_type_id(TYPE_MAP);
+            enc.write_type_id(TYPE_U32);
+            enc.write_type_id(ScryptoType::Decimal.id());
+            enc.write_dynamic_size(entries.len());
+            for (k, v) in entries {
+                k.encode_value(&mut enc);
+                v.encode_value(&mut enc);
+            }
+            ScryptoValue::from_slice(&bytes).unwrap()
+        };
+
+        let a = value(vec![(1, Decimal::one()), (2, Decimal::zero())]);
+        let b = value(vec![(2, Decimal::zero()), (1, Decimal::one())]);
+        let c = value(vec![(1, Decimal::one()), (2, Decimal::one())]);
+
+        assert_ne!(a, b);
+        assert!(a.canonical_eq(&b));
+        assert_eq!(a.hash(), b.hash());
+        assert!(!a.canonical_eq(&c));
+        assert_ne!(a.hash(), c.hash());
+    }
+
+    #[test]
+    fn test_get_path() {
+        #[derive(TypeId, Encode, Describe)]
+        struct State {
+            name: String,
+            vaults: Vec<(u8, Decimal)>,
+        }
+
+        let value = ScryptoValue::from_typed(&State {
+            name: "a".to_string(),
+            vaults: vec![(1, Decimal::one())],
+        });
+
+        assert_eq!(
+            value.get_path_with_type(&State::describe(), &["vaults", "0", "1"]),
+            Ok(ScryptoValue::from_typed(&Decimal::one()))
+        );
+        assert_eq!(
+            value.get_path(&["0"]),
+            Ok(ScryptoValue::from_typed(&"a".to_string()))
+        );
+        assert_eq!(
+            value.get_path(&["vaults"]),
+            Err(SborPathError::UnknownField("vaults".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_encode_nested_scrypto_value() {
+        let value = ScryptoValue::from_typed(&Decimal::from(5));
+
+        let bytes = scrypto_encode(&Some(value.clone()));
+
+        assert_eq!(bytes, scrypto_encode(&Some(Decimal::from(5))));
+        assert_eq!(
+            scrypto_decode::<Option<ScryptoValue>>(&bytes),
+            Ok(Some(value))
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_round_trip() {
+        let network = NetworkDefinition::simulator();
+        let value = ScryptoValue::from_typed(&(
+            Decimal::from("1.5"),
+            RADIX_TOKEN,
+            NonFungibleId::from_u32(1),
+            Hash([1u8; 32]),
+        ));
+
+        let json = value.to_json(&network);
+
+        assert_eq!(
+            json["elements"][0],
+            serde_json::json!({ "type": "Decimal", "value": "1.5" })
+        );
+        assert_eq!(
+            json["elements"][1],
+            serde_json::json!({
+                "type": "ResourceAddress",
+                "value": "resource_sim1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqzqu57yag"
+            })
+        );
+        assert_eq!(
+            json["elements"][2],
+            serde_json::json!({ "type": "NonFungibleId", "value": "0901000000" })
+        );
+        assert_eq!(ScryptoValue::from_json(&json, &network), Ok(value));
+    }
 }
@@ -1,8 +1,10 @@
 #![allow(unused_must_use)]
 use colored::*;
+use radix_engine::engine::Substate;
 use radix_engine::ledger::*;
 use radix_engine::model::*;
 use radix_engine::types::*;
+use scrypto::values::ScryptoValueFormatter;
 use std::collections::VecDeque;
 
 use crate::utils::*;
@@ -13,6 +15,7 @@ pub enum DisplayError {
     PackageNotFound,
     ComponentNotFound,
     ResourceManagerNotFound,
+    NonFungibleNotFound,
     AddressError(AddressError),
 }
 
@@ -77,8 +80,14 @@ pub fn dump_component<T: ReadableSubstateStore + QueryableSubstateStore, O: std:
                 c.blueprint_name()
             );
 
+            let access_rules: ComponentAccessRules = substate_store
+                .get_substate(&SubstateId::ComponentAccessRules(component_address))
+                .map(|s| s.substate)
+                .map(|s| s.into())
+                .unwrap();
+
             writeln!(output, "{}", "Authorization".green().bold());
-            for (_, auth) in c.authorization().iter().identify_last() {
+            for (_, auth) in access_rules.authorization().iter().identify_last() {
                 for (last, (k, v)) in auth.iter().identify_last() {
                     writeln!(output, "{} {:?} => {:?}", list_item_prefix(last), k, v);
                 }
@@ -130,11 +139,10 @@ fn dump_kv_store<T: ReadableSubstateStore + QueryableSubstateStore, O: std::io::
     );
     for (last, (k, v)) in map.iter().identify_last() {
         let key = ScryptoValue::from_slice(k).unwrap();
-        if let Some(v) = &v.kv_entry().0 {
-            let value = ScryptoValue::from_slice(&v).unwrap();
+        if let Some(value) = v.kv_entry() {
             writeln!(output, "{} {} => {}", list_item_prefix(last), key, value);
-            referenced_maps.extend(value.kv_store_ids);
-            referenced_vaults.extend(value.vault_ids);
+            referenced_maps.extend(value.kv_store_ids.iter().cloned());
+            referenced_vaults.extend(value.vault_ids.iter().cloned());
         }
     }
     Ok((referenced_maps, referenced_vaults))
@@ -181,12 +189,12 @@ fn dump_resources<T: ReadableSubstateStore, O: std::io::Write>(
         if matches!(resource_manager.resource_type(), ResourceType::NonFungible) {
             let ids = vault.total_ids().unwrap();
             for (inner_last, id) in ids.iter().identify_last() {
-                let non_fungible: NonFungibleWrapper = substate_store
+                let non_fungible: Option<NonFungible> = substate_store
                     .get_substate(&SubstateId::NonFungible(resource_address, id.clone()))
                     .map(|s| s.substate)
                     .map(|s| s.into())
                     .unwrap();
-                if let Some(non_fungible) = non_fungible.0 {
+                if let Some(non_fungible) = non_fungible {
                     let id = ScryptoValue::from_typed(id);
                     let immutable_data =
                         ScryptoValue::from_slice(&non_fungible.immutable_data()).unwrap();
@@ -209,7 +217,10 @@ fn dump_resources<T: ReadableSubstateStore, O: std::io::Write>(
 }
 
 /// Dump a resource into console.
-pub fn dump_resource_manager<T: ReadableSubstateStore, O: std::io::Write>(
+pub fn dump_resource_manager<
+    T: ReadableSubstateStore + QueryableSubstateStore,
+    O: std::io::Write,
+>(
     resource_address: ResourceAddress,
     substate_store: &T,
     output: &mut O,
@@ -247,8 +258,290 @@ pub fn dump_resource_manager<T: ReadableSubstateStore, O: std::io::Write>(
                 "Total Supply".green().bold(),
                 r.total_supply()
             );
+            if matches!(r.resource_type(), ResourceType::NonFungible) {
+                writeln!(
+                    output,
+                    "{}: {}",
+                    "Non-Fungibles".green().bold(),
+                    substate_store.get_non_fungibles(&resource_address).len()
+                );
+            }
+            let vaults: BTreeMap<VaultId, Substate> = substate_store
+                .get_vaults_holding(&resource_address)
+                .into_iter()
+                .collect();
+            writeln!(output, "{}: {}", "Vaults".green().bold(), vaults.len());
+            for (last, (vault_id, substate)) in vaults.iter().identify_last() {
+                writeln!(
+                    output,
+                    "{} {{ vault id: {}, index: {}, amount: {} }}",
+                    list_item_prefix(last),
+                    vault_id.0,
+                    vault_id.1,
+                    substate.vault().total_amount()
+                );
+            }
             Ok(())
         }
         None => Err(DisplayError::ResourceManagerNotFound),
     }
 }
+
+/// Dump a package into a JSON value.
+pub fn package_to_json<T: ReadableSubstateStore>(
+    package_address: PackageAddress,
+    substate_store: &T,
+) -> Result<serde_json::Value, DisplayError> {
+    let bech32_encoder = Bech32Encoder::new(&NetworkDefinition::simulator());
+
+    let package: Package = substate_store
+        .get_substate(&SubstateId::Package(package_address))
+        .map(|s| s.substate.into())
+        .ok_or(DisplayError::PackageNotFound)?;
+    Ok(serde_json::json!({
+        "package_address": bech32_encoder.encode_package_address(&package_address),
+        "code_size": package.code().len(),
+    }))
+}
+
+/// Returns all vaults owned by a component, assuming a tree structure.
+pub fn get_component_vaults<T: ReadableSubstateStore + QueryableSubstateStore>(
+    component_address: ComponentAddress,
+    substate_store: &T,
+) -> Result<Vec<Vault>, DisplayError> {
+    let state: ComponentState = substate_store
+        .get_substate(&SubstateId::ComponentState(component_address))
+        .map(|s| s.substate.into())
+        .ok_or(DisplayError::ComponentNotFound)?;
+    let state_data = ScryptoValue::from_slice(state.state()).unwrap();
+
+    let mut vaults_found: HashSet<VaultId> = state_data.vault_ids.iter().cloned().collect();
+    let mut queue: VecDeque<KeyValueStoreId> = state_data.kv_store_ids.iter().cloned().collect();
+    while let Some(kv_store_id) = queue.pop_front() {
+        for (_, v) in substate_store.get_kv_store_entries(&kv_store_id) {
+            if let Some(value) = v.kv_entry() {
+                queue.extend(value.kv_store_ids.iter().cloned());
+                vaults_found.extend(value.vault_ids.iter().cloned());
+            }
+        }
+    }
+
+    Ok(vaults_found
+        .iter()
+        .map(|vault_id| {
+            substate_store
+                .get_substate(&SubstateId::Vault(*vault_id))
+                .map(|s| s.substate.into())
+                .unwrap()
+        })
+        .collect())
+}
+
+/// Dump a component, including the resources it owns, into a JSON value.
+pub fn component_to_json<T: ReadableSubstateStore + QueryableSubstateStore>(
+    component_address: ComponentAddress,
+    substate_store: &T,
+) -> Result<serde_json::Value, DisplayError> {
+    let bech32_encoder = Bech32Encoder::new(&NetworkDefinition::simulator());
+
+    let component: ComponentInfo = substate_store
+        .get_substate(&SubstateId::ComponentInfo(component_address))
+        .map(|s| s.substate.into())
+        .ok_or(DisplayError::ComponentNotFound)?;
+    let state: ComponentState = substate_store
+        .get_substate(&SubstateId::ComponentState(component_address))
+        .map(|s| s.substate.into())
+        .unwrap();
+    let state_data = ScryptoValue::from_slice(state.state()).unwrap();
+
+    let mut resources = Vec::new();
+    for vault in get_component_vaults(component_address, substate_store)? {
+        let resource_address = vault.resource_address();
+        let resource_manager: ResourceManager = substate_store
+            .get_substate(&SubstateId::ResourceManager(resource_address))
+            .map(|s| s.substate.into())
+            .unwrap();
+        let non_fungible_ids = vault.total_ids().ok().map(|ids| {
+            ids.iter()
+                .map(|id| hex::encode(id.to_vec()))
+                .collect::<Vec<String>>()
+        });
+        resources.push(serde_json::json!({
+            "resource_address": bech32_encoder.encode_resource_address(&resource_address),
+            "amount": vault.total_amount().to_string(),
+            "name": resource_manager.metadata().get("name"),
+            "symbol": resource_manager.metadata().get("symbol"),
+            "non_fungible_ids": non_fungible_ids,
+        }));
+    }
+
+    Ok(serde_json::json!({
+        "component_address": bech32_encoder.encode_component_address(&component_address),
+        "package_address": bech32_encoder.encode_package_address(&component.package_address()),
+        "blueprint_name": component.blueprint_name(),
+        "state": state_data.to_json(&NetworkDefinition::simulator()),
+        "resources": resources,
+    }))
+}
+
+/// Dump a resource manager into a JSON value.
+pub fn resource_manager_to_json<T: ReadableSubstateStore + QueryableSubstateStore>(
+    resource_address: ResourceAddress,
+    substate_store: &T,
+) -> Result<serde_json::Value, DisplayError> {
+    let bech32_encoder = Bech32Encoder::new(&NetworkDefinition::simulator());
+
+    let resource_manager: ResourceManager = substate_store
+        .get_substate(&SubstateId::ResourceManager(resource_address))
+        .map(|s| s.substate.into())
+        .ok_or(DisplayError::ResourceManagerNotFound)?;
+    let non_fungibles = if matches!(resource_manager.resource_type(), ResourceType::NonFungible) {
+        Some(substate_store.get_non_fungibles(&resource_address).len())
+    } else {
+        None
+    };
+    let vaults: BTreeMap<VaultId, Substate> = substate_store
+        .get_vaults_holding(&resource_address)
+        .into_iter()
+        .collect();
+    let vaults: Vec<serde_json::Value> = vaults
+        .iter()
+        .map(|(vault_id, substate)| {
+            serde_json::json!({
+                "vault_id": hex::encode(scrypto_encode(vault_id)),
+                "amount": substate.vault().total_amount().to_string(),
+            })
+        })
+        .collect();
+
+    Ok(serde_json::json!({
+        "resource_address": bech32_encoder.encode_resource_address(&resource_address),
+        "resource_type": format!("{:?}", resource_manager.resource_type()),
+        "metadata": resource_manager.metadata(),
+        "total_supply": resource_manager.total_supply().to_string(),
+        "non_fungibles": non_fungibles,
+        "vaults": vaults,
+    }))
+}
+
+/// Splits non-fungible data into its struct fields, formatted for display.
+///
+/// Field names are not part of the SBOR encoding, so fields are listed by position.
+fn non_fungible_data_fields(data: &[u8]) -> Vec<String> {
+    let value = ScryptoValue::from_slice(data).unwrap();
+    match &value.dom {
+        Value::Struct { fields } => fields
+            .iter()
+            .map(|field| {
+                ScryptoValueFormatter::format_value(field, &HashMap::new(), &HashMap::new())
+            })
+            .collect(),
+        _ => vec![value.to_string()],
+    }
+}
+
+fn get_non_fungibles<T: ReadableSubstateStore + QueryableSubstateStore>(
+    resource_address: ResourceAddress,
+    id: Option<NonFungibleId>,
+    substate_store: &T,
+) -> Result<BTreeMap<NonFungibleId, Option<NonFungible>>, DisplayError> {
+    substate_store
+        .get_substate(&SubstateId::ResourceManager(resource_address))
+        .ok_or(DisplayError::ResourceManagerNotFound)?;
+
+    let non_fungibles: BTreeMap<NonFungibleId, Option<NonFungible>> = match id {
+        Some(id) => {
+            let non_fungible: Option<NonFungible> = substate_store
+                .get_substate(&SubstateId::NonFungible(resource_address, id.clone()))
+                .map(|s| s.substate.into())
+                .ok_or(DisplayError::NonFungibleNotFound)?;
+            BTreeMap::from([(id, non_fungible)])
+        }
+        None => substate_store
+            .get_non_fungibles(&resource_address)
+            .into_iter()
+            .map(|(id, substate)| (id, substate.into()))
+            .collect(),
+    };
+    Ok(non_fungibles)
+}
+
+/// Dump the non-fungibles of a resource, or a single one if an ID is given, into console.
+pub fn dump_non_fungibles<T: ReadableSubstateStore + QueryableSubstateStore, O: std::io::Write>(
+    resource_address: ResourceAddress,
+    id: Option<NonFungibleId>,
+    substate_store: &T,
+    output: &mut O,
+) -> Result<(), DisplayError> {
+    let non_fungibles = get_non_fungibles(resource_address, id, substate_store)?;
+
+    writeln!(
+        output,
+        "{}: {}",
+        "Non-Fungibles".green().bold(),
+        non_fungibles.len()
+    );
+    for (last, (id, non_fungible)) in non_fungibles.iter().identify_last() {
+        let indent = if last { " " } else { "│" };
+        match non_fungible {
+            Some(non_fungible) => {
+                writeln!(output, "{} {}", list_item_prefix(last), id);
+                let sections = [
+                    ("Immutable Data", non_fungible.immutable_data()),
+                    ("Mutable Data", non_fungible.mutable_data()),
+                ];
+                for (section_last, (name, data)) in sections.iter().identify_last() {
+                    writeln!(
+                        output,
+                        "{}  {} {}",
+                        indent,
+                        list_item_prefix(section_last),
+                        name.green().bold()
+                    );
+                    let fields = non_fungible_data_fields(data);
+                    for (field_last, (i, field)) in fields.iter().enumerate().identify_last() {
+                        writeln!(
+                            output,
+                            "{}  {}  {} {}: {}",
+                            indent,
+                            if section_last { " " } else { "│" },
+                            list_item_prefix(field_last),
+                            i,
+                            field
+                        );
+                    }
+                }
+            }
+            None => {
+                writeln!(output, "{} {} (burned)", list_item_prefix(last), id);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Dump the non-fungibles of a resource, or a single one if an ID is given, into a JSON value.
+pub fn non_fungibles_to_json<T: ReadableSubstateStore + QueryableSubstateStore>(
+    resource_address: ResourceAddress,
+    id: Option<NonFungibleId>,
+    substate_store: &T,
+) -> Result<serde_json::Value, DisplayError> {
+    let non_fungibles = get_non_fungibles(resource_address, id, substate_store)?;
+
+    let values: Vec<serde_json::Value> = non_fungibles
+        .iter()
+        .map(|(id, non_fungible)| match non_fungible {
+            Some(non_fungible) => serde_json::json!({
+                "id": id.to_string(),
+                "burned": false,
+                "immutable_data": non_fungible_data_fields(&non_fungible.immutable_data()),
+                "mutable_data": non_fungible_data_fields(&non_fungible.mutable_data()),
+            }),
+            None => serde_json::json!({
+                "id": id.to_string(),
+                "burned": true,
+            }),
+        })
+        .collect();
+    Ok(serde_json::Value::Array(values))
+}
@@ -0,0 +1,160 @@
+use clap::{Parser, Subcommand};
+use colored::*;
+use radix_engine::types::*;
+
+use crate::resim::*;
+
+/// Manage named accounts
+#[derive(Parser, Debug)]
+pub struct Account {
+    #[clap(subcommand)]
+    command: AccountCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AccountCommand {
+    Create(AccountCreate),
+    List(AccountList),
+    Switch(AccountSwitch),
+    Rename(AccountRename),
+}
+
+impl Account {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        match &self.command {
+            AccountCommand::Create(cmd) => cmd.run(ctx, out),
+            AccountCommand::List(cmd) => cmd.run(ctx, out),
+            AccountCommand::Switch(cmd) => cmd.run(ctx, out),
+            AccountCommand::Rename(cmd) => cmd.run(ctx, out),
+        }
+    }
+}
+
+/// Create a named account
+#[derive(Parser, Debug)]
+pub struct AccountCreate {
+    /// The account name
+    name: String,
+
+    /// The network to use when outputting manifest, [simulator | adapanet | nebunet | mainnet]
+    #[clap(short, long)]
+    network: Option<String>,
+
+    /// Output a transaction manifest without execution
+    #[clap(short, long)]
+    manifest: Option<PathBuf>,
+
+    /// Create the account without XRD, instead of with a faucet payout
+    #[clap(long)]
+    empty: bool,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
+}
+
+impl AccountCreate {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        create_account(
+            ctx,
+            &Some(self.name.clone()),
+            &self.network,
+            &self.manifest,
+            self.empty,
+            self.trace,
+            out,
+        )
+    }
+}
+
+/// List all named accounts, marking the default one
+#[derive(Parser, Debug)]
+pub struct AccountList {}
+
+impl AccountList {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let configs = get_configs(ctx)?;
+        let bech32_encoder = Bech32Encoder::new(&NetworkDefinition::simulator());
+
+        if ctx.is_json_output() {
+            let accounts: Vec<serde_json::Value> = configs
+                .accounts
+                .iter()
+                .map(|(name, profile)| {
+                    serde_json::json!({
+                        "name": name,
+                        "component_address":
+                            bech32_encoder.encode_component_address(&profile.component_address),
+                        "default": configs.default_account.as_ref() == Some(name),
+                    })
+                })
+                .collect();
+            return write_json(out, &serde_json::Value::Array(accounts));
+        }
+
+        for (name, profile) in &configs.accounts {
+            let is_default = configs.default_account.as_ref() == Some(name);
+            writeln!(
+                out,
+                "{} {}: {}",
+                if is_default { "*" } else { " " },
+                name.green().bold(),
+                bech32_encoder.encode_component_address(&profile.component_address)
+            )
+            .map_err(Error::IOError)?;
+        }
+        Ok(())
+    }
+}
+
+/// Switch the default account
+#[derive(Parser, Debug)]
+pub struct AccountSwitch {
+    /// The name of the account to use by default
+    name: String,
+}
+
+impl AccountSwitch {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let mut configs = get_configs(ctx)?;
+        if !configs.accounts.contains_key(&self.name) {
+            return Err(Error::AccountNotFound(self.name.clone()));
+        }
+        configs.default_account = Some(self.name.clone());
+        set_configs(ctx, &configs)?;
+
+        writeln!(out, "Default account updated!").map_err(Error::IOError)?;
+        Ok(())
+    }
+}
+
+/// Rename an account
+#[derive(Parser, Debug)]
+pub struct AccountRename {
+    /// The current account name
+    name: String,
+
+    /// The new account name
+    new_name: String,
+}
+
+impl AccountRename {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let mut configs = get_configs(ctx)?;
+        if configs.accounts.contains_key(&self.new_name) {
+            return Err(Error::AccountAlreadyExists(self.new_name.clone()));
+        }
+        let profile = configs
+            .accounts
+            .remove(&self.name)
+            .ok_or_else(|| Error::AccountNotFound(self.name.clone()))?;
+        configs.accounts.insert(self.new_name.clone(), profile);
+        if configs.default_account.as_ref() == Some(&self.name) {
+            configs.default_account = Some(self.new_name.clone());
+        }
+        set_configs(ctx, &configs)?;
+
+        writeln!(out, "Account renamed!").map_err(Error::IOError)?;
+        Ok(())
+    }
+}
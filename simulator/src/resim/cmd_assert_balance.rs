@@ -0,0 +1,48 @@
+use clap::Parser;
+use radix_engine::ledger::{AccountQueryStore, ReadableSubstateStore};
+use radix_engine::types::*;
+
+use crate::ledger::*;
+use crate::resim::*;
+
+/// Fail unless a component holds the expected amount of a resource
+#[derive(Parser, Debug)]
+pub struct AssertBalance {
+    /// The component address, or the name of an account
+    component: String,
+
+    /// The resource address
+    resource_address: ResourceAddress,
+
+    /// The expected amount
+    amount: Decimal,
+}
+
+impl AssertBalance {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let component_address = match ComponentAddress::from_str(&self.component) {
+            Ok(component_address) => component_address,
+            Err(_) => get_account(ctx, &Some(self.component.clone()))?,
+        };
+        let ledger = open_ledger_read_only(ctx)?;
+        if ledger
+            .get_substate(&SubstateId::ComponentState(component_address))
+            .is_none()
+        {
+            return Err(Error::LedgerDumpError(DisplayError::ComponentNotFound));
+        }
+        let balance = ledger
+            .get_component_balances(&component_address)
+            .remove(&self.resource_address)
+            .unwrap_or_else(Decimal::zero);
+
+        if balance != self.amount {
+            return Err(Error::AssertionFailed(format!(
+                "expected balance {}, found {}",
+                self.amount, balance
+            )));
+        }
+        writeln!(out, "Balance matches!").map_err(Error::IOError)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use clap::Parser;
+use radix_engine::types::*;
+
+use crate::ledger::*;
+use crate::resim::*;
+
+/// Fail unless a field of a component's state has the expected value
+#[derive(Parser, Debug)]
+pub struct AssertState {
+    /// The component address
+    component_address: ComponentAddress,
+
+    /// The field name, or its position if the fields are unnamed
+    field: String,
+
+    /// The expected value, formatted as shown by `resim show`, e.g. 5u32 or Decimal("1.5")
+    value: String,
+}
+
+impl AssertState {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let ledger = open_ledger_read_only(ctx)?;
+        let fields = get_state_fields(self.component_address, &*ledger)?
+            .ok_or(Error::LedgerDumpError(DisplayError::ComponentNotFound))?;
+        let abi = radix_engine::model::export_abi_by_component(&*ledger, self.component_address)
+            .map_err(Error::AbiExportError)?;
+        let index = field_names(&abi.structure)
+            .iter()
+            .position(|name| name == &self.field)
+            .or_else(|| self.field.parse::<usize>().ok())
+            .filter(|index| *index < fields.len())
+            .ok_or_else(|| Error::AssertionFailed(format!("no field {}", self.field)))?;
+
+        if fields[index] != self.value {
+            return Err(Error::AssertionFailed(format!(
+                "expected {} to be {}, found {}",
+                self.field, self.value, fields[index]
+            )));
+        }
+        writeln!(out, "State matches!").map_err(Error::IOError)?;
+        Ok(())
+    }
+}
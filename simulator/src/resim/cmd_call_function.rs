@@ -8,8 +8,9 @@ use crate::resim::*;
 /// Call a function
 #[derive(Parser, Debug)]
 pub struct CallFunction {
-    /// The package which the function belongs to
-    package_address: PackageAddress,
+    /// The package which the function belongs to, as an address or the name of a package
+    /// published with `publish-workspace`
+    package: String,
 
     /// The name of the blueprint which the function belongs to
     blueprint_name: String,
@@ -36,14 +37,19 @@ pub struct CallFunction {
     #[clap(short, long)]
     signing_keys: Option<String>,
 
+    /// The name of the account to use instead of the default account
+    #[clap(long)]
+    account: Option<String>,
+
     /// Turn on tracing
     #[clap(short, long)]
     trace: bool,
 }
 
 impl CallFunction {
-    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
-        let default_account = get_default_account()?;
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let default_account = get_account(ctx, &self.account)?;
+        let package_address = get_package_address(ctx, &self.package)?;
         let proofs = self.proofs.clone().unwrap_or_default();
 
         let mut manifest_builder = &mut ManifestBuilder::new(&NetworkDefinition::simulator());
@@ -59,12 +65,12 @@ impl CallFunction {
         let manifest = manifest_builder
             .lock_fee(100.into(), SYS_FAUCET_COMPONENT)
             .call_function_with_abi(
-                self.package_address,
+                package_address,
                 &self.blueprint_name,
                 &self.function_name,
                 self.arguments.clone(),
                 Some(default_account),
-                &export_abi(self.package_address, &self.blueprint_name)?,
+                &export_abi(ctx, package_address, &self.blueprint_name)?,
             )
             .map_err(Error::TransactionConstructionError)?
             .call_method(
@@ -74,8 +80,10 @@ impl CallFunction {
             )
             .build();
         handle_manifest(
+            ctx,
             manifest,
             &self.signing_keys,
+            &self.account,
             &self.network,
             &self.manifest,
             self.trace,
@@ -35,15 +35,21 @@ pub struct CallMethod {
     #[clap(short, long)]
     signing_keys: Option<String>,
 
+    /// The name of the account to use instead of the default account
+    #[clap(long)]
+    account: Option<String>,
+
     /// Turn on tracing
     #[clap(short, long)]
     trace: bool,
 }
 
 impl CallMethod {
-    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
-        let default_account = get_default_account()?;
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let default_account = get_account(ctx, &self.account)?;
         let proofs = self.proofs.clone().unwrap_or_default();
+        let abi = export_abi_by_component(ctx, self.component_address)?;
+        let arguments = complete_arguments(&abi, &self.method_name, &self.arguments)?;
 
         let mut manifest_builder = &mut ManifestBuilder::new(&NetworkDefinition::simulator());
         for resource_specifier in proofs {
@@ -60,9 +66,9 @@ impl CallMethod {
             .call_method_with_abi(
                 self.component_address,
                 &self.method_name,
-                self.arguments.clone(),
+                arguments,
                 Some(default_account),
-                &export_abi_by_component(self.component_address)?,
+                &abi,
             )
             .map_err(Error::TransactionConstructionError)?
             .call_method(
@@ -72,8 +78,10 @@ impl CallMethod {
             )
             .build();
         handle_manifest(
+            ctx,
             manifest,
             &self.signing_keys,
+            &self.account,
             &self.network,
             &self.manifest,
             self.trace,
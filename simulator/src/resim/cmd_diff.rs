@@ -0,0 +1,252 @@
+use clap::Parser;
+use colored::*;
+use radix_engine::ledger::*;
+use radix_engine::model::*;
+use radix_engine::types::*;
+use sbor::describe::Fields;
+use scrypto::values::ScryptoValueFormatter;
+use std::convert::Infallible;
+use std::str::FromStr;
+
+use crate::ledger::*;
+use crate::resim::*;
+use crate::utils::*;
+
+/// Show the field-level changes in a component's state between two points in the history of
+/// the ledger
+#[derive(Parser, Debug)]
+pub struct Diff {
+    /// The component address
+    component_address: ComponentAddress,
+
+    /// The state version or the snapshot to compare from
+    #[clap(long)]
+    from: StatePoint,
+
+    /// The state version or the snapshot to compare to; the current ledger is used if omitted
+    #[clap(long)]
+    to: Option<StatePoint>,
+}
+
+/// A point to read state at: a state version of the current ledger, or the name of a snapshot
+/// if it isn't a number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatePoint {
+    Version(u64),
+    Snapshot(String),
+}
+
+impl FromStr for StatePoint {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.parse() {
+            Ok(state_version) => StatePoint::Version(state_version),
+            Err(_) => StatePoint::Snapshot(s.to_owned()),
+        })
+    }
+}
+
+/// A change to a single field of component state; `None` means the field is absent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    pub field: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+impl Diff {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let from_state = read_state_at(ctx, Some(&self.from), self.component_address)?;
+        let to_state = read_state_at(ctx, self.to.as_ref(), self.component_address)?;
+        let names = match (&from_state, &to_state) {
+            (_, Some((names, _))) | (Some((names, _)), None) => names.clone(),
+            (None, None) => return Err(Error::LedgerDumpError(DisplayError::ComponentNotFound)),
+        };
+        let changes = diff_fields(
+            &names,
+            &from_state.map(|(_, fields)| fields).unwrap_or_default(),
+            &to_state.map(|(_, fields)| fields).unwrap_or_default(),
+        );
+
+        if ctx.is_json_output() {
+            let bech32_encoder = Bech32Encoder::new(&NetworkDefinition::simulator());
+            let changes: Vec<serde_json::Value> = changes
+                .iter()
+                .map(|c| serde_json::json!({ "field": c.field, "from": c.from, "to": c.to }))
+                .collect();
+            return write_json(
+                out,
+                &serde_json::json!({
+                    "component_address":
+                        bech32_encoder.encode_component_address(&self.component_address),
+                    "changes": changes,
+                }),
+            );
+        }
+        if changes.is_empty() {
+            writeln!(out, "No changes.").map_err(Error::IOError)?;
+            return Ok(());
+        }
+        writeln!(out, "{}:", "Changes".green().bold()).map_err(Error::IOError)?;
+        for (last, change) in changes.iter().identify_last() {
+            writeln!(
+                out,
+                "{} {}: {} → {}",
+                list_item_prefix(last),
+                change.field,
+                change.from.as_deref().unwrap_or("<none>").red(),
+                change.to.as_deref().unwrap_or("<none>").green()
+            )
+            .map_err(Error::IOError)?;
+        }
+        Ok(())
+    }
+}
+
+/// The field names of a component's blueprint, with the fields of its state.
+type NamedFields = (Vec<String>, Vec<String>);
+
+/// Reads the field names and the state fields of a component at the given point, or the current
+/// ledger if none, returning `None` if the component does not exist there.
+fn read_state_at(
+    ctx: &CommandContext,
+    at: Option<&StatePoint>,
+    component_address: ComponentAddress,
+) -> Result<Option<NamedFields>, Error> {
+    match at {
+        None => {
+            let ledger = open_ledger_read_only(ctx)?;
+            read_state(component_address, &*ledger)
+        }
+        Some(StatePoint::Version(state_version)) => {
+            let ledger = open_ledger_read_only(ctx)?;
+            if *state_version >= ledger.state_version() {
+                return Err(Error::StateVersionNotFound(*state_version));
+            }
+            read_state(component_address, &ledger.at_version(*state_version))
+        }
+        Some(StatePoint::Snapshot(name)) => {
+            let ledger = open_snapshot_read_only(ctx, name)?;
+            read_state(component_address, &*ledger)
+        }
+    }
+}
+
+fn read_state<T: ReadableSubstateStore>(
+    component_address: ComponentAddress,
+    substate_store: &T,
+) -> Result<Option<NamedFields>, Error> {
+    match get_state_fields(component_address, substate_store)? {
+        Some(fields) => {
+            let abi =
+                radix_engine::model::export_abi_by_component(substate_store, component_address)
+                    .map_err(Error::AbiExportError)?;
+            Ok(Some((field_names(&abi.structure), fields)))
+        }
+        None => Ok(None),
+    }
+}
+
+fn open_snapshot_read_only(ctx: &CommandContext, name: &str) -> Result<SecondaryLedger, Error> {
+    let snapshot_dir = get_snapshot_dir(ctx, name)?;
+    if !snapshot_dir.is_dir() {
+        return Err(Error::SnapshotNotFound(name.to_owned()));
+    }
+    let secondary_dir =
+        std::env::temp_dir().join(format!("resim-secondary-{}-{}", std::process::id(), name));
+    Ok(SecondaryLedger::open(snapshot_dir, secondary_dir))
+}
+
+/// Returns the top-level fields of a component's state, formatted for display, or `None` if
+/// the component does not exist.
+///
+/// Only the component state itself is included; the contents of its key-value stores and
+/// vaults are not.
+pub fn get_state_fields<T: ReadableSubstateStore>(
+    component_address: ComponentAddress,
+    substate_store: &T,
+) -> Result<Option<Vec<String>>, Error> {
+    let state: ComponentState =
+        match substate_store.get_substate(&SubstateId::ComponentState(component_address)) {
+            Some(output) => output.substate.into(),
+            None => return Ok(None),
+        };
+    let value = ScryptoValue::from_slice(state.state()).map_err(Error::DataError)?;
+    Ok(Some(match &value.dom {
+        Value::Struct { fields } => fields
+            .iter()
+            .map(|field| {
+                ScryptoValueFormatter::format_value(field, &HashMap::new(), &HashMap::new())
+            })
+            .collect(),
+        _ => vec![value.to_string()],
+    }))
+}
+
+/// Returns the field names of a blueprint structure, or none if its fields are unnamed.
+pub fn field_names(structure: &Type) -> Vec<String> {
+    match structure {
+        Type::Struct {
+            fields: Fields::Named { named },
+            ..
+        } => named.iter().map(|(name, _)| name.clone()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Compares fields by position, naming them from the blueprint structure where known.
+pub fn diff_fields(names: &[String], from: &[String], to: &[String]) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    for i in 0..from.len().max(to.len()) {
+        let from = from.get(i).cloned();
+        let to = to.get(i).cloned();
+        if from != to {
+            changes.push(FieldChange {
+                field: names.get(i).cloned().unwrap_or_else(|| i.to_string()),
+                from,
+                to,
+            });
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_fields() {
+        let names = vec!["count".to_owned(), "name".to_owned()];
+        let from = vec!["1u32".to_owned(), "\"a\"".to_owned()];
+        let to = vec!["2u32".to_owned(), "\"a\"".to_owned(), "true".to_owned()];
+
+        let changes = diff_fields(&names, &from, &to);
+
+        assert_eq!(
+            changes,
+            vec![
+                FieldChange {
+                    field: "count".to_owned(),
+                    from: Some("1u32".to_owned()),
+                    to: Some("2u32".to_owned()),
+                },
+                FieldChange {
+                    field: "2".to_owned(),
+                    from: None,
+                    to: Some("true".to_owned()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_state_point_from_str() {
+        assert_eq!("12".parse(), Ok(StatePoint::Version(12)));
+        assert_eq!(
+            "before-swap".parse(),
+            Ok(StatePoint::Snapshot("before-swap".to_owned()))
+        );
+    }
+}
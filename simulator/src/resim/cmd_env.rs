@@ -0,0 +1,108 @@
+use clap::{Parser, Subcommand};
+use colored::*;
+use std::fs;
+
+use crate::resim::*;
+
+/// Manage isolated environments, each with its own ledger and configurations
+#[derive(Parser, Debug)]
+pub struct Env {
+    #[clap(subcommand)]
+    command: EnvCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum EnvCommand {
+    Create(EnvCreate),
+    List(EnvList),
+    Use(EnvUse),
+}
+
+impl Env {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        match &self.command {
+            EnvCommand::Create(cmd) => cmd.run(ctx, out),
+            EnvCommand::List(cmd) => cmd.run(ctx, out),
+            EnvCommand::Use(cmd) => cmd.run(ctx, out),
+        }
+    }
+}
+
+/// Create an environment
+#[derive(Parser, Debug)]
+pub struct EnvCreate {
+    /// The environment name
+    name: String,
+}
+
+impl EnvCreate {
+    pub fn run<O: std::io::Write>(&self, _ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        if env_exists(&self.name)? {
+            return Err(Error::EnvAlreadyExists(self.name.clone()));
+        }
+        fs::create_dir_all(get_env_dir(&self.name)?).map_err(Error::IOError)?;
+
+        writeln!(out, "Environment created!").map_err(Error::IOError)?;
+        Ok(())
+    }
+}
+
+/// List all environments, marking the current one
+#[derive(Parser, Debug)]
+pub struct EnvList {}
+
+impl EnvList {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let mut names = vec![DEFAULT_ENV.to_owned()];
+        let envs_dir = get_envs_dir()?;
+        if envs_dir.is_dir() {
+            let mut others = Vec::new();
+            for entry in fs::read_dir(envs_dir).map_err(Error::IOError)? {
+                let entry = entry.map_err(Error::IOError)?;
+                if entry.file_type().map_err(Error::IOError)?.is_dir() {
+                    others.push(entry.file_name().to_string_lossy().into_owned());
+                }
+            }
+            others.sort();
+            names.extend(others);
+        }
+        let current = get_current_env(ctx)?;
+
+        if ctx.is_json_output() {
+            return write_json(
+                out,
+                &serde_json::json!({
+                    "current": current,
+                    "envs": names,
+                }),
+            );
+        }
+        for name in names {
+            if name == current {
+                writeln!(out, "* {}", name.green().bold()).map_err(Error::IOError)?;
+            } else {
+                writeln!(out, "  {}", name).map_err(Error::IOError)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Switch to an environment
+#[derive(Parser, Debug)]
+pub struct EnvUse {
+    /// The environment name
+    name: String,
+}
+
+impl EnvUse {
+    pub fn run<O: std::io::Write>(&self, _ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        if !env_exists(&self.name)? {
+            return Err(Error::EnvNotFound(self.name.clone()));
+        }
+        set_current_env(&self.name)?;
+
+        writeln!(out, "Current environment updated!").map_err(Error::IOError)?;
+        Ok(())
+    }
+}
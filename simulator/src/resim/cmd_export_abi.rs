@@ -1,9 +1,18 @@
-use clap::Parser;
+use clap::{ArgEnum, Parser};
 use radix_engine::types::*;
+use sbor::describe::Fields;
 use scrypto::abi;
+use std::fmt::Write;
 
 use crate::resim::*;
 
+/// The languages client stubs can be generated for.
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bindings {
+    Typescript,
+    Rust,
+}
+
 /// Export the ABI of a blueprint
 #[derive(Parser, Debug)]
 pub struct ExportAbi {
@@ -13,31 +22,280 @@ pub struct ExportAbi {
     /// The blueprint name
     blueprint_name: String,
 
+    /// Emit client stubs in the given language instead of the JSON ABI, [typescript | rust]
+    #[clap(long, arg_enum)]
+    bindings: Option<Bindings>,
+
     /// Turn on tracing.
     #[clap(short, long)]
     trace: bool,
 }
 
 impl ExportAbi {
-    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
         let bech32_encoder = Bech32Encoder::new(&NetworkDefinition::simulator());
 
-        match export_abi(self.package_address, &self.blueprint_name) {
+        match export_abi(ctx, self.package_address, &self.blueprint_name) {
             Ok(a) => {
                 let blueprint = abi::Blueprint {
                     package_address: bech32_encoder.encode_package_address(&self.package_address),
                     blueprint_name: self.blueprint_name.clone(),
                     abi: a,
                 };
-                writeln!(
-                    out,
-                    "{}",
-                    serde_json::to_string_pretty(&blueprint).map_err(Error::JSONError)?
-                )
-                .map_err(Error::IOError)?;
+                let output = match self.bindings {
+                    None => serde_json::to_string_pretty(&blueprint).map_err(Error::JSONError)?,
+                    Some(Bindings::Typescript) => typescript_bindings(&blueprint),
+                    Some(Bindings::Rust) => rust_bindings(&blueprint),
+                };
+                writeln!(out, "{}", output).map_err(Error::IOError)?;
                 Ok(())
             }
             Err(e) => Err(e),
         }
     }
 }
+
+fn parameters(f: &abi::Fn) -> Vec<(String, Type)> {
+    match &f.input {
+        Type::Struct {
+            fields: Fields::Named { named },
+            ..
+        } => named.clone(),
+        _ => Vec::new(),
+    }
+}
+
+fn custom_type_name(type_id: u8) -> Option<String> {
+    ScryptoType::from_id(type_id).map(|t| t.name())
+}
+
+/// Generates a TypeScript class whose members return the manifest instruction for each call.
+fn typescript_bindings(blueprint: &abi::Blueprint) -> String {
+    let mut s = String::new();
+    writeln!(
+        s,
+        "// Generated by resim from blueprint {} of package {}",
+        blueprint.blueprint_name, blueprint.package_address
+    )
+    .unwrap();
+    writeln!(
+        s,
+        "export const PACKAGE_ADDRESS = \"{}\";\n",
+        blueprint.package_address
+    )
+    .unwrap();
+    writeln!(s, "export class {} {{", blueprint.blueprint_name).unwrap();
+    writeln!(
+        s,
+        "  constructor(public readonly componentAddress: string) {{}}"
+    )
+    .unwrap();
+
+    for f in &blueprint.abi.fns {
+        let parameters = parameters(f);
+        let signature: Vec<String> = parameters
+            .iter()
+            .map(|(name, ty)| format!("{}: {}", name, typescript_type(ty)))
+            .collect();
+        let args: String = parameters
+            .iter()
+            .map(|(name, ty)| format!(" {}", manifest_value(name, ty)))
+            .collect();
+        if f.mutability.is_some() {
+            writeln!(
+                s,
+                "\n  {}({}): string {{\n    return `CALL_METHOD ComponentAddress(\"${{this.componentAddress}}\") \"{}\"{};`;\n  }}",
+                f.ident,
+                signature.join(", "),
+                f.ident,
+                args
+            )
+            .unwrap();
+        } else {
+            writeln!(
+                s,
+                "\n  static {}({}): string {{\n    return `CALL_FUNCTION PackageAddress(\"${{PACKAGE_ADDRESS}}\") \"{}\" \"{}\"{};`;\n  }}",
+                f.ident,
+                signature.join(", "),
+                blueprint.blueprint_name,
+                f.ident,
+                args
+            )
+            .unwrap();
+        }
+    }
+    writeln!(s, "}}").unwrap();
+    s
+}
+
+fn typescript_type(ty: &Type) -> &'static str {
+    match ty {
+        Type::Bool => "boolean",
+        Type::I8 | Type::I16 | Type::I32 | Type::U8 | Type::U16 | Type::U32 => "number",
+        _ => "string",
+    }
+}
+
+/// Returns the manifest syntax of an argument, as a template literal placeholder.
+fn manifest_value(name: &str, ty: &Type) -> String {
+    let suffix = match ty {
+        Type::I8 => "i8",
+        Type::I16 => "i16",
+        Type::I32 => "i32",
+        Type::I64 => "i64",
+        Type::I128 => "i128",
+        Type::U8 => "u8",
+        Type::U16 => "u16",
+        Type::U32 => "u32",
+        Type::U64 => "u64",
+        Type::U128 => "u128",
+        _ => "",
+    };
+    match ty {
+        Type::String => format!("\"${{{}}}\"", name),
+        Type::Custom { type_id, .. } => match custom_type_name(*type_id) {
+            Some(type_name) => format!("{}(\"${{{}}}\")", type_name, name),
+            None => format!("${{{}}}", name),
+        },
+        _ => format!("${{{}}}{}", name, suffix),
+    }
+}
+
+/// Generates a Rust struct whose associated functions add each call to a manifest builder.
+fn rust_bindings(blueprint: &abi::Blueprint) -> String {
+    let mut s = String::new();
+    writeln!(
+        s,
+        "// Generated by resim from blueprint {} of package {}",
+        blueprint.blueprint_name, blueprint.package_address
+    )
+    .unwrap();
+    writeln!(s, "use scrypto::prelude::*;").unwrap();
+    writeln!(s, "use transaction::builder::ManifestBuilder;\n").unwrap();
+    writeln!(s, "pub struct {};\n", blueprint.blueprint_name).unwrap();
+    writeln!(s, "impl {} {{", blueprint.blueprint_name).unwrap();
+
+    for (i, f) in blueprint.abi.fns.iter().enumerate() {
+        let parameters = parameters(f);
+        let signature: String = parameters
+            .iter()
+            .map(|(name, ty)| format!(", {}: {}", name, rust_type(ty)))
+            .collect();
+        let args: Vec<&str> = parameters.iter().map(|(name, _)| name.as_str()).collect();
+        if i > 0 {
+            writeln!(s).unwrap();
+        }
+        if f.mutability.is_some() {
+            writeln!(
+                s,
+                "    pub fn {}(\n        builder: &mut ManifestBuilder,\n        component_address: ComponentAddress{},\n    ) -> &mut ManifestBuilder {{\n        builder.call_method(component_address, \"{}\", args!({}))\n    }}",
+                f.ident,
+                signature,
+                f.ident,
+                args.join(", ")
+            )
+            .unwrap();
+        } else {
+            writeln!(
+                s,
+                "    pub fn {}(\n        builder: &mut ManifestBuilder,\n        package_address: PackageAddress{},\n    ) -> &mut ManifestBuilder {{\n        builder.call_function(package_address, \"{}\", \"{}\", args!({}))\n    }}",
+                f.ident,
+                signature,
+                blueprint.blueprint_name,
+                f.ident,
+                args.join(", ")
+            )
+            .unwrap();
+        }
+    }
+    writeln!(s, "}}").unwrap();
+    s
+}
+
+fn rust_type(ty: &Type) -> String {
+    match ty {
+        Type::Unit => "()".to_owned(),
+        Type::Bool => "bool".to_owned(),
+        Type::I8 => "i8".to_owned(),
+        Type::I16 => "i16".to_owned(),
+        Type::I32 => "i32".to_owned(),
+        Type::I64 => "i64".to_owned(),
+        Type::I128 => "i128".to_owned(),
+        Type::U8 => "u8".to_owned(),
+        Type::U16 => "u16".to_owned(),
+        Type::U32 => "u32".to_owned(),
+        Type::U64 => "u64".to_owned(),
+        Type::U128 => "u128".to_owned(),
+        Type::String => "String".to_owned(),
+        Type::Option { value } => format!("Option<{}>", rust_type(value)),
+        Type::Array { element, length } => format!("[{}; {}]", rust_type(element), length),
+        Type::Tuple { elements } => format!(
+            "({})",
+            elements
+                .iter()
+                .map(rust_type)
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        Type::Struct { name, .. } | Type::Enum { name, .. } => name.clone(),
+        Type::Result { okay, error } => {
+            format!("Result<{}, {}>", rust_type(okay), rust_type(error))
+        }
+        Type::Vec { element } => format!("Vec<{}>", rust_type(element)),
+        Type::TreeSet { element } => format!("BTreeSet<{}>", rust_type(element)),
+        Type::TreeMap { key, value } => {
+            format!("BTreeMap<{}, {}>", rust_type(key), rust_type(value))
+        }
+        Type::HashSet { element } => format!("HashSet<{}>", rust_type(element)),
+        Type::HashMap { key, value } => {
+            format!("HashMap<{}, {}>", rust_type(key), rust_type(value))
+        }
+        Type::Custom { type_id, .. } => {
+            custom_type_name(*type_id).unwrap_or_else(|| format!("{:?}", ty))
+        }
+        Type::Any => "ScryptoValue".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typescript_bindings() {
+        let blueprint = abi::Blueprint {
+            package_address: "package_sim1qy".to_owned(),
+            blueprint_name: "Hello".to_owned(),
+            abi: abi::BlueprintAbi {
+                structure: Type::Unit,
+                fns: vec![abi::Fn {
+                    ident: "withdraw".to_owned(),
+                    mutability: Some(abi::SelfMutability::Mutable),
+                    input: Type::Struct {
+                        name: "Hello_withdraw_Input".to_owned(),
+                        fields: Fields::Named {
+                            named: vec![
+                                (
+                                    "amount".to_owned(),
+                                    Type::Custom {
+                                        type_id: ScryptoType::Decimal.id(),
+                                        generics: vec![],
+                                    },
+                                ),
+                                ("times".to_owned(), Type::U32),
+                            ],
+                        },
+                    },
+                    output: Type::Unit,
+                    export_name: "Hello_withdraw".to_owned(),
+                    roles: vec![],
+                }],
+            },
+        };
+
+        let bindings = typescript_bindings(&blueprint);
+
+        assert!(bindings.contains("withdraw(amount: string, times: number): string"));
+        assert!(bindings.contains("\"withdraw\" Decimal(\"${amount}\") ${times}u32;"));
+    }
+}
@@ -0,0 +1,73 @@
+use clap::Parser;
+use colored::*;
+use radix_engine::types::*;
+use scrypto::prelude::Expression;
+use transaction::builder::ManifestBuilder;
+
+use crate::resim::*;
+
+/// Get XRD from the system faucet
+#[derive(Parser, Debug)]
+pub struct Faucet {
+    /// The recipient component address, or the name of an account; the default account is used
+    /// if omitted
+    recipient: Option<String>,
+
+    /// The amount of XRD; the faucet gives away at most 1,000,000 XRD per epoch
+    #[clap(long, default_value = "1000")]
+    amount: Decimal,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
+}
+
+impl Faucet {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let recipient = match &self.recipient {
+            Some(recipient) => match ComponentAddress::from_str(recipient) {
+                Ok(component_address) => component_address,
+                Err(_) => get_account(ctx, &Some(recipient.clone()))?,
+            },
+            None => get_account(ctx, &None)?,
+        };
+        if self.amount.is_negative() {
+            return Err(Error::InvalidFaucetAmount(self.amount));
+        }
+
+        let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+            .lock_fee(100.into(), SYS_FAUCET_COMPONENT)
+            .call_method(SYS_FAUCET_COMPONENT, "free_xrd_amount", args!(self.amount))
+            .call_method(
+                recipient,
+                "deposit_batch",
+                args!(Expression::entire_worktop()),
+            )
+            .build();
+        handle_manifest(
+            ctx,
+            manifest,
+            &Some("".to_string()), // explicit empty signer public keys
+            &None,
+            &None,
+            &None,
+            self.trace,
+            false,
+            out,
+        )?;
+
+        if ctx.is_json_output() {
+            let bech32_encoder = Bech32Encoder::new(&NetworkDefinition::simulator());
+            return write_json(
+                out,
+                &serde_json::json!({
+                    "recipient": bech32_encoder.encode_component_address(&recipient),
+                    "amount": self.amount.to_string(),
+                }),
+            );
+        }
+        writeln!(out, "Received {} XRD!", self.amount.to_string().green())
+            .map_err(Error::IOError)?;
+        Ok(())
+    }
+}
@@ -11,10 +11,19 @@ use crate::resim::*;
 pub struct GenerateKeyPair {}
 
 impl GenerateKeyPair {
-    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
         let secret = rand::thread_rng().gen::<[u8; 32]>();
         let private_key = EcdsaSecp256k1PrivateKey::from_bytes(&secret).unwrap();
         let public_key = private_key.public_key();
+        if ctx.is_json_output() {
+            return write_json(
+                out,
+                &serde_json::json!({
+                    "public_key": public_key.to_string(),
+                    "private_key": hex::encode(private_key.to_bytes()),
+                }),
+            );
+        }
         writeln!(out, "Public key: {}", public_key.to_string().green()).map_err(Error::IOError)?;
         writeln!(
             out,
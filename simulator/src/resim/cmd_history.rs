@@ -0,0 +1,54 @@
+use clap::Parser;
+use colored::*;
+
+use crate::resim::*;
+
+/// Show the transactions executed against the local ledger, most recent first
+#[derive(Parser, Debug)]
+pub struct History {
+    /// The maximum number of transactions to show
+    #[clap(short, long)]
+    limit: Option<usize>,
+}
+
+impl History {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let mut entries = get_history(ctx)?;
+        entries.reverse();
+        if let Some(limit) = self.limit {
+            entries.truncate(limit);
+        }
+
+        if ctx.is_json_output() {
+            return write_json(out, &serde_json::Value::Array(entries));
+        }
+
+        for entry in &entries {
+            let receipt = &entry["receipt"];
+            writeln!(
+                out,
+                "{} {} ({} XRD)",
+                entry["hash"].as_str().unwrap_or_default().green().bold(),
+                receipt["status"].as_str().unwrap_or_default(),
+                receipt["fee_summary"]["total_fee"]
+                    .as_str()
+                    .unwrap_or_default()
+            )
+            .map_err(Error::IOError)?;
+            for kind in ["packages", "components", "resources"] {
+                if let Some(addresses) = receipt["new_entities"][kind].as_array() {
+                    for address in addresses {
+                        writeln!(
+                            out,
+                            "└─ New {}: {}",
+                            kind,
+                            address.as_str().unwrap_or_default()
+                        )
+                        .map_err(Error::IOError)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
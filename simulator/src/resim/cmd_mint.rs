@@ -30,14 +30,18 @@ pub struct Mint {
     #[clap(short, long)]
     signing_keys: Option<String>,
 
+    /// The name of the account to use instead of the default account
+    #[clap(long)]
+    account: Option<String>,
+
     /// Turn on tracing
     #[clap(short, long)]
     trace: bool,
 }
 
 impl Mint {
-    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
-        let default_account = get_default_account()?;
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let default_account = get_account(ctx, &self.account)?;
         let proofs = self.proofs.clone().unwrap_or_default();
 
         let mut manifest_builder = &mut ManifestBuilder::new(&NetworkDefinition::simulator());
@@ -60,8 +64,10 @@ impl Mint {
             )
             .build();
         handle_manifest(
+            ctx,
             manifest,
             &self.signing_keys,
+            &self.account,
             &self.network,
             &self.manifest,
             self.trace,
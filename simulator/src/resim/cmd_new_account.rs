@@ -16,83 +16,154 @@ pub struct NewAccount {
     #[clap(short, long)]
     manifest: Option<PathBuf>,
 
+    /// Create the account without XRD, instead of with a faucet payout
+    #[clap(long)]
+    empty: bool,
+
     /// Turn on tracing
     #[clap(short, long)]
     trace: bool,
 }
 
 impl NewAccount {
-    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
-        let secret = rand::thread_rng().gen::<[u8; 32]>();
-        let private_key = EcdsaSecp256k1PrivateKey::from_bytes(&secret).unwrap();
-        let public_key = private_key.public_key();
-        let auth_address = NonFungibleAddress::from_public_key(&public_key);
-        let withdraw_auth = rule!(require(auth_address));
-        let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
-            .lock_fee(100.into(), SYS_FAUCET_COMPONENT)
-            .call_method(SYS_FAUCET_COMPONENT, "free_xrd", args!())
-            .take_from_worktop(RADIX_TOKEN, |builder, bucket_id| {
-                builder.new_account_with_resource(&withdraw_auth, bucket_id)
-            })
-            .build();
-
-        let receipt = handle_manifest(
-            manifest,
-            &Some("".to_string()), // explicit empty signer public keys
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        create_account(
+            ctx,
+            &None,
             &self.network,
             &self.manifest,
+            self.empty,
             self.trace,
-            false,
             out,
-        )?;
+        )
+    }
+}
 
-        let bech32_encoder = Bech32Encoder::new(&NetworkDefinition::simulator());
+/// Creates an account and stores it under the given name, or the next free `accountN` name.
+pub fn create_account<O: std::io::Write>(
+    ctx: &CommandContext,
+    name: &Option<String>,
+    network: &Option<String>,
+    manifest_path: &Option<PathBuf>,
+    empty: bool,
+    trace: bool,
+    out: &mut O,
+) -> Result<(), Error> {
+    if let Some(name) = name {
+        if get_configs(ctx)?.accounts.contains_key(name) {
+            return Err(Error::AccountAlreadyExists(name.clone()));
+        }
+    }
 
-        if let Some(receipt) = receipt {
-            let account = receipt
-                .expect_commit()
-                .entity_changes
-                .new_component_addresses[0];
-            writeln!(out, "A new account has been created!").map_err(Error::IOError)?;
-            writeln!(
-                out,
-                "Account component address: {}",
-                bech32_encoder
-                    .encode_component_address(&account)
-                    .to_string()
-                    .green()
-            )
-            .map_err(Error::IOError)?;
-            writeln!(out, "Public key: {}", public_key.to_string().green())
-                .map_err(Error::IOError)?;
-            writeln!(
+    let secret = rand::thread_rng().gen::<[u8; 32]>();
+    let private_key = EcdsaSecp256k1PrivateKey::from_bytes(&secret).unwrap();
+    let public_key = private_key.public_key();
+    let auth_address = NonFungibleAddress::from_public_key(&public_key);
+    let withdraw_auth = rule!(require(auth_address));
+    let mut manifest_builder = ManifestBuilder::new(&NetworkDefinition::simulator());
+    manifest_builder.lock_fee(100.into(), SYS_FAUCET_COMPONENT);
+    if empty {
+        manifest_builder.call_function(ACCOUNT_PACKAGE, "Account", "new", args!(withdraw_auth));
+    } else {
+        manifest_builder
+            .call_method(SYS_FAUCET_COMPONENT, "free_xrd", args!())
+            .take_from_worktop(RADIX_TOKEN, |builder, bucket_id| {
+                builder.new_account_with_resource(&withdraw_auth, bucket_id)
+            });
+    }
+    let manifest = manifest_builder.build();
+
+    let receipt = handle_manifest(
+        ctx,
+        manifest,
+        &Some("".to_string()), // explicit empty signer public keys
+        &None,
+        network,
+        manifest_path,
+        trace,
+        false,
+        out,
+    )?;
+
+    let bech32_encoder = Bech32Encoder::new(&NetworkDefinition::simulator());
+
+    if let Some(receipt) = receipt {
+        let account = receipt
+            .expect_commit()
+            .entity_changes
+            .new_component_addresses[0];
+        let mut configs = get_configs(ctx)?;
+        let name = name.clone().unwrap_or_else(|| configs.next_account_name());
+        let is_first_account = configs.default_account.is_none();
+
+        configs.accounts.insert(
+            name.clone(),
+            AccountProfile {
+                component_address: account,
+                private_key: hex::encode(private_key.to_bytes()),
+            },
+        );
+        if is_first_account {
+            configs.default_account = Some(name.clone());
+        }
+        set_configs(ctx, &configs)?;
+
+        if ctx.is_json_output() {
+            return write_json(
                 out,
-                "Private key: {}",
-                hex::encode(private_key.to_bytes()).green()
-            )
-            .map_err(Error::IOError)?;
+                &serde_json::json!({
+                    "account_name": name,
+                    "component_address": bech32_encoder.encode_component_address(&account),
+                    "public_key": public_key.to_string(),
+                    "private_key": hex::encode(private_key.to_bytes()),
+                    "default": is_first_account,
+                    "receipt": receipt.to_json(&NetworkDefinition::simulator()),
+                }),
+            );
+        }
 
-            let mut configs = get_configs()?;
-            if configs.default_account.is_none() {
-                writeln!(
-                    out,
-                    "No configuration found on system. will use the above account as default."
-                )
-                .map_err(Error::IOError)?;
-                configs.default_account = Some((account, hex::encode(private_key.to_bytes())));
-                set_configs(&configs)?;
-            }
-        } else {
-            writeln!(out, "A manifest has been produced for the following key pair. To complete account creation, you will need to run the manifest!").map_err(Error::IOError)?;
-            writeln!(out, "Public key: {}", public_key.to_string().green())
-                .map_err(Error::IOError)?;
+        writeln!(out, "A new account has been created!").map_err(Error::IOError)?;
+        writeln!(out, "Account name: {}", name.green()).map_err(Error::IOError)?;
+        writeln!(
+            out,
+            "Account component address: {}",
+            bech32_encoder
+                .encode_component_address(&account)
+                .to_string()
+                .green()
+        )
+        .map_err(Error::IOError)?;
+        writeln!(out, "Public key: {}", public_key.to_string().green()).map_err(Error::IOError)?;
+        writeln!(
+            out,
+            "Private key: {}",
+            hex::encode(private_key.to_bytes()).green()
+        )
+        .map_err(Error::IOError)?;
+        if is_first_account {
             writeln!(
                 out,
-                "Private key: {}",
-                hex::encode(private_key.to_bytes()).green()
+                "No configuration found on system. will use the above account as default."
             )
             .map_err(Error::IOError)?;
         }
-        Ok(())
+    } else if ctx.is_json_output() {
+        write_json(
+            out,
+            &serde_json::json!({
+                "public_key": public_key.to_string(),
+                "private_key": hex::encode(private_key.to_bytes()),
+            }),
+        )?;
+    } else {
+        writeln!(out, "A manifest has been produced for the following key pair. To complete account creation, you will need to run the manifest!").map_err(Error::IOError)?;
+        writeln!(out, "Public key: {}", public_key.to_string().green()).map_err(Error::IOError)?;
+        writeln!(
+            out,
+            "Private key: {}",
+            hex::encode(private_key.to_bytes()).green()
+        )
+        .map_err(Error::IOError)?;
     }
+    Ok(())
 }
@@ -43,14 +43,18 @@ pub struct NewBadgeFixed {
     #[clap(short, long)]
     signing_keys: Option<String>,
 
+    /// The name of the account to use instead of the default account
+    #[clap(long)]
+    account: Option<String>,
+
     /// Turn on tracing
     #[clap(short, long)]
     trace: bool,
 }
 
 impl NewBadgeFixed {
-    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
-        let default_account = get_default_account()?;
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let default_account = get_account(ctx, &self.account)?;
         let mut metadata = HashMap::new();
         if let Some(symbol) = self.symbol.clone() {
             metadata.insert("symbol".to_string(), symbol);
@@ -78,8 +82,10 @@ impl NewBadgeFixed {
             )
             .build();
         handle_manifest(
+            ctx,
             manifest,
             &self.signing_keys,
+            &self.account,
             &self.network,
             &self.manifest,
             self.trace,
@@ -42,13 +42,17 @@ pub struct NewBadgeMutable {
     #[clap(short, long)]
     signing_keys: Option<String>,
 
+    /// The name of the account to use instead of the default account
+    #[clap(long)]
+    account: Option<String>,
+
     /// Turn on tracing
     #[clap(short, long)]
     trace: bool,
 }
 
 impl NewBadgeMutable {
-    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
         let mut metadata = HashMap::new();
         if let Some(symbol) = self.symbol.clone() {
             metadata.insert("symbol".to_string(), symbol);
@@ -71,8 +75,10 @@ impl NewBadgeMutable {
             .new_badge_mutable(metadata, self.minter_resource_address)
             .build();
         handle_manifest(
+            ctx,
             manifest,
             &self.signing_keys,
+            &self.account,
             &self.network,
             &self.manifest,
             self.trace,
@@ -0,0 +1,170 @@
+use clap::Parser;
+use radix_engine::types::*;
+use scrypto::prelude::Expression;
+use transaction::builder::ManifestBuilder;
+
+use crate::resim::*;
+
+/// Create a fungible token with custom divisibility, permissions and initial supply
+#[derive(Parser, Debug)]
+pub struct NewTokenAdvanced {
+    /// The initial supply, deposited into the account
+    #[clap(long)]
+    initial_supply: Option<Decimal>,
+
+    /// The divisibility, between 0 and 18
+    #[clap(long, default_value = "18")]
+    divisibility: u8,
+
+    /// The badge resource address required to mint
+    #[clap(long)]
+    mintable: Option<ResourceAddress>,
+
+    /// The badge resource address required to burn
+    #[clap(long)]
+    burnable: Option<ResourceAddress>,
+
+    /// The badge resource address required to withdraw, making the token restricted-transfer
+    #[clap(long)]
+    restrict_withdraw: Option<ResourceAddress>,
+
+    /// The badge resource address required to deposit
+    #[clap(long)]
+    restrict_deposit: Option<ResourceAddress>,
+
+    /// The badge resource address required to update metadata
+    #[clap(long)]
+    updatable_metadata: Option<ResourceAddress>,
+
+    /// The badge resource address allowed to change the permissions later; they are locked if not given
+    #[clap(long)]
+    mutable_permissions: Option<ResourceAddress>,
+
+    /// The symbol
+    #[clap(long)]
+    symbol: Option<String>,
+
+    /// The name
+    #[clap(long)]
+    name: Option<String>,
+
+    /// The description
+    #[clap(long)]
+    description: Option<String>,
+
+    /// The website URL
+    #[clap(long)]
+    url: Option<String>,
+
+    /// The ICON url
+    #[clap(long)]
+    icon_url: Option<String>,
+
+    /// The network to use when outputting manifest, [simulator | adapanet | nebunet | mainnet]
+    #[clap(short, long)]
+    network: Option<String>,
+
+    /// Output a transaction manifest without execution
+    #[clap(short, long)]
+    manifest: Option<PathBuf>,
+
+    /// The private keys used for signing, separated by comma
+    #[clap(short, long)]
+    signing_keys: Option<String>,
+
+    /// The name of the account to use instead of the default account
+    #[clap(long)]
+    account: Option<String>,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
+}
+
+impl NewTokenAdvanced {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let default_account = get_account(ctx, &self.account)?;
+        let mut metadata = HashMap::new();
+        if let Some(symbol) = self.symbol.clone() {
+            metadata.insert("symbol".to_string(), symbol);
+        }
+        if let Some(name) = self.name.clone() {
+            metadata.insert("name".to_string(), name);
+        }
+        if let Some(description) = self.description.clone() {
+            metadata.insert("description".to_string(), description);
+        }
+        if let Some(url) = self.url.clone() {
+            metadata.insert("url".to_string(), url);
+        }
+        if let Some(icon_url) = self.icon_url.clone() {
+            metadata.insert("icon_url".to_string(), icon_url);
+        };
+
+        let mutability = match self.mutable_permissions {
+            Some(badge) => MUTABLE(rule!(require(badge))),
+            None => LOCKED,
+        };
+        let mut access_rules = HashMap::new();
+        for (key, badge, default) in [
+            (ResourceMethodAuthKey::Mint, self.mintable, rule!(deny_all)),
+            (ResourceMethodAuthKey::Burn, self.burnable, rule!(deny_all)),
+            (
+                ResourceMethodAuthKey::Withdraw,
+                self.restrict_withdraw,
+                rule!(allow_all),
+            ),
+            (
+                ResourceMethodAuthKey::Deposit,
+                self.restrict_deposit,
+                rule!(allow_all),
+            ),
+            (
+                ResourceMethodAuthKey::UpdateMetadata,
+                self.updatable_metadata,
+                rule!(deny_all),
+            ),
+        ] {
+            let rule = match badge {
+                Some(badge) => rule!(require(badge)),
+                None => default,
+            };
+            access_rules.insert(key, (rule, mutability.clone()));
+        }
+        let mint_params = self
+            .initial_supply
+            .map(|amount| MintParams::Fungible { amount });
+
+        let mut builder = ManifestBuilder::new(&NetworkDefinition::simulator());
+        builder
+            .lock_fee(100.into(), SYS_FAUCET_COMPONENT)
+            .create_resource(
+                ResourceType::Fungible {
+                    divisibility: self.divisibility,
+                },
+                metadata,
+                access_rules,
+                mint_params,
+            );
+        if self.initial_supply.is_some() {
+            builder.call_method(
+                default_account,
+                "deposit_batch",
+                args!(Expression::entire_worktop()),
+            );
+        }
+        let manifest = builder.build();
+        handle_manifest(
+            ctx,
+            manifest,
+            &self.signing_keys,
+            &self.account,
+            &self.network,
+            &self.manifest,
+            self.trace,
+            true,
+            out,
+        )
+        .map(|_| ())
+    }
+}
@@ -1,11 +1,13 @@
 use clap::Parser;
 use colored::*;
 use radix_engine::engine::Substate;
-use radix_engine::ledger::{OutputValue, ReadableSubstateStore, WriteableSubstateStore};
+use radix_engine::ledger::{OutputId, OutputValue, ReadableSubstateStore};
+use radix_engine::state_manager::StateDiff;
 use radix_engine::types::*;
 use std::ffi::OsStr;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 use transaction::builder::ManifestBuilder;
 
 use crate::resim::*;
@@ -29,71 +31,249 @@ pub struct Publish {
     #[clap(short, long)]
     manifest: Option<PathBuf>,
 
+    /// The name of the account to use instead of the default account
+    #[clap(long)]
+    account: Option<String>,
+
     /// Turn on tracing
     #[clap(short, long)]
     trace: bool,
+
+    /// Rebuild and republish the package at the same address whenever its source changes
+    #[clap(long)]
+    watch: bool,
 }
 
 impl Publish {
-    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
-        // Load wasm code
-        let code_path = if self.path.extension() != Some(OsStr::new("wasm")) {
-            build_package(&self.path, false).map_err(Error::BuildError)?
-        } else {
-            self.path.clone()
-        };
-        let abi_path = code_path.with_extension("abi");
-
-        let code = fs::read(&code_path).map_err(Error::IOError)?;
-        let abi = scrypto_decode(&fs::read(&abi_path).map_err(Error::IOError)?)
-            .map_err(Error::DataError)?;
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let (code, abi) = load_package(&self.path)?;
 
-        if let Some(package_address) = self.package_address.clone() {
-            let substate_id = SubstateId::Package(package_address);
-
-            let mut substate_store = RadixEngineDB::with_bootstrap(get_data_dir()?);
-
-            let previous_version = substate_store
-                .get_substate(&substate_id)
-                .map(|output| output.version);
-
-            let validated_package = Package::new(code, abi).map_err(Error::InvalidPackage)?;
-            let output_value = OutputValue {
-                substate: Substate::Package(validated_package),
-                version: previous_version.unwrap_or(0),
-            };
-
-            // Overwrite package
-            // TODO: implement real package overwrite
-            substate_store.put_substate(SubstateId::Package(package_address), output_value);
-            writeln!(out, "Package updated!").map_err(Error::IOError)?;
+        let package_address = if let Some(package_address) = self.package_address.clone() {
+            overwrite_package(ctx, package_address, code, abi.clone())?;
+            if ctx.is_json_output() {
+                let bech32_encoder = Bech32Encoder::new(&NetworkDefinition::simulator());
+                write_json(
+                    out,
+                    &serde_json::json!({
+                        "package_address": bech32_encoder.encode_package_address(&package_address),
+                    }),
+                )?;
+            } else {
+                writeln!(out, "Package updated!").map_err(Error::IOError)?;
+            }
+            package_address
         } else {
             let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
                 .lock_fee(100.into(), SYS_FAUCET_COMPONENT)
-                .publish_package(code, abi)
+                .publish_package(code, abi.clone())
                 .build();
 
             let receipt = handle_manifest(
+                ctx,
                 manifest,
                 &None,
+                &self.account,
                 &self.network,
                 &self.manifest,
                 self.trace,
                 false,
                 out,
             )?;
-            if let Some(receipt) = receipt {
+            let receipt = match receipt {
+                Some(receipt) => receipt,
+                // Only a manifest was produced, so there is nothing to watch
+                None => return Ok(()),
+            };
+            let package_address = receipt.expect_commit().entity_changes.new_package_addresses[0];
+            if ctx.is_json_output() {
+                let bech32_encoder = Bech32Encoder::new(&NetworkDefinition::simulator());
+                write_json(
+                    out,
+                    &serde_json::json!({
+                        "package_address": bech32_encoder.encode_package_address(&package_address),
+                        "receipt": receipt.to_json(&NetworkDefinition::simulator()),
+                    }),
+                )?;
+            } else {
                 writeln!(
                     out,
                     "Success! New Package: {}",
-                    receipt.expect_commit().entity_changes.new_package_addresses[0]
-                        .to_string()
-                        .green()
+                    package_address.to_string().green()
                 )
                 .map_err(Error::IOError)?;
             }
+            package_address
+        };
+
+        if self.watch {
+            self.watch(ctx, package_address, abi, out)?;
         }
 
         Ok(())
     }
+
+    /// Polls the package sources, overwriting the package on every change until interrupted.
+    fn watch<O: std::io::Write>(
+        &self,
+        ctx: &CommandContext,
+        package_address: PackageAddress,
+        mut abi: HashMap<String, BlueprintAbi>,
+        out: &mut O,
+    ) -> Result<(), Error> {
+        writeln!(out, "Watching {} for changes...", self.path.display()).map_err(Error::IOError)?;
+        let mut last_seen = last_modified(&self.path).map_err(Error::IOError)?;
+        loop {
+            std::thread::sleep(Duration::from_secs(1));
+            let modified = match last_modified(&self.path) {
+                Ok(modified) => modified,
+                // The sources may be briefly missing while an editor replaces them
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(Error::IOError(e)),
+            };
+            if modified <= last_seen {
+                continue;
+            }
+            last_seen = modified;
+
+            // Keep watching when the new code does not build
+            let (code, new_abi) = match load_package(&self.path) {
+                Ok(package) => package,
+                Err(e) => {
+                    writeln!(out, "{}: {:?}", "Build failed".red(), e).map_err(Error::IOError)?;
+                    continue;
+                }
+            };
+            if let Err(e) = overwrite_package(ctx, package_address, code, new_abi.clone()) {
+                writeln!(out, "{}: {:?}", "Publish failed".red(), e).map_err(Error::IOError)?;
+                continue;
+            }
+            writeln!(out, "Package updated!").map_err(Error::IOError)?;
+            for line in diff_abi(&abi, &new_abi) {
+                writeln!(out, "{}", line).map_err(Error::IOError)?;
+            }
+            abi = new_abi;
+        }
+    }
+}
+
+/// Loads the code and ABI of a package, building it first unless the path is a .wasm file.
+pub fn load_package(path: &Path) -> Result<(Vec<u8>, HashMap<String, BlueprintAbi>), Error> {
+    let code_path = if path.extension() != Some(OsStr::new("wasm")) {
+        build_package(path, false).map_err(Error::BuildError)?
+    } else {
+        path.to_owned()
+    };
+    let abi_path = code_path.with_extension("abi");
+
+    let code = fs::read(&code_path).map_err(Error::IOError)?;
+    let abi =
+        scrypto_decode(&fs::read(&abi_path).map_err(Error::IOError)?).map_err(Error::DataError)?;
+    Ok((code, abi))
+}
+
+/// Overwrites the package at the given address, bypassing the transaction layer but committing
+/// the change like a transaction would, so the substate version is bumped and the commit logged.
+fn overwrite_package(
+    ctx: &CommandContext,
+    package_address: PackageAddress,
+    code: Vec<u8>,
+    abi: HashMap<String, BlueprintAbi>,
+) -> Result<(), Error> {
+    let substate_id = SubstateId::Package(package_address);
+
+    let mut substate_store = open_ledger(ctx)?;
+
+    let validated_package = Package::new(code, abi).map_err(Error::InvalidPackage)?;
+    let mut state_diff = StateDiff::new();
+    let version = match substate_store.get_substate(&substate_id) {
+        Some(previous) => {
+            state_diff.down_substates.push(OutputId {
+                substate_id: substate_id.clone(),
+                substate_hash: hash(scrypto_encode(&previous.substate)),
+                version: previous.version,
+            });
+            previous.version + 1
+        }
+        None => 0,
+    };
+    state_diff.up_substates.insert(
+        substate_id,
+        OutputValue {
+            substate: Substate::Package(validated_package),
+            version,
+        },
+    );
+
+    substate_store.commit_state_diff(&state_diff);
+    Ok(())
+}
+
+/// Returns the latest modification time of the files under a path, ignoring build outputs.
+fn last_modified(path: &Path) -> std::io::Result<SystemTime> {
+    let metadata = fs::metadata(path)?;
+    let mut latest = metadata.modified()?;
+    if metadata.is_dir() {
+        for entry in fs::read_dir(path)? {
+            let entry = entry?;
+            if entry.file_name() == "target" {
+                continue;
+            }
+            match last_modified(&entry.path()) {
+                Ok(modified) => latest = latest.max(modified),
+                // Skip files removed since the directory was listed, such as editor swap files
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    Ok(latest)
+}
+
+/// Lists the blueprints and functions added (+), removed (-) or changed (~) between two ABIs.
+fn diff_abi(
+    old: &HashMap<String, BlueprintAbi>,
+    new: &HashMap<String, BlueprintAbi>,
+) -> Vec<String> {
+    let blueprint_names: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    let mut lines = Vec::new();
+    for blueprint_name in blueprint_names {
+        match (old.get(blueprint_name), new.get(blueprint_name)) {
+            (None, Some(_)) => lines.push(format!("{} {}", "+".green(), blueprint_name)),
+            (Some(_), None) => lines.push(format!("{} {}", "-".red(), blueprint_name)),
+            (Some(old_abi), Some(new_abi)) => {
+                for old_fn in &old_abi.fns {
+                    match new_abi.get_fn_abi(&old_fn.ident) {
+                        None => lines.push(format!(
+                            "{} {}::{}",
+                            "-".red(),
+                            blueprint_name,
+                            old_fn.ident
+                        )),
+                        Some(new_fn) if new_fn != old_fn => lines.push(format!(
+                            "{} {}::{}",
+                            "~".yellow(),
+                            blueprint_name,
+                            old_fn.ident
+                        )),
+                        _ => {}
+                    }
+                }
+                for new_fn in &new_abi.fns {
+                    if old_abi.get_fn_abi(&new_fn.ident).is_none() {
+                        lines.push(format!(
+                            "{} {}::{}",
+                            "+".green(),
+                            blueprint_name,
+                            new_fn.ident
+                        ));
+                    }
+                }
+            }
+            (None, None) => {}
+        }
+    }
+    if lines.is_empty() {
+        lines.push("ABI unchanged".to_owned());
+    }
+    lines
 }
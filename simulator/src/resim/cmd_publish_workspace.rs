@@ -0,0 +1,239 @@
+use cargo_toml::Manifest;
+use clap::Parser;
+use colored::*;
+use radix_engine::types::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use transaction::builder::ManifestBuilder;
+
+use crate::resim::*;
+use crate::utils::*;
+
+/// Publish the Scrypto packages of a Cargo workspace in dependency order, recording their
+/// addresses by package name
+#[derive(Parser, Debug)]
+pub struct PublishWorkspace {
+    /// The path to the Cargo workspace
+    path: PathBuf,
+
+    /// The name of the account to use instead of the default account
+    #[clap(long)]
+    account: Option<String>,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
+}
+
+/// A Scrypto package found in a workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspacePackage {
+    pub name: String,
+    pub path: PathBuf,
+    /// The names of the workspace packages this package depends on.
+    pub dependencies: Vec<String>,
+}
+
+impl PublishWorkspace {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let packages = sort_by_dependencies(find_workspace_packages(&self.path)?)?;
+
+        let bech32_encoder = Bech32Encoder::new(&NetworkDefinition::simulator());
+        let mut published = Vec::new();
+        for package in packages {
+            let (code, abi) = load_package(&package.path)?;
+            let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+                .lock_fee(100.into(), SYS_FAUCET_COMPONENT)
+                .publish_package(code, abi)
+                .build();
+            let receipt = handle_manifest(
+                ctx,
+                manifest,
+                &None,
+                &self.account,
+                &None,
+                &None,
+                self.trace,
+                false,
+                out,
+            )?
+            .unwrap();
+            let package_address = receipt.expect_commit().entity_changes.new_package_addresses[0];
+
+            // Record each address as soon as it is known, so a failure leaves earlier ones usable
+            let mut configs = get_configs(ctx)?;
+            configs
+                .packages
+                .insert(package.name.clone(), package_address);
+            set_configs(ctx, &configs)?;
+
+            if !ctx.is_json_output() {
+                writeln!(
+                    out,
+                    "{}: {}",
+                    package.name.green().bold(),
+                    bech32_encoder.encode_package_address(&package_address)
+                )
+                .map_err(Error::IOError)?;
+            }
+            published.push((package.name, package_address));
+        }
+
+        if ctx.is_json_output() {
+            let packages: serde_json::Map<String, serde_json::Value> = published
+                .iter()
+                .map(|(name, package_address)| {
+                    (
+                        name.clone(),
+                        bech32_encoder
+                            .encode_package_address(package_address)
+                            .into(),
+                    )
+                })
+                .collect();
+            return write_json(out, &serde_json::Value::Object(packages));
+        }
+        Ok(())
+    }
+}
+
+/// Finds the members of a workspace that depend on Scrypto, together with their path
+/// dependencies on each other.
+pub fn find_workspace_packages(path: &Path) -> Result<Vec<WorkspacePackage>, Error> {
+    let manifest = read_manifest(&path.join("Cargo.toml"))?;
+    let members = match manifest.workspace {
+        Some(workspace) => workspace.members,
+        None => vec![".".to_owned()],
+    };
+
+    // Expand trailing `/*` globs, the only form used by Scrypto workspaces
+    let mut member_dirs = Vec::new();
+    for member in members {
+        match member.strip_suffix("/*") {
+            Some(parent) => {
+                let mut dirs = Vec::new();
+                for entry in fs::read_dir(path.join(parent)).map_err(Error::IOError)? {
+                    let entry = entry.map_err(Error::IOError)?;
+                    if entry.path().join("Cargo.toml").exists() {
+                        dirs.push(entry.path());
+                    }
+                }
+                dirs.sort();
+                member_dirs.extend(dirs);
+            }
+            None => member_dirs.push(path.join(member)),
+        }
+    }
+
+    let mut members = Vec::new();
+    for dir in member_dirs {
+        let dir = dir.canonicalize().map_err(Error::IOError)?;
+        let manifest = read_manifest(&dir.join("Cargo.toml"))?;
+        if !manifest.dependencies.contains_key("scrypto") {
+            continue;
+        }
+        let name = manifest
+            .package
+            .map(|p| p.name)
+            .ok_or(Error::BuildError(BuildError::MissingPackageName))?;
+        let mut dependency_dirs = Vec::new();
+        for dependency in manifest.dependencies.values() {
+            if let Some(dependency_path) = dependency.detail().and_then(|d| d.path.as_ref()) {
+                if let Ok(dependency_dir) = dir.join(dependency_path).canonicalize() {
+                    dependency_dirs.push(dependency_dir);
+                }
+            }
+        }
+        members.push((name, dir, dependency_dirs));
+    }
+
+    Ok(members
+        .iter()
+        .map(|(name, dir, dependency_dirs)| WorkspacePackage {
+            name: name.clone(),
+            path: dir.clone(),
+            dependencies: members
+                .iter()
+                .filter(|(_, other_dir, _)| dependency_dirs.contains(other_dir))
+                .map(|(other_name, _, _)| other_name.clone())
+                .collect(),
+        })
+        .collect())
+}
+
+fn read_manifest(path: &Path) -> Result<Manifest, Error> {
+    Manifest::from_path(path).map_err(|_| Error::BuildError(BuildError::InvalidManifestFile))
+}
+
+/// Orders packages so that each comes after the packages it depends on.
+pub fn sort_by_dependencies(
+    packages: Vec<WorkspacePackage>,
+) -> Result<Vec<WorkspacePackage>, Error> {
+    fn visit(
+        index: usize,
+        packages: &[WorkspacePackage],
+        visiting: &mut Vec<usize>,
+        visited: &mut Vec<bool>,
+        sorted: &mut Vec<WorkspacePackage>,
+    ) -> Result<(), Error> {
+        if visited[index] {
+            return Ok(());
+        }
+        if visiting.contains(&index) {
+            return Err(Error::CyclicPackageDependencies(
+                packages[index].name.clone(),
+            ));
+        }
+        visiting.push(index);
+        for dependency in &packages[index].dependencies {
+            if let Some(i) = packages.iter().position(|p| &p.name == dependency) {
+                visit(i, packages, visiting, visited, sorted)?;
+            }
+        }
+        visiting.pop();
+        visited[index] = true;
+        sorted.push(packages[index].clone());
+        Ok(())
+    }
+
+    let mut visiting = Vec::new();
+    let mut visited = vec![false; packages.len()];
+    let mut sorted = Vec::new();
+    for index in 0..packages.len() {
+        visit(index, &packages, &mut visiting, &mut visited, &mut sorted)?;
+    }
+    Ok(sorted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(name: &str, dependencies: &[&str]) -> WorkspacePackage {
+        WorkspacePackage {
+            name: name.to_owned(),
+            path: PathBuf::from(name),
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_sort_by_dependencies() {
+        let sorted = sort_by_dependencies(vec![
+            package("dex", &["token", "oracle"]),
+            package("oracle", &["token"]),
+            package("token", &[]),
+        ])
+        .unwrap();
+
+        let names: Vec<&str> = sorted.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["token", "oracle", "dex"]);
+    }
+
+    #[test]
+    fn test_sort_by_dependencies_with_cycle() {
+        let result = sort_by_dependencies(vec![package("a", &["b"]), package("b", &["a"])]);
+
+        assert!(matches!(result, Err(Error::CyclicPackageDependencies(_))));
+    }
+}
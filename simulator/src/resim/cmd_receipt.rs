@@ -0,0 +1,32 @@
+use clap::Parser;
+use colored::*;
+
+use crate::resim::*;
+
+/// Show the manifest and receipt of a transaction from the history
+#[derive(Parser, Debug)]
+pub struct Receipt {
+    /// The transaction hash
+    hash: String,
+}
+
+impl Receipt {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let entry = get_history(ctx)?
+            .into_iter()
+            .find(|entry| entry["hash"].as_str() == Some(self.hash.as_str()))
+            .ok_or_else(|| Error::TransactionNotFound(self.hash.clone()))?;
+
+        if ctx.is_json_output() {
+            return write_json(out, &entry);
+        }
+
+        writeln!(out, "{} {}", "Transaction Hash:".green().bold(), self.hash)
+            .map_err(Error::IOError)?;
+        writeln!(out, "{}", "Manifest:".green().bold()).map_err(Error::IOError)?;
+        writeln!(out, "{}", entry["manifest"].as_str().unwrap_or_default())
+            .map_err(Error::IOError)?;
+        writeln!(out, "{}", "Receipt:".green().bold()).map_err(Error::IOError)?;
+        write_json(out, &entry["receipt"])
+    }
+}
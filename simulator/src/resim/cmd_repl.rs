@@ -0,0 +1,169 @@
+use clap::Parser;
+use colored::*;
+use radix_engine::types::*;
+use std::io::BufRead;
+
+use crate::resim::*;
+
+/// Start an interactive session that runs resim commands and builds manifests instruction by
+/// instruction, keeping the ledger open in between
+///
+/// Line editing, history and tab completion are left to the terminal; run it under a wrapper
+/// like `rlwrap resim repl` to get them.
+#[derive(Parser, Debug)]
+pub struct Repl {
+    /// The name of the account to run built manifests with, instead of the default account
+    #[clap(long)]
+    account: Option<String>,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
+}
+
+const REPL_HELP: &str = "\
+Enter any resim command without the leading `resim`, or:
+  manifest add <instruction>   Append an instruction to the manifest being built
+  manifest show                Print the manifest being built
+  manifest run                 Execute the manifest being built, then clear it
+  manifest clear               Discard the manifest being built
+  help                         Print this help
+  exit                         End the session";
+
+impl Repl {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        writeln!(out, "{}", REPL_HELP).map_err(Error::IOError)?;
+
+        let stdin = std::io::stdin();
+        let mut manifest = String::new();
+        loop {
+            write!(out, "{} ", "resim>".green().bold()).map_err(Error::IOError)?;
+            out.flush().map_err(Error::IOError)?;
+            let mut line = String::new();
+            if stdin.lock().read_line(&mut line).map_err(Error::IOError)? == 0 {
+                // End of input
+                return Ok(());
+            }
+
+            let arguments = split_arguments(&line);
+            let result = match arguments
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<&str>>()
+                .as_slice()
+            {
+                [] => Ok(()),
+                ["exit"] | ["quit"] => return Ok(()),
+                ["help"] => writeln!(out, "{}", REPL_HELP).map_err(Error::IOError),
+                ["manifest", "add", ..] => {
+                    let instruction = line
+                        .trim()
+                        .trim_start_matches("manifest")
+                        .trim_start()
+                        .trim_start_matches("add")
+                        .trim();
+                    manifest.push_str(instruction);
+                    if !instruction.ends_with(';') {
+                        manifest.push(';');
+                    }
+                    manifest.push('\n');
+                    Ok(())
+                }
+                ["manifest", "show"] => write!(out, "{}", manifest).map_err(Error::IOError),
+                ["manifest", "clear"] => {
+                    manifest.clear();
+                    Ok(())
+                }
+                ["manifest", "run"] => {
+                    let result = self.run_manifest(ctx, &manifest, out);
+                    if result.is_ok() {
+                        manifest.clear();
+                    }
+                    result
+                }
+                _ => match ResimCli::try_parse_from(
+                    std::iter::once("resim".to_owned()).chain(arguments.iter().cloned()),
+                ) {
+                    Ok(cli) => run_cli(cli, ctx, out),
+                    Err(e) => write!(out, "{}", e).map_err(Error::IOError),
+                },
+            };
+            // Keep the session alive on command failures
+            if let Err(e) = result {
+                writeln!(out, "{}: {:?}", "Error".red(), e).map_err(Error::IOError)?;
+            }
+        }
+    }
+
+    fn run_manifest<O: std::io::Write>(
+        &self,
+        ctx: &CommandContext,
+        manifest: &str,
+        out: &mut O,
+    ) -> Result<(), Error> {
+        let pre_processed_manifest = Run::pre_process_manifest(manifest, &get_configs(ctx)?);
+        let compiled_manifest = transaction::manifest::compile(
+            &pre_processed_manifest,
+            &NetworkDefinition::simulator(),
+            Vec::new(),
+        )
+        .map_err(Error::CompileError)?;
+        validate_arguments(ctx, &compiled_manifest)?;
+        let receipt = execute_manifest(ctx, compiled_manifest, &None, &self.account, self.trace)?;
+        process_receipt(ctx, receipt, true, out).map(|_| ())
+    }
+}
+
+/// Splits a command line into arguments at whitespace, keeping quoted text together.
+pub fn split_arguments(line: &str) -> Vec<String> {
+    let mut arguments = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote: Option<char> = None;
+    for c in line.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.get_or_insert_with(String::new).push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                current.get_or_insert_with(String::new);
+            }
+            None if c.is_whitespace() => arguments.extend(current.take()),
+            None => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    arguments.extend(current);
+    arguments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_arguments() {
+        assert_eq!(
+            split_arguments("call-method  component_sim1 greet \"hello world\" ''\n"),
+            vec!["call-method", "component_sim1", "greet", "hello world", ""]
+        );
+    }
+
+    #[test]
+    fn test_command_line_options_override_context() {
+        let ctx = CommandContext {
+            output: OutputFormat::Json,
+            fee_budget: Some(Decimal::from(10)),
+            env: Some("outer".to_owned()),
+            ..Default::default()
+        };
+
+        let cli = ResimCli::try_parse_from(["resim", "--env", "inner", "show-configs"]).unwrap();
+        let inner = ctx.for_cli(&cli);
+        assert_eq!(Some("inner".to_owned()), inner.env);
+        assert_eq!(Some(Decimal::from(10)), inner.fee_budget);
+        assert_eq!(OutputFormat::Text, inner.output);
+
+        // The options of a command line don't stick to the next one
+        let cli = ResimCli::try_parse_from(["resim", "show-configs"]).unwrap();
+        assert_eq!(Some("outer".to_owned()), ctx.for_cli(&cli).env);
+    }
+}
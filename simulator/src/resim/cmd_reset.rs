@@ -8,8 +8,9 @@ use crate::resim::*;
 pub struct Reset {}
 
 impl Reset {
-    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
-        let dir = get_data_dir()?;
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let dir = get_data_dir(ctx)?;
+        ctx.session.close_ledger();
         remove_dir_all(dir).map_err(Error::IOError)?;
         writeln!(out, "Data directory cleared.").map_err(Error::IOError)?;
         Ok(())
@@ -1,8 +1,10 @@
 use clap::Parser;
+use radix_engine::types::*;
 use regex::{Captures, Regex};
 use scrypto::core::NetworkDefinition;
 use std::env;
 use std::path::PathBuf;
+use transaction::model::{Instruction, MethodIdentifier, TransactionManifest};
 
 use crate::resim::*;
 
@@ -24,23 +26,47 @@ pub struct Run {
     #[clap(short, long)]
     signing_keys: Option<String>,
 
+    /// The name of the account to use instead of the default account
+    #[clap(long)]
+    account: Option<String>,
+
+    /// Write the JSON receipt to the given file
+    #[clap(short, long)]
+    receipt: Option<PathBuf>,
+
     /// Turn on tracing
     #[clap(short, long)]
     trace: bool,
 }
 
 impl Run {
-    pub fn pre_process_manifest(manifest: &str) -> String {
+    /// Substitutes `${name}` with the environment variable of that name or, failing that,
+    /// the address of the account with that name.
+    pub fn pre_process_manifest(manifest: &str, configs: &Configs) -> String {
+        let bech32_encoder = Bech32Encoder::new(&NetworkDefinition::simulator());
         let re = Regex::new(r"\$\{(.+?)\}").unwrap();
         re.replace_all(manifest, |caps: &Captures| {
-            env::var(&caps[1].trim()).unwrap_or_default()
+            let name = caps[1].trim();
+            env::var(name)
+                .ok()
+                .or_else(|| {
+                    configs.accounts.get(name).map(|profile| {
+                        bech32_encoder.encode_component_address(&profile.component_address)
+                    })
+                })
+                .or_else(|| {
+                    configs.packages.get(name).map(|package_address| {
+                        bech32_encoder.encode_package_address(package_address)
+                    })
+                })
+                .unwrap_or_default()
         })
         .into()
     }
 
-    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
         let manifest = std::fs::read_to_string(&self.path).map_err(Error::IOError)?;
-        let pre_processed_manifest = Self::pre_process_manifest(&manifest);
+        let pre_processed_manifest = Self::pre_process_manifest(&manifest, &get_configs(ctx)?);
         let network = match &self.network {
             Some(n) => NetworkDefinition::from_str(&n).map_err(Error::ParseNetworkError)?,
             None => NetworkDefinition::simulator(),
@@ -54,19 +80,78 @@ impl Run {
         let compiled_manifest =
             transaction::manifest::compile(&pre_processed_manifest, &network, blobs)
                 .map_err(Error::CompileError)?;
-        handle_manifest(
+        validate_arguments(ctx, &compiled_manifest)?;
+        let receipt = execute_manifest(
+            ctx,
             compiled_manifest,
             &self.signing_keys,
-            &self.network,
-            &None,
+            &self.account,
             self.trace,
-            true,
-            out,
-        )
-        .map(|_| ())
+        )?;
+        if let Some(path) = &self.receipt {
+            let json = receipt.to_json(&NetworkDefinition::simulator());
+            std::fs::write(
+                path,
+                serde_json::to_string_pretty(&json).map_err(Error::JSONError)?,
+            )
+            .map_err(Error::IOError)?;
+        }
+        process_receipt(ctx, receipt, true, out).map(|_| ())
     }
 }
 
+/// Checks the arguments of blueprint function and component method calls against the ABIs on
+/// ledger, so mistakes in hand-written arguments are reported before execution.
+///
+/// Calls to unknown blueprints, components or functions are left for the engine to reject, as
+/// are arguments holding expressions, which are only resolved at execution time.
+pub fn validate_arguments(
+    ctx: &CommandContext,
+    manifest: &TransactionManifest,
+) -> Result<(), Error> {
+    let ledger = open_ledger_read_only(ctx)?;
+    for instruction in &manifest.instructions {
+        let (abi, ident, args) = match instruction {
+            Instruction::CallFunction {
+                fn_identifier:
+                    FnIdentifier::Scrypto {
+                        package_address,
+                        blueprint_name,
+                        ident,
+                    },
+                args,
+            } => (
+                radix_engine::model::export_abi(&*ledger, *package_address, blueprint_name),
+                ident,
+                args,
+            ),
+            Instruction::CallMethod {
+                method_identifier:
+                    MethodIdentifier::Scrypto {
+                        component_address,
+                        ident,
+                    },
+                args,
+            } => (
+                radix_engine::model::export_abi_by_component(&*ledger, *component_address),
+                ident,
+                args,
+            ),
+            _ => continue,
+        };
+        let fn_abi = match abi.as_ref().ok().and_then(|abi| abi.get_fn_abi(ident)) {
+            Some(fn_abi) => fn_abi,
+            None => continue,
+        };
+        if matches!(ScryptoValue::from_slice(args), Ok(value) if !value.expressions.is_empty()) {
+            continue;
+        }
+        sbor::validate(args, &fn_abi.input)
+            .map_err(|e| Error::InvalidArguments(ident.clone(), e))?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -87,8 +172,26 @@ mod tests {
             || {
                 let manifest = r#"CALL_METHOD ComponentAddress("${  faucet  }") "free_xrd";\nTAKE_FROM_WORKTOP ResourceAddress("${xrd}") Bucket("bucket1");\n"#;
                 let after = r#"CALL_METHOD ComponentAddress("system_sim1qsqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqpql4sktx") "free_xrd";\nTAKE_FROM_WORKTOP ResourceAddress("resource_sim1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqzqu57yag") Bucket("bucket1");\n"#;
-                assert_eq!(Run::pre_process_manifest(manifest), after);
+                assert_eq!(
+                    Run::pre_process_manifest(manifest, &Configs::default()),
+                    after
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_pre_process_manifest_with_account_name() {
+        let mut configs = Configs::default();
+        configs.accounts.insert(
+            "alice".to_owned(),
+            AccountProfile {
+                component_address: SYS_FAUCET_COMPONENT,
+                private_key: String::new(),
             },
         );
+        let manifest = r#"CALL_METHOD ComponentAddress("${alice}") "free_xrd";"#;
+        let after = r#"CALL_METHOD ComponentAddress("system_sim1qsqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqs9fh54n") "free_xrd";"#;
+        assert_eq!(Run::pre_process_manifest(manifest, &configs), after);
     }
 }
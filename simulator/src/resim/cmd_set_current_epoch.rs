@@ -1,10 +1,9 @@
 use clap::Parser;
 use radix_engine::constants::*;
 use radix_engine::engine::Track;
-use radix_engine::engine::{ExecutionTrace, Kernel, SystemApi};
+use radix_engine::engine::{ExecutionTrace, Kernel, NativeFnRegistry, SystemApi};
 use radix_engine::fee::{FeeTable, SystemLoanFeeReserve};
 use radix_engine::types::*;
-use radix_engine_stores::rocks_db::RadixEngineDB;
 use transaction::model::AuthModule;
 
 use crate::resim::*;
@@ -17,32 +16,36 @@ pub struct SetCurrentEpoch {
 }
 
 impl SetCurrentEpoch {
-    pub fn run<O: std::io::Write>(&self, _out: &mut O) -> Result<(), Error> {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, _out: &mut O) -> Result<(), Error> {
         // TODO: can we construct a proper transaction to do the following?
 
-        let tx_hash = hash(get_nonce()?.to_string());
+        let tx_hash = hash(get_nonce(ctx)?.to_string());
         let blobs = HashMap::new();
-        let mut substate_store = RadixEngineDB::with_bootstrap(get_data_dir()?);
-        let mut wasm_engine = DefaultWasmEngine::new();
-        let mut wasm_instrumenter = WasmInstrumenter::new();
+        let mut substate_store = open_ledger(ctx)?;
+        let mut wasm_engine = ctx.session.wasm_engine.borrow_mut();
+        let mut wasm_instrumenter = ctx.session.wasm_instrumenter.borrow_mut();
         let mut track = Track::new(
-            &substate_store,
+            &*substate_store,
             SystemLoanFeeReserve::default(),
             FeeTable::new(),
         );
         let mut execution_trace = ExecutionTrace::new();
+        let mut package_module_cache = PackageModuleCache::new();
 
         let mut kernel = Kernel::new(
             tx_hash,
             vec![AuthModule::validator_role_nf_address()],
             &blobs,
             DEFAULT_MAX_CALL_DEPTH,
+            DEFAULT_MAX_HEAP_SIZE,
             &mut track,
-            &mut wasm_engine,
+            &mut *wasm_engine,
             &mut wasm_instrumenter,
-            WasmMeteringParams::new(InstructionCostRules::tiered(1, 5, 10, 5000), 512), // TODO: add to ExecutionConfig
+            &mut package_module_cache,
+            WasmMeteringParams::standard(),
             &mut execution_trace,
             Vec::new(),
+            NativeFnRegistry::standard(),
         );
 
         // Invoke the system
@@ -57,9 +60,7 @@ impl SetCurrentEpoch {
 
         // Commit
         let receipt = track.finalize(Ok(Vec::new()), Vec::new());
-        if let TransactionResult::Commit(c) = receipt.result {
-            c.state_updates.commit(&mut substate_store);
-        }
+        substate_store.commit(&receipt);
 
         Ok(())
     }
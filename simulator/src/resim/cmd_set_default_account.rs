@@ -14,10 +14,23 @@ pub struct SetDefaultAccount {
 }
 
 impl SetDefaultAccount {
-    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
-        let mut configs = get_configs()?;
-        configs.default_account = Some((self.component_address, self.private_key.clone()));
-        set_configs(&configs)?;
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let mut configs = get_configs(ctx)?;
+        let name = configs
+            .accounts
+            .iter()
+            .find(|(_, profile)| profile.component_address == self.component_address)
+            .map(|(name, _)| name.clone())
+            .unwrap_or_else(|| configs.next_account_name());
+        configs.accounts.insert(
+            name.clone(),
+            AccountProfile {
+                component_address: self.component_address,
+                private_key: self.private_key.clone(),
+            },
+        );
+        configs.default_account = Some(name);
+        set_configs(ctx, &configs)?;
 
         writeln!(out, "Default account updated!").map_err(Error::IOError)?;
         Ok(())
@@ -1,6 +1,7 @@
 use clap::Parser;
+use radix_engine::ledger::*;
+use radix_engine::model::ComponentState;
 use radix_engine::types::*;
-use radix_engine_stores::rocks_db::RadixEngineDB;
 use scrypto::address::Bech32Decoder;
 use scrypto::core::NetworkDefinition;
 
@@ -12,28 +13,100 @@ use crate::resim::*;
 pub struct Show {
     /// The address of a package, component or resource manager
     address: String,
+
+    /// Show only the component state at this path of field names and indexes, e.g. `vaults.0`
+    #[clap(long)]
+    field: Option<String>,
+
+    /// Show the entity as of this state version rather than the current one
+    #[clap(long)]
+    at_version: Option<u64>,
 }
 
 impl Show {
-    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
-        let ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let ledger = open_ledger_read_only(ctx)?;
+        match self.at_version {
+            Some(state_version) => {
+                if state_version >= ledger.state_version() {
+                    return Err(Error::StateVersionNotFound(state_version));
+                }
+                self.show(ctx, &ledger.at_version(state_version), out)
+            }
+            None => self.show(ctx, &*ledger, out),
+        }
+    }
 
+    fn show<T: ReadableSubstateStore + QueryableSubstateStore, O: std::io::Write>(
+        &self,
+        ctx: &CommandContext,
+        ledger: &T,
+        out: &mut O,
+    ) -> Result<(), Error> {
         let bech32_decoder = Bech32Decoder::new(&NetworkDefinition::simulator());
 
         if let Ok(package_address) =
             bech32_decoder.validate_and_decode_package_address(&self.address)
         {
-            dump_package(package_address, &ledger, out).map_err(Error::LedgerDumpError)
+            if ctx.is_json_output() {
+                let value =
+                    package_to_json(package_address, ledger).map_err(Error::LedgerDumpError)?;
+                write_json(out, &value)
+            } else {
+                dump_package(package_address, ledger, out).map_err(Error::LedgerDumpError)
+            }
         } else if let Ok(component_address) =
             bech32_decoder.validate_and_decode_component_address(&self.address)
         {
-            dump_component(component_address, &ledger, out).map_err(Error::LedgerDumpError)
+            if let Some(field) = &self.field {
+                let value = component_state_field(ledger, component_address, field)?;
+                return if ctx.is_json_output() {
+                    write_json(out, &value.to_json(&NetworkDefinition::simulator()))
+                } else {
+                    writeln!(out, "{}", value).map_err(Error::IOError)
+                };
+            }
+
+            if ctx.is_json_output() {
+                let value =
+                    component_to_json(component_address, ledger).map_err(Error::LedgerDumpError)?;
+                write_json(out, &value)
+            } else {
+                dump_component(component_address, ledger, out).map_err(Error::LedgerDumpError)
+            }
         } else if let Ok(resource_address) =
             bech32_decoder.validate_and_decode_resource_address(&self.address)
         {
-            dump_resource_manager(resource_address, &ledger, out).map_err(Error::LedgerDumpError)
+            if ctx.is_json_output() {
+                let value = resource_manager_to_json(resource_address, ledger)
+                    .map_err(Error::LedgerDumpError)?;
+                write_json(out, &value)
+            } else {
+                dump_resource_manager(resource_address, ledger, out).map_err(Error::LedgerDumpError)
+            }
         } else {
             Err(Error::InvalidId(self.address.clone()))
         }
     }
 }
+
+/// Reads the sub-value of a component's state at a dot-separated path, resolving field names
+/// against the state structure in the blueprint ABI.
+fn component_state_field<T: ReadableSubstateStore>(
+    ledger: &T,
+    component_address: ComponentAddress,
+    field: &str,
+) -> Result<ScryptoValue, Error> {
+    let abi = radix_engine::model::export_abi_by_component(ledger, component_address)
+        .map_err(Error::AbiExportError)?;
+    let state: ComponentState = ledger
+        .get_substate(&SubstateId::ComponentState(component_address))
+        .map(|s| s.substate.into())
+        .ok_or(Error::LedgerDumpError(DisplayError::ComponentNotFound))?;
+    let value = ScryptoValue::from_slice(state.state()).map_err(Error::DataError)?;
+
+    let path: Vec<&str> = field.split('.').collect();
+    value
+        .get_path_with_type(&abi.structure, &path)
+        .map_err(|e| Error::InvalidFieldPath(field.to_owned(), e))
+}
@@ -1,5 +1,6 @@
 use clap::Parser;
 use colored::*;
+use radix_engine::types::*;
 
 use crate::resim::*;
 
@@ -8,8 +9,44 @@ use crate::resim::*;
 pub struct ShowConfigs {}
 
 impl ShowConfigs {
-    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
-        let configs = get_configs()?;
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let configs = get_configs(ctx)?;
+        if ctx.is_json_output() {
+            let bech32_encoder = Bech32Encoder::new(&NetworkDefinition::simulator());
+            let accounts: serde_json::Map<String, serde_json::Value> = configs
+                .accounts
+                .iter()
+                .map(|(name, profile)| {
+                    (
+                        name.clone(),
+                        bech32_encoder
+                            .encode_component_address(&profile.component_address)
+                            .into(),
+                    )
+                })
+                .collect();
+            let packages: serde_json::Map<String, serde_json::Value> = configs
+                .packages
+                .iter()
+                .map(|(name, package_address)| {
+                    (
+                        name.clone(),
+                        bech32_encoder
+                            .encode_package_address(package_address)
+                            .into(),
+                    )
+                })
+                .collect();
+            return write_json(
+                out,
+                &serde_json::json!({
+                    "default_account": configs.default_account,
+                    "accounts": accounts,
+                    "packages": packages,
+                    "nonce": configs.nonce,
+                }),
+            );
+        }
         writeln!(
             out,
             "{}: {:?}",
@@ -17,6 +54,20 @@ impl ShowConfigs {
             configs.default_account
         )
         .map_err(Error::IOError)?;
+        writeln!(
+            out,
+            "{}: {:?}",
+            "Accounts".green().bold(),
+            configs.accounts.keys().collect::<Vec<&String>>()
+        )
+        .map_err(Error::IOError)?;
+        writeln!(
+            out,
+            "{}: {:?}",
+            "Packages".green().bold(),
+            configs.packages.keys().collect::<Vec<&String>>()
+        )
+        .map_err(Error::IOError)?;
         writeln!(
             out,
             "{}: {:?}",
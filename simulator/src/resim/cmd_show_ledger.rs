@@ -1,6 +1,5 @@
 use clap::Parser;
 use colored::*;
-use radix_engine_stores::rocks_db::RadixEngineDB;
 use scrypto::address::Bech32Encoder;
 
 use crate::resim::*;
@@ -11,13 +10,56 @@ use crate::utils::*;
 pub struct ShowLedger {}
 
 impl ShowLedger {
-    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
-        let ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let ledger = open_ledger_read_only(ctx)?;
 
         let bech32_encoder = Bech32Encoder::new(&NetworkDefinition::simulator());
 
+        if ctx.is_json_output() {
+            return write_json(
+                out,
+                &serde_json::json!({
+                    "state_version": ledger.state_version(),
+                    "state_root": ledger.state_root().to_string(),
+                    "packages": ledger
+                        .inner()
+                        .list_packages()
+                        .iter()
+                        .map(|address| bech32_encoder.encode_package_address(address))
+                        .collect::<Vec<String>>(),
+                    "components": ledger
+                        .inner()
+                        .list_components()
+                        .iter()
+                        .map(|address| bech32_encoder.encode_component_address(address))
+                        .collect::<Vec<String>>(),
+                    "resource_managers": ledger
+                        .inner()
+                        .list_resource_managers()
+                        .iter()
+                        .map(|address| bech32_encoder.encode_resource_address(address))
+                        .collect::<Vec<String>>(),
+                }),
+            );
+        }
+
+        writeln!(
+            out,
+            "{}: {}",
+            "State Version".green().bold(),
+            ledger.state_version()
+        )
+        .map_err(Error::IOError)?;
+        writeln!(
+            out,
+            "{}: {}",
+            "State Root".green().bold(),
+            ledger.state_root()
+        )
+        .map_err(Error::IOError)?;
+
         writeln!(out, "{}:", "Packages".green().bold()).map_err(Error::IOError)?;
-        for (last, package_address) in ledger.list_packages().iter().identify_last() {
+        for (last, package_address) in ledger.inner().list_packages().iter().identify_last() {
             writeln!(
                 out,
                 "{} {}",
@@ -28,7 +70,7 @@ impl ShowLedger {
         }
 
         writeln!(out, "{}:", "Components".green().bold()).map_err(Error::IOError)?;
-        for (last, component_address) in ledger.list_components().iter().identify_last() {
+        for (last, component_address) in ledger.inner().list_components().iter().identify_last() {
             writeln!(
                 out,
                 "{} {}",
@@ -39,7 +81,12 @@ impl ShowLedger {
         }
 
         writeln!(out, "{}:", "Resource Managers".green().bold()).map_err(Error::IOError)?;
-        for (last, resource_address) in ledger.list_resource_managers().iter().identify_last() {
+        for (last, resource_address) in ledger
+            .inner()
+            .list_resource_managers()
+            .iter()
+            .identify_last()
+        {
             writeln!(
                 out,
                 "{} {}",
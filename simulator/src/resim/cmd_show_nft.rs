@@ -0,0 +1,42 @@
+use clap::Parser;
+use radix_engine::types::*;
+use scrypto::address::Bech32Decoder;
+use scrypto::core::NetworkDefinition;
+
+use crate::ledger::*;
+use crate::resim::*;
+
+/// Show the non-fungibles of a resource, with their data decoded
+#[derive(Parser, Debug)]
+pub struct ShowNft {
+    /// The resource address
+    resource_address: String,
+
+    /// The non-fungible ID, in hex; all non-fungibles of the resource are shown if omitted
+    id: Option<String>,
+}
+
+impl ShowNft {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let ledger = open_ledger_read_only(ctx)?;
+
+        let bech32_decoder = Bech32Decoder::new(&NetworkDefinition::simulator());
+        let resource_address = bech32_decoder
+            .validate_and_decode_resource_address(&self.resource_address)
+            .map_err(Error::AddressError)?;
+        let id = match &self.id {
+            Some(id) => {
+                Some(NonFungibleId::from_str(id).map_err(|_| Error::InvalidId(id.clone()))?)
+            }
+            None => None,
+        };
+
+        if ctx.is_json_output() {
+            let value = non_fungibles_to_json(resource_address, id, &*ledger)
+                .map_err(Error::LedgerDumpError)?;
+            write_json(out, &value)
+        } else {
+            dump_non_fungibles(resource_address, id, &*ledger, out).map_err(Error::LedgerDumpError)
+        }
+    }
+}
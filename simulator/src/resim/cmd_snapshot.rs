@@ -0,0 +1,132 @@
+use clap::{Parser, Subcommand};
+use std::fs;
+use std::path::Path;
+
+use crate::resim::*;
+
+/// Save and restore snapshots of the data directory
+#[derive(Parser, Debug)]
+pub struct Snapshot {
+    #[clap(subcommand)]
+    command: SnapshotCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SnapshotCommand {
+    Save(SnapshotSave),
+    Restore(SnapshotRestore),
+    List(SnapshotList),
+}
+
+impl Snapshot {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        match &self.command {
+            SnapshotCommand::Save(cmd) => cmd.run(ctx, out),
+            SnapshotCommand::Restore(cmd) => cmd.run(ctx, out),
+            SnapshotCommand::List(cmd) => cmd.run(ctx, out),
+        }
+    }
+}
+
+/// Save the ledger and configurations as a named snapshot, replacing any snapshot with that name
+#[derive(Parser, Debug)]
+pub struct SnapshotSave {
+    /// The snapshot name
+    name: String,
+}
+
+impl SnapshotSave {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let snapshot_dir = get_snapshot_dir(ctx, &self.name)?;
+        if snapshot_dir.exists() {
+            fs::remove_dir_all(&snapshot_dir).map_err(Error::IOError)?;
+        }
+        // Close the ledger so that everything it holds is on disk
+        ctx.session.close_ledger();
+        copy_dir(&get_data_dir(ctx)?, &snapshot_dir).map_err(Error::IOError)?;
+
+        writeln!(out, "Snapshot saved!").map_err(Error::IOError)?;
+        Ok(())
+    }
+}
+
+/// Restore the ledger and configurations from a named snapshot
+#[derive(Parser, Debug)]
+pub struct SnapshotRestore {
+    /// The snapshot name
+    name: String,
+}
+
+impl SnapshotRestore {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let snapshot_dir = get_snapshot_dir(ctx, &self.name)?;
+        if !snapshot_dir.is_dir() {
+            return Err(Error::SnapshotNotFound(self.name.clone()));
+        }
+        let data_dir = get_data_dir(ctx)?;
+
+        // Stage the copy next to the data directory, so a failed copy leaves the ledger untouched
+        let mut staging_name = data_dir
+            .file_name()
+            .map(|n| n.to_os_string())
+            .unwrap_or_default();
+        staging_name.push("-restoring");
+        let staging_dir = data_dir.with_file_name(staging_name);
+        if staging_dir.exists() {
+            fs::remove_dir_all(&staging_dir).map_err(Error::IOError)?;
+        }
+        if let Err(e) = copy_dir(&snapshot_dir, &staging_dir) {
+            let _ = fs::remove_dir_all(&staging_dir);
+            return Err(Error::IOError(e));
+        }
+
+        ctx.session.close_ledger();
+        if data_dir.exists() {
+            fs::remove_dir_all(&data_dir).map_err(Error::IOError)?;
+        }
+        fs::rename(&staging_dir, &data_dir).map_err(Error::IOError)?;
+
+        writeln!(out, "Snapshot restored!").map_err(Error::IOError)?;
+        Ok(())
+    }
+}
+
+/// List saved snapshots
+#[derive(Parser, Debug)]
+pub struct SnapshotList {}
+
+impl SnapshotList {
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let snapshots_dir = get_snapshots_dir(ctx)?;
+        let mut names = Vec::new();
+        if snapshots_dir.is_dir() {
+            for entry in fs::read_dir(snapshots_dir).map_err(Error::IOError)? {
+                let entry = entry.map_err(Error::IOError)?;
+                names.push(entry.file_name().to_string_lossy().into_owned());
+            }
+        }
+        names.sort();
+
+        if ctx.is_json_output() {
+            return write_json(out, &serde_json::json!(names));
+        }
+        for name in names {
+            writeln!(out, "{}", name).map_err(Error::IOError)?;
+        }
+        Ok(())
+    }
+}
+
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
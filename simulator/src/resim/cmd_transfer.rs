@@ -1,8 +1,11 @@
 use clap::Parser;
+use radix_engine::ledger::*;
+use radix_engine::model::*;
 use radix_engine::types::*;
 use scrypto::prelude::Expression;
 use transaction::builder::ManifestBuilder;
 
+use crate::ledger::*;
 use crate::resim::*;
 
 /// Transfer resource to another account
@@ -17,7 +20,8 @@ pub struct Transfer {
     /// The recipient component address.
     recipient: ComponentAddress,
 
-    /// The proofs to add to the auth zone
+    /// The proofs to add to the auth zone; if not given, proofs of the badges required to
+    /// withdraw and deposit the resource are created from the account
     #[clap(short, long, multiple = true)]
     proofs: Option<Vec<String>>,
 
@@ -33,24 +37,53 @@ pub struct Transfer {
     #[clap(short, long)]
     signing_keys: Option<String>,
 
+    /// The name of the account to use instead of the default account
+    #[clap(long)]
+    account: Option<String>,
+
     /// Turn on tracing
     #[clap(short, long)]
     trace: bool,
 }
 
 impl Transfer {
-    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
-        let default_account = get_default_account()?;
-        let proofs = self.proofs.clone().unwrap_or_default();
+    pub fn run<O: std::io::Write>(&self, ctx: &CommandContext, out: &mut O) -> Result<(), Error> {
+        let default_account = get_account(ctx, &self.account)?;
 
         let mut manifest_builder = &mut ManifestBuilder::new(&NetworkDefinition::simulator());
-        for resource_specifier in proofs {
-            manifest_builder = manifest_builder
-                .create_proof_from_account_by_resource_specifier(
-                    resource_specifier,
-                    default_account,
-                )
-                .map_err(Error::FailedToBuildArgs)?;
+        match &self.proofs {
+            Some(proofs) => {
+                for resource_specifier in proofs {
+                    manifest_builder = manifest_builder
+                        .create_proof_from_account_by_resource_specifier(
+                            resource_specifier.clone(),
+                            default_account,
+                        )
+                        .map_err(Error::FailedToBuildArgs)?;
+                }
+            }
+            None => {
+                let ledger = open_ledger_read_only(ctx)?;
+                for badge in get_transfer_badges(self.resource_address, default_account, &*ledger)?
+                {
+                    manifest_builder = match badge {
+                        Badge::Resource(resource_address) => manifest_builder
+                            .create_proof_from_account(resource_address, default_account),
+                        Badge::Amount(amount, resource_address) => manifest_builder
+                            .create_proof_from_account_by_amount(
+                                amount,
+                                resource_address,
+                                default_account,
+                            ),
+                        Badge::NonFungible(non_fungible_address) => manifest_builder
+                            .create_proof_from_account_by_ids(
+                                &BTreeSet::from([non_fungible_address.non_fungible_id()]),
+                                non_fungible_address.resource_address(),
+                                default_account,
+                            ),
+                    };
+                }
+            }
         }
 
         let manifest = manifest_builder
@@ -63,8 +96,10 @@ impl Transfer {
             )
             .build();
         handle_manifest(
+            ctx,
             manifest,
             &self.signing_keys,
+            &self.account,
             &self.network,
             &self.manifest,
             self.trace,
@@ -74,3 +109,100 @@ impl Transfer {
         .map(|_| ())
     }
 }
+
+/// A badge that the account can prove.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Badge {
+    Resource(ResourceAddress),
+    Amount(Decimal, ResourceAddress),
+    NonFungible(NonFungibleAddress),
+}
+
+/// Returns the badges held by the account that appear in the withdraw or deposit rules of a
+/// resource.
+fn get_transfer_badges<T: ReadableSubstateStore + QueryableSubstateStore>(
+    resource_address: ResourceAddress,
+    account: ComponentAddress,
+    substate_store: &T,
+) -> Result<Vec<Badge>, Error> {
+    let resource_manager: ResourceManager = substate_store
+        .get_substate(&SubstateId::ResourceManager(resource_address))
+        .map(|s| s.substate.into())
+        .ok_or(Error::LedgerDumpError(
+            DisplayError::ResourceManagerNotFound,
+        ))?;
+    let vaults = get_component_vaults(account, substate_store).map_err(Error::LedgerDumpError)?;
+
+    let mut candidates = Vec::new();
+    for vault_fn in [VaultFnIdentifier::Take, VaultFnIdentifier::Put] {
+        if let MethodAuthorization::Protected(rule) = resource_manager.get_vault_auth(vault_fn) {
+            collect_auth_rule_badges(rule, &mut candidates);
+        }
+    }
+
+    let mut badges = Vec::new();
+    for badge in candidates {
+        let held = vaults.iter().any(|vault| match &badge {
+            Badge::Resource(resource_address) => {
+                vault.resource_address() == *resource_address && !vault.total_amount().is_zero()
+            }
+            Badge::Amount(amount, resource_address) => {
+                vault.resource_address() == *resource_address && vault.total_amount() >= *amount
+            }
+            Badge::NonFungible(non_fungible_address) => {
+                vault.resource_address() == non_fungible_address.resource_address()
+                    && vault
+                        .total_ids()
+                        .map(|ids| ids.contains(&non_fungible_address.non_fungible_id()))
+                        .unwrap_or(false)
+            }
+        });
+        if held && !badges.contains(&badge) {
+            badges.push(badge);
+        }
+    }
+    Ok(badges)
+}
+
+fn collect_auth_rule_badges(rule: &HardAuthRule, badges: &mut Vec<Badge>) {
+    match rule {
+        HardAuthRule::ProofRule(proof_rule) => collect_proof_rule_badges(proof_rule, badges),
+        HardAuthRule::AnyOf(rules)
+        | HardAuthRule::AllOf(rules)
+        | HardAuthRule::CountOf(_, rules) => {
+            for rule in rules {
+                collect_auth_rule_badges(rule, badges);
+            }
+        }
+    }
+}
+
+fn collect_proof_rule_badges(rule: &HardProofRule, badges: &mut Vec<Badge>) {
+    match rule {
+        HardProofRule::Require(resource) => badges.extend(to_badge(resource)),
+        HardProofRule::AmountOf(HardDecimal::Amount(amount), resource) => match resource {
+            HardResourceOrNonFungible::Resource(resource_address) => {
+                badges.push(Badge::Amount(*amount, *resource_address))
+            }
+            _ => badges.extend(to_badge(resource)),
+        },
+        HardProofRule::AllOf(HardProofRuleResourceList::List(resources))
+        | HardProofRule::AnyOf(HardProofRuleResourceList::List(resources))
+        | HardProofRule::CountOf(_, HardProofRuleResourceList::List(resources)) => {
+            badges.extend(resources.iter().filter_map(to_badge))
+        }
+        _ => {}
+    }
+}
+
+fn to_badge(resource: &HardResourceOrNonFungible) -> Option<Badge> {
+    match resource {
+        HardResourceOrNonFungible::Resource(resource_address) => {
+            Some(Badge::Resource(*resource_address))
+        }
+        HardResourceOrNonFungible::NonFungible(non_fungible_address) => {
+            Some(Badge::NonFungible(non_fungible_address.clone()))
+        }
+        HardResourceOrNonFungible::SoftResourceNotFound => None,
+    }
+}
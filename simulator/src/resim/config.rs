@@ -7,20 +7,136 @@ use transaction::signing::EcdsaSecp256k1PrivateKey;
 use crate::resim::*;
 use std::env;
 
+/// A named account, together with the private key for accessing it.
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct AccountProfile {
+    pub component_address: ComponentAddress,
+    pub private_key: String,
+}
+
 /// Simulator configurations.
 #[derive(Debug, Clone, TypeId, Encode, Decode, Default)]
 pub struct Configs {
-    pub default_account: Option<(ComponentAddress, String)>,
+    pub accounts: BTreeMap<String, AccountProfile>,
+    pub default_account: Option<String>,
+    pub packages: BTreeMap<String, PackageAddress>,
     pub nonce: u64,
 }
 
-pub fn get_data_dir() -> Result<PathBuf, Error> {
+/// The configurations as written before accounts were named, which are still read so that an
+/// existing data directory keeps its default account and nonce.
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+struct LegacyConfigs {
+    default_account: Option<(ComponentAddress, String)>,
+    nonce: u64,
+}
+
+impl From<LegacyConfigs> for Configs {
+    fn from(legacy: LegacyConfigs) -> Self {
+        let mut configs = Configs {
+            nonce: legacy.nonce,
+            ..Default::default()
+        };
+        if let Some((component_address, private_key)) = legacy.default_account {
+            let name = configs.next_account_name();
+            configs.accounts.insert(
+                name.clone(),
+                AccountProfile {
+                    component_address,
+                    private_key,
+                },
+            );
+            configs.default_account = Some(name);
+        }
+        configs
+    }
+}
+
+impl Configs {
+    /// Decodes the configurations, falling back to the layout written before accounts were named.
+    pub fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        scrypto_decode(bytes).or_else(|e| {
+            scrypto_decode::<LegacyConfigs>(bytes)
+                .map(Configs::from)
+                .map_err(|_| e)
+        })
+    }
+
+    /// Returns the first `accountN` name which is not taken yet.
+    pub fn next_account_name(&self) -> String {
+        (1..)
+            .map(|i| format!("account{}", i))
+            .find(|name| !self.accounts.contains_key(name))
+            .unwrap()
+    }
+
+    /// Returns the account with the given name, or the default account if no name is given.
+    pub fn get_account(&self, name: &Option<String>) -> Result<&AccountProfile, Error> {
+        match name {
+            Some(name) => self
+                .accounts
+                .get(name)
+                .ok_or_else(|| Error::AccountNotFound(name.clone())),
+            None => self
+                .default_account
+                .as_ref()
+                .and_then(|name| self.accounts.get(name))
+                .ok_or(Error::NoDefaultAccount),
+        }
+    }
+}
+
+/// Returns the directory holding one sub-directory per environment, other than the default one.
+pub fn get_envs_dir() -> Result<PathBuf, Error> {
+    let mut path = dirs::home_dir().ok_or(Error::HomeDirUnknown)?;
+    path.push(DEFAULT_ENVS_DIR_UNDER_HOME);
+    Ok(path)
+}
+
+/// Returns the environment selected with `--env`, or else the one last switched to.
+pub fn get_current_env(ctx: &CommandContext) -> Result<String, Error> {
+    if let Some(name) = &ctx.env {
+        return Ok(name.clone());
+    }
+    let path = get_envs_dir()?.join("current");
+    if path.exists() {
+        let name = fs::read_to_string(path).map_err(Error::IOError)?;
+        Ok(name.trim().to_owned())
+    } else {
+        Ok(DEFAULT_ENV.to_owned())
+    }
+}
+
+pub fn set_current_env(name: &str) -> Result<(), Error> {
+    let envs_dir = get_envs_dir()?;
+    fs::create_dir_all(&envs_dir).map_err(Error::IOError)?;
+    fs::write(envs_dir.join("current"), name).map_err(Error::IOError)
+}
+
+/// Returns the data directory of an environment.
+pub fn get_env_dir(name: &str) -> Result<PathBuf, Error> {
+    if name == DEFAULT_ENV {
+        let mut path = dirs::home_dir().ok_or(Error::HomeDirUnknown)?;
+        path.push(DEFAULT_SCRYPTO_DIR_UNDER_HOME);
+        Ok(path)
+    } else {
+        Ok(get_envs_dir()?.join(name).join("data"))
+    }
+}
+
+pub fn env_exists(name: &str) -> Result<bool, Error> {
+    Ok(name == DEFAULT_ENV || get_envs_dir()?.join(name).is_dir())
+}
+
+pub fn get_data_dir(ctx: &CommandContext) -> Result<PathBuf, Error> {
     let path = match env::var(ENV_DATA_DIR) {
         Ok(value) => std::path::PathBuf::from(value),
         Err(..) => {
-            let mut path = dirs::home_dir().ok_or(Error::HomeDirUnknown)?;
-            path.push(DEFAULT_SCRYPTO_DIR_UNDER_HOME);
-            path
+            let name = get_current_env(ctx)?;
+            if !env_exists(&name)? {
+                return Err(Error::EnvNotFound(name));
+            }
+            get_env_dir(&name)?
         }
     };
     if !path.exists() {
@@ -29,40 +145,123 @@ pub fn get_data_dir() -> Result<PathBuf, Error> {
     Ok(path)
 }
 
-pub fn get_configs_path() -> Result<PathBuf, Error> {
-    let mut path = get_data_dir()?;
+/// Returns the directory where snapshots of the data directory are kept, next to it.
+pub fn get_snapshots_dir(ctx: &CommandContext) -> Result<PathBuf, Error> {
+    let data_dir = get_data_dir(ctx)?;
+    let mut name = data_dir
+        .file_name()
+        .map(|n| n.to_os_string())
+        .unwrap_or_default();
+    name.push("-snapshots");
+    Ok(data_dir.with_file_name(name))
+}
+
+/// Returns the directory of the named snapshot, rejecting names which could escape the snapshots
+/// directory.
+pub fn get_snapshot_dir(ctx: &CommandContext, name: &str) -> Result<PathBuf, Error> {
+    if name.is_empty()
+        || name == "."
+        || name == ".."
+        || name.contains(|c: char| c == '/' || c == '\\' || std::path::is_separator(c))
+    {
+        return Err(Error::InvalidSnapshotName(name.to_owned()));
+    }
+    Ok(get_snapshots_dir(ctx)?.join(name))
+}
+
+pub fn get_configs_path(ctx: &CommandContext) -> Result<PathBuf, Error> {
+    let mut path = get_data_dir(ctx)?;
     path.push("config");
     Ok(path.with_extension("sbor"))
 }
 
-pub fn get_configs() -> Result<Configs, Error> {
-    let path = get_configs_path()?;
+pub fn get_configs(ctx: &CommandContext) -> Result<Configs, Error> {
+    let path = get_configs_path(ctx)?;
     if path.exists() {
-        scrypto_decode(&fs::read(path).map_err(Error::IOError)?.as_ref())
+        Configs::decode(&fs::read(path).map_err(Error::IOError)?)
             .map_err(Error::ConfigDecodingError)
     } else {
         Ok(Configs::default())
     }
 }
 
-pub fn set_configs(configs: &Configs) -> Result<(), Error> {
-    fs::write(get_configs_path()?, scrypto_encode(configs)).map_err(Error::IOError)
+pub fn set_configs(ctx: &CommandContext, configs: &Configs) -> Result<(), Error> {
+    fs::write(get_configs_path(ctx)?, scrypto_encode(configs)).map_err(Error::IOError)
 }
 
-pub fn get_default_account() -> Result<ComponentAddress, Error> {
-    get_configs()?
-        .default_account
-        .map(|pair| pair.0)
-        .ok_or(Error::NoDefaultAccount)
+pub fn get_account(ctx: &CommandContext, name: &Option<String>) -> Result<ComponentAddress, Error> {
+    Ok(get_configs(ctx)?.get_account(name)?.component_address)
+}
+
+/// Resolves a package address, or the name of a package published with `publish-workspace`.
+pub fn get_package_address(ctx: &CommandContext, package: &str) -> Result<PackageAddress, Error> {
+    if let Ok(package_address) = PackageAddress::from_str(package) {
+        return Ok(package_address);
+    }
+    get_configs(ctx)?
+        .packages
+        .get(package)
+        .cloned()
+        .ok_or_else(|| Error::PackageNotFound(package.to_owned()))
 }
 
-pub fn get_default_private_key() -> Result<EcdsaSecp256k1PrivateKey, Error> {
-    get_configs()?
-        .default_account
-        .map(|pair| EcdsaSecp256k1PrivateKey::from_bytes(&hex::decode(&pair.1).unwrap()).unwrap())
-        .ok_or(Error::NoDefaultAccount)
+pub fn get_private_key(
+    ctx: &CommandContext,
+    name: &Option<String>,
+) -> Result<EcdsaSecp256k1PrivateKey, Error> {
+    let configs = get_configs(ctx)?;
+    let profile = configs.get_account(name)?;
+    hex::decode(&profile.private_key)
+        .ok()
+        .and_then(|bytes| EcdsaSecp256k1PrivateKey::from_bytes(&bytes).ok())
+        .ok_or(Error::InvalidPrivateKey)
 }
 
-pub fn get_nonce() -> Result<u64, Error> {
-    Ok(get_configs()?.nonce)
+pub fn get_nonce(ctx: &CommandContext) -> Result<u64, Error> {
+    Ok(get_configs(ctx)?.nonce)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_names_cannot_escape_snapshots_dir() {
+        for name in ["", ".", "..", "../data", "a/b", "a\\b"] {
+            assert!(matches!(
+                get_snapshot_dir(&CommandContext::default(), name),
+                Err(Error::InvalidSnapshotName(..))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_legacy_configs_are_migrated() {
+        let component_address = ComponentAddress::Account([1u8; 26]);
+        let legacy = LegacyConfigs {
+            default_account: Some((component_address, "key".to_owned())),
+            nonce: 5,
+        };
+
+        let configs = Configs::decode(&scrypto_encode(&legacy)).unwrap();
+
+        assert_eq!(5, configs.nonce);
+        let account = configs.get_account(&None).unwrap();
+        assert_eq!(component_address, account.component_address);
+        assert_eq!("key", account.private_key);
+    }
+
+    #[test]
+    fn test_configs_round_trip() {
+        let mut configs = Configs::default();
+        configs.nonce = 3;
+        configs
+            .packages
+            .insert("hello".to_owned(), PackageAddress::Normal([2u8; 26]));
+
+        let decoded = Configs::decode(&scrypto_encode(&configs)).unwrap();
+
+        assert_eq!(3, decoded.nonce);
+        assert_eq!(configs.packages, decoded.packages);
+    }
 }
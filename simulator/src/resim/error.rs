@@ -2,6 +2,7 @@ use std::io;
 
 use radix_engine::engine::*;
 use radix_engine::model::ExtractAbiError;
+use radix_engine::types::Decimal;
 use radix_engine::wasm::PrepareError;
 use sbor::*;
 use scrypto::address::AddressError;
@@ -16,6 +17,22 @@ use crate::utils::*;
 pub enum Error {
     NoDefaultAccount,
 
+    AccountNotFound(String),
+
+    AccountAlreadyExists(String),
+
+    SnapshotNotFound(String),
+
+    InvalidSnapshotName(String),
+
+    StateVersionNotFound(u64),
+
+    EnvNotFound(String),
+
+    EnvAlreadyExists(String),
+
+    TransactionNotFound(String),
+
     HomeDirUnknown,
 
     ConfigDecodingError(sbor::DecodeError),
@@ -30,6 +47,10 @@ pub enum Error {
 
     PackageAddressNotFound,
 
+    PackageNotFound(String),
+
+    CyclicPackageDependencies(String),
+
     ExtractAbiError(ExtractAbiError),
 
     InvalidPackage(PrepareError),
@@ -42,6 +63,8 @@ pub enum Error {
 
     TransactionRejected(RejectionError),
 
+    FeeBudgetExceeded(Decimal, Decimal),
+
     AbiExportError(RuntimeError),
 
     LedgerDumpError(DisplayError),
@@ -58,5 +81,15 @@ pub enum Error {
 
     FailedToBuildArgs(BuildArgsError),
 
+    MissingArguments(String),
+
+    AssertionFailed(String),
+
+    InvalidFaucetAmount(Decimal),
+
+    InvalidArguments(String, ValidateError),
+
+    InvalidFieldPath(String, sbor::path::SborPathError),
+
     ParseNetworkError(ParseNetworkError),
 }
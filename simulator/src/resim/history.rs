@@ -0,0 +1,56 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use radix_engine::transaction::TransactionReceipt;
+use radix_engine::types::*;
+
+use crate::resim::*;
+
+/// Returns the path of the transaction history, one JSON entry per line.
+pub fn get_history_path(ctx: &CommandContext) -> Result<PathBuf, Error> {
+    let mut path = get_data_dir(ctx)?;
+    path.push("history");
+    Ok(path.with_extension("jsonl"))
+}
+
+/// Records an executed transaction, with its manifest and receipt, in the history.
+pub fn append_history(
+    ctx: &CommandContext,
+    transaction_hash: Hash,
+    manifest: &str,
+    receipt: &TransactionReceipt,
+) -> Result<(), Error> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let entry = serde_json::json!({
+        "hash": transaction_hash.to_string(),
+        "timestamp": timestamp,
+        "manifest": manifest,
+        "receipt": receipt.to_json(&NetworkDefinition::simulator()),
+    });
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(get_history_path(ctx)?)
+        .map_err(Error::IOError)?;
+    writeln!(file, "{}", entry).map_err(Error::IOError)
+}
+
+/// Returns the history entries, oldest first.
+pub fn get_history(ctx: &CommandContext) -> Result<Vec<serde_json::Value>, Error> {
+    let path = get_history_path(ctx)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    fs::read_to_string(path)
+        .map_err(Error::IOError)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(Error::JSONError))
+        .collect()
+}
@@ -1,14 +1,25 @@
+mod cmd_account;
+mod cmd_assert_balance;
+mod cmd_assert_state;
 mod cmd_call_function;
 mod cmd_call_method;
+mod cmd_diff;
+mod cmd_env;
 mod cmd_export_abi;
+mod cmd_faucet;
 mod cmd_generate_key_pair;
+mod cmd_history;
 mod cmd_mint;
 mod cmd_new_account;
 mod cmd_new_badge_fixed;
 mod cmd_new_badge_mutable;
+mod cmd_new_token_advanced;
 mod cmd_new_token_fixed;
 mod cmd_new_token_mutable;
 mod cmd_publish;
+mod cmd_publish_workspace;
+mod cmd_receipt;
+mod cmd_repl;
 mod cmd_reset;
 mod cmd_run;
 mod cmd_set_current_epoch;
@@ -16,21 +27,35 @@ mod cmd_set_default_account;
 mod cmd_show;
 mod cmd_show_configs;
 mod cmd_show_ledger;
+mod cmd_show_nft;
+mod cmd_snapshot;
 mod cmd_transfer;
 mod config;
 mod error;
+mod history;
 
+pub use cmd_account::*;
+pub use cmd_assert_balance::*;
+pub use cmd_assert_state::*;
 pub use cmd_call_function::*;
 pub use cmd_call_method::*;
+pub use cmd_diff::*;
+pub use cmd_env::*;
 pub use cmd_export_abi::*;
+pub use cmd_faucet::*;
 pub use cmd_generate_key_pair::*;
+pub use cmd_history::*;
 pub use cmd_mint::*;
 pub use cmd_new_account::*;
 pub use cmd_new_badge_fixed::*;
 pub use cmd_new_badge_mutable::*;
+pub use cmd_new_token_advanced::*;
 pub use cmd_new_token_fixed::*;
 pub use cmd_new_token_mutable::*;
 pub use cmd_publish::*;
+pub use cmd_publish_workspace::*;
+pub use cmd_receipt::*;
+pub use cmd_repl::*;
 pub use cmd_reset::*;
 pub use cmd_run::*;
 pub use cmd_set_current_epoch::*;
@@ -38,16 +63,23 @@ pub use cmd_set_default_account::*;
 pub use cmd_show::*;
 pub use cmd_show_configs::*;
 pub use cmd_show_ledger::*;
+pub use cmd_show_nft::*;
+pub use cmd_snapshot::*;
 pub use cmd_transfer::*;
 pub use config::*;
 pub use error::*;
+pub use history::*;
 
 pub const DEFAULT_SCRYPTO_DIR_UNDER_HOME: &'static str = ".scrypto";
+pub const DEFAULT_ENVS_DIR_UNDER_HOME: &'static str = ".scrypto-envs";
+pub const DEFAULT_ENV: &'static str = "default";
 pub const ENV_DATA_DIR: &'static str = "DATA_DIR";
 pub const ENV_DISABLE_MANIFEST_OUTPUT: &'static str = "DISABLE_MANIFEST_OUTPUT";
 
-use clap::{Parser, Subcommand};
+use clap::{ArgEnum, Parser, Subcommand};
+use colored::*;
 use radix_engine::constants::*;
+use radix_engine::fee::{FeeDistribution, FeeSummary};
 use radix_engine::model::*;
 use radix_engine::transaction::TransactionExecutor;
 use radix_engine::transaction::TransactionOutcome;
@@ -56,13 +88,21 @@ use radix_engine::transaction::TransactionResult;
 use radix_engine::transaction::{ExecutionConfig, FeeReserveConfig};
 use radix_engine::types::*;
 use radix_engine::wasm::*;
+use radix_engine_stores::indexed_store::IndexedSubstateStore;
 use radix_engine_stores::rocks_db::RadixEngineDB;
+use radix_engine_stores::substate_history::RetentionPolicy;
+use sbor::describe::Fields;
 use scrypto::abi;
+use std::cell::{RefCell, RefMut};
 use std::env;
 use std::fs;
+use std::io::IsTerminal;
+use std::ops::{Deref, DerefMut};
 use std::path::PathBuf;
+use std::rc::Rc;
 use transaction::builder::ManifestBuilder;
 use transaction::manifest::decompile;
+use transaction::model::ExecutableTransaction;
 use transaction::model::TestTransaction;
 use transaction::model::TransactionManifest;
 use transaction::signing::EcdsaSecp256k1PrivateKey;
@@ -71,6 +111,18 @@ use transaction::signing::EcdsaSecp256k1PrivateKey;
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None, name = "resim")]
 pub struct ResimCli {
+    /// The output format, [text | json]
+    #[clap(long, global = true, arg_enum, default_value = "text")]
+    pub(crate) output: OutputFormat,
+
+    /// The maximum XRD fee a transaction may charge before it is aborted instead of committed
+    #[clap(long, global = true)]
+    pub(crate) fee_budget: Option<Decimal>,
+
+    /// The environment to use instead of the current one
+    #[clap(long, global = true)]
+    pub(crate) env: Option<String>,
+
     #[clap(subcommand)]
     pub(crate) command: Command,
 }
@@ -81,60 +133,171 @@ impl ResimCli {
     }
 }
 
+/// The format in which commands print their results.
+#[derive(ArgEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// The global options of a resim invocation, which every command runs with.
+#[derive(Clone, Default)]
+pub struct CommandContext {
+    pub output: OutputFormat,
+    /// The maximum XRD fee of a transaction, if any
+    pub fee_budget: Option<Decimal>,
+    /// The environment to use instead of the current one, if any
+    pub env: Option<String>,
+    /// The resources kept open across the commands of this session
+    pub session: Rc<Session>,
+}
+
+/// The ledger and WASM engine of a session, which stay open across its commands so that the
+/// commands of a repl don't set them up again one by one.
+pub struct Session {
+    ledger: RefCell<Option<(PathBuf, Ledger)>>,
+    wasm_engine: RefCell<DefaultWasmEngine>,
+    wasm_instrumenter: RefCell<WasmInstrumenter>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            ledger: RefCell::new(None),
+            wasm_engine: RefCell::new(DefaultWasmEngine::new()),
+            wasm_instrumenter: RefCell::new(WasmInstrumenter::new()),
+        }
+    }
+}
+
+impl Session {
+    /// Closes the ledger, if open, releasing its lock on the data directory.
+    pub fn close_ledger(&self) {
+        self.ledger.borrow_mut().take();
+    }
+}
+
+impl CommandContext {
+    /// Returns the context of a command line run from within this context, whose own options
+    /// take precedence.
+    pub fn for_cli(&self, cli: &ResimCli) -> Self {
+        Self {
+            output: cli.output,
+            fee_budget: cli.fee_budget.or(self.fee_budget),
+            env: cli.env.clone().or_else(|| self.env.clone()),
+            session: self.session.clone(),
+        }
+    }
+
+    /// Whether commands should print a JSON result instead of console text.
+    pub fn is_json_output(&self) -> bool {
+        self.output == OutputFormat::Json
+    }
+}
+
+pub fn write_json<O: std::io::Write>(out: &mut O, value: &serde_json::Value) -> Result<(), Error> {
+    writeln!(
+        out,
+        "{}",
+        serde_json::to_string_pretty(value).map_err(Error::JSONError)?
+    )
+    .map_err(Error::IOError)
+}
+
 #[derive(Subcommand, Debug)]
 pub enum Command {
+    Account(Account),
+    AssertBalance(AssertBalance),
+    AssertState(AssertState),
     CallFunction(CallFunction),
     CallMethod(CallMethod),
+    Diff(Diff),
+    Env(Env),
     ExportAbi(ExportAbi),
+    Faucet(Faucet),
     GenerateKeyPair(GenerateKeyPair),
+    History(History),
     Mint(Mint),
     NewAccount(NewAccount),
     NewBadgeFixed(NewBadgeFixed),
     NewBadgeMutable(NewBadgeMutable),
+    NewTokenAdvanced(NewTokenAdvanced),
     NewTokenFixed(NewTokenFixed),
     NewTokenMutable(NewTokenMutable),
     Publish(Publish),
+    PublishWorkspace(PublishWorkspace),
+    Receipt(Receipt),
+    Repl(Repl),
     Reset(Reset),
     Run(Run),
     SetCurrentEpoch(SetCurrentEpoch),
     SetDefaultAccount(SetDefaultAccount),
     ShowConfigs(ShowConfigs),
     ShowLedger(ShowLedger),
+    ShowNft(ShowNft),
     Show(Show),
+    Snapshot(Snapshot),
     Transfer(Transfer),
 }
 
 pub fn run() -> Result<(), Error> {
-    let cli = ResimCli::parse();
-
-    let mut out = std::io::stdout();
+    run_cli(
+        ResimCli::parse(),
+        &CommandContext::default(),
+        &mut std::io::stdout(),
+    )
+}
 
+/// Runs a parsed command line within the given context, overriding it with the global options of
+/// the command line.
+pub fn run_cli<O: std::io::Write>(
+    cli: ResimCli,
+    ctx: &CommandContext,
+    out: &mut O,
+) -> Result<(), Error> {
+    let ctx = &ctx.for_cli(&cli);
     match cli.command {
-        Command::CallFunction(cmd) => cmd.run(&mut out),
-        Command::CallMethod(cmd) => cmd.run(&mut out),
-        Command::ExportAbi(cmd) => cmd.run(&mut out),
-        Command::GenerateKeyPair(cmd) => cmd.run(&mut out),
-        Command::Mint(cmd) => cmd.run(&mut out),
-        Command::NewAccount(cmd) => cmd.run(&mut out),
-        Command::NewBadgeFixed(cmd) => cmd.run(&mut out),
-        Command::NewBadgeMutable(cmd) => cmd.run(&mut out),
-        Command::NewTokenFixed(cmd) => cmd.run(&mut out),
-        Command::NewTokenMutable(cmd) => cmd.run(&mut out),
-        Command::Publish(cmd) => cmd.run(&mut out),
-        Command::Reset(cmd) => cmd.run(&mut out),
-        Command::Run(cmd) => cmd.run(&mut out),
-        Command::SetCurrentEpoch(cmd) => cmd.run(&mut out),
-        Command::SetDefaultAccount(cmd) => cmd.run(&mut out),
-        Command::ShowConfigs(cmd) => cmd.run(&mut out),
-        Command::ShowLedger(cmd) => cmd.run(&mut out),
-        Command::Show(cmd) => cmd.run(&mut out),
-        Command::Transfer(cmd) => cmd.run(&mut out),
+        Command::Account(cmd) => cmd.run(ctx, out),
+        Command::AssertBalance(cmd) => cmd.run(ctx, out),
+        Command::AssertState(cmd) => cmd.run(ctx, out),
+        Command::CallFunction(cmd) => cmd.run(ctx, out),
+        Command::CallMethod(cmd) => cmd.run(ctx, out),
+        Command::Diff(cmd) => cmd.run(ctx, out),
+        Command::Env(cmd) => cmd.run(ctx, out),
+        Command::ExportAbi(cmd) => cmd.run(ctx, out),
+        Command::Faucet(cmd) => cmd.run(ctx, out),
+        Command::GenerateKeyPair(cmd) => cmd.run(ctx, out),
+        Command::History(cmd) => cmd.run(ctx, out),
+        Command::Mint(cmd) => cmd.run(ctx, out),
+        Command::NewAccount(cmd) => cmd.run(ctx, out),
+        Command::NewBadgeFixed(cmd) => cmd.run(ctx, out),
+        Command::NewBadgeMutable(cmd) => cmd.run(ctx, out),
+        Command::NewTokenAdvanced(cmd) => cmd.run(ctx, out),
+        Command::NewTokenFixed(cmd) => cmd.run(ctx, out),
+        Command::NewTokenMutable(cmd) => cmd.run(ctx, out),
+        Command::Publish(cmd) => cmd.run(ctx, out),
+        Command::PublishWorkspace(cmd) => cmd.run(ctx, out),
+        Command::Receipt(cmd) => cmd.run(ctx, out),
+        Command::Repl(cmd) => cmd.run(ctx, out),
+        Command::Reset(cmd) => cmd.run(ctx, out),
+        Command::Run(cmd) => cmd.run(ctx, out),
+        Command::SetCurrentEpoch(cmd) => cmd.run(ctx, out),
+        Command::SetDefaultAccount(cmd) => cmd.run(ctx, out),
+        Command::ShowConfigs(cmd) => cmd.run(ctx, out),
+        Command::ShowLedger(cmd) => cmd.run(ctx, out),
+        Command::ShowNft(cmd) => cmd.run(ctx, out),
+        Command::Show(cmd) => cmd.run(ctx, out),
+        Command::Snapshot(cmd) => cmd.run(ctx, out),
+        Command::Transfer(cmd) => cmd.run(ctx, out),
     }
 }
 
 pub fn handle_manifest<O: std::io::Write>(
+    ctx: &CommandContext,
     manifest: TransactionManifest,
     signing_keys: &Option<String>,
+    account: &Option<String>,
     network: &Option<String>,
     manifest_path: &Option<PathBuf>,
     trace: bool,
@@ -164,71 +327,141 @@ pub fn handle_manifest<O: std::io::Write>(
             Ok(None)
         }
         None => {
-            let mut substate_store = RadixEngineDB::with_bootstrap(get_data_dir()?);
-            let mut wasm_engine = DefaultWasmEngine::new();
-            let mut wasm_instrumenter = WasmInstrumenter::new();
-            let mut executor = TransactionExecutor::new(
-                &mut substate_store,
-                &mut wasm_engine,
-                &mut wasm_instrumenter,
-            );
-
-            let sks = get_signing_keys(signing_keys)?;
-            let pks = sks
-                .iter()
-                .map(|e| e.public_key().into())
-                .collect::<Vec<PublicKey>>();
-            let nonce = get_nonce()?;
-            let transaction = TestTransaction::new(manifest, nonce, pks);
-
-            let receipt = executor.execute_and_commit(
-                &transaction,
-                &FeeReserveConfig {
-                    cost_unit_price: DEFAULT_COST_UNIT_PRICE.parse().unwrap(),
-                    system_loan: DEFAULT_SYSTEM_LOAN,
-                },
-                &ExecutionConfig {
-                    max_call_depth: DEFAULT_MAX_CALL_DEPTH,
-                    trace,
-                },
-            );
+            let receipt = execute_manifest(ctx, manifest, signing_keys, account, trace)?;
+            process_receipt(ctx, receipt, output_receipt, out)
+        }
+    }
+}
 
-            if output_receipt {
-                writeln!(out, "{:?}", receipt).map_err(Error::IOError)?;
-            }
+/// Executes a manifest against the local ledger and commits the outcome.
+pub fn execute_manifest(
+    ctx: &CommandContext,
+    manifest: TransactionManifest,
+    signing_keys: &Option<String>,
+    account: &Option<String>,
+    trace: bool,
+) -> Result<TransactionReceipt, Error> {
+    let mut substate_store = open_ledger(ctx)?;
+    let mut wasm_engine = ctx.session.wasm_engine.borrow_mut();
+    let mut wasm_instrumenter = ctx.session.wasm_instrumenter.borrow_mut();
+    let mut executor = TransactionExecutor::new(
+        &mut *substate_store,
+        &mut *wasm_engine,
+        &mut wasm_instrumenter,
+    );
 
-            if receipt.is_commit() {
-                let mut configs = get_configs()?;
-                configs.nonce = nonce + 1;
-                set_configs(&configs)?;
-                return Ok(Some(receipt));
-            }
+    let sks = get_signing_keys(ctx, signing_keys, account)?;
+    let pks = sks
+        .iter()
+        .map(|e| e.public_key().into())
+        .collect::<Vec<PublicKey>>();
+    let nonce = get_nonce(ctx)?;
+    let manifest_str = decompile(&manifest.instructions, &NetworkDefinition::simulator())
+        .map_err(Error::DecompileError)?;
+    let transaction = TestTransaction::new(manifest, nonce, pks);
 
-            match receipt.result {
-                TransactionResult::Commit(commit) => match commit.outcome {
-                    TransactionOutcome::Failure(error) => {
-                        Err(Error::TransactionExecutionError(error))
-                    }
-                    TransactionOutcome::Success(..) => {
-                        panic!("Success case handled above to appease borrowing rules")
-                    }
-                },
-                TransactionResult::Reject(rejection) => {
-                    Err(Error::TransactionRejected(rejection.error))
-                }
+    let receipt = executor.execute(
+        &transaction,
+        &FeeReserveConfig {
+            system_loan: DEFAULT_SYSTEM_LOAN,
+            distribution: FeeDistribution::default(),
+            free_credit: 0,
+        },
+        &ExecutionConfig {
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            max_heap_size: DEFAULT_MAX_HEAP_SIZE,
+            trace,
+            wasm_metering_params: WasmMeteringParams::standard(),
+        },
+    );
+    if let TransactionResult::Commit(commit) = &receipt.result {
+        let fee = receipt.execution.fee_summary.total_fee();
+        if let Some(fee_budget) = ctx.fee_budget {
+            if fee > fee_budget {
+                return Err(Error::FeeBudgetExceeded(fee, fee_budget));
             }
         }
+        substate_store.commit_state_diff(&commit.state_updates);
+
+        let mut configs = get_configs(ctx)?;
+        configs.nonce = nonce + 1;
+        set_configs(ctx, &configs)?;
+    }
+
+    append_history(ctx, transaction.transaction_hash(), &manifest_str, &receipt)?;
+
+    Ok(receipt)
+}
+
+/// Prints the receipt if requested, and turns a failed or rejected transaction into an error.
+pub fn process_receipt<O: std::io::Write>(
+    ctx: &CommandContext,
+    receipt: TransactionReceipt,
+    output_receipt: bool,
+    out: &mut O,
+) -> Result<Option<TransactionReceipt>, Error> {
+    if output_receipt {
+        if ctx.is_json_output() {
+            write_json(out, &receipt.to_json(&NetworkDefinition::simulator()))?;
+        } else {
+            writeln!(out, "{:?}", receipt).map_err(Error::IOError)?;
+        }
+    }
+    if !ctx.is_json_output() {
+        write_fee_report(&receipt.execution.fee_summary, out)?;
+    }
+
+    if receipt.is_commit() {
+        return Ok(Some(receipt));
+    }
+
+    match receipt.result {
+        TransactionResult::Commit(commit) => match commit.outcome {
+            TransactionOutcome::Failure(error) => Err(Error::TransactionExecutionError(error)),
+            TransactionOutcome::Success(..) => {
+                panic!("Success case handled above to appease borrowing rules")
+            }
+        },
+        TransactionResult::Reject(rejection) => Err(Error::TransactionRejected(rejection.error)),
     }
 }
 
+/// Prints the cost units consumed by category, and the XRD fee charged.
+pub fn write_fee_report<O: std::io::Write>(
+    fee_summary: &FeeSummary,
+    out: &mut O,
+) -> Result<(), Error> {
+    writeln!(out, "{}", "Fee Report:".bold().green()).map_err(Error::IOError)?;
+    let breakdown = fee_summary.cost_breakdown_by_category();
+    for (category, cost_units) in &breakdown {
+        writeln!(out, "├─ {:?}: {} cost units", category, cost_units).map_err(Error::IOError)?;
+    }
+    writeln!(
+        out,
+        "└─ Total: {} cost units, {} XRD",
+        fee_summary.cost_unit_consumed,
+        fee_summary.total_fee()
+    )
+    .map_err(Error::IOError)
+}
+
 pub fn get_signing_keys(
+    ctx: &CommandContext,
     signing_keys: &Option<String>,
+    account: &Option<String>,
 ) -> Result<Vec<EcdsaSecp256k1PrivateKey>, Error> {
     let private_keys = if let Some(keys) = signing_keys {
+        let configs = get_configs(ctx)?;
         keys.split(",")
             .map(str::trim)
             .filter(|s| !s.is_empty())
             .map(|key| {
+                // A key may also be given by the name of an account
+                let key = configs
+                    .accounts
+                    .get(key)
+                    .map(|profile| profile.private_key.as_str())
+                    .unwrap_or(key);
                 hex::decode(key)
                     .map_err(|_| Error::InvalidPrivateKey)
                     .and_then(|bytes| {
@@ -238,25 +471,198 @@ pub fn get_signing_keys(
             })
             .collect::<Result<Vec<EcdsaSecp256k1PrivateKey>, Error>>()?
     } else {
-        vec![get_default_private_key()?]
+        vec![get_private_key(ctx, account)?]
     };
 
     Ok(private_keys)
 }
 
+/// The local ledger, which keeps the history of every substate, an index of the vaults owned by
+/// each component and a state tree.
+pub type Ledger = IndexedSubstateStore<RadixEngineDB>;
+
+fn index_ledger(substate_store: RadixEngineDB) -> Ledger {
+    IndexedSubstateStore::new(substate_store)
+        .with_history(RetentionPolicy::KeepAll)
+        .with_account_index()
+        .with_state_tree()
+}
+
+/// A ledger opened by a command, either kept open by the session or a secondary instance owned
+/// by the command.
+pub enum OpenLedger<'a> {
+    Session(RefMut<'a, Ledger>),
+    Secondary(SecondaryLedger),
+}
+
+impl<'a> Deref for OpenLedger<'a> {
+    type Target = Ledger;
+
+    fn deref(&self) -> &Ledger {
+        match self {
+            OpenLedger::Session(ledger) => ledger,
+            OpenLedger::Secondary(ledger) => ledger,
+        }
+    }
+}
+
+impl<'a> DerefMut for OpenLedger<'a> {
+    fn deref_mut(&mut self) -> &mut Ledger {
+        match self {
+            OpenLedger::Session(ledger) => ledger,
+            OpenLedger::Secondary(ledger) => &mut ledger.ledger,
+        }
+    }
+}
+
+/// Opens the ledger for writing, bootstrapping it if it doesn't exist yet.
+///
+/// The ledger stays open for the rest of the session, until the data directory changes.
+pub fn open_ledger(ctx: &CommandContext) -> Result<RefMut<'_, Ledger>, Error> {
+    let data_dir = get_data_dir(ctx)?;
+    let mut ledger = ctx.session.ledger.borrow_mut();
+    if !matches!(&*ledger, Some((dir, _)) if *dir == data_dir) {
+        // Release the lock on the previous data directory first
+        ledger.take();
+        let substate_store = index_ledger(RadixEngineDB::new(data_dir.clone())).bootstrap();
+        *ledger = Some((data_dir, substate_store));
+    }
+    Ok(RefMut::map(ledger, |ledger| {
+        &mut ledger.as_mut().expect("Ledger opened above").1
+    }))
+}
+
+/// Opens the ledger for reading, without taking the lock held by another process writing to it.
+///
+/// Reads go through the ledger of the session instead if it's open already.
+pub fn open_ledger_read_only(ctx: &CommandContext) -> Result<OpenLedger<'_>, Error> {
+    let data_dir = get_data_dir(ctx)?;
+    let session_ledger = ctx.session.ledger.borrow();
+    let is_open = matches!(&*session_ledger, Some((dir, _)) if *dir == data_dir);
+    drop(session_ledger);
+    if RadixEngineDB::exists(&data_dir) && !is_open {
+        let secondary_dir =
+            std::env::temp_dir().join(format!("resim-secondary-{}", std::process::id()));
+        Ok(OpenLedger::Secondary(SecondaryLedger::open(
+            data_dir,
+            secondary_dir,
+        )))
+    } else {
+        open_ledger(ctx).map(OpenLedger::Session)
+    }
+}
+
+/// Opens a secondary instance of the ledger at `root`, see `RadixEngineDB::open_as_secondary`.
+pub fn open_secondary(root: PathBuf, secondary_root: PathBuf) -> Ledger {
+    index_ledger(RadixEngineDB::open_as_secondary(root, secondary_root))
+}
+
+/// A secondary instance of a ledger, whose scratch directory is removed once it's dropped.
+pub struct SecondaryLedger {
+    ledger: Ledger,
+    // Declared after the ledger, so that it's closed before its directory is removed
+    _secondary_dir: ScratchDir,
+}
+
+impl SecondaryLedger {
+    pub fn open(root: PathBuf, secondary_root: PathBuf) -> Self {
+        Self {
+            ledger: open_secondary(root, secondary_root.clone()),
+            _secondary_dir: ScratchDir(secondary_root),
+        }
+    }
+}
+
+impl Deref for SecondaryLedger {
+    type Target = Ledger;
+
+    fn deref(&self) -> &Ledger {
+        &self.ledger
+    }
+}
+
+struct ScratchDir(PathBuf);
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        // Nothing is lost if this fails, the directory is recreated on the next open
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
 pub fn export_abi(
+    ctx: &CommandContext,
     package_address: PackageAddress,
     blueprint_name: &str,
 ) -> Result<abi::BlueprintAbi, Error> {
-    let mut substate_store = RadixEngineDB::with_bootstrap(get_data_dir()?);
-    radix_engine::model::export_abi(&mut substate_store, package_address, blueprint_name)
+    let mut substate_store = open_ledger(ctx)?;
+    radix_engine::model::export_abi(&mut *substate_store, package_address, blueprint_name)
         .map_err(Error::AbiExportError)
 }
 
 pub fn export_abi_by_component(
+    ctx: &CommandContext,
     component_address: ComponentAddress,
 ) -> Result<abi::BlueprintAbi, Error> {
-    let mut substate_store = RadixEngineDB::with_bootstrap(get_data_dir()?);
-    radix_engine::model::export_abi_by_component(&mut substate_store, component_address)
+    let mut substate_store = open_ledger(ctx)?;
+    radix_engine::model::export_abi_by_component(&mut *substate_store, component_address)
         .map_err(Error::AbiExportError)
 }
+
+/// Completes the arguments of a blueprint function or method call, prompting for any missing
+/// ones when running in a terminal and failing with the expected parameters otherwise.
+pub fn complete_arguments(
+    blueprint_abi: &abi::BlueprintAbi,
+    ident: &str,
+    arguments: &[String],
+) -> Result<Vec<String>, Error> {
+    let parameters = match blueprint_abi.fns.iter().find(|f| f.ident == ident) {
+        Some(abi::Fn {
+            input:
+                Type::Struct {
+                    fields: Fields::Named { named },
+                    ..
+                },
+            ..
+        }) => named,
+        // Leave it to the manifest builder to report unknown functions and bad ABIs
+        _ => return Ok(arguments.to_vec()),
+    };
+    if arguments.len() >= parameters.len() {
+        return Ok(arguments.to_vec());
+    }
+
+    if !std::io::stdin().is_terminal() {
+        let usage: Vec<String> = parameters
+            .iter()
+            .map(|(name, ty)| format!("<{}: {}>", name, type_name(ty)))
+            .collect();
+        return Err(Error::MissingArguments(format!(
+            "{} {}",
+            ident,
+            usage.join(" ")
+        )));
+    }
+
+    let mut arguments = arguments.to_vec();
+    for (name, ty) in &parameters[arguments.len()..] {
+        eprint!("{} ({}): ", name, type_name(ty));
+        std::io::Write::flush(&mut std::io::stderr()).map_err(Error::IOError)?;
+        let mut line = String::new();
+        std::io::stdin()
+            .read_line(&mut line)
+            .map_err(Error::IOError)?;
+        arguments.push(line.trim().to_owned());
+    }
+    Ok(arguments)
+}
+
+fn type_name(ty: &Type) -> String {
+    match ty {
+        Type::Custom { type_id, .. } => ScryptoType::from_id(*type_id)
+            .map(|t| t.name())
+            .unwrap_or_else(|| format!("{:?}", ty)),
+        Type::Struct { name, .. } | Type::Enum { name, .. } => name.clone(),
+        _ => format!("{:?}", ty),
+    }
+}
@@ -13,30 +13,75 @@ temp=`$resim new-account | awk '/Account component address:/ {print $NF}'`
 account=`echo $temp | cut -d " " -f1`
 account2=`$resim new-account | awk '/Account component address:/ {print $NF}'`
 
+# Test - faucet
+empty_account=`$resim new-account --empty | awk '/Account component address:/ {print $NF}'`
+$resim faucet $empty_account --amount 2500.5
+$resim assert-balance $empty_account resource_sim1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqzqu57yag 2500.5
+$resim faucet --amount 1000000 && exit 1 || true
+
+# Test - named accounts
+$resim account create alice
+$resim account rename alice bob
+$resim account switch bob
+$resim account list
+$resim transfer 1 resource_sim1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqzqu57yag $account2
+$resim account switch account1
+$resim transfer 1 resource_sim1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqzqu57yag $account2 --account bob
+
 # Test - create fixed supply badge
 minter_badge=`$resim new-badge-fixed 1 --name 'MintBadge' | awk '/Resource:/ {print $NF}'`
 
 # Test - create mutable supply token
 token_address=`$resim new-token-mutable $minter_badge | awk '/Resource:/ {print $NF}'`
 
+# Test - create token with custom permissions
+restricted_token=`$resim new-token-advanced --initial-supply 100 --divisibility 2 --mintable $minter_badge --burnable $minter_badge --restrict-withdraw $minter_badge | awk '/Resource:/ {print $NF}'`
+
+# Test - transfer restricted token, proving the badge automatically
+$resim transfer 10 $restricted_token $account2
+
 # Test - mint and transfer
 $resim mint 777 $token_address --proofs 1,$minter_badge
 $resim transfer 111 $token_address $account2
 
+# Test - assertions
+$resim assert-balance $account2 $token_address 111
+$resim assert-balance $account2 $token_address 1 && exit 1 || true
+
 # Test - publish, call-funciton and call-method
 package=`$resim publish ../examples/hello-world | awk '/Package:/ {print $NF}'`
 component=`$resim call-function $package Hello instantiate_hello | awk '/Component:/ {print $NF}'`
 $resim call-method $component free_token
+$resim assert-state $component no_such_field 1 && exit 1 || true
+
+# Test - publish-workspace and call-function by package name
+$resim publish-workspace ../examples/hello-world
+$resim call-function hello-world Hello instantiate_hello
+
+# Test - JSON output
+$resim show $account --output json
+$resim show-ledger --output json
+$resim call-method $component free_token --output json
+
+# Test - transaction history
+$resim history --limit 5
+hash=`$resim history --limit 1 | awk 'NR==1 {print $1}'`
+$resim receipt $hash
 
 # Test - export abi
 $resim export-abi $package Hello
+$resim export-abi $package Hello --bindings typescript
+$resim export-abi $package Hello --bindings rust
 
 # Test - dump component state
 $resim show $package
 $resim show $component
+$resim show $component --field sample_vault
 $resim show $account
 $resim show $account2
 $resim show $token_address
+$resim show $account --at-version 1
+$resim show $account --at-version 1000000 && exit 1 || true
 
 # Test - output manifest
 $resim new-badge-fixed 1 --name 'MintBadge' --manifest ./target/temp.rtm
@@ -46,7 +91,8 @@ files=`ls target/*.blob`
 blobs=`echo $files | sed 's/ / --blobs /g'`
 $resim run ./target/temp2.rtm --blobs $blobs
 $resim new-account --manifest ./target/temp3.rtm
-$resim run ./target/temp3.rtm
+$resim run ./target/temp3.rtm --receipt ./target/temp3.json
+cat ./target/temp3.json
 
 # Test - run manifest with a given set of signing keys
 $resim generate-key-pair
@@ -54,8 +100,9 @@ $resim run ./target/temp2.rtm --blobs $blobs
 
 # Test - nft
 package=`$resim publish ./tests/blueprints | awk '/Package:/ {print $NF}'`
-$resim call-function $package Foo nfts
+nft_resource=`$resim call-function $package Foo nfts | awk '/Resource:/ {print $NF}'`
 $resim show $account
+$resim show-nft $nft_resource
 
 # Test - proofs
 proofs_receipt=`$resim call-function $package Proofs new`
@@ -72,8 +119,32 @@ $resim call-method $component organizational_authenticated_method --proofs 1,$su
 $resim transfer 2 $token $account2 --proofs 1,$supervisor_badge 1,$admin_badge 1,$superadmin_badge
 $resim mint 100000 $token --proofs 1,$supervisor_badge 1,$admin_badge 1,$superadmin_badge
 
+# Test - repl
+printf 'show-configs\nmanifest add CALL_METHOD ComponentAddress("%s") "lock_fee" Decimal("10")\nmanifest show\nmanifest run\nexit\n' $account | $resim repl
+
+# Test - snapshots
+$resim snapshot save before-large-package
+$resim snapshot list
+$resim snapshot save ../escape && exit 1 || true
+
 # Test - publishing a large package
 $resim publish ./tests/large_package.wasm
 
 # Test - math types and numbers
-$resim call-function $package "Numbers" test_input 1 2
\ No newline at end of file
+$resim call-function $package "Numbers" test_input 1 2
+
+# Test - diff component state against snapshot
+$resim diff $component --from before-large-package
+
+# Test - restore snapshot
+$resim snapshot restore before-large-package
+$resim show $account
+
+# Test - environments
+$resim env create scratch
+$resim env use scratch
+$resim new-account
+$resim env list
+$resim show $account --env default
+$resim env use default
+$resim show $account
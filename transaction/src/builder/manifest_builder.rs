@@ -10,11 +10,11 @@ use sbor::*;
 use scrypto::abi::*;
 use scrypto::address::Bech32Decoder;
 use scrypto::buffer::*;
-use scrypto::component::{ComponentAddress, PackageAddress};
+use scrypto::component::{ComponentAddress, PackageAddress, PackagePublishWithOwnerInput};
 use scrypto::constants::*;
 use scrypto::core::{
-    Blob, BucketFnIdentifier, FnIdentifier, NativeFnIdentifier, NetworkDefinition, Receiver,
-    ResourceManagerFnIdentifier,
+    Blob, BucketFnIdentifier, FnIdentifier, NativeFnIdentifier, NetworkDefinition,
+    PackageFnIdentifier, Receiver, ResourceManagerFnIdentifier,
 };
 use scrypto::crypto::*;
 use scrypto::engine::types::*;
@@ -41,8 +41,8 @@ pub struct ManifestBuilder {
     id_validator: IdValidator,
     /// Instructions generated.
     instructions: Vec<Instruction>,
-    /// Blobs
-    blobs: HashMap<Hash, Vec<u8>>,
+    /// Blobs, by hash
+    blobs: BTreeMap<Hash, Vec<u8>>,
 }
 
 impl ManifestBuilder {
@@ -52,7 +52,7 @@ impl ManifestBuilder {
             decoder: Bech32Decoder::new(network),
             id_validator: IdValidator::new(),
             instructions: Vec::new(),
-            blobs: HashMap::default(),
+            blobs: BTreeMap::default(),
         }
     }
 
@@ -117,6 +117,10 @@ impl ManifestBuilder {
                 self.id_validator.move_resources(&scrypt_value).unwrap();
             }
             Instruction::PublishPackage { .. } => {}
+            Instruction::ExecuteSubintent { .. } => {}
+            Instruction::ReturnWorktopContents => {}
+            Instruction::CreateProofFromAccountByAmount { .. } => {}
+            Instruction::CreateProofFromAccountByIds { .. } => {}
         }
 
         self.instructions.push(inst);
@@ -206,6 +210,11 @@ impl ManifestBuilder {
         .0
     }
 
+    /// Returns the resources on the worktop, for inspection in the receipt.
+    pub fn return_worktop_contents(&mut self) -> &mut Self {
+        self.add_instruction(Instruction::ReturnWorktopContents).0
+    }
+
     /// Pops the most recent proof from auth zone.
     pub fn pop_from_auth_zone<F>(&mut self, then: F) -> &mut Self
     where
@@ -305,6 +314,12 @@ impl ManifestBuilder {
         self.add_instruction(Instruction::DropAllProofs).0
     }
 
+    /// Runs the subintent with the given index, which the transaction must carry.
+    pub fn execute_subintent(&mut self, index: u32) -> &mut Self {
+        self.add_instruction(Instruction::ExecuteSubintent { index })
+            .0
+    }
+
     pub fn create_resource(
         &mut self,
         resource_type: ResourceType,
@@ -371,8 +386,9 @@ impl ManifestBuilder {
             .map(Clone::clone)
             .ok_or_else(|| BuildCallWithAbiError::FunctionNotFound(function.to_owned()))?;
 
+        let mut proof_buckets = Vec::new();
         let arguments = self
-            .parse_args(&abi.input, args, account)
+            .parse_args(&abi.input, args, account, &mut proof_buckets)
             .map_err(|e| BuildCallWithAbiError::FailedToBuildArgs(e))?;
 
         let mut fields = Vec::new();
@@ -382,16 +398,16 @@ impl ManifestBuilder {
         let input_struct = ::sbor::Value::Struct { fields };
         let bytes = ::sbor::encode_any(&input_struct);
 
-        Ok(self
-            .add_instruction(Instruction::CallFunction {
-                fn_identifier: FnIdentifier::Scrypto {
-                    package_address,
-                    blueprint_name: blueprint_name.to_string(),
-                    ident: function.to_string(),
-                },
-                args: bytes,
-            })
-            .0)
+        self.add_instruction(Instruction::CallFunction {
+            fn_identifier: FnIdentifier::Scrypto {
+                package_address,
+                blueprint_name: blueprint_name.to_string(),
+                ident: function.to_string(),
+            },
+            args: bytes,
+        });
+        self.return_proof_buckets(proof_buckets);
+        Ok(self)
     }
 
     /// Calls a scrypto method where the arguments should be an array of encoded Scrypto value.
@@ -450,19 +466,20 @@ impl ManifestBuilder {
             .map(Clone::clone)
             .ok_or_else(|| BuildCallWithAbiError::MethodNotFound(method.to_owned()))?;
 
+        let mut proof_buckets = Vec::new();
         let arguments = self
-            .parse_args(&abi.input, args, account)
+            .parse_args(&abi.input, args, account, &mut proof_buckets)
             .map_err(|e| BuildCallWithAbiError::FailedToBuildArgs(e))?;
 
-        Ok(self
-            .add_instruction(Instruction::CallMethod {
-                method_identifier: MethodIdentifier::Scrypto {
-                    component_address,
-                    ident: method.to_owned(),
-                },
-                args: args_from_bytes_vec!(arguments),
-            })
-            .0)
+        self.add_instruction(Instruction::CallMethod {
+            method_identifier: MethodIdentifier::Scrypto {
+                component_address,
+                ident: method.to_owned(),
+            },
+            args: args_from_bytes_vec!(arguments),
+        });
+        self.return_proof_buckets(proof_buckets);
+        Ok(self)
     }
 
     /// Publishes a package.
@@ -485,6 +502,34 @@ impl ManifestBuilder {
         .0
     }
 
+    /// Publishes a package owned by the given badge, or by a newly minted badge which is
+    /// put on the worktop if none is given.
+    pub fn publish_package_with_owner(
+        &mut self,
+        code: Vec<u8>,
+        abi: HashMap<String, BlueprintAbi>,
+        owner_badge: Option<NonFungibleAddress>,
+    ) -> &mut Self {
+        let code_hash = hash(&code);
+        self.blobs.insert(code_hash, code);
+
+        let abi = scrypto_encode(&abi);
+        let abi_hash = hash(&abi);
+        self.blobs.insert(abi_hash, abi);
+
+        self.add_instruction(Instruction::CallFunction {
+            fn_identifier: FnIdentifier::Native(NativeFnIdentifier::Package(
+                PackageFnIdentifier::PublishWithOwner,
+            )),
+            args: scrypto_encode(&PackagePublishWithOwnerInput {
+                code: Blob(code_hash),
+                abi: Blob(abi_hash),
+                owner_badge,
+            }),
+        })
+        .0
+    }
+
     /// Builds a transaction manifest.
     /// TODO: consider using self
     pub fn build(&self) -> TransactionManifest {
@@ -788,12 +833,10 @@ impl ManifestBuilder {
         resource_address: ResourceAddress,
         account: ComponentAddress,
     ) -> &mut Self {
-        self.add_instruction(Instruction::CallMethod {
-            method_identifier: MethodIdentifier::Scrypto {
-                component_address: account,
-                ident: "create_proof_by_amount".to_string(),
-            },
-            args: args!(amount, resource_address),
+        self.add_instruction(Instruction::CreateProofFromAccountByAmount {
+            account,
+            amount,
+            resource_address,
         })
         .0
     }
@@ -805,12 +848,10 @@ impl ManifestBuilder {
         resource_address: ResourceAddress,
         account: ComponentAddress,
     ) -> &mut Self {
-        self.add_instruction(Instruction::CallMethod {
-            method_identifier: MethodIdentifier::Scrypto {
-                component_address: account,
-                ident: "create_proof_by_ids".to_string(),
-            },
-            args: args!(ids.clone(), resource_address),
+        self.add_instruction(Instruction::CreateProofFromAccountByIds {
+            account,
+            ids: ids.clone(),
+            resource_address,
         })
         .0
     }
@@ -838,11 +879,20 @@ impl ManifestBuilder {
     // private methods below
     //===============================
 
+    /// Returns the buckets which were taken from worktop to create proofs for a call, once the
+    /// proofs have been consumed by it.
+    fn return_proof_buckets(&mut self, proof_buckets: Vec<BucketId>) {
+        for bucket_id in proof_buckets {
+            self.add_instruction(Instruction::ReturnToWorktop { bucket_id });
+        }
+    }
+
     fn parse_args(
         &mut self,
         arg_type: &Type,
         args: Vec<String>,
         account: Option<ComponentAddress>,
+        proof_buckets: &mut Vec<BucketId>,
     ) -> Result<Vec<Vec<u8>>, BuildArgsError> {
         let mut encoded = Vec::new();
 
@@ -869,7 +919,7 @@ impl ManifestBuilder {
                         Type::U128 => self.parse_basic_ty::<u128>(i, t, arg),
                         Type::String => self.parse_basic_ty::<String>(i, t, arg),
                         Type::Custom { type_id, .. } => {
-                            self.parse_custom_ty(i, t, arg, *type_id, account)
+                            self.parse_custom_ty(i, t, arg, *type_id, account, proof_buckets)
                         }
                         _ => Err(BuildArgsError::UnsupportedType(i, t.clone())),
                     };
@@ -906,6 +956,7 @@ impl ManifestBuilder {
         arg: &str,
         type_id: u8,
         account: Option<ComponentAddress>,
+        proof_buckets: &mut Vec<BucketId>,
     ) -> Result<Vec<u8>, BuildArgsError> {
         match ScryptoType::from_id(type_id).ok_or(BuildArgsError::UnsupportedType(i, ty.clone()))? {
             ScryptoType::Decimal => {
@@ -920,6 +971,42 @@ impl ManifestBuilder {
                     .map_err(|_| BuildArgsError::FailedToParse(i, ty.clone(), arg.to_owned()))?;
                 Ok(scrypto_encode(&value))
             }
+            ScryptoType::I256 => {
+                let value = arg
+                    .parse::<I256>()
+                    .map_err(|_| BuildArgsError::FailedToParse(i, ty.clone(), arg.to_owned()))?;
+                Ok(scrypto_encode(&value))
+            }
+            ScryptoType::I384 => {
+                let value = arg
+                    .parse::<I384>()
+                    .map_err(|_| BuildArgsError::FailedToParse(i, ty.clone(), arg.to_owned()))?;
+                Ok(scrypto_encode(&value))
+            }
+            ScryptoType::I512 => {
+                let value = arg
+                    .parse::<I512>()
+                    .map_err(|_| BuildArgsError::FailedToParse(i, ty.clone(), arg.to_owned()))?;
+                Ok(scrypto_encode(&value))
+            }
+            ScryptoType::U256 => {
+                let value = arg
+                    .parse::<U256>()
+                    .map_err(|_| BuildArgsError::FailedToParse(i, ty.clone(), arg.to_owned()))?;
+                Ok(scrypto_encode(&value))
+            }
+            ScryptoType::U384 => {
+                let value = arg
+                    .parse::<U384>()
+                    .map_err(|_| BuildArgsError::FailedToParse(i, ty.clone(), arg.to_owned()))?;
+                Ok(scrypto_encode(&value))
+            }
+            ScryptoType::U512 => {
+                let value = arg
+                    .parse::<U512>()
+                    .map_err(|_| BuildArgsError::FailedToParse(i, ty.clone(), arg.to_owned()))?;
+                Ok(scrypto_encode(&value))
+            }
             ScryptoType::PackageAddress => {
                 let value = self
                     .decoder
@@ -997,7 +1084,17 @@ impl ManifestBuilder {
                                 .2
                                 .unwrap()
                         } else {
-                            todo!("Take from worktop and create proof")
+                            let bucket_id = self
+                                .add_instruction(Instruction::TakeFromWorktopByAmount {
+                                    amount,
+                                    resource_address,
+                                })
+                                .1
+                                .unwrap();
+                            proof_buckets.push(bucket_id);
+                            self.add_instruction(Instruction::CreateProofFromBucket { bucket_id })
+                                .2
+                                .unwrap()
                         }
                     }
                     ResourceSpecifier::Ids(ids, resource_address) => {
@@ -1007,7 +1104,17 @@ impl ManifestBuilder {
                                 .2
                                 .unwrap()
                         } else {
-                            todo!("Take from worktop and create proof")
+                            let bucket_id = self
+                                .add_instruction(Instruction::TakeFromWorktopByIds {
+                                    ids,
+                                    resource_address,
+                                })
+                                .1
+                                .unwrap();
+                            proof_buckets.push(bucket_id);
+                            self.add_instruction(Instruction::CreateProofFromBucket { bucket_id })
+                                .2
+                                .unwrap()
                         }
                     }
                 };
@@ -1069,3 +1176,63 @@ fn parse_resource_specifier(
         Ok(ResourceSpecifier::Amount(amount, resource_address))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scrypto::address::Bech32Encoder;
+
+    fn blueprint_abi_with_proof_arg() -> abi::BlueprintAbi {
+        abi::BlueprintAbi {
+            structure: Type::Unit,
+            fns: vec![abi::Fn {
+                ident: "check".to_owned(),
+                mutability: Some(abi::SelfMutability::Immutable),
+                input: Type::Struct {
+                    name: "Check_Input".to_owned(),
+                    fields: Fields::Named {
+                        named: vec![(
+                            "proof".to_owned(),
+                            Type::Custom {
+                                type_id: ScryptoType::Proof.id(),
+                                generics: vec![],
+                            },
+                        )],
+                    },
+                },
+                output: Type::Unit,
+                export_name: "Check_check".to_owned(),
+                roles: vec![],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_call_method_with_abi_creates_proof_from_worktop() {
+        let network = NetworkDefinition::simulator();
+        let bech32_encoder = Bech32Encoder::new(&network);
+        let resource_specifier =
+            format!("1,{}", bech32_encoder.encode_resource_address(&RADIX_TOKEN));
+
+        let manifest = ManifestBuilder::new(&network)
+            .call_method_with_abi(
+                SYS_FAUCET_COMPONENT,
+                "check",
+                vec![resource_specifier],
+                None,
+                &blueprint_abi_with_proof_arg(),
+            )
+            .unwrap()
+            .build();
+
+        assert!(matches!(
+            manifest.instructions.as_slice(),
+            [
+                Instruction::TakeFromWorktopByAmount { .. },
+                Instruction::CreateProofFromBucket { bucket_id },
+                Instruction::CallMethod { .. },
+                Instruction::ReturnToWorktop { bucket_id: returned_bucket_id },
+            ] if bucket_id == returned_bucket_id
+        ));
+    }
+}
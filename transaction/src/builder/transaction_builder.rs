@@ -1,10 +1,12 @@
-use scrypto::{buffer::scrypto_encode, crypto::*};
+use scrypto::{core::NetworkDefinition, crypto::*};
 
+use crate::manifest::{decompile, DecompileError};
 use crate::{model::*, signing::Signer};
 
 pub struct TransactionBuilder {
     manifest: Option<TransactionManifest>,
     header: Option<TransactionHeader>,
+    subintents: Vec<SignedSubintent>,
     intent_signatures: Vec<SignatureWithPublicKey>,
     notary_signature: Option<Signature>,
 }
@@ -14,6 +16,7 @@ impl TransactionBuilder {
         Self {
             manifest: None,
             header: None,
+            subintents: vec![],
             intent_signatures: vec![],
             notary_signature: None,
         }
@@ -29,9 +32,15 @@ impl TransactionBuilder {
         self
     }
 
+    /// Adds a subintent, to be run by the `ExecuteSubintent` instruction with its index.
+    pub fn subintent(mut self, subintent: SignedSubintent) -> Self {
+        self.subintents.push(subintent);
+        self
+    }
+
     pub fn sign<S: Signer>(mut self, signer: &S) -> Self {
         let intent = self.transaction_intent();
-        let intent_payload = scrypto_encode(&intent);
+        let intent_payload = intent.to_bytes();
         self.intent_signatures.push(signer.sign(&intent_payload));
         self
     }
@@ -43,7 +52,7 @@ impl TransactionBuilder {
 
     pub fn notarize<S: Signer>(mut self, signer: &S) -> Self {
         let signed_intent = self.signed_transaction_intent();
-        let signed_intent_payload = scrypto_encode(&signed_intent);
+        let signed_intent_payload = signed_intent.to_bytes();
         self.notary_signature = Some(signer.sign(&signed_intent_payload).signature());
         self
     }
@@ -60,10 +69,20 @@ impl TransactionBuilder {
         }
     }
 
+    /// Decompiles the manifest into canonical manifest text, which can be saved as a `.rtm` file.
+    pub fn to_manifest_string(
+        &self,
+        network: &NetworkDefinition,
+    ) -> Result<String, DecompileError> {
+        let manifest = self.manifest.as_ref().expect("Manifest not specified");
+        decompile(&manifest.instructions, network)
+    }
+
     fn transaction_intent(&self) -> TransactionIntent {
         TransactionIntent {
             manifest: self.manifest.clone().expect("Manifest not specified"),
             header: self.header.clone().expect("Header not specified"),
+            subintents: self.subintents.clone(),
         }
     }
 
@@ -78,8 +97,6 @@ impl TransactionBuilder {
 
 #[cfg(test)]
 mod tests {
-    use scrypto::core::NetworkDefinition;
-
     use super::*;
     use crate::builder::*;
     use crate::signing::*;
@@ -99,6 +116,8 @@ mod tests {
                 notary_as_signatory: true,
                 cost_unit_limit: 1_000_000,
                 tip_percentage: 5,
+                declared_substates: None,
+                fee_payer: None,
             })
             .manifest(
                 ManifestBuilder::new(&NetworkDefinition::simulator())
@@ -111,4 +130,19 @@ mod tests {
         let bytes = transaction.to_bytes();
         NotarizedTransaction::from_slice(&bytes).unwrap();
     }
+
+    #[test]
+    fn manifest_string_of_built_manifest() {
+        let builder = TransactionBuilder::new().manifest(
+            ManifestBuilder::new(&NetworkDefinition::simulator())
+                .clear_auth_zone()
+                .drop_all_proofs()
+                .build(),
+        );
+
+        let manifest = builder
+            .to_manifest_string(&NetworkDefinition::simulator())
+            .unwrap();
+        assert_eq!(manifest, "CLEAR_AUTH_ZONE;\nDROP_ALL_PROOFS;\n");
+    }
 }
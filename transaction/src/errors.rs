@@ -2,6 +2,7 @@ use sbor::describe::Type;
 use sbor::rust::string::String;
 use sbor::*;
 use scrypto::component::{ComponentAddress, PackageAddress};
+use scrypto::crypto::Hash;
 use scrypto::engine::types::*;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -21,6 +22,7 @@ pub enum SignatureValidationError {
     InvalidIntentSignature,
     InvalidNotarySignature,
     DuplicateSigner,
+    MissingFeePayerSignature,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, TypeId)]
@@ -44,6 +46,30 @@ pub enum CallDataValidationError {
     KeyValueStoreNotAllowed(KeyValueStoreId),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlobValidationError {
+    /// An instruction references a blob which isn't attached to the manifest.
+    BlobNotFound(Hash),
+    /// The blob is attached to the manifest more than once.
+    DuplicateBlob(Hash),
+    /// The blob is attached to the manifest but no instruction references it.
+    UnreferencedBlob(Hash),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubintentValidationError {
+    HashRejected,
+    HeaderValidationError(HeaderValidationError),
+    SignatureValidationError(SignatureValidationError),
+    IdValidationError(IdValidationError),
+    CallDataValidationError(CallDataValidationError),
+    BlobValidationError(BlobValidationError),
+    /// The subintent contains an `ExecuteSubintent` instruction.
+    NestedSubintent,
+    /// The subintent is not executed exactly once by the transaction manifest.
+    InvalidExecutionCount(usize),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TransactionValidationError {
     TransactionTooLarge,
@@ -53,6 +79,11 @@ pub enum TransactionValidationError {
     SignatureValidationError(SignatureValidationError),
     IdValidationError(IdValidationError),
     CallDataValidationError(CallDataValidationError),
+    BlobValidationError(BlobValidationError),
+    SubintentNotFound(u32),
+    /// The subintent at the index is identical to an earlier one.
+    DuplicateSubintent(u32),
+    SubintentValidationError(u32, SubintentValidationError),
 }
 
 /// Represents an error when parsing arguments.
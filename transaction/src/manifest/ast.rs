@@ -108,6 +108,24 @@ pub enum Instruction {
         resource_address: Value,
         amount: Value,
     },
+
+    ExecuteSubintent {
+        index: Value,
+    },
+
+    ReturnWorktopContents,
+
+    CreateProofFromAccountByAmount {
+        account: Value,
+        amount: Value,
+        resource_address: Value,
+    },
+
+    CreateProofFromAccountByIds {
+        account: Value,
+        ids: Value,
+        resource_address: Value,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -145,6 +163,12 @@ pub enum Type {
     /* Custom types */
     Decimal,
     PreciseDecimal,
+    I256,
+    I384,
+    I512,
+    U256,
+    U384,
+    U512,
     PackageAddress,
     ComponentAddress,
     ResourceAddress,
@@ -187,6 +211,12 @@ pub enum Value {
 
     Decimal(Box<Value>),
     PreciseDecimal(Box<Value>),
+    I256(Box<Value>),
+    I384(Box<Value>),
+    I512(Box<Value>),
+    U256(Box<Value>),
+    U384(Box<Value>),
+    U512(Box<Value>),
     PackageAddress(Box<Value>),
     ComponentAddress(Box<Value>),
     ResourceAddress(Box<Value>),
@@ -226,6 +256,12 @@ impl Value {
             Value::Map(_, _, _) => Type::Map,
             Value::Decimal(_) => Type::Decimal,
             Value::PreciseDecimal(_) => Type::PreciseDecimal,
+            Value::I256(_) => Type::I256,
+            Value::I384(_) => Type::I384,
+            Value::I512(_) => Type::I512,
+            Value::U256(_) => Type::U256,
+            Value::U384(_) => Type::U384,
+            Value::U512(_) => Type::U512,
             Value::PackageAddress(_) => Type::PackageAddress,
             Value::ComponentAddress(_) => Type::ComponentAddress,
             Value::ResourceAddress(_) => Type::ResourceAddress,
@@ -1,4 +1,4 @@
-use sbor::rust::collections::HashMap;
+use sbor::rust::collections::BTreeMap;
 use scrypto::address::Bech32Decoder;
 use scrypto::core::NetworkDefinition;
 use scrypto::crypto::hash;
@@ -24,7 +24,7 @@ pub fn compile(
     let instructions = parser::Parser::new(tokens)
         .parse_manifest()
         .map_err(CompileError::ParserError)?;
-    let mut blobs_by_hash = HashMap::new();
+    let mut blobs_by_hash = BTreeMap::new();
     for blob in blobs {
         blobs_by_hash.insert(hash(&blob), blob);
     }
@@ -376,6 +376,39 @@ pub fn decompile(
                     code, abi
                 ));
             }
+            Instruction::ExecuteSubintent { index } => {
+                buf.push_str(&format!("EXECUTE_SUBINTENT {}u32;\n", index));
+            }
+            Instruction::ReturnWorktopContents => {
+                buf.push_str("RETURN_WORKTOP_CONTENTS;\n");
+            }
+            Instruction::CreateProofFromAccountByAmount {
+                account,
+                amount,
+                resource_address,
+            } => {
+                buf.push_str(&format!(
+                    "CREATE_PROOF_FROM_ACCOUNT_BY_AMOUNT ComponentAddress(\"{}\") Decimal(\"{}\") ResourceAddress(\"{}\");\n",
+                    bech32_encoder.encode_component_address(&account),
+                    amount,
+                    bech32_encoder.encode_resource_address(&resource_address)
+                ));
+            }
+            Instruction::CreateProofFromAccountByIds {
+                account,
+                ids,
+                resource_address,
+            } => {
+                buf.push_str(&format!(
+                    "CREATE_PROOF_FROM_ACCOUNT_BY_IDS ComponentAddress(\"{}\") Set<NonFungibleId>({}) ResourceAddress(\"{}\");\n",
+                    bech32_encoder.encode_component_address(&account),
+                    ids.iter()
+                        .map(|k| format!("NonFungibleId(\"{}\")", k))
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    bech32_encoder.encode_resource_address(&resource_address)
+                ));
+            }
         }
     }
 
@@ -425,4 +458,21 @@ PUBLISH_PACKAGE Blob("36dae540b7889956f1f1d8d46ba23e5e44bf5723aef2a8e6b698686c02
 "#
         )
     }
+
+    #[cfg(not(feature = "alloc"))]
+    #[test]
+    fn test_decompiled_manifest_recompiles() {
+        let network = NetworkDefinition::simulator();
+        let manifest_str = include_str!("../../examples/complex.rtm");
+        let blobs = vec![
+            include_bytes!("../../examples/code.blob").to_vec(),
+            include_bytes!("../../examples/abi.blob").to_vec(),
+        ];
+        let manifest = compile(manifest_str, &network, blobs.clone()).unwrap();
+
+        let decompiled = decompile(&manifest.instructions, &network).unwrap();
+        let recompiled = compile(&decompiled, &network, blobs).unwrap();
+
+        assert_eq!(manifest, recompiled);
+    }
 }
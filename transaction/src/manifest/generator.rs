@@ -1,4 +1,5 @@
 use sbor::any::{encode_any, Value};
+use sbor::rust::collections::BTreeMap;
 use sbor::rust::collections::BTreeSet;
 use sbor::rust::collections::HashMap;
 use sbor::rust::str::FromStr;
@@ -40,6 +41,7 @@ pub enum GeneratorError {
     InvalidResourceAddress(String),
     InvalidDecimal(String),
     InvalidPreciseDecimal(String),
+    InvalidInteger(String),
     InvalidHash(String),
     InvalidKeyValueStoreId(String),
     InvalidVaultId(String),
@@ -117,7 +119,7 @@ impl NameResolver {
 pub fn generate_manifest(
     instructions: &[ast::Instruction],
     bech32_decoder: &Bech32Decoder,
-    blobs: HashMap<Hash, Vec<u8>>,
+    blobs: BTreeMap<Hash, Vec<u8>>,
 ) -> Result<TransactionManifest, GeneratorError> {
     let mut id_validator = IdValidator::new();
     let mut name_resolver = NameResolver::new();
@@ -144,7 +146,7 @@ pub fn generate_instruction(
     id_validator: &mut IdValidator,
     resolver: &mut NameResolver,
     bech32_decoder: &Bech32Decoder,
-    blobs: &HashMap<Hash, Vec<u8>>,
+    blobs: &BTreeMap<Hash, Vec<u8>>,
 ) -> Result<Instruction, GeneratorError> {
     Ok(match instruction {
         ast::Instruction::TakeFromWorktop {
@@ -415,6 +417,28 @@ pub fn generate_instruction(
                 args: args!(input),
             }
         }
+        ast::Instruction::ExecuteSubintent { index } => Instruction::ExecuteSubintent {
+            index: generate_u32(index)?,
+        },
+        ast::Instruction::ReturnWorktopContents => Instruction::ReturnWorktopContents,
+        ast::Instruction::CreateProofFromAccountByAmount {
+            account,
+            amount,
+            resource_address,
+        } => Instruction::CreateProofFromAccountByAmount {
+            account: generate_component_address(account, bech32_decoder)?,
+            amount: generate_decimal(amount)?,
+            resource_address: generate_resource_address(resource_address, bech32_decoder)?,
+        },
+        ast::Instruction::CreateProofFromAccountByIds {
+            account,
+            ids,
+            resource_address,
+        } => Instruction::CreateProofFromAccountByIds {
+            account: generate_component_address(account, bech32_decoder)?,
+            ids: generate_non_fungible_ids(ids)?,
+            resource_address: generate_resource_address(resource_address, bech32_decoder)?,
+        },
     })
 }
 
@@ -432,7 +456,7 @@ fn generate_args(
     values: &Vec<ast::Value>,
     resolver: &mut NameResolver,
     bech32_decoder: &Bech32Decoder,
-    blobs: &HashMap<Hash, Vec<u8>>,
+    blobs: &BTreeMap<Hash, Vec<u8>>,
 ) -> Result<Vec<Vec<u8>>, GeneratorError> {
     let mut result = Vec::new();
     for v in values {
@@ -450,6 +474,13 @@ fn generate_string(value: &ast::Value) -> Result<String, GeneratorError> {
     }
 }
 
+fn generate_u32(value: &ast::Value) -> Result<u32, GeneratorError> {
+    match value {
+        ast::Value::U32(v) => Ok(*v),
+        v @ _ => invalid_type!(v, ast::Type::U32),
+    }
+}
+
 fn generate_decimal(value: &ast::Value) -> Result<Decimal, GeneratorError> {
     match value {
         ast::Value::Decimal(inner) => match &**inner {
@@ -473,6 +504,15 @@ fn generate_precise_decimal(value: &ast::Value) -> Result<PreciseDecimal, Genera
     }
 }
 
+fn generate_integer<T: FromStr>(inner: &ast::Value) -> Result<T, GeneratorError> {
+    match inner {
+        ast::Value::String(s) => {
+            T::from_str(s).map_err(|_| GeneratorError::InvalidInteger(s.into()))
+        }
+        v @ _ => invalid_type!(v, ast::Type::String),
+    }
+}
+
 fn generate_package_address(
     value: &ast::Value,
     bech32_decoder: &Bech32Decoder,
@@ -630,7 +670,7 @@ fn generate_expression(value: &ast::Value) -> Result<Expression, GeneratorError>
 
 fn generate_blob(
     value: &ast::Value,
-    blobs: &HashMap<Hash, Vec<u8>>,
+    blobs: &BTreeMap<Hash, Vec<u8>>,
 ) -> Result<Blob, GeneratorError> {
     match value {
         ast::Value::Blob(inner) => match &**inner {
@@ -670,7 +710,7 @@ fn generate_value(
     expected: Option<ast::Type>,
     resolver: &mut NameResolver,
     bech32_decoder: &Bech32Decoder,
-    blobs: &HashMap<Hash, Vec<u8>>,
+    blobs: &BTreeMap<Hash, Vec<u8>>,
 ) -> Result<Value, GeneratorError> {
     if let Some(ty) = expected {
         if ty != value.kind() {
@@ -792,6 +832,30 @@ fn generate_value(
             type_id: ScryptoType::PreciseDecimal.id(),
             bytes: v.to_vec(),
         }),
+        ast::Value::I256(inner) => generate_integer::<I256>(inner).map(|v| Value::Custom {
+            type_id: ScryptoType::I256.id(),
+            bytes: v.to_vec(),
+        }),
+        ast::Value::I384(inner) => generate_integer::<I384>(inner).map(|v| Value::Custom {
+            type_id: ScryptoType::I384.id(),
+            bytes: v.to_vec(),
+        }),
+        ast::Value::I512(inner) => generate_integer::<I512>(inner).map(|v| Value::Custom {
+            type_id: ScryptoType::I512.id(),
+            bytes: v.to_vec(),
+        }),
+        ast::Value::U256(inner) => generate_integer::<U256>(inner).map(|v| Value::Custom {
+            type_id: ScryptoType::U256.id(),
+            bytes: v.to_vec(),
+        }),
+        ast::Value::U384(inner) => generate_integer::<U384>(inner).map(|v| Value::Custom {
+            type_id: ScryptoType::U384.id(),
+            bytes: v.to_vec(),
+        }),
+        ast::Value::U512(inner) => generate_integer::<U512>(inner).map(|v| Value::Custom {
+            type_id: ScryptoType::U512.id(),
+            bytes: v.to_vec(),
+        }),
         ast::Value::PackageAddress(_) => {
             generate_package_address(value, bech32_decoder).map(|v| Value::Custom {
                 type_id: ScryptoType::PackageAddress.id(),
@@ -848,7 +912,7 @@ fn generate_singletons(
     ty: Option<ast::Type>,
     resolver: &mut NameResolver,
     bech32_decoder: &Bech32Decoder,
-    blobs: &HashMap<Hash, Vec<u8>>,
+    blobs: &BTreeMap<Hash, Vec<u8>>,
 ) -> Result<Vec<Value>, GeneratorError> {
     let mut result = vec![];
     for element in elements {
@@ -869,7 +933,7 @@ fn generate_pairs(
     value_type: ast::Type,
     resolver: &mut NameResolver,
     bech32_decoder: &Bech32Decoder,
-    blobs: &HashMap<Hash, Vec<u8>>,
+    blobs: &BTreeMap<Hash, Vec<u8>>,
 ) -> Result<Vec<Value>, GeneratorError> {
     if elements.len() % 2 != 0 {
         return Err(GeneratorError::OddNumberOfElements(elements.len()));
@@ -920,6 +984,12 @@ fn generate_type_id(ty: &ast::Type) -> u8 {
         ast::Type::Map => TYPE_MAP,
         ast::Type::Decimal => ScryptoType::Decimal.id(),
         ast::Type::PreciseDecimal => ScryptoType::PreciseDecimal.id(),
+        ast::Type::I256 => ScryptoType::I256.id(),
+        ast::Type::I384 => ScryptoType::I384.id(),
+        ast::Type::I512 => ScryptoType::I512.id(),
+        ast::Type::U256 => ScryptoType::U256.id(),
+        ast::Type::U384 => ScryptoType::U384.id(),
+        ast::Type::U512 => ScryptoType::U512.id(),
         ast::Type::PackageAddress => ScryptoType::PackageAddress.id(),
         ast::Type::ComponentAddress => ScryptoType::ComponentAddress.id(),
         ast::Type::ResourceAddress => ScryptoType::ResourceAddress.id(),
@@ -953,7 +1023,7 @@ mod tests {
                     None,
                     &mut resolver,
                     &Bech32Decoder::new(&NetworkDefinition::simulator()),
-                    &mut HashMap::new()
+                    &mut BTreeMap::new()
                 ),
                 Ok($expected)
             );
@@ -974,7 +1044,7 @@ mod tests {
                     &mut id_validator,
                     &mut resolver,
                     &Bech32Decoder::new(&NetworkDefinition::simulator()),
-                    &mut HashMap::new()
+                    &mut BTreeMap::new()
                 ),
                 Ok($expected)
             );
@@ -990,7 +1060,7 @@ mod tests {
                 None,
                 &mut NameResolver::new(),
                 &Bech32Decoder::new(&NetworkDefinition::simulator()),
-                &mut HashMap::new(),
+                &mut BTreeMap::new(),
             ) {
                 Ok(_) => {
                     panic!("Expected {:?} but no error is thrown", $expected);
@@ -1131,6 +1201,36 @@ mod tests {
                 bytes: scrypto::core::Expression("ENTIRE_WORKTOP".to_owned()).to_vec()
             }
         );
+        generate_value_ok!(
+            r#"List<U256>(U256("1"), U256("2"))"#,
+            Value::List {
+                element_type_id: ScryptoType::U256.id(),
+                elements: vec![
+                    Value::Custom {
+                        type_id: ScryptoType::U256.id(),
+                        bytes: U256::from(1u8).to_vec()
+                    },
+                    Value::Custom {
+                        type_id: ScryptoType::U256.id(),
+                        bytes: U256::from(2u8).to_vec()
+                    },
+                ]
+            }
+        );
+        generate_value_ok!(
+            r#"Decimal("1_500e-3")"#,
+            Value::Custom {
+                type_id: ScryptoType::Decimal.id(),
+                bytes: Decimal::from_str("1.5").unwrap().to_vec()
+            }
+        );
+        generate_value_ok!(
+            r#"I512("-3")"#,
+            Value::Custom {
+                type_id: ScryptoType::I512.id(),
+                bytes: I512::from(-3i8).to_vec()
+            }
+        );
     }
 
     #[test]
@@ -1150,6 +1250,7 @@ mod tests {
             r#"Decimal("invalid_decimal")"#,
             GeneratorError::InvalidDecimal("invalid_decimal".into())
         );
+        generate_value_error!(r#"U256("-1")"#, GeneratorError::InvalidInteger("-1".into()));
         generate_value_error!(
             r#"Map<String, String>("abc")"#,
             GeneratorError::OddNumberOfElements(1)
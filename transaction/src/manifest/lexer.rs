@@ -62,6 +62,12 @@ pub enum TokenKind {
 
     Decimal,
     PreciseDecimal,
+    I256,
+    I384,
+    I512,
+    U256,
+    U384,
+    U512,
     PackageAddress,
     ComponentAddress,
     ResourceAddress,
@@ -105,6 +111,10 @@ pub enum TokenKind {
     CreateResource,
     BurnBucket,
     MintFungible,
+    ExecuteSubintent,
+    ReturnWorktopContents,
+    CreateProofFromAccountByAmount,
+    CreateProofFromAccountByIds,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -387,6 +397,12 @@ impl Lexer {
             "Map" => Ok(TokenKind::Map),
             "Decimal" => Ok(TokenKind::Decimal),
             "PreciseDecimal" => Ok(TokenKind::PreciseDecimal),
+            "I256" => Ok(TokenKind::I256),
+            "I384" => Ok(TokenKind::I384),
+            "I512" => Ok(TokenKind::I512),
+            "U256" => Ok(TokenKind::U256),
+            "U384" => Ok(TokenKind::U384),
+            "U512" => Ok(TokenKind::U512),
             "PackageAddress" => Ok(TokenKind::PackageAddress),
             "ComponentAddress" => Ok(TokenKind::ComponentAddress),
             "ResourceAddress" => Ok(TokenKind::ResourceAddress),
@@ -428,6 +444,10 @@ impl Lexer {
             "CREATE_RESOURCE" => Ok(TokenKind::CreateResource),
             "BURN_BUCKET" => Ok(TokenKind::BurnBucket),
             "MINT_FUNGIBLE" => Ok(TokenKind::MintFungible),
+            "EXECUTE_SUBINTENT" => Ok(TokenKind::ExecuteSubintent),
+            "RETURN_WORKTOP_CONTENTS" => Ok(TokenKind::ReturnWorktopContents),
+            "CREATE_PROOF_FROM_ACCOUNT_BY_AMOUNT" => Ok(TokenKind::CreateProofFromAccountByAmount),
+            "CREATE_PROOF_FROM_ACCOUNT_BY_IDS" => Ok(TokenKind::CreateProofFromAccountByIds),
 
             s @ _ => Err(LexerError::UnknownIdentifier(s.into())),
         }
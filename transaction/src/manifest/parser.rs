@@ -177,6 +177,22 @@ impl Parser {
                 resource_address: self.parse_value()?,
                 amount: self.parse_value()?,
             },
+            TokenKind::ExecuteSubintent => Instruction::ExecuteSubintent {
+                index: self.parse_value()?,
+            },
+            TokenKind::ReturnWorktopContents => Instruction::ReturnWorktopContents,
+            TokenKind::CreateProofFromAccountByAmount => {
+                Instruction::CreateProofFromAccountByAmount {
+                    account: self.parse_value()?,
+                    amount: self.parse_value()?,
+                    resource_address: self.parse_value()?,
+                }
+            }
+            TokenKind::CreateProofFromAccountByIds => Instruction::CreateProofFromAccountByIds {
+                account: self.parse_value()?,
+                ids: self.parse_value()?,
+                resource_address: self.parse_value()?,
+            },
             _ => {
                 return Err(ParserError::UnexpectedToken(token));
             }
@@ -216,6 +232,12 @@ impl Parser {
             TokenKind::Map => self.parse_map(),
             TokenKind::Decimal
             | TokenKind::PreciseDecimal
+            | TokenKind::I256
+            | TokenKind::I384
+            | TokenKind::I512
+            | TokenKind::U256
+            | TokenKind::U384
+            | TokenKind::U512
             | TokenKind::PackageAddress
             | TokenKind::ComponentAddress
             | TokenKind::ResourceAddress
@@ -318,6 +340,12 @@ impl Parser {
         match token.kind {
             TokenKind::Decimal => Ok(Value::Decimal(self.parse_values_one()?.into())),
             TokenKind::PreciseDecimal => Ok(Value::PreciseDecimal(self.parse_values_one()?.into())),
+            TokenKind::I256 => Ok(Value::I256(self.parse_values_one()?.into())),
+            TokenKind::I384 => Ok(Value::I384(self.parse_values_one()?.into())),
+            TokenKind::I512 => Ok(Value::I512(self.parse_values_one()?.into())),
+            TokenKind::U256 => Ok(Value::U256(self.parse_values_one()?.into())),
+            TokenKind::U384 => Ok(Value::U384(self.parse_values_one()?.into())),
+            TokenKind::U512 => Ok(Value::U512(self.parse_values_one()?.into())),
             TokenKind::PackageAddress => Ok(Value::PackageAddress(self.parse_values_one()?.into())),
             TokenKind::ComponentAddress => {
                 Ok(Value::ComponentAddress(self.parse_values_one()?.into()))
@@ -416,6 +444,13 @@ impl Parser {
             TokenKind::Set => Ok(Type::Set),
             TokenKind::Map => Ok(Type::Map),
             TokenKind::Decimal => Ok(Type::Decimal),
+            TokenKind::PreciseDecimal => Ok(Type::PreciseDecimal),
+            TokenKind::I256 => Ok(Type::I256),
+            TokenKind::I384 => Ok(Type::I384),
+            TokenKind::I512 => Ok(Type::I512),
+            TokenKind::U256 => Ok(Type::U256),
+            TokenKind::U384 => Ok(Type::U384),
+            TokenKind::U512 => Ok(Type::U512),
             TokenKind::PackageAddress => Ok(Type::PackageAddress),
             TokenKind::ComponentAddress => Ok(Type::ComponentAddress),
             TokenKind::ResourceAddress => Ok(Type::ResourceAddress),
@@ -664,6 +699,30 @@ mod tests {
             }
         );
         parse_instruction_ok!(r#"DROP_ALL_PROOFS;"#, Instruction::DropAllProofs);
+        parse_instruction_ok!(
+            r#"EXECUTE_SUBINTENT 0u32;"#,
+            Instruction::ExecuteSubintent {
+                index: Value::U32(0)
+            }
+        );
+        parse_instruction_ok!(
+            r#"RETURN_WORKTOP_CONTENTS;"#,
+            Instruction::ReturnWorktopContents
+        );
+        parse_instruction_ok!(
+            r#"CREATE_PROOF_FROM_ACCOUNT_BY_AMOUNT ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1") Decimal("1.0") ResourceAddress("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d");"#,
+            Instruction::CreateProofFromAccountByAmount {
+                account: Value::ComponentAddress(
+                    Value::String("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1".into())
+                        .into()
+                ),
+                amount: Value::Decimal(Value::String("1.0".into()).into()),
+                resource_address: Value::ResourceAddress(
+                    Value::String("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d".into())
+                        .into()
+                ),
+            }
+        );
         parse_instruction_ok!(
             r#"CALL_FUNCTION  PackageAddress("01d1f50010e4102d88aacc347711491f852c515134a9ecf67ba17c")  "Airdrop"  "new"  500u32  Map<String, U8>("key", 1u8);"#,
             Instruction::CallFunction {
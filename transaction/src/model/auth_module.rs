@@ -10,19 +10,23 @@ impl AuthModule {
         NonFungibleAddress::new(SYSTEM_TOKEN, NonFungibleId::from_u32(0))
     }
 
+    pub fn signer_key_to_non_fungible(signer_public_key: &PublicKey) -> NonFungibleAddress {
+        match signer_public_key {
+            PublicKey::EddsaEd25519(pk) => {
+                NonFungibleAddress::new(ED25519_TOKEN, NonFungibleId::from_bytes(pk.to_vec()))
+            }
+            PublicKey::EcdsaSecp256k1(pk) => {
+                NonFungibleAddress::new(ECDSA_TOKEN, NonFungibleId::from_bytes(pk.to_vec()))
+            }
+        }
+    }
+
     pub fn signer_keys_to_non_fungibles(
         signer_public_keys: &[PublicKey],
     ) -> Vec<NonFungibleAddress> {
         signer_public_keys
             .iter()
-            .map(|k| match k {
-                PublicKey::EddsaEd25519(pk) => {
-                    NonFungibleAddress::new(ED25519_TOKEN, NonFungibleId::from_bytes(pk.to_vec()))
-                }
-                PublicKey::EcdsaSecp256k1(pk) => {
-                    NonFungibleAddress::new(ECDSA_TOKEN, NonFungibleId::from_bytes(pk.to_vec()))
-                }
-            })
+            .map(Self::signer_key_to_non_fungible)
             .collect()
     }
 }
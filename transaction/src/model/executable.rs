@@ -5,7 +5,8 @@ use sbor::*;
 use scrypto::component::ComponentAddress;
 use scrypto::core::{NativeFnIdentifier, Receiver};
 use scrypto::crypto::*;
-use scrypto::resource::NonFungibleAddress;
+use scrypto::engine::types::SubstateId;
+use scrypto::resource::{NonFungibleAddress, ResourceAddress};
 
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, TypeId)]
 pub enum MethodIdentifier {
@@ -19,6 +20,17 @@ pub enum MethodIdentifier {
     },
 }
 
+/// A subintent which passed validation, run by the `ExecuteSubintent` instruction.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, TypeId)]
+pub struct ExecutableSubintent {
+    /// The subintent hash, which must be recorded on commit so that the subintent can't be replayed
+    pub hash: Hash,
+    pub instructions: Vec<Instruction>,
+    /// The badges of the subintent signers, which are only available to its instructions
+    pub initial_proofs: Vec<NonFungibleAddress>,
+    pub blobs: Vec<Vec<u8>>,
+}
+
 /// A common trait for all transactions that can be executed by Radix Engine.
 pub trait ExecutableTransaction {
     /// Returns the transaction hash, which must be globally unique.
@@ -38,5 +50,26 @@ pub trait ExecutableTransaction {
 
     fn initial_proofs(&self) -> Vec<NonFungibleAddress>;
 
+    /// Returns the resources of which any non-fungible is assumed to be proven, on top of the
+    /// initial proofs.
+    fn virtual_proof_resources(&self) -> Vec<ResourceAddress>;
+
+    /// Returns the signature badge of the fee payer, which is only usable to lock fees.
+    fn fee_payer(&self) -> Option<NonFungibleAddress>;
+
+    /// Returns the subintents, indexed by the `ExecuteSubintent` instructions.
+    fn subintents(&self) -> Vec<ExecutableSubintent>;
+
+    /// Returns the hashes of the subintents, to be recorded as committed along with the intent.
+    fn subintent_hashes(&self) -> Vec<Hash> {
+        self.subintents()
+            .into_iter()
+            .map(|subintent| subintent.hash)
+            .collect()
+    }
+
     fn blobs(&self) -> &[Vec<u8>];
+
+    /// Returns the global substates the transaction declared it would access, if any.
+    fn declared_substates(&self) -> Option<&[SubstateId]>;
 }
@@ -2,6 +2,7 @@ use crate::model::MethodIdentifier;
 use sbor::rust::collections::BTreeSet;
 use sbor::rust::vec::Vec;
 use sbor::*;
+use scrypto::component::ComponentAddress;
 use scrypto::core::{Blob, FnIdentifier};
 use scrypto::engine::types::*;
 use scrypto::math::*;
@@ -97,4 +98,24 @@ pub enum Instruction {
 
     /// Publishes a package.
     PublishPackage { code: Blob, abi: Blob },
+
+    /// Runs the instructions of a subintent, with the badges of its signers.
+    ExecuteSubintent { index: u32 },
+
+    /// Returns the resources on the worktop, without taking them.
+    ReturnWorktopContents,
+
+    /// Creates a proof from an account, by the given amount, and pushes it onto the auth zone.
+    CreateProofFromAccountByAmount {
+        account: ComponentAddress,
+        amount: Decimal,
+        resource_address: ResourceAddress,
+    },
+
+    /// Creates a proof from an account, by the given non-fungible IDs, and pushes it onto the auth zone.
+    CreateProofFromAccountByIds {
+        account: ComponentAddress,
+        ids: BTreeSet<NonFungibleId>,
+        resource_address: ResourceAddress,
+    },
 }
@@ -1,14 +1,21 @@
 use sbor::*;
 use scrypto::buffer::scrypto_encode;
-use scrypto::constants::{ECDSA_TOKEN, ED25519_TOKEN};
+use scrypto::constants::{ECDSA_TOKEN, ED25519_TOKEN, PUBLIC_KEY_HASH_TOKEN};
 use scrypto::crypto::{hash, Hash, PublicKey};
-use scrypto::resource::{NonFungibleAddress, NonFungibleId};
+use scrypto::engine::types::SubstateId;
+use scrypto::resource::{NonFungibleAddress, ResourceAddress};
 
-use crate::model::{ExecutableTransaction, Instruction, TransactionIntent};
+use crate::model::{
+    AuthModule, ExecutableSubintent, ExecutableTransaction, Instruction, TransactionIntent,
+};
 
 #[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
 pub struct PreviewFlags {
     pub unlimited_loan: bool,
+    /// Whether to assume that every signature badge is in the auth zone, so that the
+    /// transaction can be previewed before it's known who will sign it
+    #[sbor(default)]
+    pub assume_all_signature_proofs: bool,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
@@ -33,6 +40,7 @@ pub struct ValidatedPreviewTransaction {
     pub preview_intent: PreviewIntent,
     pub transaction_hash: Hash,
     pub instructions: Vec<Instruction>,
+    pub subintents: Vec<ExecutableSubintent>,
 }
 
 impl ExecutableTransaction for ValidatedPreviewTransaction {
@@ -49,20 +57,36 @@ impl ExecutableTransaction for ValidatedPreviewTransaction {
     }
 
     fn initial_proofs(&self) -> Vec<NonFungibleAddress> {
+        let fee_payer = self.preview_intent.intent.header.fee_payer;
         self.preview_intent
             .signer_public_keys
             .iter()
-            .map(|k| match k {
-                PublicKey::EddsaEd25519(pk) => {
-                    NonFungibleAddress::new(ED25519_TOKEN, NonFungibleId::from_bytes(pk.to_vec()))
-                }
-                PublicKey::EcdsaSecp256k1(pk) => {
-                    NonFungibleAddress::new(ECDSA_TOKEN, NonFungibleId::from_bytes(pk.to_vec()))
-                }
-            })
+            .filter(|k| Some(**k) != fee_payer)
+            .map(AuthModule::signer_key_to_non_fungible)
             .collect()
     }
 
+    fn virtual_proof_resources(&self) -> Vec<ResourceAddress> {
+        if self.preview_intent.flags.assume_all_signature_proofs {
+            vec![ECDSA_TOKEN, ED25519_TOKEN, PUBLIC_KEY_HASH_TOKEN]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn fee_payer(&self) -> Option<NonFungibleAddress> {
+        self.preview_intent
+            .intent
+            .header
+            .fee_payer
+            .as_ref()
+            .map(AuthModule::signer_key_to_non_fungible)
+    }
+
+    fn subintents(&self) -> Vec<ExecutableSubintent> {
+        self.subintents.clone()
+    }
+
     fn cost_unit_limit(&self) -> u32 {
         self.preview_intent.intent.header.cost_unit_limit
     }
@@ -74,4 +98,40 @@ impl ExecutableTransaction for ValidatedPreviewTransaction {
     fn blobs(&self) -> &[Vec<u8>] {
         &self.preview_intent.intent.manifest.blobs
     }
+
+    fn declared_substates(&self) -> Option<&[SubstateId]> {
+        self.preview_intent
+            .intent
+            .header
+            .declared_substates
+            .as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scrypto::buffer::scrypto_decode;
+
+    #[derive(TypeId, Encode)]
+    struct PreviewFlagsV1 {
+        unlimited_loan: bool,
+    }
+
+    #[test]
+    fn test_decode_preview_flags_without_assume_all_signature_proofs() {
+        let bytes = scrypto_encode(&PreviewFlagsV1 {
+            unlimited_loan: true,
+        });
+
+        let flags: PreviewFlags = scrypto_decode(&bytes).unwrap();
+
+        assert_eq!(
+            flags,
+            PreviewFlags {
+                unlimited_loan: true,
+                assume_all_signature_proofs: false,
+            }
+        );
+    }
 }
@@ -2,10 +2,12 @@ use sbor::rust::vec::Vec;
 use scrypto::buffer::scrypto_encode;
 use scrypto::core::NetworkDefinition;
 use scrypto::crypto::*;
-use scrypto::resource::NonFungibleAddress;
+use scrypto::engine::types::SubstateId;
+use scrypto::resource::{NonFungibleAddress, ResourceAddress};
 
 use crate::builder::TransactionBuilder;
 use crate::model::*;
+use crate::validation::{recover, verify};
 
 /// Represents a test transaction, for testing/simulation purpose only.
 pub struct TestTransaction {
@@ -30,6 +32,8 @@ impl TestTransaction {
                 notary_as_signatory: false,
                 cost_unit_limit: DEFAULT_COST_UNIT_LIMIT,
                 tip_percentage: 5,
+                declared_substates: None,
+                fee_payer: None,
             })
             .manifest(manifest)
             .notary_signature(EcdsaSecp256k1Signature([0u8; 65]).into())
@@ -44,7 +48,7 @@ impl TestTransaction {
 
 impl ExecutableTransaction for TestTransaction {
     fn transaction_hash(&self) -> Hash {
-        self.transaction.hash()
+        self.transaction.payload_hash()
     }
 
     fn manifest_instructions_size(&self) -> u32 {
@@ -67,7 +71,51 @@ impl ExecutableTransaction for TestTransaction {
         AuthModule::signer_keys_to_non_fungibles(&self.signer_public_keys)
     }
 
+    fn virtual_proof_resources(&self) -> Vec<ResourceAddress> {
+        Vec::new()
+    }
+
+    fn fee_payer(&self) -> Option<NonFungibleAddress> {
+        None
+    }
+
+    fn subintents(&self) -> Vec<ExecutableSubintent> {
+        // Only signers whose signature verifies are given a badge
+        self.transaction
+            .signed_intent
+            .intent
+            .subintents
+            .iter()
+            .map(|signed_subintent| {
+                let payload = signed_subintent.subintent.to_bytes();
+                let signer_public_keys: Vec<PublicKey> = signed_subintent
+                    .signatures
+                    .iter()
+                    .filter_map(|sig| {
+                        recover(&payload, sig)
+                            .filter(|public_key| verify(&payload, public_key, &sig.signature()))
+                    })
+                    .collect();
+                ExecutableSubintent {
+                    hash: signed_subintent.subintent.hash(),
+                    instructions: signed_subintent.subintent.manifest.instructions.clone(),
+                    initial_proofs: AuthModule::signer_keys_to_non_fungibles(&signer_public_keys),
+                    blobs: signed_subintent.subintent.manifest.blobs.clone(),
+                }
+            })
+            .collect()
+    }
+
     fn blobs(&self) -> &[Vec<u8>] {
         &self.transaction.signed_intent.intent.manifest.blobs
     }
+
+    fn declared_substates(&self) -> Option<&[SubstateId]> {
+        self.transaction
+            .signed_intent
+            .intent
+            .header
+            .declared_substates
+            .as_deref()
+    }
 }
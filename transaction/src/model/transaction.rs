@@ -2,11 +2,60 @@ use sbor::*;
 use scrypto::buffer::{scrypto_decode, scrypto_encode};
 use scrypto::core::NetworkDefinition;
 use scrypto::crypto::{hash, Hash, PublicKey, Signature, SignatureWithPublicKey};
+use scrypto::engine::types::SubstateId;
 
 use crate::manifest::{compile, CompileError};
 use crate::model::Instruction;
+use crate::signing::Signer;
 
-// TODO: add versioning of transaction schema
+/// The version of the transaction payload format.
+pub const TRANSACTION_PAYLOAD_VERSION: u8 = 1;
+
+/// The kind of a transaction payload, which is encoded after the version.
+///
+/// A payload is the SBOR encoding of its value, prefixed with `[TRANSACTION_PAYLOAD_VERSION, kind]`.
+/// The payloads are what get signed and hashed, so the prefix keeps a signature over one kind of
+/// payload from being replayed as another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionPayloadKind {
+    Intent = 1,
+    SignedIntent = 2,
+    NotarizedTransaction = 3,
+    Subintent = 4,
+}
+
+/// A value which is serialized as a transaction payload.
+pub trait TransactionPayload: Encode + Decode {
+    const KIND: TransactionPayloadKind;
+}
+
+/// Encodes a value as a payload, with the version and kind prefix.
+pub fn encode_payload<T: TransactionPayload>(value: &T) -> Vec<u8> {
+    let mut bytes = vec![TRANSACTION_PAYLOAD_VERSION, T::KIND as u8];
+    bytes.extend(scrypto_encode(value));
+    bytes
+}
+
+/// Decodes a payload, checking the version and kind prefix.
+pub fn decode_payload<T: TransactionPayload>(slice: &[u8]) -> Result<T, DecodeError> {
+    match slice {
+        [TRANSACTION_PAYLOAD_VERSION, kind, rest @ ..] if *kind == T::KIND as u8 => {
+            scrypto_decode(rest)
+        }
+        [TRANSACTION_PAYLOAD_VERSION, kind, ..] => Err(DecodeError::CustomError(format!(
+            "Unexpected payload kind: {}",
+            kind
+        ))),
+        [version, _, ..] => Err(DecodeError::CustomError(format!(
+            "Unsupported payload version: {}",
+            version
+        ))),
+        _ => Err(DecodeError::Underflow {
+            required: 2,
+            remaining: slice.len(),
+        }),
+    }
+}
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
@@ -20,6 +69,56 @@ pub struct TransactionHeader {
     pub notary_as_signatory: bool,
     pub cost_unit_limit: u32,
     pub tip_percentage: u32,
+    /// The global substates the transaction may access, or `None` if not declared.
+    ///
+    /// When declared, locking any pre-existing substate outside of this set fails the transaction.
+    #[cfg_attr(feature = "serde", serde(default, with = "declared_substates_serde"))]
+    pub declared_substates: Option<Vec<SubstateId>>,
+    /// The signer sponsoring the transaction fee, or `None` if the fee is paid by the intent signers.
+    ///
+    /// The fee payer must sign the intent, but their signature badge is only usable to lock fees
+    /// from their account, not by the rest of the manifest.
+    pub fee_payer: Option<PublicKey>,
+}
+
+/// Serializes declared substates as hex-encoded SBOR, as `SubstateId` has no serde representation.
+#[cfg(feature = "serde")]
+mod declared_substates_serde {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        declared_substates: &Option<Vec<SubstateId>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        declared_substates
+            .as_ref()
+            .map(|substate_ids| {
+                substate_ids
+                    .iter()
+                    .map(|substate_id| hex::encode(scrypto_encode(substate_id)))
+                    .collect::<Vec<String>>()
+            })
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<SubstateId>>, D::Error> {
+        Option::<Vec<String>>::deserialize(deserializer)?
+            .map(|substate_ids| {
+                substate_ids
+                    .iter()
+                    .map(|substate_id| {
+                        let bytes = hex::decode(substate_id).map_err(D::Error::custom)?;
+                        scrypto_decode(&bytes).map_err(|e| D::Error::custom(format!("{:?}", e)))
+                    })
+                    .collect()
+            })
+            .transpose()
+    }
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
@@ -32,6 +131,27 @@ pub struct TransactionManifest {
 pub struct TransactionIntent {
     pub header: TransactionHeader,
     pub manifest: TransactionManifest,
+    /// The partial transactions composed into this one, run by `ExecuteSubintent` instructions.
+    pub subintents: Vec<SignedSubintent>,
+}
+
+/// A partial manifest which is signed independently of the transaction it's composed into.
+///
+/// The signers' badges are only available to the subintent's own instructions, which share the
+/// worktop with the transaction running them.
+#[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
+pub struct Subintent {
+    pub network_id: u8,
+    pub start_epoch_inclusive: u64,
+    pub end_epoch_exclusive: u64,
+    pub nonce: u64,
+    pub manifest: TransactionManifest,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
+pub struct SignedSubintent {
+    pub subintent: Subintent,
+    pub signatures: Vec<SignatureWithPublicKey>,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
@@ -63,6 +183,22 @@ pub enum IntentConfigError {
     MismatchedNetwork { expected: u8, actual: u8 },
 }
 
+impl TransactionPayload for TransactionIntent {
+    const KIND: TransactionPayloadKind = TransactionPayloadKind::Intent;
+}
+
+impl TransactionPayload for SignedTransactionIntent {
+    const KIND: TransactionPayloadKind = TransactionPayloadKind::SignedIntent;
+}
+
+impl TransactionPayload for NotarizedTransaction {
+    const KIND: TransactionPayloadKind = TransactionPayloadKind::NotarizedTransaction;
+}
+
+impl TransactionPayload for Subintent {
+    const KIND: TransactionPayloadKind = TransactionPayloadKind::Subintent;
+}
+
 impl TransactionIntent {
     pub fn new(
         network: &NetworkDefinition,
@@ -81,11 +217,39 @@ impl TransactionIntent {
         Ok(Self {
             header,
             manifest: compile(manifest, &network, blobs)?,
+            subintents: Vec::new(),
         })
     }
 
     pub fn from_slice(slice: &[u8]) -> Result<Self, DecodeError> {
-        scrypto_decode(slice)
+        decode_payload(slice)
+    }
+
+    pub fn hash(&self) -> Hash {
+        hash(self.to_bytes())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode_payload(self)
+    }
+}
+
+impl Subintent {
+    pub fn new(
+        network: &NetworkDefinition,
+        start_epoch_inclusive: u64,
+        end_epoch_exclusive: u64,
+        nonce: u64,
+        manifest: &str,
+        blobs: Vec<Vec<u8>>,
+    ) -> Result<Self, CompileError> {
+        Ok(Self {
+            network_id: network.id,
+            start_epoch_inclusive,
+            end_epoch_exclusive,
+            nonce,
+            manifest: compile(manifest, &network, blobs)?,
+        })
     }
 
     pub fn hash(&self) -> Hash {
@@ -93,13 +257,28 @@ impl TransactionIntent {
     }
 
     pub fn to_bytes(&self) -> Vec<u8> {
-        scrypto_encode(self)
+        encode_payload(self)
+    }
+}
+
+impl SignedSubintent {
+    pub fn new(subintent: Subintent) -> Self {
+        Self {
+            subintent,
+            signatures: Vec::new(),
+        }
+    }
+
+    pub fn sign<S: Signer>(mut self, signer: &S) -> Self {
+        self.signatures
+            .push(signer.sign(&self.subintent.to_bytes()));
+        self
     }
 }
 
 impl SignedTransactionIntent {
     pub fn from_slice(slice: &[u8]) -> Result<Self, DecodeError> {
-        scrypto_decode(slice)
+        decode_payload(slice)
     }
 
     pub fn hash(&self) -> Hash {
@@ -107,21 +286,37 @@ impl SignedTransactionIntent {
     }
 
     pub fn to_bytes(&self) -> Vec<u8> {
-        scrypto_encode(self)
+        encode_payload(self)
     }
 }
 
 impl NotarizedTransaction {
     pub fn from_slice(slice: &[u8]) -> Result<Self, DecodeError> {
-        scrypto_decode(slice)
+        decode_payload(slice)
     }
 
-    pub fn hash(&self) -> Hash {
+    /// The hash of the intent payload, which identifies the transaction for replay protection.
+    pub fn intent_hash(&self) -> Hash {
+        self.signed_intent.intent.hash()
+    }
+
+    /// The hash of the signed intent payload, which is what the notary signs.
+    pub fn signed_intent_hash(&self) -> Hash {
+        self.signed_intent.hash()
+    }
+
+    /// The hash of the full notarized payload, which is the transaction hash used by the engine.
+    pub fn payload_hash(&self) -> Hash {
         hash(self.to_bytes())
     }
 
+    #[deprecated(note = "Use `payload_hash` instead")]
+    pub fn hash(&self) -> Hash {
+        self.payload_hash()
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
-        scrypto_encode(self)
+        encode_payload(self)
     }
 }
 
@@ -129,7 +324,6 @@ impl NotarizedTransaction {
 mod tests {
     use super::*;
     use crate::signing::*;
-    use scrypto::buffer::scrypto_encode;
     use scrypto::core::NetworkDefinition;
 
     #[test]
@@ -152,6 +346,8 @@ mod tests {
                 notary_as_signatory: false,
                 cost_unit_limit: 1_000_000,
                 tip_percentage: 5,
+                declared_substates: None,
+                fee_payer: None,
             },
             "CLEAR_AUTH_ZONE;",
             Vec::new(),
@@ -174,18 +370,18 @@ mod tests {
         };
 
         assert_eq!(
-            "671a87cacf3f359ed6f368c50684fe963567a345eea7382ad931dd8a09d30e5a",
-            transaction.signed_intent.intent.hash().to_string()
+            "d22460af4bd679af6a8b3be738fe8fb3b18cca77b147a72edb1b29e6d68c5f3d",
+            transaction.intent_hash().to_string()
         );
         assert_eq!(
-            "95299e1b74664150ae319ccade62cc0ed605548c65115f25272c6e4269182f21",
-            transaction.signed_intent.hash().to_string()
+            "e1e6ab56900420cee79c6b73417c70d04bef82d2bb89bddd6423da4dbb9dbbee",
+            transaction.signed_intent_hash().to_string()
         );
         assert_eq!(
-            "bcfc92958a504627cfa04b8b1dc9804c5e3a039e5231759258b3be4c6d6e740a",
-            transaction.hash().to_string()
+            "ec705914bd343f27eda18029c76c9e2e5a5d6f05401aaa20f945b4b41469efca",
+            transaction.payload_hash().to_string()
         );
-        assert_eq!("1002000000100200000010020000001009000000070107f20a00000000000000000a64000000000000000a0500000000000000110e0000004563647361536563703235366b3101000000912100000002f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f901000940420f00090500000010020000003011010000000d000000436c656172417574685a6f6e65000000003030000000003011020000000e0000004563647361536563703235366b310100000092410000000132e68b38e908177113142e58aee6453c34615d1e6d8c48530d5748f6367e27925c55a01c7735fdeda44928a7d015a0e48203f4a39834e73412d150dff092abe70e0000004563647361536563703235366b3101000000924100000000144cbd023cc482c4a39dca0e2d3f2a61bc765c9bbd72e75cf10484a7a3ddf1457fc8bebef15f703cb67e9818e40954a6081f0338e34f17730133050149d93468110e0000004563647361536563703235366b3101000000924100000000245d5ac8983efbf1f4aaf9f369a571d8bdfaf07f1173299998d043252183a1ac7ab0428724dd94e195bdf0092c3e34f78814a7300cbf2ab41131f9c4da69b8ab", hex::encode(scrypto_encode(&transaction)));
+        assert_eq!("0103100200000010020000001003000000100b000000070107f20a00000000000000000a64000000000000000a0500000000000000110e0000004563647361536563703235366b3101000000912100000002f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f901000940420f0009050000001201120110020000003011010000000d000000436c656172417574685a6f6e65000000003030000000003010000000003011020000000e0000004563647361536563703235366b31010000009241000000007964d24bcec91ca5a3b3fdf630a4b77d2e2c329cba19d0265474ce45bca9e5bb692804bb5aed87f33a907d7602d40155d9fdbf706c59d6b1dcbf6d976ccd0dfb0e0000004563647361536563703235366b3101000000924100000001af3cc197b3c98afc0b8a526f717cdb50a91ed41de2803d6f8db21ef07b712db81e0ad7f102fd68eb446e02051b72269b6375699f0649cce1e35acafc3f1f4941110e0000004563647361536563703235366b3101000000924100000001d6cd2248db5c42a75293d5b4313da75bdee502746db5561ede85e2eea3e774731fca570825869d03755afec756430a4af352d6a25248030beab0db471ed4dea6", hex::encode(transaction.to_bytes()));
     }
 
     #[test]
@@ -208,6 +404,8 @@ mod tests {
                 notary_as_signatory: false,
                 cost_unit_limit: 1_000_000,
                 tip_percentage: 5,
+                declared_substates: None,
+                fee_payer: None,
             },
             "CLEAR_AUTH_ZONE;",
             Vec::new(),
@@ -230,17 +428,62 @@ mod tests {
         };
 
         assert_eq!(
-            "08e1fc53bc3542c9e641bb6335c375bffcbc7bf86c96feecff7b6689568c9d1a",
-            transaction.signed_intent.intent.hash().to_string()
+            "c3ee519cbcb1bc2d2f1cff6a69267554bd29dde4aaee07bb2571ef540801a6be",
+            transaction.intent_hash().to_string()
+        );
+        assert_eq!(
+            "a55e22b37fee59f2ba5110a101f882753bffe15abedd6356e9fc0e5541ea3228",
+            transaction.signed_intent_hash().to_string()
+        );
+        assert_eq!(
+            "cec3cdd23d80cbc50abf7f0b0286a50f4adc8f5cba075e94edc742c45d2618aa",
+            transaction.payload_hash().to_string()
         );
+        assert_eq!("0103100200000010020000001003000000100b000000070107f20a00000000000000000a64000000000000000a0500000000000000110c000000456464736145643235353139010000009320000000f381626e41e7027ea431bfe3009e94bdd25a746beec468948d6c3c7c5dc9a54b01000940420f0009050000001201120110020000003011010000000d000000436c656172417574685a6f6e65000000003030000000003010000000003011020000000c0000004564647361456432353531390200000093200000004cb5abf6ad79fbf5abbccafcc269d85cd2651ed4b885b5869f241aedf0a5ba29944000000062310d8c3ab47524cbbdea65ea657df86e005b1861d49b8ff42b717af78cb470a264d7602928e5682709a874f699d132be42b4c41e445dd9ab21b667ff8ff5030c0000004564647361456432353531390200000093200000007422b9887598068e32c4448a949adb290d0f4e35b9e01b0ee5f1a1e600fe267494400000002f9b5ec6225b3af4b4def24b96eff7a442f2d200f6dd65b9e0455f90ffa4afe7759308a3ef697b83d81e2e50a20e59f2bbdd85f55ab4de0f10bca275e695a306110c000000456464736145643235353139010000009440000000408e9ffed2532d7137fdaef36e052149258be88e601801dbd287d96afc311184471ff39b52814d530e2d92883bc879141242df176ff4794e3bee9a2db7a07d04", hex::encode(transaction.to_bytes()));
+    }
+
+    #[test]
+    fn payload_prefix_is_checked_when_decoding() {
+        let intent = TransactionIntent::new(
+            &NetworkDefinition::simulator(),
+            TransactionHeader {
+                version: 1,
+                network_id: NetworkDefinition::simulator().id,
+                start_epoch_inclusive: 0,
+                end_epoch_exclusive: 100,
+                nonce: 5,
+                notary_public_key: EcdsaSecp256k1PrivateKey::from_u64(1)
+                    .unwrap()
+                    .public_key()
+                    .into(),
+                notary_as_signatory: false,
+                cost_unit_limit: 1_000_000,
+                tip_percentage: 5,
+                declared_substates: None,
+                fee_payer: None,
+            },
+            "CLEAR_AUTH_ZONE;",
+            Vec::new(),
+        )
+        .unwrap();
+        let payload = intent.to_bytes();
+
         assert_eq!(
-            "d96f9285e8001ebb38cf676aee8009ec471afd7660f1229e512d30790d6e2b06",
-            transaction.signed_intent.hash().to_string()
+            &payload[..2],
+            &[
+                TRANSACTION_PAYLOAD_VERSION,
+                TransactionPayloadKind::Intent as u8
+            ]
         );
+        assert_eq!(TransactionIntent::from_slice(&payload), Ok(intent));
+        assert!(SignedTransactionIntent::from_slice(&payload).is_err());
+        assert!(TransactionIntent::from_slice(&payload[1..]).is_err());
         assert_eq!(
-            "5c4bae2e3713a711c513a096c45a06d695d39188c77d0f2d0d1283cfa6a026a7",
-            transaction.hash().to_string()
+            TransactionIntent::from_slice(&payload[..1]),
+            Err(DecodeError::Underflow {
+                required: 2,
+                remaining: 1
+            })
         );
-        assert_eq!("1002000000100200000010020000001009000000070107f20a00000000000000000a64000000000000000a0500000000000000110c000000456464736145643235353139010000009320000000f381626e41e7027ea431bfe3009e94bdd25a746beec468948d6c3c7c5dc9a54b01000940420f00090500000010020000003011010000000d000000436c656172417574685a6f6e65000000003030000000003011020000000c0000004564647361456432353531390200000093200000004cb5abf6ad79fbf5abbccafcc269d85cd2651ed4b885b5869f241aedf0a5ba299440000000c5a8fc87ec5d839b6b9914aeb320a8f6d758e25de9a8ae737f526a9d79df9b179e991fdf877f54ca38ad6177c34ea7cca04b4ffac627d3a224ef095121b7f0070c0000004564647361456432353531390200000093200000007422b9887598068e32c4448a949adb290d0f4e35b9e01b0ee5f1a1e600fe2674944000000079ffb153e8b19103725e2897dabf6214b5b0c189d285d9dcf4c3785bcc952540966821b07ce5cc4972c47148d4dd26087f6161054a8dd600ba933ea789b3d808110c000000456464736145643235353139010000009440000000b17f1ddea31beeb62266f450a4cdb7d8f2810941bddcf6270cad1b23208160e5c12e2952e9fa5f810d57c1b6a9c15bb9413aeb6f21bfb803c70fc15bef488e02", hex::encode(scrypto_encode(&transaction)));
     }
 }
@@ -1,7 +1,8 @@
 use sbor::rust::vec::Vec;
 use scrypto::buffer::scrypto_encode;
 use scrypto::crypto::*;
-use scrypto::resource::NonFungibleAddress;
+use scrypto::engine::types::SubstateId;
+use scrypto::resource::{NonFungibleAddress, ResourceAddress};
 
 use crate::model::*;
 
@@ -12,9 +13,12 @@ pub struct Validated<T> {
     pub transaction_hash: Hash,
     pub instructions: Vec<Instruction>,
     pub initial_proofs: Vec<NonFungibleAddress>,
+    pub subintents: Vec<ExecutableSubintent>,
     pub cost_unit_limit: u32,
     pub tip_percentage: u32,
     pub blobs: Vec<Vec<u8>>,
+    pub declared_substates: Option<Vec<SubstateId>>,
+    pub fee_payer: Option<NonFungibleAddress>,
 }
 
 impl<T> Validated<T> {
@@ -23,18 +27,24 @@ impl<T> Validated<T> {
         transaction_hash: Hash,
         instructions: Vec<Instruction>,
         initial_proofs: Vec<NonFungibleAddress>,
+        subintents: Vec<ExecutableSubintent>,
         cost_unit_limit: u32,
         tip_percentage: u32,
         blobs: Vec<Vec<u8>>,
+        declared_substates: Option<Vec<SubstateId>>,
+        fee_payer: Option<NonFungibleAddress>,
     ) -> Self {
         Self {
             transaction,
             transaction_hash,
             instructions,
             initial_proofs,
+            subintents,
             cost_unit_limit,
             tip_percentage,
             blobs,
+            declared_substates,
+            fee_payer,
         }
     }
 
@@ -72,7 +82,23 @@ impl<T> ExecutableTransaction for Validated<T> {
         self.initial_proofs.clone()
     }
 
+    fn virtual_proof_resources(&self) -> Vec<ResourceAddress> {
+        Vec::new()
+    }
+
+    fn fee_payer(&self) -> Option<NonFungibleAddress> {
+        self.fee_payer.clone()
+    }
+
+    fn subintents(&self) -> Vec<ExecutableSubintent> {
+        self.subintents.clone()
+    }
+
     fn blobs(&self) -> &[Vec<u8>] {
         &self.blobs
     }
+
+    fn declared_substates(&self) -> Option<&[SubstateId]> {
+        self.declared_substates.as_deref()
+    }
 }
@@ -18,6 +18,8 @@ pub enum IdSpace {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct IdAllocator {
     available: Range<u32>,
+    /// The number of random seeds created, counted apart from the IDs
+    random_seeds: u32,
 }
 
 impl IdAllocator {
@@ -29,6 +31,7 @@ impl IdAllocator {
                 IdSpace::Transaction => 512..1024,
                 IdSpace::Application => 1024..u32::MAX,
             },
+            random_seeds: 0,
         }
     }
 
@@ -74,6 +77,19 @@ impl IdAllocator {
         }
     }
 
+    /// Creates a new access controller address.
+    pub fn new_access_controller_address(
+        &mut self,
+        transaction_hash: Hash,
+    ) -> Result<ComponentAddress, IdAllocationError> {
+        let mut data = transaction_hash.to_vec();
+        data.extend(self.next()?.to_le_bytes());
+
+        Ok(ComponentAddress::AccessController(
+            hash(data).lower_26_bytes(),
+        ))
+    }
+
     /// Creates a new resource address.
     pub fn new_resource_address(
         &mut self,
@@ -92,6 +108,25 @@ impl IdAllocator {
         Ok(u128::from_le_bytes(hash(data).lower_16_bytes()))
     }
 
+    /// Creates a new seed for pseudo-random bytes, from the transaction hash, the index of the
+    /// instruction being run and the number of seeds created before it.
+    pub fn new_random_seed(
+        &mut self,
+        transaction_hash: Hash,
+        instruction_index: u32,
+    ) -> Result<Hash, IdAllocationError> {
+        let mut data = transaction_hash.to_vec();
+        // Keeps seeds apart from the IDs derived from the same transaction hash
+        data.extend(b"random");
+        data.extend(instruction_index.to_le_bytes());
+        data.extend(self.random_seeds.to_le_bytes());
+        self.random_seeds = self
+            .random_seeds
+            .checked_add(1)
+            .ok_or(IdAllocationError::OutOfID)?;
+        Ok(hash(data))
+    }
+
     /// Creates a new bucket ID.
     pub fn new_bucket_id(&mut self) -> Result<BucketId, IdAllocationError> {
         Ok(self.next()?)
@@ -115,3 +150,36 @@ impl IdAllocator {
         Ok((transaction_hash, self.next()?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_seeds_are_counted_apart_from_ids() {
+        let transaction_hash = hash("transaction");
+        let mut id_allocator = IdAllocator::new(IdSpace::Application);
+        let mut other_id_allocator = IdAllocator::new(IdSpace::Application);
+        other_id_allocator.new_uuid(transaction_hash).unwrap();
+
+        let seed = id_allocator.new_random_seed(transaction_hash, 0).unwrap();
+
+        assert_eq!(
+            other_id_allocator.new_random_seed(transaction_hash, 0),
+            Ok(seed)
+        );
+        assert_ne!(id_allocator.new_random_seed(transaction_hash, 0), Ok(seed));
+    }
+
+    #[test]
+    fn test_random_seeds_differ_across_instructions() {
+        let transaction_hash = hash("transaction");
+        let mut id_allocator = IdAllocator::new(IdSpace::Application);
+        let mut other_id_allocator = IdAllocator::new(IdSpace::Application);
+
+        assert_ne!(
+            id_allocator.new_random_seed(transaction_hash, 0),
+            other_id_allocator.new_random_seed(transaction_hash, 1)
+        );
+    }
+}
@@ -1,8 +1,6 @@
-use sbor::Decode;
 use std::collections::HashSet;
 
-use scrypto::buffer::scrypto_decode;
-use scrypto::crypto::PublicKey;
+use scrypto::crypto::{hash, Hash, PublicKey, SignatureWithPublicKey};
 use scrypto::values::*;
 
 use crate::errors::{SignatureValidationError, *};
@@ -11,7 +9,7 @@ use crate::validation::*;
 
 pub const MAX_PAYLOAD_SIZE: usize = 4 * 1024 * 1024;
 
-pub trait TransactionValidator<T: Decode> {
+pub trait TransactionValidator<T: TransactionPayload> {
     fn validate_from_slice<I: IntentHashManager>(
         &self,
         transaction: &[u8],
@@ -21,7 +19,7 @@ pub trait TransactionValidator<T: Decode> {
             return Err(TransactionValidationError::TransactionTooLarge);
         }
 
-        let transaction: T = scrypto_decode(transaction)
+        let transaction: T = decode_payload(transaction)
             .map_err(TransactionValidationError::DeserializationError)?;
 
         self.validate(transaction, intent_hash_manager)
@@ -56,26 +54,50 @@ impl TransactionValidator<NotarizedTransaction> for NotarizedTransactionValidato
         // verify the intent
         let instructions =
             self.validate_intent(&transaction.signed_intent.intent, intent_hash_manager)?;
+        let subintents =
+            self.validate_subintents(&transaction.signed_intent.intent, intent_hash_manager)?;
 
         // verify signatures
-        let keys = self
+        let mut keys = self
             .validate_signatures(&transaction)
             .map_err(TransactionValidationError::SignatureValidationError)?;
 
-        let transaction_hash = transaction.hash();
+        // the fee payer's badge is kept out of the initial auth zone
+        let fee_payer = transaction.signed_intent.intent.header.fee_payer;
+        if let Some(fee_payer) = &fee_payer {
+            if !keys.contains(fee_payer) {
+                return Err(TransactionValidationError::SignatureValidationError(
+                    SignatureValidationError::MissingFeePayerSignature,
+                ));
+            }
+            keys.retain(|key| key != fee_payer);
+        }
+
+        let transaction_hash = transaction.payload_hash();
 
         let cost_unit_limit = transaction.signed_intent.intent.header.cost_unit_limit;
         let tip_percentage = transaction.signed_intent.intent.header.tip_percentage;
         let blobs = transaction.signed_intent.intent.manifest.blobs.clone();
+        let declared_substates = transaction
+            .signed_intent
+            .intent
+            .header
+            .declared_substates
+            .clone();
 
         Ok(Validated::new(
             transaction,
             transaction_hash,
             instructions,
             AuthModule::signer_keys_to_non_fungibles(&keys),
+            subintents,
             cost_unit_limit,
             tip_percentage,
             blobs,
+            declared_substates,
+            fee_payer
+                .as_ref()
+                .map(AuthModule::signer_key_to_non_fungible),
         ))
     }
 }
@@ -95,11 +117,13 @@ impl NotarizedTransactionValidator {
         let transaction_hash = preview_intent.hash();
 
         let instructions = self.validate_intent(&intent, intent_hash_manager)?;
+        let subintents = self.validate_subintents(&intent, intent_hash_manager)?;
 
         Ok(ValidatedPreviewTransaction {
             preview_intent,
             transaction_hash,
             instructions,
+            subintents,
         })
     }
 
@@ -118,28 +142,136 @@ impl NotarizedTransactionValidator {
             .map_err(TransactionValidationError::HeaderValidationError)?;
 
         // semantic analysis
-        let mut id_validator = IdValidator::new();
+        Self::validate_instructions(
+            &intent.manifest.instructions,
+            TransactionValidationError::IdValidationError,
+            TransactionValidationError::CallDataValidationError,
+        )?;
+        Self::validate_blobs(&intent.manifest)
+            .map_err(TransactionValidationError::BlobValidationError)?;
+
+        return Ok(intent.manifest.instructions.clone());
+    }
+
+    /// Validates the subintents of a transaction intent, each of which must be executed exactly
+    /// once by its manifest.
+    pub fn validate_subintents<I: IntentHashManager>(
+        &self,
+        intent: &TransactionIntent,
+        intent_hash_manager: &I,
+    ) -> Result<Vec<ExecutableSubintent>, TransactionValidationError> {
+        let mut execution_counts = vec![0usize; intent.subintents.len()];
         for inst in &intent.manifest.instructions {
+            if let Instruction::ExecuteSubintent { index } = inst {
+                *execution_counts
+                    .get_mut(*index as usize)
+                    .ok_or(TransactionValidationError::SubintentNotFound(*index))? += 1;
+            }
+        }
+
+        let mut subintents = Vec::new();
+        let mut subintent_hashes = HashSet::new();
+        for (index, (signed_subintent, execution_count)) in
+            intent.subintents.iter().zip(execution_counts).enumerate()
+        {
+            // the same subintent can't be run twice within a transaction either
+            if !subintent_hashes.insert(signed_subintent.subintent.hash()) {
+                return Err(TransactionValidationError::DuplicateSubintent(index as u32));
+            }
+            let subintent = self
+                .validate_subintent(signed_subintent, execution_count, intent_hash_manager)
+                .map_err(|e| {
+                    TransactionValidationError::SubintentValidationError(index as u32, e)
+                })?;
+            subintents.push(subintent);
+        }
+        Ok(subintents)
+    }
+
+    fn validate_subintent<I: IntentHashManager>(
+        &self,
+        signed_subintent: &SignedSubintent,
+        execution_count: usize,
+        intent_hash_manager: &I,
+    ) -> Result<ExecutableSubintent, SubintentValidationError> {
+        let subintent = &signed_subintent.subintent;
+        if execution_count != 1 {
+            return Err(SubintentValidationError::InvalidExecutionCount(
+                execution_count,
+            ));
+        }
+
+        // verify subintent hash, so that a signed subintent can't be replayed
+        if !intent_hash_manager.allows(&subintent.hash()) {
+            return Err(SubintentValidationError::HashRejected);
+        }
+
+        // verify network and epochs
+        if subintent.network_id != self.config.network_id {
+            return Err(SubintentValidationError::HeaderValidationError(
+                HeaderValidationError::InvalidNetwork,
+            ));
+        }
+        self.validate_epochs(
+            subintent.start_epoch_inclusive,
+            subintent.end_epoch_exclusive,
+        )
+        .map_err(SubintentValidationError::HeaderValidationError)?;
+
+        // semantic analysis
+        if subintent
+            .manifest
+            .instructions
+            .iter()
+            .any(|inst| matches!(inst, Instruction::ExecuteSubintent { .. }))
+        {
+            return Err(SubintentValidationError::NestedSubintent);
+        }
+        Self::validate_instructions(
+            &subintent.manifest.instructions,
+            SubintentValidationError::IdValidationError,
+            SubintentValidationError::CallDataValidationError,
+        )?;
+        Self::validate_blobs(&subintent.manifest)
+            .map_err(SubintentValidationError::BlobValidationError)?;
+
+        // verify signatures
+        let signers =
+            Self::validate_intent_signatures(&subintent.to_bytes(), &signed_subintent.signatures)
+                .map_err(SubintentValidationError::SignatureValidationError)?;
+
+        Ok(ExecutableSubintent {
+            hash: subintent.hash(),
+            instructions: subintent.manifest.instructions.clone(),
+            initial_proofs: AuthModule::signer_keys_to_non_fungibles(
+                &signers.into_iter().collect::<Vec<PublicKey>>(),
+            ),
+            blobs: subintent.manifest.blobs.clone(),
+        })
+    }
+
+    /// Checks the bucket and proof ids used by the instructions, which are scoped to a manifest.
+    fn validate_instructions<E>(
+        instructions: &[Instruction],
+        id_validation_error: fn(IdValidationError) -> E,
+        call_data_validation_error: fn(CallDataValidationError) -> E,
+    ) -> Result<(), E> {
+        let mut id_validator = IdValidator::new();
+        for inst in instructions {
             match inst.clone() {
                 Instruction::TakeFromWorktop { .. } => {
-                    id_validator
-                        .new_bucket()
-                        .map_err(TransactionValidationError::IdValidationError)?;
+                    id_validator.new_bucket().map_err(id_validation_error)?;
                 }
                 Instruction::TakeFromWorktopByAmount { .. } => {
-                    id_validator
-                        .new_bucket()
-                        .map_err(TransactionValidationError::IdValidationError)?;
+                    id_validator.new_bucket().map_err(id_validation_error)?;
                 }
                 Instruction::TakeFromWorktopByIds { .. } => {
-                    id_validator
-                        .new_bucket()
-                        .map_err(TransactionValidationError::IdValidationError)?;
+                    id_validator.new_bucket().map_err(id_validation_error)?;
                 }
                 Instruction::ReturnToWorktop { bucket_id } => {
                     id_validator
                         .drop_bucket(bucket_id)
-                        .map_err(TransactionValidationError::IdValidationError)?;
+                        .map_err(id_validation_error)?;
                 }
                 Instruction::AssertWorktopContains { .. } => {}
                 Instruction::AssertWorktopContainsByAmount { .. } => {}
@@ -147,64 +279,106 @@ impl NotarizedTransactionValidator {
                 Instruction::PopFromAuthZone => {
                     id_validator
                         .new_proof(ProofKind::AuthZoneProof)
-                        .map_err(TransactionValidationError::IdValidationError)?;
+                        .map_err(id_validation_error)?;
                 }
                 Instruction::PushToAuthZone { proof_id } => {
                     id_validator
                         .drop_proof(proof_id)
-                        .map_err(TransactionValidationError::IdValidationError)?;
+                        .map_err(id_validation_error)?;
                 }
                 Instruction::ClearAuthZone => {}
                 Instruction::CreateProofFromAuthZone { .. } => {
                     id_validator
                         .new_proof(ProofKind::AuthZoneProof)
-                        .map_err(TransactionValidationError::IdValidationError)?;
+                        .map_err(id_validation_error)?;
                 }
                 Instruction::CreateProofFromAuthZoneByAmount { .. } => {
                     id_validator
                         .new_proof(ProofKind::AuthZoneProof)
-                        .map_err(TransactionValidationError::IdValidationError)?;
+                        .map_err(id_validation_error)?;
                 }
                 Instruction::CreateProofFromAuthZoneByIds { .. } => {
                     id_validator
                         .new_proof(ProofKind::AuthZoneProof)
-                        .map_err(TransactionValidationError::IdValidationError)?;
+                        .map_err(id_validation_error)?;
                 }
                 Instruction::CreateProofFromBucket { bucket_id } => {
                     id_validator
                         .new_proof(ProofKind::BucketProof(bucket_id))
-                        .map_err(TransactionValidationError::IdValidationError)?;
+                        .map_err(id_validation_error)?;
                 }
                 Instruction::CloneProof { proof_id } => {
                     id_validator
                         .clone_proof(proof_id)
-                        .map_err(TransactionValidationError::IdValidationError)?;
+                        .map_err(id_validation_error)?;
                 }
                 Instruction::DropProof { proof_id } => {
                     id_validator
                         .drop_proof(proof_id)
-                        .map_err(TransactionValidationError::IdValidationError)?;
+                        .map_err(id_validation_error)?;
                 }
                 Instruction::DropAllProofs => {
                     id_validator
                         .drop_all_proofs()
-                        .map_err(TransactionValidationError::IdValidationError)?;
+                        .map_err(id_validation_error)?;
                 }
                 Instruction::CallFunction { args, .. } => {
                     // TODO: decode into Value
                     Self::validate_call_data(&args, &mut id_validator)
-                        .map_err(TransactionValidationError::CallDataValidationError)?;
+                        .map_err(call_data_validation_error)?;
                 }
                 Instruction::CallMethod { args, .. } => {
                     // TODO: decode into Value
                     Self::validate_call_data(&args, &mut id_validator)
-                        .map_err(TransactionValidationError::CallDataValidationError)?;
+                        .map_err(call_data_validation_error)?;
                 }
                 Instruction::PublishPackage { .. } => {}
+                Instruction::ExecuteSubintent { .. } => {}
+                Instruction::ReturnWorktopContents => {}
+                Instruction::CreateProofFromAccountByAmount { .. } => {}
+                Instruction::CreateProofFromAccountByIds { .. } => {}
             }
         }
 
-        return Ok(intent.manifest.instructions.clone());
+        Ok(())
+    }
+
+    /// Checks that the blobs attached to the manifest are exactly the ones referenced by its
+    /// instructions, each attached once.
+    fn validate_blobs(manifest: &TransactionManifest) -> Result<(), BlobValidationError> {
+        let mut referenced: HashSet<Hash> = HashSet::new();
+        for inst in &manifest.instructions {
+            match inst {
+                Instruction::PublishPackage { code, abi } => {
+                    referenced.insert(code.0);
+                    referenced.insert(abi.0);
+                }
+                Instruction::CallFunction { args, .. } | Instruction::CallMethod { args, .. } => {
+                    // Call data has been validated by `validate_instructions` already
+                    if let Ok(value) = ScryptoValue::from_slice(args) {
+                        referenced.extend(value.blob_hashes);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut blob_hashes = HashSet::new();
+        for blob in &manifest.blobs {
+            let blob_hash = hash(blob);
+            if !blob_hashes.insert(blob_hash) {
+                return Err(BlobValidationError::DuplicateBlob(blob_hash));
+            }
+            if !referenced.contains(&blob_hash) {
+                return Err(BlobValidationError::UnreferencedBlob(blob_hash));
+            }
+        }
+        // The smallest missing hash, so that the error doesn't depend on the iteration order
+        if let Some(blob_hash) = referenced.difference(&blob_hashes).min() {
+            return Err(BlobValidationError::BlobNotFound(*blob_hash));
+        }
+
+        Ok(())
     }
 
     pub fn validate_header(&self, intent: &TransactionIntent) -> Result<(), HeaderValidationError> {
@@ -221,17 +395,7 @@ impl NotarizedTransactionValidator {
         }
 
         // epoch
-        if header.end_epoch_exclusive <= header.start_epoch_inclusive {
-            return Err(HeaderValidationError::InvalidEpochRange);
-        }
-        if header.end_epoch_exclusive - header.start_epoch_inclusive > MAX_EPOCH_DURATION {
-            return Err(HeaderValidationError::EpochRangeTooLarge);
-        }
-        if self.config.current_epoch < header.start_epoch_inclusive
-            || self.config.current_epoch >= header.end_epoch_exclusive
-        {
-            return Err(HeaderValidationError::OutOfEpochRange);
-        }
+        self.validate_epochs(header.start_epoch_inclusive, header.end_epoch_exclusive)?;
 
         // cost unit limit and tip
         if header.cost_unit_limit > self.config.max_cost_unit_limit {
@@ -244,30 +408,34 @@ impl NotarizedTransactionValidator {
         Ok(())
     }
 
+    fn validate_epochs(
+        &self,
+        start_epoch_inclusive: u64,
+        end_epoch_exclusive: u64,
+    ) -> Result<(), HeaderValidationError> {
+        if end_epoch_exclusive <= start_epoch_inclusive {
+            return Err(HeaderValidationError::InvalidEpochRange);
+        }
+        if end_epoch_exclusive - start_epoch_inclusive > MAX_EPOCH_DURATION {
+            return Err(HeaderValidationError::EpochRangeTooLarge);
+        }
+        if self.config.current_epoch < start_epoch_inclusive
+            || self.config.current_epoch >= end_epoch_exclusive
+        {
+            return Err(HeaderValidationError::OutOfEpochRange);
+        }
+        Ok(())
+    }
+
     pub fn validate_signatures(
         &self,
         transaction: &NotarizedTransaction,
     ) -> Result<Vec<PublicKey>, SignatureValidationError> {
-        // TODO: split into static validation part and runtime validation part to support more signatures
-        if transaction.signed_intent.intent_signatures.len() > MAX_NUMBER_OF_INTENT_SIGNATURES {
-            return Err(SignatureValidationError::TooManySignatures);
-        }
-
         // verify intent signature
-        let mut signers = HashSet::new();
-        let intent_payload = transaction.signed_intent.intent.to_bytes();
-        for sig in &transaction.signed_intent.intent_signatures {
-            let public_key = recover(&intent_payload, sig)
-                .ok_or(SignatureValidationError::InvalidIntentSignature)?;
-
-            if !verify(&intent_payload, &public_key, &sig.signature()) {
-                return Err(SignatureValidationError::InvalidIntentSignature);
-            }
-
-            if !signers.insert(public_key) {
-                return Err(SignatureValidationError::DuplicateSigner);
-            }
-        }
+        let mut signers = Self::validate_intent_signatures(
+            &transaction.signed_intent.intent.to_bytes(),
+            &transaction.signed_intent.intent_signatures,
+        )?;
 
         if transaction.signed_intent.intent.header.notary_as_signatory {
             signers.insert(transaction.signed_intent.intent.header.notary_public_key);
@@ -286,6 +454,31 @@ impl NotarizedTransactionValidator {
         Ok(signers.into_iter().collect())
     }
 
+    fn validate_intent_signatures(
+        intent_payload: &[u8],
+        intent_signatures: &[SignatureWithPublicKey],
+    ) -> Result<HashSet<PublicKey>, SignatureValidationError> {
+        // TODO: split into static validation part and runtime validation part to support more signatures
+        if intent_signatures.len() > MAX_NUMBER_OF_INTENT_SIGNATURES {
+            return Err(SignatureValidationError::TooManySignatures);
+        }
+
+        let mut signers = HashSet::new();
+        for sig in intent_signatures {
+            let public_key = recover(intent_payload, sig)
+                .ok_or(SignatureValidationError::InvalidIntentSignature)?;
+
+            if !verify(intent_payload, &public_key, &sig.signature()) {
+                return Err(SignatureValidationError::InvalidIntentSignature);
+            }
+
+            if !signers.insert(public_key) {
+                return Err(SignatureValidationError::DuplicateSigner);
+            }
+        }
+        Ok(signers)
+    }
+
     pub fn validate_call_data(
         call_data: &[u8],
         id_validator: &mut IdValidator,
@@ -310,6 +503,7 @@ impl NotarizedTransactionValidator {
 #[cfg(test)]
 mod tests {
     use scrypto::core::NetworkDefinition;
+    use std::collections::HashMap;
 
     use super::*;
     use crate::{
@@ -387,6 +581,254 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_signers_become_initial_proofs() {
+        let mut intent_hash_manager: TestIntentHashManager = TestIntentHashManager::new();
+        let validator = NotarizedTransactionValidator::new(ValidationConfig {
+            network_id: NetworkDefinition::simulator().id,
+            current_epoch: 1,
+            max_cost_unit_limit: 10_000_000,
+            min_tip_percentage: 0,
+        });
+
+        let validated = validator
+            .validate(
+                create_transaction(1, 0, 100, 5, vec![1, 2, 3], 4),
+                &mut intent_hash_manager,
+            )
+            .unwrap();
+
+        let mut expected = AuthModule::signer_keys_to_non_fungibles(
+            &[1, 2, 3]
+                .into_iter()
+                .map(|n| {
+                    EcdsaSecp256k1PrivateKey::from_u64(n)
+                        .unwrap()
+                        .public_key()
+                        .into()
+                })
+                .collect::<Vec<PublicKey>>(),
+        );
+        let mut actual = validated.initial_proofs;
+        expected.sort_by_key(|a| a.to_vec());
+        actual.sort_by_key(|a| a.to_vec());
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_fee_payer_must_sign_and_is_kept_out_of_initial_proofs() {
+        let sk_user = EcdsaSecp256k1PrivateKey::from_u64(1).unwrap();
+        let sk_sponsor = EcdsaSecp256k1PrivateKey::from_u64(2).unwrap();
+        let sk_notary = EcdsaSecp256k1PrivateKey::from_u64(3).unwrap();
+        let builder = || {
+            TransactionBuilder::new()
+                .header(TransactionHeader {
+                    version: 1,
+                    network_id: NetworkDefinition::simulator().id,
+                    start_epoch_inclusive: 0,
+                    end_epoch_exclusive: 100,
+                    nonce: 5,
+                    notary_public_key: sk_notary.public_key().into(),
+                    notary_as_signatory: false,
+                    cost_unit_limit: 1_000_000,
+                    tip_percentage: 5,
+                    declared_substates: None,
+                    fee_payer: Some(sk_sponsor.public_key().into()),
+                })
+                .manifest(
+                    ManifestBuilder::new(&NetworkDefinition::simulator())
+                        .clear_auth_zone()
+                        .build(),
+                )
+                .sign(&sk_user)
+        };
+
+        assert_eq!(
+            validator().validate(
+                builder().notarize(&sk_notary).build(),
+                &mut TestIntentHashManager::new()
+            ),
+            Err(TransactionValidationError::SignatureValidationError(
+                SignatureValidationError::MissingFeePayerSignature
+            ))
+        );
+
+        let validated = validator()
+            .validate(
+                builder().sign(&sk_sponsor).notarize(&sk_notary).build(),
+                &mut TestIntentHashManager::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            validated.initial_proofs,
+            AuthModule::signer_keys_to_non_fungibles(&[sk_user.public_key().into()])
+        );
+        assert_eq!(
+            validated.fee_payer,
+            Some(AuthModule::signer_key_to_non_fungible(
+                &sk_sponsor.public_key().into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_subintent_signers_are_scoped_to_subintent() {
+        let sk_maker = EcdsaSecp256k1PrivateKey::from_u64(3).unwrap();
+        let signed_subintent = SignedSubintent::new(subintent(5)).sign(&sk_maker);
+
+        let validated = validator()
+            .validate(
+                create_transaction_with_subintents(
+                    ManifestBuilder::new(&NetworkDefinition::simulator())
+                        .execute_subintent(0)
+                        .build(),
+                    vec![signed_subintent],
+                ),
+                &TestIntentHashManager::new(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            AuthModule::signer_keys_to_non_fungibles(&[sk_maker.public_key().into()]),
+            validated.subintents[0].initial_proofs
+        );
+        assert!(!validated
+            .initial_proofs
+            .contains(&validated.subintents[0].initial_proofs[0]));
+        assert_eq!(vec![subintent(5).hash()], validated.subintent_hashes());
+    }
+
+    #[test]
+    fn test_invalid_subintents() {
+        let sk_maker = EcdsaSecp256k1PrivateKey::from_u64(3).unwrap();
+
+        assert_eq!(
+            Err(TransactionValidationError::SubintentValidationError(
+                0,
+                SubintentValidationError::InvalidExecutionCount(0)
+            )),
+            validator().validate(
+                create_transaction_with_subintents(
+                    ManifestBuilder::new(&NetworkDefinition::simulator())
+                        .clear_auth_zone()
+                        .build(),
+                    vec![SignedSubintent::new(subintent(5)).sign(&sk_maker)],
+                ),
+                &TestIntentHashManager::new(),
+            )
+        );
+        assert_eq!(
+            Err(TransactionValidationError::SubintentNotFound(1)),
+            validator().validate(
+                create_transaction_with_subintents(
+                    ManifestBuilder::new(&NetworkDefinition::simulator())
+                        .execute_subintent(0)
+                        .execute_subintent(1)
+                        .build(),
+                    vec![SignedSubintent::new(subintent(5)).sign(&sk_maker)],
+                ),
+                &TestIntentHashManager::new(),
+            )
+        );
+        assert_eq!(
+            Err(TransactionValidationError::DuplicateSubintent(1)),
+            validator().validate(
+                create_transaction_with_subintents(
+                    ManifestBuilder::new(&NetworkDefinition::simulator())
+                        .execute_subintent(0)
+                        .execute_subintent(1)
+                        .build(),
+                    vec![
+                        SignedSubintent::new(subintent(5)).sign(&sk_maker),
+                        SignedSubintent::new(subintent(5)).sign(&sk_maker),
+                    ],
+                ),
+                &TestIntentHashManager::new(),
+            )
+        );
+
+        // A signature over another subintent doesn't recover the maker key
+        let mut signed_subintent = SignedSubintent::new(subintent(5)).sign(&sk_maker);
+        signed_subintent.subintent.nonce = 6;
+        let validated = validator()
+            .validate(
+                create_transaction_with_subintents(
+                    ManifestBuilder::new(&NetworkDefinition::simulator())
+                        .execute_subintent(0)
+                        .build(),
+                    vec![signed_subintent],
+                ),
+                &TestIntentHashManager::new(),
+            )
+            .unwrap();
+        assert_ne!(
+            AuthModule::signer_keys_to_non_fungibles(&[sk_maker.public_key().into()]),
+            validated.subintents[0].initial_proofs
+        );
+    }
+
+    #[test]
+    fn test_blobs_must_be_attached() {
+        let mut manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+            .publish_package(vec![0u8; 1024], HashMap::new())
+            .build();
+        let abi_hash = match &manifest.instructions[0] {
+            Instruction::PublishPackage { abi, .. } => abi.0,
+            _ => panic!("Expected a PublishPackage instruction"),
+        };
+        let all_blobs = manifest.blobs.clone();
+        manifest.blobs.retain(|blob| hash(blob) != abi_hash);
+
+        assert_eq!(
+            validator().validate(
+                create_transaction_with_subintents(manifest.clone(), vec![]),
+                &mut TestIntentHashManager::new(),
+            ),
+            Err(TransactionValidationError::BlobValidationError(
+                BlobValidationError::BlobNotFound(abi_hash)
+            ))
+        );
+
+        manifest.blobs = all_blobs;
+        assert!(validator()
+            .validate(
+                create_transaction_with_subintents(manifest, vec![]),
+                &mut TestIntentHashManager::new(),
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_blobs_must_be_unique_and_referenced() {
+        let manifest = ManifestBuilder::new(&NetworkDefinition::simulator())
+            .publish_package(vec![0u8; 1024], HashMap::new())
+            .build();
+
+        let mut duplicated = manifest.clone();
+        duplicated.blobs.push(manifest.blobs[0].clone());
+        assert_eq!(
+            validator().validate(
+                create_transaction_with_subintents(duplicated, vec![]),
+                &mut TestIntentHashManager::new(),
+            ),
+            Err(TransactionValidationError::BlobValidationError(
+                BlobValidationError::DuplicateBlob(hash(&manifest.blobs[0]))
+            ))
+        );
+
+        let mut unreferenced = manifest;
+        unreferenced.blobs.push(vec![1u8; 16]);
+        assert_eq!(
+            validator().validate(
+                create_transaction_with_subintents(unreferenced, vec![]),
+                &mut TestIntentHashManager::new(),
+            ),
+            Err(TransactionValidationError::BlobValidationError(
+                BlobValidationError::UnreferencedBlob(hash(vec![1u8; 16]))
+            ))
+        );
+    }
+
     #[test]
     fn test_valid_preview() {
         let mut intent_hash_manager: TestIntentHashManager = TestIntentHashManager::new();
@@ -407,6 +849,7 @@ mod tests {
                 signer_public_keys: Vec::new(),
                 flags: PreviewFlags {
                     unlimited_loan: true,
+                    assume_all_signature_proofs: false,
                 },
             },
             &mut intent_hash_manager,
@@ -415,6 +858,58 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    fn validator() -> NotarizedTransactionValidator {
+        NotarizedTransactionValidator::new(ValidationConfig {
+            network_id: NetworkDefinition::simulator().id,
+            current_epoch: 1,
+            max_cost_unit_limit: 10_000_000,
+            min_tip_percentage: 0,
+        })
+    }
+
+    fn subintent(nonce: u64) -> Subintent {
+        Subintent {
+            network_id: NetworkDefinition::simulator().id,
+            start_epoch_inclusive: 0,
+            end_epoch_exclusive: 100,
+            nonce,
+            manifest: ManifestBuilder::new(&NetworkDefinition::simulator())
+                .clear_auth_zone()
+                .build(),
+        }
+    }
+
+    fn create_transaction_with_subintents(
+        manifest: TransactionManifest,
+        subintents: Vec<SignedSubintent>,
+    ) -> NotarizedTransaction {
+        let sk_notary = EcdsaSecp256k1PrivateKey::from_u64(2).unwrap();
+
+        let mut builder = TransactionBuilder::new()
+            .header(TransactionHeader {
+                version: 1,
+                network_id: NetworkDefinition::simulator().id,
+                start_epoch_inclusive: 0,
+                end_epoch_exclusive: 100,
+                nonce: 5,
+                notary_public_key: sk_notary.public_key().into(),
+                notary_as_signatory: false,
+                cost_unit_limit: 1_000_000,
+                tip_percentage: 5,
+                declared_substates: None,
+                fee_payer: None,
+            })
+            .manifest(manifest);
+        for subintent in subintents {
+            builder = builder.subintent(subintent);
+        }
+        builder = builder
+            .sign(&EcdsaSecp256k1PrivateKey::from_u64(1).unwrap())
+            .notarize(&sk_notary);
+
+        builder.build()
+    }
+
     fn create_transaction(
         version: u8,
         start_epoch: u64,
@@ -436,6 +931,8 @@ mod tests {
                 notary_as_signatory: false,
                 cost_unit_limit: 1_000_000,
                 tip_percentage: 5,
+                declared_substates: None,
+                fee_payer: None,
             })
             .manifest(
                 ManifestBuilder::new(&NetworkDefinition::simulator())